@@ -1,11 +1,12 @@
 use clap::{Parser, Subcommand, ValueEnum};
+use seedkit_core::sample::progress::PROGRESS_FILE_NAME;
 
 #[derive(Parser, Debug)]
 #[command(
     name = "seedkit",
     about = "Generate realistic, constraint-safe seed data for any database",
     version,
-    after_help = "Examples:\n  seedkit generate --db postgres://localhost/myapp --rows 1000 --output seed.sql\n  seedkit generate --rows 100              # auto-detect DB from .env\n  seedkit introspect --db postgres://localhost/myapp\n  seedkit preview --db postgres://localhost/myapp\n  seedkit check --db postgres://localhost/myapp\n  seedkit graph --db postgres://localhost/myapp --format mermaid"
+    after_help = "Examples:\n  seedkit generate --db postgres://localhost/myapp --rows 1000 --output seed.sql\n  seedkit generate --rows 100              # auto-detect DB from .env\n  seedkit init --db postgres://localhost/myapp\n  seedkit introspect --db postgres://localhost/myapp\n  seedkit preview --db postgres://localhost/myapp\n  seedkit classify --db postgres://localhost/myapp --format json\n  seedkit check --db postgres://localhost/myapp\n  seedkit graph --db postgres://localhost/myapp --format mermaid\n  seedkit regen --table products --as updates --output products.sql\n  seedkit config validate"
 )]
 pub struct Cli {
     #[command(subcommand)]
@@ -21,12 +22,18 @@ pub enum Command {
     /// Generate seed data for a database
     Generate(GenerateArgs),
 
+    /// Write a commented starter seedkit.toml for a database
+    Init(InitArgs),
+
     /// Introspect a database schema and display analysis
     Introspect(IntrospectArgs),
 
     /// Preview sample generated data without inserting
     Preview(PreviewArgs),
 
+    /// Show how each column is classified and which strategy generates it
+    Classify(ClassifyArgs),
+
     /// Check for schema drift against a lock file
     Check(CheckArgs),
 
@@ -35,6 +42,15 @@ pub enum Command {
 
     /// Sample production distributions for realistic data generation
     Sample(SampleArgs),
+
+    /// Regenerate a single table's data for an already-seeded database
+    Regen(RegenArgs),
+
+    /// Remove seeded data: delete it children-first, or truncate the tables
+    Wipe(WipeArgs),
+
+    /// Validate seedkit.toml or print its JSON Schema
+    Config(ConfigArgs),
 }
 
 #[derive(Parser, Debug)]
@@ -44,6 +60,10 @@ pub struct GenerateArgs {
     #[arg(long, env = "DATABASE_URL")]
     pub db: Option<String>,
 
+    /// Apply the [profiles.<NAME>] overrides of seedkit.toml
+    #[arg(long = "env", value_name = "NAME", env = "SEEDKIT_ENV")]
+    pub config_env: Option<String>,
+
     /// Number of rows to generate per table
     #[arg(long, default_value = "100")]
     pub rows: usize,
@@ -80,14 +100,34 @@ pub struct GenerateArgs {
     #[arg(long)]
     pub ai: bool,
 
-    /// LLM model to use with --ai
+    /// LLM model to use with --ai and for columns configured with `llm`
     #[arg(long)]
     pub model: Option<String>,
 
+    /// Locale for person names: en, de, fr, ja, el, or a weighted mix such
+    /// as en=0.6,de=0.3,ja=0.1 (emails stay ASCII)
+    #[arg(long)]
+    pub locale: Option<String>,
+
     /// Regenerate from a lock file
     #[arg(long)]
     pub from_lock: bool,
 
+    /// Add this many rows to every table of an already-seeded database,
+    /// continuing its IDs and referencing its existing rows
+    #[arg(long, value_name = "ROWS", conflicts_with_all = ["from_lock", "rows", "table_rows", "remap"])]
+    pub append: Option<usize>,
+
+    /// Lock file profile: use seedkit.lock.<PROFILE> instead of
+    /// seedkit.lock, for environments with their own seed and row counts
+    #[arg(long, env = "SEEDKIT_PROFILE")]
+    pub profile: Option<String>,
+
+    /// Generate the dataset shape defined under [scenario.<NAME>] in
+    /// seedkit.toml
+    #[arg(long, value_name = "NAME", env = "SEEDKIT_SCENARIO")]
+    pub scenario: Option<String>,
+
     /// Force regeneration even if schema has changed
     #[arg(long)]
     pub force: bool,
@@ -99,6 +139,53 @@ pub struct GenerateArgs {
     /// Path to distribution profiles for production-like generation
     #[arg(long)]
     pub subset: Option<String>,
+
+    /// Enforce column pairs found unique during sampling (requires --subset)
+    #[arg(long, requires = "subset")]
+    pub enforce_sampled_uniqueness: bool,
+
+    /// Write the output file even if its estimated size exceeds free disk space
+    #[arg(long)]
+    pub skip_space_check: bool,
+
+    /// Merge existing dumps (.sql or .json) with IDs renumbered and foreign
+    /// keys rewritten, instead of generating (repeatable)
+    #[arg(long, value_name = "DUMP")]
+    pub remap: Vec<String>,
+
+    /// Also write a data dictionary describing every generated column
+    /// (markdown, or CSV for a .csv path)
+    #[arg(long, value_name = "PATH")]
+    pub dictionary: Option<String>,
+
+    /// Record the inserted rows as a run with this ID, which
+    /// `seedkit wipe --run` removes (direct output only)
+    #[arg(long, value_name = "ID")]
+    pub run_id: Option<String>,
+
+    /// Insert into the database inside a transaction that is rolled back,
+    /// reporting rows, SQL size and constraint violations per table
+    #[arg(long, conflicts_with = "remap")]
+    pub dry_run: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct InitArgs {
+    /// Database connection URL
+    #[arg(long, env = "DATABASE_URL")]
+    pub db: Option<String>,
+
+    /// Row count written for every table
+    #[arg(long, default_value = "100")]
+    pub rows: usize,
+
+    /// Path of the config file to write
+    #[arg(short, long, default_value = "seedkit.toml")]
+    pub output: String,
+
+    /// Overwrite an existing config file
+    #[arg(long)]
+    pub force: bool,
 }
 
 #[derive(Parser, Debug)]
@@ -129,6 +216,34 @@ pub struct PreviewArgs {
     /// Schema name
     #[arg(long)]
     pub schema: Option<String>,
+
+    /// Output format: terminal tables, or Markdown tables to paste into a
+    /// pull request
+    #[arg(long, default_value = "table")]
+    pub format: PreviewFormat,
+}
+
+#[derive(Parser, Debug)]
+pub struct ClassifyArgs {
+    /// Database connection URL
+    #[arg(long, env = "DATABASE_URL")]
+    pub db: Option<String>,
+
+    /// Apply the [profiles.<NAME>] overrides of seedkit.toml
+    #[arg(long = "env", value_name = "NAME", env = "SEEDKIT_ENV")]
+    pub config_env: Option<String>,
+
+    /// Only show these tables
+    #[arg(long, value_delimiter = ',')]
+    pub include: Vec<String>,
+
+    /// Leave out these tables
+    #[arg(long, value_delimiter = ',')]
+    pub exclude: Vec<String>,
+
+    /// Output format
+    #[arg(long, default_value = "table")]
+    pub format: ClassifyFormat,
 }
 
 #[derive(Parser, Debug)]
@@ -137,6 +252,10 @@ pub struct CheckArgs {
     #[arg(long, env = "DATABASE_URL")]
     pub db: Option<String>,
 
+    /// Apply the [profiles.<NAME>] overrides of seedkit.toml
+    #[arg(long = "env", value_name = "NAME", env = "SEEDKIT_ENV")]
+    pub config_env: Option<String>,
+
     /// Schema name
     #[arg(long)]
     pub schema: Option<String>,
@@ -144,6 +263,46 @@ pub struct CheckArgs {
     /// Output format for drift report
     #[arg(long, default_value = "text")]
     pub format: CheckFormat,
+
+    /// Also compare every version of seedkit.lock committed since this git
+    /// revision with the live schema, as a timeline
+    #[arg(long, value_name = "REV")]
+    pub since: Option<String>,
+
+    /// Which drift fails the check: only breaking changes (removed
+    /// columns, type changes, new required columns or constraints), or any
+    #[arg(long, default_value = "any", conflicts_with = "pii")]
+    pub fail_on: FailOn,
+
+    /// Keep checking for drift, printing the report once and then the drift
+    /// that appears or is resolved (stop with Ctrl-C)
+    #[arg(long, conflicts_with_all = ["pii", "since"])]
+    pub watch: bool,
+
+    /// Accept the drift: update the schema snapshot of the lock file, and
+    /// of every other profile's, to the live schema, keeping their seeds,
+    /// row counts and overrides
+    #[arg(long, conflicts_with_all = ["pii", "since", "watch"])]
+    pub update: bool,
+
+    /// Lock file profile: use seedkit.lock.<PROFILE> instead of
+    /// seedkit.lock, for environments with their own seed and row counts
+    #[arg(long, env = "SEEDKIT_PROFILE")]
+    pub profile: Option<String>,
+
+    /// Seconds between checks with --watch
+    #[arg(long, default_value_t = 5, requires = "watch")]
+    pub interval: u64,
+
+    /// Report the columns holding PII and whether sampling masks them,
+    /// instead of checking for drift (exit code 1 if any isn't masked)
+    #[arg(long, conflicts_with = "since")]
+    pub pii: bool,
+
+    /// Profiles file to check what sampling kept of PII columns (default:
+    /// seedkit.distributions.json, when present)
+    #[arg(long, requires = "pii")]
+    pub profiles: Option<String>,
 }
 
 #[derive(Parser, Debug)]
@@ -152,21 +311,52 @@ pub struct GraphArgs {
     #[arg(long, env = "DATABASE_URL")]
     pub db: Option<String>,
 
+    /// Apply the [profiles.<NAME>] overrides of seedkit.toml
+    #[arg(long = "env", value_name = "NAME", env = "SEEDKIT_ENV")]
+    pub config_env: Option<String>,
+
     /// Schema name
     #[arg(long)]
     pub schema: Option<String>,
 
-    /// Output format for the dependency graph
+    /// Output format: a Mermaid ER diagram, a Mermaid flowchart of the
+    /// dependency graph, Graphviz DOT with insertion order and
+    /// cycle-broken edges marked, or an interactive HTML page
     #[arg(long, default_value = "mermaid")]
     pub format: GraphFormat,
+
+    /// Output file path (defaults to stdout, or seedkit-graph.html for html)
+    #[arg(short, long)]
+    pub output: Option<String>,
+
+    /// Only show this table, the tables it references and the tables
+    /// referencing it
+    #[arg(long, value_name = "TABLE")]
+    pub focus: Option<String>,
+
+    /// How many foreign keys away from --focus to go, up and down
+    #[arg(long, default_value_t = 1, requires = "focus")]
+    pub depth: usize,
+
+    /// Print the insertion levels instead of a diagram: tables in a level
+    /// don't reference each other and can be loaded in parallel
+    #[arg(long, conflicts_with = "format")]
+    pub levels: bool,
 }
 
 #[derive(Parser, Debug)]
 pub struct SampleArgs {
+    #[command(subcommand)]
+    pub command: Option<SampleCommand>,
+
     /// Database connection URL (read-only replica recommended)
     #[arg(long, env = "DATABASE_URL")]
     pub db: Option<String>,
 
+    /// Apply the [profiles.<NAME>] overrides of seedkit.toml
+    #[arg(long = "env", value_name = "NAME", env = "SEEDKIT_ENV")]
+    pub config_env: Option<String>,
+
     /// Schema name to introspect
     #[arg(long)]
     pub schema: Option<String>,
@@ -186,6 +376,256 @@ pub struct SampleArgs {
     /// Minimum row count for a table to be sampled
     #[arg(long, default_value = "10")]
     pub min_rows: u64,
+
+    /// Skip probing column pairs for implicit composite uniqueness
+    #[arg(long)]
+    pub no_unique_pairs: bool,
+
+    /// Buckets of the histograms numeric columns are profiled as (0 keeps
+    /// only min/max/mean/stddev)
+    #[arg(long, default_value = "20")]
+    pub histogram_buckets: usize,
+
+    /// Quantiles captured per numeric column, so generated values keep
+    /// production's tails
+    #[arg(long, value_delimiter = ',', default_values_t = [0.5, 0.9, 0.99])]
+    pub quantiles: Vec<f64>,
+
+    /// Skip capturing quantiles of numeric columns
+    #[arg(long)]
+    pub no_quantiles: bool,
+
+    /// Skip counting distinct values per column (a full scan of each
+    /// table)
+    #[arg(long)]
+    pub no_distinct_counts: bool,
+
+    /// Skip measuring correlations between numeric columns
+    #[arg(long)]
+    pub no_correlations: bool,
+
+    /// Skip profiling when timestamp and date columns' values fall
+    #[arg(long)]
+    pub no_temporal_patterns: bool,
+
+    /// Skip counting children per parent through foreign keys
+    #[arg(long)]
+    pub no_fan_outs: bool,
+
+    /// Train word n-gram models on free-text columns (descriptions,
+    /// comments) so generated text reads like the domain
+    #[arg(long)]
+    pub text_models: bool,
+
+    /// Replace PII values with keyed pseudonyms instead of dropping them;
+    /// a value maps to the same pseudonym in every table and run, so
+    /// joins on masked columns keep working
+    #[arg(long, requires = "mask_key")]
+    pub pseudonymize: bool,
+
+    /// With --pseudonymize, keep each value's format (digits stay digits,
+    /// same length, email domain structure) instead of generating a new one
+    #[arg(long, requires = "pseudonymize")]
+    pub preserve_format: bool,
+
+    /// Add differential-privacy noise to every statistic with this
+    /// privacy budget per table (smaller is more private); text models,
+    /// JSON shapes and unique pairs are dropped
+    #[arg(long)]
+    pub epsilon: Option<f64>,
+
+    /// Warn about quasi-identifier categories (zip code, birth date,
+    /// gender...) held by fewer than this many rows
+    #[arg(long, default_value = "5")]
+    pub k_anonymity: u64,
+
+    /// Drop the quasi-identifier categories the k-anonymity audit finds
+    /// instead of only warning
+    #[arg(long)]
+    pub drop_rare: bool,
+
+    /// Secret key for --pseudonymize
+    #[arg(
+        long,
+        env = "SEEDKIT_MASK_KEY",
+        hide_env_values = true,
+        value_parser = clap::builder::NonEmptyStringValueParser::new()
+    )]
+    pub mask_key: Option<String>,
+
+    /// Attempts per query when a replica cancels it for conflicting with
+    /// recovery; the column is skipped after the last one
+    #[arg(long, default_value = "5", value_parser = clap::value_parser!(u32).range(1..))]
+    pub max_attempts: u32,
+
+    /// Save each table's profile to this file as soon as it is sampled, so
+    /// an interrupted run resumes where it stopped (default file:
+    /// seedkit.sample-progress.json). It holds profiles before PII masking
+    /// and is deleted once every table is sampled
+    #[arg(long, num_args = 0..=1, default_missing_value = PROGRESS_FILE_NAME)]
+    pub progress_file: Option<String>,
+
+    /// Re-sample every table instead of reusing saved progress
+    #[arg(long)]
+    pub no_resume: bool,
+
+    /// Relative change in a table's row count above which its saved
+    /// profile is re-sampled
+    #[arg(long, default_value = "0.1")]
+    pub resample_threshold: f64,
+}
+
+#[derive(Parser, Debug)]
+pub struct ConfigArgs {
+    #[command(subcommand)]
+    pub command: ConfigCommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ConfigCommand {
+    /// Check seedkit.toml for unknown keys, invalid values, and overrides
+    /// of tables or columns the database doesn't have
+    Validate(ConfigValidateArgs),
+
+    /// Print the JSON Schema of seedkit.toml, for editor completion
+    Schema,
+}
+
+#[derive(Parser, Debug)]
+pub struct ConfigValidateArgs {
+    /// Config file to check (default: seedkit.toml)
+    pub path: Option<String>,
+
+    /// Database connection URL
+    #[arg(long, env = "DATABASE_URL")]
+    pub db: Option<String>,
+
+    /// Apply the [profiles.<NAME>] overrides of seedkit.toml
+    #[arg(long = "env", value_name = "NAME", env = "SEEDKIT_ENV")]
+    pub config_env: Option<String>,
+
+    /// Skip the checks against the database schema
+    #[arg(long)]
+    pub offline: bool,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum SampleCommand {
+    /// Scan a saved profiles file for categorical values that look like
+    /// PII (emails, phone numbers, names) before it is committed
+    Audit(SampleAuditArgs),
+}
+
+#[derive(Parser, Debug)]
+pub struct SampleAuditArgs {
+    /// Profiles file to scan (default: seedkit.distributions.json)
+    pub path: Option<String>,
+
+    /// Remove the flagged values from the file
+    #[arg(long, conflicts_with = "hash")]
+    pub strip: bool,
+
+    /// Replace the flagged values in the file with keyed hashes
+    #[arg(long, requires = "mask_key")]
+    pub hash: bool,
+
+    /// Secret key for --hash
+    #[arg(
+        long,
+        env = "SEEDKIT_MASK_KEY",
+        hide_env_values = true,
+        value_parser = clap::builder::NonEmptyStringValueParser::new()
+    )]
+    pub mask_key: Option<String>,
+}
+
+#[derive(Parser, Debug)]
+pub struct RegenArgs {
+    /// Database connection URL (postgres://, mysql://, sqlite://)
+    /// Falls back to DATABASE_URL env var or .env file
+    #[arg(long, env = "DATABASE_URL")]
+    pub db: Option<String>,
+
+    /// Apply the [profiles.<NAME>] overrides of seedkit.toml
+    #[arg(long = "env", value_name = "NAME", env = "SEEDKIT_ENV")]
+    pub config_env: Option<String>,
+
+    /// Table to regenerate
+    #[arg(long)]
+    pub table: String,
+
+    /// Statement kind to emit for the regenerated rows
+    #[arg(long = "as", default_value = "updates")]
+    pub emit: RegenEmit,
+
+    /// Output file path (defaults to stdout)
+    #[arg(short, long)]
+    pub output: Option<String>,
+
+    /// Random seed (defaults to the lock file's seed, so FK pools match the seeded data)
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    /// Lock file profile: use seedkit.lock.<PROFILE> instead of
+    /// seedkit.lock, for environments with their own seed and row counts
+    #[arg(long, env = "SEEDKIT_PROFILE")]
+    pub profile: Option<String>,
+
+    /// The [scenario.<NAME>] of seedkit.toml the data was generated with
+    /// (defaults to the one the lock file records)
+    #[arg(long, value_name = "NAME", env = "SEEDKIT_SCENARIO")]
+    pub scenario: Option<String>,
+}
+
+#[derive(Parser, Debug)]
+pub struct WipeArgs {
+    /// Database connection URL (postgres://, mysql://, sqlite://)
+    /// Falls back to DATABASE_URL env var or .env file
+    #[arg(long, env = "DATABASE_URL")]
+    pub db: Option<String>,
+
+    /// Apply the [profiles.<NAME>] overrides of seedkit.toml
+    #[arg(long = "env", value_name = "NAME", env = "SEEDKIT_ENV")]
+    pub config_env: Option<String>,
+
+    /// Only wipe these tables (defaults to the tables seeded per the lock
+    /// file or seedkit.toml)
+    #[arg(long, value_delimiter = ',')]
+    pub include: Vec<String>,
+
+    /// Leave these tables alone
+    #[arg(long, value_delimiter = ',')]
+    pub exclude: Vec<String>,
+
+    /// Empty the tables with TRUNCATE and restart their IDs, instead of
+    /// deleting only the seeded rows
+    #[arg(long)]
+    pub truncate: bool,
+
+    /// Print the SQL without running it
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Run without asking for confirmation
+    #[arg(long, short)]
+    pub yes: bool,
+
+    /// Lock file profile: use seedkit.lock.<PROFILE> instead of
+    /// seedkit.lock
+    #[arg(long, env = "SEEDKIT_PROFILE")]
+    pub profile: Option<String>,
+
+    /// Delete only the rows of this run recorded by `seedkit generate`
+    #[arg(long, value_name = "ID", conflicts_with = "truncate")]
+    pub run: Option<String>,
+}
+
+#[derive(Debug, Clone, ValueEnum)]
+pub enum RegenEmit {
+    /// UPDATE statements keyed by primary key
+    Updates,
+    /// INSERT statements
+    Inserts,
 }
 
 #[derive(Debug, Clone, ValueEnum)]
@@ -201,16 +641,42 @@ pub enum IntrospectFormat {
     Json,
 }
 
+#[derive(Debug, Clone, ValueEnum)]
+pub enum PreviewFormat {
+    Table,
+    Markdown,
+}
+
+#[derive(Debug, Clone, ValueEnum)]
+pub enum ClassifyFormat {
+    Table,
+    Json,
+}
+
 #[derive(Debug, Clone, ValueEnum)]
 pub enum CheckFormat {
     Text,
     Json,
+    /// SARIF 2.1.0, for code-scanning tools and review bots (drift only)
+    Sarif,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum FailOn {
+    Breaking,
+    Any,
 }
 
 #[derive(Debug, Clone, ValueEnum)]
 pub enum GraphFormat {
+    /// Mermaid `erDiagram`: tables, key columns and relationships
     Mermaid,
+    /// Mermaid flowchart of the dependency graph
+    MermaidFlowchart,
+    /// Graphviz DOT of the dependency graph
     Dot,
+    /// Self-contained HTML page with a zoomable force-directed graph
+    Html,
 }
 
 impl GenerateArgs {
@@ -1,91 +1,320 @@
 use std::path::Path;
 use std::process;
+use std::time::Duration;
 
 use anyhow::{bail, Context, Result};
 
 use seedkit_core::check;
+use seedkit_core::check::history;
+use seedkit_core::check::pii::pii_report;
+use seedkit_core::check::Severity;
+use seedkit_core::classify::rules::classify_schema_with_rules;
 use seedkit_core::lock;
+use seedkit_core::sample;
 use seedkit_core::schema::introspect::{database_type_from_url, SchemaIntrospector};
-use seedkit_core::schema::types::DatabaseType;
+use seedkit_core::schema::types::{DatabaseSchema, DatabaseType};
 
-use crate::args::{CheckArgs, CheckFormat};
+use crate::args::{CheckArgs, CheckFormat, FailOn};
 
 /// Run schema drift detection against seedkit.lock.
 ///
 /// Exit codes:
-///   0 — no drift detected
-///   1 — drift detected (or error)
+///   0 — no drift detected, or only benign drift with `--fail-on breaking`
+///   1 — breaking drift detected (or error)
+///   2 — only benign drift detected, with `--fail-on any`
 pub async fn run(args: &CheckArgs) -> Result<()> {
-    let lock_path = Path::new(lock::LOCK_FILE_NAME);
-    if !lock_path.exists() {
+    let lock_name = lock::lock_file_name(args.profile.as_deref())?;
+    let lock_path = Path::new(&lock_name);
+    if args.pii && matches!(args.format, CheckFormat::Sarif) {
+        bail!("--format sarif is only supported for drift reports, not --pii");
+    }
+    if !args.pii && !lock_path.exists() {
         bail!(
             "No {} found. Run `seedkit generate` first to create a lock file.",
-            lock::LOCK_FILE_NAME,
+            lock_name,
         );
     }
 
-    let lock_file = lock::read_lock_file(lock_path)?;
-
     // Resolve DB and introspect
-    let db_url = resolve_db_url(args.db.as_deref())?;
+    let db_url = resolve_db_url(args.db.as_deref(), args.config_env.as_deref())?;
     let db_type = database_type_from_url(&db_url)?;
 
-    let schema = match db_type {
-        DatabaseType::PostgreSQL => {
-            let pool = sqlx::postgres::PgPoolOptions::new()
-                .max_connections(5)
-                .connect(&db_url)
-                .await
-                .context("Failed to connect to PostgreSQL")?;
-            let introspector = seedkit_core::schema::postgres::PostgresIntrospector::new(pool);
-            introspector.introspect().await?
+    if args.watch {
+        return watch(args, lock_path, &db_url, db_type).await;
+    }
+
+    let schema = Introspector::connect(&db_url, db_type)
+        .await?
+        .introspect()
+        .await?;
+
+    if args.pii {
+        return check_pii(args, &schema);
+    }
+
+    let lock_file = lock::read_lock_file(lock_path)?;
+    let report = check::check_drift(&lock_file.schema_snapshot, &lock_file.schema_hash, &schema);
+
+    if args.update {
+        if report.has_drift {
+            println!("{}", report.summary());
         }
-        DatabaseType::MySQL => {
-            let pool = sqlx::mysql::MySqlPoolOptions::new()
-                .max_connections(5)
-                .connect(&db_url)
-                .await
-                .context("Failed to connect to MySQL")?;
-            let db_name = db_url
-                .rsplit('/')
-                .next()
-                .map(|s| s.split('?').next().unwrap_or(s).to_string())
-                .unwrap_or("mysql".to_string());
-            let introspector = seedkit_core::schema::mysql::MySqlIntrospector::new(pool, db_name);
-            introspector.introspect().await?
+        // Profiles share the schema, so every lock file takes the snapshot
+        let schema_hash = check::compute_schema_hash(&schema);
+        let dir = match lock_path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        let mut updated = Vec::new();
+        for path in lock::lock_files(dir)? {
+            let mut lock_file = lock::read_lock_file(&path)?;
+            if lock_file.schema_hash == schema_hash {
+                continue;
+            }
+            lock_file.update_schema(schema.clone());
+            lock::write_lock_file(&lock_file, &path)?;
+            updated.push(
+                path.file_name()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+                    .into_owned(),
+            );
         }
-        DatabaseType::SQLite => {
-            let pool = sqlx::sqlite::SqlitePoolOptions::new()
-                .max_connections(1)
-                .connect(&db_url)
-                .await
-                .context("Failed to connect to SQLite")?;
-            let introspector = seedkit_core::schema::sqlite::SqliteIntrospector::new(pool);
-            introspector.introspect().await?
+        if updated.is_empty() {
+            println!("{} is up to date.", lock_name);
+        } else {
+            println!(
+                "Updated the schema snapshot in {} (seed, row counts and overrides kept).",
+                updated.join(", ")
+            );
         }
-    };
+        return Ok(());
+    }
 
-    let report = check::check_drift(&lock_file.schema_snapshot, &lock_file.schema_hash, &schema);
+    // Timeline of committed lock files, oldest first
+    let timeline = match args.since {
+        Some(ref since) => {
+            let revisions = history::lock_history(lock_path, since)?;
+            Some(history::drift_timeline(&revisions, &schema))
+        }
+        None => None,
+    };
 
     match args.format {
         CheckFormat::Json => {
-            let json = serde_json::to_string_pretty(&report)
-                .context("Failed to serialize drift report")?;
+            let json = match timeline {
+                Some(ref timeline) => serde_json::to_string_pretty(&serde_json::json!({
+                    "timeline": timeline,
+                    "current": report,
+                })),
+                None => serde_json::to_string_pretty(&report),
+            }
+            .context("Failed to serialize drift report")?;
+            println!("{}", json);
+        }
+        CheckFormat::Sarif => {
+            let sarif = check::sarif::drift_sarif(&report, &lock_name);
+            let json =
+                serde_json::to_string_pretty(&sarif).context("Failed to serialize SARIF log")?;
             println!("{}", json);
         }
         CheckFormat::Text => {
+            if let Some(ref timeline) = timeline {
+                println!("{}\n", history::timeline_summary(timeline));
+                println!("Working tree {}:", lock_name);
+            }
             println!("{}", report.summary());
         }
     }
 
-    if report.has_drift {
+    match (report.severity(), args.fail_on) {
+        (Some(Severity::Breaking), _) => process::exit(1),
+        (Some(Severity::Benign), FailOn::Any) => process::exit(2),
+        _ => {}
+    }
+
+    Ok(())
+}
+
+/// Re-check drift every `--interval` seconds until Ctrl-C, printing the
+/// report when it first comes in and then the drift items that appeared or
+/// were resolved since the last check. The lock file is re-read every time,
+/// so regenerating it is picked up too; errors (a database restarting
+/// mid-migration) are printed and checking goes on with the same pool.
+async fn watch(
+    args: &CheckArgs,
+    lock_path: &Path,
+    db_url: &str,
+    db_type: DatabaseType,
+) -> Result<()> {
+    if matches!(args.format, CheckFormat::Sarif) {
+        bail!("--format sarif is not supported with --watch");
+    }
+    let interval = Duration::from_secs(args.interval.max(1));
+    eprintln!(
+        "Watching for schema drift every {}s (Ctrl-C to stop)",
+        interval.as_secs()
+    );
+
+    let introspector = Introspector::connect(db_url, db_type).await?;
+    let mut last: Option<std::result::Result<check::DriftReport, String>> = None;
+    loop {
+        let checked = async {
+            let lock_file = lock::read_lock_file(lock_path)?;
+            let schema = introspector.introspect().await?;
+            Ok::<_, anyhow::Error>(check::check_drift(
+                &lock_file.schema_snapshot,
+                &lock_file.schema_hash,
+                &schema,
+            ))
+        }
+        .await
+        .map_err(|e| format!("Check failed: {:#}", e));
+
+        // The first report in full, then only what changed
+        let text = match (&checked, &last) {
+            (Ok(report), Some(Ok(previous))) => report.changes_since(previous),
+            (Ok(report), _) => Some(report.summary()),
+            (Err(message), Some(Err(previous))) if message == previous => None,
+            (Err(message), _) => Some(message.clone()),
+        };
+        if let Some(text) = text {
+            let now = chrono::Local::now();
+            match args.format {
+                // One JSON object per line
+                CheckFormat::Json => {
+                    let result = match checked {
+                        Ok(ref report) => serde_json::to_value(report)?,
+                        Err(ref message) => serde_json::json!({ "error": message }),
+                    };
+                    println!(
+                        "{}",
+                        serde_json::json!({ "checked_at": now.to_rfc3339(), "result": result })
+                    )
+                }
+                _ => println!("[{}] {}", now.format("%H:%M:%S"), text),
+            }
+        }
+        last = Some(checked);
+
+        tokio::select! {
+            _ = tokio::time::sleep(interval) => {}
+            _ = tokio::signal::ctrl_c() => return Ok(()),
+        }
+    }
+}
+
+/// An introspector of the live schema, connected once so `--watch` reuses
+/// its pool.
+enum Introspector {
+    Postgres(seedkit_core::schema::postgres::PostgresIntrospector),
+    MySql(seedkit_core::schema::mysql::MySqlIntrospector),
+    Sqlite(seedkit_core::schema::sqlite::SqliteIntrospector),
+}
+
+impl Introspector {
+    async fn connect(db_url: &str, db_type: DatabaseType) -> Result<Self> {
+        Ok(match db_type {
+            DatabaseType::PostgreSQL => {
+                let pool = sqlx::postgres::PgPoolOptions::new()
+                    .max_connections(5)
+                    .connect(db_url)
+                    .await
+                    .context("Failed to connect to PostgreSQL")?;
+                Self::Postgres(seedkit_core::schema::postgres::PostgresIntrospector::new(
+                    pool,
+                ))
+            }
+            DatabaseType::MySQL => {
+                let pool = sqlx::mysql::MySqlPoolOptions::new()
+                    .max_connections(5)
+                    .connect(db_url)
+                    .await
+                    .context("Failed to connect to MySQL")?;
+                let db_name = db_url
+                    .rsplit('/')
+                    .next()
+                    .map(|s| s.split('?').next().unwrap_or(s).to_string())
+                    .unwrap_or("mysql".to_string());
+                Self::MySql(seedkit_core::schema::mysql::MySqlIntrospector::new(
+                    pool, db_name,
+                ))
+            }
+            DatabaseType::SQLite => {
+                let pool = sqlx::sqlite::SqlitePoolOptions::new()
+                    .max_connections(1)
+                    .connect(db_url)
+                    .await
+                    .context("Failed to connect to SQLite")?;
+                Self::Sqlite(seedkit_core::schema::sqlite::SqliteIntrospector::new(pool))
+            }
+        })
+    }
+
+    /// Introspect the live schema.
+    async fn introspect(&self) -> Result<DatabaseSchema> {
+        Ok(match self {
+            Self::Postgres(introspector) => introspector.introspect().await?,
+            Self::MySql(introspector) => introspector.introspect().await?,
+            Self::Sqlite(introspector) => introspector.introspect().await?,
+        })
+    }
+}
+
+/// Report the schema's PII columns, classified as `seedkit generate` would
+/// without `--ai`.
+///
+/// Exit codes:
+///   0 — every PII column is masked when sampled
+///   1 — some aren't (or error)
+fn check_pii(args: &CheckArgs, schema: &DatabaseSchema) -> Result<()> {
+    let config =
+        seedkit_core::config::read_config_with_env(Path::new("."), args.config_env.as_deref())?;
+    let custom_rules = match config {
+        Some(ref cfg) => cfg.classification_rules()?,
+        None => Vec::new(),
+    };
+    let mut classifications = classify_schema_with_rules(schema, &custom_rules);
+    if let Some(ref cfg) = config {
+        cfg.apply_type_overrides(&mut classifications);
+    }
+
+    let profiles_path = match args.profiles {
+        Some(ref path) => Some(path.as_str()),
+        None => Path::new(sample::PROFILES_FILE_NAME)
+            .exists()
+            .then_some(sample::PROFILES_FILE_NAME),
+    };
+    let profiles = match profiles_path {
+        Some(path) => Some((path, sample::load_profiles(Path::new(path))?)),
+        None => None,
+    };
+    let report = pii_report(
+        schema,
+        &classifications,
+        profiles
+            .as_ref()
+            .map(|(path, profiles)| (*path, profiles.as_slice())),
+    );
+
+    match args.format {
+        CheckFormat::Json => {
+            let json =
+                serde_json::to_string_pretty(&report).context("Failed to serialize PII report")?;
+            println!("{}", json);
+        }
+        CheckFormat::Text => println!("{}", report.summary()),
+        CheckFormat::Sarif => unreachable!("rejected before introspection"),
+    }
+
+    if report.unmasked().next().is_some() {
         process::exit(1);
     }
 
     Ok(())
 }
 
-fn resolve_db_url(explicit: Option<&str>) -> Result<String> {
+fn resolve_db_url(explicit: Option<&str>, env: Option<&str>) -> Result<String> {
     if let Some(url) = explicit {
         return Ok(url.to_string());
     }
@@ -98,7 +327,9 @@ fn resolve_db_url(explicit: Option<&str>) -> Result<String> {
         }
     }
     // Try seedkit.toml
-    if let Some(config) = seedkit_core::config::read_config(std::path::Path::new("."))? {
+    if let Some(config) =
+        seedkit_core::config::read_config_with_env(std::path::Path::new("."), env)?
+    {
         if let Some(url) = config.database.url {
             return Ok(url);
         }
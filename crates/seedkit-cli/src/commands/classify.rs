@@ -0,0 +1,140 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use anyhow::Result;
+use comfy_table::{Cell, Table as ComfyTable};
+use serde::Serialize;
+
+use seedkit_core::classify::rules::classify_schema_with_rules;
+use seedkit_core::generate::plan::{filter_insertion_order, GenerationPlan};
+use seedkit_core::graph::cycle::break_cycles;
+use seedkit_core::graph::dag::DependencyGraph;
+use seedkit_core::graph::topo::topological_sort;
+use seedkit_core::output::dictionary::describe_strategy;
+use seedkit_core::schema::introspect::database_type_from_url;
+
+use super::generate::{introspect_schema, resolve_db_url};
+use crate::args::{ClassifyArgs, ClassifyFormat};
+
+/// One column's classification and generation strategy.
+#[derive(Debug, Serialize)]
+struct ClassifiedColumn {
+    table: String,
+    column: String,
+    data_type: String,
+    semantic_type: String,
+    strategy: String,
+}
+
+/// Print how `seedkit generate` would classify and generate every column.
+///
+/// Uses the same rules, `seedkit.toml` overrides and plan as a run without
+/// `--ai`. Nothing is generated and no LLM is called; columns with an
+/// `llm` prompt also show the generator used without an API key.
+pub async fn run(args: &ClassifyArgs) -> Result<()> {
+    let config =
+        seedkit_core::config::read_config_with_env(Path::new("."), args.config_env.as_deref())?;
+
+    let db_url = resolve_db_url(args.db.as_deref(), config.as_ref())?;
+    let db_type = database_type_from_url(&db_url)?;
+    let schema = introspect_schema(&db_url, db_type).await?;
+
+    let polymorphic = config
+        .as_ref()
+        .map(|c| c.polymorphic_associations())
+        .unwrap_or_default();
+    let mut dep_graph = DependencyGraph::from_schema(&schema);
+    dep_graph.add_polymorphic_edges(&schema, &polymorphic);
+    let cycle_break_hints: Vec<String> = config
+        .as_ref()
+        .map(|c| c.graph.break_cycle_at.clone())
+        .unwrap_or_default();
+    let deferred = break_cycles(&mut dep_graph, &cycle_break_hints)?;
+    let insertion_order = topological_sort(&dep_graph)?;
+    let filtered_order = filter_insertion_order(
+        &insertion_order.tables,
+        &schema,
+        &args.include,
+        &args.exclude,
+    );
+
+    let custom_rules = match config {
+        Some(ref cfg) => cfg.classification_rules()?,
+        None => Vec::new(),
+    };
+    let mut classifications = classify_schema_with_rules(&schema, &custom_rules);
+    if let Some(ref cfg) = config {
+        cfg.apply_type_overrides(&mut classifications);
+    }
+    let column_overrides = config
+        .as_ref()
+        .map(|c| c.column_overrides())
+        .unwrap_or_default();
+
+    // Strategies don't depend on the seed or row counts
+    let mut plan = GenerationPlan::build(
+        &schema,
+        &classifications,
+        &filtered_order,
+        deferred,
+        100,
+        &BTreeMap::new(),
+        0,
+        None,
+        &column_overrides,
+        None,
+    );
+    let lookup_overrides = config
+        .as_ref()
+        .map(|c| c.lookup_overrides())
+        .unwrap_or_default();
+    plan.apply_lookup_tables(&schema, &lookup_overrides, &BTreeMap::new());
+    plan.set_polymorphic_associations(&schema, &polymorphic);
+    if let Some(ref config) = config {
+        plan.set_time_series(&schema, &config.time_series());
+        plan.set_funnels(&schema, &config.funnels());
+    }
+
+    let mut columns = Vec::new();
+    for table_plan in &plan.table_plans {
+        let table = schema.tables.get(&table_plan.table_name);
+        for col_plan in &table_plan.column_plans {
+            let key = format!("{}.{}", table_plan.table_name, col_plan.column_name);
+            let mut strategy = describe_strategy(&col_plan.strategy);
+            if column_overrides.get(&key).is_some_and(|c| c.llm.is_some()) {
+                strategy = format!("LLM-written text (without a key: {})", strategy);
+            }
+            columns.push(ClassifiedColumn {
+                table: table_plan.table_name.clone(),
+                column: col_plan.column_name.clone(),
+                data_type: table
+                    .and_then(|t| t.columns.get(&col_plan.column_name))
+                    .map(|c| c.raw_type.clone())
+                    .unwrap_or_default(),
+                semantic_type: col_plan.semantic_type.to_string(),
+                strategy,
+            });
+        }
+    }
+
+    match args.format {
+        ClassifyFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&columns)?);
+        }
+        ClassifyFormat::Table => {
+            let mut t = ComfyTable::new();
+            t.set_header(vec!["Column", "Type", "Semantic", "Strategy"]);
+            for column in &columns {
+                t.add_row(vec![
+                    Cell::new(format!("{}.{}", column.table, column.column)),
+                    Cell::new(&column.data_type),
+                    Cell::new(&column.semantic_type),
+                    Cell::new(&column.strategy),
+                ]);
+            }
+            println!("{}", t);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+
+use seedkit_core::config::{parse_config, CONFIG_FILE_NAME};
+use seedkit_core::config_schema::{config_json_schema, lint_config};
+use seedkit_core::schema::introspect::database_type_from_url;
+
+use super::generate::{introspect_schema, resolve_db_url};
+use crate::args::ConfigValidateArgs;
+
+/// Check seedkit.toml: syntax, unknown keys, invalid values, and (with a
+/// database) overrides of tables or columns it doesn't have.
+pub async fn validate(args: &ConfigValidateArgs) -> Result<()> {
+    let path = Path::new(args.path.as_deref().unwrap_or(CONFIG_FILE_NAME));
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let dir = std::fs::canonicalize(dir).unwrap_or_else(|_| dir.to_path_buf());
+
+    let (config, mut problems) = lint_config(&text, Some(&dir));
+    // The database checks use the config as commands see it: profile
+    // applied and ${VAR} references filled in
+    let config = config.and_then(
+        |_| match parse_config(&text, &dir, args.config_env.as_deref()) {
+            Ok(mut config) => {
+                config.config_dir = Some(dir.clone());
+                Some(config)
+            }
+            Err(e) => {
+                problems.push(e);
+                None
+            }
+        },
+    );
+    if let Some(ref config) = config.filter(|_| !args.offline) {
+        match resolve_db_url(args.db.as_deref(), Some(config)) {
+            Ok(db_url) => {
+                let db_type = database_type_from_url(&db_url)?;
+                let schema = introspect_schema(&db_url, db_type).await?;
+                problems.extend(config.validate_against_schema(&schema));
+            }
+            Err(_) => {
+                eprintln!("No database URL: tables and columns weren't checked against the schema.")
+            }
+        }
+    }
+
+    if problems.is_empty() {
+        eprintln!("✓ {} is valid", path.display());
+        return Ok(());
+    }
+    for problem in &problems {
+        eprintln!("  ✗ {}", problem);
+    }
+    bail!("{} problems found in {}", problems.len(), path.display())
+}
+
+/// Print the JSON Schema of seedkit.toml.
+pub fn schema() -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(&config_json_schema())?);
+    Ok(())
+}
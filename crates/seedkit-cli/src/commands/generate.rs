@@ -1,31 +1,50 @@
 use std::collections::BTreeMap;
 use std::fs::File;
-use std::io::BufWriter;
+use std::io::{BufWriter, Write};
 use std::path::Path;
 
 use anyhow::{bail, Context, Result};
 use indicatif::{ProgressBar, ProgressStyle};
 
-use seedkit_core::check::compute_schema_hash;
-use seedkit_core::classify::rules::classify_schema;
+use seedkit_core::check::{compute_schema_hash, compute_table_hash};
+use seedkit_core::classify::rules::classify_schema_with_rules;
 use seedkit_core::classify::semantic::SemanticType;
+use seedkit_core::generate::append::{self, ExistingKeys};
 use seedkit_core::generate::engine;
+use seedkit_core::generate::locale::LocaleMix;
 use seedkit_core::generate::plan::{filter_insertion_order, GenerationPlan};
+use seedkit_core::generate::remap;
+use seedkit_core::generate::sequence::detect_sequence_offsets;
 use seedkit_core::graph::cycle::break_cycles;
 use seedkit_core::graph::dag::DependencyGraph;
-use seedkit_core::graph::topo::topological_sort;
+use seedkit_core::graph::topo::{topological_sort, DeferredEdge};
 use seedkit_core::llm;
 use seedkit_core::lock;
-use seedkit_core::lock::types::{LockConfig, LockFile};
+use seedkit_core::lock::types::{LockConfig, LockFile, StableTableLock};
 use seedkit_core::output;
+use seedkit_core::output::estimate;
+use seedkit_core::output::runs;
 use seedkit_core::schema::introspect::{database_type_from_url, SchemaIntrospector};
 use seedkit_core::schema::types::{DatabaseSchema, DatabaseType};
 
 use crate::args::{GenerateArgs, OutputFormat};
+use crate::progress::TableProgress;
 
 pub async fn run(args: &GenerateArgs) -> Result<()> {
-    // Load optional seedkit.toml config
-    let config = seedkit_core::config::read_config(Path::new("."))?;
+    // Load optional seedkit.toml config; --scenario shapes it once the lock
+    // file, which may record one, is read
+    let mut config =
+        seedkit_core::config::read_config_with_env(Path::new("."), args.config_env.as_deref())?;
+    if let Some(output) = args
+        .output
+        .as_deref()
+        .filter(|o| args.dry_run && *o != "direct")
+    {
+        bail!(
+            "--dry-run rolls back a direct insert and can't write to {}",
+            output
+        );
+    }
 
     let db_url = resolve_db_url(args.db.as_deref(), config.as_ref())?;
     let db_type = database_type_from_url(&db_url)?;
@@ -41,36 +60,7 @@ pub async fn run(args: &GenerateArgs) -> Result<()> {
     pb.set_message("Introspecting schema...");
     pb.enable_steady_tick(std::time::Duration::from_millis(100));
 
-    let schema = match db_type {
-        DatabaseType::PostgreSQL => {
-            let pool = sqlx::postgres::PgPoolOptions::new()
-                .max_connections(5)
-                .connect(&db_url)
-                .await
-                .context("Failed to connect to PostgreSQL")?;
-            let introspector = seedkit_core::schema::postgres::PostgresIntrospector::new(pool);
-            introspector.introspect().await?
-        }
-        DatabaseType::MySQL => {
-            let pool = sqlx::mysql::MySqlPoolOptions::new()
-                .max_connections(5)
-                .connect(&db_url)
-                .await
-                .context("Failed to connect to MySQL")?;
-            let db_name = extract_mysql_db_name(&db_url).unwrap_or("mysql".to_string());
-            let introspector = seedkit_core::schema::mysql::MySqlIntrospector::new(pool, db_name);
-            introspector.introspect().await?
-        }
-        DatabaseType::SQLite => {
-            let pool = sqlx::sqlite::SqlitePoolOptions::new()
-                .max_connections(1)
-                .connect(&db_url)
-                .await
-                .context("Failed to connect to SQLite")?;
-            let introspector = seedkit_core::schema::sqlite::SqliteIntrospector::new(pool);
-            introspector.introspect().await?
-        }
-    };
+    let schema = introspect_schema(&db_url, db_type).await?;
 
     pb.finish_with_message(format!(
         "Introspecting schema... ✓ {} tables, {} foreign keys",
@@ -79,12 +69,13 @@ pub async fn run(args: &GenerateArgs) -> Result<()> {
     ));
 
     // If --from-lock, load the lock file and validate schema hash
-    let lock_path = Path::new(lock::LOCK_FILE_NAME);
+    let lock_name = lock::lock_file_name(args.profile.as_deref())?;
+    let lock_path = Path::new(&lock_name);
     let restored_lock = if args.from_lock {
         if !lock_path.exists() {
             bail!(
                 "No {} found in current directory. Run `seedkit generate` first to create one.",
-                lock::LOCK_FILE_NAME
+                lock_name
             );
         }
         let lf = lock::read_lock_file(lock_path)?;
@@ -97,16 +88,36 @@ pub async fn run(args: &GenerateArgs) -> Result<()> {
                  Current:   {}\n\
                  \n\
                  Run with --force to regenerate, or delete {} and run fresh.",
-                lock::LOCK_FILE_NAME,
+                lock_name,
                 &lf.schema_hash[..16],
                 &current_hash[..16],
-                lock::LOCK_FILE_NAME,
+                lock_name,
             );
         }
         Some(lf)
     } else {
         None
     };
+    let scenario = apply_scenario(
+        &mut config,
+        args.scenario.as_deref(),
+        restored_lock.as_ref(),
+        &lock_name,
+    )?;
+
+    // Locale: lock file when restoring, then --locale, then seedkit.toml
+    let locale_name = match restored_lock {
+        Some(ref lf) => lf.config.locale.clone(),
+        None => args.locale.clone(),
+    };
+    let locale: LocaleMix = match locale_name {
+        Some(name) => name.parse().map_err(anyhow::Error::msg)?,
+        None if restored_lock.is_some() => LocaleMix::default(),
+        None => config
+            .as_ref()
+            .and_then(|c| c.locale_mix())
+            .unwrap_or_default(),
+    };
 
     // Phase 2: Analyze dependencies
     let pb2 = ProgressBar::new_spinner();
@@ -119,7 +130,12 @@ pub async fn run(args: &GenerateArgs) -> Result<()> {
     pb2.set_message("Analyzing dependencies...");
     pb2.enable_steady_tick(std::time::Duration::from_millis(100));
 
+    let polymorphic = config
+        .as_ref()
+        .map(|c| c.polymorphic_associations())
+        .unwrap_or_default();
     let mut dep_graph = DependencyGraph::from_schema(&schema);
+    dep_graph.add_polymorphic_edges(&schema, &polymorphic);
     let cycle_break_hints: Vec<String> = config
         .as_ref()
         .map(|c| c.graph.break_cycle_at.clone())
@@ -132,12 +148,31 @@ pub async fn run(args: &GenerateArgs) -> Result<()> {
         deferred.len()
     ));
 
+    if !args.remap.is_empty() {
+        return remap_dumps(args, &schema, &db_url, &insertion_order.tables, &deferred).await;
+    }
+
     // Classify columns (rule-based)
-    let rule_classifications = classify_schema(&schema);
+    let custom_rules = match config {
+        Some(ref cfg) => cfg.classification_rules()?,
+        None => Vec::new(),
+    };
+    let mut rule_classifications = classify_schema_with_rules(&schema, &custom_rules);
+    if let Some(ref cfg) = config {
+        cfg.apply_type_overrides(&mut rule_classifications);
+    }
 
     // Optionally enhance with LLM classification (--ai flag)
-    let (classifications, ai_cache) = if args.ai {
-        enhance_with_llm(&rule_classifications, &schema, args.model.as_deref()).await?
+    let mut llm_budget = llm::budget::LlmBudget::from_config(config.as_ref().map(|c| &c.llm));
+    let (mut classifications, ai_cache) = if args.ai {
+        enhance_with_llm(
+            &rule_classifications,
+            &schema,
+            config.as_ref().map(|c| &c.llm),
+            args.model.as_deref(),
+            &mut llm_budget,
+        )
+        .await?
     } else if let Some(ref lf) = restored_lock {
         // Restore cached AI classifications from lock file
         restore_ai_from_lock(&rule_classifications, lf)
@@ -152,8 +187,9 @@ pub async fn run(args: &GenerateArgs) -> Result<()> {
         (lf.seed, lf.config.default_row_count, overrides, bt)
     } else {
         let seed = args.seed.unwrap_or_else(|| {
-            // Check seedkit.toml for a fixed seed
-            if let Some(ref cfg) = config {
+            // Check seedkit.toml for a fixed seed. Appended rows skip it:
+            // replaying the seeded run would repeat its values.
+            if let Some(cfg) = config.as_ref().filter(|_| args.append.is_none()) {
                 if let Some(s) = cfg.generate.seed {
                     return s;
                 }
@@ -175,8 +211,11 @@ pub async fn run(args: &GenerateArgs) -> Result<()> {
             overrides.insert(k, v);
         }
 
-        // Row count: CLI --rows takes priority, then seedkit.toml, then default (100)
-        let rows = if args.rows != 100 {
+        // Row count: --append, then CLI --rows, then seedkit.toml, then default (100)
+        let rows = if let Some(rows) = args.append {
+            overrides.clear();
+            rows
+        } else if args.rows != 100 {
             args.rows
         } else {
             config
@@ -188,22 +227,30 @@ pub async fn run(args: &GenerateArgs) -> Result<()> {
         (seed, rows, overrides, None)
     };
 
-    // Apply --include / --exclude table filtering.
-    // When restoring from lock, use the lock's include/exclude lists.
+    // Apply --include / --exclude table filtering, falling back to
+    // seedkit.toml. When restoring from lock, use the lock's lists.
     let (include, exclude) = if let Some(ref lf) = restored_lock {
         (
             lf.config.include_tables.clone(),
             lf.config.exclude_tables.clone(),
         )
     } else {
-        (args.include.clone(), args.exclude.clone())
+        let from_config = |list: Option<&Vec<String>>, flag: &Vec<String>| match list {
+            Some(list) if flag.is_empty() => list.clone(),
+            _ => flag.clone(),
+        };
+        let generate = config.as_ref().map(|c| &c.generate);
+        (
+            from_config(generate.and_then(|g| g.include.as_ref()), &args.include),
+            from_config(generate.and_then(|g| g.exclude.as_ref()), &args.exclude),
+        )
     };
     let filtered_order =
         filter_insertion_order(&insertion_order.tables, &schema, &include, &exclude);
 
     let column_overrides = config
         .as_ref()
-        .map(|c| c.columns.clone())
+        .map(|c| c.column_overrides())
         .unwrap_or_default();
 
     // Load distribution profiles if --subset is specified
@@ -221,8 +268,33 @@ pub async fn run(args: &GenerateArgs) -> Result<()> {
         None
     };
 
-    let plan = GenerationPlan::build(
-        &schema,
+    // Columns the rules couldn't classify may be recognized by their sampled values
+    if let Some(ref profiles) = dist_profiles {
+        let refined = seedkit_core::classify::content::refine_with_profiles(
+            &schema,
+            &mut classifications,
+            profiles,
+        );
+        if refined > 0 {
+            eprintln!("Classified {} columns from their sampled values", refined);
+        }
+    }
+
+    // Optionally enforce sampled unique pairs. Applied to a copy so the
+    // lock file keeps the introspected schema (and its hash) untouched.
+    let mut gen_schema = None;
+    if args.enforce_sampled_uniqueness {
+        if let Some(ref profiles) = dist_profiles {
+            let mut s = schema.clone();
+            let added = seedkit_core::sample::apply_unique_pair_hints(&mut s, profiles);
+            eprintln!("Enforcing {} sampled unique column pairs", added);
+            gen_schema = Some(s);
+        }
+    }
+    let gen_schema = gen_schema.as_ref().unwrap_or(&schema);
+
+    let mut plan = GenerationPlan::build(
+        gen_schema,
         &classifications,
         &filtered_order,
         deferred,
@@ -234,52 +306,175 @@ pub async fn run(args: &GenerateArgs) -> Result<()> {
         dist_profiles.as_deref(),
     );
 
+    // Lookup tables (countries, currencies, ...) come from curated datasets.
+    // When restoring, the lock's tables are replayed instead of detected.
+    let lookup_overrides = match restored_lock
+        .as_ref()
+        .and_then(|lf| lf.config.lookup_overrides(gen_schema))
+    {
+        Some(overrides) => overrides,
+        None => config
+            .as_ref()
+            .map(|c| c.lookup_overrides())
+            .unwrap_or_default(),
+    };
+    let mut lookup_tables = BTreeMap::new();
+    for (table, dataset) in
+        plan.apply_lookup_tables(gen_schema, &lookup_overrides, &table_row_overrides)
+    {
+        eprintln!(
+            "Filling lookup table '{}' from the {} dataset",
+            table, dataset
+        );
+        lookup_tables.insert(table, dataset.to_string());
+    }
+
+    // Stable tables replay the rows recorded in the existing lock file.
+    // Without --from-lock the lock is read only for those rows.
+    let existing_lock = match restored_lock {
+        None if lock_path.exists() => lock::read_lock_file(lock_path).ok(),
+        _ => None,
+    };
+    let recorded = restored_lock
+        .as_ref()
+        .or(existing_lock.as_ref())
+        .map(|lf| &lf.stable_tables);
+    let mut stable_tables = config
+        .as_ref()
+        .map(|c| c.stable_tables())
+        .unwrap_or_default();
+    if let Some(ref lf) = restored_lock {
+        stable_tables.extend(lf.stable_tables.keys().cloned());
+        plan.table_streams = lf.config.table_streams;
+    }
+    apply_stable_tables(&mut plan, &schema, &stable_tables, recorded);
+    plan.set_locale(locale.clone());
+    plan.set_polymorphic_associations(&schema, &polymorphic);
+    if let Some(ref config) = config {
+        plan.set_language_mixes(
+            &config.language_mixes(),
+            config.default_language_mix().as_ref(),
+        );
+        plan.set_row_scripts(&config.row_scripts());
+        plan.set_time_series(&schema, &config.time_series());
+        plan.set_funnels(&schema, &config.funnels());
+        plan.set_segments(&schema, &config.segments());
+        fill_llm_content(
+            &mut plan,
+            &schema,
+            config,
+            args.model.as_deref(),
+            &mut llm_budget,
+        )
+        .await?;
+    }
+
+    if args.append.is_some() {
+        for table in append::skip_fixed_tables(&mut plan) {
+            eprintln!("Not appending to '{}': its rows are fixed", table);
+        }
+    }
+
+    // Synthesized IDs continue after the rows already in the database, so
+    // foreign keys line up with the IDs it will assign. Restored runs reuse
+    // the offsets recorded in the lock.
+    let sequence_offsets = match restored_lock {
+        Some(ref lf) => lf.sequence_offsets.clone(),
+        None => detect_sequence_offsets(&db_url, &schema, &filtered_order)
+            .await
+            .unwrap_or_else(|e| {
+                eprintln!("Warning: could not detect sequence offsets: {}", e);
+                BTreeMap::new()
+            }),
+    };
+    if !sequence_offsets.is_empty() {
+        eprintln!(
+            "Continuing IDs after existing rows in {} tables",
+            sequence_offsets.len()
+        );
+    }
+    plan.set_sequence_offsets(&sequence_offsets);
+
+    // Fail before the long generation phase, not when the disk fills up.
+    if let Some(path) = args.output.as_deref().filter(|p| *p != "direct") {
+        if !args.skip_space_check {
+            check_disk_space(&plan, gen_schema, &schema, Path::new(path), args)?;
+        }
+    }
+
     // Phase 3: Generate data
     let total_rows: usize = plan.table_plans.iter().map(|t| t.row_count).sum();
-    let pb3 = ProgressBar::new(total_rows as u64);
-    pb3.set_style(
-        ProgressStyle::default_bar()
-            .template(
-                "{spinner:.cyan} [3/4] Generating data... {bar:40.cyan/dim} {pos}/{len} ({eta})",
-            )
-            .unwrap()
-            .progress_chars("█▓░"),
-    );
+    let table_rows: Vec<(String, usize)> = plan
+        .table_plans
+        .iter()
+        .map(|t| (t.table_name.clone(), t.row_count))
+        .collect();
+    let pb3 = TableProgress::new("3/4", "Generating data...", &table_rows);
+
+    // Appended rows reference and steer clear of the rows already there
+    let existing = if args.append.is_some() {
+        append::load_existing_keys(&db_url, &schema)
+            .await
+            .context("Failed to read existing rows for --append")?
+    } else {
+        ExistingKeys::new()
+    };
 
-    let data = engine::execute_plan(
+    let mut data = engine::execute_plan_with_existing(
         &plan,
-        &schema,
-        Some(&|_table, current, _total| {
-            pb3.set_position(current as u64);
-        }),
+        gen_schema,
+        &existing,
+        Some(&|table, current, _total| pb3.update(table, current)),
     )?;
 
-    pb3.finish_with_message(format!("Generating data... ✓ ({} rows)", total_rows));
+    pb3.finish(format!("Generating data... ✓ ({} rows)", total_rows));
 
     // Phase 4: Output
-    let is_direct = args.output.as_deref() == Some("direct");
+    let is_direct = args.dry_run || args.output.as_deref() == Some("direct");
+
+    // Tracked runs: stamp the marker column, and record the run after a
+    // direct insert
+    let run_config = config.as_ref().map(|c| &c.generate);
+    let run_id = args.run_id.clone().or_else(|| {
+        run_config
+            .filter(|g| g.track_runs == Some(true) || g.run_marker.is_some())
+            .map(|_| runs::default_run_id())
+    });
+    let run_marker = run_id
+        .as_ref()
+        .and(run_config.and_then(|g| g.run_marker.as_deref()));
+    if let (Some(ref run_id), Some(marker)) = (&run_id, run_marker) {
+        if runs::stamp_run_marker(&mut data, &schema, marker, run_id).is_empty() {
+            eprintln!("  No table has the run marker column '{}'", marker);
+        }
+    }
+    if args.run_id.is_some() && !is_direct {
+        eprintln!("  --run-id: only direct inserts are recorded as runs");
+    }
+
+    if args.dry_run {
+        // Nothing is kept, so no lock file or run record either
+        return dry_run(&data, &schema, &db_url).await;
+    }
 
     if is_direct {
         // Direct database insertion
-        let pb4 = ProgressBar::new(total_rows as u64);
-        pb4.set_style(
-            ProgressStyle::default_bar()
-                .template("{spinner:.cyan} [4/4] Inserting into database... {bar:40.cyan/dim} {pos}/{len} ({eta})")
-                .unwrap()
-                .progress_chars("█▓░"),
-        );
+        let table_rows: Vec<(String, usize)> = data
+            .tables
+            .iter()
+            .map(|(table, rows)| (table.clone(), rows.len()))
+            .collect();
+        let pb4 = TableProgress::new("4/4", "Inserting into database...", &table_rows);
 
         output::direct::insert_direct(
             &data,
             &schema,
             &db_url,
-            Some(&|current, _total| {
-                pb4.set_position(current as u64);
-            }),
+            Some(&|table, current, _total| pb4.update(table, current)),
         )
         .await?;
 
-        pb4.finish_with_message(format!(
+        pb4.finish(format!(
             "Inserting into database... ✓ ({} rows)",
             total_rows
         ));
@@ -289,6 +484,15 @@ pub async fn run(args: &GenerateArgs) -> Result<()> {
             data.tables.len(),
             schema.database_type,
         );
+
+        if let Some(run_id) = run_id {
+            let run = runs::Run::new(run_id, runs::run_tables(&plan, &schema, &data, run_marker));
+            runs::record_run(&db_url, schema.database_type, &run).await?;
+            eprintln!(
+                "✓ Recorded run {}; remove its rows with `seedkit wipe --run {}`",
+                run.run_id, run.run_id
+            );
+        }
     } else {
         let pb4 = ProgressBar::new_spinner();
         pb4.set_style(
@@ -304,22 +508,10 @@ pub async fn run(args: &GenerateArgs) -> Result<()> {
                 let file = File::create(path)
                     .with_context(|| format!("Failed to create output file: {}", path))?;
                 let mut writer = BufWriter::new(file);
-
-                match args.output_format() {
-                    OutputFormat::Sql => {
-                        if args.copy && matches!(schema.database_type, DatabaseType::PostgreSQL) {
-                            output::sql::write_postgres_copy(&mut writer, &data, &schema)?;
-                        } else {
-                            output::sql::write_sql(&mut writer, &data, &schema)?;
-                        }
-                    }
-                    OutputFormat::Json => {
-                        output::json::write_json(&mut writer, &data)?;
-                    }
-                    OutputFormat::Csv => {
-                        output::csv::write_csv(&mut writer, &data)?;
-                    }
-                }
+                write_output(&mut writer, args, &data, &schema)?;
+                writer
+                    .flush()
+                    .with_context(|| format!("Failed to write output file: {}", path))?;
 
                 pb4.finish_with_message(format!("Writing to {}... ✓", path));
                 eprintln!(
@@ -334,26 +526,27 @@ pub async fn run(args: &GenerateArgs) -> Result<()> {
                 pb4.set_message("Writing to stdout...");
                 let stdout = std::io::stdout();
                 let mut writer = BufWriter::new(stdout.lock());
-
-                match args.output_format() {
-                    OutputFormat::Sql => {
-                        output::sql::write_sql(&mut writer, &data, &schema)?;
-                    }
-                    OutputFormat::Json => {
-                        output::json::write_json(&mut writer, &data)?;
-                    }
-                    OutputFormat::Csv => {
-                        output::csv::write_csv(&mut writer, &data)?;
-                    }
-                }
+                write_output(&mut writer, args, &data, &schema)?;
+                writer.flush().context("Failed to write to stdout")?;
 
                 pb4.finish_with_message("Writing to stdout... ✓");
             }
         }
     }
 
+    if let Some(ref path) = args.dictionary {
+        write_dictionary(Path::new(path), &plan, gen_schema, &data)?;
+        eprintln!("Data dictionary written to {}", path);
+    }
+
+    // Appended rows can't be replayed on their own; the lock file keeps
+    // describing the seeded run.
+    if args.append.is_some() {
+        return Ok(());
+    }
+
     // Write lock file (always, so teammates can reproduce)
-    let lock_file = LockFile::new(
+    let mut lock_file = LockFile::new(
         compute_schema_hash(&schema),
         seed,
         plan.base_time,
@@ -361,8 +554,8 @@ pub async fn run(args: &GenerateArgs) -> Result<()> {
             default_row_count: row_count,
             table_row_overrides,
             ai_enabled: args.ai,
-            include_tables: args.include.clone(),
-            exclude_tables: args.exclude.clone(),
+            include_tables: include,
+            exclude_tables: exclude,
             ai_classifications: ai_cache.clone(),
             column_overrides: if column_overrides.is_empty() {
                 None
@@ -383,17 +576,423 @@ pub async fn run(args: &GenerateArgs) -> Result<()> {
                         .collect(),
                 )
             },
+            locale: (locale != LocaleMix::default()).then(|| locale.to_string()),
+            table_streams: plan.table_streams,
+            lookup_tables: Some(lookup_tables),
+            scenario,
         },
         schema,
     );
+    lock_file.sequence_offsets = sequence_offsets;
+    lock_file.stable_tables = plan
+        .table_plans
+        .iter()
+        .filter(|t| t.stable)
+        .filter_map(|t| {
+            let table = lock_file.schema_snapshot.tables.get(&t.table_name)?;
+            let rows = data.tables.get(&t.table_name)?;
+            Some((
+                t.table_name.clone(),
+                StableTableLock {
+                    table_hash: compute_table_hash(table),
+                    rows: rows.clone(),
+                },
+            ))
+        })
+        .collect();
     lock::write_lock_file(&lock_file, lock_path)?;
-    eprintln!("Lock file written to {}", lock::LOCK_FILE_NAME);
+    eprintln!("Lock file written to {}", lock_name);
+
+    Ok(())
+}
+
+/// Insert `data` and roll it back, then report what the insert would do.
+async fn dry_run(
+    data: &engine::GeneratedData,
+    schema: &DatabaseSchema,
+    db_url: &str,
+) -> Result<()> {
+    let table_rows: Vec<(String, usize)> = data
+        .tables
+        .iter()
+        .map(|(table, rows)| (table.clone(), rows.len()))
+        .collect();
+    let pb4 = TableProgress::new("4/4", "Inserting (dry run)...", &table_rows);
+    let report = output::dry_run::dry_run_direct(
+        data,
+        schema,
+        db_url,
+        Some(&|table, current, _total| pb4.update(table, current)),
+    )
+    .await?;
+    pb4.finish("Inserting (dry run)... ✓ rolled back".to_string());
+
+    eprintln!(
+        "\n✓ Dry run rolled back: {} rows across {} tables, {} of SQL",
+        report.total_rows(),
+        report.tables.len(),
+        estimate::format_bytes(report.total_sql_bytes()),
+    );
+    let width = report
+        .tables
+        .iter()
+        .map(|t| t.table.len())
+        .max()
+        .unwrap_or(0);
+    for table in &report.tables {
+        eprintln!(
+            "  {:<width$}  {:>8} rows  {:>10}",
+            table.table,
+            table.rows,
+            estimate::format_bytes(table.sql_bytes),
+            width = width,
+        );
+    }
+
+    if report.violations.is_empty() {
+        eprintln!("✓ No constraint violations");
+        return Ok(());
+    }
+    const SHOWN: usize = 20;
+    eprintln!("\n✗ {} statements would fail:", report.violations.len());
+    for violation in report.violations.iter().take(SHOWN) {
+        let rows = if violation.rows > 0 {
+            format!(
+                "rows {}-{}",
+                violation.first_row + 1,
+                violation.first_row + violation.rows
+            )
+        } else {
+            format!("row {} (deferred UPDATE)", violation.first_row + 1)
+        };
+        eprintln!("  {} {}: {}", violation.table, rows, violation.message);
+    }
+    if report.violations.len() > SHOWN {
+        eprintln!("  ... and {} more", report.violations.len() - SHOWN);
+    }
+    bail!(
+        "The insert would fail: {} statements rejected",
+        report.violations.len()
+    )
+}
+
+/// Write the data dictionary for `--dictionary`: CSV for a `.csv` path,
+/// markdown otherwise.
+fn write_dictionary(
+    path: &Path,
+    plan: &GenerationPlan,
+    schema: &DatabaseSchema,
+    data: &engine::GeneratedData,
+) -> Result<()> {
+    let entries = output::dictionary::build_dictionary(plan, schema, data);
+    let file = File::create(path)
+        .with_context(|| format!("Failed to create data dictionary: {}", path.display()))?;
+    let mut writer = BufWriter::new(file);
+    if path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("csv"))
+    {
+        output::dictionary::write_dictionary_csv(&mut writer, &entries)?;
+    } else {
+        output::dictionary::write_dictionary_markdown(&mut writer, &entries)?;
+    }
+    writer.flush().context("Failed to write data dictionary")?;
+    Ok(())
+}
+
+/// Merge existing dumps with their IDs renumbered (`--remap`), writing the
+/// result like generated data. No lock file: nothing here is reproducible
+/// from a seed.
+async fn remap_dumps(
+    args: &GenerateArgs,
+    schema: &DatabaseSchema,
+    db_url: &str,
+    insertion_order: &[String],
+    deferred: &[DeferredEdge],
+) -> Result<()> {
+    let dumps = args
+        .remap
+        .iter()
+        .map(|path| remap::load_dump(Path::new(path), schema))
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    let (data, report) = remap::remap_ids(&dumps, schema, insertion_order, deferred);
+    let total_rows: usize = data.tables.values().map(Vec::len).sum();
+
+    if !report.unknown_tables.is_empty() {
+        eprintln!(
+            "Warning: skipped tables not in the schema: {}",
+            report.unknown_tables.join(", ")
+        );
+    }
+    eprintln!(
+        "Renumbered IDs in {} tables from {} dumps",
+        report.remapped_tables.len(),
+        dumps.len()
+    );
+    if report.nulled_references > 0 {
+        eprintln!(
+            "  {} references to rows missing from their dump set to NULL",
+            report.nulled_references
+        );
+    }
+    for (table, count) in &report.dropped_rows {
+        eprintln!(
+            "  {} rows dropped from {} (required reference missing)",
+            count, table
+        );
+    }
+    if report.duplicate_rows > 0 {
+        eprintln!(
+            "  {} rows with keys already seen in an earlier dump skipped",
+            report.duplicate_rows
+        );
+    }
 
+    match args.output.as_deref() {
+        Some("direct") => {
+            output::direct::insert_direct(&data, schema, db_url, None).await?;
+            eprintln!(
+                "\n✓ Inserted {} rows across {} tables into {}",
+                total_rows,
+                data.tables.len(),
+                schema.database_type,
+            );
+        }
+        Some(path) => {
+            let file = File::create(path)
+                .with_context(|| format!("Failed to create output file: {}", path))?;
+            let mut writer = BufWriter::new(file);
+            write_output(&mut writer, args, &data, schema)?;
+            if let OutputFormat::Sql = args.output_format() {
+                output::sql::write_sequence_resets(&mut writer, &data, schema)?;
+            }
+            writer
+                .flush()
+                .with_context(|| format!("Failed to write output file: {}", path))?;
+            eprintln!(
+                "\n✓ Merged {} rows across {} tables → {}",
+                total_rows,
+                data.tables.len(),
+                path
+            );
+        }
+        None => {
+            let stdout = std::io::stdout();
+            let mut writer = BufWriter::new(stdout.lock());
+            write_output(&mut writer, args, &data, schema)?;
+            if let OutputFormat::Sql = args.output_format() {
+                output::sql::write_sequence_resets(&mut writer, &data, schema)?;
+            }
+            writer.flush().context("Failed to write to stdout")?;
+        }
+    }
     Ok(())
 }
 
+/// Write `data` in the output format: SQL (as PostgreSQL `COPY` with
+/// `--copy`), JSON or CSV.
+fn write_output<W: Write>(
+    writer: &mut W,
+    args: &GenerateArgs,
+    data: &engine::GeneratedData,
+    schema: &DatabaseSchema,
+) -> seedkit_core::error::Result<()> {
+    match args.output_format() {
+        OutputFormat::Sql
+            if args.copy && matches!(schema.database_type, DatabaseType::PostgreSQL) =>
+        {
+            output::sql::write_postgres_copy(writer, data, schema)
+        }
+        OutputFormat::Sql => output::sql::write_sql(writer, data, schema),
+        OutputFormat::Json => output::json::write_json(writer, data),
+        OutputFormat::Csv => output::csv::write_csv(writer, data),
+    }
+}
+
+/// Estimate the output file's size from a small generated batch and bail if
+/// it won't fit in the free space at `path`.
+fn check_disk_space(
+    plan: &GenerationPlan,
+    gen_schema: &DatabaseSchema,
+    schema: &DatabaseSchema,
+    path: &Path,
+    args: &GenerateArgs,
+) -> Result<()> {
+    let sample = engine::execute_plan(
+        &estimate::sample_plan(plan, estimate::SAMPLE_ROWS_PER_TABLE),
+        gen_schema,
+        None,
+    )?;
+    let estimated = estimate::estimate_output_size(plan, &sample, |w, data| {
+        write_output(w, args, data, schema)
+    })?;
+
+    let Some(free) = estimate::available_space(path) else {
+        return Ok(());
+    };
+    // Overwriting an existing file frees its space first.
+    let available = free + std::fs::metadata(path).map_or(0, |m| m.len());
+    if estimated > available {
+        bail!(
+            "Output needs about {} but only {} is free at {}.\n\
+             Reduce --rows, write to a larger disk, or pass --skip-space-check to try anyway.",
+            estimate::format_bytes(estimated),
+            estimate::format_bytes(available),
+            path.display(),
+        );
+    }
+    if estimated > available / 10 * 9 {
+        eprintln!(
+            "Warning: output needs about {} of the {} free at {}",
+            estimate::format_bytes(estimated),
+            estimate::format_bytes(available),
+            path.display(),
+        );
+    }
+    Ok(())
+}
+
+/// Write the texts of columns marked for LLM generation and plan those
+/// columns to use them. Columns the provider can't write for (no API key,
+/// failed requests, over budget with `over_budget = "rule-based"`) keep
+/// their usual generator.
+pub(crate) async fn fill_llm_content(
+    plan: &mut GenerationPlan,
+    schema: &DatabaseSchema,
+    config: &seedkit_core::config::SeedKitConfig,
+    model_override: Option<&str>,
+    budget: &mut llm::budget::LlmBudget,
+) -> Result<()> {
+    let columns = config.llm_columns();
+    if columns.is_empty() {
+        return Ok(());
+    }
+    let provider = match llm::client::LlmProvider::from_config(Some(&config.llm), model_override) {
+        Ok(provider) => provider,
+        Err(e) => {
+            eprintln!(
+                "Warning: {} LLM columns use their usual generators: {}",
+                columns.len(),
+                e
+            );
+            return Ok(());
+        }
+    };
+
+    let targets: Vec<_> = columns
+        .iter()
+        .filter_map(|(key, content)| {
+            let (table, column) = key.split_once('.')?;
+            let col = schema.tables.get(table)?.columns.get(column)?;
+            Some((key, table, column, content, col.max_length))
+        })
+        .collect();
+    let mut estimate = llm::budget::LlmEstimate::default();
+    for (_, table, column, content, max_length) in &targets {
+        estimate += llm::content::estimate_content(table, column, content, *max_length);
+    }
+    if estimate.requests > 0 {
+        eprintln!("LLM content estimate: {}", budget.describe(&estimate));
+        if let Err(reason) = budget.reserve(&estimate) {
+            return over_llm_budget(budget, "content", &reason);
+        }
+    }
+
+    let mut pools = BTreeMap::new();
+    for (key, table, column, content, max_length) in targets {
+        match llm::content::fetch_content(&provider, table, column, content, max_length).await {
+            Ok(texts) => {
+                pools.insert(key.clone(), texts);
+            }
+            Err(e) => eprintln!(
+                "Warning: {} uses its usual generator, LLM content failed: {}",
+                key, e
+            ),
+        }
+    }
+    plan.set_llm_content(&pools);
+    Ok(())
+}
+
+/// Mark stable tables in the plan and pin the rows recorded for them, as
+/// long as the table definition hasn't changed since they were recorded.
+pub(crate) fn apply_stable_tables(
+    plan: &mut GenerationPlan,
+    schema: &DatabaseSchema,
+    stable_tables: &[String],
+    recorded: Option<&BTreeMap<String, StableTableLock>>,
+) {
+    for name in stable_tables {
+        let Some(table) = schema.tables.get(name) else {
+            eprintln!("Warning: stable table '{}' not found in schema", name);
+            continue;
+        };
+        if !plan.mark_stable(name) {
+            continue;
+        }
+
+        for fk in &table.foreign_keys {
+            if !stable_tables.contains(&fk.referenced_table) {
+                eprintln!(
+                    "Warning: stable table '{}' references '{}', which is not stable; \
+                     its foreign keys may point at different rows between runs",
+                    name, fk.referenced_table
+                );
+            }
+        }
+
+        match recorded.and_then(|r| r.get(name)) {
+            Some(lock) if lock.table_hash == compute_table_hash(table) => {
+                plan.pin_rows(name, lock.rows.clone());
+            }
+            Some(_) => eprintln!(
+                "Table '{}' changed since its stable rows were recorded; regenerating them",
+                name
+            ),
+            None => {}
+        }
+    }
+}
+
+/// Connect to the database at `db_url` and introspect its schema.
+pub(crate) async fn introspect_schema(
+    db_url: &str,
+    db_type: DatabaseType,
+) -> Result<DatabaseSchema> {
+    match db_type {
+        DatabaseType::PostgreSQL => {
+            let pool = sqlx::postgres::PgPoolOptions::new()
+                .max_connections(5)
+                .connect(db_url)
+                .await
+                .context("Failed to connect to PostgreSQL")?;
+            let introspector = seedkit_core::schema::postgres::PostgresIntrospector::new(pool);
+            Ok(introspector.introspect().await?)
+        }
+        DatabaseType::MySQL => {
+            let pool = sqlx::mysql::MySqlPoolOptions::new()
+                .max_connections(5)
+                .connect(db_url)
+                .await
+                .context("Failed to connect to MySQL")?;
+            let db_name = extract_mysql_db_name(db_url).unwrap_or("mysql".to_string());
+            let introspector = seedkit_core::schema::mysql::MySqlIntrospector::new(pool, db_name);
+            Ok(introspector.introspect().await?)
+        }
+        DatabaseType::SQLite => {
+            let pool = sqlx::sqlite::SqlitePoolOptions::new()
+                .max_connections(1)
+                .connect(db_url)
+                .await
+                .context("Failed to connect to SQLite")?;
+            let introspector = seedkit_core::schema::sqlite::SqliteIntrospector::new(pool);
+            Ok(introspector.introspect().await?)
+        }
+    }
+}
+
 /// Resolve database URL from args, env, .env file, or seedkit.toml.
-fn resolve_db_url(
+pub(crate) fn resolve_db_url(
     explicit: Option<&str>,
     config: Option<&seedkit_core::config::SeedKitConfig>,
 ) -> Result<String> {
@@ -432,47 +1031,174 @@ fn extract_mysql_db_name(url: &str) -> Option<String> {
 
 /// Enhance rule-based classifications with LLM analysis.
 ///
-/// Sends the schema to the configured LLM provider, caches the response,
-/// and merges the results (LLM overrides only for `Unknown` columns).
+/// Sends the tables with columns missing from the per-column cache to the
+/// configured LLM provider in batches, caches the answers, and merges the
+/// results (LLM overrides only for `Unknown` columns). A batch that fails
+/// after its retries keeps rule-based types; the run fails only when every
+/// batch does.
 async fn enhance_with_llm(
     rule_classifications: &BTreeMap<(String, String), SemanticType>,
     schema: &DatabaseSchema,
+    llm_config: Option<&seedkit_core::config::LlmConfig>,
     model_override: Option<&str>,
+    budget: &mut llm::budget::LlmBudget,
 ) -> Result<(
     BTreeMap<(String, String), SemanticType>,
     Option<BTreeMap<String, BTreeMap<String, SemanticType>>>,
 )> {
-    let provider = llm::client::LlmProvider::from_env(model_override)
+    let provider = llm::client::LlmProvider::from_config(llm_config, model_override)
         .context("--ai flag requires an LLM API key")?;
 
-    let schema_hash = compute_schema_hash(schema);
-
     // Check cache first
-    let response = if let Some(cached) = llm::client::load_cached_response(&schema_hash) {
-        eprintln!("Using cached LLM classification (schema unchanged)");
-        cached
+    let cache_path = Path::new(llm::cache::CLASSIFICATION_CACHE_FILE);
+    let mut cache = llm::cache::ClassificationCache::load(cache_path);
+    let mut lookup = cache.lookup(schema, provider.model());
+    if lookup.pending_tables.is_empty() {
+        eprintln!("Using cached LLM classification (columns unchanged)");
     } else {
-        eprintln!("Sending schema to LLM for classification...");
-        let ddl = llm::prompt::schema_to_compact_ddl(schema);
-        let prompt = llm::prompt::classification_prompt(&ddl);
-        let resp = provider
-            .classify(&prompt)
-            .await
-            .context("LLM classification request failed")?;
-        llm::client::save_cached_response(&schema_hash, &resp);
-        resp
-    };
+        let pending = llm::cache::schema_subset(schema, &lookup.pending_tables);
+        let batches: Vec<_> = llm::prompt::classification_batches(
+            &pending,
+            llm::prompt::CLASSIFICATION_BATCH_COLUMNS,
+        )
+        .into_iter()
+        .map(|tables| {
+            let batch = llm::cache::schema_subset(schema, &tables);
+            let ddl = llm::prompt::schema_to_compact_ddl(&batch);
+            (tables, batch, llm::prompt::classification_prompt(&ddl))
+        })
+        .collect();
 
-    let merged = llm::parse::merge_classifications(rule_classifications, &response);
+        let mut estimate = llm::budget::LlmEstimate::default();
+        for (_, batch, prompt) in &batches {
+            let columns: usize = batch.tables.values().map(|t| t.columns.len()).sum();
+            estimate.add_request(
+                prompt,
+                columns as u64 * llm::budget::CLASSIFICATION_TOKENS_PER_COLUMN,
+            );
+        }
+        eprintln!(
+            "LLM classification estimate: {}",
+            budget.describe(&estimate)
+        );
+        if let Err(reason) = budget.reserve(&estimate) {
+            over_llm_budget(budget, "classification", &reason)?;
+            let merged = llm::parse::merge_column_classifications(
+                rule_classifications,
+                &lookup.classifications,
+            );
+            let cache = llm::parse::build_ai_classification_cache(rule_classifications, &merged);
+            return Ok((merged, (!cache.is_empty()).then_some(cache)));
+        }
+
+        eprintln!(
+            "Sending {} of {} tables to LLM for classification in {} batches...",
+            lookup.pending_tables.len(),
+            schema.tables.len(),
+            batches.len()
+        );
+        let mut failures = Vec::new();
+        for (i, (tables, batch, prompt)) in batches.iter().enumerate() {
+            let result = match provider.classify(prompt).await {
+                Ok(resp) => llm::parse::parse_classifications(&resp),
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(fresh) => {
+                    // Saved batch by batch, so an interrupted run keeps them
+                    cache.record(batch, provider.model(), &fresh);
+                    if let Err(e) = cache.save(cache_path) {
+                        eprintln!(
+                            "Warning: failed to save LLM cache {}: {}",
+                            cache_path.display(),
+                            e
+                        );
+                    }
+                    lookup.classifications.extend(fresh);
+                }
+                Err(e) => {
+                    eprintln!(
+                        "Warning: LLM classification of batch {}/{} ({}) failed, keeping rule-based types: {}",
+                        i + 1,
+                        batches.len(),
+                        tables.join(", "),
+                        e
+                    );
+                    failures.push(e);
+                }
+            }
+        }
+        if failures.len() == batches.len() {
+            if let Some(e) = failures.pop() {
+                return Err(e).context("LLM classification request failed");
+            }
+        }
+    }
+
+    let merged =
+        llm::parse::merge_column_classifications(rule_classifications, &lookup.classifications);
     let cache = llm::parse::build_ai_classification_cache(rule_classifications, &merged);
     let ai_cache = if cache.is_empty() { None } else { Some(cache) };
 
     Ok((merged, ai_cache))
 }
 
+/// Handle a phase whose LLM estimate doesn't fit the budget: fail the run,
+/// or skip the phase when `over_budget = "rule-based"`.
+fn over_llm_budget(budget: &llm::budget::LlmBudget, phase: &str, reason: &str) -> Result<()> {
+    match budget.over_budget {
+        llm::budget::OverBudget::Abort => bail!(
+            "LLM {} is over budget: {}. Raise the [llm] limits in seedkit.toml, or set \
+             over_budget = \"rule-based\" to skip it",
+            phase,
+            reason
+        ),
+        llm::budget::OverBudget::RuleBased => {
+            eprintln!(
+                "Warning: skipping LLM {}, over budget: {}. Using rule-based generation",
+                phase, reason
+            );
+            Ok(())
+        }
+    }
+}
+
+/// Shape `config` by `scenario`, or by the scenario `lock_file` was
+/// generated with; naming another one than the lock file's is an error.
+/// Returns the scenario applied.
+pub(crate) fn apply_scenario(
+    config: &mut Option<seedkit_core::config::SeedKitConfig>,
+    scenario: Option<&str>,
+    lock_file: Option<&LockFile>,
+    lock_name: &str,
+) -> Result<Option<String>> {
+    let recorded = lock_file.and_then(|lf| lf.config.scenario.as_deref());
+    let scenario = match (scenario, recorded) {
+        (Some(scenario), Some(recorded)) if scenario != recorded => bail!(
+            "{} was generated with --scenario {}, not {}",
+            lock_name,
+            recorded,
+            scenario
+        ),
+        (scenario, recorded) => scenario.or(recorded),
+    };
+    let Some(scenario) = scenario else {
+        return Ok(None);
+    };
+    let Some(cfg) = config else {
+        bail!(
+            "--scenario {} needs a seedkit.toml defining [scenario.{}]",
+            scenario,
+            scenario
+        );
+    };
+    cfg.apply_scenario(scenario)?;
+    Ok(Some(scenario.to_string()))
+}
+
 /// Restore AI classifications from a lock file into the classification map.
 #[allow(clippy::type_complexity)]
-fn restore_ai_from_lock(
+pub(crate) fn restore_ai_from_lock(
     rule_classifications: &BTreeMap<(String, String), SemanticType>,
     lock_file: &LockFile,
 ) -> (
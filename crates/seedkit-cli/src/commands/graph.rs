@@ -1,69 +1,132 @@
+use std::fmt::Write;
+use std::path::Path;
+
 use anyhow::{Context, Result};
 
-use seedkit_core::graph::cycle::break_cycles;
+use seedkit_core::graph::cycle::{break_cycles_explained, explain_cycles};
 use seedkit_core::graph::dag::DependencyGraph;
+use seedkit_core::graph::html;
+use seedkit_core::graph::topo;
 use seedkit_core::graph::visualize::{self, GraphFormat as VizFormat};
-use seedkit_core::schema::introspect::{database_type_from_url, SchemaIntrospector};
-use seedkit_core::schema::types::DatabaseType;
+use seedkit_core::schema::introspect::database_type_from_url;
+
+use super::generate::{introspect_schema, resolve_db_url};
+use crate::args::{GraphArgs, GraphFormat};
 
-use crate::args::GraphArgs;
+/// Where `--format html` writes without `--output`.
+const DEFAULT_HTML_PATH: &str = "seedkit-graph.html";
 
+/// Print the schema as a Mermaid ER diagram, or the dependency graph.
+/// The HTML page is written to a file, sized by estimated row counts.
+/// Cycles are broken as `seedkit generate` breaks them, honoring
+/// `[graph] break_cycle_at` and polymorphic associations from
+/// seedkit.toml, and each broken cycle is explained on stderr. `--focus`
+/// narrows everything to one table's neighborhood; `--levels` prints the
+/// insertion levels instead of a diagram.
 pub async fn run(args: &GraphArgs) -> Result<()> {
-    let db_url = args
-        .db
-        .as_deref()
-        .ok_or_else(|| seedkit_core::error::SeedKitError::NoDatabaseUrl)?;
+    let config =
+        seedkit_core::config::read_config_with_env(Path::new("."), args.config_env.as_deref())?;
+    let db_url = resolve_db_url(args.db.as_deref(), config.as_ref())?;
+    let db_type = database_type_from_url(&db_url)?;
+    let mut schema = introspect_schema(&db_url, db_type).await?;
 
-    let db_type = database_type_from_url(db_url)?;
+    let polymorphic = config
+        .as_ref()
+        .map(|c| c.polymorphic_associations())
+        .unwrap_or_default();
+    let mut dep_graph = DependencyGraph::from_schema(&schema);
+    dep_graph.add_polymorphic_edges(&schema, &polymorphic);
+    let cycle_break_hints: Vec<String> = config
+        .as_ref()
+        .map(|c| c.graph.break_cycle_at.clone())
+        .unwrap_or_default();
+    // Taken before cycles are broken, so deferred edges still link tables
+    let focus = match &args.focus {
+        Some(table) => Some(
+            dep_graph
+                .neighborhood(table, args.depth)
+                .with_context(|| format!("Unknown table '{}' for --focus", table))?,
+        ),
+        None => None,
+    };
+    let mut breaks = break_cycles_explained(&mut dep_graph, &cycle_break_hints)?;
+    if let Some(tables) = &focus {
+        eprintln!(
+            "Showing {} of {} tables within {} foreign key(s) of {}",
+            tables.len(),
+            schema.table_count(),
+            args.depth,
+            args.focus.as_deref().unwrap_or_default()
+        );
+        dep_graph = dep_graph.subgraph(tables);
+        breaks.retain(|b| {
+            tables.contains(&b.edge.source_table) && tables.contains(&b.edge.target_table)
+        });
+        schema.tables.retain(|name, _| tables.contains(name));
+    }
+    let deferred: Vec<_> = breaks.iter().map(|b| b.edge.clone()).collect();
 
-    let schema = match db_type {
-        DatabaseType::PostgreSQL => {
-            let pool = sqlx::postgres::PgPoolOptions::new()
-                .max_connections(5)
-                .connect(db_url)
-                .await
-                .context("Failed to connect to PostgreSQL")?;
-            let introspector = seedkit_core::schema::postgres::PostgresIntrospector::new(pool);
-            introspector.introspect().await?
-        }
-        DatabaseType::MySQL => {
-            let pool = sqlx::mysql::MySqlPoolOptions::new()
-                .max_connections(5)
-                .connect(db_url)
-                .await
-                .context("Failed to connect to MySQL")?;
-            let db_name = db_url
-                .rsplit('/')
-                .next()
-                .unwrap_or("mysql")
-                .split('?')
-                .next()
-                .unwrap_or("mysql")
-                .to_string();
-            let introspector = seedkit_core::schema::mysql::MySqlIntrospector::new(pool, db_name);
-            introspector.introspect().await?
+    let output = if args.levels {
+        let levels = topo::insertion_levels(&dep_graph)?;
+        let mut output = String::new();
+        for (i, level) in levels.iter().enumerate() {
+            let _ = writeln!(
+                output,
+                "Level {} ({} table{}): {}",
+                i + 1,
+                level.len(),
+                if level.len() == 1 { "" } else { "s" },
+                level.join(", ")
+            );
         }
-        DatabaseType::SQLite => {
-            let pool = sqlx::sqlite::SqlitePoolOptions::new()
-                .max_connections(1)
-                .connect(db_url)
-                .await
-                .context("Failed to connect to SQLite")?;
-            let introspector = seedkit_core::schema::sqlite::SqliteIntrospector::new(pool);
-            introspector.introspect().await?
+        output
+    } else {
+        match args.format {
+            GraphFormat::Mermaid => visualize::er_diagram(&schema),
+            GraphFormat::MermaidFlowchart => {
+                visualize::visualize(&dep_graph, &deferred, VizFormat::Mermaid)
+            }
+            GraphFormat::Dot => visualize::visualize(&dep_graph, &deferred, VizFormat::Dot),
+            GraphFormat::Html => {
+                let row_counts = html::estimate_row_counts(&db_url, &schema)
+                    .await
+                    .unwrap_or_else(|e| {
+                        tracing::warn!(
+                            "Could not estimate row counts, sizing tables equally: {}",
+                            e
+                        );
+                        Default::default()
+                    });
+                html::html_graph(&schema, &dep_graph, &deferred, &row_counts)
+            }
         }
     };
 
-    let mut dep_graph = DependencyGraph::from_schema(&schema);
-    let deferred = break_cycles(&mut dep_graph, &[])?;
-
-    let format = match args.format {
-        crate::args::GraphFormat::Mermaid => VizFormat::Mermaid,
-        crate::args::GraphFormat::Dot => VizFormat::Dot,
-    };
+    let path = args
+        .output
+        .clone()
+        .or_else(|| matches!(args.format, GraphFormat::Html).then(|| DEFAULT_HTML_PATH.into()));
+    match path {
+        Some(path) => {
+            std::fs::write(&path, output).with_context(|| format!("Failed to write {}", path))?;
+            eprintln!(
+                "✓ Wrote {} ({} tables, {} relationships)",
+                path,
+                schema.table_count(),
+                dep_graph.graph.edge_count() + deferred.len()
+            );
+        }
+        None => print!("{}", output),
+    }
 
-    let output = visualize::visualize(&dep_graph, &deferred, format);
-    println!("{}", output);
+    // On stderr, so the diagram can still be piped
+    if !breaks.is_empty() {
+        eprintln!(
+            "\n{} foreign key(s) deferred to break cycles:\n\n{}",
+            breaks.len(),
+            explain_cycles(&schema, &breaks)
+        );
+    }
 
     Ok(())
 }
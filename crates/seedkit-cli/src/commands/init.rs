@@ -0,0 +1,35 @@
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+
+use seedkit_core::classify::rules::classify_schema;
+use seedkit_core::output::starter_config::starter_config;
+use seedkit_core::schema::introspect::database_type_from_url;
+
+use super::generate::{introspect_schema, resolve_db_url};
+use crate::args::InitArgs;
+
+/// Introspect the database and write a commented starter seedkit.toml.
+pub async fn run(args: &InitArgs) -> Result<()> {
+    let path = Path::new(&args.output);
+    if path.exists() && !args.force {
+        bail!(
+            "{} already exists. Run with --force to overwrite it.",
+            path.display()
+        );
+    }
+
+    let db_url = resolve_db_url(args.db.as_deref(), None)?;
+    let db_type = database_type_from_url(&db_url)?;
+    let schema = introspect_schema(&db_url, db_type).await?;
+    let classifications = classify_schema(&schema);
+
+    let config = starter_config(&schema, &classifications, args.rows);
+    std::fs::write(path, config).with_context(|| format!("Failed to write {}", path.display()))?;
+    eprintln!(
+        "✓ Wrote {} for {} tables. Edit it, then run `seedkit generate`.",
+        path.display(),
+        schema.tables.len()
+    );
+    Ok(())
+}
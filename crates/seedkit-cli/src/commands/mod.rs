@@ -1,6 +1,11 @@
 pub mod check;
+pub mod classify;
+pub mod config;
 pub mod generate;
 pub mod graph;
+pub mod init;
 pub mod introspect;
 pub mod preview;
+pub mod regen;
 pub mod sample;
+pub mod wipe;
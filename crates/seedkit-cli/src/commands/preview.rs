@@ -7,10 +7,11 @@ use seedkit_core::generate::plan::GenerationPlan;
 use seedkit_core::graph::cycle::break_cycles;
 use seedkit_core::graph::dag::DependencyGraph;
 use seedkit_core::graph::topo::topological_sort;
+use seedkit_core::output::markdown::preview_markdown;
 use seedkit_core::schema::introspect::{database_type_from_url, SchemaIntrospector};
 use seedkit_core::schema::types::DatabaseType;
 
-use crate::args::PreviewArgs;
+use crate::args::{PreviewArgs, PreviewFormat};
 
 pub async fn run(args: &PreviewArgs) -> Result<()> {
     let db_url = args
@@ -65,7 +66,7 @@ pub async fn run(args: &PreviewArgs) -> Result<()> {
     let insertion_order = topological_sort(&dep_graph)?;
 
     let seed = 42u64; // Fixed seed for preview
-    let plan = GenerationPlan::build(
+    let mut plan = GenerationPlan::build(
         &schema,
         &classifications,
         &insertion_order.tables,
@@ -77,15 +78,33 @@ pub async fn run(args: &PreviewArgs) -> Result<()> {
         &std::collections::BTreeMap::new(),
         None,
     );
+    plan.apply_lookup_tables(
+        &schema,
+        &std::collections::BTreeMap::new(),
+        &std::collections::BTreeMap::new(),
+    );
 
     let data = engine::execute_plan(&plan, &schema, None)?;
 
+    if let PreviewFormat::Markdown = args.format {
+        print!("{}", preview_markdown(&data, &schema));
+        return Ok(());
+    }
+
     for (table_name, rows) in &data.tables {
         if rows.is_empty() {
             continue;
         }
 
         println!("━━━ {} ({} rows) ━━━", table_name, rows.len());
+        // Comments say what cryptic column names mean
+        if let Some(table) = schema.tables.get(table_name) {
+            for column in table.columns.values() {
+                if let Some(ref comment) = column.comment {
+                    println!("  {}: {}", column.name, comment);
+                }
+            }
+        }
 
         let columns: Vec<&String> = rows[0].keys().collect();
 
@@ -0,0 +1,251 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+
+use seedkit_core::check::compute_schema_hash;
+use seedkit_core::classify::rules::classify_schema_with_rules;
+use seedkit_core::generate::engine::{self, GeneratedData};
+use seedkit_core::generate::locale::LocaleMix;
+use seedkit_core::generate::plan::{filter_insertion_order, GenerationPlan};
+use seedkit_core::graph::cycle::break_cycles;
+use seedkit_core::graph::dag::DependencyGraph;
+use seedkit_core::graph::topo::topological_sort;
+use seedkit_core::lock;
+use seedkit_core::output;
+use seedkit_core::schema::introspect::database_type_from_url;
+
+use super::generate::{
+    apply_scenario, apply_stable_tables, fill_llm_content, introspect_schema, resolve_db_url,
+    restore_ai_from_lock,
+};
+use crate::args::{RegenArgs, RegenEmit};
+
+/// Regenerate one table and emit it as UPDATE (or INSERT) statements.
+///
+/// The full plan is re-executed with the original seed so that every table
+/// generated before the target sees the same RNG stream, which keeps the
+/// target's foreign keys pointing at rows that exist in the seeded database.
+/// Only the target table's rows are written.
+pub async fn run(args: &RegenArgs) -> Result<()> {
+    let mut config =
+        seedkit_core::config::read_config_with_env(Path::new("."), args.config_env.as_deref())?;
+
+    let db_url = resolve_db_url(args.db.as_deref(), config.as_ref())?;
+    let db_type = database_type_from_url(&db_url)?;
+    let schema = introspect_schema(&db_url, db_type).await?;
+
+    if !schema.tables.contains_key(&args.table) {
+        bail!("Table '{}' not found in the database schema", args.table);
+    }
+
+    let lock_name = lock::lock_file_name(args.profile.as_deref())?;
+    let lock_path = Path::new(&lock_name);
+    let restored_lock = if lock_path.exists() {
+        let lf = lock::read_lock_file(lock_path)?;
+        if compute_schema_hash(&schema) != lf.schema_hash {
+            eprintln!(
+                "Warning: schema has changed since {} was created; foreign keys may not match seeded rows",
+                lock_name
+            );
+        }
+        Some(lf)
+    } else {
+        None
+    };
+    apply_scenario(
+        &mut config,
+        args.scenario.as_deref(),
+        restored_lock.as_ref(),
+        &lock_name,
+    )?;
+
+    let seed = args
+        .seed
+        .or(restored_lock.as_ref().map(|lf| lf.seed))
+        .or(config.as_ref().and_then(|c| c.generate.seed));
+    let Some(seed) = seed else {
+        bail!(
+            "No seed available. Pass --seed, or run `seedkit generate` first to create {}.",
+            lock_name
+        );
+    };
+
+    let polymorphic = config
+        .as_ref()
+        .map(|c| c.polymorphic_associations())
+        .unwrap_or_default();
+    let mut dep_graph = DependencyGraph::from_schema(&schema);
+    dep_graph.add_polymorphic_edges(&schema, &polymorphic);
+    let cycle_break_hints: Vec<String> = config
+        .as_ref()
+        .map(|c| c.graph.break_cycle_at.clone())
+        .unwrap_or_default();
+    let deferred = break_cycles(&mut dep_graph, &cycle_break_hints)?;
+    let insertion_order = topological_sort(&dep_graph)?;
+
+    let custom_rules = match config {
+        Some(ref cfg) => cfg.classification_rules()?,
+        None => Vec::new(),
+    };
+    let mut rule_classifications = classify_schema_with_rules(&schema, &custom_rules);
+    if let Some(ref cfg) = config {
+        cfg.apply_type_overrides(&mut rule_classifications);
+    }
+    let (classifications, row_count, table_row_overrides, base_time, include, exclude) =
+        match restored_lock {
+            Some(ref lf) => (
+                restore_ai_from_lock(&rule_classifications, lf).0,
+                lf.config.default_row_count,
+                lf.config.table_row_overrides.clone(),
+                lf.parse_base_time(),
+                lf.config.include_tables.clone(),
+                lf.config.exclude_tables.clone(),
+            ),
+            None => (
+                rule_classifications,
+                config.as_ref().and_then(|c| c.generate.rows).unwrap_or(100),
+                config
+                    .as_ref()
+                    .map(|c| c.table_row_overrides())
+                    .unwrap_or_default(),
+                None,
+                Vec::new(),
+                Vec::new(),
+            ),
+        };
+
+    let filtered_order =
+        filter_insertion_order(&insertion_order.tables, &schema, &include, &exclude);
+    if !filtered_order.contains(&args.table) {
+        bail!(
+            "Table '{}' was excluded from generation; nothing to regenerate",
+            args.table
+        );
+    }
+
+    // Column overrides come from the current seedkit.toml, which is what
+    // makes regenerating a single table useful while iterating on it.
+    let column_overrides = config
+        .as_ref()
+        .map(|c| c.column_overrides())
+        .unwrap_or_default();
+
+    let locale: LocaleMix = match restored_lock {
+        Some(ref lf) => match lf.config.locale {
+            Some(ref name) => name.parse().map_err(anyhow::Error::msg)?,
+            None => LocaleMix::default(),
+        },
+        None => config
+            .as_ref()
+            .and_then(|c| c.locale_mix())
+            .unwrap_or_default(),
+    };
+
+    let mut plan = GenerationPlan::build(
+        &schema,
+        &classifications,
+        &filtered_order,
+        deferred,
+        row_count,
+        &table_row_overrides,
+        seed,
+        base_time,
+        &column_overrides,
+        None,
+    );
+    let lookup_overrides = match restored_lock
+        .as_ref()
+        .and_then(|lf| lf.config.lookup_overrides(&schema))
+    {
+        Some(overrides) => overrides,
+        None => config
+            .as_ref()
+            .map(|c| c.lookup_overrides())
+            .unwrap_or_default(),
+    };
+    plan.apply_lookup_tables(&schema, &lookup_overrides, &table_row_overrides);
+    // Stable tables use their own RNG, so they must be marked here too for
+    // a shared stream (lock files without per-table streams) to line up
+    // with the original run.
+    let mut stable_tables = config
+        .as_ref()
+        .map(|c| c.stable_tables())
+        .unwrap_or_default();
+    if let Some(ref lf) = restored_lock {
+        stable_tables.extend(lf.stable_tables.keys().cloned());
+        plan.table_streams = lf.config.table_streams;
+    }
+    apply_stable_tables(
+        &mut plan,
+        &schema,
+        &stable_tables,
+        restored_lock.as_ref().map(|lf| &lf.stable_tables),
+    );
+    plan.set_locale(locale);
+    plan.set_polymorphic_associations(&schema, &polymorphic);
+    if let Some(ref config) = config {
+        plan.set_language_mixes(
+            &config.language_mixes(),
+            config.default_language_mix().as_ref(),
+        );
+        plan.set_row_scripts(&config.row_scripts());
+        plan.set_time_series(&schema, &config.time_series());
+        plan.set_funnels(&schema, &config.funnels());
+        plan.set_segments(&schema, &config.segments());
+        let mut llm_budget = seedkit_core::llm::budget::LlmBudget::from_config(Some(&config.llm));
+        fill_llm_content(&mut plan, &schema, config, None, &mut llm_budget).await?;
+    }
+    if let Some(ref lf) = restored_lock {
+        plan.set_sequence_offsets(&lf.sequence_offsets);
+    }
+    let mut data = engine::execute_plan(&plan, &schema, None)?;
+    data.tables.retain(|name, _| *name == args.table);
+    data.deferred_updates.retain(|u| u.table_name == args.table);
+
+    match &args.output {
+        Some(path) => {
+            let file = File::create(path)
+                .with_context(|| format!("Failed to create output file: {}", path))?;
+            let mut writer = BufWriter::new(file);
+            let written = write_table(&mut writer, &data, &schema, args)?;
+            writer
+                .flush()
+                .with_context(|| format!("Failed to write output file: {}", path))?;
+            eprintln!(
+                "✓ Regenerated {} rows of {} → {}",
+                written, args.table, path
+            );
+        }
+        None => {
+            let stdout = std::io::stdout();
+            let mut writer = BufWriter::new(stdout.lock());
+            write_table(&mut writer, &data, &schema, args)?;
+            writer.flush().context("Failed to write to stdout")?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Write the target table's rows in the requested statement kind.
+fn write_table<W: Write>(
+    writer: &mut W,
+    data: &GeneratedData,
+    schema: &seedkit_core::schema::types::DatabaseSchema,
+    args: &RegenArgs,
+) -> Result<usize> {
+    match args.emit {
+        RegenEmit::Updates => Ok(output::sql::write_updates(
+            writer,
+            data,
+            schema,
+            &args.table,
+        )?),
+        RegenEmit::Inserts => {
+            output::sql::write_sql(writer, data, schema)?;
+            Ok(data.tables.get(&args.table).map_or(0, Vec::len))
+        }
+    }
+}
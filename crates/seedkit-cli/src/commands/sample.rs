@@ -1,18 +1,33 @@
 use std::path::Path;
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use indicatif::{ProgressBar, ProgressStyle};
 
 use seedkit_core::sample;
-use seedkit_core::sample::mask::mask_pii_distributions;
+use seedkit_core::sample::audit::{audit_profiles, drop_rare_categories};
+use seedkit_core::sample::mask::{
+    mask_pii_distributions, pseudonymize_pii_distributions, Pseudonymizer,
+};
+use seedkit_core::sample::privacy::apply_differential_privacy;
+use seedkit_core::sample::scan::{hash_pii_values, scan_profiles, strip_pii_values};
 use seedkit_core::sample::stats::{extract_distributions, SampleOptions};
 use seedkit_core::schema::introspect::{database_type_from_url, SchemaIntrospector};
 use seedkit_core::schema::types::DatabaseType;
 
-use crate::args::SampleArgs;
+use crate::args::{SampleArgs, SampleAuditArgs};
 
 pub async fn run(args: &SampleArgs) -> Result<()> {
-    let config = seedkit_core::config::read_config(Path::new("."))?;
+    if let Some(q) = args.quantiles.iter().find(|q| !(**q > 0.0 && **q < 1.0)) {
+        bail!("--quantiles must be between 0 and 1 (exclusive), got {}", q);
+    }
+    if !args.resample_threshold.is_finite() || args.resample_threshold < 0.0 {
+        bail!(
+            "--resample-threshold must be zero or more, got {}",
+            args.resample_threshold
+        );
+    }
+    let config =
+        seedkit_core::config::read_config_with_env(Path::new("."), args.config_env.as_deref())?;
 
     let db_url = resolve_db_url(args.db.as_deref(), config.as_ref())?;
     let db_type = database_type_from_url(&db_url)?;
@@ -83,6 +98,23 @@ pub async fn run(args: &SampleArgs) -> Result<()> {
         },
         categorical_limit: args.categorical_limit,
         min_row_count: args.min_rows,
+        detect_unique_pairs: !args.no_unique_pairs,
+        text_models: args.text_models,
+        histogram_buckets: args.histogram_buckets,
+        quantiles: if args.no_quantiles {
+            Vec::new()
+        } else {
+            args.quantiles.clone()
+        },
+        distinct_counts: !args.no_distinct_counts,
+        correlations: !args.no_correlations,
+        temporal_patterns: !args.no_temporal_patterns,
+        fan_outs: !args.no_fan_outs,
+        max_attempts: args.max_attempts,
+        progress_file: args.progress_file.as_ref().map(Into::into),
+        resume: !args.no_resume,
+        resample_threshold: args.resample_threshold,
+        ..SampleOptions::default()
     };
 
     let mut profiles = extract_distributions(&db_url, &schema, &options).await?;
@@ -102,7 +134,18 @@ pub async fn run(args: &SampleArgs) -> Result<()> {
     pb3.set_prefix("3/3");
     pb3.set_message("Masking PII...");
 
-    let masked_count = mask_pii_distributions(&mut profiles);
+    let masked_count = match (args.pseudonymize, args.mask_key.as_deref()) {
+        (true, Some(key)) => {
+            let pseudonymizer = Pseudonymizer::new(key);
+            let pseudonymizer = if args.preserve_format {
+                pseudonymizer.preserve_format()
+            } else {
+                pseudonymizer
+            };
+            pseudonymize_pii_distributions(&mut profiles, &pseudonymizer)
+        }
+        _ => mask_pii_distributions(&mut profiles),
+    };
     if masked_count > 0 {
         pb3.finish_with_message(format!(
             "Masking PII... done ({} columns masked)",
@@ -112,6 +155,34 @@ pub async fn run(args: &SampleArgs) -> Result<()> {
         pb3.finish_with_message("Masking PII... done (no PII detected)");
     }
 
+    if let Some(epsilon) = args.epsilon {
+        let dropped =
+            apply_differential_privacy(&mut profiles, &schema, epsilon, &mut rand::rng())?;
+        eprintln!(
+            "Added differential-privacy noise (epsilon={}, {} distributions dropped)",
+            epsilon, dropped
+        );
+    }
+
+    // k-anonymity audit of quasi-identifiers
+    let findings = audit_profiles(&profiles, args.k_anonymity);
+    if !findings.is_empty() {
+        eprintln!(
+            "Warning: {} re-identification risks below k={}:",
+            findings.len(),
+            args.k_anonymity
+        );
+        for finding in &findings {
+            eprintln!("  {}", finding);
+        }
+        if args.drop_rare {
+            let dropped = drop_rare_categories(&mut profiles, args.k_anonymity);
+            eprintln!("Dropped {} rare categories", dropped);
+        } else {
+            eprintln!("Drop rare categories with --drop-rare");
+        }
+    }
+
     // Save profiles
     let output_path = args.output.as_deref().unwrap_or(sample::PROFILES_FILE_NAME);
     let path = Path::new(output_path);
@@ -125,11 +196,57 @@ pub async fn run(args: &SampleArgs) -> Result<()> {
         total_distributions,
         output_path
     );
+    let unique_pairs: usize = profiles.iter().map(|p| p.unique_pairs.len()).sum();
+    if unique_pairs > 0 {
+        eprintln!(
+            "Detected {} implicit unique column pairs (enforce with --enforce-sampled-uniqueness)",
+            unique_pairs
+        );
+    }
     eprintln!("Use with: seedkit generate --subset {}", output_path);
 
     Ok(())
 }
 
+/// `seedkit sample audit`: report categorical values that look like PII,
+/// stripping or hashing them when asked. Exits 1 when any are left, so it
+/// can guard commits.
+pub fn audit(args: &SampleAuditArgs) -> Result<()> {
+    let path = Path::new(args.path.as_deref().unwrap_or(sample::PROFILES_FILE_NAME));
+    let mut profiles = sample::load_profiles(path)?;
+
+    let findings = scan_profiles(&profiles);
+    if findings.is_empty() {
+        eprintln!("No PII-looking values in {}", path.display());
+        return Ok(());
+    }
+    eprintln!("Values that look like PII in {}:", path.display());
+    for finding in &findings {
+        eprintln!("  {}", finding);
+    }
+
+    let changed = match (args.strip, args.hash, args.mask_key.as_deref()) {
+        (true, _, _) => {
+            let stripped = strip_pii_values(&mut profiles);
+            eprintln!("Stripped {} values", stripped);
+            true
+        }
+        (_, true, Some(key)) => {
+            let hashed = hash_pii_values(&mut profiles, &Pseudonymizer::new(key));
+            eprintln!("Hashed {} values", hashed);
+            true
+        }
+        _ => false,
+    };
+    if !changed {
+        eprintln!("Strip them with --strip, or hash them with --hash --mask-key <KEY>");
+        std::process::exit(1);
+    }
+    sample::save_profiles(&profiles, path)?;
+    eprintln!("Saved {}", path.display());
+    Ok(())
+}
+
 fn resolve_db_url(
     explicit: Option<&str>,
     config: Option<&seedkit_core::config::SeedKitConfig>,
@@ -0,0 +1,210 @@
+use std::io::{BufRead, IsTerminal, Write};
+use std::path::Path;
+
+use anyhow::{bail, Result};
+
+use seedkit_core::generate::plan::filter_insertion_order;
+use seedkit_core::graph::cycle::break_cycles;
+use seedkit_core::graph::dag::DependencyGraph;
+use seedkit_core::graph::topo::{topological_sort, DeferredEdge};
+use seedkit_core::lock;
+use seedkit_core::output::runs::{self, RUNS_TABLE};
+use seedkit_core::output::wipe::{self, WipeMode};
+use seedkit_core::schema::introspect::database_type_from_url;
+use seedkit_core::schema::types::{DatabaseSchema, DatabaseType};
+
+use super::generate::{introspect_schema, resolve_db_url};
+use crate::args::WipeArgs;
+
+/// Remove seeded data from the tables `seedkit generate` seeds, children
+/// before parents. The tables come from --include/--exclude, then the lock
+/// file, then seedkit.toml; the lock file's sequence offsets keep rows that
+/// were there before seeding. With --run, only that run's rows go.
+pub async fn run(args: &WipeArgs) -> Result<()> {
+    let config =
+        seedkit_core::config::read_config_with_env(Path::new("."), args.config_env.as_deref())?;
+    let db_url = resolve_db_url(args.db.as_deref(), config.as_ref())?;
+    let db_type = database_type_from_url(&db_url)?;
+    let schema = introspect_schema(&db_url, db_type).await?;
+
+    let lock_name = lock::lock_file_name(args.profile.as_deref())?;
+    let lock_path = Path::new(&lock_name);
+    let restored_lock = if lock_path.exists() {
+        Some(lock::read_lock_file(lock_path)?)
+    } else {
+        None
+    };
+
+    let polymorphic = config
+        .as_ref()
+        .map(|c| c.polymorphic_associations())
+        .unwrap_or_default();
+    let mut dep_graph = DependencyGraph::from_schema(&schema);
+    dep_graph.add_polymorphic_edges(&schema, &polymorphic);
+    let cycle_break_hints: Vec<String> = config
+        .as_ref()
+        .map(|c| c.graph.break_cycle_at.clone())
+        .unwrap_or_default();
+    let deferred = break_cycles(&mut dep_graph, &cycle_break_hints)?;
+    let insertion_order = topological_sort(&dep_graph)?;
+
+    let (include, exclude) = if !args.include.is_empty() || !args.exclude.is_empty() {
+        (args.include.clone(), args.exclude.clone())
+    } else if let Some(ref lf) = restored_lock {
+        (
+            lf.config.include_tables.clone(),
+            lf.config.exclude_tables.clone(),
+        )
+    } else {
+        let generate = config.as_ref().map(|c| &c.generate);
+        (
+            generate.and_then(|g| g.include.clone()).unwrap_or_default(),
+            generate.and_then(|g| g.exclude.clone()).unwrap_or_default(),
+        )
+    };
+    for table in include.iter().chain(&exclude) {
+        if !schema.tables.contains_key(table) {
+            bail!("Table '{}' not found in the database schema", table);
+        }
+    }
+    let tables = filter_insertion_order(&insertion_order.tables, &schema, &include, &exclude);
+
+    if let Some(ref run_id) = args.run {
+        let marker = config
+            .as_ref()
+            .and_then(|c| c.generate.run_marker.as_deref());
+        return wipe_run(args, &db_url, &schema, &tables, &deferred, run_id, marker).await;
+    }
+
+    let mode = if args.truncate {
+        WipeMode::Truncate
+    } else {
+        WipeMode::Delete
+    };
+    let offsets = restored_lock
+        .as_ref()
+        .map(|lf| lf.sequence_offsets.clone())
+        .unwrap_or_default();
+    let sqlite_sequence = match db_type {
+        DatabaseType::SQLite => wipe::sqlite_sequence_exists(&db_url).await?,
+        _ => false,
+    };
+    let statements =
+        wipe::wipe_statements(&schema, &tables, &deferred, &offsets, mode, sqlite_sequence);
+
+    if args.dry_run {
+        for statement in &statements {
+            println!("{};", statement.sql);
+        }
+        return Ok(());
+    }
+
+    if !args.yes {
+        if !std::io::stdin().is_terminal() {
+            bail!("Not wiping without confirmation: pass --yes, or --dry-run to see the SQL");
+        }
+        let kept = offsets
+            .keys()
+            .filter(|t| mode == WipeMode::Delete && tables.contains(t))
+            .count();
+        eprint!(
+            "{} {} tables in {}{}? [y/N] ",
+            if args.truncate { "Truncate" } else { "Wipe" },
+            tables.len(),
+            schema.database_name,
+            if kept > 0 {
+                format!(" (keeping rows from before seeding in {})", kept)
+            } else {
+                String::new()
+            }
+        );
+        if !confirmed()? {
+            return Ok(());
+        }
+    }
+
+    wipe::execute_wipe(&db_url, db_type, &statements).await?;
+    eprintln!(
+        "✓ {} {} tables",
+        if args.truncate { "Truncated" } else { "Wiped" },
+        tables.len()
+    );
+    Ok(())
+}
+
+/// Delete the rows of one run, by the marker column or the ID ranges
+/// recorded in the runs table.
+async fn wipe_run(
+    args: &WipeArgs,
+    db_url: &str,
+    schema: &DatabaseSchema,
+    tables: &[String],
+    deferred: &[DeferredEdge],
+    run_id: &str,
+    marker: Option<&str>,
+) -> Result<()> {
+    let recorded = runs::load_runs(db_url, schema.database_type).await?;
+    let run = recorded.iter().find(|r| r.run_id == run_id);
+    if run.is_none() && marker.is_none() {
+        if recorded.is_empty() {
+            bail!("No runs are recorded in {}", RUNS_TABLE);
+        }
+        let ids: Vec<&str> = recorded.iter().map(|r| r.run_id.as_str()).collect();
+        bail!(
+            "Run '{}' is not recorded in {} (recorded: {})",
+            run_id,
+            RUNS_TABLE,
+            ids.join(", ")
+        );
+    }
+
+    let filters = wipe::run_filters(schema, tables, run_id, run, marker);
+    for table in run.iter().flat_map(|r| &r.tables) {
+        if tables.contains(&table.table) && !filters.contains_key(&table.table) {
+            eprintln!(
+                "  {}: {} rows have no auto-increment key or run marker to find them by, left in place",
+                table.table, table.row_count
+            );
+        }
+    }
+    let statements =
+        wipe::run_wipe_statements(schema, tables, deferred, run_id, &filters, run.is_some());
+
+    if args.dry_run {
+        for statement in &statements {
+            println!("{};", statement.sql);
+        }
+        return Ok(());
+    }
+
+    if !args.yes {
+        if !std::io::stdin().is_terminal() {
+            bail!("Not wiping without confirmation: pass --yes, or --dry-run to see the SQL");
+        }
+        eprint!(
+            "Delete run {} from {} tables in {}? [y/N] ",
+            run_id,
+            filters.len(),
+            schema.database_name
+        );
+        if !confirmed()? {
+            return Ok(());
+        }
+    }
+
+    wipe::execute_wipe(db_url, schema.database_type, &statements).await?;
+    eprintln!("✓ Removed run {} from {} tables", run_id, filters.len());
+    Ok(())
+}
+
+/// Read a y/N answer to the prompt just printed.
+fn confirmed() -> Result<bool> {
+    std::io::stderr().flush()?;
+    let mut answer = String::new();
+    std::io::stdin().lock().read_line(&mut answer)?;
+    let yes = matches!(answer.trim(), "y" | "Y" | "yes");
+    if !yes {
+        eprintln!("Nothing was removed.");
+    }
+    Ok(yes)
+}
@@ -3,8 +3,9 @@ use tracing_subscriber::EnvFilter;
 
 mod args;
 mod commands;
+mod progress;
 
-use args::{Cli, Command};
+use args::{Cli, Command, ConfigCommand, SampleCommand};
 
 #[tokio::main]
 async fn main() {
@@ -28,11 +29,24 @@ async fn main() {
 
     let result = match &cli.command {
         Command::Generate(args) => commands::generate::run(args).await,
+        Command::Init(args) => commands::init::run(args).await,
         Command::Introspect(args) => commands::introspect::run(args).await,
         Command::Preview(args) => commands::preview::run(args).await,
+        Command::Classify(args) => commands::classify::run(args).await,
         Command::Check(args) => commands::check::run(args).await,
         Command::Graph(args) => commands::graph::run(args).await,
-        Command::Sample(args) => commands::sample::run(args).await,
+        Command::Sample(args) => match &args.command {
+            Some(SampleCommand::Audit(audit_args)) => commands::sample::audit(audit_args),
+            None => commands::sample::run(args).await,
+        },
+        Command::Regen(args) => commands::regen::run(args).await,
+        Command::Wipe(args) => commands::wipe::run(args).await,
+        Command::Config(args) => match &args.command {
+            ConfigCommand::Validate(validate_args) => {
+                commands::config::validate(validate_args).await
+            }
+            ConfigCommand::Schema => commands::config::schema(),
+        },
     };
 
     if let Err(err) = result {
@@ -0,0 +1,84 @@
+//! Progress bars for generating and inserting rows: one bar for the whole
+//! run, with its rate and ETA, and one under it for the table in progress.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use indicatif::{HumanCount, MultiProgress, ProgressBar, ProgressState, ProgressStyle};
+
+pub struct TableProgress {
+    overall: ProgressBar,
+    table: ProgressBar,
+    /// Each table's first row in the overall count, and its row count.
+    ranges: HashMap<String, (u64, u64)>,
+    current_table: Mutex<Option<String>>,
+}
+
+impl TableProgress {
+    /// Bars for the rows of `tables`, in the order they're worked through.
+    pub fn new(prefix: &str, message: &str, tables: &[(String, usize)]) -> Self {
+        let mut ranges = HashMap::new();
+        let mut start = 0u64;
+        for (table, rows) in tables {
+            ranges.insert(table.clone(), (start, *rows as u64));
+            start += *rows as u64;
+        }
+
+        let multi = MultiProgress::new();
+        let overall = multi.add(ProgressBar::new(start));
+        overall.set_style(
+            ProgressStyle::default_bar()
+                .template(
+                    "{spinner:.cyan} [{prefix}] {msg} {bar:40.cyan/dim} {pos}/{len} ({rate}, ETA {eta})",
+                )
+                .unwrap()
+                .with_key("rate", |state: &ProgressState, w: &mut dyn fmt::Write| {
+                    let _ = write!(w, "{} rows/s", HumanCount(state.per_sec() as u64));
+                })
+                .progress_chars("█▓░"),
+        );
+        overall.set_prefix(prefix.to_string());
+        overall.set_message(message.to_string());
+        overall.enable_steady_tick(Duration::from_millis(100));
+
+        let table = multi.add(ProgressBar::new(0));
+        table.set_style(
+            ProgressStyle::default_bar()
+                .template("      {msg:24!} {bar:40.green/dim} {pos}/{len} ({eta})")
+                .unwrap()
+                .progress_chars("█▓░"),
+        );
+
+        TableProgress {
+            overall,
+            table,
+            ranges,
+            current_table: Mutex::new(None),
+        }
+    }
+
+    /// Move to `current` rows done overall, in `table`.
+    pub fn update(&self, table: &str, current: usize) {
+        self.overall.set_position(current as u64);
+        let Some(&(start, rows)) = self.ranges.get(table) else {
+            return;
+        };
+        let mut current_table = self.current_table.lock().unwrap();
+        if current_table.as_deref() != Some(table) {
+            *current_table = Some(table.to_string());
+            self.table.reset();
+            self.table.set_length(rows);
+            self.table.set_message(table.to_string());
+        }
+        self.table
+            .set_position((current as u64).saturating_sub(start).min(rows));
+    }
+
+    /// Clear the table bar and leave `message` on the overall one.
+    pub fn finish(&self, message: String) {
+        self.table.finish_and_clear();
+        self.overall.finish_with_message(message);
+    }
+}
@@ -5,10 +5,12 @@
 
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
 use std::collections::BTreeMap;
+use std::sync::Arc;
 
 use seedkit_core::classify::semantic::SemanticType;
 use seedkit_core::generate::engine::execute_plan;
 use seedkit_core::generate::plan::*;
+use seedkit_core::generate::seed::DefaultSeedDerivation;
 use seedkit_core::sample::stats::ColumnDistribution;
 use seedkit_core::schema::types::*;
 
@@ -266,14 +268,30 @@ fn bench_value_list_strategy(c: &mut Criterion) {
                 nullable: false,
                 null_probability: 0.0,
                 check_constraints: Vec::new(),
+                max_length: None,
+                text_profile: None,
+                distinct_ratio: None,
+                temporal_profile: None,
+                sampled_fan_out: None,
             }],
             correlation_groups: Vec::new(),
+            stable: false,
+            pinned_rows: None,
+            sequence_offset: None,
+            language_mix: None,
+            row_script: None,
+            time_series: None,
+            funnel: None,
+            segments: Vec::new(),
+            copula: None,
         }],
         deferred_edges: Vec::new(),
         seed: 42,
         default_row_count: 10_000,
         base_time: chrono::Utc::now().naive_utc(),
         sequence_offset: 0,
+        seed_derivation: Arc::new(DefaultSeedDerivation),
+        table_streams: true,
     };
 
     group.throughput(Throughput::Elements(10_000));
@@ -311,19 +329,36 @@ fn bench_distribution_strategy(c: &mut Criterion) {
                         max: 1000.0,
                         mean: 49.99,
                         stddev: 25.0,
+                        quantiles: Vec::new(),
                     },
                 },
                 nullable: false,
                 null_probability: 0.0,
                 check_constraints: Vec::new(),
+                max_length: None,
+                text_profile: None,
+                distinct_ratio: None,
+                temporal_profile: None,
+                sampled_fan_out: None,
             }],
             correlation_groups: Vec::new(),
+            stable: false,
+            pinned_rows: None,
+            sequence_offset: None,
+            language_mix: None,
+            row_script: None,
+            time_series: None,
+            funnel: None,
+            segments: Vec::new(),
+            copula: None,
         }],
         deferred_edges: Vec::new(),
         seed: 42,
         default_row_count: 10_000,
         base_time: chrono::Utc::now().naive_utc(),
         sequence_offset: 0,
+        seed_derivation: Arc::new(DefaultSeedDerivation),
+        table_streams: true,
     };
 
     group.throughput(Throughput::Elements(10_000));
@@ -0,0 +1,268 @@
+//! # Lock History
+//!
+//! Walks the git history of `seedkit.lock` and compares each committed
+//! version's schema snapshot with the live schema, giving a timeline of how
+//! far every locked seed plan has drifted. Backs `seedkit check --since`.
+
+use std::path::Path;
+use std::process::Command;
+
+use serde::Serialize;
+
+use crate::check::{check_drift, DriftReport};
+use crate::error::{Result, SeedKitError};
+use crate::lock::parse_lock_file;
+use crate::lock::types::LockFile;
+use crate::schema::types::DatabaseSchema;
+
+/// A committed version of the lock file.
+#[derive(Debug, Clone)]
+pub struct LockRevision {
+    pub commit: String,
+    /// Commit date, `YYYY-MM-DD`.
+    pub date: String,
+    pub subject: String,
+    pub lock: LockFile,
+}
+
+/// Drift between one committed lock file and the live schema.
+#[derive(Debug, Clone, Serialize)]
+pub struct TimelineEntry {
+    pub commit: String,
+    pub date: String,
+    pub subject: String,
+    pub seedkit_version: String,
+    pub report: DriftReport,
+}
+
+/// Versions of `lock_path` committed after `since` (any git revision),
+/// oldest first. The version current at `since` comes first when there is
+/// one, as the timeline's baseline.
+pub fn lock_history(lock_path: &Path, since: &str) -> Result<Vec<LockRevision>> {
+    let dir = lock_path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let file = lock_path
+        .file_name()
+        .map(|f| f.to_string_lossy().into_owned())
+        .ok_or_else(|| history_error(format!("Invalid lock path {}", lock_path.display())))?;
+
+    // Commits deleting the lock file have no version of it to show
+    let format = "--format=%H%x1f%as%x1f%s";
+    let mut log = git(
+        dir,
+        &[
+            "log",
+            "-1",
+            "--diff-filter=ACMRT",
+            format,
+            since,
+            "--",
+            &file,
+        ],
+    )?;
+    log.push_str(&git(
+        dir,
+        &[
+            "log",
+            "--reverse",
+            "--diff-filter=ACMRT",
+            format,
+            &format!("{}..HEAD", since),
+            "--",
+            &file,
+        ],
+    )?);
+
+    let mut revisions = Vec::new();
+    for line in log.lines() {
+        let mut fields = line.splitn(3, '\x1f');
+        let (Some(commit), Some(date), Some(subject)) =
+            (fields.next(), fields.next(), fields.next())
+        else {
+            continue;
+        };
+        let content = git(dir, &["show", &format!("{}:./{}", commit, file)])?;
+        match parse_lock_file(&content) {
+            Ok(lock) => revisions.push(LockRevision {
+                commit: commit.to_string(),
+                date: date.to_string(),
+                subject: subject.to_string(),
+                lock,
+            }),
+            // Written by an incompatible version
+            Err(e) => tracing::warn!("Skipping {} at {}: {}", file, &commit[..7], e),
+        }
+    }
+    Ok(revisions)
+}
+
+/// Compare every revision's schema snapshot with `current`.
+pub fn drift_timeline(revisions: &[LockRevision], current: &DatabaseSchema) -> Vec<TimelineEntry> {
+    revisions
+        .iter()
+        .map(|rev| TimelineEntry {
+            commit: rev.commit.clone(),
+            date: rev.date.clone(),
+            subject: rev.subject.clone(),
+            seedkit_version: rev.lock.seedkit_version.clone(),
+            report: check_drift(&rev.lock.schema_snapshot, &rev.lock.schema_hash, current),
+        })
+        .collect()
+}
+
+/// Human-readable timeline for terminal output.
+pub fn timeline_summary(entries: &[TimelineEntry]) -> String {
+    if entries.is_empty() {
+        return "No committed versions of the lock file in this range.".to_string();
+    }
+
+    let mut lines = vec![format!(
+        "Lock history ({} version{}, oldest first, compared with the live schema):",
+        entries.len(),
+        if entries.len() == 1 { "" } else { "s" }
+    )];
+    for entry in entries {
+        let changes = change_count(&entry.report);
+        lines.push(format!(
+            "{} {} {} (seedkit {}): {}",
+            &entry.commit[..entry.commit.len().min(7)],
+            entry.date,
+            entry.subject,
+            entry.seedkit_version,
+            match changes {
+                0 => "up to date".to_string(),
+                1 => "1 change".to_string(),
+                n => format!("{} changes", n),
+            }
+        ));
+        // Skip the report's own heading line
+        for line in entry.report.summary().lines().skip(1) {
+            lines.push(format!("  {}", line));
+        }
+    }
+    lines.join("\n")
+}
+
+fn change_count(report: &DriftReport) -> usize {
+    report.new_tables.len()
+        + report.removed_tables.len()
+        + report.new_columns.len()
+        + report.removed_columns.len()
+        + report.changed_columns.len()
+}
+
+fn git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .map_err(|e| history_error(format!("Failed to run git: {}", e)))?;
+    if !output.status.success() {
+        return Err(history_error(format!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn history_error(message: String) -> SeedKitError {
+    SeedKitError::LockFile { message }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::compute_schema_hash;
+    use crate::lock::types::LockConfig;
+    use crate::lock::write_lock_file;
+    use crate::schema::types::{Column, DataType, DatabaseType, Table};
+
+    fn schema(tables: &[&str]) -> DatabaseSchema {
+        let mut schema = DatabaseSchema::new(DatabaseType::PostgreSQL, "test".to_string());
+        for name in tables {
+            let mut table = Table::new(name.to_string());
+            let id = Column::new("id".to_string(), DataType::Serial, "serial".to_string());
+            table.columns.insert("id".to_string(), id);
+            schema.tables.insert(name.to_string(), table);
+        }
+        schema
+    }
+
+    fn lock(schema: DatabaseSchema) -> LockFile {
+        LockFile::new(
+            compute_schema_hash(&schema),
+            42,
+            chrono::Utc::now().naive_utc(),
+            LockConfig {
+                default_row_count: 100,
+                table_row_overrides: Default::default(),
+                ai_enabled: false,
+                include_tables: Vec::new(),
+                exclude_tables: Vec::new(),
+                ai_classifications: None,
+                column_overrides: None,
+                locale: None,
+                table_streams: true,
+                lookup_tables: None,
+                scenario: None,
+            },
+            schema,
+        )
+    }
+
+    fn commit(dir: &Path, message: &str) {
+        let run = |args: &[&str]| {
+            let status = Command::new("git")
+                .arg("-C")
+                .arg(dir)
+                .args(["-c", "user.name=seedkit", "-c", "user.email=seedkit@test"])
+                .args(args)
+                .output()
+                .unwrap()
+                .status;
+            assert!(status.success(), "git {:?}", args);
+        };
+        run(&["add", "-A"]);
+        run(&["commit", "-q", "-m", message]);
+    }
+
+    #[test]
+    fn test_lock_history_timeline() {
+        let dir = tempfile::tempdir().unwrap();
+        git(dir.path(), &["init", "-q"]).unwrap();
+        let path = dir.path().join("seedkit.lock");
+
+        std::fs::write(dir.path().join("README"), "seed data").unwrap();
+        commit(dir.path(), "Initial commit");
+        write_lock_file(&lock(schema(&["users"])), &path).unwrap();
+        commit(dir.path(), "Lock users");
+        let since = git(dir.path(), &["rev-parse", "HEAD"]).unwrap();
+        write_lock_file(&lock(schema(&["users", "orders"])), &path).unwrap();
+        commit(dir.path(), "Lock orders");
+        std::fs::write(dir.path().join("README"), "unrelated").unwrap();
+        commit(dir.path(), "Touch README");
+        std::fs::remove_file(&path).unwrap();
+        commit(dir.path(), "Drop the lock");
+        write_lock_file(&lock(schema(&["users", "orders"])), &path).unwrap();
+        commit(dir.path(), "Restore the lock");
+
+        let revisions = lock_history(&path, since.trim()).unwrap();
+        let subjects: Vec<_> = revisions.iter().map(|r| r.subject.as_str()).collect();
+        assert_eq!(subjects, ["Lock users", "Lock orders", "Restore the lock"]);
+
+        let current = schema(&["users", "orders", "payments"]);
+        let timeline = drift_timeline(&revisions, &current);
+        assert_eq!(timeline[0].report.new_tables, ["orders", "payments"]);
+        assert_eq!(timeline[1].report.new_tables, ["payments"]);
+
+        let summary = timeline_summary(&timeline);
+        assert!(summary.contains("Lock users (seedkit"), "{}", summary);
+        assert!(summary.contains("2 changes"), "{}", summary);
+        assert!(summary.contains("  + table: payments"), "{}", summary);
+    }
+}
@@ -1,6 +1,33 @@
+pub mod history;
+pub mod pii;
+pub mod sarif;
+
 use serde::{Deserialize, Serialize};
 
-use crate::schema::types::DatabaseSchema;
+use crate::schema::types::{Column, DatabaseSchema, Index, Table};
+
+/// How much a drift item matters to the locked seed plan.
+///
+/// New tables are always benign and removed tables always breaking.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Severity {
+    /// Seeding still works, it just doesn't cover the change (a new
+    /// nullable column, a dropped constraint).
+    Benign,
+    /// Seeding with the lock file would fail or violate the schema (a
+    /// removed column, a type change, a new NOT NULL column or constraint).
+    Breaking,
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Benign => write!(f, "benign"),
+            Severity::Breaking => write!(f, "breaking"),
+        }
+    }
+}
 
 /// Result of schema drift detection.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,42 +41,131 @@ pub struct DriftReport {
 }
 
 impl DriftReport {
+    /// Severity of every drift item.
+    fn severities(&self) -> impl Iterator<Item = Severity> + '_ {
+        self.new_tables
+            .iter()
+            .map(|_| Severity::Benign)
+            .chain(self.removed_tables.iter().map(|_| Severity::Breaking))
+            .chain(self.new_columns.iter().map(|c| c.severity))
+            .chain(self.removed_columns.iter().map(|c| c.severity))
+            .chain(self.changed_columns.iter().map(|c| c.severity))
+    }
+
+    /// The most severe drift item's severity; `None` without drift.
+    pub fn severity(&self) -> Option<Severity> {
+        self.severities().max()
+    }
+
+    /// Number of breaking drift items.
+    pub fn breaking_count(&self) -> usize {
+        self.severities()
+            .filter(|s| *s == Severity::Breaking)
+            .count()
+    }
+
     /// Human-readable summary for terminal output.
     pub fn summary(&self) -> String {
         if !self.has_drift {
             return "No schema drift detected.".to_string();
         }
-
         let mut lines = vec!["Schema drift detected:".to_string()];
+        lines.extend(self.item_lines());
+        lines.push(self.verdict());
+        lines.join("\n")
+    }
+
+    /// What changed since `previous`, for following drift as it happens:
+    /// the drift items that appeared, those that were resolved and the
+    /// verdict on the drift now. `None` when the items are the same.
+    pub fn changes_since(&self, previous: &DriftReport) -> Option<String> {
+        let items = self.item_lines();
+        let previous_items = previous.item_lines();
+        let mut lines = vec!["Schema drift changed:".to_string()];
+        for item in items.iter().filter(|i| !previous_items.contains(i)) {
+            lines.push(format!("  new      {}", item.trim_start()));
+        }
+        for item in previous_items.iter().filter(|i| !items.contains(i)) {
+            lines.push(format!("  resolved {}", item.trim_start()));
+        }
+        if lines.len() == 1 {
+            return None;
+        }
+        lines.push(if self.has_drift {
+            self.verdict()
+        } else {
+            "No schema drift left.".to_string()
+        });
+        Some(lines.join("\n"))
+    }
+
+    /// One line per drift item.
+    fn item_lines(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        let mark = |severity: Severity| match severity {
+            Severity::Breaking => " [breaking]",
+            Severity::Benign => "",
+        };
 
         for t in &self.new_tables {
             lines.push(format!("  + table: {}", t));
         }
         for t in &self.removed_tables {
-            lines.push(format!("  - table: {}", t));
+            lines.push(format!("  - table: {}{}", t, mark(Severity::Breaking)));
         }
         for c in &self.new_columns {
-            lines.push(format!("  + column: {}.{}", c.table, c.column));
+            lines.push(format!(
+                "  + column: {}.{}{}",
+                c.table,
+                c.column,
+                mark(c.severity)
+            ));
         }
         for c in &self.removed_columns {
-            lines.push(format!("  - column: {}.{}", c.table, c.column));
+            lines.push(format!(
+                "  - column: {}.{}{}",
+                c.table,
+                c.column,
+                mark(c.severity)
+            ));
         }
         for c in &self.changed_columns {
             if c.column.is_empty() {
                 // Table-level constraint change (FK, unique, check)
                 lines.push(format!(
-                    "  ~ {}: {} ({})",
-                    c.table, c.change_type, c.details
+                    "  ~ {}: {} ({}){}",
+                    c.table,
+                    c.change_type,
+                    c.details,
+                    mark(c.severity)
                 ));
             } else {
                 lines.push(format!(
-                    "  ~ {}.{}: {} ({})",
-                    c.table, c.column, c.change_type, c.details
+                    "  ~ {}.{}: {} ({}){}",
+                    c.table,
+                    c.column,
+                    c.change_type,
+                    c.details,
+                    mark(c.severity)
                 ));
             }
         }
 
-        lines.join("\n")
+        lines
+    }
+
+    /// Whether the lock file still seeds the schema, given the drift.
+    fn verdict(&self) -> String {
+        let breaking = self.breaking_count();
+        if breaking == 0 {
+            "All changes are benign: the lock file still seeds this schema.".to_string()
+        } else {
+            format!(
+                "{} breaking change{}: seeding with the lock file would fail.",
+                breaking,
+                if breaking == 1 { "" } else { "s" }
+            )
+        }
     }
 }
 
@@ -57,6 +173,7 @@ impl DriftReport {
 pub struct ColumnRef {
     pub table: String,
     pub column: String,
+    pub severity: Severity,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -65,6 +182,7 @@ pub struct ColumnChange {
     pub column: String,
     pub change_type: String,
     pub details: String,
+    pub severity: Severity,
 }
 
 /// Compute the schema hash for drift detection.
@@ -83,37 +201,62 @@ pub fn compute_schema_hash(schema: &DatabaseSchema) -> String {
     format!("{:x}", hasher.finalize())
 }
 
+/// Hash a single table's definition, normalized the same way as
+/// [`compute_schema_hash`]. Used to tell whether rows recorded for a stable
+/// table still fit its current shape.
+pub fn compute_table_hash(table: &Table) -> String {
+    use sha2::{Digest, Sha256};
+    let mut normalized = table.clone();
+    normalize_table(&mut normalized);
+    let serialized = serde_json::to_string(&normalized).unwrap_or_default();
+    let mut hasher = Sha256::new();
+    hasher.update(serialized.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
 /// Clone the schema and sort all non-deterministic arrays so that
 /// serialization is order-independent.
 fn normalize_for_hash(schema: &DatabaseSchema) -> DatabaseSchema {
     let mut normalized = schema.clone();
-
     for table in normalized.tables.values_mut() {
-        // Sort foreign keys by source columns (deterministic key)
-        table.foreign_keys.sort_by(|a, b| {
-            a.source_columns
-                .cmp(&b.source_columns)
-                .then_with(|| a.referenced_table.cmp(&b.referenced_table))
-        });
-
-        // Sort unique constraints by column list
-        table
-            .unique_constraints
-            .sort_by(|a, b| a.columns.cmp(&b.columns));
-
-        // Sort check constraints by expression (the stable identifier)
-        table
-            .check_constraints
-            .sort_by(|a, b| a.expression.cmp(&b.expression));
+        normalize_table(table);
     }
-
     normalized
 }
 
+fn normalize_table(table: &mut Table) {
+    // Comments document columns without changing their shape, and default
+    // expressions and indexes don't change what can be inserted (their
+    // drift is diffed separately)
+    for column in table.columns.values_mut() {
+        column.comment = None;
+        column.default_expression = None;
+    }
+    table.indexes.clear();
+
+    // Sort foreign keys by source columns (deterministic key)
+    table.foreign_keys.sort_by(|a, b| {
+        a.source_columns
+            .cmp(&b.source_columns)
+            .then_with(|| a.referenced_table.cmp(&b.referenced_table))
+    });
+
+    // Sort unique constraints by column list
+    table
+        .unique_constraints
+        .sort_by(|a, b| a.columns.cmp(&b.columns));
+
+    // Sort check constraints by expression (the stable identifier)
+    table
+        .check_constraints
+        .sort_by(|a, b| a.expression.cmp(&b.expression));
+}
+
 /// Compare the lock file's stored schema snapshot against the current live schema.
 ///
-/// Uses a fast-path hash comparison first: if the hashes match, returns
-/// immediately with no drift. When they differ, delegates to
+/// Uses a fast-path hash comparison first: if the hashes match, only
+/// default expressions and indexes (which the hash leaves out) are
+/// compared. When they differ, delegates to
 /// `check_drift_detailed` to produce an actionable column-level diff.
 ///
 /// The lock file stores the full `DatabaseSchema` snapshot (like
@@ -126,15 +269,36 @@ pub fn check_drift(
 ) -> DriftReport {
     let current_hash = compute_schema_hash(current_schema);
 
-    // Fast path: if hashes match, no drift.
+    // Fast path: if hashes match, only default expressions and indexes can
+    // have drifted.
     if current_hash == schema_hash {
+        let mut changed_columns = Vec::new();
+        for (table_name, current_table) in &current_schema.tables {
+            if let Some(old_table) = schema_snapshot.tables.get(table_name) {
+                for (col_name, current_col) in &current_table.columns {
+                    if let Some(change) = old_table
+                        .columns
+                        .get(col_name)
+                        .and_then(|old_col| default_change(table_name, old_col, current_col))
+                    {
+                        changed_columns.push(change);
+                    }
+                }
+                diff_indexes(
+                    schema_snapshot,
+                    old_table,
+                    current_table,
+                    &mut changed_columns,
+                );
+            }
+        }
         return DriftReport {
-            has_drift: false,
+            has_drift: !changed_columns.is_empty(),
             new_tables: Vec::new(),
             removed_tables: Vec::new(),
             new_columns: Vec::new(),
             removed_columns: Vec::new(),
-            changed_columns: Vec::new(),
+            changed_columns,
         };
     }
 
@@ -174,12 +338,18 @@ pub fn check_drift_detailed(old: &DatabaseSchema, current: &DatabaseSchema) -> D
             None => continue, // Already captured as new_tables
         };
 
-        // New columns
-        for col_name in current_table.columns.keys() {
+        // New columns: inserts without them only fail when they're required
+        for (col_name, col) in &current_table.columns {
             if !old_table.columns.contains_key(col_name) {
+                let required = !col.nullable && !col.has_default && !col.is_auto_increment;
                 new_columns.push(ColumnRef {
                     table: table_name.clone(),
                     column: col_name.clone(),
+                    severity: if required {
+                        Severity::Breaking
+                    } else {
+                        Severity::Benign
+                    },
                 });
             }
         }
@@ -190,11 +360,12 @@ pub fn check_drift_detailed(old: &DatabaseSchema, current: &DatabaseSchema) -> D
                 removed_columns.push(ColumnRef {
                     table: table_name.clone(),
                     column: col_name.clone(),
+                    severity: Severity::Breaking,
                 });
             }
         }
 
-        // Changed columns (type or nullability)
+        // Changed columns (type, nullability, default, auto-increment)
         for (col_name, current_col) in &current_table.columns {
             if let Some(old_col) = old_table.columns.get(col_name) {
                 if current_col.data_type != old_col.data_type {
@@ -203,6 +374,7 @@ pub fn check_drift_detailed(old: &DatabaseSchema, current: &DatabaseSchema) -> D
                         column: col_name.clone(),
                         change_type: "type_changed".to_string(),
                         details: format!("{} → {}", old_col.data_type, current_col.data_type),
+                        severity: Severity::Breaking,
                     });
                 }
                 if current_col.nullable != old_col.nullable {
@@ -214,6 +386,29 @@ pub fn check_drift_detailed(old: &DatabaseSchema, current: &DatabaseSchema) -> D
                             "nullable: {} → {}",
                             old_col.nullable, current_col.nullable
                         ),
+                        // Generated NULLs only break a column that became NOT NULL
+                        severity: if current_col.nullable {
+                            Severity::Benign
+                        } else {
+                            Severity::Breaking
+                        },
+                    });
+                }
+                if let Some(change) = default_change(table_name, old_col, current_col) {
+                    changed_columns.push(change);
+                }
+                if current_col.is_auto_increment != old_col.is_auto_increment {
+                    changed_columns.push(ColumnChange {
+                        table: table_name.clone(),
+                        column: col_name.clone(),
+                        change_type: "auto_increment_changed".to_string(),
+                        details: format!(
+                            "auto_increment: {} → {}",
+                            old_col.is_auto_increment, current_col.is_auto_increment
+                        ),
+                        // Either way, seeded IDs are no longer assigned the
+                        // way they were when the lock file was written
+                        severity: Severity::Breaking,
                     });
                 }
             }
@@ -250,6 +445,7 @@ pub fn check_drift_detailed(old: &DatabaseSchema, current: &DatabaseSchema) -> D
                 column: String::new(),
                 change_type: "fk_added".to_string(),
                 details: format!("FOREIGN KEY {}", added),
+                severity: Severity::Breaking,
             });
         }
         for removed in old_fks.difference(&cur_fks) {
@@ -258,6 +454,7 @@ pub fn check_drift_detailed(old: &DatabaseSchema, current: &DatabaseSchema) -> D
                 column: String::new(),
                 change_type: "fk_removed".to_string(),
                 details: format!("FOREIGN KEY {}", removed),
+                severity: Severity::Benign,
             });
         }
 
@@ -278,6 +475,7 @@ pub fn check_drift_detailed(old: &DatabaseSchema, current: &DatabaseSchema) -> D
                 column: String::new(),
                 change_type: "unique_added".to_string(),
                 details: format!("UNIQUE {}", added),
+                severity: Severity::Breaking,
             });
         }
         for removed in old_uqs.difference(&cur_uqs) {
@@ -286,6 +484,7 @@ pub fn check_drift_detailed(old: &DatabaseSchema, current: &DatabaseSchema) -> D
                 column: String::new(),
                 change_type: "unique_removed".to_string(),
                 details: format!("UNIQUE {}", removed),
+                severity: Severity::Benign,
             });
         }
 
@@ -306,6 +505,7 @@ pub fn check_drift_detailed(old: &DatabaseSchema, current: &DatabaseSchema) -> D
                 column: String::new(),
                 change_type: "check_added".to_string(),
                 details: format!("CHECK ({})", added),
+                severity: Severity::Breaking,
             });
         }
         for removed in old_cks.difference(&cur_cks) {
@@ -314,8 +514,11 @@ pub fn check_drift_detailed(old: &DatabaseSchema, current: &DatabaseSchema) -> D
                 column: String::new(),
                 change_type: "check_removed".to_string(),
                 details: format!("CHECK ({})", removed),
+                severity: Severity::Benign,
             });
         }
+
+        diff_indexes(old, old_table, current_table, &mut changed_columns);
     }
 
     let has_drift = !new_tables.is_empty()
@@ -334,6 +537,96 @@ pub fn check_drift_detailed(old: &DatabaseSchema, current: &DatabaseSchema) -> D
     }
 }
 
+/// A change in whether a column has a server default, or in its
+/// expression. Seeding writes every column it doesn't leave to the
+/// database, so default drift is benign.
+///
+/// Lock files written before default expressions were introspected only
+/// record whether there is one, so only that is compared for them.
+fn default_change(
+    table_name: &str,
+    old_col: &Column,
+    current_col: &Column,
+) -> Option<ColumnChange> {
+    let expression_changed = old_col.default_expression.is_some()
+        && old_col.default_expression != current_col.default_expression;
+    if old_col.has_default == current_col.has_default && !expression_changed {
+        return None;
+    }
+    let describe = |col: &Column| match (col.has_default, &col.default_expression) {
+        (false, _) => "none".to_string(),
+        (true, Some(expression)) => expression.clone(),
+        (true, None) => "set".to_string(),
+    };
+    Some(ColumnChange {
+        table: table_name.to_string(),
+        column: current_col.name.clone(),
+        change_type: "default_changed".to_string(),
+        details: format!("default: {} → {}", describe(old_col), describe(current_col)),
+        severity: Severity::Benign,
+    })
+}
+
+/// Diff the non-unique indexes of a table by name. Index drift never
+/// breaks seeding, so it is always benign.
+///
+/// Lock files written before indexes were introspected have none in their
+/// snapshot; rather than reporting every index as added, they are skipped.
+fn diff_indexes(
+    old_schema: &DatabaseSchema,
+    old_table: &Table,
+    current_table: &Table,
+    changes: &mut Vec<ColumnChange>,
+) {
+    if old_schema.tables.values().all(|t| t.indexes.is_empty()) {
+        return;
+    }
+    let describe = |index: &Index| match index.method {
+        Some(ref method) => format!("({}) USING {}", index.columns.join(", "), method),
+        None => format!("({})", index.columns.join(", ")),
+    };
+    let mut push = |change_type: &str, details: String| {
+        changes.push(ColumnChange {
+            table: current_table.name.clone(),
+            column: String::new(),
+            change_type: change_type.to_string(),
+            details,
+            severity: Severity::Benign,
+        });
+    };
+
+    for index in &current_table.indexes {
+        match old_table.indexes.iter().find(|i| i.name == index.name) {
+            None => push(
+                "index_added",
+                format!("INDEX {} {}", index.name, describe(index)),
+            ),
+            Some(old_index) if old_index != index => push(
+                "index_changed",
+                format!(
+                    "INDEX {} {} → {}",
+                    index.name,
+                    describe(old_index),
+                    describe(index)
+                ),
+            ),
+            Some(_) => {}
+        }
+    }
+    for old_index in &old_table.indexes {
+        if !current_table
+            .indexes
+            .iter()
+            .any(|i| i.name == old_index.name)
+        {
+            push(
+                "index_removed",
+                format!("INDEX {} {}", old_index.name, describe(old_index)),
+            );
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -430,6 +723,121 @@ mod tests {
         assert_eq!(report.changed_columns[0].change_type, "nullable_changed");
     }
 
+    #[test]
+    fn test_default_and_auto_increment_drift() {
+        let mut old = make_schema(vec![(
+            "users",
+            vec![
+                ("id", DataType::Integer, false),
+                ("status", DataType::VarChar, false),
+                ("created_at", DataType::Timestamp, false),
+            ],
+        )]);
+        {
+            let created_at = &mut old.tables["users"].columns["created_at"];
+            created_at.has_default = true;
+            created_at.default_expression = Some("now()".to_string());
+        }
+
+        let mut current = old.clone();
+        {
+            let users = &mut current.tables["users"];
+            users.columns["id"].is_auto_increment = true;
+            users.columns["status"].has_default = true;
+            users.columns["status"].default_expression = Some("'active'".to_string());
+        }
+        let report = check_drift_detailed(&old, &current);
+        let changes: Vec<(&str, &str, Severity)> = report
+            .changed_columns
+            .iter()
+            .map(|c| (c.change_type.as_str(), c.details.as_str(), c.severity))
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                (
+                    "auto_increment_changed",
+                    "auto_increment: false → true",
+                    Severity::Breaking
+                ),
+                (
+                    "default_changed",
+                    "default: none → 'active'",
+                    Severity::Benign
+                ),
+            ]
+        );
+
+        // Expressions stay out of the hash but are still compared
+        let mut current = old.clone();
+        current.tables["users"].columns["created_at"].default_expression =
+            Some("CURRENT_TIMESTAMP".to_string());
+        let report = check_drift(&old, &compute_schema_hash(&old), &current);
+        assert_eq!(report.changed_columns.len(), 1);
+        assert_eq!(
+            report.changed_columns[0].details,
+            "default: now() → CURRENT_TIMESTAMP"
+        );
+
+        // Snapshots without expressions only record that there is a default
+        old.tables["users"].columns["created_at"].default_expression = None;
+        assert!(!check_drift_detailed(&old, &current).has_drift);
+    }
+
+    #[test]
+    fn test_index_drift() {
+        let index = |name: &str, columns: &[&str]| Index {
+            name: name.to_string(),
+            columns: columns.iter().map(|c| c.to_string()).collect(),
+            method: Some("btree".to_string()),
+        };
+        let mut old = make_schema(vec![(
+            "users",
+            vec![
+                ("id", DataType::Serial, false),
+                ("email", DataType::VarChar, false),
+                ("name", DataType::VarChar, false),
+            ],
+        )]);
+        let mut current = old.clone();
+
+        // Snapshots without indexes predate their introspection
+        current.tables["users"].indexes = vec![index("users_email_idx", &["email"])];
+        assert!(!check_drift(&old, &compute_schema_hash(&old), &current).has_drift);
+
+        old.tables["users"].indexes = vec![
+            index("users_email_idx", &["email"]),
+            index("users_name_idx", &["name"]),
+        ];
+        current.tables["users"].indexes = vec![
+            index("users_email_idx", &["email", "name"]),
+            index("users_created_idx", &["id"]),
+        ];
+        // Indexes stay out of the hash, so lock files still replay
+        let hash = compute_schema_hash(&old);
+        assert_eq!(hash, compute_schema_hash(&current));
+
+        let report = check_drift(&old, &hash, &current);
+        assert!(report.has_drift);
+        let changes: Vec<(&str, &str)> = report
+            .changed_columns
+            .iter()
+            .map(|c| (c.change_type.as_str(), c.details.as_str()))
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                (
+                    "index_changed",
+                    "INDEX users_email_idx (email) USING btree → (email, name) USING btree"
+                ),
+                ("index_added", "INDEX users_created_idx (id) USING btree"),
+                ("index_removed", "INDEX users_name_idx (name) USING btree"),
+            ]
+        );
+        assert_eq!(report.severity(), Some(Severity::Benign));
+    }
+
     #[test]
     fn test_schema_hash_deterministic() {
         let schema = make_schema(vec![("users", vec![("id", DataType::Serial, false)])]);
@@ -460,6 +868,7 @@ mod tests {
             new_columns: vec![ColumnRef {
                 table: "users".into(),
                 column: "email".into(),
+                severity: Severity::Breaking,
             }],
             removed_columns: Vec::new(),
             changed_columns: Vec::new(),
@@ -467,7 +876,76 @@ mod tests {
         let s = report.summary();
         assert!(s.contains("+ table: posts"));
         assert!(s.contains("- table: legacy"));
-        assert!(s.contains("+ column: users.email"));
+        assert!(s.contains("+ column: users.email [breaking]"));
+        assert!(s.contains("2 breaking changes"), "{}", s);
+    }
+
+    #[test]
+    fn test_drift_severity() {
+        let old = make_schema(vec![(
+            "users",
+            vec![
+                ("id", DataType::Serial, false),
+                ("name", DataType::VarChar, false),
+            ],
+        )]);
+
+        // A new nullable column and a relaxed NOT NULL are benign
+        let benign = make_schema(vec![(
+            "users",
+            vec![
+                ("id", DataType::Serial, false),
+                ("name", DataType::VarChar, true),
+                ("bio", DataType::Text, true),
+            ],
+        )]);
+        let report = check_drift_detailed(&old, &benign);
+        assert_eq!(report.severity(), Some(Severity::Benign));
+        assert!(report.summary().contains("All changes are benign"));
+
+        // A new required column and a type change are breaking
+        let breaking = make_schema(vec![(
+            "users",
+            vec![
+                ("id", DataType::BigInt, false),
+                ("name", DataType::VarChar, false),
+                ("email", DataType::VarChar, false),
+            ],
+        )]);
+        let report = check_drift_detailed(&old, &breaking);
+        assert_eq!(report.severity(), Some(Severity::Breaking));
+        assert_eq!(report.breaking_count(), 2);
+
+        assert_eq!(check_drift_detailed(&old, &old).severity(), None);
+    }
+
+    #[test]
+    fn test_changes_since() {
+        let old = make_schema(vec![("users", vec![("id", DataType::Serial, false)])]);
+        let posts = make_schema(vec![
+            ("users", vec![("id", DataType::Serial, false)]),
+            ("posts", vec![("id", DataType::Serial, false)]),
+        ]);
+        let comments = make_schema(vec![
+            ("users", vec![("id", DataType::Serial, false)]),
+            ("comments", vec![("id", DataType::Serial, false)]),
+        ]);
+        let first = check_drift_detailed(&old, &posts);
+        let second = check_drift_detailed(&old, &comments);
+
+        assert_eq!(first.changes_since(&first), None);
+        let changes = second.changes_since(&first).unwrap();
+        assert!(
+            changes.contains("  new      + table: comments"),
+            "{}",
+            changes
+        );
+        assert!(changes.contains("  resolved + table: posts"), "{}", changes);
+        assert!(!changes.contains("users"), "{}", changes);
+
+        let none = check_drift_detailed(&old, &old);
+        let changes = none.changes_since(&second).unwrap();
+        assert!(changes.ends_with("No schema drift left."), "{}", changes);
     }
 
     // --- Heavy lockfile: check_drift with schema snapshot ---
@@ -0,0 +1,230 @@
+//! # PII Report
+//!
+//! `seedkit check --pii` lists the columns of a schema that hold personal
+//! data, for compliance sign-off before production is sampled:
+//!
+//! - a column is PII when it is classified as a PII semantic type (names,
+//!   email, phone, address, birth date, credentials...) or its name matches
+//!   the patterns `seedkit sample` masks (which also cover SSNs, passports
+//!   and tax IDs);
+//! - **masked** columns are the ones sample masking catches by name; a PII
+//!   column that isn't would have its production values sampled verbatim;
+//! - with a profiles file, the report also says what sampling kept of each
+//!   column.
+//!
+//! Unmasked PII columns are best kept out of sampling (`seedkit sample
+//! --tables`), or their values stripped from the profiles before they are
+//! committed (`seedkit sample audit --strip`).
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::classify::semantic::SemanticType;
+use crate::sample::mask::is_pii_column;
+use crate::sample::stats::{ColumnDistribution, DistributionProfile};
+use crate::schema::types::DatabaseSchema;
+
+/// What a profiles file keeps of a column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Sampled {
+    /// Individual values (categorical values, text models).
+    Values,
+    /// Aggregates only (ranges, histograms, shapes).
+    Aggregates,
+}
+
+/// A column holding personal data.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PiiColumn {
+    pub table: String,
+    pub column: String,
+    pub semantic_type: SemanticType,
+    /// Whether `seedkit sample` masks the column's values.
+    pub masked: bool,
+    /// What the profiles file keeps of it; `None` when it has nothing or
+    /// no profiles were checked.
+    pub sampled: Option<Sampled>,
+}
+
+/// The PII columns of a schema.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PiiReport {
+    pub columns: Vec<PiiColumn>,
+    /// Profiles file the report checked, if any.
+    pub profiles: Option<String>,
+}
+
+impl PiiReport {
+    /// PII columns `seedkit sample` doesn't mask.
+    pub fn unmasked(&self) -> impl Iterator<Item = &PiiColumn> {
+        self.columns.iter().filter(|c| !c.masked)
+    }
+
+    /// Human-readable summary for terminal output.
+    pub fn summary(&self) -> String {
+        if self.columns.is_empty() {
+            return "No PII columns found.".to_string();
+        }
+        let mut lines = vec![format!("{} PII columns:", self.columns.len())];
+        for c in &self.columns {
+            let mut notes = vec![if c.masked {
+                "masked when sampled"
+            } else {
+                "NOT masked when sampled"
+            }];
+            match (c.sampled, &self.profiles) {
+                (Some(Sampled::Values), Some(_)) if !c.masked => {
+                    notes.push("profiles hold production values")
+                }
+                (Some(Sampled::Values), Some(_)) => notes.push("profiles hold masked values"),
+                (Some(Sampled::Aggregates), Some(_)) => notes.push("profiles hold aggregates"),
+                _ => {}
+            }
+            lines.push(format!(
+                "  {} {}.{} ({}): {}",
+                if c.masked { "✓" } else { "✗" },
+                c.table,
+                c.column,
+                c.semantic_type,
+                notes.join(", ")
+            ));
+        }
+        let unmasked = self.unmasked().count();
+        if unmasked > 0 {
+            lines.push(format!(
+                "{} PII columns would be sampled unmasked; keep their tables out of \
+                 `seedkit sample --tables` or strip them with `seedkit sample audit --strip`.",
+                unmasked
+            ));
+        }
+        lines.join("\n")
+    }
+}
+
+/// Find the PII columns of `schema`, and what `profiles` (a profiles
+/// file's path and contents) keep of them.
+pub fn pii_report(
+    schema: &DatabaseSchema,
+    classifications: &BTreeMap<(String, String), SemanticType>,
+    profiles: Option<(&str, &[DistributionProfile])>,
+) -> PiiReport {
+    let mut columns = Vec::new();
+    for (table_name, table) in &schema.tables {
+        let profile = profiles
+            .and_then(|(_, profiles)| profiles.iter().find(|p| p.table_name == *table_name));
+        for col_name in table.columns.keys() {
+            let semantic_type = classifications
+                .get(&(table_name.clone(), col_name.clone()))
+                .copied()
+                .unwrap_or(SemanticType::Unknown);
+            let masked = is_pii_column(col_name);
+            if !semantic_type.is_pii() && !masked {
+                continue;
+            }
+            let sampled = profile.and_then(|p| {
+                let values = p.column_distributions.get(col_name).map(|dist| match dist {
+                    ColumnDistribution::Categorical { .. } | ColumnDistribution::Text { .. } => {
+                        Sampled::Values
+                    }
+                    _ => Sampled::Aggregates,
+                });
+                values.or(p.text_profiles.get(col_name).map(|_| Sampled::Aggregates))
+            });
+            columns.push(PiiColumn {
+                table: table_name.clone(),
+                column: col_name.clone(),
+                semantic_type,
+                masked,
+                sampled,
+            });
+        }
+    }
+    PiiReport {
+        columns,
+        profiles: profiles.map(|(path, _)| path.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::classify::rules::classify_schema;
+    use crate::schema::types::{Column, DataType, DatabaseType, Table};
+    use std::collections::HashMap;
+
+    fn schema() -> DatabaseSchema {
+        let mut schema = DatabaseSchema::new(DatabaseType::PostgreSQL, "app".to_string());
+        let mut table = Table::new("customers".to_string());
+        for name in ["id", "email", "first_name", "ssn", "status", "login_ip"] {
+            table.columns.insert(
+                name.to_string(),
+                Column::new(name.to_string(), DataType::VarChar, "varchar".to_string()),
+            );
+        }
+        schema.tables.insert("customers".to_string(), table);
+        schema
+    }
+
+    #[test]
+    fn test_pii_report() {
+        let schema = schema();
+        let mut classifications = classify_schema(&schema);
+        classifications.insert(
+            ("customers".to_string(), "login_ip".to_string()),
+            SemanticType::IpAddress,
+        );
+        let profile = DistributionProfile {
+            table_name: "customers".to_string(),
+            row_count: 100,
+            unique_pairs: Vec::new(),
+            text_profiles: HashMap::new(),
+            null_rates: HashMap::new(),
+            distinct_counts: HashMap::new(),
+            correlations: Vec::new(),
+            temporal_profiles: HashMap::new(),
+            fan_outs: HashMap::new(),
+            column_distributions: HashMap::from([(
+                "login_ip".to_string(),
+                ColumnDistribution::Categorical {
+                    values: vec![("10.0.0.1".to_string(), 1.0)],
+                },
+            )]),
+        };
+
+        let report = pii_report(
+            &schema,
+            &classifications,
+            Some(("seedkit.distributions.json", &[profile])),
+        );
+        let flagged: Vec<(&str, bool, Option<Sampled>)> = report
+            .columns
+            .iter()
+            .map(|c| (c.column.as_str(), c.masked, c.sampled))
+            .collect();
+        assert_eq!(
+            flagged,
+            vec![
+                ("email", true, None),
+                ("first_name", true, None),
+                ("ssn", true, None),
+                ("login_ip", false, Some(Sampled::Values)),
+            ]
+        );
+        assert_eq!(report.unmasked().count(), 1);
+        let summary = report.summary();
+        assert!(
+            summary.contains("✗ customers.login_ip (IpAddress): NOT masked when sampled, profiles hold production values"),
+            "{}",
+            summary
+        );
+
+        let empty = pii_report(
+            &DatabaseSchema::new(DatabaseType::SQLite, "x".into()),
+            &classifications,
+            None,
+        );
+        assert_eq!(empty.summary(), "No PII columns found.");
+    }
+}
@@ -0,0 +1,189 @@
+//! # SARIF Drift Reports
+//!
+//! `seedkit check --format sarif` writes a drift report as SARIF 2.1.0, the
+//! static-analysis format code-scanning tools and review bots read. Every
+//! drift item is one result located at the lock file, with the drifted
+//! table or column as its logical location: breaking items are errors,
+//! benign ones warnings.
+
+use serde_json::{json, Value};
+
+use crate::check::{DriftReport, Severity};
+
+/// SARIF version written.
+pub const SARIF_VERSION: &str = "2.1.0";
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// One drift item, flattened for SARIF.
+struct DriftItem {
+    rule_id: String,
+    /// `table` or `table.column`.
+    location: String,
+    message: String,
+    severity: Severity,
+}
+
+fn drift_items(report: &DriftReport) -> Vec<DriftItem> {
+    let mut items = Vec::new();
+    for t in &report.new_tables {
+        items.push(DriftItem {
+            rule_id: "table_added".to_string(),
+            location: t.clone(),
+            message: format!("Table {} is not in the lock file", t),
+            severity: Severity::Benign,
+        });
+    }
+    for t in &report.removed_tables {
+        items.push(DriftItem {
+            rule_id: "table_removed".to_string(),
+            location: t.clone(),
+            message: format!("Table {} in the lock file no longer exists", t),
+            severity: Severity::Breaking,
+        });
+    }
+    for c in &report.new_columns {
+        items.push(DriftItem {
+            rule_id: "column_added".to_string(),
+            location: format!("{}.{}", c.table, c.column),
+            message: format!("Column {}.{} is not in the lock file", c.table, c.column),
+            severity: c.severity,
+        });
+    }
+    for c in &report.removed_columns {
+        items.push(DriftItem {
+            rule_id: "column_removed".to_string(),
+            location: format!("{}.{}", c.table, c.column),
+            message: format!(
+                "Column {}.{} in the lock file no longer exists",
+                c.table, c.column
+            ),
+            severity: c.severity,
+        });
+    }
+    for c in &report.changed_columns {
+        let location = if c.column.is_empty() {
+            c.table.clone()
+        } else {
+            format!("{}.{}", c.table, c.column)
+        };
+        items.push(DriftItem {
+            rule_id: c.change_type.clone(),
+            message: format!("{}: {} ({})", location, c.change_type, c.details),
+            location,
+            severity: c.severity,
+        });
+    }
+    items
+}
+
+/// The SARIF log of `report`, with results located in `lock_uri`.
+pub fn drift_sarif(report: &DriftReport, lock_uri: &str) -> Value {
+    let items = drift_items(report);
+
+    let mut rule_ids: Vec<&str> = items.iter().map(|i| i.rule_id.as_str()).collect();
+    rule_ids.sort_unstable();
+    rule_ids.dedup();
+    let rules: Vec<Value> = rule_ids
+        .iter()
+        .map(|id| {
+            json!({
+                "id": id,
+                "shortDescription": { "text": format!("Schema drift: {}", id.replace('_', " ")) },
+            })
+        })
+        .collect();
+
+    let results: Vec<Value> = items
+        .iter()
+        .map(|item| {
+            json!({
+                "ruleId": item.rule_id,
+                "level": match item.severity {
+                    Severity::Breaking => "error",
+                    Severity::Benign => "warning",
+                },
+                "message": { "text": item.message },
+                "properties": { "severity": item.severity },
+                "locations": [{
+                    "physicalLocation": { "artifactLocation": { "uri": lock_uri } },
+                    "logicalLocations": [{
+                        "fullyQualifiedName": item.location,
+                        "kind": if item.location.contains('.') { "member" } else { "type" },
+                    }],
+                }],
+            })
+        })
+        .collect();
+
+    json!({
+        "$schema": SARIF_SCHEMA,
+        "version": SARIF_VERSION,
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "seedkit",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": "https://github.com/kclaka/seedkit",
+                    "rules": rules,
+                },
+            },
+            "results": results,
+        }],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check::{ColumnChange, ColumnRef};
+
+    #[test]
+    fn test_drift_sarif() {
+        let report = DriftReport {
+            has_drift: true,
+            new_tables: vec!["posts".to_string()],
+            removed_tables: Vec::new(),
+            new_columns: Vec::new(),
+            removed_columns: vec![ColumnRef {
+                table: "users".into(),
+                column: "name".into(),
+                severity: Severity::Breaking,
+            }],
+            changed_columns: vec![ColumnChange {
+                table: "orders".into(),
+                column: String::new(),
+                change_type: "fk_removed".into(),
+                details: "FOREIGN KEY (user_id) -> users(id)".into(),
+                severity: Severity::Benign,
+            }],
+        };
+        let sarif = drift_sarif(&report, "seedkit.lock");
+        assert_eq!(sarif["version"], "2.1.0");
+
+        let run = &sarif["runs"][0];
+        let rules: Vec<&str> = run["tool"]["driver"]["rules"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|r| r["id"].as_str().unwrap())
+            .collect();
+        assert_eq!(rules, vec!["column_removed", "fk_removed", "table_added"]);
+
+        let results = run["results"].as_array().unwrap();
+        assert_eq!(results.len(), 3);
+        let removed = &results[1];
+        assert_eq!(removed["ruleId"], "column_removed");
+        assert_eq!(removed["level"], "error");
+        assert_eq!(
+            removed["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+            "seedkit.lock"
+        );
+        assert_eq!(
+            removed["locations"][0]["logicalLocations"][0]["fullyQualifiedName"],
+            "users.name"
+        );
+        assert_eq!(results[2]["level"], "warning");
+        assert_eq!(results[2]["properties"]["severity"], "benign");
+    }
+}
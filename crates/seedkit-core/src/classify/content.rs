@@ -0,0 +1,194 @@
+//! # Content-Based Classification
+//!
+//! Column names like `ref`, `val2` or `contact` say little about what they
+//! hold. When sampled distribution profiles are loaded (`--subset`), string
+//! columns the rules left `Unknown` are classified from their sampled
+//! values instead: if every value looks like an email address, the column
+//! becomes `Email`; if every value parses as a UUID, `Uuid`; and so on for
+//! URLs, IP addresses, hex colors, phone numbers and personal names.
+//!
+//! Only categorical values are looked at, so columns whose values sampling
+//! masked or never kept stay as classified.
+
+use std::collections::BTreeMap;
+
+use crate::classify::semantic::SemanticType;
+use crate::sample::scan::{detect_pii, PiiKind};
+use crate::sample::stats::{ColumnDistribution, DistributionProfile};
+use crate::schema::types::DatabaseSchema;
+
+/// Fewest sampled values a column needs to be classified from them.
+pub const MIN_SAMPLED_VALUES: usize = 2;
+
+/// The semantic type a single value looks like, if any.
+pub fn classify_value(value: &str) -> Option<SemanticType> {
+    let value = value.trim();
+    if value.len() == 36 && uuid::Uuid::try_parse(value).is_ok() {
+        return Some(SemanticType::Uuid);
+    }
+    if (value.starts_with("http://") || value.starts_with("https://"))
+        && !value.contains(char::is_whitespace)
+    {
+        return Some(SemanticType::Url);
+    }
+    if value.parse::<std::net::IpAddr>().is_ok() {
+        return Some(SemanticType::IpAddress);
+    }
+    if let Some(hex) = value.strip_prefix('#') {
+        if matches!(hex.len(), 3 | 6) && hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Some(SemanticType::HexColor);
+        }
+    }
+    match detect_pii(value)? {
+        PiiKind::Email => Some(SemanticType::Email),
+        PiiKind::Phone => Some(SemanticType::Phone),
+        PiiKind::Name => Some(SemanticType::FullName),
+    }
+}
+
+/// The semantic type every one of `values` looks like, if they agree.
+pub fn classify_values<'a>(values: impl IntoIterator<Item = &'a str>) -> Option<SemanticType> {
+    let mut shared = None;
+    let mut count = 0;
+    for value in values {
+        let semantic_type = classify_value(value)?;
+        if shared.is_some_and(|s| s != semantic_type) {
+            return None;
+        }
+        shared = Some(semantic_type);
+        count += 1;
+    }
+    shared.filter(|_| count >= MIN_SAMPLED_VALUES)
+}
+
+/// Classify the `Unknown` string columns of `schema` from the categorical
+/// values sampled into `profiles`.
+///
+/// Returns the number of columns classified.
+pub fn refine_with_profiles(
+    schema: &DatabaseSchema,
+    classifications: &mut BTreeMap<(String, String), SemanticType>,
+    profiles: &[DistributionProfile],
+) -> usize {
+    let mut refined = 0;
+    for profile in profiles {
+        let Some(table) = schema.tables.get(&profile.table_name) else {
+            continue;
+        };
+        for (col_name, dist) in &profile.column_distributions {
+            let ColumnDistribution::Categorical { values } = dist else {
+                continue;
+            };
+            if !table
+                .columns
+                .get(col_name)
+                .is_some_and(|c| c.data_type.is_string())
+            {
+                continue;
+            }
+            let key = (profile.table_name.clone(), col_name.clone());
+            if classifications.get(&key) != Some(&SemanticType::Unknown) {
+                continue;
+            }
+            if let Some(semantic_type) = classify_values(values.iter().map(|(v, _)| v.as_str())) {
+                classifications.insert(key, semantic_type);
+                refined += 1;
+            }
+        }
+    }
+    refined
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::classify::rules::classify_schema;
+    use crate::schema::types::{Column, DataType, DatabaseType, Table};
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_classify_values() {
+        assert_eq!(
+            classify_values(["ada@example.com", "bob@example.org"]),
+            Some(SemanticType::Email)
+        );
+        assert_eq!(
+            classify_values([
+                "550e8400-e29b-41d4-a716-446655440000",
+                "6ba7b810-9dad-11d1-80b4-00c04fd430c8",
+            ]),
+            Some(SemanticType::Uuid)
+        );
+        assert_eq!(
+            classify_values(["https://example.com/a", "http://example.org"]),
+            Some(SemanticType::Url)
+        );
+        assert_eq!(
+            classify_values(["10.0.0.1", "::1"]),
+            Some(SemanticType::IpAddress)
+        );
+        assert_eq!(
+            classify_values(["#ff0000", "#0AF"]),
+            Some(SemanticType::HexColor)
+        );
+        // Mixed, unrecognized, or too few values
+        assert_eq!(classify_values(["ada@example.com", "10.0.0.1"]), None);
+        assert_eq!(classify_values(["ada@example.com", "pending"]), None);
+        assert_eq!(classify_values(["ada@example.com"]), None);
+    }
+
+    #[test]
+    fn test_refine_with_profiles() {
+        let mut schema = DatabaseSchema::new(DatabaseType::PostgreSQL, "app".to_string());
+        let mut table = Table::new("accounts".to_string());
+        for (name, data_type) in [
+            ("contact", DataType::Text),
+            ("val2", DataType::VarChar),
+            ("email", DataType::VarChar),
+            ("score", DataType::Integer),
+        ] {
+            table.columns.insert(
+                name.to_string(),
+                Column::new(name.to_string(), data_type, "text".to_string()),
+            );
+        }
+        schema.tables.insert("accounts".to_string(), table);
+        let mut classifications = classify_schema(&schema);
+
+        let categorical = |values: &[&str]| ColumnDistribution::Categorical {
+            values: values.iter().map(|v| (v.to_string(), 0.5)).collect(),
+        };
+        let profile = DistributionProfile {
+            table_name: "accounts".to_string(),
+            row_count: 100,
+            unique_pairs: Vec::new(),
+            text_profiles: HashMap::new(),
+            null_rates: HashMap::new(),
+            distinct_counts: HashMap::new(),
+            correlations: Vec::new(),
+            temporal_profiles: HashMap::new(),
+            fan_outs: HashMap::new(),
+            column_distributions: HashMap::from([
+                (
+                    "contact".to_string(),
+                    categorical(&["ada@example.com", "bob@example.org"]),
+                ),
+                ("val2".to_string(), categorical(&["A-1", "B-2"])),
+                (
+                    "email".to_string(),
+                    categorical(&["https://a.example", "https://b.example"]),
+                ),
+            ]),
+        };
+
+        assert_eq!(
+            refine_with_profiles(&schema, &mut classifications, &[profile]),
+            1
+        );
+        let get = |column: &str| classifications[&("accounts".to_string(), column.to_string())];
+        assert_eq!(get("contact"), SemanticType::Email);
+        assert_eq!(get("val2"), SemanticType::Unknown);
+        // Columns the rules classified are left alone
+        assert_eq!(get("email"), SemanticType::Email);
+    }
+}
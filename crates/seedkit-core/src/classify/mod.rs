@@ -1,3 +1,4 @@
+pub mod content;
 pub mod correlations;
 pub mod rules;
 pub mod semantic;
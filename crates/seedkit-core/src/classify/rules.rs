@@ -6,20 +6,95 @@ use crate::classify::semantic::SemanticType;
 use crate::schema::types::DataType;
 
 /// A pre-compiled classification rule with ready-to-use regex patterns.
+#[derive(Debug, Clone)]
 struct CompiledRule {
     pattern: Regex,
-    type_constraint: Option<&'static [DataType]>,
+    type_constraint: Option<Vec<DataType>>,
     table_pattern: Option<Regex>,
     semantic_type: SemanticType,
 }
 
+impl CompiledRule {
+    /// Whether the rule applies to a column, given its normalized name.
+    fn matches(&self, normalized: &str, table_name: &str, data_type: &DataType) -> bool {
+        if let Some(table_re) = &self.table_pattern {
+            if !table_re.is_match(table_name) {
+                return false;
+            }
+        }
+        if !self.pattern.is_match(normalized) {
+            return false;
+        }
+        match &self.type_constraint {
+            Some(types) => types.contains(data_type),
+            None => true,
+        }
+    }
+}
+
+/// A user-defined classification rule (`[[classify]]` in seedkit.toml).
+/// Custom rules are checked in order before the built-in ones, after the
+/// primary key and enum pre-checks.
+#[derive(Debug, Clone)]
+pub struct CustomRule {
+    rule: CompiledRule,
+}
+
+impl CustomRule {
+    /// Compile a rule: `column` and `table` are regexes matched against the
+    /// normalized (snake_case, lowercase) column name and the table name,
+    /// and `data_types` are SQL type names the column must have one of.
+    pub fn new(
+        column: &str,
+        table: Option<&str>,
+        data_types: Option<&[String]>,
+        semantic_type: SemanticType,
+    ) -> Result<Self, String> {
+        let compile =
+            |pattern: &str| Regex::new(pattern).map_err(|e| format!("invalid regex: {}", e));
+        let type_constraint = match data_types {
+            Some(names) => Some(
+                names
+                    .iter()
+                    .map(|name| match DataType::from_raw(name) {
+                        DataType::Unknown(_) => Err(format!("unknown data type '{}'", name)),
+                        data_type => Ok(data_type),
+                    })
+                    .collect::<Result<Vec<_>, String>>()?,
+            ),
+            None => None,
+        };
+        Ok(Self {
+            rule: CompiledRule {
+                pattern: compile(column)?,
+                type_constraint,
+                table_pattern: table.map(compile).transpose()?,
+                semantic_type,
+            },
+        })
+    }
+}
+
 /// Pre-compiled table-context rules — built once on first access.
 static COMPILED_TABLE_CONTEXT_RULES: LazyLock<Vec<CompiledRule>> = LazyLock::new(|| {
     TABLE_CONTEXT_RULES
         .iter()
         .map(|r| CompiledRule {
             pattern: Regex::new(r.pattern).unwrap(),
-            type_constraint: r.type_constraint,
+            type_constraint: r.type_constraint.map(<[DataType]>::to_vec),
+            table_pattern: r.table_pattern.map(|p| Regex::new(p).unwrap()),
+            semantic_type: r.semantic_type,
+        })
+        .collect()
+});
+
+/// Pre-compiled non-English rules — built once on first access.
+static COMPILED_INTERNATIONAL_RULES: LazyLock<Vec<CompiledRule>> = LazyLock::new(|| {
+    INTERNATIONAL_RULES
+        .iter()
+        .map(|r| CompiledRule {
+            pattern: Regex::new(r.pattern).unwrap(),
+            type_constraint: r.type_constraint.map(<[DataType]>::to_vec),
             table_pattern: r.table_pattern.map(|p| Regex::new(p).unwrap()),
             semantic_type: r.semantic_type,
         })
@@ -32,7 +107,7 @@ static COMPILED_GENERAL_RULES: LazyLock<Vec<CompiledRule>> = LazyLock::new(|| {
         .iter()
         .map(|r| CompiledRule {
             pattern: Regex::new(r.pattern).unwrap(),
-            type_constraint: r.type_constraint,
+            type_constraint: r.type_constraint.map(<[DataType]>::to_vec),
             table_pattern: r.table_pattern.map(|p| Regex::new(p).unwrap()),
             semantic_type: r.semantic_type,
         })
@@ -59,6 +134,29 @@ pub fn classify_column(
     is_auto_increment: bool,
     is_primary_key: bool,
     enum_values: Option<&[String]>,
+) -> SemanticType {
+    classify_column_with_rules(
+        &[],
+        column_name,
+        data_type,
+        table_name,
+        is_auto_increment,
+        is_primary_key,
+        enum_values,
+        None,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn classify_column_with_rules(
+    custom_rules: &[CustomRule],
+    column_name: &str,
+    data_type: &DataType,
+    table_name: &str,
+    is_auto_increment: bool,
+    is_primary_key: bool,
+    enum_values: Option<&[String]>,
+    comment: Option<&str>,
 ) -> SemanticType {
     // Pre-check: auto-increment PK columns
     if is_auto_increment && is_primary_key {
@@ -89,39 +187,58 @@ pub fn classify_column(
 
     let normalized = normalize_column_name(column_name);
 
-    // Pass 1: Table-contextual rules (highest priority)
-    for rule in COMPILED_TABLE_CONTEXT_RULES.iter() {
-        if let Some(table_re) = &rule.table_pattern {
-            if !table_re.is_match(table_name) {
-                continue;
-            }
-        }
-        if rule.pattern.is_match(&normalized) {
-            if let Some(types) = rule.type_constraint {
-                if !types.contains(data_type) {
-                    continue;
-                }
-            }
+    // Pass 0: User-defined rules from seedkit.toml
+    // Pass 1: Table-contextual rules
+    // Pass 2: General name-based rules, English then other languages
+    let rules = || {
+        custom_rules
+            .iter()
+            .map(|r| &r.rule)
+            .chain(COMPILED_TABLE_CONTEXT_RULES.iter())
+            .chain(COMPILED_GENERAL_RULES.iter())
+            .chain(COMPILED_INTERNATIONAL_RULES.iter())
+    };
+    for rule in rules() {
+        if rule.matches(&normalized, table_name, data_type) {
             return rule.semantic_type;
         }
     }
 
-    // Pass 2: General name-based rules
-    for rule in COMPILED_GENERAL_RULES.iter() {
-        if rule.pattern.is_match(&normalized) {
-            if let Some(types) = rule.type_constraint {
-                if !types.contains(data_type) {
-                    continue;
-                }
+    // Pass 3: The same rules against the column comment, for cryptic names
+    // ("cst_ml" — "Customer email address")
+    if let Some(comment) = comment {
+        let phrases = comment_phrases(comment);
+        for rule in rules() {
+            if phrases
+                .iter()
+                .any(|phrase| rule.matches(phrase, table_name, data_type))
+            {
+                return rule.semantic_type;
             }
-            return rule.semantic_type;
         }
     }
 
-    // Pass 3: Type-based fallbacks
+    // Pass 4: Type-based fallbacks
     type_based_fallback(data_type)
 }
 
+/// Words of a column comment and runs of up to three of them, lowercased
+/// and joined in snake_case the way rules expect column names.
+fn comment_phrases(comment: &str) -> Vec<String> {
+    let words: Vec<String> = comment
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    let mut phrases = Vec::new();
+    for len in (1..=3).rev() {
+        for run in words.windows(len) {
+            phrases.push(run.join("_"));
+        }
+    }
+    phrases
+}
+
 /// Normalize a column name for pattern matching:
 /// - CamelCase to snake_case (e.g., firstName → first_name)
 /// - lowercase (Unicode-safe)
@@ -129,7 +246,7 @@ pub fn classify_column(
 ///
 /// Uses explicit previous-character tracking instead of byte indexing
 /// to avoid UTF-8 multi-byte footguns with non-ASCII column names.
-fn normalize_column_name(name: &str) -> String {
+pub(crate) fn normalize_column_name(name: &str) -> String {
     let mut result = String::with_capacity(name.len() + 4);
     let mut prev_char: Option<char> = None;
 
@@ -703,9 +820,191 @@ static GENERAL_RULES: &[ClassificationRule] = &[
     },
 ];
 
+// === Non-English names ===
+// Spanish, German, Portuguese and French spellings, with and without
+// accents, checked after the English rules.
+
+static INTERNATIONAL_RULES: &[ClassificationRule] = &[
+    // === Identity ===
+    ClassificationRule {
+        pattern: r"^(nombre|primer_?nombre|vorname|pr[eé]nom|primeiro_?nome)$",
+        type_constraint: None,
+        table_pattern: None,
+        semantic_type: SemanticType::FirstName,
+    },
+    ClassificationRule {
+        pattern: r"^(apellidos?|primer_?apellido|segundo_?apellido|nachname|familien_?name|sobrenome|nom(_?de)?(_?famille)?)$",
+        type_constraint: None,
+        table_pattern: None,
+        semantic_type: SemanticType::LastName,
+    },
+    ClassificationRule {
+        pattern: r"^(nombre_?completo|nome(_?completo)?|nom_?complet|vollst(ä|ae)ndiger_?name|voller_?name)$",
+        type_constraint: None,
+        table_pattern: None,
+        semantic_type: SemanticType::FullName,
+    },
+    ClassificationRule {
+        pattern: r"^(usuario|nombre_?(de_?)?usuario|benutzer(_?name)?|usu[aá]rio|nome_?(de_?)?usu[aá]rio|utilisateur|nom_?d_?utilisateur|identifiant)$",
+        type_constraint: None,
+        table_pattern: None,
+        semantic_type: SemanticType::Username,
+    },
+    // === Contact ===
+    ClassificationRule {
+        pattern: r"^(correo(_?electr[oó]nico)?|e?_?mail_?adresse|courriel|adresse_?e?_?mail|endere[cç]o_?(de_?)?e?_?mail)$",
+        type_constraint: None,
+        table_pattern: None,
+        semantic_type: SemanticType::Email,
+    },
+    ClassificationRule {
+        pattern: r"^(tel[eé]fono|celular|m[oó]vil|telefon(_?nummer)?|handy(_?nummer)?|mobil(_?nummer)?|telefone|t[eé]l[eé]phone|portable|num[eé]ro_?(de_?)?t[eé]l[eé]phone)$",
+        type_constraint: None,
+        table_pattern: None,
+        semantic_type: SemanticType::Phone,
+    },
+    // === Address ===
+    ClassificationRule {
+        pattern: r"^(direcci[oó]n|domicilio|calle|stra(ss|ß)e|anschrift|adresse|endere[cç]o|logradouro|rua|rue)$",
+        type_constraint: None,
+        table_pattern: None,
+        semantic_type: SemanticType::StreetAddress,
+    },
+    ClassificationRule {
+        pattern: r"^(ciudad|localidad|municipio|stadt|ort|wohnort|cidade|munic[ií]pio|ville|commune)$",
+        type_constraint: None,
+        table_pattern: None,
+        semantic_type: SemanticType::City,
+    },
+    ClassificationRule {
+        pattern: r"^(provincia|bundesland|r[eé]gion|d[eé]partement)$",
+        type_constraint: None,
+        table_pattern: None,
+        semantic_type: SemanticType::State,
+    },
+    ClassificationRule {
+        pattern: r"^(c[oó]digo_?postal|plz|postleitzahl|cep|code_?postal)$",
+        type_constraint: None,
+        table_pattern: None,
+        semantic_type: SemanticType::PostalCode,
+    },
+    ClassificationRule {
+        pattern: r"^(pa[ií]s|land|pays)$",
+        type_constraint: None,
+        table_pattern: None,
+        semantic_type: SemanticType::Country,
+    },
+    // === Company ===
+    ClassificationRule {
+        pattern: r"^(empresa|compa[nñ][ií]a|raz[oó]n_?social|raz[aã]o_?social|firma|unternehmen|soci[eé]t[eé]|entreprise)$",
+        type_constraint: None,
+        table_pattern: None,
+        semantic_type: SemanticType::CompanyName,
+    },
+    ClassificationRule {
+        pattern: r"^(puesto|beruf|profiss[aã]o|m[eé]tier)$",
+        type_constraint: None,
+        table_pattern: None,
+        semantic_type: SemanticType::JobTitle,
+    },
+    // === Content ===
+    ClassificationRule {
+        pattern: r"^(t[ií]tulo|titel|titre)$",
+        type_constraint: None,
+        table_pattern: None,
+        semantic_type: SemanticType::Title,
+    },
+    ClassificationRule {
+        pattern: r"^(descripci[oó]n|beschreibung|descri[cç][aã]o)$",
+        type_constraint: None,
+        table_pattern: None,
+        semantic_type: SemanticType::Description,
+    },
+    ClassificationRule {
+        pattern: r"^(comentarios?|observaci[oó]n(es)?|notas?|bemerkung(en)?|kommentar|notiz|observa[cç](ão|ao|ões|oes)|remarques?)$",
+        type_constraint: None,
+        table_pattern: None,
+        semantic_type: SemanticType::Paragraph,
+    },
+    // === Financial ===
+    ClassificationRule {
+        pattern: r"^(precio|importe|monto|preis|betrag|pre[cç]o|valor|prix|montant)$",
+        type_constraint: None,
+        table_pattern: None,
+        semantic_type: SemanticType::Price,
+    },
+    ClassificationRule {
+        pattern: r"^(moneda|w(ä|ae)hrung|moeda|devise)$",
+        type_constraint: None,
+        table_pattern: None,
+        semantic_type: SemanticType::Currency,
+    },
+    // === Temporal ===
+    ClassificationRule {
+        pattern: r"^(fecha_?(de_?)?creaci[oó]n|creado(_?en)?|erstellt(_?am)?|erstellungs_?datum|data_?(de_?)?cria[cç][aã]o|criado(_?em)?|date_?(de_?)?cr[eé]ation|cr[eé][eé]_?le)$",
+        type_constraint: None,
+        table_pattern: None,
+        semantic_type: SemanticType::CreatedAt,
+    },
+    ClassificationRule {
+        pattern: r"^(fecha_?(de_?)?(actualizaci[oó]n|modificaci[oó]n)|actualizado(_?en)?|modificado(_?en)?|ge(ä|ae)ndert(_?am)?|aktualisiert(_?am)?|(ä|ae)nderungs_?datum|data_?(de_?)?atualiza[cç][aã]o|atualizado(_?em)?|date_?(de_?)?modification|modifi[eé]_?le)$",
+        type_constraint: None,
+        table_pattern: None,
+        semantic_type: SemanticType::UpdatedAt,
+    },
+    ClassificationRule {
+        pattern: r"^(fecha_?(de_?)?nacimiento|geburts_?(datum|tag)|data_?(de_?)?nascimento|date_?(de_?)?naissance)$",
+        type_constraint: None,
+        table_pattern: None,
+        semantic_type: SemanticType::BirthDate,
+    },
+    // === Auth/Security ===
+    ClassificationRule {
+        pattern: r"^(contrase[nñ]a|clave|passwort|kennwort|senha|mot_?de_?passe)$",
+        type_constraint: None,
+        table_pattern: None,
+        semantic_type: SemanticType::PasswordHash,
+    },
+    // === Status/Enum ===
+    ClassificationRule {
+        pattern: r"^(estado|estatus|situaci[oó]n|situa[cç][aã]o|statut)$",
+        type_constraint: None,
+        table_pattern: None,
+        semantic_type: SemanticType::Status,
+    },
+    ClassificationRule {
+        pattern: r"^(categor[ií]a|kategorie|cat[eé]gorie)$",
+        type_constraint: None,
+        table_pattern: None,
+        semantic_type: SemanticType::Category,
+    },
+    // === Numeric ===
+    ClassificationRule {
+        pattern: r"^(cantidad|menge|anzahl|quantidade|quantit[eé])$",
+        type_constraint: Some(NUMERIC_TYPES),
+        table_pattern: None,
+        semantic_type: SemanticType::Quantity,
+    },
+    ClassificationRule {
+        pattern: r"^(edad|alter|idade|[aâ]ge)$",
+        type_constraint: Some(INTEGER_TYPES),
+        table_pattern: None,
+        semantic_type: SemanticType::Age,
+    },
+];
+
 /// Classify all columns in a schema and return a map of (table_name, column_name) -> SemanticType.
 pub fn classify_schema(
     schema: &crate::schema::types::DatabaseSchema,
+) -> std::collections::BTreeMap<(String, String), SemanticType> {
+    classify_schema_with_rules(schema, &[])
+}
+
+/// Classify all columns in a schema, checking `custom_rules` before the
+/// built-in rules.
+pub fn classify_schema_with_rules(
+    schema: &crate::schema::types::DatabaseSchema,
+    custom_rules: &[CustomRule],
 ) -> std::collections::BTreeMap<(String, String), SemanticType> {
     let mut result = std::collections::BTreeMap::new();
 
@@ -718,13 +1017,15 @@ pub fn classify_schema(
 
         for (col_name, column) in &table.columns {
             let is_pk = pk_columns.contains(&col_name.as_str());
-            let semantic_type = classify_column(
+            let semantic_type = classify_column_with_rules(
+                custom_rules,
                 col_name,
                 &column.data_type,
                 table_name,
                 column.is_auto_increment,
                 is_pk,
                 column.enum_values.as_deref(),
+                column.comment.as_deref(),
             );
 
             result.insert((table_name.clone(), col_name.clone()), semantic_type);
@@ -919,6 +1220,129 @@ mod tests {
         assert_eq!(st, SemanticType::ExternalId);
     }
 
+    #[test]
+    fn test_custom_rules_take_priority() {
+        let rules = [
+            CustomRule::new("^cust_nm$", None, None, SemanticType::FullName).unwrap(),
+            CustomRule::new(
+                "^addr[0-9]$",
+                Some("^crm_"),
+                Some(&["varchar".to_string()]),
+                SemanticType::StreetAddress,
+            )
+            .unwrap(),
+            CustomRule::new("^email$", None, None, SemanticType::Username).unwrap(),
+        ];
+        let classify = |column: &str, data_type: DataType, table: &str| {
+            classify_column_with_rules(&rules, column, &data_type, table, false, false, None, None)
+        };
+        assert_eq!(
+            classify("custNm", DataType::VarChar, "accounts"),
+            SemanticType::FullName
+        );
+        assert_eq!(
+            classify("addr1", DataType::VarChar, "crm_contacts"),
+            SemanticType::StreetAddress
+        );
+        assert_ne!(
+            classify("addr1", DataType::VarChar, "contacts"),
+            SemanticType::StreetAddress
+        );
+        assert_ne!(
+            classify("addr1", DataType::Integer, "crm_contacts"),
+            SemanticType::StreetAddress
+        );
+        // Ahead of the built-in rules, but not the primary key pre-checks
+        assert_eq!(
+            classify("email", DataType::VarChar, "users"),
+            SemanticType::Username
+        );
+        assert_eq!(
+            classify_column_with_rules(
+                &[CustomRule::new("^id$", None, None, SemanticType::Sku).unwrap()],
+                "id",
+                &DataType::Serial,
+                "users",
+                true,
+                true,
+                None,
+                None
+            ),
+            SemanticType::AutoIncrement
+        );
+    }
+
+    #[test]
+    fn test_comment_classifies_cryptic_names() {
+        let classify = |column: &str, data_type: DataType, comment: Option<&str>| {
+            classify_column_with_rules(
+                &[],
+                column,
+                &data_type,
+                "customers",
+                false,
+                false,
+                None,
+                comment,
+            )
+        };
+        assert_eq!(
+            classify("cst_ml", DataType::VarChar, Some("Customer e-mail address")),
+            SemanticType::Email
+        );
+        assert_eq!(
+            classify("dob_txt", DataType::Date, Some("Date of birth, UTC")),
+            SemanticType::BirthDate
+        );
+        // The name wins over the comment
+        assert_eq!(
+            classify(
+                "city",
+                DataType::VarChar,
+                Some("Phone number of the office")
+            ),
+            SemanticType::City
+        );
+        // A comment that says nothing leaves the type fallback
+        assert_eq!(
+            classify("flg2", DataType::Text, Some("Reserved")),
+            classify("flg2", DataType::Text, None)
+        );
+    }
+
+    #[test]
+    fn test_classify_non_english_names() {
+        for (column, data_type, expected) in [
+            ("nombre", DataType::VarChar, SemanticType::FirstName),
+            ("apellidos", DataType::VarChar, SemanticType::LastName),
+            ("correo", DataType::VarChar, SemanticType::Email),
+            ("teléfono", DataType::VarChar, SemanticType::Phone),
+            ("telefone", DataType::VarChar, SemanticType::Phone),
+            ("Geburtsdatum", DataType::Date, SemanticType::BirthDate),
+            ("geburts_datum", DataType::Date, SemanticType::BirthDate),
+            ("Straße", DataType::VarChar, SemanticType::StreetAddress),
+            ("PLZ", DataType::VarChar, SemanticType::PostalCode),
+            ("prénom", DataType::VarChar, SemanticType::FirstName),
+            ("nom", DataType::VarChar, SemanticType::LastName),
+            ("code_postal", DataType::VarChar, SemanticType::PostalCode),
+            ("dataDeNascimento", DataType::Date, SemanticType::BirthDate),
+            (
+                "fecha_creacion",
+                DataType::Timestamp,
+                SemanticType::CreatedAt,
+            ),
+            ("cidade", DataType::VarChar, SemanticType::City),
+            ("precio", DataType::Numeric, SemanticType::Price),
+            ("edad", DataType::Integer, SemanticType::Age),
+        ] {
+            let st = classify_column(column, &data_type, "clientes", false, false, None);
+            assert_eq!(st, expected, "{}", column);
+        }
+        // Type constraints still apply
+        let st = classify_column("edad", &DataType::VarChar, "clientes", false, false, None);
+        assert_ne!(st, SemanticType::Age);
+    }
+
     #[test]
     fn test_normalize_camel_case() {
         assert_eq!(normalize_column_name("firstName"), "first_name");
@@ -124,6 +124,22 @@ pub enum SemanticType {
 }
 
 impl SemanticType {
+    /// The type named in snake_case (`first_name` → `FirstName`), as
+    /// templates and `type` overrides in seedkit.toml spell it.
+    pub fn from_snake_case(name: &str) -> Option<Self> {
+        let camel: String = name
+            .split('_')
+            .map(|word| {
+                let mut chars = word.chars();
+                match chars.next() {
+                    Some(c) => c.to_ascii_uppercase().to_string() + chars.as_str(),
+                    None => String::new(),
+                }
+            })
+            .collect();
+        serde_json::from_value(serde_json::Value::String(camel)).ok()
+    }
+
     /// Returns true if this type is typically auto-generated by the database
     /// and should be skipped during data generation.
     pub fn is_auto_generated(&self) -> bool {
@@ -167,8 +183,10 @@ impl SemanticType {
             SemanticType::FirstName
             | SemanticType::LastName
             | SemanticType::FullName
+            | SemanticType::DisplayName
             | SemanticType::Email
-            | SemanticType::Username => Some(CorrelationGroup::PersonIdentity),
+            | SemanticType::Username
+            | SemanticType::AvatarUrl => Some(CorrelationGroup::PersonIdentity),
 
             SemanticType::CreatedAt | SemanticType::UpdatedAt | SemanticType::DeletedAt => {
                 Some(CorrelationGroup::Temporal)
@@ -7,18 +7,37 @@
 //! - `[generate]` — default row count, seed, AI settings
 //! - `[tables.<name>]` — per-table row count overrides
 //! - `[columns."<table>.<column>"]` — custom values, weights, or provider paths
+//! - `[types.<name>]` — user-defined semantic types columns can be given
+//!   with `type = "<name>"`
+//! - `[[classify]]` — column-name rules checked before the built-in
+//!   classification rules
 //! - `[graph]` — explicit cycle-breaking edge overrides
+//! - `[llm]` — provider and model for `--ai` and LLM-written columns
+//! - `[scenario.<name>]` — named dataset shapes (row counts, table filters,
+//!   column overrides, seed) selected with `seedkit generate --scenario`
+//! - `[profiles.<env>]` — per-environment overrides of any of the above,
+//!   selected with `--env` (or `SEEDKIT_ENV`) and merged key by key
+//! - `include` — shared config files this one is overlaid on, e.g. a
+//!   monorepo-wide `seedkit.base.toml`
+//!
+//! String values may reference environment variables as `${NAME}`, or
+//! `${NAME:-default}` to fall back when it is unset; `$${` is a literal
+//! `${`.
 //!
 //! Example `seedkit.toml`:
 //!
 //! ```toml
+//! include = ["../seedkit.base.toml"]
+//!
 //! [database]
-//! url = "postgres://localhost/myapp"
+//! url = "${DATABASE_URL:-postgres://localhost/myapp}"
 //!
 //! [generate]
 //! rows = 500
 //! seed = 42
 //! ai = false
+//! locales = { en = 0.6, de = 0.3, ja = 0.1 }
+//! language_mix = { en = 0.95, de = 0.05 }
 //!
 //! [tables.users]
 //! rows = 1000
@@ -26,15 +45,120 @@
 //! [tables.orders]
 //! rows = 5000
 //!
+//! [tables.products]
+//! stable = true
+//!
+//! [tables.regions]
+//! lookup = "countries"
+//!
+//! [tables.campaigns]
+//! language_mix = { en = 0.9, de = 0.05, ja = 0.05 }
+//!
+//! [tables.metrics.time_series]
+//! interval = "5 minutes"
+//! series_by = "device_id"
+//!
+//! [tables.events.funnel]
+//! steps = ["signup", "activate", "purchase"]
+//! drop_off = [0.4, 0.7]
+//!
+//! [tables.users.segments.power]
+//! share = 0.05
+//! activity = 50
+//! columns = { plan = ["pro", "enterprise"] }
+//!
+//! [tables.users.segments.dormant]
+//! share = 0.2
+//! activity = 0
+//!
 //! [columns."products.color"]
 //! values = ["red", "blue", "green", "black", "white"]
 //! weights = [0.25, 0.20, 0.20, 0.20, 0.15]
 //!
+//! [types.icd10_code]
+//! pattern = "[A-TV-Z][0-9]{2}(\\.[0-9]{1,2})?"
+//!
+//! [columns."claims.diagnosis"]
+//! type = "icd10_code"
+//!
+//! [columns."users.contact"]
+//! type = "email"
+//!
+//! [[classify]]
+//! column = "^cust_nm$"
+//! type = "full_name"
+//!
+//! [[classify]]
+//! column = "^addr[0-9]$"
+//! table = "^crm_"
+//! data_types = ["varchar", "text"]
+//! type = "street_address"
+//!
+//! [columns."orders.status".lifecycle]
+//! states = ["pending", "processing", "shipped", "delivered"]
+//! weights = [0.1, 0.2, 0.3, 0.4]
+//!
 //! [columns."orders.tax_code"]
 //! custom = "./scripts/tax_gen.js"
 //!
+//! [columns."users.work_email"]
+//! template = "{first_name}.{last_name}@{company_domain}"
+//!
+//! [columns."order_items.line_total"]
+//! expr = "quantity * unit_price"
+//!
+//! [columns."orders.total"]
+//! aggregate = "sum(order_items.unit_price * quantity)"
+//!
+//! [columns."users.settings"]
+//! json_schema = "./schemas/user_settings.json"
+//!
+//! [columns."products.description".llm]
+//! prompt = "A two-sentence description of a kitchenware product"
+//!
+//! [columns."posts.tags"]
+//! array_length = [0, 8]
+//!
+//! [columns."bookings.during"]
+//! range_span = [1, 14]
+//!
+//! [columns."plans.billing_period"]
+//! interval_span = [86400, 31536000]
+//!
+//! [columns."attachments.payload"]
+//! binary_size = [256, 4096]
+//!
+//! [columns."stores.location"]
+//! bbox = [-74.26, 40.49, -73.70, 40.92]
+//!
 //! [graph]
 //! break_cycle_at = ["users.invited_by_id", "comments.parent_id"]
+//!
+//! [llm]
+//! provider = "anthropic"
+//! model = "claude-sonnet-4-20250514"
+//! max_tokens_per_run = 200000
+//! over_budget = "rule-based"
+//!
+//! [scenario.demo]
+//! rows = 20
+//! seed = 7
+//! exclude = ["audit_logs"]
+//!
+//! [scenario.demo.columns."users.plan"]
+//! values = ["pro"]
+//!
+//! [scenario.load-test]
+//! rows = 100000
+//!
+//! [scenario.load-test.tables.orders]
+//! rows = 1000000
+//!
+//! [profiles.ci.generate]
+//! rows = 50
+//!
+//! [profiles.staging.database]
+//! url = "${STAGING_DATABASE_URL}"
 //! ```
 
 use std::collections::BTreeMap;
@@ -42,7 +166,26 @@ use std::path::{Path, PathBuf};
 
 use serde::Deserialize;
 
+use crate::classify::rules::CustomRule;
+use crate::classify::semantic::SemanticType;
 use crate::error::{Result, SeedKitError};
+use crate::generate::aggregate::parse_aggregate;
+use crate::generate::expression::check_expression;
+use crate::generate::foreign_key::FanOut;
+use crate::generate::funnel::Funnel;
+use crate::generate::json_schema::{check_schema, JsonSchemaSource};
+use crate::generate::language::LanguageMix;
+use crate::generate::lifecycle::Lifecycle;
+use crate::generate::locale::{Locale, LocaleMix};
+use crate::generate::lookup::{parse_lookup_override, LookupDataset};
+use crate::generate::pattern::Pattern;
+use crate::generate::polymorphic::PolymorphicAssociation;
+use crate::generate::segment::{validate_segments, Segment};
+use crate::generate::template::parse_template;
+use crate::generate::time_series::TimeSeries;
+use crate::llm::budget::OverBudget;
+use crate::llm::client::ProviderKind;
+use crate::llm::content::LlmContent;
 
 /// Default config file name.
 pub const CONFIG_FILE_NAME: &str = "seedkit.toml";
@@ -59,8 +202,16 @@ pub struct SeedKitConfig {
     pub tables: BTreeMap<String, TableConfig>,
     /// Per-column overrides, keyed by "table.column".
     pub columns: BTreeMap<String, ColumnConfig>,
+    /// User-defined semantic types, keyed by snake_case name.
+    pub types: BTreeMap<String, TypeConfig>,
+    /// Custom classification rules, checked in order.
+    pub classify: Vec<ClassifyRule>,
     /// Dependency graph settings.
     pub graph: GraphConfig,
+    /// LLM provider settings.
+    pub llm: LlmConfig,
+    /// Named scenarios, applied on top of the rest with `--scenario`.
+    pub scenario: BTreeMap<String, ScenarioConfig>,
 
     /// Absolute path to the directory containing seedkit.toml.
     ///
@@ -94,6 +245,19 @@ pub struct GenerateConfig {
     pub include: Option<Vec<String>>,
     /// Tables to exclude.
     pub exclude: Option<Vec<String>>,
+    /// Locale for person names (`en`, `de`, `fr`, `ja`, `el`). Emails and
+    /// usernames are transliterated to ASCII.
+    pub locale: Option<String>,
+    /// Weights of name locales, picked per row, instead of one `locale`.
+    pub locales: Option<BTreeMap<String, f64>>,
+    /// Weights of languages for free-text content (`en`, `de`, `fr`, `es`,
+    /// `ja`, `el`), picked per row. Tables can override it.
+    pub language_mix: Option<BTreeMap<String, f64>>,
+    /// Record every direct insert as a run `seedkit wipe --run` can remove
+    /// (see `output::runs`).
+    pub track_runs: Option<bool>,
+    /// Column set to the run ID in the tables that have it.
+    pub run_marker: Option<String>,
 }
 
 /// Per-table configuration override.
@@ -102,18 +266,186 @@ pub struct GenerateConfig {
 pub struct TableConfig {
     /// Number of rows to generate for this table.
     pub rows: Option<usize>,
+    /// Generate this table's rows once, record them in `seedkit.lock`, and
+    /// replay them on every later run.
+    pub stable: Option<bool>,
+    /// Fill this table from a curated dataset (`countries`, `currencies`,
+    /// `languages`, `statuses`), or `none` to opt out of lookup detection.
+    pub lookup: Option<String>,
+    /// Weights of languages for this table's free-text content, replacing
+    /// `[generate] language_mix`.
+    pub language_mix: Option<BTreeMap<String, f64>>,
+    /// Rhai script run over every generated row of this table, relative to
+    /// seedkit.toml (see `generate::script`).
+    pub script: Option<String>,
+    /// Polymorphic associations: name → (type value → target table), for
+    /// `<name>_type` / `<name>_id` column pairs (see `generate::polymorphic`).
+    pub polymorphic: Option<BTreeMap<String, BTreeMap<String, String>>>,
+    /// Generate the rows as time series: a steady cadence per parent of
+    /// `series_by`, with optional trend and seasonality (see
+    /// `generate::time_series`).
+    pub time_series: Option<TimeSeries>,
+    /// Generate the rows as user journeys through a funnel of events (see
+    /// `generate::funnel`).
+    pub funnel: Option<Funnel>,
+    /// Population segments by name: shares of the rows with their own
+    /// column values and activity (see `generate::segment`).
+    pub segments: Option<BTreeMap<String, Segment>>,
 }
 
 /// Per-column configuration override.
 #[derive(Debug, Clone, Default, Deserialize)]
 #[serde(default)]
 pub struct ColumnConfig {
+    /// Semantic type of the column, overriding classification: a built-in
+    /// type in snake_case (`email`, `company_name`) or one defined under
+    /// `[types]`, whose generator the column then uses.
+    #[serde(rename = "type")]
+    pub semantic_type: Option<String>,
     /// Fixed list of values to pick from.
     pub values: Option<Vec<String>>,
     /// Weights for each value (must be same length as `values`).
     pub weights: Option<Vec<f64>>,
+    /// Regular expression generated values match (see
+    /// `generate::pattern`).
+    pub pattern: Option<String>,
     /// Path to a custom JS or WASM provider.
     pub custom: Option<String>,
+    /// External provider process and its arguments, speaking JSON over
+    /// stdin/stdout (see `generate::process`).
+    pub command: Option<Vec<String>>,
+    /// The directory `command` runs in: the one holding seedkit.toml.
+    #[serde(skip)]
+    pub command_dir: Option<PathBuf>,
+    /// Template rendered from the row, e.g.
+    /// `"{first_name}.{last_name}@{company_domain}"`. Placeholders name a
+    /// column of the same table or a generator (see `generate::template`).
+    pub template: Option<String>,
+    /// Status path with optional weights and the timestamp column of each
+    /// state (see `generate::lifecycle`).
+    pub lifecycle: Option<Lifecycle>,
+    /// Expression over the row's other columns, e.g. `"quantity * unit_price"`
+    /// or `"lower(email)"` (see `generate::expression`).
+    pub expr: Option<String>,
+    /// Aggregate over child rows, e.g.
+    /// `"sum(order_items.unit_price * quantity)"` (see `generate::aggregate`).
+    pub aggregate: Option<String>,
+    /// JSON Schema for JSON/JSONB documents: a file relative to
+    /// seedkit.toml, or an inline table (see `generate::json_schema`).
+    pub json_schema: Option<JsonSchemaSource>,
+    /// Text written by the LLM provider from a prompt (see
+    /// `llm::content`).
+    pub llm: Option<LlmContent>,
+    /// Element count range `[min, max]` for array columns (default `[1, 5]`).
+    pub array_length: Option<(usize, usize)>,
+    /// Span `[min, max]` between lower and upper bound for range columns:
+    /// units for numeric ranges, days for `daterange`, hours for timestamp
+    /// ranges.
+    pub range_span: Option<(i64, i64)>,
+    /// Length `[min, max]` in seconds for interval columns. CHECK
+    /// constraints on the column narrow it further.
+    pub interval_span: Option<(i64, i64)>,
+    /// Payload size `[min, max]` in bytes for binary columns (default
+    /// `[8, 32]`). A `varbinary(n)`/`binary(n)` limit caps the maximum.
+    pub binary_size: Option<(usize, usize)>,
+    /// Bounding box `[min_x, min_y, max_x, max_y]` for PostGIS columns, in
+    /// the column's coordinate units (longitude/latitude for SRID 4326).
+    pub bbox: Option<(f64, f64, f64, f64)>,
+    /// Center generated geometries on the row's latitude/longitude columns
+    /// when the table has both (default `true`).
+    pub follow_lat_lng: Option<bool>,
+    /// How a foreign key column's rows spread over parent rows: `"uniform"`
+    /// (default) or `{ zipf = 1.2 }` so a few parents get most children.
+    pub fan_out: Option<FanOut>,
+}
+
+/// A user-defined semantic type and its generator: exactly one of a value
+/// list, a pattern, a template, or a script provider (`custom` JS/WASM
+/// module or external `command`).
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TypeConfig {
+    pub values: Option<Vec<String>>,
+    pub weights: Option<Vec<f64>>,
+    pub pattern: Option<String>,
+    pub template: Option<String>,
+    pub custom: Option<String>,
+    pub command: Option<Vec<String>>,
+}
+
+impl TypeConfig {
+    /// Number of generators set; valid types set exactly one.
+    fn generator_count(&self) -> usize {
+        [
+            self.values.is_some(),
+            self.pattern.is_some(),
+            self.template.is_some(),
+            self.custom.is_some(),
+            self.command.is_some(),
+        ]
+        .into_iter()
+        .filter(|set| *set)
+        .count()
+    }
+}
+
+/// Check a user-defined type's name and generator.
+fn validate_type(name: &str, ty: &TypeConfig) -> std::result::Result<(), String> {
+    let mut chars = name.chars();
+    let snake_case = chars.next().is_some_and(|c| c.is_ascii_lowercase())
+        && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+    if !snake_case {
+        return Err("type names must be snake_case, e.g. flight_number.".to_string());
+    }
+    if SemanticType::from_snake_case(name).is_some() {
+        return Err(format!(
+            "'{}' is a built-in semantic type; give the custom type another name.",
+            name
+        ));
+    }
+    if ty.generator_count() != 1 {
+        return Err(
+            "needs exactly one of values, pattern, template, custom or command.".to_string(),
+        );
+    }
+    match (&ty.values, &ty.weights) {
+        (Some(values), Some(weights)) if values.len() != weights.len() => {
+            return Err(format!(
+                "weights has {} entries but values has {} entries.",
+                weights.len(),
+                values.len()
+            ))
+        }
+        (None, Some(_)) => return Err("weights provided without values.".to_string()),
+        _ => {}
+    }
+    if let Some(ref pattern) = ty.pattern {
+        Pattern::parse(pattern)?;
+    }
+    if let Some(ref template) = ty.template {
+        parse_template(template)?;
+    }
+    if ty.command.as_ref().is_some_and(|c| c.is_empty()) {
+        return Err("command must name a program.".to_string());
+    }
+    Ok(())
+}
+
+/// A custom classification rule: columns whose name matches `column` get
+/// `type`, optionally only in tables matching `table` and for columns of
+/// one of `data_types`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ClassifyRule {
+    /// Regex matched against the column name in snake_case.
+    pub column: String,
+    /// Built-in semantic type in snake_case.
+    #[serde(rename = "type")]
+    pub semantic_type: String,
+    /// Regex matched against the table name.
+    pub table: Option<String>,
+    /// SQL types the column must have one of (`varchar`, `integer`, ...).
+    pub data_types: Option<Vec<String>>,
 }
 
 /// Dependency graph configuration.
@@ -128,21 +460,89 @@ pub struct GraphConfig {
     pub break_cycle_at: Vec<String>,
 }
 
+/// A named dataset shape: settings that replace the top-level ones when
+/// the scenario is selected.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ScenarioConfig {
+    /// Default number of rows per table.
+    pub rows: Option<usize>,
+    /// Fixed random seed.
+    pub seed: Option<u64>,
+    /// Tables to include (empty = all).
+    pub include: Option<Vec<String>>,
+    /// Tables to exclude.
+    pub exclude: Option<Vec<String>>,
+    /// Per-table row counts, keyed by table name.
+    pub tables: BTreeMap<String, ScenarioTableConfig>,
+    /// Per-column overrides, replacing the `[columns]` entry of the same
+    /// column.
+    pub columns: BTreeMap<String, ColumnConfig>,
+}
+
+/// A scenario's per-table settings.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ScenarioTableConfig {
+    /// Number of rows to generate for this table.
+    pub rows: Option<usize>,
+}
+
+/// LLM provider configuration. `SEEDKIT_LLM_PROVIDER` and
+/// `SEEDKIT_LLM_MODEL` override it (see `LlmProvider::from_config`).
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct LlmConfig {
+    /// `anthropic` (or `claude`), `openai` or `local` (or `ollama`). Unset
+    /// picks whichever API key is in the environment, Anthropic's first,
+    /// unless `base_url` is set.
+    pub provider: Option<String>,
+    /// Model to call instead of the provider's default.
+    pub model: Option<String>,
+    /// OpenAI-compatible endpoint of a self-hosted model, e.g.
+    /// `http://localhost:11434/v1` for Ollama. Implies the local provider.
+    pub base_url: Option<String>,
+    /// Most tokens (estimated, prompts and answers) one run may spend.
+    pub max_tokens_per_run: Option<u64>,
+    /// Most requests one run may send.
+    pub max_requests: Option<usize>,
+    /// What to do when a run would go over budget (see `llm::budget`).
+    pub over_budget: Option<OverBudget>,
+    /// Price used to show the cost of a run's estimate.
+    pub usd_per_million_tokens: Option<f64>,
+}
+
 /// Read and parse a seedkit.toml file from the given directory.
 ///
 /// Returns `None` if the file doesn't exist (config is optional).
 /// Returns an error if the file exists but can't be parsed.
 pub fn read_config(dir: &Path) -> Result<Option<SeedKitConfig>> {
+    read_config_with_env(dir, None)
+}
+
+/// Read seedkit.toml from `dir` with the `[profiles.<env>]` overrides of
+/// `env` applied. Unlike the file itself, a profile is not optional.
+pub fn read_config_with_env(dir: &Path, env: Option<&str>) -> Result<Option<SeedKitConfig>> {
     let path = dir.join(CONFIG_FILE_NAME);
     if !path.exists() {
-        return Ok(None);
+        return match env {
+            Some(env) => Err(SeedKitError::Config {
+                message: format!(
+                    "--env {} needs a {} defining [profiles.{}]",
+                    env,
+                    path.display(),
+                    env
+                ),
+            }),
+            None => Ok(None),
+        };
     }
 
     let content = std::fs::read_to_string(&path).map_err(|e| SeedKitError::Config {
         message: format!("Failed to read {}: {}", path.display(), e),
     })?;
 
-    let mut config: SeedKitConfig = toml::from_str(&content).map_err(|e| SeedKitError::Config {
+    let mut config = parse_config(&content, dir, env).map_err(|e| SeedKitError::Config {
         message: format!("Failed to parse {}: {}", path.display(), e),
     })?;
 
@@ -156,7 +556,230 @@ pub fn read_config(dir: &Path) -> Result<Option<SeedKitConfig>> {
     Ok(Some(config))
 }
 
+/// Parse seedkit.toml text: overlay it on its `include` files (relative
+/// to `dir`), merge the `[profiles.<env>]` overrides into the rest, then
+/// replace `${NAME}` references with environment variables.
+pub fn parse_config(
+    text: &str,
+    dir: &Path,
+    env: Option<&str>,
+) -> std::result::Result<SeedKitConfig, String> {
+    let mut document: toml::Value = toml::from_str(text).map_err(|e| e.to_string())?;
+    // Plain files parse directly, keeping line numbers in errors
+    if env.is_none() && !text.contains("${") && document.get("include").is_none() {
+        return toml::from_str(text).map_err(|e| e.to_string());
+    }
+    resolve_includes(&mut document, dir, &mut Vec::new())?;
+    apply_profile(&mut document, env)?;
+    interpolate(&mut document, &|name| std::env::var(name).ok())?;
+    document
+        .try_into()
+        .map_err(|e: toml::de::Error| e.to_string())
+}
+
+/// Overlay `document` on the files its top-level `include` list names,
+/// relative to `dir`: later files override earlier ones and `document`
+/// overrides them all, merged like profiles. `chain` holds the files
+/// being included, to catch cycles.
+pub(crate) fn resolve_includes(
+    document: &mut toml::Value,
+    dir: &Path,
+    chain: &mut Vec<PathBuf>,
+) -> std::result::Result<(), String> {
+    let Some(root) = document.as_table_mut() else {
+        return Ok(());
+    };
+    let includes = match root.remove("include") {
+        Some(toml::Value::Array(includes)) => includes,
+        Some(_) => return Err("include must be a list of file paths".to_string()),
+        None => return Ok(()),
+    };
+
+    let mut base = toml::Table::new();
+    for include in includes {
+        let Some(name) = include.as_str() else {
+            return Err("include must be a list of file paths".to_string());
+        };
+        let path = dir.join(name);
+        let path = std::fs::canonicalize(&path)
+            .map_err(|e| format!("include {}: {}", path.display(), e))?;
+        if chain.contains(&path) {
+            return Err(format!("include cycle through {}", path.display()));
+        }
+        let text = std::fs::read_to_string(&path)
+            .map_err(|e| format!("include {}: {}", path.display(), e))?;
+        let mut included: toml::Value =
+            toml::from_str(&text).map_err(|e| format!("include {}: {}", path.display(), e))?;
+        chain.push(path.clone());
+        resolve_includes(&mut included, path.parent().unwrap_or(dir), chain)?;
+        chain.pop();
+        if let toml::Value::Table(included) = included {
+            merge_tables(&mut base, included);
+        }
+    }
+    merge_tables(&mut base, std::mem::take(root));
+    *root = base;
+    Ok(())
+}
+
+/// Merge the `[profiles.<env>]` table into the rest of `document` and drop
+/// the `[profiles]` section.
+pub(crate) fn apply_profile(
+    document: &mut toml::Value,
+    env: Option<&str>,
+) -> std::result::Result<(), String> {
+    let Some(root) = document.as_table_mut() else {
+        return Ok(());
+    };
+    let mut profiles = match root.remove("profiles") {
+        Some(toml::Value::Table(profiles)) => profiles,
+        Some(_) => return Err("[profiles] must be a table of profiles".to_string()),
+        None => toml::Table::new(),
+    };
+    let Some(env) = env else {
+        return Ok(());
+    };
+    match profiles.remove(env) {
+        Some(toml::Value::Table(profile)) => {
+            merge_tables(root, profile);
+            Ok(())
+        }
+        Some(_) => Err(format!("[profiles.{}] must be a table", env)),
+        None if profiles.is_empty() => Err(format!("unknown profile '{}': none defined", env)),
+        None => Err(format!(
+            "unknown profile '{}'. Defined profiles: {}",
+            env,
+            profiles.keys().cloned().collect::<Vec<_>>().join(", ")
+        )),
+    }
+}
+
+/// Merge `overrides` into `base`: tables key by key, other values replaced.
+fn merge_tables(base: &mut toml::Table, overrides: toml::Table) {
+    for (key, value) in overrides {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(base)), toml::Value::Table(value)) => {
+                merge_tables(base, value)
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+/// Replace `${NAME}` and `${NAME:-default}` in every string of `value`,
+/// looking names up with `lookup`.
+pub(crate) fn interpolate(
+    value: &mut toml::Value,
+    lookup: &dyn Fn(&str) -> Option<String>,
+) -> std::result::Result<(), String> {
+    match value {
+        toml::Value::String(s) if s.contains("${") => *s = interpolate_str(s, lookup)?,
+        toml::Value::Array(items) => {
+            for item in items {
+                interpolate(item, lookup)?;
+            }
+        }
+        toml::Value::Table(table) => {
+            for (_, item) in table.iter_mut() {
+                interpolate(item, lookup)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn interpolate_str(
+    s: &str,
+    lookup: &dyn Fn(&str) -> Option<String>,
+) -> std::result::Result<String, String> {
+    let mut out = String::new();
+    let mut rest = s;
+    while let Some(start) = rest.find("${") {
+        if rest[..start].ends_with('$') {
+            out.push_str(&rest[..start - 1]);
+            out.push_str("${");
+            rest = &rest[start + 2..];
+            continue;
+        }
+        out.push_str(&rest[..start]);
+        let Some(len) = rest[start + 2..].find('}') else {
+            return Err(format!("unclosed ${{ in \"{}\"", s));
+        };
+        let reference = &rest[start + 2..start + 2 + len];
+        let (name, default) = match reference.split_once(":-") {
+            Some((name, default)) => (name, Some(default)),
+            None => (reference, None),
+        };
+        // Like the shell, a default also covers a variable set but empty
+        let value = match default {
+            Some(default) => Some(
+                lookup(name)
+                    .filter(|v| !v.is_empty())
+                    .unwrap_or_else(|| default.to_string()),
+            ),
+            None => lookup(name),
+        };
+        match value {
+            Some(value) => out.push_str(&value),
+            None => return Err(format!("environment variable {} is not set", name)),
+        }
+        rest = &rest[start + 3 + len..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
 impl SeedKitConfig {
+    /// Apply the `[scenario.<name>]` settings on top of the rest of the
+    /// config. Row counts, seed and table filters replace the `[generate]`
+    /// and `[tables]` ones; column overrides replace whole `[columns]`
+    /// entries.
+    pub fn apply_scenario(&mut self, name: &str) -> Result<()> {
+        let Some(scenario) = self.scenario.get(name).cloned() else {
+            let known: Vec<&str> = self.scenario.keys().map(String::as_str).collect();
+            return Err(SeedKitError::Config {
+                message: if known.is_empty() {
+                    format!("Unknown scenario '{}': seedkit.toml defines none.", name)
+                } else {
+                    format!(
+                        "Unknown scenario '{}'. Defined scenarios: {}",
+                        name,
+                        known.join(", ")
+                    )
+                },
+            });
+        };
+
+        if scenario.rows.is_some() {
+            self.generate.rows = scenario.rows;
+        }
+        if scenario.seed.is_some() {
+            self.generate.seed = scenario.seed;
+        }
+        if scenario.include.is_some() {
+            self.generate.include = scenario.include;
+        }
+        if scenario.exclude.is_some() {
+            self.generate.exclude = scenario.exclude;
+        }
+        for (table, tc) in scenario.tables {
+            if tc.rows.is_some() {
+                self.tables.entry(table).or_default().rows = tc.rows;
+            }
+        }
+        self.columns.extend(scenario.columns);
+
+        self.validate().map_err(|e| match e {
+            SeedKitError::Config { message } => SeedKitError::Config {
+                message: format!("[scenario.{}] {}", name, message),
+            },
+            other => other,
+        })
+    }
+
     /// Build table_row_overrides from the [tables] section.
     pub fn table_row_overrides(&self) -> BTreeMap<String, usize> {
         let mut map = BTreeMap::new();
@@ -168,6 +791,245 @@ impl SeedKitConfig {
         map
     }
 
+    /// The [columns] overrides, with relative `custom` provider and
+    /// `json_schema` paths resolved against the directory holding
+    /// seedkit.toml.
+    pub fn column_overrides(&self) -> BTreeMap<String, ColumnConfig> {
+        let mut columns = self.columns.clone();
+        // Columns of a user-defined type take its generator, unless they set
+        // their own
+        for col_cfg in columns.values_mut() {
+            let Some(ty) = col_cfg
+                .semantic_type
+                .as_ref()
+                .and_then(|name| self.types.get(name))
+            else {
+                continue;
+            };
+            let has_generator = col_cfg.values.is_some()
+                || col_cfg.pattern.is_some()
+                || col_cfg.template.is_some()
+                || col_cfg.custom.is_some()
+                || col_cfg.command.is_some();
+            if !has_generator {
+                col_cfg.values = ty.values.clone();
+                col_cfg.weights = ty.weights.clone();
+                col_cfg.pattern = ty.pattern.clone();
+                col_cfg.template = ty.template.clone();
+                col_cfg.custom = ty.custom.clone();
+                col_cfg.command = ty.command.clone();
+            }
+        }
+        if let Some(ref dir) = self.config_dir {
+            for col_cfg in columns.values_mut() {
+                if let Some(ref mut path) = col_cfg.custom {
+                    if Path::new(path).is_relative() {
+                        *path = dir.join(&*path).to_string_lossy().into_owned();
+                    }
+                }
+                if let Some(JsonSchemaSource::Path(ref mut path)) = col_cfg.json_schema {
+                    if Path::new(path).is_relative() {
+                        *path = dir.join(&*path).to_string_lossy().into_owned();
+                    }
+                }
+                if col_cfg.command.is_some() {
+                    col_cfg.command_dir = Some(dir.clone());
+                }
+            }
+        }
+        columns
+    }
+
+    /// Classify columns given a built-in `type`, overriding the rule-based
+    /// (and LLM) classification.
+    pub fn apply_type_overrides(
+        &self,
+        classifications: &mut BTreeMap<(String, String), SemanticType>,
+    ) {
+        for (key, col_cfg) in &self.columns {
+            let Some((table, column)) = key.split_once('.') else {
+                continue;
+            };
+            if let Some(semantic_type) = col_cfg
+                .semantic_type
+                .as_deref()
+                .and_then(SemanticType::from_snake_case)
+            {
+                classifications.insert((table.to_string(), column.to_string()), semantic_type);
+            }
+        }
+    }
+
+    /// Compile the `[[classify]]` rules, in file order.
+    pub fn classification_rules(&self) -> Result<Vec<CustomRule>> {
+        self.classify
+            .iter()
+            .enumerate()
+            .map(|(i, rule)| {
+                let semantic_type =
+                    SemanticType::from_snake_case(&rule.semantic_type).ok_or_else(|| {
+                        if self.types.contains_key(&rule.semantic_type) {
+                            format!(
+                                "'{}' is a custom type; assign it to columns with \
+                                 [columns.\"<table>.<column>\"] type = \"{}\".",
+                                rule.semantic_type, rule.semantic_type
+                            )
+                        } else {
+                            format!(
+                                "unknown type '{}'; use a built-in semantic type in snake_case.",
+                                rule.semantic_type
+                            )
+                        }
+                    });
+                semantic_type
+                    .and_then(|semantic_type| {
+                        CustomRule::new(
+                            &rule.column,
+                            rule.table.as_deref(),
+                            rule.data_types.as_deref(),
+                            semantic_type,
+                        )
+                    })
+                    .map_err(|message| SeedKitError::Config {
+                        message: format!("[[classify]] rule {}: {}", i + 1, message),
+                    })
+            })
+            .collect()
+    }
+
+    /// Tables marked `stable = true` in the [tables] section.
+    pub fn stable_tables(&self) -> Vec<String> {
+        self.tables
+            .iter()
+            .filter(|(_, tc)| tc.stable.unwrap_or(false))
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+
+    /// Lookup overrides from `lookup = "..."` in the [tables] section:
+    /// a dataset to force, or `None` for tables opted out with `"none"`.
+    /// Invalid names are rejected by `validate()` and skipped here.
+    pub fn lookup_overrides(&self) -> BTreeMap<String, Option<LookupDataset>> {
+        self.tables
+            .iter()
+            .filter_map(|(name, tc)| {
+                let dataset = parse_lookup_override(tc.lookup.as_deref()?).ok()?;
+                Some((name.clone(), dataset))
+            })
+            .collect()
+    }
+
+    /// The `[generate] locale` or `locales`, if either is set. Invalid
+    /// values are rejected by `validate()` and skipped here.
+    pub fn locale_mix(&self) -> Option<LocaleMix> {
+        match (&self.generate.locale, &self.generate.locales) {
+            (Some(locale), _) => locale.parse::<Locale>().ok().map(LocaleMix::single),
+            (None, Some(weights)) => LocaleMix::from_weights(weights).ok(),
+            (None, None) => None,
+        }
+    }
+
+    /// The `[generate] language_mix`, applied to tables without their own.
+    /// Invalid mixes are rejected by `validate()` and skipped here.
+    pub fn default_language_mix(&self) -> Option<LanguageMix> {
+        LanguageMix::from_weights(self.generate.language_mix.as_ref()?).ok()
+    }
+
+    /// Per-table `language_mix` overrides from the [tables] section.
+    pub fn language_mixes(&self) -> BTreeMap<String, LanguageMix> {
+        self.tables
+            .iter()
+            .filter_map(|(name, tc)| {
+                let mix = LanguageMix::from_weights(tc.language_mix.as_ref()?).ok()?;
+                Some((name.clone(), mix))
+            })
+            .collect()
+    }
+
+    /// Per-table row `script` paths from the [tables] section, resolved
+    /// against the directory holding seedkit.toml.
+    pub fn row_scripts(&self) -> BTreeMap<String, String> {
+        self.tables
+            .iter()
+            .filter_map(|(name, tc)| {
+                let script = Path::new(tc.script.as_ref()?);
+                let path = match self.config_dir {
+                    Some(ref dir) if script.is_relative() => dir.join(script),
+                    _ => script.to_path_buf(),
+                };
+                Some((name.clone(), path.to_string_lossy().into_owned()))
+            })
+            .collect()
+    }
+
+    /// Polymorphic associations from the [tables] section.
+    pub fn polymorphic_associations(&self) -> Vec<PolymorphicAssociation> {
+        self.tables
+            .iter()
+            .flat_map(|(table, tc)| {
+                tc.polymorphic
+                    .iter()
+                    .flatten()
+                    .map(|(name, targets)| PolymorphicAssociation {
+                        table: table.clone(),
+                        name: name.clone(),
+                        targets: targets.clone(),
+                    })
+            })
+            .collect()
+    }
+
+    /// Per-table `time_series` settings from the [tables] section. Invalid
+    /// settings are rejected by `validate()` and skipped here.
+    pub fn time_series(&self) -> BTreeMap<String, TimeSeries> {
+        self.tables
+            .iter()
+            .filter_map(|(name, tc)| {
+                let ts = tc.time_series.as_ref().filter(|ts| ts.validate().is_ok())?;
+                Some((name.clone(), ts.clone()))
+            })
+            .collect()
+    }
+
+    /// Per-table `funnel` settings from the [tables] section. Invalid
+    /// settings are rejected by `validate()` and skipped here.
+    pub fn funnels(&self) -> BTreeMap<String, Funnel> {
+        self.tables
+            .iter()
+            .filter_map(|(name, tc)| {
+                let funnel = tc.funnel.as_ref().filter(|f| f.validate().is_ok())?;
+                Some((name.clone(), funnel.clone()))
+            })
+            .collect()
+    }
+
+    /// Per-table `segments` from the [tables] section. Invalid settings
+    /// are rejected by `validate()` and skipped here.
+    pub fn segments(&self) -> BTreeMap<String, BTreeMap<String, Segment>> {
+        self.tables
+            .iter()
+            .filter_map(|(name, tc)| {
+                let segments = tc
+                    .segments
+                    .as_ref()
+                    .filter(|s| validate_segments(s).is_ok())?;
+                Some((name.clone(), segments.clone()))
+            })
+            .collect()
+    }
+
+    /// Columns marked for LLM-written text, keyed by `table.column`.
+    /// Invalid settings are rejected by `validate()` and skipped here.
+    pub fn llm_columns(&self) -> BTreeMap<String, LlmContent> {
+        self.columns
+            .iter()
+            .filter_map(|(key, cc)| {
+                let llm = cc.llm.as_ref().filter(|l| l.validate().is_ok())?;
+                Some((key.clone(), llm.clone()))
+            })
+            .collect()
+    }
+
     /// Parse break_cycle_at entries into (table, column) pairs.
     ///
     /// Malformed entries (missing the `table.column` dot separator) are logged
@@ -199,7 +1061,196 @@ impl SeedKitConfig {
     /// (e.g., mismatched `values`/`weights` lengths) before any expensive
     /// database introspection runs.
     pub fn validate(&self) -> Result<()> {
+        if let Some(ref provider) = self.llm.provider {
+            let kind = ProviderKind::parse(provider).map_err(|message| SeedKitError::Config {
+                message: format!("[llm] provider: {}", message),
+            })?;
+            if kind != ProviderKind::Local && self.llm.base_url.is_some() {
+                return Err(SeedKitError::Config {
+                    message: format!(
+                        "[llm] base_url only applies to provider = \"local\", not \"{}\".",
+                        provider
+                    ),
+                });
+            }
+        }
+        if let Some(ref locale) = self.generate.locale {
+            locale
+                .parse::<Locale>()
+                .map_err(|message| SeedKitError::Config { message })?;
+        }
+        if let Some(ref weights) = self.generate.locales {
+            if self.generate.locale.is_some() {
+                return Err(SeedKitError::Config {
+                    message: "[generate] Set either locale or locales, not both.".to_string(),
+                });
+            }
+            LocaleMix::from_weights(weights).map_err(|message| SeedKitError::Config {
+                message: format!("[generate] locales: {}", message),
+            })?;
+        }
+        if let Some(ref weights) = self.generate.language_mix {
+            LanguageMix::from_weights(weights).map_err(|message| SeedKitError::Config {
+                message: format!("[generate] language_mix: {}", message),
+            })?;
+        }
+        for (table, table_cfg) in &self.tables {
+            if let Some(ref weights) = table_cfg.language_mix {
+                LanguageMix::from_weights(weights).map_err(|message| SeedKitError::Config {
+                    message: format!("[tables.{}] language_mix: {}", table, message),
+                })?;
+            }
+            for (name, targets) in table_cfg.polymorphic.iter().flatten() {
+                if targets.is_empty() {
+                    return Err(SeedKitError::Config {
+                        message: format!(
+                            "[tables.{}] polymorphic.{}: needs at least one target table.",
+                            table, name
+                        ),
+                    });
+                }
+            }
+            if let Some(ref ts) = table_cfg.time_series {
+                ts.validate().map_err(|message| SeedKitError::Config {
+                    message: format!("[tables.{}] time_series: {}", table, message),
+                })?;
+            }
+            if let Some(ref funnel) = table_cfg.funnel {
+                funnel.validate().map_err(|message| SeedKitError::Config {
+                    message: format!("[tables.{}] funnel: {}", table, message),
+                })?;
+            }
+            if let Some(ref segments) = table_cfg.segments {
+                validate_segments(segments).map_err(|message| SeedKitError::Config {
+                    message: format!("[tables.{}] segments: {}", table, message),
+                })?;
+            }
+            if let Some(ref lookup) = table_cfg.lookup {
+                parse_lookup_override(lookup).map_err(|message| SeedKitError::Config {
+                    message: format!("[tables.{}] lookup: {}", table, message),
+                })?;
+            }
+        }
+        for (name, ty) in &self.types {
+            validate_type(name, ty).map_err(|message| SeedKitError::Config {
+                message: format!("[types.{}] {}", name, message),
+            })?;
+        }
+        self.classification_rules()?;
         for (key, col_cfg) in &self.columns {
+            if let Some(ref name) = col_cfg.semantic_type {
+                if !self.types.contains_key(name) && SemanticType::from_snake_case(name).is_none() {
+                    return Err(SeedKitError::Config {
+                        message: format!(
+                            "Column '{}': unknown type '{}'. Use a built-in semantic type in \
+                             snake_case or define it under [types.{}].",
+                            key, name, name
+                        ),
+                    });
+                }
+            }
+            if let Some(ref pattern) = col_cfg.pattern {
+                Pattern::parse(pattern).map_err(|message| SeedKitError::Config {
+                    message: format!("Column '{}': {}", key, message),
+                })?;
+            }
+            if let Some(ref template) = col_cfg.template {
+                parse_template(template).map_err(|message| SeedKitError::Config {
+                    message: format!("Column '{}': {}", key, message),
+                })?;
+            }
+            if let Some(ref lifecycle) = col_cfg.lifecycle {
+                lifecycle
+                    .validate()
+                    .map_err(|message| SeedKitError::Config {
+                        message: format!("Column '{}': lifecycle {}", key, message),
+                    })?;
+            }
+            if let Some(ref expr) = col_cfg.expr {
+                check_expression(expr).map_err(|message| SeedKitError::Config {
+                    message: format!("Column '{}': {}", key, message),
+                })?;
+            }
+            if let Some(ref aggregate) = col_cfg.aggregate {
+                parse_aggregate(aggregate).map_err(|message| SeedKitError::Config {
+                    message: format!("Column '{}': {}", key, message),
+                })?;
+            }
+            if let Some(ref source) = col_cfg.json_schema {
+                source
+                    .load(self.config_dir.as_deref())
+                    .and_then(|schema| check_schema(&schema))
+                    .map_err(|message| SeedKitError::Config {
+                        message: format!("Column '{}': json_schema {}", key, message),
+                    })?;
+            }
+            if let Some(ref llm) = col_cfg.llm {
+                llm.validate().map_err(|message| SeedKitError::Config {
+                    message: format!("Column '{}': llm {}", key, message),
+                })?;
+            }
+            if let Some(fan_out) = col_cfg.fan_out {
+                fan_out.validate().map_err(|message| SeedKitError::Config {
+                    message: format!("Column '{}': fan_out {}", key, message),
+                })?;
+            }
+            if col_cfg.command.as_ref().is_some_and(|c| c.is_empty()) {
+                return Err(SeedKitError::Config {
+                    message: format!("Column '{}': command must name a program.", key),
+                });
+            }
+            if let Some((min, max)) = col_cfg.array_length {
+                if min > max {
+                    return Err(SeedKitError::Config {
+                        message: format!(
+                            "Column '{}': array_length minimum {} is greater than maximum {}.",
+                            key, min, max,
+                        ),
+                    });
+                }
+            }
+            if let Some((min, max)) = col_cfg.range_span {
+                if min < 1 || min > max {
+                    return Err(SeedKitError::Config {
+                        message: format!(
+                            "Column '{}': range_span [{}, {}] is invalid. The minimum must be \
+                             at least 1 (ranges must be non-empty) and not exceed the maximum.",
+                            key, min, max,
+                        ),
+                    });
+                }
+            }
+            if let Some((min, max)) = col_cfg.interval_span {
+                if min > max {
+                    return Err(SeedKitError::Config {
+                        message: format!(
+                            "Column '{}': interval_span minimum {} is greater than maximum {}.",
+                            key, min, max,
+                        ),
+                    });
+                }
+            }
+            if let Some((min, max)) = col_cfg.binary_size {
+                if min > max {
+                    return Err(SeedKitError::Config {
+                        message: format!(
+                            "Column '{}': binary_size minimum {} is greater than maximum {}.",
+                            key, min, max,
+                        ),
+                    });
+                }
+            }
+            if let Some((min_x, min_y, max_x, max_y)) = col_cfg.bbox {
+                if min_x >= max_x || min_y >= max_y {
+                    return Err(SeedKitError::Config {
+                        message: format!(
+                            "Column '{}': bbox must be [min_x, min_y, max_x, max_y] \
+                             with min < max on both axes.",
+                            key,
+                        ),
+                    });
+                }
+            }
             if let Some(ref weights) = col_cfg.weights {
                 match col_cfg.values {
                     Some(ref values) => {
@@ -230,7 +1281,8 @@ impl SeedKitConfig {
         Ok(())
     }
 
-    /// Validate column overrides against the introspected schema.
+    /// Validate table and column overrides (scenarios' included) against
+    /// the introspected schema.
     ///
     /// Returns a list of warning messages for overrides that reference
     /// tables or columns not present in the schema. Called after introspection
@@ -240,27 +1292,55 @@ impl SeedKitConfig {
         schema: &crate::schema::types::DatabaseSchema,
     ) -> Vec<String> {
         let mut warnings = Vec::new();
-        for key in self.columns.keys() {
+        let mut check_table = |section: String, table: &str| {
+            if !schema.tables.contains_key(table) {
+                warnings.push(format!(
+                    "seedkit.toml: [{}.{}] references table '{}' \
+                     which does not exist in schema",
+                    section, table, table
+                ));
+            }
+        };
+        for table in self.tables.keys() {
+            check_table("tables".to_string(), table);
+        }
+        for (name, scenario) in &self.scenario {
+            for table in scenario.tables.keys() {
+                check_table(format!("scenario.{}.tables", name), table);
+            }
+        }
+
+        let column_sections = self
+            .columns
+            .keys()
+            .map(|key| ("columns".to_string(), key))
+            .chain(self.scenario.iter().flat_map(|(name, scenario)| {
+                scenario
+                    .columns
+                    .keys()
+                    .map(move |key| (format!("scenario.{}.columns", name), key))
+            }));
+        for (section, key) in column_sections {
             if let Some((table, col)) = key.split_once('.') {
                 if let Some(table_def) = schema.tables.get(table) {
                     if !table_def.columns.contains_key(col) {
                         warnings.push(format!(
-                            "seedkit.toml: [columns.\"{}\"] references column '{}' \
+                            "seedkit.toml: [{}.\"{}\"] references column '{}' \
                              which does not exist in table '{}'",
-                            key, col, table
+                            section, key, col, table
                         ));
                     }
                 } else {
                     warnings.push(format!(
-                        "seedkit.toml: [columns.\"{}\"] references table '{}' \
+                        "seedkit.toml: [{}.\"{}\"] references table '{}' \
                          which does not exist in schema",
-                        key, table
+                        section, key, table
                     ));
                 }
             } else {
                 warnings.push(format!(
-                    "seedkit.toml: [columns.\"{}\"] is not in 'table.column' format",
-                    key
+                    "seedkit.toml: [{}.\"{}\"] is not in 'table.column' format",
+                    section, key
                 ));
             }
         }
@@ -340,6 +1420,45 @@ break_cycle_at = ["users.invited_by_id", "comments.parent_id"]
         assert!(config.graph.break_cycle_at.is_empty());
     }
 
+    #[test]
+    fn test_parse_llm_config() {
+        let config: SeedKitConfig = toml::from_str(
+            r#"
+[llm]
+provider = "anthropic"
+model = "claude-opus-4-20250514"
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.llm.provider.as_deref(), Some("anthropic"));
+        assert_eq!(config.llm.model.as_deref(), Some("claude-opus-4-20250514"));
+        assert!(config.validate().is_ok());
+
+        let config: SeedKitConfig = toml::from_str("[llm]\nprovider = \"gemini\"\n").unwrap();
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("[llm] provider"), "{}", err);
+
+        let config: SeedKitConfig = toml::from_str(
+            "[llm]\nprovider = \"ollama\"\nbase_url = \"http://localhost:11434/v1\"\n",
+        )
+        .unwrap();
+        assert!(config.validate().is_ok());
+        let config: SeedKitConfig = toml::from_str(
+            "[llm]\nprovider = \"openai\"\nbase_url = \"http://localhost:8000/v1\"\n",
+        )
+        .unwrap();
+        assert!(config.validate().is_err());
+
+        let config: SeedKitConfig = toml::from_str(
+            "[llm]\nmax_tokens_per_run = 50000\nmax_requests = 5\nover_budget = \"rule-based\"\n",
+        )
+        .unwrap();
+        assert_eq!(config.llm.max_tokens_per_run, Some(50000));
+        assert_eq!(config.llm.max_requests, Some(5));
+        assert_eq!(config.llm.over_budget, Some(OverBudget::RuleBased));
+        assert!(toml::from_str::<SeedKitConfig>("[llm]\nover_budget = \"maybe\"\n").is_err());
+    }
+
     #[test]
     fn test_parse_minimal_config() {
         let toml = r#"
@@ -373,11 +1492,145 @@ rows = 5000
     }
 
     #[test]
-    fn test_cycle_break_edges() {
-        let toml = r#"
-[graph]
-break_cycle_at = ["users.invited_by_id", "comments.parent_id", "invalid"]
-"#;
+    fn test_validate_locale() {
+        let config: SeedKitConfig = toml::from_str("[generate]\nlocale = \"el\"").unwrap();
+        assert!(config.validate().is_ok());
+
+        let config: SeedKitConfig = toml::from_str("[generate]\nlocale = \"xx\"").unwrap();
+        let msg = format!("{}", config.validate().unwrap_err());
+        assert!(msg.contains("Unsupported locale"), "{}", msg);
+    }
+
+    #[test]
+    fn test_validate_locales() {
+        let config: SeedKitConfig =
+            toml::from_str("[generate]\nlocales = { en = 0.6, de = 0.3, ja = 0.1 }").unwrap();
+        assert!(config.validate().is_ok());
+        let mix = config.locale_mix().unwrap();
+        assert_eq!(mix.to_string(), "de=0.3,en=0.6,ja=0.1");
+
+        let config: SeedKitConfig =
+            toml::from_str("[generate]\nlocale = \"ja\"\nlocales = { en = 1.0 }").unwrap();
+        let msg = format!("{}", config.validate().unwrap_err());
+        assert!(msg.contains("not both"), "{}", msg);
+
+        let config: SeedKitConfig =
+            toml::from_str("[generate]\nlocales = { en = 0.5, xx = 0.5 }").unwrap();
+        let msg = format!("{}", config.validate().unwrap_err());
+        assert!(msg.contains("[generate] locales"), "{}", msg);
+    }
+
+    #[test]
+    fn test_stable_tables() {
+        let toml = r#"
+[tables.products]
+stable = true
+
+[tables.orders]
+rows = 5000
+stable = false
+"#;
+        let config: SeedKitConfig = toml::from_str(toml).unwrap();
+        assert_eq!(config.stable_tables(), vec!["products".to_string()]);
+    }
+
+    #[test]
+    fn test_lookup_overrides() {
+        let toml = r#"
+[tables.regions]
+lookup = "countries"
+
+[tables.order_statuses]
+lookup = "none"
+"#;
+        let config: SeedKitConfig = toml::from_str(toml).unwrap();
+        assert!(config.validate().is_ok());
+        let overrides = config.lookup_overrides();
+        assert_eq!(overrides["regions"], Some(LookupDataset::Countries));
+        assert_eq!(overrides["order_statuses"], None);
+
+        let config: SeedKitConfig =
+            toml::from_str("[tables.planets]\nlookup = \"planets\"").unwrap();
+        let msg = format!("{}", config.validate().unwrap_err());
+        assert!(msg.contains("Unknown lookup dataset"), "{}", msg);
+    }
+
+    #[test]
+    fn test_column_overrides_resolve_custom_paths() {
+        let toml = r#"
+[columns."orders.tax_code"]
+custom = "./providers/tax_code.wasm"
+
+[columns."orders.region"]
+custom = "/opt/providers/region.wasm"
+
+[columns."orders.note"]
+command = ["python3", "providers/note.py"]
+"#;
+        let mut config: SeedKitConfig = toml::from_str(toml).unwrap();
+        config.config_dir = Some(PathBuf::from("/srv/app"));
+        let columns = config.column_overrides();
+        assert_eq!(
+            columns["orders.note"].command_dir.as_deref(),
+            Some(Path::new("/srv/app"))
+        );
+        assert_eq!(
+            columns["orders.tax_code"].custom.as_deref().map(Path::new),
+            Some(Path::new("/srv/app/providers/tax_code.wasm"))
+        );
+        assert_eq!(
+            columns["orders.region"].custom.as_deref(),
+            Some("/opt/providers/region.wasm")
+        );
+    }
+
+    #[test]
+    fn test_row_scripts_resolve_against_config_dir() {
+        let toml = r#"
+[tables.orders]
+script = "scripts/orders.rhai"
+
+[tables.users]
+rows = 10
+"#;
+        let mut config: SeedKitConfig = toml::from_str(toml).unwrap();
+        config.config_dir = Some(PathBuf::from("/srv/app"));
+        let scripts = config.row_scripts();
+        assert_eq!(scripts.len(), 1);
+        assert_eq!(
+            Path::new(&scripts["orders"]),
+            Path::new("/srv/app/scripts/orders.rhai")
+        );
+    }
+
+    #[test]
+    fn test_language_mixes() {
+        let toml = r#"
+[generate]
+language_mix = { en = 0.95, fr = 0.05 }
+
+[tables.campaigns]
+language_mix = { en = 0.9, de = 0.05, ja = 0.05 }
+"#;
+        let config: SeedKitConfig = toml::from_str(toml).unwrap();
+        assert!(config.validate().is_ok());
+        assert!(config.default_language_mix().is_some());
+        let mixes = config.language_mixes();
+        assert_eq!(mixes.len(), 1);
+        assert!(mixes.contains_key("campaigns"));
+
+        let config: SeedKitConfig =
+            toml::from_str("[tables.posts]\nlanguage_mix = { tlh = 1.0 }").unwrap();
+        let msg = format!("{}", config.validate().unwrap_err());
+        assert!(msg.contains("[tables.posts] language_mix"), "{}", msg);
+    }
+
+    #[test]
+    fn test_cycle_break_edges() {
+        let toml = r#"
+[graph]
+break_cycle_at = ["users.invited_by_id", "comments.parent_id", "invalid"]
+"#;
 
         let config: SeedKitConfig = toml::from_str(toml).unwrap();
         let edges = config.cycle_break_edges();
@@ -421,6 +1674,123 @@ rows = 200
         assert_eq!(config.generate.rows, Some(200));
     }
 
+    #[test]
+    fn test_profiles_and_interpolation() {
+        let text = r#"
+[database]
+url = "${APP_DB:-postgres://localhost/app}"
+
+[generate]
+rows = 500
+seed = 42
+
+[tables.users]
+rows = 1000
+stable = true
+
+[profiles.ci.generate]
+rows = 10
+
+[profiles.ci.tables.users]
+rows = 5
+
+[profiles.staging.database]
+url = "postgres://${DB_HOST}/app?note=$${literal}"
+"#;
+        let base = parse_config(text, Path::new("."), None).unwrap();
+        assert_eq!(base.generate.rows, Some(500));
+        assert_eq!(
+            base.database.url.as_deref(),
+            std::env::var("APP_DB")
+                .ok()
+                .filter(|v| !v.is_empty())
+                .as_deref()
+                .or(Some("postgres://localhost/app"))
+        );
+
+        let ci = parse_config(text, Path::new("."), Some("ci")).unwrap();
+        assert_eq!(ci.generate.rows, Some(10));
+        assert_eq!(ci.generate.seed, Some(42));
+        assert_eq!(ci.tables["users"].rows, Some(5));
+        assert_eq!(ci.tables["users"].stable, Some(true));
+
+        let mut document: toml::Value = toml::from_str(text).unwrap();
+        apply_profile(&mut document, Some("staging")).unwrap();
+        assert!(document.get("profiles").is_none());
+        let lookup = |name: &str| (name == "DB_HOST").then(|| "db.internal".to_string());
+        interpolate(&mut document, &lookup).unwrap();
+        assert_eq!(
+            document["database"]["url"].as_str(),
+            Some("postgres://db.internal/app?note=${literal}")
+        );
+        let mut document: toml::Value = toml::from_str(text).unwrap();
+        apply_profile(&mut document, Some("staging")).unwrap();
+        let msg = interpolate(&mut document, &|_| None).unwrap_err();
+        assert!(msg.contains("DB_HOST is not set"), "{}", msg);
+
+        let msg = parse_config(text, Path::new("."), Some("prod")).unwrap_err();
+        assert!(msg.contains("Defined profiles: ci, staging"), "{}", msg);
+    }
+
+    #[test]
+    fn test_config_includes() {
+        let root = tempfile::tempdir().unwrap();
+        std::fs::write(
+            root.path().join("seedkit.base.toml"),
+            r#"
+[generate]
+rows = 500
+seed = 42
+
+[tables.users]
+rows = 1000
+stable = true
+
+[columns."users.plan"]
+values = ["free", "pro"]
+weights = [0.8, 0.2]
+"#,
+        )
+        .unwrap();
+        let service = root.path().join("billing");
+        std::fs::create_dir(&service).unwrap();
+        std::fs::write(
+            service.join(CONFIG_FILE_NAME),
+            r#"
+include = ["../seedkit.base.toml"]
+
+[generate]
+rows = 20
+
+[tables.users]
+rows = 50
+"#,
+        )
+        .unwrap();
+
+        let config = read_config(&service).unwrap().unwrap();
+        assert_eq!(config.generate.rows, Some(20));
+        assert_eq!(config.generate.seed, Some(42));
+        assert_eq!(config.tables["users"].rows, Some(50));
+        assert_eq!(config.tables["users"].stable, Some(true));
+        assert_eq!(
+            config.columns["users.plan"].values.as_ref().unwrap().len(),
+            2
+        );
+
+        // A file that includes itself, directly or not, is an error
+        std::fs::write(
+            root.path().join("seedkit.base.toml"),
+            "include = [\"billing/seedkit.toml\"]\n",
+        )
+        .unwrap();
+        let err = read_config(&service).unwrap_err().to_string();
+        assert!(err.contains("include cycle"), "{}", err);
+
+        let msg = parse_config("include = [\"missing.toml\"]", root.path(), None).unwrap_err();
+        assert!(msg.contains("missing.toml"), "{}", msg);
+    }
+
     #[test]
     fn test_read_config_invalid_toml() {
         let dir = tempfile::tempdir().unwrap();
@@ -541,6 +1911,460 @@ values = ["red", "blue", "green"]
         assert!(config.validate().is_ok());
     }
 
+    #[test]
+    fn test_validate_array_length() {
+        let toml = r#"
+[columns."posts.tags"]
+array_length = [0, 8]
+"#;
+        let config: SeedKitConfig = toml::from_str(toml).unwrap();
+        assert_eq!(config.columns["posts.tags"].array_length, Some((0, 8)));
+        assert!(config.validate().is_ok());
+
+        let toml = r#"
+[columns."posts.tags"]
+array_length = [5, 2]
+"#;
+        let config: SeedKitConfig = toml::from_str(toml).unwrap();
+        let msg = format!("{}", config.validate().unwrap_err());
+        assert!(
+            msg.contains("posts.tags"),
+            "Error should name the column: {}",
+            msg
+        );
+    }
+
+    #[test]
+    fn test_validate_command() {
+        let toml = r#"
+[columns."users.nickname"]
+command = ["python3", "providers/nickname.py"]
+"#;
+        let config: SeedKitConfig = toml::from_str(toml).unwrap();
+        assert_eq!(
+            config.columns["users.nickname"].command.as_deref(),
+            Some(&["python3".to_string(), "providers/nickname.py".to_string()][..])
+        );
+        assert!(config.validate().is_ok());
+
+        let toml = r#"
+[columns."users.nickname"]
+command = []
+"#;
+        let config: SeedKitConfig = toml::from_str(toml).unwrap();
+        let msg = format!("{}", config.validate().unwrap_err());
+        assert!(msg.contains("users.nickname"), "{}", msg);
+    }
+
+    #[test]
+    fn test_polymorphic_associations() {
+        let toml = r#"
+[tables.comments.polymorphic]
+commentable = { Post = "posts", Photo = "photos" }
+"#;
+        let config: SeedKitConfig = toml::from_str(toml).unwrap();
+        assert!(config.validate().is_ok());
+        let associations = config.polymorphic_associations();
+        assert_eq!(associations.len(), 1);
+        assert_eq!(associations[0].table, "comments");
+        assert_eq!(associations[0].type_column(), "commentable_type");
+        assert_eq!(associations[0].targets["Photo"], "photos");
+
+        let config: SeedKitConfig =
+            toml::from_str("[tables.comments.polymorphic]\ncommentable = {}").unwrap();
+        let msg = format!("{}", config.validate().unwrap_err());
+        assert!(msg.contains("polymorphic.commentable"), "{}", msg);
+    }
+
+    #[test]
+    fn test_time_series() {
+        let toml = r#"
+[tables.metrics.time_series]
+interval = "5 minutes"
+series_by = "device_id"
+"#;
+        let config: SeedKitConfig = toml::from_str(toml).unwrap();
+        assert!(config.validate().is_ok());
+        let series = config.time_series();
+        assert_eq!(series["metrics"].series_by.as_deref(), Some("device_id"));
+
+        let toml = "[tables.metrics.time_series]\ninterval = \"often\"";
+        let config: SeedKitConfig = toml::from_str(toml).unwrap();
+        let msg = format!("{}", config.validate().unwrap_err());
+        assert!(msg.contains("[tables.metrics] time_series"), "{}", msg);
+        assert!(config.time_series().is_empty());
+    }
+
+    #[test]
+    fn test_apply_scenario() {
+        let toml = r#"
+[generate]
+rows = 500
+seed = 42
+
+[tables.users]
+rows = 1000
+stable = true
+
+[columns."users.plan"]
+values = ["free", "pro"]
+weights = [0.8, 0.2]
+
+[scenario.demo]
+rows = 20
+exclude = ["audit_logs"]
+
+[scenario.demo.tables.users]
+rows = 5
+
+[scenario.demo.columns."users.plan"]
+values = ["pro"]
+"#;
+        let base: SeedKitConfig = toml::from_str(toml).unwrap();
+        let mut config = base.clone();
+        config.apply_scenario("demo").unwrap();
+        assert_eq!(config.generate.rows, Some(20));
+        assert_eq!(config.generate.seed, Some(42));
+        assert_eq!(
+            config.generate.exclude,
+            Some(vec!["audit_logs".to_string()])
+        );
+        assert_eq!(config.table_row_overrides()["users"], 5);
+        assert_eq!(config.tables["users"].stable, Some(true));
+        let plan = &config.column_overrides()["users.plan"];
+        assert_eq!(plan.values.as_ref().unwrap().len(), 1);
+        assert!(plan.weights.is_none());
+
+        let mut config = base.clone();
+        let msg = format!("{}", config.apply_scenario("load").unwrap_err());
+        assert!(msg.contains("Defined scenarios: demo"), "{}", msg);
+
+        // Scenario column overrides are validated like top-level ones
+        let toml = "[scenario.bad.columns.\"users.plan\"]\nvalues = [\"a\"]\nweights = [0.5, 0.5]";
+        let mut config: SeedKitConfig = toml::from_str(toml).unwrap();
+        let msg = format!("{}", config.apply_scenario("bad").unwrap_err());
+        assert!(msg.contains("[scenario.bad]"), "{}", msg);
+    }
+
+    #[test]
+    fn test_funnels() {
+        let toml = r#"
+[tables.events.funnel]
+steps = ["signup", "activate", "purchase"]
+drop_off = [0.4, 0.7]
+"#;
+        let config: SeedKitConfig = toml::from_str(toml).unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(config.funnels()["events"].steps.len(), 3);
+
+        let toml = "[tables.events.funnel]\nsteps = [\"signup\"]\ndrop_off = [0.5]";
+        let config: SeedKitConfig = toml::from_str(toml).unwrap();
+        let msg = format!("{}", config.validate().unwrap_err());
+        assert!(msg.contains("[tables.events] funnel"), "{}", msg);
+        assert!(config.funnels().is_empty());
+    }
+
+    #[test]
+    fn test_segments() {
+        let toml = r#"
+[tables.users.segments.power]
+share = 0.05
+activity = 50
+columns = { plan = ["pro", "enterprise"] }
+
+[tables.users.segments.dormant]
+share = 0.2
+activity = 0
+"#;
+        let config: SeedKitConfig = toml::from_str(toml).unwrap();
+        assert!(config.validate().is_ok());
+        let segments = &config.segments()["users"];
+        assert_eq!(segments["power"].activity, 50.0);
+        assert_eq!(segments["dormant"].columns.len(), 0);
+
+        let toml = "[tables.users.segments.power]\nshare = 1.5";
+        let config: SeedKitConfig = toml::from_str(toml).unwrap();
+        let msg = format!("{}", config.validate().unwrap_err());
+        assert!(msg.contains("[tables.users] segments: power"), "{}", msg);
+        assert!(config.segments().is_empty());
+    }
+
+    #[test]
+    fn test_validate_fan_out() {
+        let toml = r#"
+[columns."orders.user_id"]
+fan_out = { zipf = 1.2 }
+
+[columns."orders.store_id"]
+fan_out = "uniform"
+
+[columns."order_items.order_id"]
+fan_out = { children = [1, 5] }
+"#;
+        let config: SeedKitConfig = toml::from_str(toml).unwrap();
+        assert_eq!(
+            config.columns["orders.user_id"].fan_out,
+            Some(FanOut::Zipf(1.2))
+        );
+        assert_eq!(
+            config.columns["orders.store_id"].fan_out,
+            Some(FanOut::Uniform)
+        );
+        assert_eq!(
+            config.columns["order_items.order_id"].fan_out,
+            Some(FanOut::Children(1, 5))
+        );
+        assert!(config.validate().is_ok());
+
+        let toml = "[columns.\"orders.user_id\"]\nfan_out = { zipf = -1.0 }";
+        let config: SeedKitConfig = toml::from_str(toml).unwrap();
+        let msg = format!("{}", config.validate().unwrap_err());
+        assert!(msg.contains("orders.user_id"), "{}", msg);
+    }
+
+    #[test]
+    #[cfg(feature = "rhai")]
+    fn test_validate_derived_columns() {
+        let toml = r#"
+[columns."orders.total"]
+aggregate = "sum(order_items.unit_price * quantity)"
+
+[columns."order_items.line_total"]
+expr = "quantity * unit_price"
+"#;
+        let config: SeedKitConfig = toml::from_str(toml).unwrap();
+        assert!(config.validate().is_ok());
+
+        let toml = "[columns.\"orders.total\"]\naggregate = \"total(order_items.price)\"";
+        let config: SeedKitConfig = toml::from_str(toml).unwrap();
+        let msg = format!("{}", config.validate().unwrap_err());
+        assert!(msg.contains("orders.total"), "{}", msg);
+
+        let toml = "[columns.\"order_items.line_total\"]\nexpr = \"quantity *\"";
+        let config: SeedKitConfig = toml::from_str(toml).unwrap();
+        let msg = format!("{}", config.validate().unwrap_err());
+        assert!(msg.contains("order_items.line_total"), "{}", msg);
+    }
+
+    #[test]
+    fn test_user_defined_types() {
+        let toml = r#"
+[types.icd10_code]
+pattern = "[A-TV-Z][0-9]{2}"
+
+[types.flight_number]
+values = ["LH400", "BA117"]
+weights = [0.7, 0.3]
+
+[columns."claims.diagnosis"]
+type = "icd10_code"
+
+[columns."claims.secondary"]
+type = "icd10_code"
+values = ["Z00"]
+
+[columns."users.contact"]
+type = "email"
+"#;
+        let config: SeedKitConfig = toml::from_str(toml).unwrap();
+        assert!(config.validate().is_ok());
+
+        let overrides = config.column_overrides();
+        assert_eq!(
+            overrides["claims.diagnosis"].pattern.as_deref(),
+            Some("[A-TV-Z][0-9]{2}")
+        );
+        // A column's own generator wins over its type's
+        assert!(overrides["claims.secondary"].pattern.is_none());
+        assert!(overrides["users.contact"].values.is_none());
+
+        let mut classifications = BTreeMap::new();
+        config.apply_type_overrides(&mut classifications);
+        assert_eq!(
+            classifications,
+            BTreeMap::from([(
+                ("users".to_string(), "contact".to_string()),
+                SemanticType::Email
+            )])
+        );
+
+        for (toml, expected) in [
+            (
+                "[columns.\"a.b\"]\ntype = \"flight_number\"\n",
+                "unknown type",
+            ),
+            ("[types.email]\npattern = \"x\"\n", "built-in"),
+            ("[types.FlightNo]\npattern = \"x\"\n", "snake_case"),
+            (
+                "[types.code]\npattern = \"x\"\nvalues = [\"y\"]\n",
+                "exactly one",
+            ),
+            ("[types.code]\npattern = \"[A-\"\n", "invalid pattern"),
+            ("[columns.\"a.b\"]\npattern = \"(\"\n", "invalid pattern"),
+        ] {
+            let config: SeedKitConfig = toml::from_str(toml).unwrap();
+            let err = config.validate().unwrap_err().to_string();
+            assert!(err.contains(expected), "{}: {}", toml, err);
+        }
+    }
+
+    #[test]
+    fn test_classify_rules() {
+        let toml = r#"
+[[classify]]
+column = "^cust_nm$"
+type = "full_name"
+
+[[classify]]
+column = "^addr[0-9]$"
+table = "^crm_"
+data_types = ["varchar", "text"]
+type = "street_address"
+"#;
+        let config: SeedKitConfig = toml::from_str(toml).unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(config.classification_rules().unwrap().len(), 2);
+
+        for (toml, expected) in [
+            (
+                "[[classify]]\ncolumn = \"x\"\ntype = \"nickname\"\n",
+                "unknown type",
+            ),
+            (
+                "[types.code]\npattern = \"x\"\n[[classify]]\ncolumn = \"x\"\ntype = \"code\"\n",
+                "custom type",
+            ),
+            (
+                "[[classify]]\ncolumn = \"(\"\ntype = \"email\"\n",
+                "invalid regex",
+            ),
+            (
+                "[[classify]]\ncolumn = \"x\"\ntype = \"email\"\ndata_types = [\"strng\"]\n",
+                "unknown data type",
+            ),
+        ] {
+            let config: SeedKitConfig = toml::from_str(toml).unwrap();
+            let err = config.validate().unwrap_err().to_string();
+            assert!(err.contains("rule 1") && err.contains(expected), "{}", err);
+        }
+    }
+
+    #[test]
+    fn test_llm_columns() {
+        let toml = r#"
+[columns."products.description".llm]
+prompt = "A two-sentence description of a kitchenware product"
+pool = 40
+"#;
+        let config: SeedKitConfig = toml::from_str(toml).unwrap();
+        assert!(config.validate().is_ok());
+        let columns = config.llm_columns();
+        assert_eq!(columns["products.description"].pool_size(), 40);
+
+        let toml = "[columns.\"products.description\".llm]\nprompt = \"\"";
+        let config: SeedKitConfig = toml::from_str(toml).unwrap();
+        let msg = format!("{}", config.validate().unwrap_err());
+        assert!(msg.contains("products.description"), "{}", msg);
+        assert!(config.llm_columns().is_empty());
+    }
+
+    #[test]
+    fn test_validate_json_schema() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("settings.json"),
+            r#"{"type": "object", "properties": {"theme": {"enum": ["light", "dark"]}}}"#,
+        )
+        .unwrap();
+        let toml = r#"
+[columns."users.settings"]
+json_schema = "settings.json"
+
+[columns."events.payload"]
+json_schema = { type = "object", required = ["kind"], properties = { kind = { type = "string" } } }
+"#;
+        let mut config: SeedKitConfig = toml::from_str(toml).unwrap();
+        config.config_dir = Some(dir.path().to_path_buf());
+        assert!(config.validate().is_ok());
+        let columns = config.column_overrides();
+        let schema = columns["users.settings"]
+            .json_schema
+            .as_ref()
+            .unwrap()
+            .load(None)
+            .unwrap();
+        assert_eq!(schema["properties"]["theme"]["enum"][1], "dark");
+
+        for toml in [
+            "[columns.\"users.settings\"]\njson_schema = \"missing.json\"",
+            "[columns.\"users.settings\"]\njson_schema = { type = \"decimal\" }",
+        ] {
+            let mut config: SeedKitConfig = toml::from_str(toml).unwrap();
+            config.config_dir = Some(dir.path().to_path_buf());
+            let msg = format!("{}", config.validate().unwrap_err());
+            assert!(msg.contains("users.settings"), "{}", msg);
+        }
+    }
+
+    #[test]
+    fn test_validate_range_span() {
+        let toml = r#"
+[columns."bookings.during"]
+range_span = [1, 14]
+"#;
+        let config: SeedKitConfig = toml::from_str(toml).unwrap();
+        assert_eq!(config.columns["bookings.during"].range_span, Some((1, 14)));
+        assert!(config.validate().is_ok());
+
+        let toml = r#"
+[columns."bookings.during"]
+range_span = [0, 14]
+"#;
+        let config: SeedKitConfig = toml::from_str(toml).unwrap();
+        let msg = format!("{}", config.validate().unwrap_err());
+        assert!(msg.contains("range_span"));
+    }
+
+    #[test]
+    fn test_validate_binary_size() {
+        let toml = r#"
+[columns."attachments.payload"]
+binary_size = [256, 4096]
+"#;
+        let config: SeedKitConfig = toml::from_str(toml).unwrap();
+        assert_eq!(
+            config.columns["attachments.payload"].binary_size,
+            Some((256, 4096))
+        );
+        assert!(config.validate().is_ok());
+
+        let toml = r#"
+[columns."attachments.payload"]
+binary_size = [64, 16]
+"#;
+        let config: SeedKitConfig = toml::from_str(toml).unwrap();
+        let msg = format!("{}", config.validate().unwrap_err());
+        assert!(msg.contains("binary_size"));
+    }
+
+    #[test]
+    fn test_validate_bbox() {
+        let toml = r#"
+[columns."stores.location"]
+bbox = [-74.26, 40.49, -73.70, 40.92]
+"#;
+        let config: SeedKitConfig = toml::from_str(toml).unwrap();
+        assert!(config.validate().is_ok());
+
+        let toml = r#"
+[columns."stores.location"]
+bbox = [-73.70, 40.49, -74.26, 40.92]
+"#;
+        let config: SeedKitConfig = toml::from_str(toml).unwrap();
+        let msg = format!("{}", config.validate().unwrap_err());
+        assert!(msg.contains("bbox"));
+    }
+
     #[test]
     fn test_validate_no_columns_ok() {
         let toml = r#"
@@ -603,6 +2427,39 @@ values = ["x"]
         );
     }
 
+    #[test]
+    fn test_validate_against_schema_tables_and_scenarios() {
+        let toml = r#"
+[tables.ghosts]
+rows = 5
+
+[scenario.demo.tables.phantoms]
+rows = 1
+
+[scenario.demo.columns."ghosts.name"]
+values = ["x"]
+"#;
+        let config: SeedKitConfig = toml::from_str(toml).unwrap();
+        let schema = crate::schema::types::DatabaseSchema::new(
+            crate::schema::types::DatabaseType::PostgreSQL,
+            "test".to_string(),
+        );
+
+        let warnings = config.validate_against_schema(&schema);
+        assert_eq!(warnings.len(), 3, "{:?}", warnings);
+        assert!(warnings[0].contains("[tables.ghosts]"), "{}", warnings[0]);
+        assert!(
+            warnings[1].contains("[scenario.demo.tables.phantoms]"),
+            "{}",
+            warnings[1]
+        );
+        assert!(
+            warnings[2].contains("[scenario.demo.columns.\"ghosts.name\"]"),
+            "{}",
+            warnings[2]
+        );
+    }
+
     #[test]
     fn test_validate_against_schema_nonexistent_column() {
         use crate::schema::types::*;
@@ -0,0 +1,472 @@
+//! # Config Schema
+//!
+//! Most `seedkit.toml` sections ignore keys they don't know, so a typo like
+//! `[generate] row = 500` or `[columns."users.plan"] weight = [...]` is
+//! silently dropped. This module describes every key as a JSON Schema —
+//! written by `seedkit config schema` for editor completion — and checks
+//! a config file's keys against it (`seedkit config validate`), suggesting
+//! the key that was probably meant.
+
+use std::path::Path;
+
+use serde_json::{json, Value};
+
+use crate::config::{apply_profile, resolve_includes, SeedKitConfig};
+use crate::output::starter_config::toml_key;
+
+const JSON_SCHEMA_DRAFT: &str = "http://json-schema.org/draft-07/schema#";
+
+/// An object with exactly these keys.
+fn object(properties: Value) -> Value {
+    json!({ "type": "object", "properties": properties, "additionalProperties": false })
+}
+
+/// A table keyed by name, every value matching `value`.
+fn map_of(value: Value) -> Value {
+    json!({ "type": "object", "additionalProperties": value })
+}
+
+fn string() -> Value {
+    json!({ "type": "string" })
+}
+
+fn strings() -> Value {
+    json!({ "type": "array", "items": { "type": "string" } })
+}
+
+fn numbers() -> Value {
+    json!({ "type": "array", "items": { "type": "number" } })
+}
+
+fn integer() -> Value {
+    json!({ "type": "integer", "minimum": 0 })
+}
+
+/// Fixed-length arrays of `item`, e.g. `[min, max]`.
+fn tuple(item: &str, len: usize) -> Value {
+    json!({ "type": "array", "items": { "type": item }, "minItems": len, "maxItems": len })
+}
+
+fn described(mut schema: Value, description: &str) -> Value {
+    schema["description"] = Value::String(description.to_string());
+    schema
+}
+
+/// Generator keys shared by `[columns]` entries and `[types]`.
+fn generator_properties() -> serde_json::Map<String, Value> {
+    let mut properties = serde_json::Map::new();
+    properties.insert(
+        "values".into(),
+        described(strings(), "Fixed list of values to pick from."),
+    );
+    properties.insert(
+        "weights".into(),
+        described(numbers(), "Weight of each value, as many as values."),
+    );
+    properties.insert(
+        "pattern".into(),
+        described(string(), "Regular expression generated values match."),
+    );
+    properties.insert(
+        "template".into(),
+        described(
+            string(),
+            "Template rendered from the row, e.g. \"{first_name}.{last_name}@{company_domain}\".",
+        ),
+    );
+    properties.insert(
+        "custom".into(),
+        described(string(), "Path to a custom JS or WASM provider."),
+    );
+    properties.insert(
+        "command".into(),
+        described(
+            strings(),
+            "External provider process and its arguments, speaking JSON over stdin/stdout.",
+        ),
+    );
+    properties
+}
+
+fn column_schema() -> Value {
+    let mut properties = generator_properties();
+    let more = json!({
+        "type": described(string(), "Semantic type in snake_case (email, company_name) or one defined under [types]."),
+        "lifecycle": object(json!({
+            "states": strings(),
+            "weights": numbers(),
+            "timestamps": map_of(string()),
+        })),
+        "expr": described(string(), "Expression over the row's other columns, e.g. \"quantity * unit_price\"."),
+        "aggregate": described(string(), "Aggregate over child rows, e.g. \"sum(order_items.unit_price * quantity)\"."),
+        "json_schema": described(
+            json!({ "anyOf": [string(), { "type": "object" }] }),
+            "JSON Schema for JSON/JSONB documents: a file relative to seedkit.toml, or an inline table.",
+        ),
+        "llm": object(json!({
+            "prompt": string(),
+            "pool": integer(),
+        })),
+        "array_length": described(tuple("integer", 2), "Element count [min, max] for array columns."),
+        "range_span": described(tuple("integer", 2), "Span [min, max] between the bounds of range columns."),
+        "interval_span": described(tuple("integer", 2), "Length [min, max] in seconds for interval columns."),
+        "binary_size": described(tuple("integer", 2), "Payload size [min, max] in bytes for binary columns."),
+        "bbox": described(tuple("number", 4), "Bounding box [min_x, min_y, max_x, max_y] for PostGIS columns."),
+        "follow_lat_lng": { "type": "boolean" },
+        "fan_out": described(
+            json!({ "anyOf": [
+                { "enum": ["uniform"] },
+                {
+                    "type": "object",
+                    "properties": { "zipf": { "type": "number" }, "children": tuple("integer", 2) },
+                    "additionalProperties": false,
+                    "maxProperties": 1,
+                },
+            ] }),
+            "How rows spread over parent rows: \"uniform\", { zipf = 1.2 } or { children = [min, max] }.",
+        ),
+    });
+    properties.extend(more.as_object().cloned().unwrap_or_default());
+    object(Value::Object(properties))
+}
+
+fn table_schema() -> Value {
+    object(json!({
+        "rows": described(integer(), "Number of rows to generate for this table."),
+        "stable": described(json!({ "type": "boolean" }), "Generate the rows once and replay them from seedkit.lock."),
+        "lookup": described(string(), "Curated dataset (countries, currencies, languages, statuses), or none."),
+        "language_mix": map_of(json!({ "type": "number" })),
+        "script": described(string(), "Rhai script run over every generated row."),
+        "polymorphic": map_of(map_of(string())),
+        "time_series": object(json!({
+            "timestamp": string(),
+            "interval": string(),
+            "jitter": { "type": "number" },
+            "series_by": string(),
+            "value": string(),
+            "trend": { "type": "number" },
+            "seasonality": object(json!({ "period": string(), "amplitude": { "type": "number" } })),
+            "noise": { "type": "number" },
+        })),
+        "funnel": object(json!({
+            "steps": strings(),
+            "drop_off": numbers(),
+            "step_gap": string(),
+            "event_column": string(),
+            "user_column": string(),
+            "session_column": string(),
+            "timestamp": string(),
+        })),
+        "segments": described(
+            map_of(object(json!({
+                "share": described(json!({ "type": "number" }), "Share of the table's rows in the segment."),
+                "activity": described(json!({ "type": "number" }), "Child rows per row, relative to a regular row."),
+                "columns": map_of(strings()),
+            }))),
+            "Population segments: shares of the rows with their own column values and activity.",
+        ),
+    }))
+}
+
+/// The JSON Schema of `seedkit.toml`.
+pub fn config_json_schema() -> Value {
+    let mut schema = object(json!({
+        "database": object(json!({
+            "url": described(string(), "Database URL, e.g. postgres://localhost/myapp."),
+            "schema": described(string(), "Schema to introspect."),
+        })),
+        "generate": object(json!({
+            "rows": described(integer(), "Default number of rows per table."),
+            "seed": described(integer(), "Fixed random seed."),
+            "ai": { "type": "boolean" },
+            "include": strings(),
+            "exclude": strings(),
+            "locale": described(string(), "Locale for person names (en, de, fr, ja, el)."),
+            "locales": map_of(json!({ "type": "number" })),
+            "language_mix": map_of(json!({ "type": "number" })),
+            "track_runs": described(json!({ "type": "boolean" }), "Record direct inserts as runs seedkit wipe --run can remove."),
+            "run_marker": described(string(), "Column set to the run ID in the tables that have it."),
+        })),
+        "tables": map_of(table_schema()),
+        "columns": described(map_of(column_schema()), "Per-column overrides, keyed by \"table.column\"."),
+        "types": map_of(object(Value::Object(generator_properties()))),
+        "classify": {
+            "type": "array",
+            "items": {
+                "type": "object",
+                "properties": {
+                    "column": string(),
+                    "type": string(),
+                    "table": string(),
+                    "data_types": strings(),
+                },
+                "required": ["column", "type"],
+                "additionalProperties": false,
+            },
+        },
+        "graph": object(json!({ "break_cycle_at": strings() })),
+        "llm": object(json!({
+            "provider": { "enum": ["anthropic", "claude", "openai", "local", "ollama"] },
+            "model": string(),
+            "base_url": string(),
+            "max_tokens_per_run": integer(),
+            "max_requests": integer(),
+            "over_budget": { "enum": ["abort", "rule-based"] },
+            "usd_per_million_tokens": { "type": "number" },
+        })),
+        "scenario": map_of(object(json!({
+            "rows": integer(),
+            "seed": integer(),
+            "include": strings(),
+            "exclude": strings(),
+            "tables": map_of(object(json!({ "rows": integer() }))),
+            "columns": map_of(column_schema()),
+        }))),
+    }));
+    // A profile overrides any of the sections above
+    let profile = schema.clone();
+    schema["properties"]["profiles"] = described(
+        map_of(profile),
+        "Per-environment overrides, selected with --env.",
+    );
+    schema["properties"]["include"] = described(
+        strings(),
+        "Config files this one is overlaid on, relative to it.",
+    );
+    schema["$schema"] = Value::String(JSON_SCHEMA_DRAFT.to_string());
+    schema["title"] = Value::String("seedkit.toml".to_string());
+    schema
+}
+
+/// Keys of `config` the schema doesn't know, with the key probably meant.
+pub fn unknown_keys(config: &toml::Value) -> Vec<String> {
+    let mut unknown = Vec::new();
+    check_keys(config, &config_json_schema(), "", &mut unknown);
+    unknown
+}
+
+fn check_keys(value: &toml::Value, schema: &Value, path: &str, unknown: &mut Vec<String>) {
+    match value {
+        toml::Value::Table(table) => {
+            // Of alternatives, the table one
+            let schema = schema["anyOf"]
+                .as_array()
+                .and_then(|alts| alts.iter().find(|s| s["type"] == "object"))
+                .unwrap_or(schema);
+            let properties = schema["properties"].as_object();
+            let additional = &schema["additionalProperties"];
+            for (key, value) in table {
+                let key_path = if path.is_empty() {
+                    toml_key(key)
+                } else {
+                    format!("{}.{}", path, toml_key(key))
+                };
+                match properties.and_then(|p| p.get(key)) {
+                    Some(schema) => check_keys(value, schema, &key_path, unknown),
+                    None if additional.is_object() => {
+                        check_keys(value, additional, &key_path, unknown)
+                    }
+                    None if *additional == Value::Bool(false) => {
+                        let known = properties.into_iter().flat_map(|p| p.keys());
+                        unknown.push(match closest_key(key, known) {
+                            Some(meant) => {
+                                format!("unknown key `{}` (did you mean `{}`?)", key_path, meant)
+                            }
+                            None => format!("unknown key `{}`", key_path),
+                        });
+                    }
+                    None => {}
+                }
+            }
+        }
+        toml::Value::Array(items) if schema["items"].is_object() => {
+            for (i, item) in items.iter().enumerate() {
+                check_keys(item, &schema["items"], &format!("{}[{}]", path, i), unknown);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The known key within two edits of `key`, if any.
+fn closest_key<'a>(key: &str, known: impl Iterator<Item = &'a String>) -> Option<&'a str> {
+    known
+        .map(|k| (edit_distance(key, k), k))
+        .filter(|(d, _)| *d <= 2)
+        .min_by_key(|(d, _)| *d)
+        .map(|(_, k)| k.as_str())
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut prev = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cur = row[j + 1];
+            row[j + 1] = (prev + usize::from(ca != *cb)).min(row[j] + 1).min(cur + 1);
+            prev = cur;
+        }
+    }
+    row[b.len()]
+}
+
+/// Check a `seedkit.toml`'s text without a database: TOML syntax, unknown
+/// keys, and the values [`SeedKitConfig::validate`] rejects, with and
+/// without each `[profiles]` entry. `include` files are merged in first;
+/// `${NAME}` references are left as they are. `dir` is where the file
+/// lives, for relative paths.
+///
+/// Returns the parsed config, when it parses, and every problem found.
+pub fn lint_config(text: &str, dir: Option<&Path>) -> (Option<SeedKitConfig>, Vec<String>) {
+    let mut problems = Vec::new();
+    let mut document: toml::Value = match toml::from_str(text) {
+        Ok(document) => document,
+        Err(e) => return (None, vec![format!("invalid TOML: {}", e)]),
+    };
+    // Included files are checked as part of this one
+    let included = document.get("include").is_some();
+    let include_dir = dir.unwrap_or(Path::new("."));
+    if let Err(e) = resolve_includes(&mut document, include_dir, &mut Vec::new()) {
+        return (None, vec![e]);
+    }
+    problems.extend(unknown_keys(&document));
+
+    let parsed = if included {
+        document.clone().try_into()
+    } else {
+        toml::from_str(text)
+    };
+    let mut config: SeedKitConfig = match parsed {
+        Ok(config) => config,
+        Err(e) => {
+            problems.push(e.message().to_string());
+            return (None, problems);
+        }
+    };
+    config.config_dir = dir.map(Path::to_path_buf);
+    if let Err(e) = config.validate() {
+        problems.push(e.to_string());
+    }
+
+    let profiles = document
+        .get("profiles")
+        .and_then(|p| p.as_table())
+        .map(|p| p.keys().cloned().collect::<Vec<_>>())
+        .unwrap_or_default();
+    for name in profiles {
+        let mut merged = document.clone();
+        let profile: std::result::Result<SeedKitConfig, String> =
+            apply_profile(&mut merged, Some(&name)).and_then(|_| {
+                merged
+                    .try_into()
+                    .map_err(|e: toml::de::Error| e.to_string())
+            });
+        let problem = match profile {
+            Ok(mut profile) => {
+                profile.config_dir = config.config_dir.clone();
+                profile.validate().err().map(|e| e.to_string())
+            }
+            Err(e) => Some(e),
+        };
+        if let Some(problem) = problem {
+            problems.push(format!("[profiles.{}] {}", name, problem));
+        }
+    }
+    (Some(config), problems)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unknown_keys() {
+        let toml = r#"
+[generate]
+row = 500
+
+[tables.users]
+rows = 10
+stabel = true
+
+[tables.events.funnel]
+steps = ["a", "b"]
+dropoff = [0.5]
+
+[columns."users.plan"]
+values = ["free", "pro"]
+weight = [0.8, 0.2]
+fan_out = { zipf = 1.2 }
+
+[columns."users.settings"]
+json_schema = { type = "object", properties = {} }
+
+[[classify]]
+column = "^nm$"
+type = "full_name"
+tabel = "^crm_"
+
+[scenario.demo.columns."users.plan"]
+valuez = ["pro"]
+"#;
+        let document: toml::Value = toml::from_str(toml).unwrap();
+        assert_eq!(
+            unknown_keys(&document),
+            vec![
+                "unknown key `classify[0].tabel` (did you mean `table`?)",
+                "unknown key `columns.\"users.plan\".weight` (did you mean `weights`?)",
+                "unknown key `generate.row` (did you mean `rows`?)",
+                "unknown key `scenario.demo.columns.\"users.plan\".valuez` (did you mean `values`?)",
+                "unknown key `tables.events.funnel.dropoff` (did you mean `drop_off`?)",
+                "unknown key `tables.users.stabel` (did you mean `stable`?)",
+            ]
+        );
+
+        // The documented example config has no unknown keys
+        let example: String = include_str!("config.rs")
+            .lines()
+            .skip_while(|l| *l != "//! ```toml")
+            .skip(1)
+            .take_while(|l| *l != "//! ```")
+            .map(|l| format!("{}\n", l.trim_start_matches("//!").trim_start()))
+            .collect();
+        let document: toml::Value = toml::from_str(&example).unwrap();
+        assert_eq!(unknown_keys(&document), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_lint_config() {
+        let (config, problems) = lint_config(
+            "[columns.\"t.c\"]\nvalues = [\"a\"]\nweights = [0.5, 0.5]\n",
+            None,
+        );
+        assert!(config.is_some());
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].contains("weights"), "{:?}", problems);
+
+        let (_, problems) = lint_config("[[classify]]\ncolumn = \"(\"\ntype = \"email\"\n", None);
+        assert_eq!(problems.len(), 1, "{:?}", problems);
+
+        let (_, problems) = lint_config(
+            "[profiles.ci.columns.\"t.c\"]\nvalues = [\"a\"]\nweights = [1.0, 2.0]\n",
+            None,
+        );
+        assert_eq!(problems.len(), 1, "{:?}", problems);
+        assert!(problems[0].starts_with("[profiles.ci]"), "{:?}", problems);
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("base.toml"), "[generate]\nrowz = 5\n").unwrap();
+        let (config, problems) = lint_config("include = [\"base.toml\"]\n", Some(dir.path()));
+        assert!(config.is_some());
+        assert_eq!(
+            problems,
+            vec!["unknown key `generate.rowz` (did you mean `rows`?)"]
+        );
+
+        let (config, problems) = lint_config("[generate\nrows = 1", None);
+        assert!(config.is_none());
+        assert!(problems[0].starts_with("invalid TOML"), "{:?}", problems);
+    }
+}
@@ -0,0 +1,320 @@
+//! # Ephemeral SQLite Databases
+//!
+//! A one-call fast path for unit test suites: create an in-memory SQLite
+//! database, apply a schema, generate seed data and insert it — without
+//! temp files, CLI round-trips or a lock file.
+//!
+//! ```ignore
+//! let pool = seedkit_core::ephemeral_sqlite(
+//!     "CREATE TABLE users (id INTEGER PRIMARY KEY, email TEXT NOT NULL UNIQUE);",
+//!     &EphemeralOptions::default(),
+//! )
+//! .await?;
+//! ```
+//!
+//! The in-memory database lives on the pool's single connection, so the
+//! returned pool is capped at one connection that is never recycled. Drop
+//! the pool to discard the database.
+
+use std::collections::BTreeMap;
+
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions};
+
+use crate::classify::rules::classify_schema;
+use crate::config::ColumnConfig;
+use crate::error::{Result, SeedKitError};
+use crate::generate::engine::execute_plan;
+use crate::generate::plan::GenerationPlan;
+use crate::graph::cycle::break_cycles;
+use crate::graph::dag::DependencyGraph;
+use crate::graph::topo::topological_sort;
+use crate::output::direct::{insert_sqlite_pool, quote_identifier};
+use crate::schema::introspect::SchemaIntrospector;
+use crate::schema::sqlite::SqliteIntrospector;
+use crate::schema::types::{DataType, DatabaseSchema, DatabaseType};
+
+/// What to build the ephemeral database from.
+pub enum EphemeralSource<'a> {
+    /// Raw SQLite DDL (one or more `CREATE ...` statements). The schema used
+    /// for generation is introspected after the DDL is applied.
+    Ddl(&'a str),
+    /// An already-introspected schema (from any database). SQLite DDL is
+    /// derived from it, and generation uses the schema as-is so enum values,
+    /// length limits and parsed CHECKs still apply.
+    Schema(&'a DatabaseSchema),
+}
+
+impl<'a> From<&'a str> for EphemeralSource<'a> {
+    fn from(ddl: &'a str) -> Self {
+        EphemeralSource::Ddl(ddl)
+    }
+}
+
+impl<'a> From<&'a String> for EphemeralSource<'a> {
+    fn from(ddl: &'a String) -> Self {
+        EphemeralSource::Ddl(ddl)
+    }
+}
+
+impl<'a> From<&'a DatabaseSchema> for EphemeralSource<'a> {
+    fn from(schema: &'a DatabaseSchema) -> Self {
+        EphemeralSource::Schema(schema)
+    }
+}
+
+/// Generation options for [`ephemeral_sqlite`].
+#[derive(Debug, Clone)]
+pub struct EphemeralOptions {
+    /// Rows per table unless overridden in `table_rows`.
+    pub rows: usize,
+    /// Per-table row count overrides.
+    pub table_rows: BTreeMap<String, usize>,
+    /// RNG seed; the same seed and schema produce the same data.
+    pub seed: u64,
+    /// Pinned anchor for temporal values. `None` uses the current time.
+    pub base_time: Option<chrono::NaiveDateTime>,
+    /// Column overrides, keyed `"table.column"` as in `seedkit.toml`.
+    pub columns: BTreeMap<String, ColumnConfig>,
+    /// FK columns to defer when breaking cycles (e.g. `"users.invited_by_id"`).
+    pub break_cycle_at: Vec<String>,
+}
+
+impl Default for EphemeralOptions {
+    fn default() -> Self {
+        Self {
+            rows: 10,
+            table_rows: BTreeMap::new(),
+            seed: 42,
+            base_time: None,
+            columns: BTreeMap::new(),
+            break_cycle_at: Vec::new(),
+        }
+    }
+}
+
+/// Create an in-memory SQLite database, apply the schema, and seed it.
+///
+/// Everything runs on one connection: DDL, generation and a single-
+/// transaction batched insert. Returns the pool holding the database.
+pub async fn ephemeral_sqlite<'a>(
+    source: impl Into<EphemeralSource<'a>>,
+    options: &EphemeralOptions,
+) -> Result<SqlitePool> {
+    let connect_options = SqliteConnectOptions::new()
+        .in_memory(true)
+        .foreign_keys(true);
+    let pool = SqlitePoolOptions::new()
+        .min_connections(1)
+        .max_connections(1)
+        .idle_timeout(None)
+        .max_lifetime(None)
+        .connect_with(connect_options)
+        .await
+        .map_err(|e| SeedKitError::Connection {
+            message: "Failed to open in-memory SQLite database".to_string(),
+            connection_hint: "sqlite::memory:".to_string(),
+            source: e,
+        })?;
+
+    let schema = match source.into() {
+        EphemeralSource::Ddl(ddl) => {
+            apply_ddl(&pool, ddl).await?;
+            SqliteIntrospector::new(pool.clone()).introspect().await?
+        }
+        EphemeralSource::Schema(schema) => {
+            apply_ddl(&pool, &sqlite_ddl(schema)).await?;
+            let mut schema = schema.clone();
+            schema.database_type = DatabaseType::SQLite;
+            schema
+        }
+    };
+
+    let mut graph = DependencyGraph::from_schema(&schema);
+    let deferred = break_cycles(&mut graph, &options.break_cycle_at)?;
+    let insertion_order = topological_sort(&graph)?;
+    let classifications = classify_schema(&schema);
+
+    let plan = GenerationPlan::build(
+        &schema,
+        &classifications,
+        &insertion_order.tables,
+        deferred,
+        options.rows,
+        &options.table_rows,
+        options.seed,
+        options.base_time,
+        &options.columns,
+        None,
+    );
+    let data = execute_plan(&plan, &schema, None)?;
+    let total_rows = data.tables.values().map(|rows| rows.len()).sum();
+    insert_sqlite_pool(&data, &schema, &pool, total_rows, None).await?;
+
+    Ok(pool)
+}
+
+async fn apply_ddl(pool: &SqlitePool, ddl: &str) -> Result<()> {
+    sqlx::raw_sql(ddl)
+        .execute(pool)
+        .await
+        .map_err(|e| SeedKitError::InsertFailed {
+            table: "(schema)".to_string(),
+            row_index: 0,
+            message: "Failed to apply DDL to in-memory SQLite database".to_string(),
+            sql_preview: ddl.chars().take(200).collect(),
+            source: e,
+        })?;
+    Ok(())
+}
+
+/// Render SQLite `CREATE TABLE` statements for a schema.
+///
+/// Types map to SQLite affinities. Primary keys, NOT NULL, UNIQUE and
+/// foreign keys are kept; CHECK expressions are dropped because they are
+/// usually written in the source database's dialect (the generator still
+/// honours the parsed checks).
+pub fn sqlite_ddl(schema: &DatabaseSchema) -> String {
+    let db_type = &DatabaseType::SQLite;
+    let mut ddl = String::new();
+
+    for (table_name, table) in &schema.tables {
+        let single_pk = table
+            .primary_key
+            .as_ref()
+            .filter(|pk| pk.columns.len() == 1)
+            .map(|pk| pk.columns[0].as_str());
+
+        let mut lines: Vec<String> = Vec::new();
+        for (col_name, column) in &table.columns {
+            let mut line = format!(
+                "{} {}",
+                quote_identifier(col_name, db_type),
+                sqlite_affinity(&column.data_type)
+            );
+            if single_pk == Some(col_name.as_str()) {
+                // INTEGER PRIMARY KEY aliases the rowid, so SQLite assigns it
+                line.push_str(" PRIMARY KEY");
+            } else if !column.nullable {
+                line.push_str(" NOT NULL");
+            }
+            lines.push(line);
+        }
+
+        if let Some(pk) = table.primary_key.as_ref().filter(|pk| pk.columns.len() > 1) {
+            lines.push(format!("PRIMARY KEY ({})", quote_list(&pk.columns)));
+        }
+        for uc in &table.unique_constraints {
+            lines.push(format!("UNIQUE ({})", quote_list(&uc.columns)));
+        }
+        for fk in &table.foreign_keys {
+            lines.push(format!(
+                "FOREIGN KEY ({}) REFERENCES {} ({})",
+                quote_list(&fk.source_columns),
+                quote_identifier(&fk.referenced_table, db_type),
+                quote_list(&fk.referenced_columns)
+            ));
+        }
+
+        ddl.push_str(&format!(
+            "CREATE TABLE {} (\n    {}\n);\n",
+            quote_identifier(table_name, db_type),
+            lines.join(",\n    ")
+        ));
+    }
+
+    ddl
+}
+
+fn quote_list(columns: &[String]) -> String {
+    columns
+        .iter()
+        .map(|c| quote_identifier(c, &DatabaseType::SQLite))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn sqlite_affinity(data_type: &DataType) -> &'static str {
+    match data_type {
+        DataType::SmallInt
+        | DataType::Integer
+        | DataType::BigInt
+        | DataType::Serial
+        | DataType::BigSerial
+        | DataType::Boolean => "INTEGER",
+        DataType::Float | DataType::Double => "REAL",
+        DataType::Numeric | DataType::Money => "NUMERIC",
+        DataType::Binary => "BLOB",
+        _ => "TEXT",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::Row;
+
+    const DDL: &str = r#"
+        CREATE TABLE users (
+            id INTEGER PRIMARY KEY,
+            email TEXT NOT NULL UNIQUE,
+            first_name TEXT
+        );
+        CREATE TABLE orders (
+            id INTEGER PRIMARY KEY,
+            user_id INTEGER NOT NULL REFERENCES users(id),
+            total NUMERIC NOT NULL
+        );
+    "#;
+
+    #[tokio::test]
+    async fn test_ephemeral_sqlite_from_ddl() {
+        let mut options = EphemeralOptions::default();
+        options.table_rows.insert("orders".to_string(), 25);
+        let pool = ephemeral_sqlite(DDL, &options).await.unwrap();
+
+        let users: i64 = sqlx::query("SELECT COUNT(*) AS n FROM users")
+            .fetch_one(&pool)
+            .await
+            .unwrap()
+            .get("n");
+        let orphans: i64 = sqlx::query(
+            "SELECT COUNT(*) AS n FROM orders o LEFT JOIN users u ON u.id = o.user_id \
+             WHERE u.id IS NULL",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap()
+        .get("n");
+        let orders: i64 = sqlx::query("SELECT COUNT(*) AS n FROM orders")
+            .fetch_one(&pool)
+            .await
+            .unwrap()
+            .get("n");
+
+        assert_eq!(users, 10);
+        assert_eq!(orders, 25);
+        assert_eq!(orphans, 0, "every order must reference a seeded user");
+    }
+
+    #[tokio::test]
+    async fn test_ephemeral_sqlite_from_schema() {
+        let source = ephemeral_sqlite(DDL, &EphemeralOptions::default())
+            .await
+            .unwrap();
+        let schema = SqliteIntrospector::new(source).introspect().await.unwrap();
+
+        let ddl = sqlite_ddl(&schema);
+        assert!(ddl.contains("CREATE TABLE \"orders\""));
+        assert!(ddl.contains("FOREIGN KEY (\"user_id\") REFERENCES \"users\" (\"id\")"));
+
+        let pool = ephemeral_sqlite(&schema, &EphemeralOptions::default())
+            .await
+            .unwrap();
+        let orders: i64 = sqlx::query("SELECT COUNT(*) AS n FROM orders")
+            .fetch_one(&pool)
+            .await
+            .unwrap()
+            .get("n");
+        assert_eq!(orders, 10);
+    }
+}
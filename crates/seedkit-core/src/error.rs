@@ -58,6 +58,15 @@ pub enum SeedKitError {
         source: sqlx::Error,
     },
 
+    #[error("Wipe failed on {table}: {message}\n  SQL: {sql_preview}\n  DB error: {source}")]
+    WipeFailed {
+        table: String,
+        message: String,
+        sql_preview: String,
+        #[source]
+        source: sqlx::Error,
+    },
+
     #[error("Foreign key resolution failed: {source_table}.{source_column} references {target_table}.{target_column}, but target table has no generated rows")]
     ForeignKeyResolution {
         source_table: String,
@@ -101,6 +110,15 @@ pub enum SeedKitError {
         max_retries: usize,
     },
 
+    #[error("Custom provider {path} failed: {message}")]
+    CustomProvider { path: String, message: String },
+
+    #[error("Row script {path} failed: {message}")]
+    RowScript { path: String, message: String },
+
+    #[error("Expression for {column} failed: {message}")]
+    Expression { column: String, message: String },
+
     #[error("{0}")]
     Other(String),
 }
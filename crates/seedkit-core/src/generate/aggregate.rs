@@ -0,0 +1,419 @@
+//! # Derived Aggregates
+//!
+//! A parent column can be declared as an aggregate over its child rows, so
+//! totals always match their line items:
+//!
+//! ```toml
+//! [columns."orders.total"]
+//! aggregate = "sum(order_items.unit_price * quantity)"
+//!
+//! [columns."orders.item_count"]
+//! aggregate = "count(order_items)"
+//! ```
+//!
+//! The functions are `sum`, `count`, `avg`, `min` and `max`. The argument is
+//! an arithmetic expression (`+ - * /`, numbers, parentheses) over columns of
+//! one child table; the first column names the table, later ones may leave it
+//! out. `count(<table>)` counts the child rows. Children join their parent
+//! through the child table's foreign key to it.
+//!
+//! Aggregates are computed in a pass over the generated data once every
+//! table exists. Rows where a term is NULL are left out. Parents without
+//! children get 0 from `sum` and `count`, and NULL from `avg`, `min` and
+//! `max` (0 when the column is NOT NULL).
+
+use std::collections::HashMap;
+use std::fmt;
+
+use indexmap::IndexMap;
+
+use crate::generate::engine::GeneratedData;
+use crate::generate::plan::{GenerationPlan, GenerationStrategy};
+use crate::generate::value::Value;
+use crate::schema::types::DatabaseSchema;
+
+/// The aggregate function applied over child rows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AggregateFunction {
+    Sum,
+    Count,
+    Avg,
+    Min,
+    Max,
+}
+
+impl fmt::Display for AggregateFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            AggregateFunction::Sum => "sum",
+            AggregateFunction::Count => "count",
+            AggregateFunction::Avg => "avg",
+            AggregateFunction::Min => "min",
+            AggregateFunction::Max => "max",
+        })
+    }
+}
+
+/// An arithmetic expression over a child row's columns.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Number(f64),
+    Column(String),
+    Neg(Box<Expr>),
+    Binary(Box<Expr>, char, Box<Expr>),
+}
+
+impl Expr {
+    /// The expression's value for `row`; `None` when a column is NULL or not
+    /// numeric, or on division by zero.
+    pub fn eval(&self, row: &IndexMap<String, Value>) -> Option<f64> {
+        match self {
+            Expr::Number(n) => Some(*n),
+            Expr::Column(name) => match row.get(name)? {
+                Value::Int(n) => Some(*n as f64),
+                Value::Float(f) => Some(*f),
+                Value::String(s) => s.parse().ok(),
+                _ => None,
+            },
+            Expr::Neg(inner) => inner.eval(row).map(|v| -v),
+            Expr::Binary(left, op, right) => {
+                let (l, r) = (left.eval(row)?, right.eval(row)?);
+                match op {
+                    '+' => Some(l + r),
+                    '-' => Some(l - r),
+                    '*' => Some(l * r),
+                    _ => (r != 0.0).then(|| l / r),
+                }
+            }
+        }
+    }
+}
+
+/// A parsed `function(child_table.expression)` declaration.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Aggregate {
+    pub function: AggregateFunction,
+    /// The child table aggregated over.
+    pub table: String,
+    /// `None` for `count(<table>)`, which counts rows.
+    pub expr: Option<Expr>,
+}
+
+/// Parse an aggregate declaration such as
+/// `sum(order_items.unit_price * quantity)`.
+pub fn parse_aggregate(source: &str) -> Result<Aggregate, String> {
+    let invalid = |message: &str| format!("invalid aggregate '{}': {}", source, message);
+    let (name, rest) = source
+        .trim()
+        .split_once('(')
+        .ok_or_else(|| invalid("expected function(expression)"))?;
+    let function = match name.trim().to_lowercase().as_str() {
+        "sum" => AggregateFunction::Sum,
+        "count" => AggregateFunction::Count,
+        "avg" => AggregateFunction::Avg,
+        "min" => AggregateFunction::Min,
+        "max" => AggregateFunction::Max,
+        other => {
+            return Err(invalid(&format!(
+                "unknown function '{}' (expected sum, count, avg, min or max)",
+                other
+            )))
+        }
+    };
+    let body = rest
+        .trim_end()
+        .strip_suffix(')')
+        .ok_or_else(|| invalid("missing closing ')'"))?;
+
+    let tokens = tokenize(body).map_err(|m| invalid(&m))?;
+    if let (AggregateFunction::Count, [Token::Ident(table)]) = (function, tokens.as_slice()) {
+        if !table.contains('.') {
+            return Ok(Aggregate {
+                function,
+                table: table.clone(),
+                expr: None,
+            });
+        }
+    }
+
+    let mut parser = Parser {
+        tokens,
+        pos: 0,
+        table: None,
+    };
+    let expr = parser.expr().map_err(|m| invalid(&m))?;
+    if parser.pos < parser.tokens.len() {
+        return Err(invalid("unexpected trailing input"));
+    }
+    let table = parser
+        .table
+        .ok_or_else(|| invalid("name the child table, e.g. order_items.quantity"))?;
+    Ok(Aggregate {
+        function,
+        table,
+        expr: Some(expr),
+    })
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Ident(String),
+    Op(char),
+    Open,
+    Close,
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = source.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '+' | '-' | '*' | '/' => {
+                chars.next();
+                tokens.push(Token::Op(c));
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::Open);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::Close);
+            }
+            c if c.is_ascii_digit() || c == '.' => {
+                let mut number = String::new();
+                while let Some(&d) = chars.peek().filter(|d| d.is_ascii_digit() || **d == '.') {
+                    number.push(d);
+                    chars.next();
+                }
+                let n = number
+                    .parse()
+                    .map_err(|_| format!("invalid number '{}'", number))?;
+                tokens.push(Token::Number(n));
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut ident = String::new();
+                while let Some(&d) = chars
+                    .peek()
+                    .filter(|d| d.is_alphanumeric() || **d == '_' || **d == '.')
+                {
+                    ident.push(d);
+                    chars.next();
+                }
+                tokens.push(Token::Ident(ident));
+            }
+            other => return Err(format!("unexpected '{}'", other)),
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    /// The child table, from the first qualified column.
+    table: Option<String>,
+}
+
+impl Parser {
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn peek_op(&self, ops: &[char]) -> Option<char> {
+        match self.tokens.get(self.pos) {
+            Some(Token::Op(op)) if ops.contains(op) => Some(*op),
+            _ => None,
+        }
+    }
+
+    fn expr(&mut self) -> Result<Expr, String> {
+        let mut left = self.term()?;
+        while let Some(op) = self.peek_op(&['+', '-']) {
+            self.pos += 1;
+            left = Expr::Binary(Box::new(left), op, Box::new(self.term()?));
+        }
+        Ok(left)
+    }
+
+    fn term(&mut self) -> Result<Expr, String> {
+        let mut left = self.factor()?;
+        while let Some(op) = self.peek_op(&['*', '/']) {
+            self.pos += 1;
+            left = Expr::Binary(Box::new(left), op, Box::new(self.factor()?));
+        }
+        Ok(left)
+    }
+
+    fn factor(&mut self) -> Result<Expr, String> {
+        match self.next() {
+            Some(Token::Number(n)) => Ok(Expr::Number(n)),
+            Some(Token::Op('-')) => Ok(Expr::Neg(Box::new(self.factor()?))),
+            Some(Token::Open) => {
+                let inner = self.expr()?;
+                match self.next() {
+                    Some(Token::Close) => Ok(inner),
+                    _ => Err("missing closing ')'".to_string()),
+                }
+            }
+            Some(Token::Ident(ident)) => match ident.split_once('.') {
+                Some((table, column)) if !table.is_empty() && !column.is_empty() => {
+                    match &self.table {
+                        Some(t) if t != table => Err(format!(
+                            "columns of both {} and {}; aggregate over one child table",
+                            t, table
+                        )),
+                        _ => {
+                            self.table = Some(table.to_string());
+                            Ok(Expr::Column(column.to_string()))
+                        }
+                    }
+                }
+                Some(_) => Err(format!("invalid column '{}'", ident)),
+                None => Ok(Expr::Column(ident)),
+            },
+            Some(token) => Err(format!("unexpected {:?}", token)),
+            None => Err("unexpected end of expression".to_string()),
+        }
+    }
+}
+
+/// Fill every aggregate column of `generated` from its child rows.
+pub fn fill_aggregates(
+    plan: &GenerationPlan,
+    schema: &DatabaseSchema,
+    generated: &mut GeneratedData,
+) {
+    for table_plan in &plan.table_plans {
+        for col_plan in &table_plan.column_plans {
+            let GenerationStrategy::Aggregate {
+                ref aggregate,
+                ref child_column,
+                ref parent_column,
+                integer,
+                scale,
+            } = col_plan.strategy
+            else {
+                continue;
+            };
+
+            // Child values by the parent key they reference
+            let mut groups: HashMap<String, Vec<f64>> = HashMap::new();
+            for row in generated.tables.get(&aggregate.table).into_iter().flatten() {
+                let Some(key) = row.get(child_column).filter(|v| !v.is_null()) else {
+                    continue;
+                };
+                let value = match aggregate.expr {
+                    Some(ref expr) => expr.eval(row),
+                    None => Some(1.0),
+                };
+                if let Some(value) = value {
+                    groups.entry(key.to_unique_key()).or_default().push(value);
+                }
+            }
+
+            // Auto-increment keys aren't in the rows; they follow the
+            // sequence the engine synthesized for the FK pool.
+            let synthesized = schema
+                .tables
+                .get(&table_plan.table_name)
+                .and_then(|t| t.columns.get(parent_column))
+                .is_some_and(|c| c.is_auto_increment || c.data_type.is_serial());
+            let offset = table_plan.sequence_offset.unwrap_or(plan.sequence_offset);
+
+            let Some(rows) = generated.tables.get_mut(&table_plan.table_name) else {
+                continue;
+            };
+            for (row_idx, row) in rows.iter_mut().enumerate() {
+                let key = match row.get(parent_column) {
+                    Some(value) => value.to_unique_key(),
+                    None if synthesized => {
+                        Value::Int(offset as i64 + row_idx as i64 + 1).to_unique_key()
+                    }
+                    None => continue,
+                };
+                let values = groups.get(&key).map(Vec::as_slice).unwrap_or_default();
+                let result = match aggregate.function {
+                    AggregateFunction::Sum => Some(values.iter().sum()),
+                    AggregateFunction::Count => Some(values.len() as f64),
+                    AggregateFunction::Avg => (!values.is_empty())
+                        .then(|| values.iter().sum::<f64>() / values.len() as f64),
+                    AggregateFunction::Min => values.iter().copied().reduce(f64::min),
+                    AggregateFunction::Max => values.iter().copied().reduce(f64::max),
+                };
+                let value = match result.or((!col_plan.nullable).then_some(0.0)) {
+                    None => Value::Null,
+                    Some(v) if integer => Value::Int(v.round() as i64),
+                    Some(v) => {
+                        let factor = 10f64.powi(scale as i32);
+                        Value::Float((v * factor).round() / factor)
+                    }
+                };
+                row.insert(col_plan.column_name.clone(), value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(pairs: &[(&str, Value)]) -> IndexMap<String, Value> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_aggregate() {
+        let agg = parse_aggregate("sum(order_items.unit_price * quantity)").unwrap();
+        assert_eq!(agg.function, AggregateFunction::Sum);
+        assert_eq!(agg.table, "order_items");
+        let item = row(&[
+            ("unit_price", Value::Float(2.5)),
+            ("quantity", Value::Int(4)),
+        ]);
+        assert_eq!(agg.expr.unwrap().eval(&item), Some(10.0));
+
+        let agg = parse_aggregate("count(order_items)").unwrap();
+        assert_eq!(agg.function, AggregateFunction::Count);
+        assert_eq!(agg.table, "order_items");
+        assert!(agg.expr.is_none());
+
+        let agg = parse_aggregate("AVG((order_items.price - discount) / 2)").unwrap();
+        let item = row(&[("price", Value::Int(10)), ("discount", Value::Null)]);
+        assert_eq!(
+            agg.expr.unwrap().eval(&item),
+            None,
+            "NULL terms skip the row"
+        );
+
+        let agg = parse_aggregate("max(-order_items.delta * 1.5)").unwrap();
+        let item = row(&[("delta", Value::String("2".into()))]);
+        assert_eq!(agg.expr.unwrap().eval(&item), Some(-3.0));
+    }
+
+    #[test]
+    fn test_parse_aggregate_rejects_invalid() {
+        for source in [
+            "total(order_items.price)",
+            "sum order_items.price",
+            "sum(order_items.price",
+            "sum(price * quantity)",
+            "sum(order_items.price * refunds.amount)",
+            "sum(order_items.price *)",
+            "sum(order_items.price quantity)",
+            "sum(order_items.price % 2)",
+        ] {
+            assert!(parse_aggregate(source).is_err(), "{}", source);
+        }
+    }
+}
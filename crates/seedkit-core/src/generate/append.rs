@@ -0,0 +1,284 @@
+//! # Append Mode
+//!
+//! `seedkit generate --append N` adds N rows to every table of a database
+//! that was already seeded, for tests that need a growing dataset. The
+//! existing rows are read back so the new ones fit around them:
+//!
+//! - synthesized IDs continue after the existing ones (see
+//!   [`super::sequence`]);
+//! - foreign keys reference existing parent rows as well as new ones;
+//! - unique columns don't repeat a value already in the table.
+//!
+//! Only key columns are read — primary keys, columns foreign keys point at
+//! and single-column unique constraints — up to [`MAX_EXISTING_KEYS`]
+//! values each. Tables whose rows are fixed (lookup and stable tables) get
+//! no new rows; their existing rows are still referenced.
+
+use std::collections::BTreeMap;
+
+use crate::error::{Result, SeedKitError};
+use crate::generate::plan::{GenerationPlan, GenerationStrategy};
+use crate::generate::value::Value;
+use crate::output::direct::quote_identifier;
+use crate::schema::types::{DataType, DatabaseSchema, DatabaseType, Table};
+
+/// Most values read back per key column.
+pub const MAX_EXISTING_KEYS: usize = 100_000;
+
+/// Key column values already in the database, by (table, column).
+#[derive(Debug, Clone, Default)]
+pub struct ExistingKeys {
+    keys: BTreeMap<(String, String), Vec<Value>>,
+}
+
+impl ExistingKeys {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, table: &str, column: &str, values: Vec<Value>) {
+        self.keys
+            .entry((table.to_string(), column.to_string()))
+            .or_default()
+            .extend(values);
+    }
+
+    /// The values of `table.column`, empty when none were read.
+    pub fn get(&self, table: &str, column: &str) -> &[Value] {
+        self.keys
+            .get(&(table.to_string(), column.to_string()))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str, &[Value])> {
+        self.keys
+            .iter()
+            .map(|((t, c), values)| (t.as_str(), c.as_str(), values.as_slice()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.values().all(Vec::is_empty)
+    }
+}
+
+/// The columns of `table` read back for append mode: its primary key, the
+/// columns foreign keys in `schema` point at, and its single-column unique
+/// constraints.
+pub fn key_columns(schema: &DatabaseSchema, table: &Table) -> Vec<String> {
+    let mut columns: Vec<String> = Vec::new();
+    let mut add = |column: &String| {
+        if table.columns.contains_key(column) && !columns.contains(column) {
+            columns.push(column.clone());
+        }
+    };
+    for column in table.primary_key.iter().flat_map(|pk| &pk.columns) {
+        add(column);
+    }
+    for fk in schema.tables.values().flat_map(|t| &t.foreign_keys) {
+        if fk.referenced_table == table.name {
+            fk.referenced_columns.iter().for_each(&mut add);
+        }
+    }
+    for uc in &table.unique_constraints {
+        if let [column] = uc.columns.as_slice() {
+            add(column);
+        }
+    }
+    columns
+}
+
+/// A key value read back as text, typed the way the engine generates it.
+pub fn existing_value(text: &str, data_type: &DataType) -> Value {
+    match data_type {
+        DataType::SmallInt
+        | DataType::Integer
+        | DataType::BigInt
+        | DataType::Serial
+        | DataType::BigSerial => text
+            .parse()
+            .map(Value::Int)
+            .unwrap_or_else(|_| Value::String(text.to_string().into())),
+        DataType::Uuid => uuid::Uuid::parse_str(text)
+            .map(Value::Uuid)
+            .unwrap_or_else(|_| Value::String(text.to_string().into())),
+        _ => Value::String(text.to_string().into()),
+    }
+}
+
+/// SQL reading up to [`MAX_EXISTING_KEYS`] non-null values of
+/// `table.column` as text.
+pub fn existing_keys_query(table: &str, column: &str, db_type: DatabaseType) -> String {
+    let qt = quote_identifier(table, &db_type);
+    let qc = quote_identifier(column, &db_type);
+    let text = match db_type {
+        DatabaseType::PostgreSQL => format!("{}::text", qc),
+        DatabaseType::MySQL => format!("CAST({} AS CHAR)", qc),
+        DatabaseType::SQLite => format!("CAST({} AS TEXT)", qc),
+    };
+    format!(
+        "SELECT {} FROM {} WHERE {} IS NOT NULL LIMIT {}",
+        text, qt, qc, MAX_EXISTING_KEYS
+    )
+}
+
+/// Keep lookup and stable tables out of an append run: their rows are
+/// fixed, so adding more would only duplicate them. Returns the tables
+/// left out.
+pub fn skip_fixed_tables(plan: &mut GenerationPlan) -> Vec<String> {
+    let mut skipped = Vec::new();
+    plan.table_plans.retain(|t| {
+        let fixed = t.stable
+            || t.pinned_rows.is_some()
+            || t.column_plans
+                .iter()
+                .any(|c| matches!(c.strategy, GenerationStrategy::Lookup { .. }));
+        if fixed {
+            skipped.push(t.table_name.clone());
+        }
+        !fixed
+    });
+    skipped
+}
+
+/// Read the key columns of every table in `schema` from the database.
+pub async fn load_existing_keys(url: &str, schema: &DatabaseSchema) -> Result<ExistingKeys> {
+    let targets: Vec<(&str, String, &DataType)> = schema
+        .tables
+        .values()
+        .flat_map(|table| {
+            key_columns(schema, table)
+                .into_iter()
+                .map(move |c| (table.name.as_str(), c.clone(), &table.columns[&c].data_type))
+        })
+        .collect();
+
+    let mut existing = ExistingKeys::new();
+    if targets.is_empty() {
+        return Ok(existing);
+    }
+
+    let connect_err = |e| SeedKitError::Introspection {
+        query: "existing keys connect".to_string(),
+        source: e,
+    };
+    let query_err = |table: &str, column: &str| {
+        let query = format!("existing keys of {}.{}", table, column);
+        move |e| SeedKitError::Introspection { query, source: e }
+    };
+
+    match schema.database_type {
+        DatabaseType::PostgreSQL => {
+            let pool = sqlx::postgres::PgPoolOptions::new()
+                .max_connections(1)
+                .connect(url)
+                .await
+                .map_err(connect_err)?;
+            for (table, column, data_type) in targets {
+                let sql = existing_keys_query(table, &column, DatabaseType::PostgreSQL);
+                let values: Vec<String> = sqlx::query_scalar(&sql)
+                    .fetch_all(&pool)
+                    .await
+                    .map_err(query_err(table, &column))?;
+                let values = values.iter().map(|v| existing_value(v, data_type));
+                existing.insert(table, &column, values.collect());
+            }
+            pool.close().await;
+        }
+        DatabaseType::MySQL => {
+            let pool = sqlx::mysql::MySqlPoolOptions::new()
+                .max_connections(1)
+                .connect(url)
+                .await
+                .map_err(connect_err)?;
+            for (table, column, data_type) in targets {
+                let sql = existing_keys_query(table, &column, DatabaseType::MySQL);
+                let values: Vec<String> = sqlx::query_scalar(&sql)
+                    .fetch_all(&pool)
+                    .await
+                    .map_err(query_err(table, &column))?;
+                let values = values.iter().map(|v| existing_value(v, data_type));
+                existing.insert(table, &column, values.collect());
+            }
+            pool.close().await;
+        }
+        DatabaseType::SQLite => {
+            let pool = sqlx::sqlite::SqlitePoolOptions::new()
+                .max_connections(1)
+                .connect(url)
+                .await
+                .map_err(connect_err)?;
+            for (table, column, data_type) in targets {
+                let sql = existing_keys_query(table, &column, DatabaseType::SQLite);
+                let values: Vec<String> = sqlx::query_scalar(&sql)
+                    .fetch_all(&pool)
+                    .await
+                    .map_err(query_err(table, &column))?;
+                let values = values.iter().map(|v| existing_value(v, data_type));
+                existing.insert(table, &column, values.collect());
+            }
+            pool.close().await;
+        }
+    }
+
+    Ok(existing)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::types::*;
+
+    #[test]
+    fn test_key_columns_and_values() {
+        let mut schema = DatabaseSchema::new(DatabaseType::PostgreSQL, "app".to_string());
+        let mut users = Table::new("users".to_string());
+        for (name, data_type) in [
+            ("id", DataType::BigSerial),
+            ("email", DataType::VarChar),
+            ("handle", DataType::VarChar),
+            ("bio", DataType::Text),
+        ] {
+            users.columns.insert(
+                name.to_string(),
+                Column::new(name.to_string(), data_type, "x".to_string()),
+            );
+        }
+        users.primary_key = Some(PrimaryKey {
+            columns: vec!["id".to_string()],
+            name: None,
+        });
+        users.unique_constraints.push(UniqueConstraint {
+            name: None,
+            columns: vec!["email".to_string()],
+        });
+        let mut orders = Table::new("orders".to_string());
+        orders.foreign_keys.push(ForeignKey {
+            name: None,
+            source_columns: vec!["user_handle".to_string()],
+            referenced_table: "users".to_string(),
+            referenced_columns: vec!["handle".to_string()],
+            on_delete: ForeignKeyAction::NoAction,
+            on_update: ForeignKeyAction::NoAction,
+            is_deferrable: false,
+        });
+        schema.tables.insert("orders".to_string(), orders);
+        schema.tables.insert("users".to_string(), users.clone());
+
+        assert_eq!(key_columns(&schema, &users), vec!["id", "handle", "email"]);
+
+        assert_eq!(existing_value("42", &DataType::BigSerial), Value::Int(42));
+        assert_eq!(
+            existing_value("550e8400-e29b-41d4-a716-446655440000", &DataType::Uuid),
+            Value::Uuid(uuid::Uuid::parse_str("550e8400-e29b-41d4-a716-446655440000").unwrap())
+        );
+        assert_eq!(
+            existing_value("ada@example.com", &DataType::VarChar),
+            Value::String("ada@example.com".into())
+        );
+        assert_eq!(
+            existing_keys_query("users", "id", DatabaseType::PostgreSQL),
+            "SELECT \"id\"::text FROM \"users\" WHERE \"id\" IS NOT NULL LIMIT 100000"
+        );
+    }
+}
@@ -0,0 +1,265 @@
+//! # Gaussian Copula
+//!
+//! `seedkit sample` measures correlations between numeric columns (see
+//! `DistributionProfile::correlations`), such as `quantity` and `total` or
+//! `age` and `income`. Columns drawn from their sampled distributions one by
+//! one would come out independent; a Gaussian copula ties them back together
+//! while each keeps its own distribution:
+//!
+//! 1. draw standard normals correlated like the sampled columns;
+//! 2. turn each into its quantile, `Φ(z)`;
+//! 3. read each column's value at that quantile of its distribution.
+//!
+//! The sampled correlations are measured on the values and applied to normal
+//! scores, which is close enough for the distributions they come from.
+
+use rand::Rng;
+
+use crate::generate::engine::box_muller_normal;
+
+/// Times the off-diagonal correlations are shrunk towards zero before giving
+/// up on a set that isn't a valid correlation matrix.
+const MAX_SHRINK_STEPS: usize = 50;
+
+/// Columns of a table drawn together through a Gaussian copula.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CopulaPlan {
+    pub columns: Vec<String>,
+    /// Lower-triangular Cholesky factor of the correlation matrix.
+    factor: Vec<Vec<f64>>,
+}
+
+impl CopulaPlan {
+    /// Plan a copula over those of `columns` that appear in a sampled
+    /// `(a, b, r)` correlation; pairs without one are uncorrelated. `None`
+    /// when no pair is correlated.
+    ///
+    /// Correlations measured pair by pair may not fit together; they are
+    /// shrunk towards zero until they do.
+    pub fn new(columns: &[&str], correlations: &[(String, String, f64)]) -> Option<Self> {
+        let correlations: Vec<(&str, &str, f64)> = correlations
+            .iter()
+            .filter(|(a, b, _)| {
+                a != b && columns.contains(&a.as_str()) && columns.contains(&b.as_str())
+            })
+            .map(|(a, b, r)| (a.as_str(), b.as_str(), r.clamp(-1.0, 1.0)))
+            .collect();
+        let members: Vec<&str> = columns
+            .iter()
+            .copied()
+            .filter(|c| correlations.iter().any(|(a, b, _)| a == c || b == c))
+            .collect();
+        if members.len() < 2 {
+            return None;
+        }
+
+        let index = |name: &str| members.iter().position(|m| *m == name);
+        let n = members.len();
+        let mut matrix = vec![vec![0.0; n]; n];
+        for (i, row) in matrix.iter_mut().enumerate() {
+            row[i] = 1.0;
+        }
+        for (a, b, r) in &correlations {
+            if let (Some(i), Some(j)) = (index(a), index(b)) {
+                matrix[i][j] = *r;
+                matrix[j][i] = *r;
+            }
+        }
+
+        for _ in 0..MAX_SHRINK_STEPS {
+            if let Some(factor) = cholesky(&matrix) {
+                return Some(Self {
+                    columns: members.iter().map(|m| m.to_string()).collect(),
+                    factor,
+                });
+            }
+            for (i, row) in matrix.iter_mut().enumerate() {
+                for (j, value) in row.iter_mut().enumerate() {
+                    if i != j {
+                        *value *= 0.9;
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Draw one quantile in (0, 1) per column, in `columns` order.
+    pub fn draw(&self, rng: &mut impl Rng) -> Vec<f64> {
+        let normals: Vec<f64> = self.factor.iter().map(|_| box_muller_normal(rng)).collect();
+        self.factor
+            .iter()
+            .map(|row| {
+                let z: f64 = row.iter().zip(&normals).map(|(l, e)| l * e).sum();
+                normal_cdf(z)
+            })
+            .collect()
+    }
+}
+
+/// Cholesky factor of a symmetric matrix; `None` unless it is positive
+/// definite.
+fn cholesky(matrix: &[Vec<f64>]) -> Option<Vec<Vec<f64>>> {
+    let n = matrix.len();
+    let mut factor = vec![vec![0.0; n]; n];
+    for i in 0..n {
+        for j in 0..=i {
+            let sum: f64 = (0..j).map(|k| factor[i][k] * factor[j][k]).sum();
+            if i == j {
+                let diagonal = matrix[i][i] - sum;
+                if diagonal <= 1e-9 {
+                    return None;
+                }
+                factor[i][i] = diagonal.sqrt();
+            } else {
+                factor[i][j] = (matrix[i][j] - sum) / factor[j][j];
+            }
+        }
+    }
+    Some(factor)
+}
+
+/// Standard normal CDF, Φ(x).
+pub fn normal_cdf(x: f64) -> f64 {
+    0.5 * erfc(-x / std::f64::consts::SQRT_2)
+}
+
+/// Complementary error function, to a relative error below 1.2e-7
+/// (Numerical Recipes' Chebyshev fit).
+fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1.0 / (1.0 + 0.5 * z);
+    let poly = -1.265_512_23
+        + t * (1.000_023_68
+            + t * (0.374_091_96
+                + t * (0.096_784_18
+                    + t * (-0.186_288_06
+                        + t * (0.278_868_07
+                            + t * (-1.135_203_98
+                                + t * (1.488_515_87 + t * (-0.822_152_23 + t * 0.170_872_77))))))));
+    let r = t * (-z * z + poly).exp();
+    if x >= 0.0 {
+        r
+    } else {
+        2.0 - r
+    }
+}
+
+/// Inverse of the standard normal CDF, Φ⁻¹(p), to a relative error below
+/// 1.2e-9 (Acklam's rational approximation).
+pub fn normal_quantile(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969_683_028_665_376e1,
+        2.209_460_984_245_205e2,
+        -2.759_285_104_469_687e2,
+        1.383_577_518_672_69e2,
+        -3.066_479_806_614_716e1,
+        2.506_628_277_459_239,
+    ];
+    const B: [f64; 5] = [
+        -5.447_609_879_822_406e1,
+        1.615_858_368_580_409e2,
+        -1.556_989_798_598_866e2,
+        6.680_131_188_771_972e1,
+        -1.328_068_155_288_572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784_894_002_430_293e-3,
+        -3.223_964_580_411_365e-1,
+        -2.400_758_277_161_838,
+        -2.549_732_539_343_734,
+        4.374_664_141_464_968,
+        2.938_163_982_698_783,
+    ];
+    const D: [f64; 4] = [
+        7.784_695_709_041_462e-3,
+        3.224_671_290_700_398e-1,
+        2.445_134_137_142_996,
+        3.754_408_661_907_416,
+    ];
+    const P_LOW: f64 = 0.024_25;
+
+    let p = p.clamp(1e-12, 1.0 - 1e-12);
+    let tail = |q: f64| {
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+    if p < P_LOW {
+        tail((-2.0 * p.ln()).sqrt())
+    } else if p <= 1.0 - P_LOW {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    } else {
+        -tail((-2.0 * (1.0 - p).ln()).sqrt())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    fn pearson(a: &[f64], b: &[f64]) -> f64 {
+        let n = a.len() as f64;
+        let (mean_a, mean_b) = (a.iter().sum::<f64>() / n, b.iter().sum::<f64>() / n);
+        let cov: f64 = a
+            .iter()
+            .zip(b)
+            .map(|(x, y)| (x - mean_a) * (y - mean_b))
+            .sum();
+        let var_a: f64 = a.iter().map(|x| (x - mean_a).powi(2)).sum();
+        let var_b: f64 = b.iter().map(|y| (y - mean_b).powi(2)).sum();
+        cov / (var_a * var_b).sqrt()
+    }
+
+    #[test]
+    fn test_normal_cdf_and_quantile() {
+        assert!((normal_cdf(0.0) - 0.5).abs() < 1e-7);
+        assert!((normal_cdf(1.96) - 0.975).abs() < 1e-4);
+        assert!((normal_cdf(-1.0) - 0.158_655).abs() < 1e-5);
+        for p in [0.001, 0.02, 0.3, 0.5, 0.9, 0.999] {
+            assert!((normal_cdf(normal_quantile(p)) - p).abs() < 1e-6, "{}", p);
+        }
+        assert!(normal_quantile(0.0).is_finite());
+    }
+
+    #[test]
+    fn test_copula_keeps_correlations() {
+        let correlations = vec![
+            ("quantity".to_string(), "total".to_string(), 0.8),
+            ("total".to_string(), "discount".to_string(), -0.4),
+        ];
+        let plan =
+            CopulaPlan::new(&["quantity", "total", "discount", "weight"], &correlations).unwrap();
+        assert_eq!(plan.columns, vec!["quantity", "total", "discount"]);
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        let draws: Vec<Vec<f64>> = (0..5000).map(|_| plan.draw(&mut rng)).collect();
+        let column = |i: usize| -> Vec<f64> { draws.iter().map(|d| d[i]).collect() };
+        assert!(draws.iter().flatten().all(|u| (0.0..=1.0).contains(u)));
+        assert!((pearson(&column(0), &column(1)) - 0.8).abs() < 0.05);
+        assert!((pearson(&column(1), &column(2)) + 0.4).abs() < 0.05);
+
+        assert_eq!(CopulaPlan::new(&["quantity"], &correlations), None);
+    }
+
+    #[test]
+    fn test_inconsistent_correlations_are_shrunk() {
+        // a ~ b and a ~ c, but b and c opposed: no valid matrix as measured
+        let correlations = vec![
+            ("a".to_string(), "b".to_string(), 0.9),
+            ("a".to_string(), "c".to_string(), 0.9),
+            ("b".to_string(), "c".to_string(), -0.9),
+        ];
+        assert!(cholesky(&[
+            vec![1.0, 0.9, 0.9],
+            vec![0.9, 1.0, -0.9],
+            vec![0.9, -0.9, 1.0]
+        ])
+        .is_none());
+        let plan = CopulaPlan::new(&["a", "b", "c"], &correlations).unwrap();
+        assert_eq!(plan.columns.len(), 3);
+    }
+}
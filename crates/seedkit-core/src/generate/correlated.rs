@@ -1,9 +1,15 @@
 use std::borrow::Cow;
+use std::collections::HashMap;
 
 use chrono::Duration as ChronoDuration;
 use rand::Rng;
 
+use crate::classify::rules::normalize_column_name;
 use crate::classify::semantic::{CorrelationGroup, SemanticType};
+use crate::generate::locale::{
+    ascii_handle, transliterate_greek, transliterate_kana, transliterate_latin, Locale,
+    EL_FIRST_NAMES, EL_LAST_NAMES, JA_FIRST_NAMES, JA_LAST_NAMES,
+};
 use crate::generate::plan::CorrelationGroupPlan;
 use crate::generate::providers::generate_value;
 use crate::generate::value::Value;
@@ -155,16 +161,16 @@ fn generate_person(
     rng: &mut impl Rng,
     base_time: chrono::NaiveDateTime,
 ) -> Vec<(String, Value)> {
-    use fake::faker::name::en::*;
-    use fake::Fake;
-
-    let first: String = FirstName().fake_with_rng(rng);
-    let last: String = LastName().fake_with_rng(rng);
-    let full_name = format!("{} {}", first, last);
+    // A single locale draws nothing, keeping its output unchanged
+    let locale = match plan.locale.as_single() {
+        Some(locale) => locale,
+        None => plan.locale.pick(rng),
+    };
+    let (first, last, full_name, first_ascii, last_ascii) = person_names(locale, rng);
     let email = format!(
         "{}.{}{}@example.com",
-        first.to_lowercase(),
-        last.to_lowercase(),
+        first_ascii,
+        last_ascii,
         if row_index > 0 {
             format!(".{}", row_index)
         } else {
@@ -173,11 +179,11 @@ fn generate_person(
     );
     // Use first.last.index format to safely clear common minimum-length
     // constraints (many apps require LENGTH(username) >= 5).
-    let username = format!(
-        "{}.{}{}",
-        first.to_lowercase(),
-        last.to_lowercase(),
-        row_index
+    let username = format!("{}.{}{}", first_ascii, last_ascii, row_index);
+    // The avatar is drawn from the username, so it matches the person
+    let avatar_url = format!(
+        "https://api.dicebear.com/7.x/avataaars/svg?seed={}",
+        username
     );
 
     let mut values = Vec::new();
@@ -190,6 +196,7 @@ fn generate_person(
             }
             SemanticType::Email => Value::String(Cow::Owned(email.clone())),
             SemanticType::Username => Value::String(Cow::Owned(username.clone())),
+            SemanticType::AvatarUrl => Value::String(Cow::Owned(avatar_url.clone())),
             _ => generate_value(*st, rng, row_index, &[], base_time),
         };
         values.push((col_name.clone(), value));
@@ -197,6 +204,74 @@ fn generate_person(
     values
 }
 
+/// Pick a name for `locale`, returning (first, last, full name) as written
+/// plus ASCII first/last handles for emails and usernames.
+fn person_names(locale: Locale, rng: &mut impl Rng) -> (String, String, String, String, String) {
+    match locale {
+        Locale::En => {
+            use fake::faker::name::en::*;
+            use fake::Fake;
+
+            let first: String = FirstName().fake_with_rng(rng);
+            let last: String = LastName().fake_with_rng(rng);
+            let full_name = format!("{} {}", first, last);
+            // Lowercased as written, as before locales, so existing seeds
+            // keep their emails and usernames
+            let (first_ascii, last_ascii) = (first.to_lowercase(), last.to_lowercase());
+            (first, last, full_name, first_ascii, last_ascii)
+        }
+        Locale::De => {
+            use fake::faker::name::de_de::*;
+            use fake::Fake;
+
+            let first: String = FirstName().fake_with_rng(rng);
+            let last: String = LastName().fake_with_rng(rng);
+            let full_name = format!("{} {}", first, last);
+            let (first_ascii, last_ascii) = (
+                ascii_handle(&transliterate_latin(&first)),
+                ascii_handle(&transliterate_latin(&last)),
+            );
+            (first, last, full_name, first_ascii, last_ascii)
+        }
+        Locale::Fr => {
+            use fake::faker::name::fr_fr::*;
+            use fake::Fake;
+
+            let first: String = FirstName().fake_with_rng(rng);
+            let last: String = LastName().fake_with_rng(rng);
+            let full_name = format!("{} {}", first, last);
+            let (first_ascii, last_ascii) = (
+                ascii_handle(&transliterate_latin(&first)),
+                ascii_handle(&transliterate_latin(&last)),
+            );
+            (first, last, full_name, first_ascii, last_ascii)
+        }
+        Locale::Ja => {
+            let (first, first_kana) = JA_FIRST_NAMES[rng.random_range(0..JA_FIRST_NAMES.len())];
+            let (last, last_kana) = JA_LAST_NAMES[rng.random_range(0..JA_LAST_NAMES.len())];
+            // Family name first, as written in Japanese
+            (
+                first.to_string(),
+                last.to_string(),
+                format!("{} {}", last, first),
+                ascii_handle(&transliterate_kana(first_kana)),
+                ascii_handle(&transliterate_kana(last_kana)),
+            )
+        }
+        Locale::El => {
+            let first = EL_FIRST_NAMES[rng.random_range(0..EL_FIRST_NAMES.len())];
+            let last = EL_LAST_NAMES[rng.random_range(0..EL_LAST_NAMES.len())];
+            (
+                first.to_string(),
+                last.to_string(),
+                format!("{} {}", first, last),
+                ascii_handle(&transliterate_greek(first)),
+                ascii_handle(&transliterate_greek(last)),
+            )
+        }
+    }
+}
+
 fn generate_temporal(
     plan: &CorrelationGroupPlan,
     rng: &mut impl Rng,
@@ -211,11 +286,32 @@ fn generate_temporal(
         None
     };
 
+    // Lifecycle events (`paid_at`, `shipped_at`, ...) happen in order
+    // between creation and the last update
+    let mut events: Vec<(&str, usize)> = plan
+        .columns
+        .iter()
+        .filter(|(_, st)| *st == SemanticType::Timestamp)
+        .filter_map(|(name, _)| Some((name.as_str(), lifecycle_rank(name)?)))
+        .collect();
+    events.sort_by_key(|&(_, rank)| rank);
+    let span = (updated - created).num_seconds();
+    let mut offsets: Vec<i64> = events.iter().map(|_| rng.random_range(0..=span)).collect();
+    offsets.sort_unstable();
+    let event_times: HashMap<&str, chrono::NaiveDateTime> = events
+        .iter()
+        .zip(offsets)
+        .map(|(&(name, _), offset)| (name, created + ChronoDuration::seconds(offset)))
+        .collect();
+
     let mut values = Vec::new();
     for (col_name, st) in &plan.columns {
         let value = match st {
             SemanticType::CreatedAt => Value::Timestamp(created),
             SemanticType::UpdatedAt => Value::Timestamp(updated),
+            SemanticType::Timestamp if event_times.contains_key(col_name.as_str()) => {
+                Value::Timestamp(event_times[col_name.as_str()])
+            }
             SemanticType::DeletedAt => match deleted {
                 Some(d) => Value::Timestamp(d),
                 None => Value::Null,
@@ -227,6 +323,35 @@ fn generate_temporal(
     values
 }
 
+/// Lifecycle events in the order they happen to a record.
+const LIFECYCLE_EVENTS: &[&str] = &[
+    "submitted",
+    "verified",
+    "confirmed",
+    "approved",
+    "accepted",
+    "paid",
+    "processed",
+    "published",
+    "started",
+    "shipped",
+    "dispatched",
+    "delivered",
+    "received",
+    "completed",
+    "finished",
+    "resolved",
+    "closed",
+];
+
+/// Position of a timestamp column like `shipped_at` or `emailVerifiedAt`
+/// in [`LIFECYCLE_EVENTS`], if it names one.
+pub fn lifecycle_rank(column_name: &str) -> Option<usize> {
+    let name = normalize_column_name(column_name);
+    let event = name.strip_suffix("_at")?.rsplit('_').next()?;
+    LIFECYCLE_EVENTS.iter().position(|&e| e == event)
+}
+
 fn generate_temporal_range(
     plan: &CorrelationGroupPlan,
     rng: &mut impl Rng,
@@ -271,6 +396,7 @@ mod tests {
                 ("state".to_string(), SemanticType::State),
                 ("zip".to_string(), SemanticType::ZipCode),
             ],
+            locale: Locale::En.into(),
         };
         let mut rng = rand::rngs::StdRng::seed_from_u64(42);
         let values = generate_correlated_group(&plan, 0, &mut rng, test_base_time());
@@ -291,6 +417,7 @@ mod tests {
                 ("last_name".to_string(), SemanticType::LastName),
                 ("email".to_string(), SemanticType::Email),
             ],
+            locale: Locale::En.into(),
         };
         let mut rng = rand::rngs::StdRng::seed_from_u64(42);
         let values = generate_correlated_group(&plan, 0, &mut rng, test_base_time());
@@ -305,6 +432,126 @@ mod tests {
             assert!(e.contains('@'));
             assert!(e.contains('.'));
         }
+
+        // English handles are the names lowercased as written
+        let get = |name: &str| {
+            values
+                .iter()
+                .find(|(n, _)| n == name)
+                .unwrap()
+                .1
+                .to_string()
+        };
+        assert!(get("email").starts_with(&format!(
+            "{}.{}",
+            get("first_name").to_lowercase(),
+            get("last_name").to_lowercase()
+        )));
+    }
+
+    #[test]
+    fn test_person_identity_columns_agree() {
+        let plan = CorrelationGroupPlan {
+            group: CorrelationGroup::PersonIdentity,
+            columns: vec![
+                ("first_name".to_string(), SemanticType::FirstName),
+                ("last_name".to_string(), SemanticType::LastName),
+                ("full_name".to_string(), SemanticType::FullName),
+                ("display_name".to_string(), SemanticType::DisplayName),
+                ("username".to_string(), SemanticType::Username),
+                ("email".to_string(), SemanticType::Email),
+                ("avatar_url".to_string(), SemanticType::AvatarUrl),
+            ],
+            locale: Locale::En.into(),
+        };
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        for row in 0..20 {
+            let values = generate_correlated_group(&plan, row, &mut rng, test_base_time());
+            let get = |name: &str| {
+                values
+                    .iter()
+                    .find(|(n, _)| n == name)
+                    .unwrap()
+                    .1
+                    .to_string()
+            };
+
+            let full_name = format!("{} {}", get("first_name"), get("last_name"));
+            assert_eq!(get("full_name"), full_name);
+            assert_eq!(get("display_name"), full_name);
+            let first = ascii_handle(&get("first_name"));
+            let last = ascii_handle(&get("last_name"));
+            let handle = format!("{}.{}", first, last);
+            assert!(get("email").starts_with(&handle), "{}", get("email"));
+            assert!(get("username").starts_with(&handle), "{}", get("username"));
+            assert!(
+                get("avatar_url").ends_with(&format!("seed={}", get("username"))),
+                "{}",
+                get("avatar_url")
+            );
+        }
+    }
+
+    #[test]
+    fn test_person_correlation_transliterates_non_latin_names() {
+        for locale in [Locale::Ja, Locale::El] {
+            let plan = CorrelationGroupPlan {
+                group: CorrelationGroup::PersonIdentity,
+                columns: vec![
+                    ("first_name".to_string(), SemanticType::FirstName),
+                    ("email".to_string(), SemanticType::Email),
+                    ("username".to_string(), SemanticType::Username),
+                ],
+                locale: locale.into(),
+            };
+            let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+            for row in 0..20 {
+                let values = generate_correlated_group(&plan, row, &mut rng, test_base_time());
+                let get = |name: &str| {
+                    values
+                        .iter()
+                        .find(|(n, _)| n == name)
+                        .unwrap()
+                        .1
+                        .to_string()
+                };
+
+                assert!(
+                    !get("first_name").is_ascii(),
+                    "{:?} name should be native",
+                    locale
+                );
+                let email = get("email");
+                let local = email.split('@').next().unwrap();
+                assert!(
+                    local.len() > 3 && local.chars().all(|c| c.is_ascii_alphanumeric() || c == '.'),
+                    "bad email '{}'",
+                    email
+                );
+                assert!(get("username").is_ascii());
+            }
+        }
+    }
+
+    #[test]
+    fn test_person_correlation_mixes_locales() {
+        let plan = CorrelationGroupPlan {
+            group: CorrelationGroup::PersonIdentity,
+            columns: vec![
+                ("full_name".to_string(), SemanticType::FullName),
+                ("email".to_string(), SemanticType::Email),
+            ],
+            locale: "en=0.5,de=0.3,ja=0.2".parse().unwrap(),
+        };
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        let mut native = 0;
+        for row in 0..200 {
+            let values = generate_correlated_group(&plan, row, &mut rng, test_base_time());
+            native += usize::from(!values[0].1.to_string().is_ascii());
+            assert!(values[1].1.to_string().is_ascii(), "{:?}", values[1]);
+        }
+        // Japanese names, plus German names with umlauts
+        assert!((20..120).contains(&native), "{} native names", native);
     }
 
     #[test]
@@ -315,6 +562,7 @@ mod tests {
                 ("created_at".to_string(), SemanticType::CreatedAt),
                 ("updated_at".to_string(), SemanticType::UpdatedAt),
             ],
+            locale: Locale::En.into(),
         };
         let bt = test_base_time();
         let mut rng1 = rand::rngs::StdRng::seed_from_u64(42);
@@ -329,6 +577,50 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_temporal_lifecycle_order_within_row() {
+        let plan = CorrelationGroupPlan {
+            group: CorrelationGroup::Temporal,
+            columns: vec![
+                ("deleted_at".to_string(), SemanticType::DeletedAt),
+                ("delivered_at".to_string(), SemanticType::Timestamp),
+                ("updated_at".to_string(), SemanticType::UpdatedAt),
+                ("paidAt".to_string(), SemanticType::Timestamp),
+                ("created_at".to_string(), SemanticType::CreatedAt),
+                ("shipped_at".to_string(), SemanticType::Timestamp),
+            ],
+            locale: Locale::En.into(),
+        };
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        for row in 0..200 {
+            let values = generate_correlated_group(&plan, row, &mut rng, test_base_time());
+            let times: Vec<chrono::NaiveDateTime> = [
+                "created_at",
+                "paidAt",
+                "shipped_at",
+                "delivered_at",
+                "updated_at",
+                "deleted_at",
+            ]
+            .iter()
+            .filter_map(|name| match values.iter().find(|(n, _)| n == name)?.1 {
+                Value::Timestamp(t) => Some(t),
+                _ => None,
+            })
+            .collect();
+            assert!(times.len() >= 5, "{:?}", values);
+            assert!(times.is_sorted(), "row {} out of order: {:?}", row, values);
+        }
+    }
+
+    #[test]
+    fn test_lifecycle_rank() {
+        assert!(lifecycle_rank("paid_at") < lifecycle_rank("shipped_at"));
+        assert!(lifecycle_rank("emailVerifiedAt") < lifecycle_rank("order_delivered_at"));
+        assert_eq!(lifecycle_rank("expires_at"), None);
+        assert_eq!(lifecycle_rank("shipped_on"), None);
+    }
+
     #[test]
     fn test_unmatched_column_falls_back_to_provider() {
         let plan = CorrelationGroupPlan {
@@ -337,6 +629,7 @@ mod tests {
                 ("city".to_string(), SemanticType::City),
                 ("notes".to_string(), SemanticType::Paragraph),
             ],
+            locale: Locale::En.into(),
         };
         let mut rng = rand::rngs::StdRng::seed_from_u64(42);
         let values = generate_correlated_group(&plan, 0, &mut rng, test_base_time());
@@ -358,6 +651,7 @@ mod tests {
                 ("last_name".to_string(), SemanticType::LastName),
                 ("username".to_string(), SemanticType::Username),
             ],
+            locale: Locale::En.into(),
         };
         let mut rng = rand::rngs::StdRng::seed_from_u64(42);
         let values = generate_correlated_group(&plan, 0, &mut rng, test_base_time());
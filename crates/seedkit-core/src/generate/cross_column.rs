@@ -0,0 +1,532 @@
+//! # Cross-Column Constraints
+//!
+//! CHECKs that compare two columns of the same row (`start_date < end_date`,
+//! `discount <= price`, `min_qty <= max_qty`) can't be met by generating
+//! each column on its own. [`RowSolver`] orders the compared columns and,
+//! once a row's values are generated, derives each dependent column from the
+//! one it is compared against:
+//!
+//! - Dates and timestamps derive forward: the later column is the earlier
+//!   one plus up to 30 days, kept within its own date bounds.
+//! - Numbers derive downward: the smaller column is drawn between its own
+//!   lower bound (0 by default) and the larger one, so a discount is a share
+//!   of the price rather than a second, unrelated price.
+//!
+//! References are never derived — they must point at real rows — but can
+//! still anchor the column they are compared against.
+
+use std::collections::{HashMap, HashSet};
+
+use chrono::{Duration, NaiveDateTime, NaiveTime};
+use indexmap::IndexMap;
+use rand::Rng;
+use tracing::warn;
+
+use crate::generate::plan::{GenerationStrategy, TableGenerationPlan};
+use crate::generate::providers::{
+    compute_numeric_bounds_f64, compute_numeric_bounds_i64, datetime_bounds,
+};
+use crate::generate::value::Value;
+use crate::schema::types::ParsedCheck;
+
+/// Largest gap, in days, between a derived date and the one it follows.
+const MAX_DAYS_AFTER: i64 = 30;
+
+/// `left < right` (or `<=` when inclusive) between two columns of a row.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnOrdering {
+    pub left: String,
+    pub right: String,
+    pub inclusive: bool,
+}
+
+/// Satisfies a table's column orderings row by row.
+#[derive(Debug, Default)]
+pub struct RowSolver<'a> {
+    orderings: Vec<ColumnOrdering>,
+    /// Columns in dependency order: every `left` before its `right`.
+    order: Vec<&'a str>,
+    /// Single-column checks of each ordered column, for its own bounds.
+    checks: HashMap<&'a str, &'a [ParsedCheck]>,
+    /// Columns whose values must be kept as generated.
+    fixed: HashSet<&'a str>,
+}
+
+impl<'a> RowSolver<'a> {
+    /// Collect the table's column orderings. Orderings that would form a
+    /// cycle (`a < b`, `b < a`) can't all hold and are dropped with a
+    /// warning.
+    pub fn new(table_plan: &'a TableGenerationPlan) -> Self {
+        let mut orderings = Vec::new();
+        let mut checks = HashMap::new();
+        let mut fixed = HashSet::new();
+        for col_plan in &table_plan.column_plans {
+            let name = col_plan.column_name.as_str();
+            if matches!(
+                col_plan.strategy,
+                GenerationStrategy::ForeignKeyReference { .. }
+                    | GenerationStrategy::PolymorphicType { .. }
+                    | GenerationStrategy::PolymorphicId { .. }
+                    | GenerationStrategy::Deferred
+                    | GenerationStrategy::AutoIncrement
+                    | GenerationStrategy::Skip
+                    | GenerationStrategy::Expression { .. }
+                    | GenerationStrategy::Aggregate { .. }
+            ) {
+                fixed.insert(name);
+            }
+            for check in &col_plan.check_constraints {
+                if let ParsedCheck::ColumnLessThan {
+                    left,
+                    right,
+                    inclusive,
+                } = check
+                {
+                    checks.insert(name, col_plan.check_constraints.as_slice());
+                    // Each ordering is listed on both of its columns
+                    if left == name && left != right {
+                        orderings.push(ColumnOrdering {
+                            left: left.clone(),
+                            right: right.clone(),
+                            inclusive: *inclusive,
+                        });
+                    }
+                }
+            }
+        }
+
+        let mut solver = RowSolver {
+            orderings,
+            order: Vec::new(),
+            checks,
+            fixed,
+        };
+        solver.order = solver.sort_columns(table_plan);
+        solver
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.orderings.is_empty()
+    }
+
+    /// Whether `solve` may rewrite `column`.
+    pub fn derives(&self, column: &str) -> bool {
+        self.order.contains(&column) && !self.fixed.contains(column)
+    }
+
+    /// Topologically sort the ordered columns, keeping plan order among
+    /// independent ones. Orderings left over by a cycle are dropped.
+    fn sort_columns(&mut self, table_plan: &'a TableGenerationPlan) -> Vec<&'a str> {
+        let columns: Vec<&'a str> = table_plan
+            .column_plans
+            .iter()
+            .map(|c| c.column_name.as_str())
+            .filter(|name| {
+                self.orderings
+                    .iter()
+                    .any(|o| o.left == *name || o.right == *name)
+            })
+            .collect();
+
+        let mut order = Vec::with_capacity(columns.len());
+        let mut placed: HashSet<&str> = HashSet::new();
+        while order.len() < columns.len() {
+            let next = columns.iter().find(|name| {
+                !placed.contains(**name)
+                    && self
+                        .orderings
+                        .iter()
+                        .filter(|o| o.right == **name)
+                        .all(|o| placed.contains(o.left.as_str()))
+            });
+            match next {
+                Some(&name) => {
+                    placed.insert(name);
+                    order.push(name);
+                }
+                None => {
+                    let before = self.orderings.len();
+                    let cyclic: Vec<bool> = self
+                        .orderings
+                        .iter()
+                        .map(|o| self.reaches(&o.right, &o.left))
+                        .collect();
+                    let mut cyclic = cyclic.into_iter();
+                    self.orderings.retain(|_| !cyclic.next().unwrap_or(false));
+                    warn!(
+                        "Dropping {} column comparisons on {} that form a cycle",
+                        before - self.orderings.len(),
+                        table_plan.table_name
+                    );
+                    return self.sort_columns(table_plan);
+                }
+            }
+        }
+        order
+    }
+
+    /// Whether `to` can be reached from `from` by following orderings.
+    fn reaches(&self, from: &str, to: &str) -> bool {
+        let mut seen: HashSet<&str> = HashSet::new();
+        let mut stack = vec![from];
+        while let Some(column) = stack.pop() {
+            if column == to {
+                return true;
+            }
+            if seen.insert(column) {
+                stack.extend(
+                    self.orderings
+                        .iter()
+                        .filter(|o| o.left == column)
+                        .map(|o| o.right.as_str()),
+                );
+            }
+        }
+        false
+    }
+
+    /// Rewrite the dependent columns of `row` so every ordering holds.
+    /// NULLs satisfy a CHECK, so orderings with a NULL side are skipped.
+    pub fn solve(&self, row: &mut IndexMap<String, Value>, rng: &mut impl Rng) {
+        // Later dates follow the earlier ones
+        for &column in &self.order {
+            if self.fixed.contains(column) {
+                continue;
+            }
+            let mut floor: Option<(NaiveDateTime, bool)> = None;
+            for ordering in self.orderings.iter().filter(|o| o.right == column) {
+                let Some(bound) = row.get(&ordering.left).and_then(as_datetime) else {
+                    continue;
+                };
+                let strict = !ordering.inclusive;
+                floor = Some(match floor {
+                    Some((current, s)) if current > bound => (current, s),
+                    Some((current, s)) if current == bound => (current, s || strict),
+                    _ => (bound, strict),
+                });
+            }
+            let Some((floor, strict)) = floor else {
+                continue;
+            };
+            let checks = self.checks.get(column).copied().unwrap_or_default();
+            let (own_min, own_max) = datetime_bounds(checks);
+            let derived = match row.get(column) {
+                Some(Value::Timestamp(_)) => {
+                    let mut earliest = if strict {
+                        floor + Duration::minutes(1)
+                    } else {
+                        floor
+                    };
+                    if let Some(min) = own_min {
+                        earliest = earliest.max(min);
+                    }
+                    let latest = own_max.map_or(MAX_DAYS_AFTER * 24 * 60, |max| {
+                        (max - earliest).num_minutes().min(MAX_DAYS_AFTER * 24 * 60)
+                    });
+                    if latest < 0 {
+                        continue;
+                    }
+                    let minutes = rng.random_range(0..=latest);
+                    Value::Timestamp(earliest + Duration::minutes(minutes))
+                }
+                Some(Value::Date(_)) => {
+                    let next_day = |t: NaiveDateTime, strict: bool| {
+                        if strict || t.time() != NaiveTime::MIN {
+                            t.date() + Duration::days(1)
+                        } else {
+                            t.date()
+                        }
+                    };
+                    let mut earliest = next_day(floor, strict);
+                    if let Some(min) = own_min {
+                        earliest = earliest.max(next_day(min, false));
+                    }
+                    let latest = own_max.map_or(MAX_DAYS_AFTER, |max| {
+                        (max.date() - earliest).num_days().min(MAX_DAYS_AFTER)
+                    });
+                    if latest < 0 {
+                        continue;
+                    }
+                    let days = rng.random_range(0..=latest);
+                    Value::Date(earliest + Duration::days(days))
+                }
+                _ => continue,
+            };
+            row.insert(column.to_string(), derived);
+        }
+
+        // Smaller numbers are drawn below the larger ones
+        for &column in self.order.iter().rev() {
+            if self.fixed.contains(column) {
+                continue;
+            }
+            let mut ceiling: Option<(f64, bool)> = None;
+            for ordering in self.orderings.iter().filter(|o| o.left == column) {
+                let Some(bound) = row.get(&ordering.right).and_then(as_number) else {
+                    continue;
+                };
+                let strict = !ordering.inclusive;
+                ceiling = Some(match ceiling {
+                    Some((current, s)) if current < bound => (current, s),
+                    Some((current, s)) if current == bound => (current, s || strict),
+                    _ => (bound, strict),
+                });
+            }
+            let Some((ceiling, strict)) = ceiling else {
+                continue;
+            };
+            let checks = self.checks.get(column).copied().unwrap_or_default();
+            let derived = match row.get(column) {
+                Some(Value::Int(_)) => {
+                    let high = if strict {
+                        ceiling.ceil() as i64 - 1
+                    } else {
+                        ceiling.floor() as i64
+                    };
+                    let (own_min, own_max) = compute_numeric_bounds_i64(i64::MIN, i64::MAX, checks);
+                    let default_min = if high > 0 {
+                        0
+                    } else {
+                        high.saturating_sub(100)
+                    };
+                    let (low, high) = (own_min.max(default_min), own_max.min(high));
+                    if low > high {
+                        continue;
+                    }
+                    Value::Int(rng.random_range(low..=high))
+                }
+                Some(Value::Float(_)) => {
+                    let high = if strict { ceiling - 0.01 } else { ceiling };
+                    let (own_min, own_max) = compute_numeric_bounds_f64(f64::MIN, f64::MAX, checks);
+                    let default_min = if high > 0.0 { 0.0 } else { high - 100.0 };
+                    let (low, high) = (own_min.max(default_min), own_max.min(high));
+                    if low > high {
+                        continue;
+                    }
+                    let value = rng.random_range(low..=high);
+                    // Round to cents, staying inside the bounds
+                    let rounded = (value * 100.0).round() / 100.0;
+                    Value::Float(if (low..=high).contains(&rounded) {
+                        rounded
+                    } else {
+                        value
+                    })
+                }
+                _ => continue,
+            };
+            row.insert(column.to_string(), derived);
+        }
+    }
+}
+
+fn as_datetime(value: &Value) -> Option<NaiveDateTime> {
+    match value {
+        Value::Timestamp(t) => Some(*t),
+        Value::Date(d) => Some(d.and_time(NaiveTime::MIN)),
+        _ => None,
+    }
+}
+
+fn as_number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Int(v) => Some(*v as f64),
+        Value::Float(v) => Some(*v),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::classify::semantic::SemanticType;
+    use crate::generate::plan::ColumnGenerationPlan;
+    use chrono::NaiveDate;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn column(name: &str, checks: Vec<ParsedCheck>) -> ColumnGenerationPlan {
+        ColumnGenerationPlan {
+            column_name: name.to_string(),
+            semantic_type: SemanticType::Unknown,
+            strategy: GenerationStrategy::SemanticProvider,
+            nullable: false,
+            null_probability: 0.0,
+            check_constraints: checks,
+            max_length: None,
+            text_profile: None,
+            distinct_ratio: None,
+            temporal_profile: None,
+            sampled_fan_out: None,
+        }
+    }
+
+    fn less_than(left: &str, right: &str, inclusive: bool) -> ParsedCheck {
+        ParsedCheck::ColumnLessThan {
+            left: left.to_string(),
+            right: right.to_string(),
+            inclusive,
+        }
+    }
+
+    fn table(columns: Vec<ColumnGenerationPlan>) -> TableGenerationPlan {
+        TableGenerationPlan {
+            table_name: "t".to_string(),
+            row_count: 1,
+            column_plans: columns,
+            correlation_groups: Vec::new(),
+            stable: false,
+            pinned_rows: None,
+            sequence_offset: None,
+            language_mix: None,
+            row_script: None,
+            time_series: None,
+            funnel: None,
+            segments: Vec::new(),
+            copula: None,
+        }
+    }
+
+    #[test]
+    fn test_dates_derive_forward_through_a_chain() {
+        let placed = less_than("placed_on", "shipped_on", false);
+        let shipped = less_than("shipped_on", "delivered_on", true);
+        // Declared out of dependency order on purpose
+        let plan = table(vec![
+            column("delivered_on", vec![shipped.clone()]),
+            column("shipped_on", vec![placed.clone(), shipped]),
+            column("placed_on", vec![placed]),
+        ]);
+        let solver = RowSolver::new(&plan);
+        assert_eq!(solver.order, ["placed_on", "shipped_on", "delivered_on"]);
+
+        let day = |d| Value::Date(NaiveDate::from_ymd_opt(2024, 3, d).unwrap());
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..50 {
+            let mut row = IndexMap::new();
+            row.insert("delivered_on".to_string(), day(1));
+            row.insert("shipped_on".to_string(), day(5));
+            row.insert("placed_on".to_string(), day(20));
+            solver.solve(&mut row, &mut rng);
+            let dates: Vec<_> = ["placed_on", "shipped_on", "delivered_on"]
+                .iter()
+                .map(|c| as_datetime(&row[*c]).unwrap())
+                .collect();
+            assert_eq!(dates[0], as_datetime(&day(20)).unwrap());
+            assert!(dates[0] < dates[1] && dates[1] <= dates[2]);
+        }
+    }
+
+    #[test]
+    fn test_derived_dates_keep_their_own_bounds() {
+        let at = |d| NaiveDate::from_ymd_opt(2024, 3, d).unwrap();
+        let ordering = less_than("starts_on", "ends_on", false);
+        let after = ParsedCheck::After {
+            column: "ends_on".to_string(),
+            value: at(10).and_time(NaiveTime::MIN),
+            inclusive: true,
+        };
+        let before = ParsedCheck::Before {
+            column: "ends_on".to_string(),
+            value: at(14).and_time(NaiveTime::MIN),
+            inclusive: true,
+        };
+        let plan = table(vec![
+            column("starts_on", vec![ordering.clone()]),
+            column("ends_on", vec![ordering, after, before]),
+        ]);
+        let solver = RowSolver::new(&plan);
+
+        let mut rng = StdRng::seed_from_u64(7);
+        for (start, range) in [(1, at(10)..=at(14)), (12, at(13)..=at(14))] {
+            for _ in 0..50 {
+                let mut row = IndexMap::new();
+                row.insert("starts_on".to_string(), Value::Date(at(start)));
+                row.insert("ends_on".to_string(), Value::Date(at(1)));
+                solver.solve(&mut row, &mut rng);
+                let Value::Date(ends_on) = row["ends_on"] else {
+                    panic!("Expected a date");
+                };
+                assert!(range.contains(&ends_on), "{}", ends_on);
+            }
+        }
+
+        // Bounds that can't hold with the ordering leave the value alone
+        let mut row = IndexMap::new();
+        row.insert("starts_on".to_string(), Value::Date(at(20)));
+        row.insert("ends_on".to_string(), Value::Date(at(12)));
+        solver.solve(&mut row, &mut rng);
+        assert_eq!(row["ends_on"], Value::Date(at(12)));
+    }
+
+    #[test]
+    fn test_numbers_derive_below_the_larger_column() {
+        let ordering = less_than("discount", "price", true);
+        let min_qty = ParsedCheck::GreaterThanOrEqual {
+            column: "min_qty".to_string(),
+            value: 1.0,
+        };
+        let qty = less_than("min_qty", "max_qty", false);
+        let plan = table(vec![
+            column("price", vec![ordering.clone()]),
+            column("discount", vec![ordering]),
+            column("min_qty", vec![min_qty, qty.clone()]),
+            column("max_qty", vec![qty]),
+        ]);
+        let solver = RowSolver::new(&plan);
+
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..50 {
+            let mut row = IndexMap::new();
+            row.insert("price".to_string(), Value::Float(19.99));
+            row.insert("discount".to_string(), Value::Float(250.0));
+            row.insert("min_qty".to_string(), Value::Int(80));
+            row.insert("max_qty".to_string(), Value::Int(12));
+            solver.solve(&mut row, &mut rng);
+            let Value::Float(discount) = row["discount"] else {
+                panic!("Expected a float");
+            };
+            assert!((0.0..=19.99).contains(&discount));
+            let Value::Int(min_qty) = row["min_qty"] else {
+                panic!("Expected an int");
+            };
+            assert!((1..12).contains(&min_qty));
+        }
+    }
+
+    #[test]
+    fn test_references_and_nulls_are_left_alone() {
+        let ordering = less_than("parent_id", "id", false);
+        let mut parent = column("parent_id", vec![ordering.clone()]);
+        parent.strategy = GenerationStrategy::ForeignKeyReference {
+            referenced_table: "t".to_string(),
+            referenced_column: "id".to_string(),
+            fan_out: Default::default(),
+        };
+        let plan = table(vec![column("id", vec![ordering]), parent]);
+        let solver = RowSolver::new(&plan);
+
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut row = IndexMap::new();
+        row.insert("id".to_string(), Value::Int(1));
+        row.insert("parent_id".to_string(), Value::Int(9));
+        solver.solve(&mut row, &mut rng);
+        assert_eq!(row["parent_id"], Value::Int(9));
+
+        row.insert("id".to_string(), Value::Null);
+        solver.solve(&mut row, &mut rng);
+        assert_eq!(row["parent_id"], Value::Int(9));
+    }
+
+    #[test]
+    fn test_cyclic_orderings_are_dropped() {
+        let forward = less_than("a", "b", false);
+        let backward = less_than("b", "a", false);
+        let plan = table(vec![
+            column("a", vec![forward.clone(), backward.clone()]),
+            column("b", vec![forward, backward]),
+        ]);
+        let solver = RowSolver::new(&plan);
+        assert!(solver.is_empty());
+        assert!(solver.order.is_empty());
+    }
+}
@@ -1,29 +1,350 @@
 //! # Custom Provider Support
 //!
-//! Placeholder for Phase 4. Custom providers allow users to supply JS scripts
-//! or WASM modules via `seedkit.toml` for domain-specific value generation
-//! (e.g., tax codes, diagnosis codes, weighted product colors).
+//! Custom providers supply domain-specific values (tax codes, diagnosis
+//! codes, ...) from a WebAssembly module configured in `seedkit.toml`:
 //!
 //! ```toml
 //! [columns."orders.tax_code"]
-//! custom = "./scripts/tax_gen.js"
+//! custom = "./providers/tax_code.wasm"
 //! ```
+//!
+//! Modules run in wasmtime with no imports — no clock, randomness or I/O —
+//! so a value depends only on what the ABI passes in and generation stays
+//! deterministic under the seed. Text-format modules (`.wat`) load too.
+//! JavaScript providers are not supported yet. WebAssembly support is the
+//! `wasm` cargo feature (on by default); without it these columns fail to
+//! load.
+//!
+//! ## ABI
+//!
+//! A module exports:
+//!
+//! - `memory`
+//! - `seedkit_alloc(len: i32) -> i32` — reserve `len` bytes and return their
+//!   address. Called once, to pass the column context.
+//! - `seedkit_generate(row_index: i64, seed: i64, ctx_ptr: i32, ctx_len: i32) -> i64`
+//!   — the value for one row, as a UTF-8 JSON value in memory: its address
+//!   in the upper 32 bits of the result, its byte length in the lower 32.
+//!
+//! The context is a JSON object such as
+//! `{"table":"orders","column":"tax_code","data_type":"varchar(20)"}`.
+//! `seed` is derived from the plan's seed and the column name, so a column's
+//! values don't shift when other columns change. JSON numbers, strings,
+//! booleans and `null` become the matching values; arrays and objects are
+//! inserted as JSON.
+//!
+//! Columns configured with `command = [...]` run an external process
+//! instead; see [`crate::generate::process`]. Both kinds load here.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+#[cfg(feature = "wasm")]
+use wasmtime::{Config, Engine, Instance, Memory, Module, Store, TypedFunc};
+
+use crate::error::{Result, SeedKitError};
+use crate::generate::plan::{GenerationPlan, GenerationStrategy};
+use crate::generate::process::ProcessProvider;
+use crate::generate::value::Value;
+use crate::schema::types::DatabaseSchema;
+
+/// Fuel for a single `seedkit_generate` call, roughly one unit per
+/// instruction. Stops a module that loops forever instead of hanging the run.
+#[cfg(feature = "wasm")]
+const FUEL_PER_VALUE: u64 = 10_000_000;
+
+/// Whether `path` names a provider this module can run.
+pub fn is_wasm_provider(path: &str) -> bool {
+    Path::new(path)
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("wasm") || ext.eq_ignore_ascii_case("wat"))
+}
+
+/// The loaded providers of a plan, by table and column.
+#[derive(Default)]
+pub struct CustomProviders {
+    providers: HashMap<String, HashMap<String, Provider>>,
+}
+
+enum Provider {
+    #[cfg(feature = "wasm")]
+    Wasm(WasmProvider),
+    Process(ProcessProvider),
+}
+
+impl CustomProviders {
+    /// Compile and instantiate every `.wasm`/`.wat` provider in the plan,
+    /// and start every provider process.
+    pub fn load(plan: &GenerationPlan, schema: &DatabaseSchema) -> Result<Self> {
+        let mut providers: HashMap<String, HashMap<String, Provider>> = HashMap::new();
+        #[cfg(feature = "wasm")]
+        let mut engine = None;
+        for table_plan in &plan.table_plans {
+            for col_plan in &table_plan.column_plans {
+                let loadable = match col_plan.strategy {
+                    GenerationStrategy::Custom { ref provider_path } => {
+                        is_wasm_provider(provider_path)
+                    }
+                    GenerationStrategy::Process { .. } => true,
+                    _ => false,
+                };
+                if !loadable {
+                    continue;
+                }
+                let data_type = schema
+                    .tables
+                    .get(&table_plan.table_name)
+                    .and_then(|t| t.columns.get(&col_plan.column_name))
+                    .map(|c| c.raw_type.clone())
+                    .unwrap_or_default();
+                let context = serde_json::json!({
+                    "table": table_plan.table_name,
+                    "column": col_plan.column_name,
+                    "data_type": data_type,
+                });
+                let seed = plan.seed_derivation.derive(
+                    plan.seed,
+                    &format!("{}.{}", table_plan.table_name, col_plan.column_name),
+                );
+                let provider = match col_plan.strategy {
+                    GenerationStrategy::Process {
+                        ref command,
+                        ref current_dir,
+                    } => Provider::Process(ProcessProvider::spawn(
+                        command,
+                        current_dir.as_deref(),
+                        context,
+                        seed,
+                        table_plan.row_count,
+                    )?),
+                    #[cfg(feature = "wasm")]
+                    GenerationStrategy::Custom { ref provider_path } => {
+                        let engine = match engine {
+                            Some(ref engine) => engine,
+                            None => engine.insert(new_engine(provider_path)?),
+                        };
+                        Provider::Wasm(WasmProvider::load(engine, provider_path, &context, seed)?)
+                    }
+                    #[cfg(not(feature = "wasm"))]
+                    GenerationStrategy::Custom { ref provider_path } => {
+                        return Err(provider_error(
+                            provider_path,
+                            "SeedKit was built without the `wasm` feature",
+                        ));
+                    }
+                    _ => continue,
+                };
+                providers
+                    .entry(table_plan.table_name.clone())
+                    .or_default()
+                    .insert(col_plan.column_name.clone(), provider);
+            }
+        }
+        Ok(Self { providers })
+    }
+
+    /// The value of `table.column` for `row_index`, or `None` when the
+    /// column has no loaded provider.
+    pub fn generate(
+        &mut self,
+        table: &str,
+        column: &str,
+        row_index: usize,
+    ) -> Option<Result<Value>> {
+        let provider = self.providers.get_mut(table)?.get_mut(column)?;
+        Some(match provider {
+            #[cfg(feature = "wasm")]
+            Provider::Wasm(wasm) => wasm.generate(row_index),
+            Provider::Process(process) => process.generate(row_index),
+        })
+    }
+}
 
-/// A custom provider configuration from seedkit.toml.
-#[derive(Debug, Clone)]
-pub struct CustomProvider {
-    pub path: String,
-    pub provider_type: CustomProviderType,
+#[cfg(feature = "wasm")]
+fn new_engine(path: &str) -> Result<Engine> {
+    let mut config = Config::new();
+    config.consume_fuel(true);
+    // NaN bit patterns otherwise depend on the host CPU
+    config.cranelift_nan_canonicalization(true);
+    Engine::new(&config).map_err(|e| provider_error(path, e))
 }
 
-#[derive(Debug, Clone)]
-pub enum CustomProviderType {
-    JavaScript,
-    Wasm,
-    ValueList {
-        values: Vec<String>,
-        weights: Option<Vec<f64>>,
-    },
+fn provider_error(path: &str, message: impl std::fmt::Display) -> SeedKitError {
+    SeedKitError::CustomProvider {
+        path: path.to_string(),
+        message: message.to_string(),
+    }
 }
 
-// TODO: Phase 4 - Implement JS/WASM provider execution via deno_core
+#[cfg(feature = "wasm")]
+struct WasmProvider {
+    path: String,
+    store: Store<()>,
+    memory: Memory,
+    generate: TypedFunc<(i64, i64, i32, i32), i64>,
+    seed: i64,
+    context: (i32, i32),
+}
+
+#[cfg(feature = "wasm")]
+impl WasmProvider {
+    fn load(engine: &Engine, path: &str, context: &serde_json::Value, seed: u64) -> Result<Self> {
+        let err = |e| provider_error(path, e);
+        let module = Module::from_file(engine, path).map_err(err)?;
+        if let Some(import) = module.imports().next() {
+            return Err(provider_error(
+                path,
+                format!(
+                    "modules must not import anything, but it imports {}::{}",
+                    import.module(),
+                    import.name()
+                ),
+            ));
+        }
+
+        let mut store = Store::new(engine, ());
+        store.set_fuel(FUEL_PER_VALUE).map_err(err)?;
+        let instance = Instance::new(&mut store, &module, &[]).map_err(err)?;
+        let memory = instance
+            .get_memory(&mut store, "memory")
+            .ok_or_else(|| provider_error(path, "missing export 'memory'"))?;
+        let alloc = instance
+            .get_typed_func::<i32, i32>(&mut store, "seedkit_alloc")
+            .map_err(err)?;
+        let generate = instance
+            .get_typed_func::<(i64, i64, i32, i32), i64>(&mut store, "seedkit_generate")
+            .map_err(err)?;
+
+        let context = context.to_string();
+        let len = context.len() as i32;
+        let ptr = alloc.call(&mut store, len).map_err(err)?;
+        memory
+            .write(&mut store, ptr as u32 as usize, context.as_bytes())
+            .map_err(|e| provider_error(path, e))?;
+
+        Ok(Self {
+            path: path.to_string(),
+            store,
+            memory,
+            generate,
+            seed: seed as i64,
+            context: (ptr, len),
+        })
+    }
+
+    fn generate(&mut self, row_index: usize) -> Result<Value> {
+        let path = self.path.as_str();
+        self.store
+            .set_fuel(FUEL_PER_VALUE)
+            .map_err(|e| provider_error(path, e))?;
+        let (ctx_ptr, ctx_len) = self.context;
+        let packed = self
+            .generate
+            .call(
+                &mut self.store,
+                (row_index as i64, self.seed, ctx_ptr, ctx_len),
+            )
+            .map_err(|e| provider_error(path, format!("row {}: {}", row_index, e)))?;
+
+        let ptr = (packed >> 32) as u32 as usize;
+        let len = packed as u32 as usize;
+        let bytes = self
+            .memory
+            .data(&self.store)
+            .get(ptr..ptr + len)
+            .ok_or_else(|| {
+                provider_error(path, format!("row {}: result is out of bounds", row_index))
+            })?;
+        let json: serde_json::Value = serde_json::from_slice(bytes).map_err(|e| {
+            provider_error(
+                path,
+                format!("row {}: result is not JSON: {}", row_index, e),
+            )
+        })?;
+        Ok(Value::from_json(&json))
+    }
+}
+
+#[cfg(all(test, feature = "wasm"))]
+mod tests {
+    use super::*;
+    use crate::generate::plan::single_column_plan;
+    use crate::schema::types::DatabaseType;
+
+    /// Writes `"TX-<row>"`-style codes: the row index modulo 10 as one digit
+    /// after a fixed prefix, at address 1024.
+    const TAX_CODE: &str = r#"
+        (module
+          (memory (export "memory") 1)
+          (data (i32.const 1024) "\"TX-0\"")
+          (func (export "seedkit_alloc") (param i32) (result i32) (i32.const 0))
+          (func (export "seedkit_generate")
+                (param $row i64) (param $seed i64) (param i32) (param i32) (result i64)
+            (i32.store8 (i32.const 1028)
+              (i32.add (i32.const 48) (i32.wrap_i64 (i64.rem_u (local.get $row) (i64.const 10)))))
+            (i64.or (i64.shl (i64.const 1024) (i64.const 32)) (i64.const 6))))
+    "#;
+
+    fn plan_with_provider(path: &str) -> GenerationPlan {
+        single_column_plan(
+            "orders",
+            "tax_code",
+            GenerationStrategy::Custom {
+                provider_path: path.to_string(),
+            },
+            3,
+        )
+    }
+
+    fn write_module(dir: &tempfile::TempDir, wat: &str) -> String {
+        let path = dir.path().join("provider.wat");
+        std::fs::write(&path, wat).unwrap();
+        path.to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn test_wasm_provider_generates_per_row() {
+        let dir = tempfile::tempdir().unwrap();
+        let plan = plan_with_provider(&write_module(&dir, TAX_CODE));
+        let schema = DatabaseSchema::new(DatabaseType::PostgreSQL, "test".to_string());
+        let mut providers = CustomProviders::load(&plan, &schema).unwrap();
+
+        let value = |providers: &mut CustomProviders, row| {
+            providers
+                .generate("orders", "tax_code", row)
+                .unwrap()
+                .unwrap()
+        };
+        assert_eq!(value(&mut providers, 0), Value::String("TX-0".into()));
+        assert_eq!(value(&mut providers, 7), Value::String("TX-7".into()));
+        assert!(providers.generate("orders", "other", 0).is_none());
+    }
+
+    #[test]
+    fn test_wasm_provider_rejects_imports_and_runaway_loops() {
+        let dir = tempfile::tempdir().unwrap();
+        let schema = DatabaseSchema::new(DatabaseType::PostgreSQL, "test".to_string());
+
+        let importing = r#"(module (import "env" "now" (func)) (memory (export "memory") 1))"#;
+        let plan = plan_with_provider(&write_module(&dir, importing));
+        let err = CustomProviders::load(&plan, &schema).err().unwrap();
+        assert!(err.to_string().contains("env::now"), "{}", err);
+
+        let looping = r#"
+            (module
+              (memory (export "memory") 1)
+              (func (export "seedkit_alloc") (param i32) (result i32) (i32.const 0))
+              (func (export "seedkit_generate")
+                    (param i64 i64 i32 i32) (result i64)
+                (loop $forever (br $forever))
+                (i64.const 0)))
+        "#;
+        let plan = plan_with_provider(&write_module(&dir, looping));
+        let mut providers = CustomProviders::load(&plan, &schema).unwrap();
+        let err = providers
+            .generate("orders", "tax_code", 0)
+            .unwrap()
+            .unwrap_err();
+        assert!(err.to_string().contains("row 0"), "{}", err);
+    }
+}
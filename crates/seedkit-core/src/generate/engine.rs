@@ -3,16 +3,39 @@ use rand::rngs::StdRng;
 use rand::Rng;
 use rand::SeedableRng;
 use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
 use tracing::warn;
 
+use crate::classify::semantic::{CorrelationGroup, SemanticType};
 use crate::error::{Result, SeedKitError};
-use crate::generate::foreign_key::ForeignKeyPool;
+use crate::generate::aggregate::fill_aggregates;
+use crate::generate::append::ExistingKeys;
+use crate::generate::copula::normal_quantile;
+use crate::generate::cross_column::RowSolver;
+use crate::generate::custom::CustomProviders;
+use crate::generate::expression::RowExpressions;
+use crate::generate::foreign_key::{FanOut, ForeignKeyPool};
+use crate::generate::funnel::JourneyCursor;
+use crate::generate::json_schema::generate_document;
+use crate::generate::json_shape::generate_shaped;
+use crate::generate::language::generate_text;
+use crate::generate::lifecycle::apply_lifecycle;
 use crate::generate::plan::*;
-use crate::generate::providers::generate_value;
-use crate::generate::unique::UniqueTracker;
+use crate::generate::polymorphic::pick_target;
+use crate::generate::providers::{
+    fit_identity_to_length, generate_binary, generate_geometry, generate_interval, generate_range,
+    generate_value, generate_value_for_type, satisfy_checks,
+};
+use crate::generate::script::RowScripts;
+use crate::generate::segment::assign_segments;
+use crate::generate::template::{render, unknown_column};
+use crate::generate::temporal_profile::{shape_date, shape_timestamp};
+use crate::generate::text_profile::conform_text;
+use crate::generate::time_series::SeriesCursor;
+use crate::generate::unique::{lead_with_row_index, UniqueTracker};
 use crate::generate::value::Value;
 use crate::sample::stats::ColumnDistribution;
-use crate::schema::types::DatabaseSchema;
+use crate::schema::types::{DataType, DatabaseSchema};
 
 /// The result of generating data for all tables.
 #[derive(Debug)]
@@ -26,7 +49,7 @@ pub struct GeneratedData {
 }
 
 /// A deferred FK update (for cycle-breaking).
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct DeferredUpdate {
     pub table_name: String,
     pub row_index: usize,
@@ -47,15 +70,29 @@ const PROGRESS_BATCH_SIZE: usize = 100;
 /// - Use `direct.rs` with `INSERT ... RETURNING id` to capture real
 ///   database-assigned IDs (Phase 2 RETURNING pipeline).
 ///
-/// The `sequence_offset` in `GenerationPlan` allows callers to shift the
-/// synthesized IDs (e.g., if the sequence starts at 5001 instead of 1).
+/// The `sequence_offset` in `GenerationPlan` (or per table, as detected by
+/// `generate::sequence`) shifts the synthesized IDs, e.g. to 5001 onwards
+/// when the table already holds 5000 rows.
 #[allow(clippy::type_complexity)]
 pub fn execute_plan(
     plan: &GenerationPlan,
     schema: &DatabaseSchema,
     progress_callback: Option<&dyn Fn(&str, usize, usize)>,
 ) -> Result<GeneratedData> {
-    let mut rng = StdRng::seed_from_u64(plan.seed);
+    execute_plan_with_existing(plan, schema, &ExistingKeys::new(), progress_callback)
+}
+
+/// Execute a generation plan around rows already in the database
+/// (`seedkit generate --append`): foreign keys may reference the `existing`
+/// key values, and unique columns avoid them.
+#[allow(clippy::type_complexity)]
+pub fn execute_plan_with_existing(
+    plan: &GenerationPlan,
+    schema: &DatabaseSchema,
+    existing: &ExistingKeys,
+    progress_callback: Option<&dyn Fn(&str, usize, usize)>,
+) -> Result<GeneratedData> {
+    let mut rng = StdRng::seed_from_u64(plan.seed_derivation.run_seed(plan.seed));
     let mut fk_pool = ForeignKeyPool::new();
     let mut unique_tracker = UniqueTracker::new();
     let mut generated = GeneratedData {
@@ -69,6 +106,9 @@ pub fn execute_plan(
             for uc in &table.unique_constraints {
                 unique_tracker.register_constraint(&table_plan.table_name, &uc.columns);
             }
+            for spec in &table.unique_key_specs {
+                unique_tracker.register_key_spec(&table_plan.table_name, spec);
+            }
             // Also register PK as unique
             if let Some(pk) = &table.primary_key {
                 unique_tracker.register_constraint(&table_plan.table_name, &pk.columns);
@@ -76,33 +116,185 @@ pub fn execute_plan(
         }
     }
 
+    // Non-PK columns that foreign keys point at (e.g. `countries.code`) are
+    // recorded in the FK pool too, so references to them resolve.
+    let mut referenced_columns: HashMap<&str, HashSet<&str>> = HashMap::new();
+    for fk in schema.tables.values().flat_map(|t| &t.foreign_keys) {
+        let pk_columns = schema
+            .tables
+            .get(&fk.referenced_table)
+            .and_then(|t| t.primary_key.as_ref())
+            .map(|pk| pk.columns.as_slice())
+            .unwrap_or_default();
+        referenced_columns
+            .entry(fk.referenced_table.as_str())
+            .or_default()
+            .extend(
+                fk.referenced_columns
+                    .iter()
+                    .filter(|c| !pk_columns.contains(c))
+                    .map(String::as_str),
+            );
+    }
+
+    // Existing rows come first in the FK pools and count towards uniqueness
+    for (table_name, column, values) in existing.iter() {
+        let Some(table) = schema.tables.get(table_name) else {
+            continue;
+        };
+        let pooled = table
+            .primary_key
+            .as_ref()
+            .is_some_and(|pk| pk.columns.iter().any(|c| c == column))
+            || referenced_columns
+                .get(table_name)
+                .is_some_and(|cols| cols.contains(column));
+        for value in values {
+            unique_tracker.try_insert_single(table_name, column, value);
+            if pooled {
+                fk_pool.record_value(table_name, column, value.clone());
+            }
+        }
+    }
+
+    let mut custom = CustomProviders::load(plan, schema)?;
+    let scripts = RowScripts::load(plan, schema)?;
+    let expressions = RowExpressions::load(plan)?;
+
     let total_rows: usize = plan.table_plans.iter().map(|t| t.row_count).sum();
     let mut rows_generated = 0usize;
 
     for table_plan in &plan.table_plans {
         let mut table_rows = Vec::with_capacity(table_plan.row_count);
+        // Stable tables always draw from their own stream
+        let mut table_rng = (plan.table_streams || table_plan.stable).then(|| {
+            StdRng::seed_from_u64(
+                plan.seed_derivation
+                    .derive(plan.seed, &table_plan.table_name),
+            )
+        });
+        let solver = RowSolver::new(table_plan);
+        let repeats = repeat_slots(plan, table_plan);
+        let mut series = SeriesCursor::new(table_plan, &fk_pool);
+        let mut journeys = JourneyCursor::new(table_plan);
+        let segments = match table_plan.pinned_rows {
+            None if !table_plan.segments.is_empty() => assign_segments(
+                &table_plan.segments,
+                table_plan.row_count,
+                table_rng.as_mut().unwrap_or(&mut rng),
+            ),
+            _ => Vec::new(),
+        };
+        let segment_of = |row_idx: usize| {
+            let index = segments.get(row_idx).copied().flatten()?;
+            table_plan.segments.get(index)
+        };
+
+        if table_plan.pinned_rows.is_none() {
+            for col_plan in &table_plan.column_plans {
+                if let GenerationStrategy::ForeignKeyReference {
+                    referenced_table,
+                    referenced_column,
+                    fan_out: FanOut::Children(min, max),
+                } = &col_plan.strategy
+                {
+                    fk_pool.allocate_children(
+                        &table_plan.table_name,
+                        &col_plan.column_name,
+                        referenced_table,
+                        referenced_column,
+                        (*min, *max),
+                        table_plan.row_count,
+                        table_rng.as_mut().unwrap_or(&mut rng),
+                    );
+                } else if let (
+                    GenerationStrategy::ForeignKeyReference {
+                        referenced_table,
+                        referenced_column,
+                        ..
+                    },
+                    Some(shares),
+                ) = (&col_plan.strategy, &col_plan.sampled_fan_out)
+                {
+                    fk_pool.allocate_sampled(
+                        &table_plan.table_name,
+                        &col_plan.column_name,
+                        referenced_table,
+                        referenced_column,
+                        shares,
+                        table_plan.row_count,
+                        table_rng.as_mut().unwrap_or(&mut rng),
+                    );
+                }
+            }
+        }
 
         for row_idx in 0..table_plan.row_count {
-            let row = generate_row(
-                table_plan,
-                row_idx,
-                &mut rng,
-                &fk_pool,
-                &mut unique_tracker,
-                schema,
-                plan.base_time,
-            )?;
+            let row = match &table_plan.pinned_rows {
+                Some(rows) => rows[row_idx].clone(),
+                None => {
+                    let mut row = generate_row(
+                        table_plan,
+                        row_idx,
+                        table_rng.as_mut().unwrap_or(&mut rng),
+                        &fk_pool,
+                        &mut unique_tracker,
+                        schema,
+                        &solver,
+                        &mut custom,
+                        &repeats,
+                        plan.base_time,
+                    )?;
+                    if let Some(ref mut series) = series {
+                        series.apply(
+                            row_idx,
+                            &mut row,
+                            &fk_pool,
+                            plan.base_time,
+                            table_rng.as_mut().unwrap_or(&mut rng),
+                        );
+                    }
+                    if let Some(ref mut journeys) = journeys {
+                        journeys.apply(
+                            &mut row,
+                            plan.base_time,
+                            table_rng.as_mut().unwrap_or(&mut rng),
+                        );
+                    }
+                    if let Some(segment) = segment_of(row_idx) {
+                        segment.apply(&mut row, table_rng.as_mut().unwrap_or(&mut rng));
+                    }
+                    expressions.apply(&table_plan.table_name, row_idx, &mut row)?;
+                    scripts.apply(&table_plan.table_name, row_idx, &mut row)?;
+                    row
+                }
+            };
 
             // Record PK values into the FK pool so child tables can reference them.
             // For auto-increment PKs that we skip during generation,
             // synthesize sequential IDs offset by `sequence_offset`.
             // See the clean-slate assumption in the doc comment above.
+            // Children are created after the rows they reference
+            let created_at = row_created_at(table_plan, &row);
+            let activity = segment_of(row_idx).map(|s| s.activity);
             if let Some(table) = schema.tables.get(&table_plan.table_name) {
                 if let Some(pk) = &table.primary_key {
                     for pk_col in &pk.columns {
                         if let Some(value) = row.get(pk_col) {
                             if !value.is_null() {
-                                fk_pool.record_value(&table_plan.table_name, pk_col, value.clone());
+                                fk_pool.record_value_at(
+                                    &table_plan.table_name,
+                                    pk_col,
+                                    value.clone(),
+                                    created_at,
+                                );
+                                if let Some(activity) = activity {
+                                    fk_pool.set_last_activity(
+                                        &table_plan.table_name,
+                                        pk_col,
+                                        activity,
+                                    );
+                                }
                             }
                         } else {
                             // Auto-increment PK not in row — synthesize sequential ID
@@ -111,16 +303,43 @@ pub fn execute_plan(
                                 .map(|c| c.is_auto_increment || c.data_type.is_serial())
                                 .unwrap_or(false);
                             if is_auto {
-                                let id = plan.sequence_offset as i64 + row_idx as i64 + 1;
-                                fk_pool.record_value(
+                                let offset =
+                                    table_plan.sequence_offset.unwrap_or(plan.sequence_offset);
+                                let id = offset as i64 + row_idx as i64 + 1;
+                                fk_pool.record_value_at(
                                     &table_plan.table_name,
                                     pk_col,
                                     Value::Int(id),
+                                    created_at,
                                 );
+                                if let Some(activity) = activity {
+                                    fk_pool.set_last_activity(
+                                        &table_plan.table_name,
+                                        pk_col,
+                                        activity,
+                                    );
+                                }
                             }
                         }
                     }
                 }
+                for &col_name in referenced_columns
+                    .get(table_plan.table_name.as_str())
+                    .into_iter()
+                    .flatten()
+                {
+                    if let Some(value) = row.get(col_name).filter(|v| !v.is_null()) {
+                        fk_pool.record_value_at(
+                            &table_plan.table_name,
+                            col_name,
+                            value.clone(),
+                            created_at,
+                        );
+                        if let Some(activity) = activity {
+                            fk_pool.set_last_activity(&table_plan.table_name, col_name, activity);
+                        }
+                    }
+                }
             }
 
             table_rows.push(row);
@@ -151,9 +370,12 @@ pub fn execute_plan(
                     .iter()
                     .zip(deferred.target_columns.iter())
                 {
-                    if let Some(value) =
-                        fk_pool.pick_reference(&deferred.target_table, tgt_col, &mut rng)
-                    {
+                    if let Some(value) = fk_pool.pick_reference(
+                        &deferred.target_table,
+                        tgt_col,
+                        FanOut::Uniform,
+                        &mut rng,
+                    ) {
                         generated.deferred_updates.push(DeferredUpdate {
                             table_name: deferred.source_table.clone(),
                             row_index: row_idx,
@@ -166,9 +388,25 @@ pub fn execute_plan(
         }
     }
 
+    fill_aggregates(plan, schema, &mut generated);
+
     Ok(generated)
 }
 
+/// Derive the RNG seed for a stable table from the run seed and table name.
+///
+/// Stable tables never touch the shared RNG, so they come out the same
+/// whether they are generated or replayed from the lock file, and the
+/// tables after them are unaffected either way.
+pub fn stable_table_seed(seed: u64, table_name: &str) -> u64 {
+    use sha2::{Digest, Sha256};
+    let mut hasher = Sha256::new();
+    hasher.update(seed.to_le_bytes());
+    hasher.update(table_name.as_bytes());
+    let digest = hasher.finalize();
+    u64::from_le_bytes(digest[..8].try_into().expect("digest is 32 bytes"))
+}
+
 /// Maximum attempts to regenerate an entire row when a composite unique
 /// constraint collision is detected.
 const MAX_ROW_RETRIES: usize = 50;
@@ -178,6 +416,7 @@ const MAX_ROW_RETRIES: usize = 50;
 ///
 /// The outer loop retries the entire row if a composite constraint fires.
 /// Single-column unique constraints are retried at the column level (cheaper).
+#[allow(clippy::too_many_arguments)]
 fn generate_row(
     table_plan: &TableGenerationPlan,
     row_index: usize,
@@ -185,16 +424,29 @@ fn generate_row(
     fk_pool: &ForeignKeyPool,
     unique_tracker: &mut UniqueTracker,
     schema: &DatabaseSchema,
+    solver: &RowSolver,
+    custom: &mut CustomProviders,
+    repeats: &HashMap<&str, (u64, usize)>,
     base_time: chrono::NaiveDateTime,
 ) -> Result<IndexMap<String, Value>> {
+    // Composite keys, and single-column keys the solver may rewrite, are
+    // checked once the row is complete.
     let composite_constraints: Vec<&Vec<String>> = schema
         .tables
         .get(&table_plan.table_name)
         .map(|t| {
+            let solved_pk = t
+                .primary_key
+                .iter()
+                .filter(|pk| pk.columns.len() == 1 && solver.derives(&pk.columns[0]));
             t.unique_constraints
                 .iter()
-                .filter(|uc| uc.columns.len() > 1)
+                .filter(|uc| match uc.columns.as_slice() {
+                    [column] => solver.derives(column),
+                    columns => columns.len() > 1,
+                })
                 .map(|uc| &uc.columns)
+                .chain(solved_pk.map(|pk| &pk.columns))
                 .collect()
         })
         .unwrap_or_default();
@@ -208,6 +460,9 @@ fn generate_row(
             fk_pool,
             unique_tracker,
             schema,
+            solver,
+            custom,
+            repeats,
             base_time,
         )?;
 
@@ -252,6 +507,7 @@ fn generate_row(
 }
 
 /// Generate a single candidate row (columns only, no composite unique check).
+#[allow(clippy::too_many_arguments)]
 fn generate_row_candidate(
     table_plan: &TableGenerationPlan,
     row_index: usize,
@@ -259,6 +515,9 @@ fn generate_row_candidate(
     fk_pool: &ForeignKeyPool,
     unique_tracker: &mut UniqueTracker,
     schema: &DatabaseSchema,
+    solver: &RowSolver,
+    custom: &mut CustomProviders,
+    repeats: &HashMap<&str, (u64, usize)>,
     base_time: chrono::NaiveDateTime,
 ) -> Result<IndexMap<String, Value>> {
     let mut row = IndexMap::new();
@@ -274,7 +533,44 @@ fn generate_row_candidate(
         }
     }
 
+    // Correlated numeric columns take their quantiles from one copula draw
+    let copula_draw: Vec<(&str, f64)> = match table_plan.copula {
+        Some(ref copula) => copula
+            .columns
+            .iter()
+            .map(String::as_str)
+            .zip(copula.draw(rng))
+            .collect(),
+        None => Vec::new(),
+    };
+
+    // Latest creation time among the rows this row references
+    let mut parent_created: Option<chrono::NaiveDateTime> = None;
+
+    // Polymorphic pairs are picked together, like correlated groups
+    for col_plan in &table_plan.column_plans {
+        if let GenerationStrategy::PolymorphicType { id_column, targets } = &col_plan.strategy {
+            if let Some((type_value, id, created_at)) = pick_target(targets, fk_pool, rng) {
+                correlated_values.insert(col_plan.column_name.clone(), type_value);
+                correlated_values.insert(id_column.clone(), id);
+                parent_created = parent_created.max(created_at);
+            }
+        }
+    }
+
+    // One language per row for its free-text columns
+    let language = table_plan.language_mix.as_ref().map(|mix| mix.pick(rng));
+
+    // Templates render once the rest of the row exists
+    let mut templates = Vec::new();
+
     for col_plan in &table_plan.column_plans {
+        // Skipped columns are omitted entirely, not even as NULL, so that
+        // generated columns never receive an explicit value.
+        if matches!(col_plan.strategy, GenerationStrategy::Skip) {
+            continue;
+        }
+
         // Check null probability
         if col_plan.nullable && col_plan.null_probability > 0.0 {
             let roll: f64 = rng.random();
@@ -284,6 +580,18 @@ fn generate_row_candidate(
             }
         }
 
+        // Columns whose sampled values repeat pick one of a fixed number of
+        // slots, each generated from a seed of its own, so rows share values
+        let mut slot_rng;
+        let (rng, row_index) = match repeats.get(col_plan.column_name.as_str()) {
+            Some(&(seed, slots)) => {
+                let slot = rng.random_range(0..slots);
+                slot_rng = StdRng::seed_from_u64(seed.wrapping_add(slot as u64));
+                (&mut slot_rng, slot)
+            }
+            None => (&mut *rng, row_index),
+        };
+
         let value = match &col_plan.strategy {
             GenerationStrategy::AutoIncrement => {
                 // Skip — database handles this
@@ -292,6 +600,17 @@ fn generate_row_candidate(
             GenerationStrategy::Skip => {
                 continue;
             }
+            GenerationStrategy::Template { .. } => {
+                // Hold the column's place in the row
+                row.insert(col_plan.column_name.clone(), Value::Null);
+                templates.push(col_plan);
+                continue;
+            }
+            GenerationStrategy::Expression { .. } | GenerationStrategy::Aggregate { .. } => {
+                // Filled in once the row (or the child rows) exist
+                row.insert(col_plan.column_name.clone(), Value::Null);
+                continue;
+            }
             GenerationStrategy::Deferred => {
                 // Insert NULL for now, will be updated later
                 Value::Null
@@ -299,21 +618,41 @@ fn generate_row_candidate(
             GenerationStrategy::ForeignKeyReference {
                 referenced_table,
                 referenced_column,
-            } => match fk_pool.pick_reference(referenced_table, referenced_column, rng) {
-                Some(v) => v,
-                None => {
-                    if col_plan.nullable {
-                        Value::Null
-                    } else {
-                        return Err(SeedKitError::ForeignKeyResolution {
-                            source_table: table_plan.table_name.clone(),
-                            source_column: col_plan.column_name.clone(),
-                            target_table: referenced_table.clone(),
-                            target_column: referenced_column.clone(),
-                        });
+                fan_out,
+            } => {
+                // Bounded and sampled fan-outs were allocated up front
+                let allocated =
+                    matches!(fan_out, FanOut::Children(..)) || col_plan.sampled_fan_out.is_some();
+                let picked = if allocated {
+                    fk_pool.pick_allocated(
+                        &table_plan.table_name,
+                        &col_plan.column_name,
+                        referenced_table,
+                        referenced_column,
+                        row_index,
+                    )
+                } else {
+                    fk_pool.pick_reference_at(referenced_table, referenced_column, *fan_out, rng)
+                };
+                match picked {
+                    Some((v, created_at)) => {
+                        parent_created = parent_created.max(created_at);
+                        v
+                    }
+                    None => {
+                        if col_plan.nullable {
+                            Value::Null
+                        } else {
+                            return Err(SeedKitError::ForeignKeyResolution {
+                                source_table: table_plan.table_name.clone(),
+                                source_column: col_plan.column_name.clone(),
+                                target_table: referenced_table.clone(),
+                                target_column: referenced_column.clone(),
+                            });
+                        }
                     }
                 }
-            },
+            }
             GenerationStrategy::EnumValue { values } => {
                 if values.is_empty() {
                     Value::Null
@@ -325,6 +664,22 @@ fn generate_row_candidate(
                     Value::String(Cow::Owned(values[idx].clone()))
                 }
             }
+            GenerationStrategy::Lookup { values } => {
+                Value::String(Cow::Owned(values[row_index % values.len()].clone()))
+            }
+            GenerationStrategy::SetValue { values } => {
+                // Labels keep their declared order; the empty set is valid.
+                let picked: Vec<&str> = values
+                    .iter()
+                    .filter(|_| rng.random_bool(0.5))
+                    .map(String::as_str)
+                    .collect();
+                Value::String(Cow::Owned(picked.join(",")))
+            }
+            GenerationStrategy::PolymorphicType { .. }
+            | GenerationStrategy::PolymorphicId { .. } => correlated_values
+                .shift_remove(&col_plan.column_name)
+                .unwrap_or(Value::Null),
             GenerationStrategy::Correlated { .. } => {
                 // Use pre-generated correlated value
                 correlated_values
@@ -339,27 +694,46 @@ fn generate_row_candidate(
                         )
                     })
             }
-            GenerationStrategy::SemanticProvider => generate_value(
-                col_plan.semantic_type,
-                rng,
-                row_index,
-                &col_plan.check_constraints,
-                base_time,
-            ),
+            GenerationStrategy::SemanticProvider => language
+                .and_then(|language| generate_text(col_plan.semantic_type, language, rng))
+                .unwrap_or_else(|| {
+                    generate_value(
+                        col_plan.semantic_type,
+                        rng,
+                        row_index,
+                        &col_plan.check_constraints,
+                        base_time,
+                    )
+                }),
             GenerationStrategy::Custom { ref provider_path } => {
-                return Err(SeedKitError::Config {
-                    message: format!(
-                        "Custom JS/WASM provider '{}' for {}.{} is not yet supported. \
-                         Use [columns.\"{}.{}\"] values = [...] in seedkit.toml instead.",
-                        provider_path,
-                        table_plan.table_name,
-                        col_plan.column_name,
-                        table_plan.table_name,
-                        col_plan.column_name,
-                    ),
-                });
+                if let Some(value) =
+                    custom.generate(&table_plan.table_name, &col_plan.column_name, row_index)
+                {
+                    value?
+                } else {
+                    return Err(SeedKitError::Config {
+                        message: format!(
+                            "Custom JavaScript provider '{}' for {}.{} is not yet supported; \
+                         use a WASM module, or [columns.\"{}.{}\"] values = [...] in seedkit.toml.",
+                            provider_path,
+                            table_plan.table_name,
+                            col_plan.column_name,
+                            table_plan.table_name,
+                            col_plan.column_name,
+                        ),
+                    });
+                }
             }
-            GenerationStrategy::ValueList {
+            // Always loaded by `CustomProviders::load`
+            GenerationStrategy::Process { .. } => custom
+                .generate(&table_plan.table_name, &col_plan.column_name, row_index)
+                .unwrap_or(Ok(Value::Null))?,
+            GenerationStrategy::Lifecycle {
+                states: ref values,
+                ref weights,
+                ..
+            }
+            | GenerationStrategy::ValueList {
                 ref values,
                 ref weights,
             } => {
@@ -372,11 +746,100 @@ fn generate_row_candidate(
                     Value::String(Cow::Owned(values[idx].clone()))
                 }
             }
-            GenerationStrategy::Distribution { ref distribution } => {
-                generate_from_distribution(distribution, rng)
+            GenerationStrategy::Distribution {
+                distribution: ColumnDistribution::Json { ref shape },
+            } => Value::Json(generate_shaped(shape, rng, row_index, base_time)),
+            GenerationStrategy::Distribution { ref distribution } => copula_draw
+                .iter()
+                .find(|(column, _)| *column == col_plan.column_name)
+                .and_then(|&(_, u)| numeric_at(distribution, u))
+                .map(Value::Float)
+                .unwrap_or_else(|| generate_from_distribution(distribution, rng)),
+            GenerationStrategy::LlmContent { ref texts } => {
+                if texts.is_empty() {
+                    Value::Null
+                } else {
+                    Value::String(Cow::Owned(texts[rng.random_range(0..texts.len())].clone()))
+                }
+            }
+            GenerationStrategy::JsonSchema { ref schema } => {
+                Value::Json(generate_document(schema, rng, row_index, base_time))
+            }
+            GenerationStrategy::Pattern { ref pattern } => {
+                Value::String(Cow::Owned(pattern.generate(rng)))
+            }
+            GenerationStrategy::Composite {
+                ref type_name,
+                ref fields,
+            } => generate_composite(type_name, fields, rng, row_index, base_time),
+            GenerationStrategy::Array {
+                ref element,
+                dimensions,
+                min_len,
+                max_len,
+            } => generate_array(
+                element,
+                *dimensions,
+                *min_len,
+                *max_len,
+                rng,
+                row_index,
+                base_time,
+            ),
+            GenerationStrategy::Range {
+                ref subtype,
+                min_span,
+                max_span,
+            } => generate_range(subtype, *min_span, *max_span, rng, base_time),
+            GenerationStrategy::Interval { min_secs, max_secs } => {
+                generate_interval(*min_secs, *max_secs, &col_plan.check_constraints, rng)
+            }
+            GenerationStrategy::Binary { min_len, max_len } => {
+                generate_binary(*min_len, *max_len, rng)
+            }
+            GenerationStrategy::Spatial {
+                ref shape,
+                srid,
+                bbox,
+                ref anchor,
+            } => {
+                // Latitude/longitude come from the correlated first pass or
+                // from columns already generated for this row.
+                let center = anchor.as_ref().and_then(|(lat_col, lng_col)| {
+                    let coord = |col: &String| match row.get(col).or(correlated_values.get(col)) {
+                        Some(Value::Float(v)) => Some(*v),
+                        _ => None,
+                    };
+                    Some((coord(lng_col)?, coord(lat_col)?))
+                });
+                generate_geometry(shape, *srid, *bbox, center, rng)
             }
         };
 
+        // Bring the value inside the column's CHECK constraints. References
+        // must point at real rows, so they are left as picked.
+        let value = match col_plan.strategy {
+            GenerationStrategy::ForeignKeyReference { .. }
+            | GenerationStrategy::PolymorphicType { .. }
+            | GenerationStrategy::PolymorphicId { .. }
+            | GenerationStrategy::Deferred => value,
+            _ => satisfy_checks(value, &col_plan.check_constraints, rng),
+        };
+
+        let value = conform_to_profile(col_plan, value, rng, row_index, 0);
+
+        // Keep identity values inside varchar limits without breaking them
+        let value = match col_plan.max_length {
+            Some(max_len) => fit_identity_to_length(
+                col_plan.semantic_type,
+                value,
+                max_len as usize,
+                row_index,
+                0,
+            ),
+            None => value,
+        };
+
         // Single-column unique constraint check with retry
         if let Some(table) = schema.tables.get(&table_plan.table_name) {
             let needs_unique = table
@@ -387,7 +850,7 @@ fn generate_row_candidate(
                     pk.columns.len() == 1 && pk.columns[0] == col_plan.column_name
                 });
 
-            if needs_unique && !value.is_null() {
+            if needs_unique && !value.is_null() && !solver.derives(&col_plan.column_name) {
                 let mut final_value = value;
                 let mut col_attempts = 0;
                 while !unique_tracker.try_insert_single(
@@ -411,6 +874,33 @@ fn generate_row_candidate(
                         &col_plan.check_constraints,
                         base_time,
                     );
+                    final_value = satisfy_checks(final_value, &col_plan.check_constraints, rng);
+                    final_value =
+                        conform_to_profile(col_plan, final_value, rng, row_index, col_attempts);
+                    // Fresh values rarely escape a short shared prefix (common
+                    // first names under `email(10)`), so lead with the row
+                    // index, fitting the value to the room left after it.
+                    let prefix_indexed = unique_tracker
+                        .prefix_length(&table_plan.table_name, &col_plan.column_name)
+                        .is_some();
+                    let lead_len = if prefix_indexed {
+                        row_index.to_string().len() + 1
+                    } else {
+                        0
+                    };
+                    if let Some(max_len) = col_plan.max_length {
+                        final_value = fit_identity_to_length(
+                            col_plan.semantic_type,
+                            final_value,
+                            (max_len as usize).saturating_sub(lead_len),
+                            row_index,
+                            col_attempts,
+                        );
+                    }
+                    if prefix_indexed {
+                        final_value =
+                            lead_with_row_index(col_plan.semantic_type, final_value, row_index);
+                    }
                 }
                 row.insert(col_plan.column_name.clone(), final_value);
                 continue;
@@ -420,9 +910,274 @@ fn generate_row_candidate(
         row.insert(col_plan.column_name.clone(), value);
     }
 
+    if let Some(parent_created) = parent_created {
+        follow_parent_creation(table_plan, &mut row, parent_created, base_time);
+    }
+
+    for col_plan in &table_plan.column_plans {
+        if let GenerationStrategy::Lifecycle {
+            ref states,
+            ref timestamps,
+            ..
+        } = col_plan.strategy
+        {
+            apply_lifecycle(
+                &col_plan.column_name,
+                states,
+                timestamps,
+                &mut row,
+                |name| {
+                    table_plan
+                        .column_plans
+                        .iter()
+                        .any(|c| c.column_name == name && c.nullable)
+                },
+            );
+        }
+    }
+
+    // Derive dependent columns so comparisons between them hold
+    if !solver.is_empty() {
+        solver.solve(&mut row, rng);
+    }
+
+    for col_plan in templates {
+        let value = render_template_column(
+            table_plan,
+            col_plan,
+            &row,
+            row_index,
+            rng,
+            unique_tracker,
+            schema,
+            base_time,
+        )?;
+        row.insert(col_plan.column_name.clone(), value);
+    }
+
     Ok(row)
 }
 
+/// The row's `created_at`, if its table has one.
+fn row_created_at(
+    table_plan: &TableGenerationPlan,
+    row: &IndexMap<String, Value>,
+) -> Option<chrono::NaiveDateTime> {
+    let col_plan = table_plan
+        .column_plans
+        .iter()
+        .find(|c| c.semantic_type == SemanticType::CreatedAt)?;
+    match row.get(&col_plan.column_name)? {
+        Value::Timestamp(t) => Some(*t),
+        Value::Date(d) => d.and_hms_opt(0, 0, 0),
+        _ => None,
+    }
+}
+
+/// Keep a row from being created before a row it references. When its
+/// `created_at` precedes `parent_created`, its timeline (`created_at`,
+/// `updated_at`, `deleted_at` and lifecycle events) is compressed from
+/// `[created_at, base_time]` into `[parent_created, base_time]`, keeping
+/// the timestamps in order.
+fn follow_parent_creation(
+    table_plan: &TableGenerationPlan,
+    row: &mut IndexMap<String, Value>,
+    parent_created: chrono::NaiveDateTime,
+    base_time: chrono::NaiveDateTime,
+) {
+    let Some(created) = row_created_at(table_plan, row) else {
+        return;
+    };
+    if created >= parent_created {
+        return;
+    }
+
+    let temporal: Vec<&str> = table_plan
+        .correlation_groups
+        .iter()
+        .filter(|g| g.group == CorrelationGroup::Temporal)
+        .flat_map(|g| g.columns.iter().map(|(name, _)| name.as_str()))
+        .collect();
+    let from = (base_time - created).num_seconds().max(1) as f64;
+    let to = (base_time - parent_created).num_seconds().max(0) as f64;
+    for col_plan in &table_plan.column_plans {
+        let on_timeline = col_plan.semantic_type == SemanticType::CreatedAt
+            || temporal.contains(&col_plan.column_name.as_str());
+        if !on_timeline {
+            continue;
+        }
+        if let Some(Value::Timestamp(t)) = row.get_mut(&col_plan.column_name) {
+            let offset = (*t - created).num_seconds() as f64 * to / from;
+            *t = parent_created + chrono::Duration::seconds(offset.round() as i64);
+        }
+    }
+}
+
+/// Render a template column from the finished row. Generators in the
+/// template re-roll while the result collides with a unique constraint.
+#[allow(clippy::too_many_arguments)]
+fn render_template_column(
+    table_plan: &TableGenerationPlan,
+    col_plan: &ColumnGenerationPlan,
+    row: &IndexMap<String, Value>,
+    row_index: usize,
+    rng: &mut StdRng,
+    unique_tracker: &mut UniqueTracker,
+    schema: &DatabaseSchema,
+    base_time: chrono::NaiveDateTime,
+) -> Result<Value> {
+    let GenerationStrategy::Template { ref parts } = col_plan.strategy else {
+        return Ok(Value::Null);
+    };
+    let table = schema.tables.get(&table_plan.table_name);
+    let is_column = |name: &str| table.is_some_and(|t| t.columns.contains_key(name));
+    if let Some(name) = unknown_column(parts, is_column) {
+        return Err(SeedKitError::Config {
+            message: format!(
+                "Template for {}.{} references '{}', which is neither a column of {} \
+                 nor a known generator.",
+                table_plan.table_name, col_plan.column_name, name, table_plan.table_name,
+            ),
+        });
+    }
+
+    let render_attempt = |rng: &mut StdRng, attempt: usize| {
+        let value = render(
+            parts,
+            |name| row.get(name).cloned(),
+            rng,
+            row_index + attempt,
+            base_time,
+        );
+        let value = satisfy_checks(value, &col_plan.check_constraints, rng);
+        match col_plan.max_length {
+            Some(max_len) => fit_identity_to_length(
+                col_plan.semantic_type,
+                value,
+                max_len as usize,
+                row_index,
+                attempt,
+            ),
+            None => value,
+        }
+    };
+
+    let mut value = render_attempt(rng, 0);
+    let needs_unique = table.is_some_and(|t| {
+        t.unique_constraints
+            .iter()
+            .any(|uc| uc.columns.len() == 1 && uc.columns[0] == col_plan.column_name)
+    });
+    if needs_unique && !value.is_null() {
+        let mut attempts = 0;
+        while !unique_tracker.try_insert_single(
+            &table_plan.table_name,
+            &col_plan.column_name,
+            &value,
+        ) {
+            attempts += 1;
+            if attempts >= unique_tracker.max_retries {
+                return Err(SeedKitError::UniqueExhausted {
+                    table: table_plan.table_name.clone(),
+                    column: col_plan.column_name.clone(),
+                    row_index,
+                    max_retries: unique_tracker.max_retries,
+                });
+            }
+            value = render_attempt(rng, attempts);
+        }
+    }
+    Ok(value)
+}
+
+/// Generate a composite (row) value, one field at a time.
+///
+/// Enum fields pick from their labels, nested composites recurse, and all
+/// other fields use their semantic classification with a data-type fallback.
+fn generate_composite(
+    type_name: &str,
+    fields: &[CompositeFieldPlan],
+    rng: &mut StdRng,
+    row_index: usize,
+    base_time: chrono::NaiveDateTime,
+) -> Value {
+    let values = fields
+        .iter()
+        .map(|field| {
+            (
+                field.name.clone(),
+                generate_field(field, rng, row_index, base_time),
+            )
+        })
+        .collect();
+
+    Value::Composite {
+        type_name: type_name.to_string(),
+        fields: values,
+    }
+}
+
+/// Generate a (possibly multi-dimensional) array. Every sub-array at the
+/// same depth has the same length, since PostgreSQL requires rectangular
+/// multi-dimensional arrays.
+fn generate_array(
+    element: &CompositeFieldPlan,
+    dimensions: u32,
+    min_len: usize,
+    max_len: usize,
+    rng: &mut StdRng,
+    row_index: usize,
+    base_time: chrono::NaiveDateTime,
+) -> Value {
+    let lengths: Vec<usize> = (0..dimensions.max(1))
+        .map(|_| rng.random_range(min_len..=max_len.max(min_len)))
+        .collect();
+    build_array_level(element, &lengths, rng, row_index, base_time)
+}
+
+fn build_array_level(
+    element: &CompositeFieldPlan,
+    lengths: &[usize],
+    rng: &mut StdRng,
+    row_index: usize,
+    base_time: chrono::NaiveDateTime,
+) -> Value {
+    let items = (0..lengths[0])
+        .map(|_| {
+            if lengths.len() > 1 {
+                build_array_level(element, &lengths[1..], rng, row_index, base_time)
+            } else {
+                generate_field(element, rng, row_index, base_time)
+            }
+        })
+        .collect();
+    Value::Array(items)
+}
+
+/// Generate a single nested value (composite field or array element).
+fn generate_field(
+    field: &CompositeFieldPlan,
+    rng: &mut StdRng,
+    row_index: usize,
+    base_time: chrono::NaiveDateTime,
+) -> Value {
+    if let DataType::Composite(ref nested) = field.data_type {
+        generate_composite(nested, &field.fields, rng, row_index, base_time)
+    } else if let Some(labels) = field.enum_values.as_ref().filter(|v| !v.is_empty()) {
+        let idx = rng.random_range(0..labels.len());
+        Value::String(Cow::Owned(labels[idx].clone()))
+    } else {
+        generate_value_for_type(
+            field.semantic_type,
+            &field.data_type,
+            rng,
+            row_index,
+            &[],
+            base_time,
+        )
+    }
+}
+
 /// Weighted random selection from a value list.
 ///
 /// Uses cumulative distribution for O(n) selection.
@@ -486,7 +1241,13 @@ fn generate_from_distribution(distribution: &ColumnDistribution, rng: &mut impl
             max,
             mean,
             stddev,
+            quantiles,
         } => {
+            if !quantiles.is_empty() && max > min {
+                // Follow the sampled quantiles so heavy tails survive
+                let knots = numeric_knots(*min, *max, quantiles);
+                return Value::Float(sample_cdf(knots, rng).clamp(*min, *max));
+            }
             if *stddev <= 0.0 || min == max {
                 // No variance — return the mean (clamped)
                 let val = mean.clamp(*min, *max);
@@ -498,56 +1259,223 @@ fn generate_from_distribution(distribution: &ColumnDistribution, rng: &mut impl
             let clamped = raw.clamp(*min, *max);
             Value::Float(clamped)
         }
+        ColumnDistribution::Histogram {
+            min,
+            max,
+            buckets,
+            quantiles,
+        } => {
+            let Some(knots) = histogram_knots(*min, *max, buckets, quantiles) else {
+                return Value::Float(*min);
+            };
+            Value::Float(sample_cdf(knots, rng).clamp(*min, *max))
+        }
         ColumnDistribution::Ratio { .. } => {
             // Ratios affect row counts at plan level, not individual values
             Value::Null
         }
+        ColumnDistribution::Text { model } => Value::String(Cow::Owned(model.generate(rng))),
+        ColumnDistribution::Json { .. } => {
+            // Shaped in generate_row_candidate, which has the row's context
+            Value::Null
+        }
     }
 }
 
-/// Box-Muller transform: generate a standard normal random variable.
-fn box_muller_normal(rng: &mut impl Rng) -> f64 {
-    let u1: f64 = rng.random::<f64>().max(f64::MIN_POSITIVE); // avoid log(0)
-    let u2: f64 = rng.random::<f64>();
-    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+/// The value at quantile `u` of a numeric distribution, for columns drawn
+/// through a copula. `None` for other distributions.
+fn numeric_at(distribution: &ColumnDistribution, u: f64) -> Option<f64> {
+    match distribution {
+        ColumnDistribution::Numeric {
+            min,
+            max,
+            mean,
+            stddev,
+            quantiles,
+        } => {
+            let value = if !quantiles.is_empty() && max > min {
+                inverse_cdf(numeric_knots(*min, *max, quantiles), u)
+            } else if *stddev > 0.0 {
+                mean + stddev * normal_quantile(u)
+            } else {
+                *mean
+            };
+            Some(value.clamp(*min, *max))
+        }
+        ColumnDistribution::Histogram {
+            min,
+            max,
+            buckets,
+            quantiles,
+        } => {
+            let value = histogram_knots(*min, *max, buckets, quantiles)
+                .map_or(*min, |knots| inverse_cdf(knots, u));
+            Some(value.clamp(*min, *max))
+        }
+        _ => None,
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::schema::types::*;
-    use rand::rngs::StdRng;
-    use rand::SeedableRng;
-    use std::borrow::Cow;
+/// Knots of a numeric distribution's sampled quantiles, between its bounds.
+fn numeric_knots(min: f64, max: f64, quantiles: &[(f64, f64)]) -> Vec<(f64, f64)> {
+    std::iter::once((min, 0.0))
+        .chain(quantiles.iter().map(|&(q, value)| (value, q)))
+        .chain(std::iter::once((max, 1.0)))
+        .collect()
+}
 
-    /// Build a minimal plan with a single table + single column using the given strategy.
-    fn single_column_plan(
-        table: &str,
-        column: &str,
-        strategy: GenerationStrategy,
-        row_count: usize,
-    ) -> GenerationPlan {
-        GenerationPlan {
-            table_plans: vec![TableGenerationPlan {
-                table_name: table.to_string(),
-                row_count,
-                column_plans: vec![ColumnGenerationPlan {
-                    column_name: column.to_string(),
-                    semantic_type: crate::classify::semantic::SemanticType::Unknown,
-                    strategy,
-                    nullable: false,
-                    null_probability: 0.0,
-                    check_constraints: Vec::new(),
-                }],
-                correlation_groups: Vec::new(),
-            }],
-            deferred_edges: Vec::new(),
-            seed: 42,
-            default_row_count: row_count,
-            base_time: chrono::Utc::now().naive_utc(),
-            sequence_offset: 0,
-        }
+/// Knots of a histogram: bucket edges with their cumulative shares, refined
+/// by the sampled quantiles. `None` for an empty histogram.
+fn histogram_knots(
+    min: f64,
+    max: f64,
+    buckets: &[f64],
+    quantiles: &[(f64, f64)],
+) -> Option<Vec<(f64, f64)>> {
+    let total: f64 = buckets.iter().map(|share| share.max(0.0)).sum();
+    if total <= 0.0 || max <= min {
+        return None;
     }
+    let width = (max - min) / buckets.len() as f64;
+    let mut cumulative = 0.0;
+    let edges = buckets.iter().enumerate().map(|(i, share)| {
+        cumulative += share.max(0.0) / total;
+        (min + width * (i + 1) as f64, cumulative)
+    });
+    Some(
+        std::iter::once((min, 0.0))
+            .chain(edges)
+            .chain(quantiles.iter().map(|&(q, value)| (value, q)))
+            .collect(),
+    )
+}
+
+/// Fit a value to the column's sampled profiles, if it has them: strings
+/// to its text profile, timestamps and dates to its temporal profile.
+/// Identity values (emails, usernames...) are shortened without breaking
+/// them first, `attempt` as for `fit_identity_to_length`.
+fn conform_to_profile(
+    col_plan: &ColumnGenerationPlan,
+    value: Value,
+    rng: &mut impl Rng,
+    row_index: usize,
+    attempt: usize,
+) -> Value {
+    if let Some(ref profile) = col_plan.temporal_profile {
+        match value {
+            Value::Timestamp(ts) => return Value::Timestamp(shape_timestamp(profile, ts, rng)),
+            Value::Date(date) => return Value::Date(shape_date(profile, date, rng)),
+            _ => {}
+        }
+    }
+    let Some(ref profile) = col_plan.text_profile else {
+        return value;
+    };
+    match fit_identity_to_length(
+        col_plan.semantic_type,
+        value,
+        profile.length.1,
+        row_index,
+        attempt,
+    ) {
+        Value::String(s) => Value::String(Cow::Owned(conform_text(profile, &s, rng))),
+        value => value,
+    }
+}
+
+/// Slots (value seed, slot count) for a table's columns with a sampled
+/// `distinct_ratio`. Rows pick slots uniformly, so there are more slots than
+/// the distinct values wanted: with `s` slots and `n` rows, about
+/// `s * (1 - e^(-n/s))` of them are picked.
+fn repeat_slots<'a>(
+    plan: &GenerationPlan,
+    table_plan: &'a TableGenerationPlan,
+) -> HashMap<&'a str, (u64, usize)> {
+    table_plan
+        .column_plans
+        .iter()
+        .filter(|c| matches!(c.strategy, GenerationStrategy::SemanticProvider))
+        .filter_map(|col_plan| {
+            let rows = table_plan.row_count as f64 * (1.0 - col_plan.null_probability);
+            let slots = slots_for(col_plan.distinct_ratio?, rows)?;
+            let scope = format!("{}.{}", table_plan.table_name, col_plan.column_name);
+            let seed = plan.seed_derivation.derive(plan.seed, &scope);
+            Some((col_plan.column_name.as_str(), (seed, slots)))
+        })
+        .collect()
+}
+
+/// Slots giving about `ratio * rows` distinct values over `rows` uniform
+/// picks. `None` when that many would barely repeat anyway.
+fn slots_for(ratio: f64, rows: f64) -> Option<usize> {
+    let wanted = (ratio * rows).ceil().max(1.0);
+    let picked = |slots: f64| slots * (1.0 - (-rows / slots).exp());
+    let (mut low, mut high) = (wanted, rows * 64.0);
+    if wanted >= rows || picked(high) < wanted {
+        return None;
+    }
+    for _ in 0..64 {
+        let mid = (low + high) / 2.0;
+        if picked(mid) < wanted {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    Some(high.round().max(1.0) as usize)
+}
+
+/// Draw from the piecewise-linear distribution through `knots`, pairs of
+/// (value, share of values at or below it).
+fn sample_cdf(knots: Vec<(f64, f64)>, rng: &mut impl Rng) -> f64 {
+    inverse_cdf(knots, rng.random::<f64>())
+}
+
+/// The value at quantile `u` of the piecewise-linear distribution through
+/// `knots`. Knots are sorted by value and shares made non-decreasing, so
+/// sources that disagree slightly (bucket edges vs quantiles) still give a
+/// valid distribution.
+fn inverse_cdf(mut knots: Vec<(f64, f64)>, u: f64) -> f64 {
+    knots.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let mut highest = 0.0f64;
+    for knot in knots.iter_mut() {
+        highest = highest.max(knot.1);
+        knot.1 = highest;
+    }
+    let roll = u * highest;
+    let Some(i) = knots
+        .iter()
+        .position(|&(_, share)| share >= roll && share > 0.0)
+    else {
+        return knots.last().map_or(0.0, |&(value, _)| value);
+    };
+    if i == 0 {
+        return knots[0].0;
+    }
+    let ((low, low_share), (high, high_share)) = (knots[i - 1], knots[i]);
+    if high_share <= low_share {
+        return high;
+    }
+    low + (high - low) * (roll - low_share) / (high_share - low_share)
+}
+
+/// Box-Muller transform: generate a standard normal random variable.
+pub(crate) fn box_muller_normal(rng: &mut impl Rng) -> f64 {
+    let u1: f64 = rng.random::<f64>().max(f64::MIN_POSITIVE); // avoid log(0)
+    let u2: f64 = rng.random::<f64>();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::plan::single_column_plan;
+    use crate::schema::types::*;
+    use rand::rngs::StdRng;
+
+    use rand::SeedableRng;
+    use std::borrow::Cow;
+    use std::sync::Arc;
 
     fn empty_schema() -> DatabaseSchema {
         let mut schema = DatabaseSchema::new(DatabaseType::PostgreSQL, "test".to_string());
@@ -556,6 +1484,58 @@ mod tests {
         schema
     }
 
+    #[test]
+    fn test_prefix_unique_retries_stay_within_length() {
+        let mut plan =
+            single_column_plan("items", "email", GenerationStrategy::SemanticProvider, 300);
+        let col_plan = &mut plan.table_plans[0].column_plans[0];
+        col_plan.semantic_type = crate::classify::semantic::SemanticType::Email;
+        col_plan.max_length = Some(18);
+
+        let mut schema = empty_schema();
+        let table = schema.tables.get_mut("items").unwrap();
+        table.unique_constraints.push(UniqueConstraint {
+            name: Some("uq_email".to_string()),
+            columns: vec!["email".to_string()],
+        });
+        table.unique_key_specs.push(UniqueKeySpec {
+            columns: vec!["email".to_string()],
+            prefix_lengths: vec![Some(4)],
+            multi_valued: false,
+        });
+
+        let data = execute_plan(&plan, &schema, None).unwrap();
+        let mut prefixes = std::collections::HashSet::new();
+        for row in &data.tables["items"] {
+            let email = row["email"].to_string();
+            assert!(email.len() <= 18, "'{}' exceeds varchar(18)", email);
+            assert!(prefixes.insert(email.chars().take(4).collect::<String>()));
+        }
+    }
+
+    #[test]
+    fn test_pattern_strategy() {
+        let pattern = crate::generate::pattern::Pattern::parse(r"[A-Z]{2}\d{3,4}").unwrap();
+        let plan = single_column_plan(
+            "flights",
+            "number",
+            GenerationStrategy::Pattern { pattern },
+            100,
+        );
+        let mut schema = empty_schema();
+        schema
+            .tables
+            .insert("flights".to_string(), Table::new("flights".to_string()));
+        let data = execute_plan(&plan, &schema, None).unwrap();
+        let check = regex::Regex::new(r"^[A-Z]{2}\d{3,4}$").unwrap();
+        for row in &data.tables["flights"] {
+            match row.get("number") {
+                Some(Value::String(s)) => assert!(check.is_match(s), "{}", s),
+                other => panic!("Expected string, got {:?}", other),
+            }
+        }
+    }
+
     #[test]
     fn test_value_list_uniform_distribution() {
         let plan = single_column_plan(
@@ -750,7 +1730,7 @@ mod tests {
         let err_msg = format!("{}", result.unwrap_err());
         assert!(
             err_msg.contains("not yet supported"),
-            "Error should explain JS/WASM is not supported: {}",
+            "Error should explain JavaScript is not supported: {}",
             err_msg
         );
         assert!(
@@ -820,6 +1800,7 @@ mod tests {
                     max: 100.0,
                     mean: 50.0,
                     stddev: 15.0,
+                    quantiles: Vec::new(),
                 },
             },
             1000,
@@ -860,6 +1841,7 @@ mod tests {
                     max: 20.0,
                     mean: 15.0,
                     stddev: 3.0,
+                    quantiles: Vec::new(),
                 },
                 &mut rng,
             );
@@ -880,12 +1862,58 @@ mod tests {
                 max: 100.0,
                 mean: 42.5,
                 stddev: 0.0,
+                quantiles: Vec::new(),
             },
             &mut rng,
         );
         assert_eq!(val, Value::Float(42.5));
     }
 
+    #[test]
+    fn test_histogram_distribution_keeps_its_shape() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let histogram = ColumnDistribution::Histogram {
+            min: 0.0,
+            max: 100.0,
+            buckets: vec![0.5, 0.0, 0.0, 0.5],
+            quantiles: Vec::new(),
+        };
+        for _ in 0..1000 {
+            match generate_from_distribution(&histogram, &mut rng) {
+                Value::Float(f) => assert!(
+                    (0.0..=25.0).contains(&f) || (75.0..=100.0).contains(&f),
+                    "{} falls in an empty bucket",
+                    f
+                ),
+                other => panic!("Expected Float, got {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_quantiles_keep_heavy_tails() {
+        let mut rng = StdRng::seed_from_u64(42);
+        // Order totals: median 40, but 1% above 2000
+        let totals = ColumnDistribution::Numeric {
+            min: 1.0,
+            max: 10_000.0,
+            mean: 90.0,
+            stddev: 300.0,
+            quantiles: vec![(0.5, 40.0), (0.9, 150.0), (0.99, 2000.0)],
+        };
+        let mut values: Vec<f64> = (0..20_000)
+            .map(|_| match generate_from_distribution(&totals, &mut rng) {
+                Value::Float(f) => f,
+                other => panic!("Expected Float, got {:?}", other),
+            })
+            .collect();
+        values.sort_by(f64::total_cmp);
+        let at = |q: f64| values[(q * values.len() as f64) as usize];
+        assert!((35.0..=45.0).contains(&at(0.5)), "p50 {}", at(0.5));
+        assert!((130.0..=170.0).contains(&at(0.9)), "p90 {}", at(0.9));
+        assert!((1700.0..=2300.0).contains(&at(0.99)), "p99 {}", at(0.99));
+    }
+
     #[test]
     fn test_distribution_empty_categorical_produces_null() {
         let mut rng = StdRng::seed_from_u64(42);
@@ -909,4 +1937,1673 @@ mod tests {
         );
         assert_eq!(val, Value::Null);
     }
+
+    #[test]
+    fn test_composite_column_generates_typed_fields() {
+        use crate::generate::plan::GenerationPlan;
+        use std::collections::BTreeMap;
+
+        let mut schema = DatabaseSchema::new(DatabaseType::PostgreSQL, "test".to_string());
+        schema.enums.insert(
+            "address_kind".to_string(),
+            vec!["home".to_string(), "work".to_string()],
+        );
+        let mut kind = Column::new(
+            "kind".to_string(),
+            DataType::Enum("address_kind".to_string()),
+            "address_kind".to_string(),
+        );
+        kind.enum_values = Some(vec!["home".to_string(), "work".to_string()]);
+        schema.composite_types.insert(
+            "address".to_string(),
+            vec![
+                Column::new("city".to_string(), DataType::Text, "text".to_string()),
+                Column::new(
+                    "floor".to_string(),
+                    DataType::Integer,
+                    "integer".to_string(),
+                ),
+                kind,
+            ],
+        );
+        let mut table = Table::new("customers".to_string());
+        let mut col = Column::new(
+            "home".to_string(),
+            DataType::Composite("address".to_string()),
+            "USER-DEFINED".to_string(),
+        );
+        col.nullable = false;
+        table.columns.insert("home".to_string(), col);
+        schema.tables.insert("customers".to_string(), table);
+
+        let plan = GenerationPlan::build(
+            &schema,
+            &crate::classify::rules::classify_schema(&schema),
+            &["customers".to_string()],
+            Vec::new(),
+            5,
+            &BTreeMap::new(),
+            42,
+            None,
+            &BTreeMap::new(),
+            None,
+        );
+        let data = execute_plan(&plan, &schema, None).unwrap();
+
+        for row in &data.tables["customers"] {
+            match row.get("home").unwrap() {
+                Value::Composite { type_name, fields } => {
+                    assert_eq!(type_name, "address");
+                    assert_eq!(fields[0].0, "city");
+                    assert!(matches!(fields[0].1, Value::String(_)));
+                    assert!(matches!(fields[1].1, Value::Int(_)));
+                    let kind = fields[2].1.as_string().unwrap();
+                    assert!(kind == "home" || kind == "work");
+                }
+                other => panic!("Expected composite, got {:?}", other),
+            }
+        }
+
+        let literal = data.tables["customers"][0]["home"].to_sql_literal(&DatabaseType::PostgreSQL);
+        assert!(literal.starts_with("ROW('"), "got {}", literal);
+        assert!(literal.ends_with(")::\"address\""), "got {}", literal);
+    }
+
+    #[test]
+    fn test_composite_record_text_quotes_fields() {
+        let value = Value::Composite {
+            type_name: "address".to_string(),
+            fields: vec![
+                (
+                    "street".to_string(),
+                    Value::String(Cow::Owned("1 Main St".into())),
+                ),
+                ("unit".to_string(), Value::Null),
+                ("zip".to_string(), Value::String(Cow::Owned("02139".into()))),
+                (
+                    "note".to_string(),
+                    Value::String(Cow::Owned("say \"hi\"".into())),
+                ),
+            ],
+        };
+        assert_eq!(
+            value.to_record_text(),
+            "(\"1 Main St\",,02139,\"say \"\"hi\"\"\")"
+        );
+    }
+
+    #[test]
+    fn test_array_column_respects_length_and_dimensions() {
+        let element = CompositeFieldPlan {
+            name: "scores".to_string(),
+            semantic_type: crate::classify::semantic::SemanticType::Unknown,
+            data_type: DataType::Integer,
+            enum_values: None,
+            fields: Vec::new(),
+        };
+        let plan = single_column_plan(
+            "items",
+            "scores",
+            GenerationStrategy::Array {
+                element,
+                dimensions: 2,
+                min_len: 2,
+                max_len: 3,
+            },
+            20,
+        );
+        let data = execute_plan(&plan, &empty_schema(), None).unwrap();
+
+        for row in &data.tables["items"] {
+            let outer = match row.get("scores").unwrap() {
+                Value::Array(items) => items,
+                other => panic!("Expected array, got {:?}", other),
+            };
+            assert!((2..=3).contains(&outer.len()));
+            let inner_len = match &outer[0] {
+                Value::Array(items) => items.len(),
+                other => panic!("Expected nested array, got {:?}", other),
+            };
+            for inner in outer {
+                match inner {
+                    Value::Array(items) => {
+                        assert_eq!(items.len(), inner_len, "arrays must be rectangular");
+                        assert!(items.iter().all(|v| matches!(v, Value::Int(_))));
+                    }
+                    other => panic!("Expected nested array, got {:?}", other),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_array_text_literal() {
+        let value = Value::Array(vec![
+            Value::String(Cow::Owned("red".into())),
+            Value::String(Cow::Owned("dark blue".into())),
+            Value::Null,
+            Value::String(Cow::Owned("null".into())),
+        ]);
+        assert_eq!(value.to_array_text(), "{red,\"dark blue\",NULL,\"null\"}");
+        assert_eq!(
+            value.to_sql_literal(&DatabaseType::PostgreSQL),
+            "'{red,\"dark blue\",NULL,\"null\"}'"
+        );
+
+        let nested = Value::Array(vec![
+            Value::Array(vec![Value::Int(1), Value::Int(2)]),
+            Value::Array(vec![Value::Int(3), Value::Int(4)]),
+        ]);
+        assert_eq!(nested.to_csv_string(), "{{1,2},{3,4}}");
+        assert_eq!(
+            nested.to_sql_literal(&DatabaseType::PostgreSQL),
+            "ARRAY[ARRAY[1,2],ARRAY[3,4]]"
+        );
+
+        // Without a typed element, ARRAY[...] can't infer the element type
+        let empty = Value::Array(vec![Value::Null, Value::Null]);
+        assert_eq!(
+            empty.to_sql_literal(&DatabaseType::PostgreSQL),
+            "'{NULL,NULL}'"
+        );
+        assert_eq!(
+            Value::Array(Vec::new()).to_sql_literal(&DatabaseType::PostgreSQL),
+            "'{}'"
+        );
+    }
+
+    #[test]
+    fn test_seed_derivation_gives_each_tenant_its_own_data() {
+        #[derive(Debug)]
+        struct TenantSeeds(&'static str);
+
+        impl crate::generate::seed::SeedDerivation for TenantSeeds {
+            fn run_seed(&self, seed: u64) -> u64 {
+                stable_table_seed(seed, self.0)
+            }
+
+            fn derive(&self, seed: u64, scope: &str) -> u64 {
+                stable_table_seed(self.run_seed(seed), scope)
+            }
+        }
+
+        let generate = |tenant: Option<&'static str>| {
+            let values = (0..10).map(|i| i.to_string()).collect();
+            let mut plan = single_column_plan(
+                "items",
+                "bucket",
+                GenerationStrategy::ValueList {
+                    values,
+                    weights: None,
+                },
+                50,
+            );
+            if let Some(tenant) = tenant {
+                plan.set_seed_derivation(Arc::new(TenantSeeds(tenant)));
+            }
+            execute_plan(&plan, &empty_schema(), None).unwrap().tables["items"].clone()
+        };
+
+        assert_eq!(generate(Some("acme")), generate(Some("acme")));
+        assert_ne!(generate(Some("acme")), generate(Some("globex")));
+        assert_ne!(generate(Some("acme")), generate(None));
+    }
+
+    #[test]
+    fn test_stable_table_ignores_other_tables_and_replays_pinned_rows() {
+        let two_tables = |first_rows: usize| {
+            let mut plan =
+                single_column_plan("products", "sku", GenerationStrategy::SemanticProvider, 5);
+            let mut other = plan.table_plans[0].clone();
+            other.table_name = "orders".to_string();
+            other.row_count = first_rows;
+            plan.table_plans.insert(0, other);
+            plan.mark_stable("products");
+            plan
+        };
+
+        let small = execute_plan(&two_tables(3), &empty_schema(), None).unwrap();
+        let large = execute_plan(&two_tables(30), &empty_schema(), None).unwrap();
+        assert_eq!(small.tables["products"], large.tables["products"]);
+
+        let mut replay = two_tables(3);
+        let mut pinned = small.tables["products"].clone();
+        pinned.truncate(2);
+        assert!(replay.pin_rows("products", pinned.clone()));
+        let data = execute_plan(&replay, &empty_schema(), None).unwrap();
+        assert_eq!(data.tables["products"], pinned);
+        // The unstable table is untouched by whether rows were replayed
+        assert_eq!(data.tables["orders"], small.tables["orders"]);
+    }
+
+    #[test]
+    fn test_table_streams_isolate_row_count_changes() {
+        let two_tables = |first_rows: usize, table_streams: bool| {
+            let mut plan =
+                single_column_plan("products", "sku", GenerationStrategy::SemanticProvider, 5);
+            let mut other = plan.table_plans[0].clone();
+            other.table_name = "orders".to_string();
+            other.row_count = first_rows;
+            plan.table_plans.insert(0, other);
+            plan.table_streams = table_streams;
+            plan
+        };
+
+        let small = execute_plan(&two_tables(3, true), &empty_schema(), None).unwrap();
+        let large = execute_plan(&two_tables(30, true), &empty_schema(), None).unwrap();
+        assert_eq!(small.tables["products"], large.tables["products"]);
+        assert_eq!(small.tables["orders"][..], large.tables["orders"][..3]);
+
+        // A shared stream (older lock files) shifts every later table
+        let small = execute_plan(&two_tables(3, false), &empty_schema(), None).unwrap();
+        let large = execute_plan(&two_tables(30, false), &empty_schema(), None).unwrap();
+        assert_ne!(small.tables["products"], large.tables["products"]);
+    }
+
+    #[test]
+    fn test_binary_column_respects_size_and_literals() {
+        let plan = single_column_plan(
+            "attachments",
+            "payload",
+            GenerationStrategy::Binary {
+                min_len: 4,
+                max_len: 6,
+            },
+            20,
+        );
+        let data = execute_plan(&plan, &empty_schema(), None).unwrap();
+        for row in &data.tables["attachments"] {
+            match row.get("payload").unwrap() {
+                Value::Bytes(b) => assert!((4..=6).contains(&b.len())),
+                other => panic!("Expected bytes, got {:?}", other),
+            }
+        }
+
+        let value = Value::Bytes(vec![0xde, 0xad, 0xbe, 0xef]);
+        assert_eq!(
+            value.to_sql_literal(&DatabaseType::PostgreSQL),
+            "'\\xdeadbeef'"
+        );
+        assert_eq!(value.to_sql_literal(&DatabaseType::MySQL), "X'deadbeef'");
+        assert_eq!(value.to_sql_literal(&DatabaseType::SQLite), "X'deadbeef'");
+    }
+
+    #[test]
+    fn test_per_table_sequence_offset_shifts_fk_references() {
+        use std::collections::BTreeMap;
+
+        let mut schema = DatabaseSchema::new(DatabaseType::PostgreSQL, "test".to_string());
+        let mut users = Table::new("users".to_string());
+        let mut id = Column::new("id".to_string(), DataType::Serial, "serial".to_string());
+        id.nullable = false;
+        users.columns.insert("id".to_string(), id);
+        users.primary_key = Some(PrimaryKey {
+            columns: vec!["id".to_string()],
+            name: None,
+        });
+        schema.tables.insert("users".to_string(), users);
+
+        let mut orders = Table::new("orders".to_string());
+        let mut user_id = Column::new("user_id".to_string(), DataType::Integer, "int4".to_string());
+        user_id.nullable = false;
+        orders.columns.insert("user_id".to_string(), user_id);
+        orders.foreign_keys.push(ForeignKey {
+            name: None,
+            source_columns: vec!["user_id".to_string()],
+            referenced_table: "users".to_string(),
+            referenced_columns: vec!["id".to_string()],
+            on_delete: ForeignKeyAction::NoAction,
+            on_update: ForeignKeyAction::NoAction,
+            is_deferrable: false,
+        });
+        schema.tables.insert("orders".to_string(), orders);
+
+        let mut plan = GenerationPlan::build(
+            &schema,
+            &crate::classify::rules::classify_schema(&schema),
+            &["users".to_string(), "orders".to_string()],
+            Vec::new(),
+            10,
+            &BTreeMap::new(),
+            42,
+            None,
+            &BTreeMap::new(),
+            None,
+        );
+        plan.set_sequence_offsets(&BTreeMap::from([("users".to_string(), 500)]));
+        assert_eq!(plan.table_plans[0].sequence_offset, Some(500));
+        assert_eq!(plan.table_plans[1].sequence_offset, None);
+
+        let data = execute_plan(&plan, &schema, None).unwrap();
+        for row in &data.tables["orders"] {
+            match row.get("user_id").unwrap() {
+                Value::Int(id) => assert!((501..=510).contains(id), "user_id {} not shifted", id),
+                other => panic!("Expected int, got {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_append_references_existing_parents() {
+        use std::collections::BTreeMap;
+
+        let mut schema = DatabaseSchema::new(DatabaseType::PostgreSQL, "test".to_string());
+        let mut users = Table::new("users".to_string());
+        let mut id = Column::new("id".to_string(), DataType::Serial, "serial".to_string());
+        id.nullable = false;
+        users.columns.insert("id".to_string(), id);
+        users.primary_key = Some(PrimaryKey {
+            columns: vec!["id".to_string()],
+            name: None,
+        });
+        schema.tables.insert("users".to_string(), users);
+
+        let mut orders = Table::new("orders".to_string());
+        let mut user_id = Column::new("user_id".to_string(), DataType::Integer, "int4".to_string());
+        user_id.nullable = false;
+        orders.columns.insert("user_id".to_string(), user_id);
+        orders.foreign_keys.push(ForeignKey {
+            name: None,
+            source_columns: vec!["user_id".to_string()],
+            referenced_table: "users".to_string(),
+            referenced_columns: vec!["id".to_string()],
+            on_delete: ForeignKeyAction::NoAction,
+            on_update: ForeignKeyAction::NoAction,
+            is_deferrable: false,
+        });
+        schema.tables.insert("orders".to_string(), orders);
+
+        let mut plan = GenerationPlan::build(
+            &schema,
+            &crate::classify::rules::classify_schema(&schema),
+            &["users".to_string(), "orders".to_string()],
+            Vec::new(),
+            5,
+            &BTreeMap::from([("orders".to_string(), 200)]),
+            42,
+            None,
+            &BTreeMap::new(),
+            None,
+        );
+        plan.set_sequence_offsets(&BTreeMap::from([("users".to_string(), 5)]));
+        let mut existing = ExistingKeys::new();
+        existing.insert("users", "id", (1..=5).map(Value::Int).collect());
+
+        let data = execute_plan_with_existing(&plan, &schema, &existing, None).unwrap();
+        let ids: HashSet<i64> = data.tables["orders"]
+            .iter()
+            .map(|row| match row.get("user_id").unwrap() {
+                Value::Int(id) => *id,
+                other => panic!("Expected int, got {:?}", other),
+            })
+            .collect();
+        assert!(ids.iter().all(|id| (1..=10).contains(id)), "{:?}", ids);
+        assert!(ids.iter().any(|id| *id <= 5), "no existing user referenced");
+        assert!(ids.iter().any(|id| *id > 5), "no new user referenced");
+    }
+
+    #[test]
+    fn test_bounded_fan_out_allocates_children_per_parent() {
+        use crate::config::ColumnConfig;
+        use crate::generate::foreign_key::FanOut;
+        use std::collections::BTreeMap;
+
+        let mut schema = DatabaseSchema::new(DatabaseType::PostgreSQL, "test".to_string());
+        let mut users = Table::new("users".to_string());
+        let id = Column::new("id".to_string(), DataType::Serial, "serial".to_string());
+        users.columns.insert("id".to_string(), id);
+        users.primary_key = Some(PrimaryKey {
+            columns: vec!["id".to_string()],
+            name: None,
+        });
+        schema.tables.insert("users".to_string(), users);
+
+        let mut addresses = Table::new("addresses".to_string());
+        // Nullable, but bounded fan-out still gives every row a parent
+        let user_id = Column::new("user_id".to_string(), DataType::Integer, "int4".to_string());
+        addresses.columns.insert("user_id".to_string(), user_id);
+        addresses.foreign_keys.push(ForeignKey {
+            name: None,
+            source_columns: vec!["user_id".to_string()],
+            referenced_table: "users".to_string(),
+            referenced_columns: vec!["id".to_string()],
+            on_delete: ForeignKeyAction::NoAction,
+            on_update: ForeignKeyAction::NoAction,
+            is_deferrable: false,
+        });
+        schema.tables.insert("addresses".to_string(), addresses);
+
+        let overrides = BTreeMap::from([(
+            "addresses.user_id".to_string(),
+            ColumnConfig {
+                fan_out: Some(FanOut::Children(1, 3)),
+                ..Default::default()
+            },
+        )]);
+        let plan = GenerationPlan::build(
+            &schema,
+            &BTreeMap::new(),
+            &["users".to_string(), "addresses".to_string()],
+            Vec::new(),
+            10,
+            // 100 addresses can't fit 1-3 per user; the planner caps it at 30
+            &BTreeMap::from([("addresses".to_string(), 100)]),
+            42,
+            None,
+            &overrides,
+            None,
+        );
+        assert_eq!(plan.table_plans[1].row_count, 30);
+
+        let data = execute_plan(&plan, &schema, None).unwrap();
+        let mut per_user: BTreeMap<i64, usize> = BTreeMap::new();
+        for row in &data.tables["addresses"] {
+            match row.get("user_id").unwrap() {
+                Value::Int(id) => *per_user.entry(*id).or_default() += 1,
+                other => panic!("Expected int, got {:?}", other),
+            }
+        }
+        assert_eq!(per_user.len(), 10, "every user has an address");
+        assert!(per_user.values().all(|&n| n == 3), "{:?}", per_user);
+    }
+
+    #[test]
+    fn test_segments_set_columns_and_fan_out() {
+        use crate::generate::segment::Segment;
+        use std::collections::BTreeMap;
+
+        let mut schema = DatabaseSchema::new(DatabaseType::PostgreSQL, "test".to_string());
+        let mut users = Table::new("users".to_string());
+        for (name, data_type) in [("id", DataType::Serial), ("status", DataType::VarChar)] {
+            users.columns.insert(
+                name.to_string(),
+                Column::new(name.to_string(), data_type, "x".to_string()),
+            );
+        }
+        users.primary_key = Some(PrimaryKey {
+            columns: vec!["id".to_string()],
+            name: None,
+        });
+        schema.tables.insert("users".to_string(), users);
+        let mut orders = Table::new("orders".to_string());
+        let mut user_id = Column::new("user_id".to_string(), DataType::Integer, "int4".to_string());
+        user_id.nullable = false;
+        orders.columns.insert("user_id".to_string(), user_id);
+        orders.foreign_keys.push(ForeignKey {
+            name: None,
+            source_columns: vec!["user_id".to_string()],
+            referenced_table: "users".to_string(),
+            referenced_columns: vec!["id".to_string()],
+            on_delete: ForeignKeyAction::NoAction,
+            on_update: ForeignKeyAction::NoAction,
+            is_deferrable: false,
+        });
+        schema.tables.insert("orders".to_string(), orders);
+
+        let mut plan = GenerationPlan::build(
+            &schema,
+            &BTreeMap::new(),
+            &["users".to_string(), "orders".to_string()],
+            Vec::new(),
+            100,
+            &BTreeMap::from([("orders".to_string(), 2000)]),
+            42,
+            None,
+            &BTreeMap::new(),
+            None,
+        );
+        let segment = |share, activity, status: &str| Segment {
+            share,
+            activity,
+            columns: BTreeMap::from([("status".to_string(), vec![status.to_string()])]),
+        };
+        plan.set_segments(
+            &schema,
+            &BTreeMap::from([(
+                "users".to_string(),
+                BTreeMap::from([
+                    ("power".to_string(), segment(0.1, 20.0, "power")),
+                    ("dormant".to_string(), segment(0.3, 0.0, "dormant")),
+                ]),
+            )]),
+        );
+
+        let data = execute_plan(&plan, &schema, None).unwrap();
+        let status_of: Vec<String> = data.tables["users"]
+            .iter()
+            .map(|row| row["status"].to_string())
+            .collect();
+        assert_eq!(status_of.iter().filter(|s| *s == "power").count(), 10);
+        assert_eq!(status_of.iter().filter(|s| *s == "dormant").count(), 30);
+
+        let mut per_status: BTreeMap<&str, usize> = BTreeMap::new();
+        for row in &data.tables["orders"] {
+            let Value::Int(id) = row["user_id"] else {
+                panic!("Expected int, got {:?}", row["user_id"]);
+            };
+            *per_status.entry(&status_of[id as usize - 1]).or_default() += 1;
+        }
+        assert_eq!(per_status.get("dormant"), None, "{:?}", per_status);
+        // 10 users with 20x activity against 60 regular ones: about 77%
+        assert!(per_status["power"] > 1200, "{:?}", per_status);
+    }
+
+    #[test]
+    fn test_polymorphic_association_pairs_type_and_id() {
+        use crate::generate::polymorphic::PolymorphicAssociation;
+        use std::collections::BTreeMap;
+
+        let mut schema = DatabaseSchema::new(DatabaseType::PostgreSQL, "test".to_string());
+        for name in ["posts", "photos"] {
+            let mut table = Table::new(name.to_string());
+            let id = Column::new("id".to_string(), DataType::Serial, "serial".to_string());
+            table.columns.insert("id".to_string(), id);
+            table.primary_key = Some(PrimaryKey {
+                columns: vec!["id".to_string()],
+                name: None,
+            });
+            schema.tables.insert(name.to_string(), table);
+        }
+        let mut comments = Table::new("comments".to_string());
+        let mut kind = Column::new(
+            "commentable_type".to_string(),
+            DataType::VarChar,
+            "varchar".to_string(),
+        );
+        kind.nullable = true;
+        comments
+            .columns
+            .insert("commentable_type".to_string(), kind);
+        let id = Column::new(
+            "commentable_id".to_string(),
+            DataType::BigInt,
+            "int8".to_string(),
+        );
+        comments.columns.insert("commentable_id".to_string(), id);
+        schema.tables.insert("comments".to_string(), comments);
+
+        let mut plan = GenerationPlan::build(
+            &schema,
+            &crate::classify::rules::classify_schema(&schema),
+            &[
+                "posts".to_string(),
+                "photos".to_string(),
+                "comments".to_string(),
+            ],
+            Vec::new(),
+            10,
+            &BTreeMap::new(),
+            42,
+            None,
+            &BTreeMap::new(),
+            None,
+        );
+        plan.set_sequence_offsets(&BTreeMap::from([("photos".to_string(), 1000)]));
+        plan.set_polymorphic_associations(
+            &schema,
+            &[PolymorphicAssociation {
+                table: "comments".to_string(),
+                name: "commentable".to_string(),
+                targets: BTreeMap::from([
+                    ("Post".to_string(), "posts".to_string()),
+                    ("Photo".to_string(), "photos".to_string()),
+                    ("Video".to_string(), "videos".to_string()),
+                ]),
+            }],
+        );
+
+        let data = execute_plan(&plan, &schema, None).unwrap();
+        for row in &data.tables["comments"] {
+            let Value::Int(id) = row["commentable_id"] else {
+                panic!("Expected an int id, got {:?}", row["commentable_id"]);
+            };
+            match row["commentable_type"].to_string().as_str() {
+                "Post" => assert!((1..=10).contains(&id), "post {}", id),
+                "Photo" => assert!((1001..=1010).contains(&id), "photo {}", id),
+                other => panic!("unexpected type {}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_children_are_created_after_their_parents() {
+        use std::collections::BTreeMap;
+
+        let timestamp = |name: &str| {
+            let mut col = Column::new(
+                name.to_string(),
+                DataType::TimestampTz,
+                "timestamptz".to_string(),
+            );
+            col.nullable = false;
+            col
+        };
+        let mut schema = DatabaseSchema::new(DatabaseType::PostgreSQL, "test".to_string());
+        let mut users = Table::new("users".to_string());
+        let id = Column::new("id".to_string(), DataType::Serial, "serial".to_string());
+        users.columns.insert("id".to_string(), id);
+        users
+            .columns
+            .insert("created_at".to_string(), timestamp("created_at"));
+        users.primary_key = Some(PrimaryKey {
+            columns: vec!["id".to_string()],
+            name: None,
+        });
+        schema.tables.insert("users".to_string(), users);
+
+        let mut orders = Table::new("orders".to_string());
+        let mut user_id = Column::new("user_id".to_string(), DataType::Integer, "int4".to_string());
+        user_id.nullable = false;
+        orders.columns.insert("user_id".to_string(), user_id);
+        for name in ["created_at", "shipped_at", "updated_at"] {
+            orders.columns.insert(name.to_string(), timestamp(name));
+        }
+        orders.foreign_keys.push(ForeignKey {
+            name: None,
+            source_columns: vec!["user_id".to_string()],
+            referenced_table: "users".to_string(),
+            referenced_columns: vec!["id".to_string()],
+            on_delete: ForeignKeyAction::NoAction,
+            on_update: ForeignKeyAction::NoAction,
+            is_deferrable: false,
+        });
+        schema.tables.insert("orders".to_string(), orders);
+
+        let plan = GenerationPlan::build(
+            &schema,
+            &crate::classify::rules::classify_schema(&schema),
+            &["users".to_string(), "orders".to_string()],
+            Vec::new(),
+            50,
+            &BTreeMap::new(),
+            42,
+            None,
+            &BTreeMap::new(),
+            None,
+        );
+        let data = execute_plan(&plan, &schema, None).unwrap();
+
+        let time = |row: &IndexMap<String, Value>, col: &str| match row[col] {
+            Value::Timestamp(t) => t,
+            ref other => panic!("Expected a timestamp in {}, got {:?}", col, other),
+        };
+        for order in &data.tables["orders"] {
+            let Value::Int(user_id) = order["user_id"] else {
+                panic!("Expected an int user_id");
+            };
+            let user = &data.tables["users"][user_id as usize - 1];
+            let created = time(order, "created_at");
+            assert!(
+                created >= time(user, "created_at"),
+                "{:?} before {:?}",
+                order,
+                user
+            );
+            assert!(created <= time(order, "shipped_at"));
+            assert!(time(order, "shipped_at") <= time(order, "updated_at"));
+            assert!(time(order, "updated_at") <= plan.base_time);
+        }
+    }
+
+    #[test]
+    fn test_aggregates_match_child_rows() {
+        use crate::config::ColumnConfig;
+        use std::collections::BTreeMap;
+
+        let mut schema = DatabaseSchema::new(DatabaseType::PostgreSQL, "test".to_string());
+        let mut orders = Table::new("orders".to_string());
+        let id = Column::new("id".to_string(), DataType::Serial, "serial".to_string());
+        orders.columns.insert("id".to_string(), id);
+        let mut total = Column::new(
+            "total".to_string(),
+            DataType::Numeric,
+            "numeric".to_string(),
+        );
+        total.nullable = false;
+        total.numeric_scale = Some(2);
+        orders.columns.insert("total".to_string(), total);
+        let count = Column::new(
+            "item_count".to_string(),
+            DataType::Integer,
+            "int4".to_string(),
+        );
+        orders.columns.insert("item_count".to_string(), count);
+        orders.primary_key = Some(PrimaryKey {
+            columns: vec!["id".to_string()],
+            name: None,
+        });
+        schema.tables.insert("orders".to_string(), orders);
+
+        let mut items = Table::new("order_items".to_string());
+        for (name, data_type) in [
+            ("order_id", DataType::Integer),
+            ("unit_price", DataType::Numeric),
+            ("quantity", DataType::Integer),
+        ] {
+            let mut col = Column::new(name.to_string(), data_type, "numeric".to_string());
+            col.nullable = false;
+            items.columns.insert(name.to_string(), col);
+        }
+        items.foreign_keys.push(ForeignKey {
+            name: None,
+            source_columns: vec!["order_id".to_string()],
+            referenced_table: "orders".to_string(),
+            referenced_columns: vec!["id".to_string()],
+            on_delete: ForeignKeyAction::NoAction,
+            on_update: ForeignKeyAction::NoAction,
+            is_deferrable: false,
+        });
+        schema.tables.insert("order_items".to_string(), items);
+
+        let aggregate = |source: &str| ColumnConfig {
+            aggregate: Some(source.to_string()),
+            ..Default::default()
+        };
+        let overrides = BTreeMap::from([
+            (
+                "orders.total".to_string(),
+                aggregate("sum(order_items.unit_price * quantity)"),
+            ),
+            (
+                "orders.item_count".to_string(),
+                aggregate("count(order_items)"),
+            ),
+        ]);
+        let mut plan = GenerationPlan::build(
+            &schema,
+            &crate::classify::rules::classify_schema(&schema),
+            &["orders".to_string(), "order_items".to_string()],
+            Vec::new(),
+            20,
+            &BTreeMap::from([("order_items".to_string(), 60)]),
+            42,
+            None,
+            &overrides,
+            None,
+        );
+        plan.sequence_offset = 100;
+        let data = execute_plan(&plan, &schema, None).unwrap();
+
+        let as_f64 = |value: &Value| match value {
+            Value::Int(n) => *n as f64,
+            Value::Float(f) => *f,
+            other => panic!("Expected a number, got {:?}", other),
+        };
+        let mut totals = [0.0; 20];
+        let mut counts = [0; 20];
+        for item in &data.tables["order_items"] {
+            let index = as_f64(&item["order_id"]) as usize - 101;
+            totals[index] += as_f64(&item["unit_price"]) * as_f64(&item["quantity"]);
+            counts[index] += 1;
+        }
+        for (index, order) in data.tables["orders"].iter().enumerate() {
+            let total = as_f64(&order["total"]);
+            assert!(
+                (total - totals[index]).abs() < 0.01,
+                "{} vs {}",
+                total,
+                totals[index]
+            );
+            assert_eq!(order["item_count"], Value::Int(counts[index]));
+        }
+    }
+
+    #[test]
+    fn test_time_series_rows_follow_a_cadence() {
+        use crate::generate::time_series::TimeSeries;
+        use crate::schema::check_expr::parse_check_constraint;
+        use std::collections::BTreeMap;
+
+        let mut schema = DatabaseSchema::new(DatabaseType::PostgreSQL, "test".to_string());
+        let mut devices = Table::new("devices".to_string());
+        let id = Column::new("id".to_string(), DataType::Serial, "serial".to_string());
+        devices.columns.insert("id".to_string(), id);
+        devices.primary_key = Some(PrimaryKey {
+            columns: vec!["id".to_string()],
+            name: None,
+        });
+        schema.tables.insert("devices".to_string(), devices);
+
+        let mut metrics = Table::new("metrics".to_string());
+        for (name, data_type, raw) in [
+            ("device_id", DataType::Integer, "int4"),
+            ("recorded_at", DataType::TimestampTz, "timestamptz"),
+            ("cpu_percent", DataType::Double, "float8"),
+        ] {
+            let col = Column::new(name.to_string(), data_type, raw.to_string());
+            metrics.columns.insert(name.to_string(), col);
+        }
+        metrics.foreign_keys.push(ForeignKey {
+            name: None,
+            source_columns: vec!["device_id".to_string()],
+            referenced_table: "devices".to_string(),
+            referenced_columns: vec!["id".to_string()],
+            on_delete: ForeignKeyAction::NoAction,
+            on_update: ForeignKeyAction::NoAction,
+            is_deferrable: false,
+        });
+        let expression = "cpu_percent >= 0 AND cpu_percent <= 100";
+        metrics.check_constraints.push(CheckConstraint {
+            name: None,
+            expression: expression.to_string(),
+            parsed: parse_check_constraint(expression),
+        });
+        schema.tables.insert("metrics".to_string(), metrics);
+
+        let mut classifications = crate::classify::rules::classify_schema(&schema);
+        classifications.insert(
+            ("metrics".to_string(), "cpu_percent".to_string()),
+            SemanticType::Percentage,
+        );
+        let mut plan = GenerationPlan::build(
+            &schema,
+            &classifications,
+            &["devices".to_string(), "metrics".to_string()],
+            Vec::new(),
+            4,
+            &BTreeMap::from([("metrics".to_string(), 40)]),
+            42,
+            None,
+            &BTreeMap::new(),
+            None,
+        );
+        let settings: TimeSeries = toml::from_str(
+            r#"
+interval = "5 minutes"
+jitter = 0.2
+series_by = "device_id"
+value = "cpu_percent"
+trend = 1.0
+noise = 0.5
+"#,
+        )
+        .unwrap();
+        plan.set_time_series(
+            &schema,
+            &BTreeMap::from([("metrics".to_string(), settings)]),
+        );
+        let data = execute_plan(&plan, &schema, None).unwrap();
+
+        let rows = &data.tables["metrics"];
+        for (device, series) in rows.chunks(10).enumerate() {
+            let mut previous: Option<chrono::NaiveDateTime> = None;
+            for row in series {
+                assert_eq!(row["device_id"], Value::Int(device as i64 + 1));
+                let Value::Timestamp(time) = row["recorded_at"] else {
+                    panic!("Expected a timestamp, got {:?}", row["recorded_at"]);
+                };
+                assert!(time <= plan.base_time);
+                if let Some(previous) = previous {
+                    let gap = (time - previous).num_seconds();
+                    assert!((240..=360).contains(&gap), "gap of {}s", gap);
+                }
+                previous = Some(time);
+                let Value::Float(cpu) = row["cpu_percent"] else {
+                    panic!("Expected a float, got {:?}", row["cpu_percent"]);
+                };
+                assert!((0.0..=100.0).contains(&cpu), "{}", cpu);
+            }
+            let last = previous.unwrap();
+            assert!(plan.base_time - last < chrono::Duration::minutes(1));
+        }
+    }
+
+    #[test]
+    fn test_funnel_journeys_are_coherent() {
+        use crate::generate::funnel::Funnel;
+        use std::collections::BTreeMap;
+
+        let mut schema = DatabaseSchema::new(DatabaseType::PostgreSQL, "test".to_string());
+        let mut users = Table::new("users".to_string());
+        let id = Column::new("id".to_string(), DataType::Serial, "serial".to_string());
+        users.columns.insert("id".to_string(), id);
+        users.primary_key = Some(PrimaryKey {
+            columns: vec!["id".to_string()],
+            name: None,
+        });
+        schema.tables.insert("users".to_string(), users);
+
+        let mut events = Table::new("events".to_string());
+        for (name, data_type, raw) in [
+            ("user_id", DataType::Integer, "int4"),
+            ("session_id", DataType::Uuid, "uuid"),
+            ("event_name", DataType::Text, "text"),
+            ("occurred_at", DataType::TimestampTz, "timestamptz"),
+        ] {
+            let col = Column::new(name.to_string(), data_type, raw.to_string());
+            events.columns.insert(name.to_string(), col);
+        }
+        events.foreign_keys.push(ForeignKey {
+            name: None,
+            source_columns: vec!["user_id".to_string()],
+            referenced_table: "users".to_string(),
+            referenced_columns: vec!["id".to_string()],
+            on_delete: ForeignKeyAction::NoAction,
+            on_update: ForeignKeyAction::NoAction,
+            is_deferrable: false,
+        });
+        schema.tables.insert("events".to_string(), events);
+
+        let mut plan = GenerationPlan::build(
+            &schema,
+            &crate::classify::rules::classify_schema(&schema),
+            &["users".to_string(), "events".to_string()],
+            Vec::new(),
+            10,
+            &BTreeMap::from([("events".to_string(), 500)]),
+            42,
+            None,
+            &BTreeMap::new(),
+            None,
+        );
+        let funnel: Funnel = toml::from_str(
+            r#"
+steps = ["signup", "activate", "purchase"]
+drop_off = [0.4, 0.5]
+step_gap = "2 hours"
+"#,
+        )
+        .unwrap();
+        plan.set_funnels(&schema, &BTreeMap::from([("events".to_string(), funnel)]));
+        let data = execute_plan(&plan, &schema, None).unwrap();
+
+        let steps = ["signup", "activate", "purchase"];
+        let mut counts = [0usize; 3];
+        let mut previous: Option<&IndexMap<String, Value>> = None;
+        for row in &data.tables["events"] {
+            let step = steps
+                .iter()
+                .position(|s| row["event_name"] == Value::String((*s).into()))
+                .unwrap();
+            counts[step] += 1;
+            let Value::Timestamp(time) = row["occurred_at"] else {
+                panic!("Expected a timestamp, got {:?}", row["occurred_at"]);
+            };
+            assert!(time <= plan.base_time);
+            if step > 0 {
+                let prev = previous.unwrap();
+                assert_eq!(
+                    prev["event_name"],
+                    Value::String(steps[step - 1].into()),
+                    "steps happen in order"
+                );
+                assert_eq!(prev["user_id"], row["user_id"]);
+                assert_eq!(prev["session_id"], row["session_id"]);
+                let Value::Timestamp(prev_time) = prev["occurred_at"] else {
+                    unreachable!()
+                };
+                assert!(prev_time < time && time - prev_time <= chrono::Duration::hours(2));
+            }
+            previous = Some(row);
+        }
+        let activation = counts[1] as f64 / counts[0] as f64;
+        assert!((0.5..0.7).contains(&activation), "{:?}", counts);
+        assert!(counts[2] < counts[1]);
+    }
+
+    #[test]
+    fn test_lifecycle_timestamps_follow_status() {
+        use crate::config::ColumnConfig;
+        use crate::generate::lifecycle::Lifecycle;
+        use std::collections::BTreeMap;
+
+        let mut schema = DatabaseSchema::new(DatabaseType::PostgreSQL, "test".to_string());
+        let mut orders = Table::new("orders".to_string());
+        let status = Column::new("status".to_string(), DataType::Text, "text".to_string());
+        orders.columns.insert("status".to_string(), status);
+        for name in ["created_at", "shipped_at", "delivered_at"] {
+            let col = Column::new(
+                name.to_string(),
+                DataType::TimestampTz,
+                "timestamptz".to_string(),
+            );
+            orders.columns.insert(name.to_string(), col);
+        }
+        schema.tables.insert("orders".to_string(), orders);
+
+        let lifecycle: Lifecycle =
+            toml::from_str(r#"states = ["pending", "processing", "shipped", "delivered"]"#)
+                .unwrap();
+        let overrides = BTreeMap::from([(
+            "orders.status".to_string(),
+            ColumnConfig {
+                lifecycle: Some(lifecycle),
+                ..Default::default()
+            },
+        )]);
+        let plan = GenerationPlan::build(
+            &schema,
+            &crate::classify::rules::classify_schema(&schema),
+            &["orders".to_string()],
+            Vec::new(),
+            200,
+            &BTreeMap::new(),
+            42,
+            None,
+            &overrides,
+            None,
+        );
+        let data = execute_plan(&plan, &schema, None).unwrap();
+
+        let mut seen = HashSet::new();
+        for row in &data.tables["orders"] {
+            let status = row["status"].to_string();
+            let reached = |state: &str| match status.as_str() {
+                "delivered" => true,
+                "shipped" => state == "shipped",
+                _ => false,
+            };
+            assert_eq!(
+                !row["shipped_at"].is_null(),
+                reached("shipped"),
+                "{:?}",
+                row
+            );
+            assert_eq!(
+                !row["delivered_at"].is_null(),
+                reached("delivered"),
+                "{:?}",
+                row
+            );
+            if let (Value::Timestamp(shipped), Value::Timestamp(delivered)) =
+                (&row["shipped_at"], &row["delivered_at"])
+            {
+                assert!(shipped <= delivered);
+            }
+            seen.insert(status);
+        }
+        assert_eq!(seen.len(), 4);
+    }
+
+    #[test]
+    fn test_lookup_table_rows_and_child_references() {
+        use crate::generate::lookup::LookupDataset;
+        use std::collections::BTreeMap;
+
+        let mut schema = DatabaseSchema::new(DatabaseType::PostgreSQL, "test".to_string());
+        let mut countries = Table::new("countries".to_string());
+        let mut id = Column::new("id".to_string(), DataType::Serial, "serial".to_string());
+        id.nullable = false;
+        countries.columns.insert("id".to_string(), id);
+        let mut code = Column::new("code".to_string(), DataType::Char, "bpchar".to_string());
+        code.nullable = false;
+        code.max_length = Some(2);
+        countries.columns.insert("code".to_string(), code);
+        let mut name = Column::new("name".to_string(), DataType::VarChar, "varchar".to_string());
+        name.nullable = false;
+        countries.columns.insert("name".to_string(), name);
+        countries.primary_key = Some(PrimaryKey {
+            columns: vec!["id".to_string()],
+            name: None,
+        });
+        schema.tables.insert("countries".to_string(), countries);
+
+        let mut addresses = Table::new("addresses".to_string());
+        let mut country_code = Column::new(
+            "country_code".to_string(),
+            DataType::Char,
+            "bpchar".to_string(),
+        );
+        country_code.nullable = false;
+        addresses
+            .columns
+            .insert("country_code".to_string(), country_code);
+        addresses.foreign_keys.push(ForeignKey {
+            name: None,
+            source_columns: vec!["country_code".to_string()],
+            referenced_table: "countries".to_string(),
+            referenced_columns: vec!["code".to_string()],
+            on_delete: ForeignKeyAction::NoAction,
+            on_update: ForeignKeyAction::NoAction,
+            is_deferrable: false,
+        });
+        schema.tables.insert("addresses".to_string(), addresses);
+
+        let mut plan = GenerationPlan::build(
+            &schema,
+            &crate::classify::rules::classify_schema(&schema),
+            &["countries".to_string(), "addresses".to_string()],
+            Vec::new(),
+            20,
+            &BTreeMap::new(),
+            42,
+            None,
+            &BTreeMap::new(),
+            None,
+        );
+        let applied = plan.apply_lookup_tables(&schema, &BTreeMap::new(), &BTreeMap::new());
+        assert_eq!(
+            applied,
+            vec![("countries".to_string(), LookupDataset::Countries)]
+        );
+
+        let data = execute_plan(&plan, &schema, None).unwrap();
+        let rows = &data.tables["countries"];
+        assert_eq!(rows.len(), LookupDataset::Countries.len());
+        let codes: Vec<String> = rows.iter().map(|r| r["code"].to_string()).collect();
+        let jp = codes.iter().position(|c| c == "JP").unwrap();
+        assert_eq!(rows[jp]["name"].to_string(), "Japan");
+
+        for row in &data.tables["addresses"] {
+            assert!(codes.contains(&row["country_code"].to_string()));
+        }
+
+        // Opting out keeps the configured row count
+        let overrides = BTreeMap::from([("countries".to_string(), None)]);
+        let mut fresh = GenerationPlan::build(
+            &schema,
+            &crate::classify::rules::classify_schema(&schema),
+            &["countries".to_string(), "addresses".to_string()],
+            Vec::new(),
+            20,
+            &BTreeMap::new(),
+            42,
+            None,
+            &BTreeMap::new(),
+            None,
+        );
+        assert!(fresh
+            .apply_lookup_tables(&schema, &overrides, &BTreeMap::new())
+            .is_empty());
+        assert_eq!(fresh.table_plans[0].row_count, 20);
+
+        // An explicit row count is kept, up to the dataset's size
+        for (rows, expected) in [(5, 5), (100_000, LookupDataset::Countries.len())] {
+            let row_overrides = BTreeMap::from([("countries".to_string(), rows)]);
+            let mut fresh = GenerationPlan::build(
+                &schema,
+                &crate::classify::rules::classify_schema(&schema),
+                &["countries".to_string(), "addresses".to_string()],
+                Vec::new(),
+                20,
+                &row_overrides,
+                42,
+                None,
+                &BTreeMap::new(),
+                None,
+            );
+            fresh.apply_lookup_tables(&schema, &BTreeMap::new(), &row_overrides);
+            assert_eq!(fresh.table_plans[0].row_count, expected);
+        }
+    }
+
+    #[test]
+    fn test_mysql_set_and_unsigned_columns() {
+        use std::collections::BTreeMap;
+
+        let mut schema = DatabaseSchema::new(DatabaseType::MySQL, "test".to_string());
+        let mut table = Table::new("posts".to_string());
+        let mut flags = Column::new(
+            "flags".to_string(),
+            DataType::Set("flags".to_string()),
+            "set".to_string(),
+        );
+        flags.nullable = false;
+        flags.enum_values = Some(vec![
+            "featured".to_string(),
+            "pinned".to_string(),
+            "locked".to_string(),
+        ]);
+        table.columns.insert("flags".to_string(), flags);
+        let mut level = Column::new(
+            "level".to_string(),
+            DataType::SmallInt,
+            "tinyint".to_string(),
+        );
+        level.nullable = false;
+        level.unsigned = true;
+        table.columns.insert("level".to_string(), level);
+        schema.tables.insert("posts".to_string(), table);
+
+        let plan = GenerationPlan::build(
+            &schema,
+            &crate::classify::rules::classify_schema(&schema),
+            &["posts".to_string()],
+            Vec::new(),
+            100,
+            &BTreeMap::new(),
+            42,
+            None,
+            &BTreeMap::new(),
+            None,
+        );
+        assert!(matches!(
+            plan.table_plans[0].column_plans[0].strategy,
+            GenerationStrategy::SetValue { .. }
+        ));
+        assert!(matches!(
+            plan.table_plans[0].column_plans[1].check_constraints.as_slice(),
+            [
+                ParsedCheck::GreaterThanOrEqual { value: min, .. },
+                ParsedCheck::LessThanOrEqual { value: max, .. },
+            ] if *min == 0.0 && *max == 255.0
+        ));
+
+        let data = execute_plan(&plan, &schema, None).unwrap();
+        let mut saw_multiple = false;
+        for row in &data.tables["posts"] {
+            let Value::String(flags) = row.get("flags").unwrap() else {
+                panic!("Expected string flags");
+            };
+            let picked: Vec<&str> = flags.split(',').filter(|s| !s.is_empty()).collect();
+            assert!(picked
+                .iter()
+                .all(|f| ["featured", "pinned", "locked"].contains(f)));
+            saw_multiple |= picked.len() > 1;
+        }
+        assert!(saw_multiple, "SET columns should produce multi-value rows");
+    }
+
+    #[test]
+    fn test_check_constraints_are_satisfied() {
+        use crate::schema::check_expr::parse_check_constraint;
+        use std::collections::BTreeMap;
+
+        let mut schema = DatabaseSchema::new(DatabaseType::PostgreSQL, "test".to_string());
+        let mut table = Table::new("subscriptions".to_string());
+        for (name, data_type) in [
+            ("status", DataType::VarChar),
+            ("sku", DataType::VarChar),
+            ("starts_on", DataType::Date),
+            ("ends_on", DataType::Date),
+            ("renewed_at", DataType::Timestamp),
+        ] {
+            let mut col = Column::new(name.to_string(), data_type, "".to_string());
+            col.nullable = false;
+            table.columns.insert(name.to_string(), col);
+        }
+        for expression in [
+            "((status)::text = ANY ((ARRAY['trial'::character varying, 'paid'::character varying])::text[]))",
+            "(((sku)::text ~~ 'SKU-%'::text) AND (char_length((sku)::text) = 8))",
+            "(ends_on > starts_on)",
+            "(renewed_at >= '2099-01-01 00:00:00'::timestamp without time zone)",
+        ] {
+            table.check_constraints.push(CheckConstraint {
+                name: None,
+                expression: expression.to_string(),
+                parsed: parse_check_constraint(expression),
+            });
+        }
+        schema.tables.insert("subscriptions".to_string(), table);
+
+        let plan = GenerationPlan::build(
+            &schema,
+            &crate::classify::rules::classify_schema(&schema),
+            &["subscriptions".to_string()],
+            Vec::new(),
+            200,
+            &BTreeMap::new(),
+            42,
+            None,
+            &BTreeMap::new(),
+            None,
+        );
+        let data = execute_plan(&plan, &schema, None).unwrap();
+        let floor = chrono::NaiveDate::from_ymd_opt(2099, 1, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+        for row in &data.tables["subscriptions"] {
+            let status = row["status"].as_string().unwrap();
+            assert!(["trial", "paid"].contains(&status), "status {}", status);
+            let sku = row["sku"].as_string().unwrap();
+            assert!(
+                sku.starts_with("SKU-") && sku.chars().count() == 8,
+                "sku {}",
+                sku
+            );
+            let (Value::Date(starts), Value::Date(ends)) = (&row["starts_on"], &row["ends_on"])
+            else {
+                panic!("Expected dates");
+            };
+            assert!(starts < ends);
+            let Value::Timestamp(renewed) = &row["renewed_at"] else {
+                panic!("Expected a timestamp");
+            };
+            assert!(*renewed >= floor);
+        }
+    }
+
+    #[test]
+    fn test_solved_columns_keep_bounds_and_uniqueness() {
+        use crate::schema::check_expr::parse_check_constraint;
+        use std::collections::{BTreeMap, HashSet};
+
+        let mut schema = DatabaseSchema::new(DatabaseType::PostgreSQL, "test".to_string());
+        let mut table = Table::new("bookings".to_string());
+        for name in ["starts_on", "ends_on"] {
+            let mut col = Column::new(name.to_string(), DataType::Date, "date".to_string());
+            col.nullable = false;
+            table.columns.insert(name.to_string(), col);
+        }
+        for expression in [
+            "((starts_on >= '2024-01-01'::date) AND (starts_on <= '2024-01-05'::date))",
+            "(ends_on < '2024-02-10'::date)",
+            "(ends_on > starts_on)",
+        ] {
+            table.check_constraints.push(CheckConstraint {
+                name: None,
+                expression: expression.to_string(),
+                parsed: parse_check_constraint(expression),
+            });
+        }
+        table.unique_constraints.push(UniqueConstraint {
+            name: None,
+            columns: vec!["ends_on".to_string()],
+        });
+        schema.tables.insert("bookings".to_string(), table);
+
+        let plan = GenerationPlan::build(
+            &schema,
+            &crate::classify::rules::classify_schema(&schema),
+            &["bookings".to_string()],
+            Vec::new(),
+            25,
+            &BTreeMap::new(),
+            42,
+            None,
+            &BTreeMap::new(),
+            None,
+        );
+        let data = execute_plan(&plan, &schema, None).unwrap();
+        let limit = chrono::NaiveDate::from_ymd_opt(2024, 2, 10).unwrap();
+        let mut seen = HashSet::new();
+        for row in &data.tables["bookings"] {
+            let (Value::Date(starts), Value::Date(ends)) = (&row["starts_on"], &row["ends_on"])
+            else {
+                panic!("Expected dates");
+            };
+            assert!(starts < ends && *ends < limit, "{} .. {}", starts, ends);
+            assert!(seen.insert(*ends), "duplicate ends_on {}", ends);
+        }
+    }
+
+    #[test]
+    fn test_spatial_point_follows_lat_lng_columns() {
+        let mut schema = DatabaseSchema::new(DatabaseType::PostgreSQL, "test".to_string());
+        let mut table = Table::new("stores".to_string());
+        for name in ["latitude", "longitude"] {
+            let mut col = Column::new(name.to_string(), DataType::Double, "double".to_string());
+            col.nullable = false;
+            table.columns.insert(name.to_string(), col);
+        }
+        let mut location = Column::new(
+            "location".to_string(),
+            DataType::Spatial {
+                geography: true,
+                shape: "Point".to_string(),
+                srid: 4326,
+            },
+            "USER-DEFINED".to_string(),
+        );
+        location.nullable = false;
+        table.columns.insert("location".to_string(), location);
+        schema.tables.insert("stores".to_string(), table);
+
+        let classifications = crate::classify::rules::classify_schema(&schema);
+        let plan = GenerationPlan::build(
+            &schema,
+            &classifications,
+            &["stores".to_string()],
+            Vec::new(),
+            10,
+            &std::collections::BTreeMap::new(),
+            42,
+            None,
+            &std::collections::BTreeMap::new(),
+            None,
+        );
+        let data = execute_plan(&plan, &schema, None).unwrap();
+
+        for row in &data.tables["stores"] {
+            let lat = row["latitude"].to_string();
+            let lng = row["longitude"].to_string();
+            assert_eq!(
+                row["location"].to_string(),
+                format!("SRID=4326;POINT({} {})", lng, lat)
+            );
+        }
+    }
+
+    #[test]
+    fn test_language_mix_applies_per_row_to_text_columns() {
+        use crate::classify::semantic::SemanticType;
+        use crate::generate::language::LanguageMix;
+
+        let mut plan =
+            single_column_plan("items", "title", GenerationStrategy::SemanticProvider, 200);
+        plan.table_plans[0].column_plans[0].semantic_type = SemanticType::Title;
+        let weights = [("en".to_string(), 0.5), ("ja".to_string(), 0.5)].into();
+        let mix = LanguageMix::from_weights(&weights).unwrap();
+        plan.set_language_mixes(&Default::default(), Some(&mix));
+
+        let titles = |data: &GeneratedData| -> Vec<String> {
+            data.tables["items"]
+                .iter()
+                .map(|r| r["title"].as_string().unwrap().to_string())
+                .collect()
+        };
+        let data = execute_plan(&plan, &empty_schema(), None).unwrap();
+        let japanese = titles(&data).iter().filter(|t| !t.is_ascii()).count();
+        assert!(
+            (60..140).contains(&japanese),
+            "{} Japanese titles",
+            japanese
+        );
+
+        // Deterministic under the seed
+        let again = execute_plan(&plan, &empty_schema(), None).unwrap();
+        assert_eq!(titles(&data), titles(&again));
+    }
+
+    #[test]
+    fn test_template_column_interpolates_row() {
+        use crate::generate::template::{parse_template, resolve_columns};
+
+        let mut plan = single_column_plan(
+            "users",
+            "first_name",
+            GenerationStrategy::ValueList {
+                values: vec!["ada".to_string()],
+                weights: None,
+            },
+            3,
+        );
+        let mut email = plan.table_plans[0].column_plans[0].clone();
+        email.column_name = "email".to_string();
+        email.strategy = GenerationStrategy::Template {
+            parts: resolve_columns(
+                parse_template("{first_name}.{row}@example.com").unwrap(),
+                &["first_name", "email"],
+            ),
+        };
+        // Listed before the column it references
+        plan.table_plans[0].column_plans.insert(0, email);
+
+        let mut schema = DatabaseSchema::new(DatabaseType::PostgreSQL, "test".to_string());
+        let mut table = Table::new("users".to_string());
+        for name in ["email", "first_name"] {
+            table.columns.insert(
+                name.to_string(),
+                Column::new(name.to_string(), DataType::Text, "text".to_string()),
+            );
+        }
+        table.unique_constraints.push(UniqueConstraint {
+            name: None,
+            columns: vec!["email".to_string()],
+        });
+        schema.tables.insert("users".to_string(), table);
+
+        let data = execute_plan(&plan, &schema, None).unwrap();
+        let rows = &data.tables["users"];
+        let first = rows[0].keys().next().map(String::as_str);
+        assert_eq!(first, Some("email"));
+        assert_eq!(rows[0]["email"].as_string(), Some("ada.1@example.com"));
+        assert_eq!(rows[2]["email"].as_string(), Some("ada.3@example.com"));
+
+        // Unknown placeholders are reported
+        plan.table_plans[0].column_plans[0].strategy = GenerationStrategy::Template {
+            parts: parse_template("{nickname}").unwrap(),
+        };
+        let err = execute_plan(&plan, &schema, None).unwrap_err();
+        assert!(err.to_string().contains("'nickname'"), "{}", err);
+    }
+
+    #[test]
+    fn test_sampled_distinct_ratio_repeats_values() {
+        use crate::classify::semantic::SemanticType;
+
+        let mut plan =
+            single_column_plan("users", "city", GenerationStrategy::SemanticProvider, 2000);
+        plan.table_plans[0].column_plans[0].semantic_type = SemanticType::City;
+        plan.table_plans[0].column_plans[0].distinct_ratio = Some(0.05);
+
+        let cities = |data: &GeneratedData| -> Vec<String> {
+            data.tables["users"]
+                .iter()
+                .map(|r| r["city"].to_string())
+                .collect()
+        };
+        let data = execute_plan(&plan, &empty_schema(), None).unwrap();
+        let distinct = cities(&data).into_iter().collect::<HashSet<_>>().len();
+        assert!(
+            (80..=100).contains(&distinct),
+            "{} distinct cities",
+            distinct
+        );
+
+        let again = execute_plan(&plan, &empty_schema(), None).unwrap();
+        assert_eq!(cities(&data), cities(&again));
+    }
+
+    #[test]
+    fn test_slots_for() {
+        let slots = slots_for(0.5, 1000.0).unwrap();
+        let picked = slots as f64 * (1.0 - (-1000.0 / slots as f64).exp());
+        assert!((picked - 500.0).abs() < 1.0, "{} slots", slots);
+        assert_eq!(slots_for(0.001, 1000.0), Some(1));
+        assert_eq!(slots_for(1.0, 1000.0), None);
+        assert_eq!(slots_for(0.999, 1000.0), None);
+    }
+
+    #[test]
+    fn test_copula_correlates_sampled_columns() {
+        use crate::generate::copula::CopulaPlan;
+
+        let numeric =
+            |min: f64, max: f64, mean: f64, stddev: f64| GenerationStrategy::Distribution {
+                distribution: ColumnDistribution::Numeric {
+                    min,
+                    max,
+                    mean,
+                    stddev,
+                    quantiles: Vec::new(),
+                },
+            };
+        let mut plan = single_column_plan("orders", "quantity", numeric(1.0, 20.0, 4.0, 3.0), 2000);
+        let mut total = plan.table_plans[0].column_plans[0].clone();
+        total.column_name = "total".to_string();
+        total.strategy = GenerationStrategy::Distribution {
+            distribution: ColumnDistribution::Histogram {
+                min: 0.0,
+                max: 500.0,
+                buckets: vec![0.4, 0.3, 0.2, 0.1],
+                quantiles: Vec::new(),
+            },
+        };
+        plan.table_plans[0].column_plans.push(total);
+        plan.table_plans[0].copula = CopulaPlan::new(
+            &["quantity", "total"],
+            &[("quantity".to_string(), "total".to_string(), 0.9)],
+        );
+
+        let data = execute_plan(&plan, &empty_schema(), None).unwrap();
+        let column = |name: &str| -> Vec<f64> {
+            data.tables["orders"]
+                .iter()
+                .map(|r| r[name].to_string().parse().unwrap())
+                .collect()
+        };
+        let (quantity, total) = (column("quantity"), column("total"));
+        assert!(quantity.iter().all(|q| (1.0..=20.0).contains(q)));
+        assert!(total.iter().all(|t| (0.0..=500.0).contains(t)));
+
+        let n = quantity.len() as f64;
+        let mean = |v: &[f64]| v.iter().sum::<f64>() / n;
+        let (mq, mt) = (mean(&quantity), mean(&total));
+        let cov: f64 = quantity
+            .iter()
+            .zip(&total)
+            .map(|(q, t)| (q - mq) * (t - mt))
+            .sum();
+        let sq = quantity
+            .iter()
+            .map(|q| (q - mq).powi(2))
+            .sum::<f64>()
+            .sqrt();
+        let st = total.iter().map(|t| (t - mt).powi(2)).sum::<f64>().sqrt();
+        let r = cov / (sq * st);
+        assert!(r > 0.75, "correlation {}", r);
+    }
+
+    #[test]
+    fn test_numeric_at_follows_quantiles() {
+        let histogram = ColumnDistribution::Histogram {
+            min: 0.0,
+            max: 100.0,
+            buckets: vec![0.5, 0.5],
+            quantiles: Vec::new(),
+        };
+        assert_eq!(numeric_at(&histogram, 0.25), Some(25.0));
+        assert_eq!(numeric_at(&histogram, 1.0), Some(100.0));
+
+        let normal = ColumnDistribution::Numeric {
+            min: 0.0,
+            max: 100.0,
+            mean: 50.0,
+            stddev: 10.0,
+            quantiles: Vec::new(),
+        };
+        assert!((numeric_at(&normal, 0.5).unwrap() - 50.0).abs() < 1e-6);
+        assert!((numeric_at(&normal, 0.975).unwrap() - 69.6).abs() < 0.1);
+        assert_eq!(
+            numeric_at(&ColumnDistribution::Categorical { values: Vec::new() }, 0.5),
+            None
+        );
+    }
+
+    #[test]
+    fn test_temporal_profile_shapes_timestamps() {
+        use crate::classify::semantic::SemanticType;
+        use crate::sample::stats::temporal_from_counts;
+        use chrono::{Datelike, Timelike};
+
+        // Weekday office hours
+        let counts: Vec<(i64, i64, i64, i64)> = (1..=5)
+            .flat_map(|dow| (9..17).map(move |hour| (hour, dow, 6, 10)))
+            .collect();
+        let mut plan = single_column_plan(
+            "orders",
+            "created_at",
+            GenerationStrategy::SemanticProvider,
+            300,
+        );
+        plan.table_plans[0].column_plans[0].semantic_type = SemanticType::CreatedAt;
+        plan.table_plans[0].column_plans[0].temporal_profile = temporal_from_counts(&counts);
+
+        let data = execute_plan(&plan, &empty_schema(), None).unwrap();
+        for row in &data.tables["orders"] {
+            let Value::Timestamp(ts) = row["created_at"] else {
+                panic!("unexpected {:?}", row["created_at"]);
+            };
+            assert!((9..17).contains(&ts.hour()), "{}", ts);
+            assert!(
+                (1..=5).contains(&ts.weekday().num_days_from_sunday()),
+                "{}",
+                ts
+            );
+            assert_eq!(ts.month(), 6, "{}", ts);
+            assert!(ts <= plan.base_time, "{}", ts);
+        }
+    }
 }
@@ -0,0 +1,258 @@
+//! # Derived Columns
+//!
+//! Denormalized columns can be computed from the rest of their row without
+//! a custom provider:
+//!
+//! ```toml
+//! [columns."order_items.line_total"]
+//! expr = "quantity * unit_price"
+//!
+//! [columns."users.email_normalized"]
+//! expr = "lower(trim(email))"
+//! ```
+//!
+//! Expressions are [Rhai](https://rhai.rs) expressions over the row's
+//! columns, which appear as variables (NULL is `()`). Besides Rhai's own
+//! operators and functions there are `lower`, `upper`, `trim`,
+//! `round(x, digits)` and `coalesce(a, b)`; `lower`, `upper` and `trim`
+//! pass NULL through.
+//!
+//! Expressions evaluate once the rest of the row is generated, in column
+//! order, so one may use a derived column declared before it. Row scripts
+//! run afterwards and see the derived values.
+//!
+//! Like row scripts, expressions need the `rhai` cargo feature.
+
+#[cfg(feature = "rhai")]
+use std::collections::HashMap;
+
+use indexmap::IndexMap;
+#[cfg(feature = "rhai")]
+use rhai::{Dynamic, Engine, Scope, AST};
+
+use crate::error::{Result, SeedKitError};
+use crate::generate::plan::{GenerationPlan, GenerationStrategy};
+#[cfg(feature = "rhai")]
+use crate::generate::script::{from_dynamic, to_dynamic};
+use crate::generate::value::Value;
+
+/// Operations an expression may run per row before it is stopped.
+#[cfg(feature = "rhai")]
+const MAX_OPERATIONS_PER_ROW: u64 = 100_000;
+
+/// The compiled derived columns of a plan, by table.
+#[cfg(feature = "rhai")]
+pub struct RowExpressions {
+    engine: Engine,
+    /// Table → (column, source, compiled expression), in column order.
+    columns: HashMap<String, Vec<(String, String, AST)>>,
+}
+
+#[cfg(feature = "rhai")]
+impl RowExpressions {
+    /// Compile the expression of every derived column in the plan.
+    pub fn load(plan: &GenerationPlan) -> Result<Self> {
+        let engine = expression_engine();
+        let mut columns: HashMap<String, Vec<_>> = HashMap::new();
+        for table_plan in &plan.table_plans {
+            for col_plan in &table_plan.column_plans {
+                let GenerationStrategy::Expression { ref source } = col_plan.strategy else {
+                    continue;
+                };
+                let ast = engine.compile_expression(source).map_err(|e| {
+                    expression_error(&table_plan.table_name, &col_plan.column_name, e)
+                })?;
+                columns
+                    .entry(table_plan.table_name.clone())
+                    .or_default()
+                    .push((col_plan.column_name.clone(), source.clone(), ast));
+            }
+        }
+        Ok(Self { engine, columns })
+    }
+
+    /// Evaluate `table`'s derived columns, if any, into `row`.
+    pub fn apply(
+        &self,
+        table: &str,
+        row_index: usize,
+        row: &mut IndexMap<String, Value>,
+    ) -> Result<()> {
+        let Some(columns) = self.columns.get(table) else {
+            return Ok(());
+        };
+
+        let mut scope = Scope::new();
+        for (name, value) in row.iter() {
+            scope.push(name.as_str(), to_dynamic(value));
+        }
+        for (column, source, ast) in columns {
+            let result = self
+                .engine
+                .eval_ast_with_scope::<Dynamic>(&mut scope, ast)
+                .map_err(|e| {
+                    expression_error(
+                        table,
+                        column,
+                        format!("row {}: `{}`: {}", row_index, source, e),
+                    )
+                })?;
+            scope.set_value(column.as_str(), result.clone());
+            row.insert(column.clone(), from_dynamic(result));
+        }
+        Ok(())
+    }
+}
+
+/// Check that `source` parses as an expression.
+#[cfg(feature = "rhai")]
+pub fn check_expression(source: &str) -> std::result::Result<(), String> {
+    expression_engine()
+        .compile_expression(source)
+        .map(|_| ())
+        .map_err(|e| format!("invalid expr '{}': {}", source, e))
+}
+
+/// Without Rhai no column may be derived.
+#[cfg(not(feature = "rhai"))]
+pub struct RowExpressions;
+
+#[cfg(not(feature = "rhai"))]
+impl RowExpressions {
+    pub fn load(plan: &GenerationPlan) -> Result<Self> {
+        for table_plan in &plan.table_plans {
+            for col_plan in &table_plan.column_plans {
+                if let GenerationStrategy::Expression { .. } = col_plan.strategy {
+                    return Err(expression_error(
+                        &table_plan.table_name,
+                        &col_plan.column_name,
+                        "SeedKit was built without the `rhai` feature",
+                    ));
+                }
+            }
+        }
+        Ok(Self)
+    }
+
+    pub fn apply(
+        &self,
+        _table: &str,
+        _row_index: usize,
+        _row: &mut IndexMap<String, Value>,
+    ) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Without Rhai no expression can be checked, or run.
+#[cfg(not(feature = "rhai"))]
+pub fn check_expression(source: &str) -> std::result::Result<(), String> {
+    Err(format!(
+        "expr '{}' needs SeedKit built with the `rhai` feature",
+        source
+    ))
+}
+
+#[cfg(feature = "rhai")]
+fn expression_engine() -> Engine {
+    let mut engine = Engine::new();
+    engine.set_max_operations(MAX_OPERATIONS_PER_ROW);
+    engine
+        .register_fn("lower", |s: &str| s.to_lowercase())
+        .register_fn("lower", |_: ()| ())
+        .register_fn("upper", |s: &str| s.to_uppercase())
+        .register_fn("upper", |_: ()| ())
+        .register_fn("trim", |s: &str| s.trim().to_string())
+        .register_fn("trim", |_: ()| ())
+        .register_fn("round", |x: f64, digits: i64| {
+            let factor = 10f64.powi(digits as i32);
+            (x * factor).round() / factor
+        })
+        .register_fn("round", |x: i64, _: i64| x)
+        .register_fn(
+            "coalesce",
+            |a: Dynamic, b: Dynamic| {
+                if a.is_unit() {
+                    b
+                } else {
+                    a
+                }
+            },
+        );
+    engine
+}
+
+fn expression_error(table: &str, column: &str, message: impl std::fmt::Display) -> SeedKitError {
+    SeedKitError::Expression {
+        column: format!("{}.{}", table, column),
+        message: message.to_string(),
+    }
+}
+
+#[cfg(all(test, feature = "rhai"))]
+mod tests {
+    use super::*;
+    use crate::generate::plan::{column_plan, single_column_plan};
+
+    fn load(exprs: &[(&str, &str)]) -> RowExpressions {
+        let mut plan = single_column_plan("order_items", "id", GenerationStrategy::Skip, 1);
+        plan.table_plans[0].column_plans = exprs
+            .iter()
+            .map(|(column, source)| {
+                let mut col_plan = column_plan(
+                    column,
+                    GenerationStrategy::Expression {
+                        source: source.to_string(),
+                    },
+                );
+                col_plan.nullable = true;
+                col_plan
+            })
+            .collect();
+        RowExpressions::load(&plan).unwrap()
+    }
+
+    fn item() -> IndexMap<String, Value> {
+        IndexMap::from([
+            ("quantity".to_string(), Value::Int(3)),
+            ("unit_price".to_string(), Value::Float(2.5)),
+            ("sku".to_string(), Value::String("  AB-12 ".into())),
+            ("note".to_string(), Value::Null),
+        ])
+    }
+
+    #[test]
+    fn test_expressions_derive_from_the_row() {
+        let exprs = load(&[
+            ("line_total", "quantity * unit_price"),
+            ("doubled", "round(line_total * 2.0, 1)"),
+            ("sku_key", "lower(trim(sku))"),
+            ("note_upper", "upper(note)"),
+            ("label", "coalesce(note, \"none\")"),
+        ]);
+        let mut row = item();
+        exprs.apply("order_items", 0, &mut row).unwrap();
+
+        assert_eq!(row["line_total"], Value::Float(7.5));
+        assert_eq!(row["doubled"], Value::Float(15.0));
+        assert_eq!(row["sku_key"], Value::String("ab-12".into()));
+        assert_eq!(row["note_upper"], Value::Null);
+        assert_eq!(row["label"], Value::String("none".into()));
+
+        let mut other = item();
+        exprs.apply("orders", 0, &mut other).unwrap();
+        assert_eq!(other, item());
+    }
+
+    #[test]
+    fn test_expression_errors_name_the_column() {
+        let exprs = load(&[("line_total", "quantity * discount")]);
+        let err = exprs.apply("order_items", 4, &mut item()).unwrap_err();
+        let msg = err.to_string();
+        assert!(msg.contains("order_items.line_total"), "{}", msg);
+        assert!(msg.contains("row 4"), "{}", msg);
+
+        assert!(check_expression("quantity *").is_err());
+        assert!(check_expression("lower(email)").is_ok());
+    }
+}
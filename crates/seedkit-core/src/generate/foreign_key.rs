@@ -4,49 +4,374 @@
 //! references. As parent tables are generated first (topological order),
 //! their PK values are recorded here. Child table columns with
 //! `GenerationStrategy::ForeignKeyReference` then sample from the pool.
+//!
+//! Sampling is uniform unless the column has a skewed [`FanOut`], in which
+//! case a few parents get most of the children, as in real workloads:
+//!
+//! ```toml
+//! [columns."orders.user_id"]
+//! fan_out = { zipf = 1.2 }
+//! ```
+//!
+//! or has children-per-parent bounds, in which case child rows are
+//! allocated to parents up front instead of picked independently:
+//!
+//! ```toml
+//! [columns."order_items.order_id"]
+//! fan_out = { children = [1, 5] }
+//! ```
+//!
+//! Without either, a column whose children-per-parent counts were sampled
+//! (see `DistributionProfile::fan_outs`) has its rows allocated to match, so
+//! the handful of accounts with thousands of orders show up in seeded data
+//! too.
+//!
+//! Parents in population segments (see `generate::segment`) are picked in
+//! proportion to their segment's activity, on top of the fan-out.
 
+use chrono::NaiveDateTime;
+use rand::seq::SliceRandom;
 use rand::Rng;
+use serde::Deserialize;
 use std::collections::HashMap;
 
 use crate::generate::value::Value;
 
+/// Most parents drawn for one pick weighed by activity before the last
+/// draw is taken as is.
+const MAX_ACTIVITY_DRAWS: usize = 1000;
+
+/// How child rows spread over the parent rows they reference.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FanOut {
+    /// Every parent is equally likely.
+    #[default]
+    Uniform,
+    /// The `k`-th parent recorded is picked with probability proportional
+    /// to `1 / k^s`. With `s = 1.2` and 1,000 parents, the first 1% get
+    /// about half of the children; larger exponents skew harder.
+    Zipf(f64),
+    /// Every parent gets between `min` and `max` children. The planner fits
+    /// the child table's row count to the bounds.
+    Children(usize, usize),
+}
+
+impl FanOut {
+    /// Check the Zipf exponent is usable.
+    pub fn validate(&self) -> Result<(), String> {
+        match *self {
+            FanOut::Zipf(s) if !s.is_finite() || s <= 0.0 => Err(format!(
+                "zipf exponent must be a positive number, got {}.",
+                s
+            )),
+            FanOut::Children(min, max) if min > max || max == 0 => Err(format!(
+                "children [{}, {}] must have a minimum no greater than a maximum of at least 1.",
+                min, max
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Index of the parent to pick from a pool of `len > 0`. Draws once
+    /// from `rng`.
+    fn index(&self, len: usize, rng: &mut impl Rng) -> usize {
+        match *self {
+            // Bounded fan-out is allocated up front; stray picks are uniform
+            FanOut::Uniform | FanOut::Children(..) => rng.random_range(0..len),
+            FanOut::Zipf(s) => {
+                // Invert the CDF of the continuous power law on [1, len + 1)
+                // and round down: O(1) per pick, no per-pool tables
+                let u: f64 = rng.random();
+                let end = len as f64 + 1.0;
+                let x = if (s - 1.0).abs() < 1e-9 {
+                    end.powf(u)
+                } else {
+                    let a = 1.0 - s;
+                    ((end.powf(a) - 1.0) * u + 1.0).powf(1.0 / a)
+                };
+                (x as usize).saturating_sub(1).min(len - 1)
+            }
+        }
+    }
+}
+
+/// Share of parents with each number of children, from sampled
+/// `(children, parents)` counts and, when known, the parent table's row
+/// count, whose remaining rows have no children. `None` when no parent has
+/// a child.
+pub fn fan_out_shares(counts: &[(u64, u64)], parent_rows: Option<u64>) -> Option<Vec<(u64, f64)>> {
+    let with_children: u64 = counts.iter().map(|(_, parents)| parents).sum();
+    if with_children == 0 {
+        return None;
+    }
+    let childless = parent_rows.map_or(0, |rows| rows.saturating_sub(with_children));
+    let total = (with_children + childless) as f64;
+    let mut shares: Vec<(u64, f64)> = Vec::with_capacity(counts.len() + 1);
+    if childless > 0 {
+        shares.push((0, childless as f64 / total));
+    }
+    shares.extend(
+        counts
+            .iter()
+            .filter(|(children, parents)| *children > 0 && *parents > 0)
+            .map(|&(children, parents)| (children, parents as f64 / total)),
+    );
+    Some(shares)
+}
+
 /// Manages pools of generated primary key values for FK references.
 pub struct ForeignKeyPool {
     /// Map from (table_name, column_name) to list of generated values.
     pools: HashMap<(String, String), Vec<Value>>,
+    /// Creation time of the row behind each pool value, when it has one.
+    created_at: HashMap<(String, String), Vec<Option<NaiveDateTime>>>,
+    /// Map from child (table_name, column_name) to the pool index of each
+    /// child row's parent, for columns with bounded fan-out.
+    allocations: HashMap<(String, String), Vec<usize>>,
+    /// Activity of each pool value of segmented tables (1 when unset), with
+    /// an upper bound of them.
+    activity: HashMap<(String, String), (Vec<f64>, f64)>,
 }
 
 impl ForeignKeyPool {
     pub fn new() -> Self {
         Self {
             pools: HashMap::new(),
+            created_at: HashMap::new(),
+            allocations: HashMap::new(),
+            activity: HashMap::new(),
         }
     }
 
     /// Record a generated value for a column (typically a PK column).
     pub fn record_value(&mut self, table_name: &str, column_name: &str, value: Value) {
-        self.pools
-            .entry((table_name.to_string(), column_name.to_string()))
+        self.record_value_at(table_name, column_name, value, None);
+    }
+
+    /// Record a generated value along with its row's `created_at`, so
+    /// children can be created after it.
+    pub fn record_value_at(
+        &mut self,
+        table_name: &str,
+        column_name: &str,
+        value: Value,
+        created_at: Option<NaiveDateTime>,
+    ) {
+        let key = (table_name.to_string(), column_name.to_string());
+        self.created_at
+            .entry(key.clone())
             .or_default()
-            .push(value);
+            .push(created_at);
+        self.pools.entry(key).or_default().push(value);
     }
 
-    /// Pick a random value from the pool for a FK reference.
+    /// Set the activity of the value last recorded for a column: how many
+    /// references it gets relative to a value without one.
+    pub fn set_last_activity(&mut self, table_name: &str, column_name: &str, activity: f64) {
+        let key = (table_name.to_string(), column_name.to_string());
+        let len = self.pools.get(&key).map_or(0, Vec::len);
+        if len == 0 {
+            return;
+        }
+        let (weights, max) = self.activity.entry(key).or_insert((Vec::new(), 1.0));
+        weights.resize(len, 1.0);
+        weights[len - 1] = activity;
+        *max = max.max(activity);
+    }
+
+    fn activity_of(&self, key: &(String, String), index: usize) -> f64 {
+        self.activity
+            .get(key)
+            .and_then(|(weights, _)| weights.get(index).copied())
+            .unwrap_or(1.0)
+    }
+
+    /// Pick a random value from the pool for a FK reference, spread over
+    /// the parents according to `fan_out`.
     pub fn pick_reference(
         &self,
         table_name: &str,
         column_name: &str,
+        fan_out: FanOut,
         rng: &mut impl Rng,
     ) -> Option<Value> {
-        self.pools
-            .get(&(table_name.to_string(), column_name.to_string()))
-            .and_then(|pool| {
-                if pool.is_empty() {
-                    None
-                } else {
-                    Some(pool[rng.random_range(0..pool.len())].clone())
+        self.pick_reference_at(table_name, column_name, fan_out, rng)
+            .map(|(value, _)| value)
+    }
+
+    /// Like [`pick_reference`](Self::pick_reference), also returning the
+    /// picked row's `created_at`.
+    pub fn pick_reference_at(
+        &self,
+        table_name: &str,
+        column_name: &str,
+        fan_out: FanOut,
+        rng: &mut impl Rng,
+    ) -> Option<(Value, Option<NaiveDateTime>)> {
+        let key = (table_name.to_string(), column_name.to_string());
+        let pool = self.pools.get(&key).filter(|pool| !pool.is_empty())?;
+        let mut index = fan_out.index(pool.len(), rng);
+        if let Some(&(ref weights, max)) = self.activity.get(&key) {
+            // Redraw until a parent is accepted with probability
+            // activity / max, which keeps the fan-out's skew
+            for _ in 0..MAX_ACTIVITY_DRAWS {
+                let activity = weights.get(index).copied().unwrap_or(1.0);
+                if rng.random::<f64>() * max < activity {
+                    break;
                 }
+                index = fan_out.index(pool.len(), rng);
+            }
+        }
+        Some((pool[index].clone(), self.created_at_of(&key, index)))
+    }
+
+    fn created_at_of(&self, key: &(String, String), index: usize) -> Option<NaiveDateTime> {
+        self.created_at.get(key)?.get(index).copied().flatten()
+    }
+
+    /// Assign each of the `row_count` rows of `child_table` a parent from
+    /// the `parent_table.parent_column` pool, giving every parent between
+    /// `min` and `max` children. Children of a parent are adjacent, like
+    /// rows inserted together.
+    ///
+    /// When `row_count` doesn't fit the bounds (the planner normally
+    /// prevents this), parents get `min` children until rows run out, and
+    /// rows beyond `max` per parent are assigned uniformly.
+    #[allow(clippy::too_many_arguments)]
+    pub fn allocate_children(
+        &mut self,
+        child_table: &str,
+        child_column: &str,
+        parent_table: &str,
+        parent_column: &str,
+        (min, max): (usize, usize),
+        row_count: usize,
+        rng: &mut impl Rng,
+    ) {
+        let parents = self.pool_size(parent_table, parent_column);
+        if parents == 0 {
+            return;
+        }
+
+        let mut counts = vec![min; parents];
+        let spare = max.saturating_sub(min);
+        let extra = row_count
+            .saturating_sub(parents.saturating_mul(min))
+            .min(parents.saturating_mul(spare));
+        if extra > 0 {
+            // Choose which of the parents' spare slots get filled
+            for slot in rand::seq::index::sample(rng, parents * spare, extra) {
+                counts[slot / spare] += 1;
+            }
+        }
+
+        self.store_allocation(child_table, child_column, &counts, row_count, rng);
+    }
+
+    /// Assign each of the `row_count` rows of `child_table` a parent from
+    /// the `parent_table.parent_column` pool, giving parents numbers of
+    /// children drawn from sampled `(children, share of parents)` shares
+    /// (see [`fan_out_shares`]) and weighed by their activity, scaled to
+    /// `row_count`.
+    ///
+    /// Draws are stratified over the parents, so a count held by one parent
+    /// in a thousand is drawn once every thousand parents rather than
+    /// maybe; parents are then shuffled.
+    #[allow(clippy::too_many_arguments)]
+    pub fn allocate_sampled(
+        &mut self,
+        child_table: &str,
+        child_column: &str,
+        parent_table: &str,
+        parent_column: &str,
+        shares: &[(u64, f64)],
+        row_count: usize,
+        rng: &mut impl Rng,
+    ) {
+        let parents = self.pool_size(parent_table, parent_column);
+        let total: f64 = shares.iter().map(|(_, share)| share.max(0.0)).sum();
+        if parents == 0 || total <= 0.0 {
+            return;
+        }
+
+        let mut drawn: Vec<f64> = (0..parents)
+            .map(|i| {
+                let mut roll = (i as f64 + rng.random::<f64>()) / parents as f64 * total;
+                shares
+                    .iter()
+                    .find(|(_, share)| {
+                        roll -= share.max(0.0);
+                        roll < 0.0
+                    })
+                    .or(shares.last())
+                    .map_or(0.0, |&(children, _)| children as f64)
             })
+            .collect();
+        drawn.shuffle(rng);
+        let key = (parent_table.to_string(), parent_column.to_string());
+        for (parent, count) in drawn.iter_mut().enumerate() {
+            *count *= self.activity_of(&key, parent);
+        }
+
+        // Scale to the rows asked for, rounding up or down at random so
+        // the total stays close
+        let sum: f64 = drawn.iter().sum();
+        let scale = if sum > 0.0 {
+            row_count as f64 / sum
+        } else {
+            0.0
+        };
+        let counts: Vec<usize> = drawn
+            .iter()
+            .map(|count| (count * scale + rng.random::<f64>()).floor() as usize)
+            .collect();
+        self.store_allocation(child_table, child_column, &counts, row_count, rng);
+    }
+
+    /// Lay out `counts[parent]` adjacent rows per parent, cut to
+    /// `row_count`, with any rows left over assigned uniformly.
+    fn store_allocation(
+        &mut self,
+        child_table: &str,
+        child_column: &str,
+        counts: &[usize],
+        row_count: usize,
+        rng: &mut impl Rng,
+    ) {
+        let mut allocation: Vec<usize> = counts
+            .iter()
+            .enumerate()
+            .flat_map(|(parent, &count)| std::iter::repeat_n(parent, count))
+            .take(row_count)
+            .collect();
+        while allocation.len() < row_count {
+            allocation.push(rng.random_range(0..counts.len()));
+        }
+        self.allocations.insert(
+            (child_table.to_string(), child_column.to_string()),
+            allocation,
+        );
+    }
+
+    /// The parent allocated to `row_index` of `child_table.child_column` by
+    /// [`allocate_children`](Self::allocate_children), with its
+    /// `created_at`.
+    pub fn pick_allocated(
+        &self,
+        child_table: &str,
+        child_column: &str,
+        parent_table: &str,
+        parent_column: &str,
+        row_index: usize,
+    ) -> Option<(Value, Option<NaiveDateTime>)> {
+        let parent = *self
+            .allocations
+            .get(&(child_table.to_string(), child_column.to_string()))?
+            .get(row_index)?;
+        let key = (parent_table.to_string(), parent_column.to_string());
+        let value = self.pools.get(&key)?.get(parent)?.clone();
+        Some((value, self.created_at_of(&key, parent)))
     }
 
     /// Get the number of values in a pool.
@@ -85,18 +410,159 @@ mod tests {
         pool.record_value("users", "id", Value::Int(2));
         pool.record_value("users", "id", Value::Int(3));
 
-        let picked = pool.pick_reference("users", "id", &mut rng);
+        let picked = pool.pick_reference("users", "id", FanOut::Uniform, &mut rng);
         assert!(picked.is_some());
         if let Some(Value::Int(v)) = picked {
             assert!((1..=3).contains(&v));
         }
     }
 
+    #[test]
+    fn test_pick_reference_at_returns_parent_creation_time() {
+        let mut pool = ForeignKeyPool::new();
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        let created = chrono::NaiveDate::from_ymd_opt(2025, 1, 2)
+            .unwrap()
+            .and_hms_opt(3, 4, 5)
+            .unwrap();
+        pool.record_value_at("users", "id", Value::Int(1), Some(created));
+
+        let picked = pool.pick_reference_at("users", "id", FanOut::Uniform, &mut rng);
+        assert_eq!(picked, Some((Value::Int(1), Some(created))));
+
+        pool.record_value("teams", "id", Value::Int(7));
+        let picked = pool.pick_reference_at("teams", "id", FanOut::Uniform, &mut rng);
+        assert_eq!(picked, Some((Value::Int(7), None)));
+    }
+
     #[test]
     fn test_empty_pool() {
         let pool = ForeignKeyPool::new();
         let mut rng = rand::rngs::StdRng::seed_from_u64(42);
 
-        assert!(pool.pick_reference("users", "id", &mut rng).is_none());
+        assert!(pool
+            .pick_reference("users", "id", FanOut::Zipf(1.0), &mut rng)
+            .is_none());
+    }
+
+    #[test]
+    fn test_zipf_fan_out_concentrates_children() {
+        let mut pool = ForeignKeyPool::new();
+        for id in 0..1000 {
+            pool.record_value("users", "id", Value::Int(id));
+        }
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        let top_share = |fan_out: FanOut, rng: &mut rand::rngs::StdRng| {
+            let top = (0..10_000)
+                .filter(|_| {
+                    let picked = pool.pick_reference("users", "id", fan_out, rng);
+                    matches!(picked, Some(Value::Int(id)) if id < 10)
+                })
+                .count();
+            top as f64 / 10_000.0
+        };
+
+        // 1% of users own about half of the orders, vs. 1% when uniform
+        let skewed = top_share(FanOut::Zipf(1.2), &mut rng);
+        assert!((0.4..0.6).contains(&skewed), "top 1% got {}", skewed);
+        let flat = top_share(FanOut::Uniform, &mut rng);
+        assert!(flat < 0.03, "top 1% got {}", flat);
+        assert!(top_share(FanOut::Zipf(2.0), &mut rng) > skewed);
+    }
+
+    #[test]
+    fn test_allocate_children_respects_bounds() {
+        let mut pool = ForeignKeyPool::new();
+        for id in 0..100 {
+            pool.record_value("orders", "id", Value::Int(id));
+        }
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        pool.allocate_children(
+            "order_items",
+            "order_id",
+            "orders",
+            "id",
+            (1, 5),
+            300,
+            &mut rng,
+        );
+
+        let mut children = [0usize; 100];
+        for row in 0..300 {
+            match pool.pick_allocated("order_items", "order_id", "orders", "id", row) {
+                Some((Value::Int(id), _)) => children[id as usize] += 1,
+                other => panic!("row {} got {:?}", row, other),
+            }
+        }
+        assert!(
+            children.iter().all(|c| (1..=5).contains(c)),
+            "{:?}",
+            children
+        );
+        assert!(pool
+            .pick_allocated("order_items", "order_id", "orders", "id", 300)
+            .is_none());
+
+        // More rows than the bounds allow: every parent is full, the rest spread out
+        pool.allocate_children(
+            "order_items",
+            "order_id",
+            "orders",
+            "id",
+            (1, 2),
+            250,
+            &mut rng,
+        );
+        assert!(pool
+            .pick_allocated("order_items", "order_id", "orders", "id", 249)
+            .is_some());
+    }
+
+    #[test]
+    fn test_allocate_sampled_keeps_whales() {
+        // 98% of users have one order, 2% have fifty
+        let shares = fan_out_shares(&[(1, 490), (50, 10)], Some(500)).unwrap();
+        assert_eq!(shares, vec![(1, 0.98), (50, 0.02)]);
+
+        let mut pool = ForeignKeyPool::new();
+        for id in 0..1000 {
+            pool.record_value("users", "id", Value::Int(id));
+        }
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        pool.allocate_sampled("orders", "user_id", "users", "id", &shares, 1980, &mut rng);
+
+        let mut children = [0usize; 1000];
+        for row in 0..1980 {
+            match pool.pick_allocated("orders", "user_id", "users", "id", row) {
+                Some((Value::Int(id), _)) => children[id as usize] += 1,
+                other => panic!("row {} got {:?}", row, other),
+            }
+        }
+        let whales = children.iter().filter(|c| **c >= 40).count();
+        let singles = children.iter().filter(|c| **c == 1).count();
+        assert!((18..=22).contains(&whales), "{}", whales);
+        assert!(singles > 900, "{}", singles);
+    }
+
+    #[test]
+    fn test_fan_out_shares_counts_childless_parents() {
+        let shares = fan_out_shares(&[(2, 30), (5, 10)], Some(100)).unwrap();
+        assert_eq!(shares, vec![(0, 0.6), (2, 0.3), (5, 0.1)]);
+        // Fewer parent rows than parents seen: nothing left childless
+        assert_eq!(
+            fan_out_shares(&[(2, 30)], Some(10)).unwrap(),
+            vec![(2, 1.0)]
+        );
+        assert!(fan_out_shares(&[], Some(10)).is_none());
+    }
+
+    #[test]
+    fn test_fan_out_validate() {
+        assert!(FanOut::Children(1, 5).validate().is_ok());
+        assert!(FanOut::Children(5, 1).validate().is_err());
+        assert!(FanOut::Children(0, 0).validate().is_err());
+        assert!(FanOut::Zipf(1.2).validate().is_ok());
+        assert!(FanOut::Zipf(0.0).validate().is_err());
+        assert!(FanOut::Zipf(f64::NAN).validate().is_err());
     }
 }
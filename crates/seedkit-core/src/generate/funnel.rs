@@ -0,0 +1,255 @@
+//! # Event Funnels
+//!
+//! Event-log tables generated row by row look like uniform noise: every
+//! event type equally common, sessions scattered over users, purchases
+//! before signups. A table configured with a funnel is generated as user
+//! journeys instead:
+//!
+//! ```toml
+//! [tables.events.funnel]
+//! steps = ["signup", "activate", "purchase"]
+//! drop_off = [0.4, 0.7]        # 40% never activate, 70% of the rest never buy
+//! step_gap = "2 hours"         # longest wait between two steps
+//! event_column = "event_name"  # default: event_name, event_type, event or action
+//! user_column = "user_id"      # default: user_id, if the table has one
+//! session_column = "sid"       # default: session_id, if the table has one
+//! timestamp = "occurred_at"    # default: the table's first timestamp column
+//! ```
+//!
+//! Each journey starts at the first step and moves on until it drops off or
+//! completes the funnel, one row per step. The user and session of a journey
+//! are those generated for its first row, and its steps follow each other
+//! by up to `step_gap`, ending no later than the plan's base time. Journeys
+//! fill the table's rows in order; the last one may be cut short.
+
+use chrono::{Duration, NaiveDateTime};
+use indexmap::IndexMap;
+use rand::Rng;
+use serde::Deserialize;
+
+use crate::generate::plan::TableGenerationPlan;
+use crate::generate::value::Value;
+use crate::schema::check_expr::parse_interval_seconds;
+
+/// Event columns tried, in order, when `event_column` isn't set.
+pub const EVENT_COLUMN_NAMES: &[&str] = &["event_name", "event_type", "event", "action"];
+
+/// A table's `funnel` settings from `seedkit.toml`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Funnel {
+    pub steps: Vec<String>,
+    /// Share of journeys that stop after each step but the last.
+    #[serde(default)]
+    pub drop_off: Vec<f64>,
+    pub step_gap: Option<String>,
+    pub event_column: Option<String>,
+    pub user_column: Option<String>,
+    pub session_column: Option<String>,
+    pub timestamp: Option<String>,
+}
+
+/// Longest wait between two steps when `step_gap` isn't set.
+const DEFAULT_STEP_GAP_SECS: i64 = 86_400;
+
+impl Funnel {
+    /// Check the settings that don't depend on the schema.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.steps.is_empty() {
+            return Err("needs at least one step".to_string());
+        }
+        if self.drop_off.len() > self.steps.len().saturating_sub(1) {
+            return Err(format!(
+                "{} drop_off rates for {} steps; give one per step but the last",
+                self.drop_off.len(),
+                self.steps.len()
+            ));
+        }
+        if let Some(rate) = self.drop_off.iter().find(|r| !(0.0..=1.0).contains(*r)) {
+            return Err(format!("drop_off rate {} is not between 0 and 1", rate));
+        }
+        self.step_gap_secs().map(|_| ())
+    }
+
+    fn step_gap_secs(&self) -> Result<i64, String> {
+        let Some(ref gap) = self.step_gap else {
+            return Ok(DEFAULT_STEP_GAP_SECS);
+        };
+        match parse_interval_seconds(gap) {
+            Some(secs) if secs > 0 => Ok(secs),
+            _ => Err(format!(
+                "step_gap '{}' is not a positive interval such as \"2 hours\"",
+                gap
+            )),
+        }
+    }
+}
+
+/// A funnel resolved against the schema.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FunnelPlan {
+    pub steps: Vec<String>,
+    /// One rate per step but the last; missing rates are 0.
+    pub drop_off: Vec<f64>,
+    pub step_gap_secs: i64,
+    pub event_column: String,
+    pub timestamp_column: String,
+    /// Columns held for a whole journey: its user and session.
+    pub journey_columns: Vec<String>,
+}
+
+impl FunnelPlan {
+    /// Resolve `settings` with the given columns. Invalid settings are
+    /// rejected by config validation and give `None` here.
+    pub fn new(
+        settings: &Funnel,
+        event_column: String,
+        timestamp_column: String,
+        journey_columns: Vec<String>,
+    ) -> Option<Self> {
+        let mut drop_off = settings.drop_off.clone();
+        drop_off.resize(settings.steps.len().saturating_sub(1), 0.0);
+        Some(Self {
+            steps: settings.steps.clone(),
+            drop_off,
+            step_gap_secs: settings.step_gap_secs().ok()?,
+            event_column,
+            timestamp_column,
+            journey_columns,
+        })
+    }
+}
+
+/// Lays a table's rows out as funnel journeys while it is generated.
+pub struct JourneyCursor<'a> {
+    plan: &'a FunnelPlan,
+    /// Rows left in the current journey.
+    remaining: usize,
+    step: usize,
+    /// When each step of the current journey happens.
+    times: Vec<NaiveDateTime>,
+    /// The user and session of the current journey.
+    held: Vec<(String, Value)>,
+}
+
+impl<'a> JourneyCursor<'a> {
+    pub fn new(table_plan: &'a TableGenerationPlan) -> Option<Self> {
+        Some(Self {
+            plan: table_plan.funnel.as_ref()?,
+            remaining: 0,
+            step: 0,
+            times: Vec::new(),
+            held: Vec::new(),
+        })
+    }
+
+    /// Make `row` the next step of the current journey, or start a new one.
+    pub fn apply(
+        &mut self,
+        row: &mut IndexMap<String, Value>,
+        base_time: NaiveDateTime,
+        rng: &mut impl Rng,
+    ) {
+        if self.remaining == 0 {
+            self.start_journey(row, base_time, rng);
+        }
+
+        row.insert(
+            self.plan.event_column.clone(),
+            Value::String(self.plan.steps[self.step].clone().into()),
+        );
+        if let Some(value) = row.get_mut(&self.plan.timestamp_column) {
+            let time = self.times[self.step];
+            *value = match value {
+                Value::Date(_) => Value::Date(time.date()),
+                _ => Value::Timestamp(time),
+            };
+        }
+        for (column, value) in &self.held {
+            row.insert(column.clone(), value.clone());
+        }
+        self.step += 1;
+        self.remaining -= 1;
+    }
+
+    fn start_journey(
+        &mut self,
+        row: &IndexMap<String, Value>,
+        base_time: NaiveDateTime,
+        rng: &mut impl Rng,
+    ) {
+        let mut length = 1;
+        while length < self.plan.steps.len()
+            && rng.random::<f64>() >= self.plan.drop_off[length - 1]
+        {
+            length += 1;
+        }
+
+        let gaps: Vec<i64> = (1..length)
+            .map(|_| rng.random_range(1..=self.plan.step_gap_secs))
+            .collect();
+        let span: i64 = gaps.iter().sum();
+        let latest_start = base_time - Duration::seconds(span);
+        let start = match row.get(&self.plan.timestamp_column) {
+            Some(Value::Timestamp(t)) => (*t).min(latest_start),
+            Some(Value::Date(d)) => d
+                .and_hms_opt(0, 0, 0)
+                .unwrap_or(latest_start)
+                .min(latest_start),
+            _ => latest_start,
+        };
+        self.times = std::iter::once(start)
+            .chain(gaps.iter().scan(start, |time, gap| {
+                *time += Duration::seconds(*gap);
+                Some(*time)
+            }))
+            .collect();
+
+        self.held = self
+            .plan
+            .journey_columns
+            .iter()
+            .filter_map(|column| Some((column.clone(), row.get(column)?.clone())))
+            .collect();
+        self.remaining = length;
+        self.step = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(toml: &str) -> Funnel {
+        toml::from_str(toml).unwrap()
+    }
+
+    #[test]
+    fn test_validate_funnel() {
+        let funnel = settings(
+            r#"
+steps = ["signup", "activate", "purchase"]
+drop_off = [0.4]
+step_gap = "2 hours"
+"#,
+        );
+        assert!(funnel.validate().is_ok());
+        let plan = FunnelPlan::new(
+            &funnel,
+            "event_name".to_string(),
+            "occurred_at".to_string(),
+            Vec::new(),
+        )
+        .unwrap();
+        assert_eq!(plan.drop_off, vec![0.4, 0.0]);
+        assert_eq!(plan.step_gap_secs, 7200);
+
+        for bad in [
+            "steps = []",
+            "steps = [\"signup\"]\ndrop_off = [0.5]",
+            "steps = [\"signup\", \"buy\"]\ndrop_off = [1.5]",
+            "steps = [\"signup\", \"buy\"]\nstep_gap = \"later\"",
+        ] {
+            assert!(settings(bad).validate().is_err(), "{}", bad);
+        }
+    }
+}
@@ -0,0 +1,431 @@
+//! # JSON Schema Documents
+//!
+//! JSON/JSONB columns get a generic `JsonData` blob unless they have a
+//! [JSON Schema](https://json-schema.org) attached in `seedkit.toml`, from a
+//! file (relative to seedkit.toml) or inline:
+//!
+//! ```toml
+//! [columns."users.settings"]
+//! json_schema = "./schemas/user_settings.json"
+//!
+//! [columns."events.payload"]
+//! json_schema = { type = "object", required = ["kind"], properties = { kind = { enum = ["click", "view"] } } }
+//! ```
+//!
+//! Every document validates against the schema. Supported keywords:
+//! `type` (one or a list), `enum`, `const`, `properties`, `required`,
+//! `items`, `minItems`/`maxItems`, `minLength`/`maxLength`,
+//! `minimum`/`maximum` and their exclusive forms, `format` (`email`,
+//! `uuid`, `date`, `date-time`, `time`, `uri`, `hostname`, `ipv4`),
+//! `oneOf`/`anyOf` (one branch is picked), `allOf` (object branches merged)
+//! and local `$ref`s (`#/$defs/...`). Optional properties appear in most
+//! documents, not all. Strings without a format are drawn from the
+//! generator their property name suggests (`email`, `city`, ...), or are
+//! words otherwise.
+
+use std::path::Path;
+
+use chrono::NaiveDateTime;
+use rand::Rng;
+use serde::Deserialize;
+use serde_json::{Map, Value as Json};
+
+use crate::classify::rules::classify_column;
+use crate::classify::semantic::SemanticType;
+use crate::generate::providers::generate_value;
+use crate::generate::value::Value;
+use crate::schema::types::DataType;
+
+/// How deep `$ref`s and nesting may go before documents are cut short.
+const MAX_DEPTH: usize = 16;
+
+/// Share of documents in which an optional property appears.
+const OPTIONAL_PROPERTY_PROBABILITY: f64 = 0.7;
+
+/// Default `maxItems` for arrays.
+const DEFAULT_MAX_ITEMS: u64 = 4;
+
+const KNOWN_TYPES: &[&str] = &[
+    "object", "array", "string", "integer", "number", "boolean", "null",
+];
+
+/// A column's `json_schema`: a path to a schema file, or the schema itself.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum JsonSchemaSource {
+    Path(String),
+    Inline(Json),
+}
+
+impl JsonSchemaSource {
+    /// The schema, reading it from its file (relative to `dir`) if needed.
+    pub fn load(&self, dir: Option<&Path>) -> Result<Json, String> {
+        let path = match self {
+            JsonSchemaSource::Inline(schema) => return Ok(schema.clone()),
+            JsonSchemaSource::Path(path) => match dir {
+                Some(dir) if Path::new(path).is_relative() => dir.join(path),
+                _ => Path::new(path).to_path_buf(),
+            },
+        };
+        let text = std::fs::read_to_string(&path)
+            .map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+        serde_json::from_str(&text).map_err(|e| format!("{} is not JSON: {}", path.display(), e))
+    }
+}
+
+/// Check that `schema` uses types and references this generator
+/// understands.
+pub fn check_schema(schema: &Json) -> Result<(), String> {
+    check_node(schema, schema, 0)
+}
+
+fn check_node(root: &Json, node: &Json, depth: usize) -> Result<(), String> {
+    if depth > MAX_DEPTH {
+        return Ok(());
+    }
+    let object = match node {
+        Json::Bool(_) => return Ok(()),
+        Json::Object(object) => object,
+        other => return Err(format!("a schema must be an object, got {}", other)),
+    };
+    if let Some(reference) = object.get("$ref") {
+        let target = reference
+            .as_str()
+            .and_then(|r| resolve_ref(root, r))
+            .ok_or_else(|| format!("cannot resolve $ref {}", reference))?;
+        return check_node(root, target, depth + 1);
+    }
+    for name in types(object) {
+        if !KNOWN_TYPES.contains(&name) {
+            return Err(format!("unknown type '{}'", name));
+        }
+    }
+    if object.get("enum").is_some_and(|e| !e.is_array()) {
+        return Err("enum must be an array".to_string());
+    }
+    let children = object
+        .get("properties")
+        .and_then(Json::as_object)
+        .into_iter()
+        .flat_map(|p| p.values())
+        .chain(object.get("items"))
+        .chain(
+            ["oneOf", "anyOf", "allOf"]
+                .iter()
+                .filter_map(|k| object.get(*k).and_then(Json::as_array))
+                .flatten(),
+        );
+    for child in children {
+        check_node(root, child, depth + 1)?;
+    }
+    Ok(())
+}
+
+/// Generate a document valid against `schema`.
+pub fn generate_document(
+    schema: &Json,
+    rng: &mut impl Rng,
+    row_index: usize,
+    base_time: NaiveDateTime,
+) -> Json {
+    let mut generator = Generator {
+        root: schema,
+        row_index,
+        base_time,
+    };
+    generator.node(schema, None, rng, 0)
+}
+
+struct Generator<'a> {
+    root: &'a Json,
+    row_index: usize,
+    base_time: NaiveDateTime,
+}
+
+impl Generator<'_> {
+    fn node(&mut self, node: &Json, name: Option<&str>, rng: &mut impl Rng, depth: usize) -> Json {
+        let Some(object) = node.as_object() else {
+            return Json::Null;
+        };
+        if depth > MAX_DEPTH {
+            return Json::Null;
+        }
+        if let Some(target) = object
+            .get("$ref")
+            .and_then(Json::as_str)
+            .and_then(|r| resolve_ref(self.root, r))
+        {
+            return self.node(target, name, rng, depth + 1);
+        }
+        if let Some(value) = object.get("const") {
+            return value.clone();
+        }
+        if let Some(options) = object.get("enum").and_then(Json::as_array) {
+            if !options.is_empty() {
+                return options[rng.random_range(0..options.len())].clone();
+            }
+        }
+        for key in ["oneOf", "anyOf"] {
+            if let Some(branches) = object.get(key).and_then(Json::as_array) {
+                if !branches.is_empty() {
+                    let branch = &branches[rng.random_range(0..branches.len())];
+                    return self.node(branch, name, rng, depth + 1);
+                }
+            }
+        }
+        if let Some(branches) = object.get("allOf").and_then(Json::as_array) {
+            let merged = merge_branches(self.root, object, branches);
+            return self.object(&merged, rng, depth);
+        }
+
+        let types = types(object);
+        let type_name = match types.len() {
+            0 if object.contains_key("properties") => "object",
+            0 if object.contains_key("items") => "array",
+            0 => "string",
+            n => types[rng.random_range(0..n)],
+        };
+        match type_name {
+            "object" => self.object(object, rng, depth),
+            "array" => {
+                let min = object.get("minItems").and_then(Json::as_u64).unwrap_or(0);
+                let max = object
+                    .get("maxItems")
+                    .and_then(Json::as_u64)
+                    .unwrap_or(DEFAULT_MAX_ITEMS.max(min))
+                    .max(min);
+                let len = rng.random_range(min..=max);
+                let items = object.get("items").unwrap_or(&Json::Null);
+                Json::Array(
+                    (0..len)
+                        .map(|_| self.node(items, name, rng, depth + 1))
+                        .collect(),
+                )
+            }
+            "integer" => {
+                let (min, max) = bounds(object, 1.0);
+                let (min, max) = (min.ceil() as i64, max.floor() as i64);
+                Json::from(rng.random_range(min..=max.max(min)))
+            }
+            "number" => {
+                let (min, max) = bounds(object, 0.01);
+                let max = max.max(min);
+                let value = rng.random_range(min..=max);
+                // Two decimals, unless rounding leaves the bounds
+                let rounded = (value * 100.0).round() / 100.0;
+                Json::from(if (min..=max).contains(&rounded) {
+                    rounded
+                } else {
+                    value
+                })
+            }
+            "boolean" => Json::Bool(rng.random()),
+            "null" => Json::Null,
+            _ => Json::String(self.string(object, name, rng)),
+        }
+    }
+
+    fn object(&mut self, object: &Map<String, Json>, rng: &mut impl Rng, depth: usize) -> Json {
+        let required: Vec<&str> = object
+            .get("required")
+            .and_then(Json::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Json::as_str)
+            .collect();
+        let mut document = Map::new();
+        if let Some(properties) = object.get("properties").and_then(Json::as_object) {
+            for (key, property) in properties {
+                if required.contains(&key.as_str())
+                    || rng.random_bool(OPTIONAL_PROPERTY_PROBABILITY)
+                {
+                    let value = self.node(property, Some(key), rng, depth + 1);
+                    document.insert(key.clone(), value);
+                }
+            }
+        }
+        Json::Object(document)
+    }
+
+    fn string(
+        &mut self,
+        object: &Map<String, Json>,
+        name: Option<&str>,
+        rng: &mut impl Rng,
+    ) -> String {
+        let min = object.get("minLength").and_then(Json::as_u64).unwrap_or(0) as usize;
+        let max = object
+            .get("maxLength")
+            .and_then(Json::as_u64)
+            .map_or(usize::MAX, |m| m as usize);
+        generate_text(
+            name,
+            object.get("format").and_then(Json::as_str),
+            (min, max),
+            rng,
+            self.row_index,
+            self.base_time,
+        )
+    }
+}
+
+/// A string for `format`, or else for a property called `name`, between
+/// `length.0` and `length.1` characters long.
+pub(crate) fn generate_text(
+    name: Option<&str>,
+    format: Option<&str>,
+    length: (usize, usize),
+    rng: &mut impl Rng,
+    row_index: usize,
+    base_time: NaiveDateTime,
+) -> String {
+    let semantic = match format {
+        Some("email") => SemanticType::Email,
+        Some("uuid") => SemanticType::Uuid,
+        Some("date") => SemanticType::DateOnly,
+        Some("date-time") => SemanticType::Timestamp,
+        Some("time") => SemanticType::TimeOnly,
+        Some("uri") | Some("url") => SemanticType::Url,
+        Some("hostname") => SemanticType::DomainName,
+        Some("ipv4") => SemanticType::IpAddress,
+        _ => name
+            .map(|n| classify_column(n, &DataType::Text, "", false, false, None))
+            .unwrap_or(SemanticType::Unknown),
+    };
+    let value = generate_value(semantic, rng, row_index, &[], base_time);
+    let text = match value {
+        Value::Timestamp(t) => t.format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+        Value::String(_) | Value::Uuid(_) | Value::Date(_) | Value::Time(_) => {
+            value.to_csv_string()
+        }
+        _ => generate_value(SemanticType::Unknown, rng, row_index, &[], base_time).to_csv_string(),
+    };
+
+    let (min, max) = length;
+    let mut text: String = text.chars().take(max).collect();
+    while text.chars().count() < min {
+        text.push(rng.random_range(b'a'..=b'z') as char);
+    }
+    text
+}
+
+/// The schema's `type`, as a list.
+fn types(object: &Map<String, Json>) -> Vec<&str> {
+    match object.get("type") {
+        Some(Json::String(name)) => vec![name.as_str()],
+        Some(Json::Array(names)) => names.iter().filter_map(Json::as_str).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Numeric bounds, with exclusive bounds moved inward by `step`.
+fn bounds(object: &Map<String, Json>, step: f64) -> (f64, f64) {
+    let get = |key: &str| object.get(key).and_then(Json::as_f64);
+    let min = get("minimum")
+        .or(get("exclusiveMinimum").map(|m| m + step))
+        .unwrap_or(0.0);
+    let max = get("maximum")
+        .or(get("exclusiveMaximum").map(|m| m - step))
+        .unwrap_or(min.max(0.0) + 1000.0);
+    (min, max)
+}
+
+/// Resolve a local reference such as `#/$defs/address`.
+fn resolve_ref<'a>(root: &'a Json, reference: &str) -> Option<&'a Json> {
+    let pointer = reference.strip_prefix('#')?;
+    root.pointer(pointer)
+}
+
+/// Merge `allOf` branches (and the node's own keywords) into one object
+/// schema: properties and required lists combine.
+fn merge_branches(root: &Json, object: &Map<String, Json>, branches: &[Json]) -> Map<String, Json> {
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+    let own = Json::Object(object.clone());
+    for branch in std::iter::once(&own).chain(branches) {
+        let branch = match branch.get("$ref").and_then(Json::as_str) {
+            Some(reference) => resolve_ref(root, reference).unwrap_or(branch),
+            None => branch,
+        };
+        if let Some(p) = branch.get("properties").and_then(Json::as_object) {
+            properties.extend(p.clone());
+        }
+        if let Some(r) = branch.get("required").and_then(Json::as_array) {
+            required.extend(r.iter().cloned());
+        }
+    }
+    let mut merged = Map::new();
+    merged.insert("properties".to_string(), Json::Object(properties));
+    merged.insert("required".to_string(), Json::Array(required));
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    fn base_time() -> NaiveDateTime {
+        chrono::NaiveDate::from_ymd_opt(2025, 6, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_documents_follow_the_schema() {
+        let schema = serde_json::json!({
+            "type": "object",
+            "required": ["id", "email", "kind", "tags", "address", "score"],
+            "properties": {
+                "id": { "type": "string", "format": "uuid" },
+                "email": { "type": "string" },
+                "kind": { "enum": ["click", "view"] },
+                "tags": {
+                    "type": "array",
+                    "items": { "type": "string", "maxLength": 6 },
+                    "minItems": 1,
+                    "maxItems": 3
+                },
+                "address": { "$ref": "#/$defs/address" },
+                "score": { "type": "integer", "minimum": 1, "exclusiveMaximum": 6 },
+                "note": { "type": ["string", "null"] }
+            },
+            "$defs": {
+                "address": {
+                    "type": "object",
+                    "required": ["city"],
+                    "properties": { "city": { "type": "string" } }
+                }
+            }
+        });
+        assert!(check_schema(&schema).is_ok());
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        for row in 0..50 {
+            let doc = generate_document(&schema, &mut rng, row, base_time());
+            assert!(uuid::Uuid::parse_str(doc["id"].as_str().unwrap()).is_ok());
+            assert!(doc["email"].as_str().unwrap().contains('@'), "{}", doc);
+            assert!(["click", "view"].contains(&doc["kind"].as_str().unwrap()));
+            let tags = doc["tags"].as_array().unwrap();
+            assert!((1..=3).contains(&tags.len()));
+            assert!(tags
+                .iter()
+                .all(|t| t.as_str().unwrap().chars().count() <= 6));
+            assert!(doc["address"]["city"].is_string());
+            assert!((1..=5).contains(&doc["score"].as_i64().unwrap()));
+        }
+    }
+
+    #[test]
+    fn test_check_schema_rejects_unknown_types_and_refs() {
+        for schema in [
+            serde_json::json!({ "type": "decimal" }),
+            serde_json::json!({ "properties": { "a": { "$ref": "#/$defs/missing" } } }),
+            serde_json::json!({ "enum": "click" }),
+            serde_json::json!("object"),
+        ] {
+            assert!(check_schema(&schema).is_err(), "{}", schema);
+        }
+    }
+}
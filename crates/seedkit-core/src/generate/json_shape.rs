@@ -0,0 +1,139 @@
+//! # Sampled JSON Shapes
+//!
+//! `seedkit sample` profiles JSON/JSONB columns by structure only (see
+//! `sample::stats::JsonShape`): the types found at each place in the
+//! documents, which keys objects have and how often, how long arrays and
+//! strings are and what range numbers span. Documents generated from a
+//! shape follow it level by level — each key appears with its sampled
+//! frequency — while strings come from the generator the key's name
+//! suggests, so no production value is reproduced.
+
+use chrono::NaiveDateTime;
+use rand::Rng;
+use serde_json::{Map, Value as Json};
+
+use crate::generate::json_schema::generate_text;
+use crate::sample::stats::JsonShape;
+
+/// Array length range when the shape has none.
+const DEFAULT_ARRAY_LENGTH: (usize, usize) = (0, 3);
+
+/// Generate a document shaped like the sampled ones.
+pub fn generate_shaped(
+    shape: &JsonShape,
+    rng: &mut impl Rng,
+    row_index: usize,
+    base_time: NaiveDateTime,
+) -> Json {
+    shaped(shape, None, rng, row_index, base_time)
+}
+
+fn shaped(
+    shape: &JsonShape,
+    name: Option<&str>,
+    rng: &mut impl Rng,
+    row_index: usize,
+    base_time: NaiveDateTime,
+) -> Json {
+    match pick_type(shape, rng) {
+        "object" => {
+            let mut document = Map::new();
+            for key in &shape.keys {
+                if rng.random_bool(key.frequency.clamp(0.0, 1.0)) {
+                    let value = shaped(&key.shape, Some(&key.name), rng, row_index, base_time);
+                    document.insert(key.name.clone(), value);
+                }
+            }
+            Json::Object(document)
+        }
+        "array" => {
+            let (min, max) = shape.array_length.unwrap_or(DEFAULT_ARRAY_LENGTH);
+            let len = rng.random_range(min..=max.max(min));
+            Json::Array(match shape.items {
+                Some(ref items) => (0..len)
+                    .map(|_| shaped(items, name, rng, row_index, base_time))
+                    .collect(),
+                None => Vec::new(),
+            })
+        }
+        "integer" => {
+            let (min, max) = shape.number_range.unwrap_or((0.0, 1000.0));
+            let (min, max) = (min.ceil() as i64, max.floor() as i64);
+            Json::from(rng.random_range(min..=max.max(min)))
+        }
+        "number" => {
+            let (min, max) = shape.number_range.unwrap_or((0.0, 1000.0));
+            let value = rng.random_range(min..=max.max(min));
+            Json::from(((value * 100.0).round() / 100.0).clamp(min, max.max(min)))
+        }
+        "string" => Json::String(generate_text(
+            name,
+            None,
+            shape.string_length.unwrap_or((0, usize::MAX)),
+            rng,
+            row_index,
+            base_time,
+        )),
+        "boolean" => Json::Bool(rng.random()),
+        _ => Json::Null,
+    }
+}
+
+/// Pick one of the shape's types by its share of sampled values.
+fn pick_type<'a>(shape: &'a JsonShape, rng: &mut impl Rng) -> &'a str {
+    let total: f64 = shape.types.iter().map(|(_, share)| share.max(0.0)).sum();
+    let mut roll = rng.random::<f64>() * total;
+    for (name, share) in &shape.types {
+        roll -= share.max(0.0);
+        if roll < 0.0 {
+            return name;
+        }
+    }
+    shape.types.last().map_or("null", |(name, _)| name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sample::stats::profile_json;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_generated_documents_follow_the_sampled_shape() {
+        let samples = [
+            r#"{"theme": "dark", "email": "ann@corp.example", "tags": ["a", "b"], "limits": {"daily": 5}}"#,
+            r#"{"theme": "light", "email": "bob@corp.example", "tags": [], "limits": {"daily": 12}}"#,
+            r#"{"theme": "dark", "email": "cy@corp.example", "tags": ["c"], "beta": true}"#,
+            r#"{"theme": "light", "email": "di@corp.example", "tags": ["d", "e", "f"]}"#,
+        ];
+        let shape = profile_json(samples);
+        assert_eq!(shape.types, vec![("object".to_string(), 1.0)]);
+        let key = |name: &str| shape.keys.iter().find(|k| k.name == name).unwrap();
+        assert_eq!(key("theme").frequency, 1.0);
+        assert_eq!(key("limits").frequency, 0.5);
+        assert_eq!(key("tags").shape.array_length, Some((0, 3)));
+        assert_eq!(
+            key("limits").shape.keys[0].shape.number_range,
+            Some((5.0, 12.0))
+        );
+
+        let base_time = chrono::NaiveDate::from_ymd_opt(2025, 6, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        let mut with_limits = 0;
+        for row in 0..200 {
+            let doc = generate_shaped(&shape, &mut rng, row, base_time);
+            assert!(doc["theme"].is_string());
+            assert!(doc["email"].as_str().unwrap().contains('@'), "{}", doc);
+            let tags = doc["tags"].as_array().unwrap();
+            assert!(tags.len() <= 3 && tags.iter().all(Json::is_string));
+            if let Some(daily) = doc.get("limits").map(|l| &l["daily"]) {
+                assert!((5..=12).contains(&daily.as_i64().unwrap()));
+                with_limits += 1;
+            }
+        }
+        assert!((60..=140).contains(&with_limits), "{}", with_limits);
+    }
+}
@@ -0,0 +1,420 @@
+//! # Text Language Mixing
+//!
+//! Renders a share of rows' free-text content (titles, descriptions,
+//! paragraphs, ...) in other languages, so i18n rendering gets exercised by
+//! seed data. A [`LanguageMix`] such as `{ en = 0.9, de = 0.05, ja = 0.05 }`
+//! picks one language per row from the row's RNG, keeping output
+//! deterministic under the seed. English keeps the default lorem text.
+//!
+//! The word pools are small and the grammar is nonsense — the point is
+//! realistic scripts, diacritics, word lengths and punctuation, not prose.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use rand::Rng;
+
+use crate::classify::semantic::SemanticType;
+use crate::generate::value::Value;
+
+/// Language for generated text content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextLanguage {
+    En,
+    De,
+    Fr,
+    Es,
+    Ja,
+    El,
+}
+
+impl FromStr for TextLanguage {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "en" => Ok(TextLanguage::En),
+            "de" => Ok(TextLanguage::De),
+            "fr" => Ok(TextLanguage::Fr),
+            "es" => Ok(TextLanguage::Es),
+            "ja" => Ok(TextLanguage::Ja),
+            "el" => Ok(TextLanguage::El),
+            _ => Err(format!(
+                "Unsupported text language '{}'. Supported: en, de, fr, es, ja, el.",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for TextLanguage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let code = match self {
+            TextLanguage::En => "en",
+            TextLanguage::De => "de",
+            TextLanguage::Fr => "fr",
+            TextLanguage::Es => "es",
+            TextLanguage::Ja => "ja",
+            TextLanguage::El => "el",
+        };
+        write!(f, "{}", code)
+    }
+}
+
+static DE_WORDS: &[&str] = &[
+    "der",
+    "die",
+    "und",
+    "mit",
+    "für",
+    "über",
+    "neue",
+    "schnell",
+    "einfach",
+    "Größe",
+    "Qualität",
+    "Straße",
+    "Frühling",
+    "Bücher",
+    "Lösung",
+    "Übersicht",
+    "Kunden",
+    "Angebot",
+    "heute",
+    "morgen",
+    "günstig",
+    "schön",
+    "Mädchen",
+    "Fußball",
+    "Küche",
+    "Geschäft",
+    "Erfahrung",
+    "Möglichkeit",
+    "zuverlässig",
+    "natürlich",
+    "Veranstaltung",
+    "wählen",
+];
+
+static FR_WORDS: &[&str] = &[
+    "le",
+    "la",
+    "et",
+    "avec",
+    "pour",
+    "très",
+    "nouveau",
+    "été",
+    "déjà",
+    "où",
+    "château",
+    "forêt",
+    "garçon",
+    "français",
+    "événement",
+    "qualité",
+    "réponse",
+    "idée",
+    "hôtel",
+    "île",
+    "fenêtre",
+    "bientôt",
+    "élégant",
+    "première",
+    "cœur",
+    "naïf",
+    "saison",
+    "prix",
+    "découvrir",
+    "entreprise",
+    "service",
+    "élève",
+];
+
+static ES_WORDS: &[&str] = &[
+    "el",
+    "la",
+    "y",
+    "con",
+    "para",
+    "más",
+    "nuevo",
+    "año",
+    "niño",
+    "mañana",
+    "corazón",
+    "canción",
+    "rápido",
+    "fácil",
+    "información",
+    "compañía",
+    "pequeño",
+    "señor",
+    "español",
+    "ciudad",
+    "música",
+    "teléfono",
+    "también",
+    "después",
+    "montaña",
+    "camión",
+    "café",
+    "árbol",
+    "oferta",
+    "calidad",
+    "servicio",
+    "jardín",
+];
+
+static JA_WORDS: &[&str] = &[
+    "新しい",
+    "製品",
+    "今日",
+    "東京",
+    "季節",
+    "お知らせ",
+    "サービス",
+    "情報",
+    "簡単",
+    "品質",
+    "限定",
+    "特別",
+    "春",
+    "夏",
+    "秋",
+    "冬",
+    "お客様",
+    "予約",
+    "料理",
+    "旅行",
+    "天気",
+    "便利",
+    "人気",
+    "最新",
+    "コーヒー",
+    "デザイン",
+    "イベント",
+    "体験",
+];
+
+static EL_WORDS: &[&str] = &[
+    "και",
+    "με",
+    "για",
+    "νέο",
+    "σήμερα",
+    "αύριο",
+    "ποιότητα",
+    "προσφορά",
+    "καλοκαίρι",
+    "θάλασσα",
+    "ταξίδι",
+    "εμπειρία",
+    "υπηρεσία",
+    "πελάτες",
+    "εύκολο",
+    "γρήγορο",
+    "όμορφο",
+    "πόλη",
+    "σπίτι",
+    "βιβλίο",
+    "μουσική",
+    "καφές",
+    "ήλιος",
+    "ειδήσεις",
+    "εκδήλωση",
+    "ευκαιρία",
+    "κουζίνα",
+    "δρόμος",
+];
+
+/// Weighted choice of text language, e.g. `{ en = 0.9, de = 0.05, ja = 0.05 }`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LanguageMix {
+    /// Languages with cumulative weights, normalized so the last is 1.0.
+    cumulative: Vec<(TextLanguage, f64)>,
+}
+
+impl LanguageMix {
+    /// Build a mix from language codes and weights. Weights are relative and
+    /// need not sum to 1.
+    pub fn from_weights(weights: &BTreeMap<String, f64>) -> Result<Self, String> {
+        let mut parsed = Vec::new();
+        for (code, &weight) in weights {
+            let language = code.parse::<TextLanguage>()?;
+            if !weight.is_finite() || weight < 0.0 {
+                return Err(format!(
+                    "Weight for '{}' must be a non-negative number, got {}.",
+                    code, weight
+                ));
+            }
+            parsed.push((language, weight));
+        }
+        let total: f64 = parsed.iter().map(|(_, w)| w).sum();
+        if total <= 0.0 {
+            return Err("At least one language needs a positive weight.".to_string());
+        }
+
+        let mut running = 0.0;
+        let cumulative = parsed
+            .into_iter()
+            .map(|(language, weight)| {
+                running += weight / total;
+                (language, running)
+            })
+            .collect();
+        Ok(Self { cumulative })
+    }
+
+    /// Pick a language. Draws once from `rng`.
+    pub fn pick(&self, rng: &mut impl Rng) -> TextLanguage {
+        let roll: f64 = rng.random();
+        self.cumulative
+            .iter()
+            .find(|(_, bound)| roll < *bound)
+            .or(self.cumulative.last())
+            .map(|(language, _)| *language)
+            .unwrap_or(TextLanguage::En)
+    }
+}
+
+/// Text content for `semantic_type` in `language`, or `None` when the
+/// default provider should handle it (English, or not a free-text type).
+pub fn generate_text(
+    semantic_type: SemanticType,
+    language: TextLanguage,
+    rng: &mut impl Rng,
+) -> Option<Value> {
+    let words = match language {
+        TextLanguage::En => return None,
+        TextLanguage::De => DE_WORDS,
+        TextLanguage::Fr => FR_WORDS,
+        TextLanguage::Es => ES_WORDS,
+        TextLanguage::Ja => JA_WORDS,
+        TextLanguage::El => EL_WORDS,
+    };
+    let text = Text { language, words };
+    let content = match semantic_type {
+        SemanticType::Title => capitalize(&text.words(rng, 3, 8)),
+        SemanticType::Description => text.sentences(rng, 2, 4),
+        SemanticType::Bio => text.sentences(rng, 1, 3),
+        SemanticType::Paragraph => {
+            let count = rng.random_range(1..3);
+            (0..count)
+                .map(|_| text.sentences(rng, 3, 6))
+                .collect::<Vec<_>>()
+                .join("\n\n")
+        }
+        SemanticType::Sentence => text.sentence(rng, 5, 12),
+        SemanticType::HtmlContent => {
+            let count = rng.random_range(2..5);
+            let sentences: Vec<String> = (0..count).map(|_| text.sentence(rng, 4, 10)).collect();
+            format!("<p>{}</p>", sentences.join("</p><p>"))
+        }
+        SemanticType::MarkdownContent => {
+            let heading = capitalize(&text.words(rng, 3, 6));
+            format!("# {}\n\n{}", heading, text.sentences(rng, 2, 4))
+        }
+        _ => return None,
+    };
+    Some(Value::String(content.into()))
+}
+
+struct Text {
+    language: TextLanguage,
+    words: &'static [&'static str],
+}
+
+impl Text {
+    /// Japanese is written without spaces between words.
+    fn separator(&self) -> &'static str {
+        match self.language {
+            TextLanguage::Ja => "",
+            _ => " ",
+        }
+    }
+
+    fn words(&self, rng: &mut impl Rng, min: usize, max: usize) -> String {
+        let count = rng.random_range(min..max);
+        (0..count)
+            .map(|_| self.words[rng.random_range(0..self.words.len())])
+            .collect::<Vec<_>>()
+            .join(self.separator())
+    }
+
+    fn sentence(&self, rng: &mut impl Rng, min: usize, max: usize) -> String {
+        let words = self.words(rng, min, max);
+        match self.language {
+            TextLanguage::Ja => format!("{}。", words),
+            _ => format!("{}.", capitalize(&words)),
+        }
+    }
+
+    fn sentences(&self, rng: &mut impl Rng, min: usize, max: usize) -> String {
+        let count = rng.random_range(min..max);
+        (0..count)
+            .map(|_| self.sentence(rng, 4, 10))
+            .collect::<Vec<_>>()
+            .join(self.separator())
+    }
+}
+
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        None => String::new(),
+        Some(c) => c.to_uppercase().collect::<String>() + chars.as_str(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn mix(entries: &[(&str, f64)]) -> Result<LanguageMix, String> {
+        let weights = entries
+            .iter()
+            .map(|(code, w)| (code.to_string(), *w))
+            .collect();
+        LanguageMix::from_weights(&weights)
+    }
+
+    #[test]
+    fn test_mix_picks_languages_by_weight() {
+        let mix = mix(&[("en", 0.9), ("de", 0.05), ("ja", 0.05)]).unwrap();
+        let mut rng = StdRng::seed_from_u64(7);
+        let picks: Vec<_> = (0..2000).map(|_| mix.pick(&mut rng)).collect();
+        let share = |lang| picks.iter().filter(|&&l| l == lang).count() as f64 / 2000.0;
+        assert!((share(TextLanguage::En) - 0.9).abs() < 0.03);
+        assert!(share(TextLanguage::De) > 0.02);
+        assert!(share(TextLanguage::Ja) > 0.02);
+        assert_eq!(share(TextLanguage::Fr), 0.0);
+    }
+
+    #[test]
+    fn test_mix_rejects_invalid_weights() {
+        assert!(mix(&[("xx", 1.0)]).unwrap_err().contains("Unsupported"));
+        assert!(mix(&[("en", -1.0)]).is_err());
+        assert!(mix(&[("en", 0.0)]).is_err());
+    }
+
+    #[test]
+    fn test_generate_text_in_language() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let ja = generate_text(SemanticType::Sentence, TextLanguage::Ja, &mut rng).unwrap();
+        let ja = ja.as_string().unwrap();
+        assert!(ja.ends_with('。') && !ja.contains(' '), "{}", ja);
+
+        let de = generate_text(SemanticType::Title, TextLanguage::De, &mut rng).unwrap();
+        let de = de.as_string().unwrap();
+        assert!(de
+            .split(' ')
+            .all(|w| DE_WORDS.iter().any(|d| *d == w || capitalize(d) == w)));
+
+        assert!(generate_text(SemanticType::Title, TextLanguage::En, &mut rng).is_none());
+        assert!(generate_text(SemanticType::Email, TextLanguage::De, &mut rng).is_none());
+    }
+}
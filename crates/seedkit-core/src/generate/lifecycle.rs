@@ -0,0 +1,210 @@
+//! # Status Lifecycles
+//!
+//! A status column often walks a fixed path, and the row's timestamps record
+//! how far it got. Declaring the path keeps the two consistent:
+//!
+//! ```toml
+//! [columns."orders.status".lifecycle]
+//! states = ["pending", "processing", "shipped", "delivered"]
+//! weights = [0.1, 0.2, 0.3, 0.4]   # share of rows in each state
+//! timestamps = { shipped = "shipped_at", delivered = "delivered_at" }
+//! ```
+//!
+//! `timestamps` defaults to the `<state>_at` columns the table has. A row's
+//! status is picked by weight (uniformly without `weights`), and the
+//! timestamps of states it hasn't reached are NULL; those it has reached
+//! are put in path order. NOT NULL timestamp columns keep their value.
+
+use std::collections::BTreeMap;
+
+use chrono::NaiveDateTime;
+use indexmap::IndexMap;
+use serde::Deserialize;
+
+use crate::generate::value::Value;
+
+/// A status column's `lifecycle` settings from `seedkit.toml`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Lifecycle {
+    pub states: Vec<String>,
+    pub weights: Option<Vec<f64>>,
+    /// State → the column holding when the row reached it.
+    pub timestamps: Option<BTreeMap<String, String>>,
+}
+
+impl Lifecycle {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.states.is_empty() {
+            return Err("needs at least one state".to_string());
+        }
+        if let Some(ref weights) = self.weights {
+            if weights.len() != self.states.len() {
+                return Err(format!(
+                    "{} weights for {} states",
+                    weights.len(),
+                    self.states.len()
+                ));
+            }
+            if weights.iter().any(|w| !w.is_finite() || *w < 0.0)
+                || weights.iter().sum::<f64>() <= 0.0
+            {
+                return Err("weights must be non-negative with a positive sum".to_string());
+            }
+        }
+        if let Some(state) = self
+            .timestamps
+            .iter()
+            .flatten()
+            .map(|(state, _)| state)
+            .find(|state| !self.states.contains(state))
+        {
+            return Err(format!("timestamps names unknown state '{}'", state));
+        }
+        Ok(())
+    }
+
+    /// (state index, column) of each timestamp column, in path order:
+    /// the configured ones, or the `<state>_at` columns that exist.
+    pub fn timestamp_columns(&self, column_names: &[&str]) -> Vec<(usize, String)> {
+        self.states
+            .iter()
+            .enumerate()
+            .filter_map(|(index, state)| {
+                let column = match self.timestamps {
+                    Some(ref timestamps) => timestamps.get(state)?.clone(),
+                    None => format!("{}_at", state),
+                };
+                column_names
+                    .contains(&column.as_str())
+                    .then_some((index, column))
+            })
+            .collect()
+    }
+}
+
+/// Null the timestamps of states `row` hasn't reached and order the rest
+/// along the path. `nullable` tells which columns may be NULL.
+pub fn apply_lifecycle(
+    status_column: &str,
+    states: &[String],
+    timestamps: &[(usize, String)],
+    row: &mut IndexMap<String, Value>,
+    nullable: impl Fn(&str) -> bool,
+) {
+    let Some(reached) = row.get(status_column).and_then(|status| {
+        states
+            .iter()
+            .position(|s| status.as_string() == Some(s.as_str()))
+    }) else {
+        return;
+    };
+
+    let mut kept: Vec<&String> = Vec::new();
+    for (index, column) in timestamps {
+        if *index > reached && nullable(column) {
+            if let Some(value) = row.get_mut(column) {
+                *value = Value::Null;
+            }
+        } else if matches!(row.get(column), Some(Value::Timestamp(_))) {
+            kept.push(column);
+        }
+    }
+
+    let mut times: Vec<NaiveDateTime> = kept
+        .iter()
+        .filter_map(|column| match row.get(*column) {
+            Some(Value::Timestamp(t)) => Some(*t),
+            _ => None,
+        })
+        .collect();
+    times.sort();
+    for (column, time) in kept.into_iter().zip(times) {
+        row.insert(column.clone(), Value::Timestamp(time));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lifecycle(toml: &str) -> Lifecycle {
+        toml::from_str(toml).unwrap()
+    }
+
+    #[test]
+    fn test_validate_lifecycle() {
+        let lc = lifecycle(
+            r#"
+states = ["pending", "shipped", "delivered"]
+weights = [0.2, 0.3, 0.5]
+"#,
+        );
+        assert!(lc.validate().is_ok());
+        let columns = ["status", "shipped_at", "delivered_at", "created_at"];
+        assert_eq!(
+            lc.timestamp_columns(&columns),
+            vec![
+                (1, "shipped_at".to_string()),
+                (2, "delivered_at".to_string())
+            ]
+        );
+
+        for bad in [
+            "states = []",
+            "states = [\"a\", \"b\"]\nweights = [1.0]",
+            "states = [\"a\", \"b\"]\nweights = [0.0, 0.0]",
+            "states = [\"a\"]\ntimestamps = { b = \"b_at\" }",
+        ] {
+            assert!(lifecycle(bad).validate().is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_apply_lifecycle() {
+        let states: Vec<String> = ["pending", "shipped", "delivered"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let timestamps = vec![
+            (1, "shipped_at".to_string()),
+            (2, "delivered_at".to_string()),
+        ];
+        let day = |d: u32| {
+            chrono::NaiveDate::from_ymd_opt(2025, 3, d)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap()
+        };
+        let row = |status: &str| {
+            IndexMap::from([
+                (
+                    "status".to_string(),
+                    Value::String(status.to_string().into()),
+                ),
+                ("shipped_at".to_string(), Value::Timestamp(day(9))),
+                ("delivered_at".to_string(), Value::Timestamp(day(4))),
+            ])
+        };
+
+        let mut pending = row("pending");
+        apply_lifecycle("status", &states, &timestamps, &mut pending, |_| true);
+        assert_eq!(pending["shipped_at"], Value::Null);
+        assert_eq!(pending["delivered_at"], Value::Null);
+
+        let mut shipped = row("shipped");
+        apply_lifecycle("status", &states, &timestamps, &mut shipped, |_| true);
+        assert_eq!(shipped["shipped_at"], Value::Timestamp(day(9)));
+        assert_eq!(shipped["delivered_at"], Value::Null);
+
+        let mut delivered = row("delivered");
+        apply_lifecycle("status", &states, &timestamps, &mut delivered, |_| true);
+        assert_eq!(delivered["shipped_at"], Value::Timestamp(day(4)));
+        assert_eq!(delivered["delivered_at"], Value::Timestamp(day(9)));
+
+        let mut required = row("pending");
+        apply_lifecycle("status", &states, &timestamps, &mut required, |c| {
+            c != "delivered_at"
+        });
+        assert_eq!(required["delivered_at"], Value::Timestamp(day(4)));
+    }
+}
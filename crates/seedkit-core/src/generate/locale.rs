@@ -0,0 +1,623 @@
+//! # Name Locales
+//!
+//! Locale-specific name pools for the person identity group, plus the
+//! transliteration that keeps emails and usernames ASCII when names are
+//! written in another script: Japanese readings go kana → romaji (passport
+//! Hepburn) and Greek goes to Latin (ELOT 743).
+//!
+//! Japanese names are stored as `(kanji, kana reading)` pairs, since kanji
+//! cannot be romanized without knowing the reading. German and French names
+//! come from `fake` and fold their diacritics (`Müller` → `mueller`).
+//!
+//! A [`LocaleMix`] such as `en=0.6,de=0.3,ja=0.1` picks a locale per row, so
+//! one dataset mixes scripts and diacritics.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Locale used for generated person names.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Locale {
+    #[default]
+    En,
+    De,
+    Fr,
+    Ja,
+    El,
+}
+
+impl FromStr for Locale {
+    type Err = String;
+
+    /// Accepts a language code with an optional region (`ja`, `ja_JP`, `el-GR`).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lang = s.split(['_', '-']).next().unwrap_or_default();
+        match lang.to_ascii_lowercase().as_str() {
+            "en" => Ok(Locale::En),
+            "de" => Ok(Locale::De),
+            "fr" => Ok(Locale::Fr),
+            "ja" => Ok(Locale::Ja),
+            "el" => Ok(Locale::El),
+            _ => Err(format!(
+                "Unsupported locale '{}'. Supported locales: en, de, fr, ja, el.",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Locale::En => write!(f, "en"),
+            Locale::De => write!(f, "de"),
+            Locale::Fr => write!(f, "fr"),
+            Locale::Ja => write!(f, "ja"),
+            Locale::El => write!(f, "el"),
+        }
+    }
+}
+
+/// Weighted choice of name locale, e.g. `en=0.6,de=0.3,ja=0.1`. A single
+/// locale (`ja`) is a mix of one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocaleMix {
+    /// Locales with positive relative weights, in parse order.
+    weights: Vec<(Locale, f64)>,
+}
+
+impl Default for LocaleMix {
+    fn default() -> Self {
+        Self::single(Locale::default())
+    }
+}
+
+impl From<Locale> for LocaleMix {
+    fn from(locale: Locale) -> Self {
+        Self::single(locale)
+    }
+}
+
+impl LocaleMix {
+    pub fn single(locale: Locale) -> Self {
+        Self {
+            weights: vec![(locale, 1.0)],
+        }
+    }
+
+    /// Build a mix from locale codes and weights. Weights are relative and
+    /// need not sum to 1.
+    pub fn from_weights(weights: &BTreeMap<String, f64>) -> Result<Self, String> {
+        Self::from_pairs(weights.iter().map(|(code, &w)| (code.as_str(), w)))
+    }
+
+    fn from_pairs<'a>(pairs: impl Iterator<Item = (&'a str, f64)>) -> Result<Self, String> {
+        let mut parsed = Vec::new();
+        for (code, weight) in pairs {
+            let locale = code.parse::<Locale>()?;
+            if !weight.is_finite() || weight < 0.0 {
+                return Err(format!(
+                    "Weight for '{}' must be a non-negative number, got {}.",
+                    code, weight
+                ));
+            }
+            parsed.push((locale, weight));
+        }
+        let total: f64 = parsed.iter().map(|(_, w)| w).sum();
+        if total <= 0.0 {
+            return Err("At least one locale needs a positive weight.".to_string());
+        }
+        let weights = parsed.into_iter().filter(|(_, w)| *w > 0.0).collect();
+        Ok(Self { weights })
+    }
+
+    /// The locale when the mix has only one.
+    pub fn as_single(&self) -> Option<Locale> {
+        match self.weights.as_slice() {
+            [(locale, _)] => Some(*locale),
+            _ => None,
+        }
+    }
+
+    /// Pick a locale. Draws once from `rng`.
+    pub fn pick(&self, rng: &mut impl Rng) -> Locale {
+        let total: f64 = self.weights.iter().map(|(_, w)| w).sum();
+        let roll = rng.random::<f64>() * total;
+        let mut bound = 0.0;
+        for (locale, weight) in &self.weights {
+            bound += weight;
+            if roll < bound {
+                return *locale;
+            }
+        }
+        self.weights.last().map(|(l, _)| *l).unwrap_or_default()
+    }
+}
+
+impl FromStr for LocaleMix {
+    type Err = String;
+
+    /// Accepts one locale (`ja_JP`) or weighted locales
+    /// (`en=0.6,de=0.3,ja=0.1`).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if !s.contains('=') {
+            return s.parse().map(Self::single);
+        }
+        let mut pairs = Vec::new();
+        for entry in s.split(',') {
+            let (code, weight) = entry
+                .split_once('=')
+                .ok_or_else(|| format!("Expected locale=weight, got '{}'.", entry))?;
+            let weight = weight
+                .trim()
+                .parse::<f64>()
+                .map_err(|_| format!("Invalid weight '{}' for locale '{}'.", weight, code))?;
+            pairs.push((code.trim(), weight));
+        }
+        Self::from_pairs(pairs.into_iter())
+    }
+}
+
+impl fmt::Display for LocaleMix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(locale) = self.as_single() {
+            return write!(f, "{}", locale);
+        }
+        let entries: Vec<String> = self
+            .weights
+            .iter()
+            .map(|(locale, weight)| format!("{}={}", locale, weight))
+            .collect();
+        write!(f, "{}", entries.join(","))
+    }
+}
+
+/// Common Japanese given names as (written form, reading).
+pub(crate) static JA_FIRST_NAMES: &[(&str, &str)] = &[
+    ("翔太", "しょうた"),
+    ("健太", "けんた"),
+    ("大輔", "だいすけ"),
+    ("拓海", "たくみ"),
+    ("蓮", "れん"),
+    ("悠斗", "ゆうと"),
+    ("陽翔", "はると"),
+    ("湊", "みなと"),
+    ("一平", "いっぺい"),
+    ("駿", "しゅん"),
+    ("涼", "りょう"),
+    ("潤", "じゅん"),
+    ("直樹", "なおき"),
+    ("さくら", "さくら"),
+    ("陽菜", "ひな"),
+    ("結衣", "ゆい"),
+    ("美咲", "みさき"),
+    ("葵", "あおい"),
+    ("京子", "きょうこ"),
+    ("千尋", "ちひろ"),
+    ("優子", "ゆうこ"),
+    ("美月", "みつき"),
+];
+
+/// Common Japanese family names as (written form, reading).
+pub(crate) static JA_LAST_NAMES: &[(&str, &str)] = &[
+    ("佐藤", "さとう"),
+    ("鈴木", "すずき"),
+    ("高橋", "たかはし"),
+    ("田中", "たなか"),
+    ("伊藤", "いとう"),
+    ("渡辺", "わたなべ"),
+    ("山本", "やまもと"),
+    ("中村", "なかむら"),
+    ("小林", "こばやし"),
+    ("加藤", "かとう"),
+    ("吉田", "よしだ"),
+    ("山田", "やまだ"),
+    ("佐々木", "ささき"),
+    ("松本", "まつもと"),
+    ("井上", "いのうえ"),
+    ("清水", "しみず"),
+    ("山口", "やまぐち"),
+    ("大野", "おおの"),
+];
+
+/// Common Greek given names.
+pub(crate) static EL_FIRST_NAMES: &[&str] = &[
+    "Γιώργος",
+    "Κωνσταντίνος",
+    "Δημήτρης",
+    "Νίκος",
+    "Γιάννης",
+    "Παναγιώτης",
+    "Χρήστος",
+    "Βασίλης",
+    "Θανάσης",
+    "Μιχάλης",
+    "Μαρία",
+    "Ελένη",
+    "Αικατερίνη",
+    "Βασιλική",
+    "Σοφία",
+    "Αγγελική",
+    "Γεωργία",
+    "Δήμητρα",
+    "Ευαγγελία",
+    "Χριστίνα",
+];
+
+/// Greek family names in the genitive form shared by men and women.
+pub(crate) static EL_LAST_NAMES: &[&str] = &[
+    "Γεωργίου",
+    "Οικονόμου",
+    "Νικολάου",
+    "Βασιλείου",
+    "Κωνσταντίνου",
+    "Αντωνίου",
+    "Ιωάννου",
+    "Δημητρίου",
+    "Αθανασίου",
+    "Παπανικολάου",
+    "Χριστοδούλου",
+    "Αλεξίου",
+];
+
+/// Romanize hiragana or katakana using passport Hepburn: `しょうた` →
+/// `shota`, `いっぺい` → `ippei`. Long vowels collapse and the prolonged
+/// sound mark is dropped. Other characters pass through.
+pub fn transliterate_kana(text: &str) -> String {
+    // Katakana shares the hiragana layout, offset by 0x60
+    let hiragana: Vec<char> = text
+        .chars()
+        .map(|c| match c {
+            '\u{30A1}'..='\u{30F6}' => char::from_u32(c as u32 - 0x60).unwrap_or(c),
+            _ => c,
+        })
+        .collect();
+
+    let mut out = String::new();
+    let mut double_next = false;
+    let mut i = 0;
+    while i < hiragana.len() {
+        let c = hiragana[i];
+        i += 1;
+        if c == 'っ' {
+            double_next = true;
+            continue;
+        }
+        if c == 'ー' {
+            continue;
+        }
+        let Some(mut syllable) = kana_romaji(c).map(str::to_string) else {
+            out.push(c);
+            continue;
+        };
+        // Yōon: a small ゃ/ゅ/ょ merges into the preceding i-row syllable
+        if let Some(vowel) = hiragana.get(i).and_then(|&n| small_y_vowel(n)) {
+            if let Some(stem) = syllable.strip_suffix('i') {
+                syllable = if stem.ends_with("sh") || stem.ends_with("ch") || stem.ends_with('j') {
+                    format!("{}{}", stem, vowel)
+                } else {
+                    format!("{}y{}", stem, vowel)
+                };
+                i += 1;
+            }
+        }
+        if double_next {
+            // Sokuon doubles the next consonant; before ch Hepburn writes t
+            if syllable.starts_with("ch") {
+                out.push('t');
+            } else if let Some(first) = syllable.chars().next().filter(|c| !"aiueo".contains(*c)) {
+                out.push(first);
+            }
+            double_next = false;
+        }
+        out.push_str(&syllable);
+    }
+
+    collapse_long_vowels(&out)
+}
+
+/// Drop the second vowel of `ou`, `uu` and `oo` unless it starts the next
+/// syllable (`inoue` keeps its `u`, `satou` becomes `sato`).
+fn collapse_long_vowels(romaji: &str) -> String {
+    let chars: Vec<char> = romaji.chars().collect();
+    let is_vowel = |c: Option<&char>| c.is_some_and(|c| "aiueo".contains(*c));
+    chars
+        .iter()
+        .enumerate()
+        .filter(|&(i, &c)| {
+            let long = i > 0
+                && matches!((chars[i - 1], c), ('o', 'u') | ('u', 'u') | ('o', 'o'))
+                && !is_vowel(chars.get(i + 1));
+            !long
+        })
+        .map(|(_, &c)| c)
+        .collect()
+}
+
+fn small_y_vowel(c: char) -> Option<&'static str> {
+    match c {
+        'ゃ' => Some("a"),
+        'ゅ' => Some("u"),
+        'ょ' => Some("o"),
+        _ => None,
+    }
+}
+
+fn kana_romaji(c: char) -> Option<&'static str> {
+    Some(match c {
+        'あ' | 'ぁ' => "a",
+        'い' | 'ぃ' => "i",
+        'う' | 'ぅ' => "u",
+        'え' | 'ぇ' => "e",
+        'お' | 'ぉ' => "o",
+        'か' => "ka",
+        'き' => "ki",
+        'く' => "ku",
+        'け' => "ke",
+        'こ' => "ko",
+        'が' => "ga",
+        'ぎ' => "gi",
+        'ぐ' => "gu",
+        'げ' => "ge",
+        'ご' => "go",
+        'さ' => "sa",
+        'し' => "shi",
+        'す' => "su",
+        'せ' => "se",
+        'そ' => "so",
+        'ざ' => "za",
+        'じ' | 'ぢ' => "ji",
+        'ず' | 'づ' => "zu",
+        'ぜ' => "ze",
+        'ぞ' => "zo",
+        'た' => "ta",
+        'ち' => "chi",
+        'つ' => "tsu",
+        'て' => "te",
+        'と' => "to",
+        'だ' => "da",
+        'で' => "de",
+        'ど' => "do",
+        'な' => "na",
+        'に' => "ni",
+        'ぬ' => "nu",
+        'ね' => "ne",
+        'の' => "no",
+        'は' => "ha",
+        'ひ' => "hi",
+        'ふ' => "fu",
+        'へ' => "he",
+        'ほ' => "ho",
+        'ば' => "ba",
+        'び' => "bi",
+        'ぶ' => "bu",
+        'べ' => "be",
+        'ぼ' => "bo",
+        'ぱ' => "pa",
+        'ぴ' => "pi",
+        'ぷ' => "pu",
+        'ぺ' => "pe",
+        'ぽ' => "po",
+        'ま' => "ma",
+        'み' => "mi",
+        'む' => "mu",
+        'め' => "me",
+        'も' => "mo",
+        'や' | 'ゃ' => "ya",
+        'ゆ' | 'ゅ' => "yu",
+        'よ' | 'ょ' => "yo",
+        'ら' => "ra",
+        'り' => "ri",
+        'る' => "ru",
+        'れ' => "re",
+        'ろ' => "ro",
+        'わ' => "wa",
+        'を' => "o",
+        'ん' => "n",
+        'ゔ' => "vu",
+        _ => return None,
+    })
+}
+
+/// Romanize Greek using ELOT 743: `Γιώργος` → `giorgos`, `Ευαγγελία` →
+/// `evangelia`, `Μπαλτά` → `balta`. Output is lowercase; accents are
+/// dropped and a diaeresis keeps vowels apart (`ϊ` never joins a digraph).
+pub fn transliterate_greek(text: &str) -> String {
+    // (base letter, can join a preceding vowel into a digraph)
+    let letters: Vec<(char, bool)> = text
+        .to_lowercase()
+        .chars()
+        .map(|c| match c {
+            'ά' => ('α', true),
+            'έ' => ('ε', true),
+            'ή' => ('η', true),
+            'ί' => ('ι', true),
+            'ό' => ('ο', true),
+            'ύ' => ('υ', true),
+            'ώ' => ('ω', true),
+            'ϊ' | 'ΐ' => ('ι', false),
+            'ϋ' | 'ΰ' => ('υ', false),
+            other => (other, true),
+        })
+        .collect();
+
+    let mut out = String::new();
+    let mut i = 0;
+    while i < letters.len() {
+        let (c, _) = letters[i];
+        let next = letters
+            .get(i + 1)
+            .filter(|(_, joins)| *joins)
+            .map(|(n, _)| *n);
+        let word_start = i == 0 || !letters[i - 1].0.is_alphabetic();
+        let digraph = match (c, next) {
+            ('ο', Some('υ')) => Some("ou"),
+            ('α', Some('υ')) => Some("av"),
+            ('ε', Some('υ')) => Some("ev"),
+            ('γ', Some('γ')) => Some("ng"),
+            ('μ', Some('π')) if word_start => Some("b"),
+            ('ν', Some('τ')) if word_start => Some("d"),
+            _ => None,
+        };
+        if let Some(latin) = digraph {
+            out.push_str(latin);
+            i += 2;
+            continue;
+        }
+        match greek_latin(c) {
+            Some(latin) => out.push_str(latin),
+            None => out.push(c),
+        }
+        i += 1;
+    }
+    out
+}
+
+fn greek_latin(c: char) -> Option<&'static str> {
+    Some(match c {
+        'α' => "a",
+        'β' => "v",
+        'γ' => "g",
+        'δ' => "d",
+        'ε' => "e",
+        'ζ' => "z",
+        'η' => "i",
+        'θ' => "th",
+        'ι' => "i",
+        'κ' => "k",
+        'λ' => "l",
+        'μ' => "m",
+        'ν' => "n",
+        'ξ' => "x",
+        'ο' => "o",
+        'π' => "p",
+        'ρ' => "r",
+        'σ' | 'ς' => "s",
+        'τ' => "t",
+        'υ' => "y",
+        'φ' => "f",
+        'χ' => "ch",
+        'ψ' => "ps",
+        'ω' => "o",
+        _ => return None,
+    })
+}
+
+/// Fold Latin diacritics to ASCII the way German and French names are
+/// usually written in email addresses: `Müller` → `mueller`, `Strauß` →
+/// `strauss`, `Hélène` → `helene`. Output is lowercase.
+pub fn transliterate_latin(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.to_lowercase().chars() {
+        match c {
+            'ä' | 'æ' => out.push_str("ae"),
+            'ö' | 'œ' => out.push_str("oe"),
+            'ü' => out.push_str("ue"),
+            'ß' => out.push_str("ss"),
+            'à' | 'á' | 'â' | 'ã' | 'å' => out.push('a'),
+            'ç' => out.push('c'),
+            'è' | 'é' | 'ê' | 'ë' => out.push('e'),
+            'ì' | 'í' | 'î' | 'ï' => out.push('i'),
+            'ñ' => out.push('n'),
+            'ò' | 'ó' | 'ô' | 'õ' | 'ø' => out.push('o'),
+            'ù' | 'ú' | 'û' => out.push('u'),
+            'ý' | 'ÿ' => out.push('y'),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Reduce `text` to the lowercase ASCII letters and digits allowed in an
+/// email local part or username.
+pub fn ascii_handle(text: &str) -> String {
+    text.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_transliterate_kana() {
+        assert_eq!(transliterate_kana("しょうた"), "shota");
+        assert_eq!(transliterate_kana("いっぺい"), "ippei");
+        assert_eq!(transliterate_kana("きょうこ"), "kyoko");
+        assert_eq!(transliterate_kana("じゅん"), "jun");
+        assert_eq!(transliterate_kana("おおの"), "ono");
+        assert_eq!(transliterate_kana("いのうえ"), "inoue");
+        assert_eq!(transliterate_kana("ササキ"), "sasaki");
+        assert_eq!(transliterate_kana("マッチャ"), "matcha");
+    }
+
+    #[test]
+    fn test_transliterate_greek() {
+        assert_eq!(transliterate_greek("Γιώργος"), "giorgos");
+        assert_eq!(transliterate_greek("Ευαγγελία"), "evangelia");
+        assert_eq!(transliterate_greek("Χριστοδούλου"), "christodoulou");
+        assert_eq!(transliterate_greek("Μπαλτά"), "balta");
+        assert_eq!(transliterate_greek("Ντούμα"), "douma");
+        assert_eq!(transliterate_greek("Θανάσης"), "thanasis");
+        // Diaeresis keeps the vowels apart
+        assert_eq!(transliterate_greek("Προϋπόθεση"), "proypothesi");
+    }
+
+    #[test]
+    fn test_name_pools_transliterate_to_ascii() {
+        for (_, reading) in JA_FIRST_NAMES.iter().chain(JA_LAST_NAMES) {
+            let latin = transliterate_kana(reading);
+            assert!(latin.chars().all(|c| c.is_ascii_lowercase()), "{}", latin);
+        }
+        for name in EL_FIRST_NAMES.iter().chain(EL_LAST_NAMES) {
+            let latin = transliterate_greek(name);
+            assert!(latin.chars().all(|c| c.is_ascii_lowercase()), "{}", latin);
+        }
+    }
+
+    #[test]
+    fn test_transliterate_latin() {
+        assert_eq!(transliterate_latin("Müller"), "mueller");
+        assert_eq!(transliterate_latin("Strauß"), "strauss");
+        assert_eq!(transliterate_latin("Hélène"), "helene");
+        assert_eq!(transliterate_latin("Françoise"), "francoise");
+    }
+
+    #[test]
+    fn test_locale_mix_parses_and_picks_by_weight() {
+        let mix: LocaleMix = "en=0.6, de=0.3, ja=0.1".parse().unwrap();
+        assert_eq!(mix.to_string().parse::<LocaleMix>(), Ok(mix.clone()));
+        assert_eq!(mix.as_single(), None);
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(5);
+        let picks: Vec<Locale> = (0..2000).map(|_| mix.pick(&mut rng)).collect();
+        let share = |l| picks.iter().filter(|&&p| p == l).count() as f64 / 2000.0;
+        assert!((share(Locale::En) - 0.6).abs() < 0.04);
+        assert!((share(Locale::De) - 0.3).abs() < 0.04);
+        assert!(share(Locale::El) == 0.0);
+
+        assert_eq!(
+            "ja".parse::<LocaleMix>().unwrap().as_single(),
+            Some(Locale::Ja)
+        );
+        assert_eq!(LocaleMix::default().to_string(), "en");
+        assert!("en=0.5,xx=0.5".parse::<LocaleMix>().is_err());
+        assert!("en=0,de=0".parse::<LocaleMix>().is_err());
+        assert!("en=lots".parse::<LocaleMix>().is_err());
+    }
+
+    #[test]
+    fn test_locale_from_str() {
+        assert_eq!("ja_JP".parse::<Locale>(), Ok(Locale::Ja));
+        assert_eq!("el-GR".parse::<Locale>(), Ok(Locale::El));
+        assert_eq!("EN".parse::<Locale>(), Ok(Locale::En));
+        assert!("tlh".parse::<Locale>().is_err());
+    }
+}
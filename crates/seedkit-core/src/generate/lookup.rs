@@ -0,0 +1,430 @@
+//! # Lookup Tables
+//!
+//! Small reference tables (countries, currencies, languages, statuses) hold
+//! a fixed, well-known set of rows. Filling them with faker words gives child
+//! tables foreign keys like `country_code = 'xq'`, so they are detected by
+//! name and shape and filled from curated datasets instead: one row per
+//! entry, with each column taking the matching field (ISO code, name,
+//! symbol) so a row's code and name always agree.
+//!
+//! Detection can be overridden per table in `seedkit.toml`:
+//!
+//! ```toml
+//! [tables.regions]
+//! lookup = "countries"
+//!
+//! [tables.order_statuses]
+//! lookup = "none"
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::schema::types::{Column, Table};
+
+/// Tables with more columns than this are treated as entities, not lookups.
+const MAX_LOOKUP_COLUMNS: usize = 6;
+
+/// A curated dataset a lookup table can be filled from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LookupDataset {
+    Countries,
+    Currencies,
+    Languages,
+    Statuses,
+}
+
+impl FromStr for LookupDataset {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "countries" => Ok(LookupDataset::Countries),
+            "currencies" => Ok(LookupDataset::Currencies),
+            "languages" => Ok(LookupDataset::Languages),
+            "statuses" => Ok(LookupDataset::Statuses),
+            _ => Err(format!(
+                "Unknown lookup dataset '{}'. Supported: countries, currencies, languages, statuses, none.",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for LookupDataset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LookupDataset::Countries => write!(f, "countries"),
+            LookupDataset::Currencies => write!(f, "currencies"),
+            LookupDataset::Languages => write!(f, "languages"),
+            LookupDataset::Statuses => write!(f, "statuses"),
+        }
+    }
+}
+
+/// Parse a `lookup = "..."` config value. `"none"` disables detection and
+/// yields `Ok(None)`.
+pub fn parse_lookup_override(s: &str) -> Result<Option<LookupDataset>, String> {
+    if s.eq_ignore_ascii_case("none") {
+        Ok(None)
+    } else {
+        s.parse().map(Some)
+    }
+}
+
+/// Countries as (ISO 3166-1 alpha-2, alpha-3, name).
+static COUNTRIES: &[(&str, &str, &str)] = &[
+    ("US", "USA", "United States"),
+    ("CA", "CAN", "Canada"),
+    ("MX", "MEX", "Mexico"),
+    ("BR", "BRA", "Brazil"),
+    ("AR", "ARG", "Argentina"),
+    ("CL", "CHL", "Chile"),
+    ("CO", "COL", "Colombia"),
+    ("PE", "PER", "Peru"),
+    ("GB", "GBR", "United Kingdom"),
+    ("IE", "IRL", "Ireland"),
+    ("FR", "FRA", "France"),
+    ("DE", "DEU", "Germany"),
+    ("NL", "NLD", "Netherlands"),
+    ("BE", "BEL", "Belgium"),
+    ("CH", "CHE", "Switzerland"),
+    ("AT", "AUT", "Austria"),
+    ("ES", "ESP", "Spain"),
+    ("PT", "PRT", "Portugal"),
+    ("IT", "ITA", "Italy"),
+    ("GR", "GRC", "Greece"),
+    ("SE", "SWE", "Sweden"),
+    ("NO", "NOR", "Norway"),
+    ("DK", "DNK", "Denmark"),
+    ("FI", "FIN", "Finland"),
+    ("PL", "POL", "Poland"),
+    ("CZ", "CZE", "Czechia"),
+    ("HU", "HUN", "Hungary"),
+    ("RO", "ROU", "Romania"),
+    ("UA", "UKR", "Ukraine"),
+    ("TR", "TUR", "Turkey"),
+    ("IL", "ISR", "Israel"),
+    ("AE", "ARE", "United Arab Emirates"),
+    ("SA", "SAU", "Saudi Arabia"),
+    ("EG", "EGY", "Egypt"),
+    ("NG", "NGA", "Nigeria"),
+    ("KE", "KEN", "Kenya"),
+    ("GH", "GHA", "Ghana"),
+    ("ZA", "ZAF", "South Africa"),
+    ("MA", "MAR", "Morocco"),
+    ("IN", "IND", "India"),
+    ("PK", "PAK", "Pakistan"),
+    ("BD", "BGD", "Bangladesh"),
+    ("CN", "CHN", "China"),
+    ("JP", "JPN", "Japan"),
+    ("KR", "KOR", "South Korea"),
+    ("TW", "TWN", "Taiwan"),
+    ("HK", "HKG", "Hong Kong"),
+    ("SG", "SGP", "Singapore"),
+    ("MY", "MYS", "Malaysia"),
+    ("TH", "THA", "Thailand"),
+    ("VN", "VNM", "Vietnam"),
+    ("PH", "PHL", "Philippines"),
+    ("ID", "IDN", "Indonesia"),
+    ("AU", "AUS", "Australia"),
+    ("NZ", "NZL", "New Zealand"),
+];
+
+/// Currencies as (ISO 4217 code, name, symbol).
+static CURRENCIES: &[(&str, &str, &str)] = &[
+    ("USD", "US Dollar", "$"),
+    ("EUR", "Euro", "€"),
+    ("GBP", "Pound Sterling", "£"),
+    ("JPY", "Japanese Yen", "¥"),
+    ("CNY", "Chinese Yuan", "¥"),
+    ("CHF", "Swiss Franc", "CHF"),
+    ("CAD", "Canadian Dollar", "$"),
+    ("AUD", "Australian Dollar", "$"),
+    ("NZD", "New Zealand Dollar", "$"),
+    ("SEK", "Swedish Krona", "kr"),
+    ("NOK", "Norwegian Krone", "kr"),
+    ("DKK", "Danish Krone", "kr"),
+    ("PLN", "Polish Zloty", "zł"),
+    ("CZK", "Czech Koruna", "Kč"),
+    ("HUF", "Hungarian Forint", "Ft"),
+    ("TRY", "Turkish Lira", "₺"),
+    ("INR", "Indian Rupee", "₹"),
+    ("KRW", "South Korean Won", "₩"),
+    ("SGD", "Singapore Dollar", "$"),
+    ("HKD", "Hong Kong Dollar", "$"),
+    ("BRL", "Brazilian Real", "R$"),
+    ("MXN", "Mexican Peso", "$"),
+    ("ZAR", "South African Rand", "R"),
+    ("NGN", "Nigerian Naira", "₦"),
+    ("AED", "UAE Dirham", "د.إ"),
+    ("ILS", "Israeli New Shekel", "₪"),
+];
+
+/// Languages as (ISO 639-1 code, English name, native name).
+static LANGUAGES: &[(&str, &str, &str)] = &[
+    ("en", "English", "English"),
+    ("es", "Spanish", "Español"),
+    ("fr", "French", "Français"),
+    ("de", "German", "Deutsch"),
+    ("it", "Italian", "Italiano"),
+    ("pt", "Portuguese", "Português"),
+    ("nl", "Dutch", "Nederlands"),
+    ("sv", "Swedish", "Svenska"),
+    ("pl", "Polish", "Polski"),
+    ("el", "Greek", "Ελληνικά"),
+    ("tr", "Turkish", "Türkçe"),
+    ("ru", "Russian", "Русский"),
+    ("uk", "Ukrainian", "Українська"),
+    ("ar", "Arabic", "العربية"),
+    ("he", "Hebrew", "עברית"),
+    ("hi", "Hindi", "हिन्दी"),
+    ("bn", "Bengali", "বাংলা"),
+    ("zh", "Chinese", "中文"),
+    ("ja", "Japanese", "日本語"),
+    ("ko", "Korean", "한국어"),
+    ("vi", "Vietnamese", "Tiếng Việt"),
+    ("th", "Thai", "ไทย"),
+    ("id", "Indonesian", "Bahasa Indonesia"),
+    ("sw", "Swahili", "Kiswahili"),
+];
+
+/// Statuses as (code, label, description).
+static STATUSES: &[(&str, &str, &str)] = &[
+    ("active", "Active", "In use"),
+    ("pending", "Pending", "Awaiting review"),
+    ("inactive", "Inactive", "Temporarily disabled"),
+    ("suspended", "Suspended", "Blocked pending investigation"),
+    ("archived", "Archived", "Kept for history only"),
+];
+
+/// Which field of a dataset entry a column takes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Code,
+    /// Countries: the alpha-3 code.
+    AltCode,
+    Name,
+    /// Currencies: symbol. Languages: native name. Statuses: description.
+    Extra,
+}
+
+impl LookupDataset {
+    fn entries(&self) -> &'static [(&'static str, &'static str, &'static str)] {
+        match self {
+            LookupDataset::Countries => COUNTRIES,
+            LookupDataset::Currencies => CURRENCIES,
+            LookupDataset::Languages => LANGUAGES,
+            LookupDataset::Statuses => STATUSES,
+        }
+    }
+
+    /// Number of rows the dataset fills.
+    pub fn len(&self) -> usize {
+        self.entries().len()
+    }
+
+    /// Datasets are never empty; provided for clippy's `len_without_is_empty`.
+    pub fn is_empty(&self) -> bool {
+        self.entries().is_empty()
+    }
+
+    /// The value of `column` for every dataset row, in row order, or `None`
+    /// if the column doesn't correspond to a field or a value would not fit
+    /// its length limit.
+    pub fn column_values(&self, column: &Column) -> Option<Vec<String>> {
+        if !column.data_type.is_string() {
+            return None;
+        }
+        let field = self.field_for(&column.name.to_lowercase(), column.max_length)?;
+        let values: Vec<String> = self
+            .entries()
+            .iter()
+            .map(|&(code, alt_or_name, last)| {
+                let value = match (self, field) {
+                    (LookupDataset::Countries, Field::AltCode) => alt_or_name,
+                    (LookupDataset::Countries, Field::Name) => last,
+                    (_, Field::Name) => alt_or_name,
+                    (_, Field::Extra) => last,
+                    (_, _) => code,
+                };
+                value.to_string()
+            })
+            .collect();
+        let fits = column
+            .max_length
+            .is_none_or(|max| values.iter().all(|v| v.chars().count() <= max as usize));
+        fits.then_some(values)
+    }
+
+    fn field_for(&self, column: &str, max_length: Option<u32>) -> Option<Field> {
+        const NAME_COLUMNS: &[&str] = &["name", "title", "label", "display_name"];
+        const CODE_COLUMNS: &[&str] = &["code", "iso", "iso_code", "key", "slug"];
+
+        let code = CODE_COLUMNS.contains(&column);
+        let name = NAME_COLUMNS.contains(&column);
+        match self {
+            LookupDataset::Countries => match column {
+                "alpha3" | "iso3" | "code3" | "iso_alpha3" => Some(Field::AltCode),
+                _ if code && max_length == Some(3) => Some(Field::AltCode),
+                "alpha2" | "iso2" | "iso_alpha2" | "country_code" => Some(Field::Code),
+                "country" | "country_name" => Some(Field::Name),
+                _ if code => Some(Field::Code),
+                _ if name => Some(Field::Name),
+                _ => None,
+            },
+            LookupDataset::Currencies => match column {
+                "currency" | "currency_code" => Some(Field::Code),
+                "currency_name" => Some(Field::Name),
+                "symbol" | "sign" => Some(Field::Extra),
+                _ if code => Some(Field::Code),
+                _ if name => Some(Field::Name),
+                _ => None,
+            },
+            LookupDataset::Languages => match column {
+                "language_code" | "lang" | "locale" => Some(Field::Code),
+                "language" | "language_name" => Some(Field::Name),
+                "native_name" | "native" | "endonym" => Some(Field::Extra),
+                _ if code => Some(Field::Code),
+                _ if name => Some(Field::Name),
+                _ => None,
+            },
+            LookupDataset::Statuses => match column {
+                "status" | "value" => Some(Field::Code),
+                "description" => Some(Field::Extra),
+                _ if code => Some(Field::Code),
+                _ if name => Some(Field::Name),
+                _ => None,
+            },
+        }
+    }
+}
+
+/// Detect whether `table` is a lookup table and which dataset fills it.
+///
+/// The last word of the table name picks the dataset (`countries`,
+/// `ref_currency`, `order_statuses`); the table must also be small, have no
+/// foreign keys of its own, and have at least one column the dataset fills.
+pub fn detect_lookup_table(table: &Table) -> Option<LookupDataset> {
+    let name = table.name.to_lowercase();
+    let last_word = name.rsplit('_').next().unwrap_or_default();
+    let dataset = match last_word {
+        "country" | "countries" => LookupDataset::Countries,
+        "currency" | "currencies" => LookupDataset::Currencies,
+        "language" | "languages" => LookupDataset::Languages,
+        "status" | "statuses" => LookupDataset::Statuses,
+        _ => return None,
+    };
+
+    let is_small = table.columns.len() <= MAX_LOOKUP_COLUMNS && table.foreign_keys.is_empty();
+    let has_field = table
+        .columns
+        .values()
+        .any(|c| dataset.column_values(c).is_some());
+    (is_small && has_field).then_some(dataset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::types::*;
+
+    fn lookup_table(name: &str, columns: &[(&str, Option<u32>)]) -> Table {
+        let mut table = Table::new(name.to_string());
+        let mut id = Column::new("id".to_string(), DataType::Serial, "serial".to_string());
+        id.is_auto_increment = true;
+        table.columns.insert("id".to_string(), id);
+        for &(col, max_length) in columns {
+            let mut column = Column::new(col.to_string(), DataType::VarChar, "varchar".to_string());
+            column.max_length = max_length;
+            table.columns.insert(col.to_string(), column);
+        }
+        table
+    }
+
+    #[test]
+    fn test_detect_lookup_table_by_name_and_shape() {
+        let countries = lookup_table("countries", &[("code", Some(2)), ("name", None)]);
+        assert_eq!(
+            detect_lookup_table(&countries),
+            Some(LookupDataset::Countries)
+        );
+
+        let statuses = lookup_table("order_statuses", &[("name", Some(50))]);
+        assert_eq!(
+            detect_lookup_table(&statuses),
+            Some(LookupDataset::Statuses)
+        );
+
+        // No column the dataset can fill
+        let currencies = lookup_table("currencies", &[("rate", None)]);
+        assert_eq!(detect_lookup_table(&currencies), None);
+
+        // Too wide to be a lookup table
+        let wide = lookup_table(
+            "countries",
+            &[
+                ("code", None),
+                ("name", None),
+                ("a", None),
+                ("b", None),
+                ("c", None),
+                ("d", None),
+            ],
+        );
+        assert_eq!(detect_lookup_table(&wide), None);
+
+        let users = lookup_table("users", &[("name", None)]);
+        assert_eq!(detect_lookup_table(&users), None);
+    }
+
+    #[test]
+    fn test_column_values_keep_rows_consistent() {
+        let table = lookup_table(
+            "countries",
+            &[("code", Some(2)), ("iso3", None), ("name", None)],
+        );
+        let dataset = LookupDataset::Countries;
+        let codes = dataset.column_values(&table.columns["code"]).unwrap();
+        let alpha3 = dataset.column_values(&table.columns["iso3"]).unwrap();
+        let names = dataset.column_values(&table.columns["name"]).unwrap();
+        assert_eq!(codes.len(), dataset.len());
+        let jp = codes.iter().position(|c| c == "JP").unwrap();
+        assert_eq!(alpha3[jp], "JPN");
+        assert_eq!(names[jp], "Japan");
+
+        // A char(3) code column takes the alpha-3 code
+        let char3 = lookup_table("countries", &[("code", Some(3))]);
+        let codes = dataset.column_values(&char3.columns["code"]).unwrap();
+        assert!(codes.iter().all(|c| c.len() == 3));
+
+        // Values that don't fit the column are not used
+        let short = lookup_table("countries", &[("name", Some(5))]);
+        assert!(dataset.column_values(&short.columns["name"]).is_none());
+    }
+
+    #[test]
+    fn test_dataset_codes_are_unique() {
+        for dataset in [
+            LookupDataset::Countries,
+            LookupDataset::Currencies,
+            LookupDataset::Languages,
+            LookupDataset::Statuses,
+        ] {
+            let mut codes: Vec<_> = dataset.entries().iter().map(|e| e.0).collect();
+            codes.sort_unstable();
+            codes.dedup();
+            assert_eq!(codes.len(), dataset.len(), "duplicate code in {}", dataset);
+        }
+    }
+
+    #[test]
+    fn test_parse_lookup_override() {
+        assert_eq!(
+            parse_lookup_override("Currencies"),
+            Ok(Some(LookupDataset::Currencies))
+        );
+        assert_eq!(parse_lookup_override("none"), Ok(None));
+        assert!(parse_lookup_override("planets").is_err());
+    }
+}
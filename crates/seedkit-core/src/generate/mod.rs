@@ -1,8 +1,32 @@
+pub mod aggregate;
+pub mod append;
+pub mod copula;
 pub mod correlated;
+pub mod cross_column;
 pub mod custom;
 pub mod engine;
+pub mod expression;
 pub mod foreign_key;
+pub mod funnel;
+pub mod json_schema;
+pub mod json_shape;
+pub mod language;
+pub mod lifecycle;
+pub mod locale;
+pub mod lookup;
+pub mod pattern;
 pub mod plan;
+pub mod polymorphic;
+pub mod process;
 pub mod providers;
+pub mod remap;
+pub mod script;
+pub mod seed;
+pub mod segment;
+pub mod sequence;
+pub mod template;
+pub mod temporal_profile;
+pub mod text_profile;
+pub mod time_series;
 pub mod unique;
 pub mod value;
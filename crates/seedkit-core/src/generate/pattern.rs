@@ -0,0 +1,176 @@
+//! # Pattern Generator
+//!
+//! Codes with a fixed shape — ICD-10 codes, flight numbers, license plates —
+//! are easiest to describe by the regular expression their values match:
+//!
+//! ```toml
+//! [columns."flights.number"]
+//! pattern = "[A-Z]{2}[0-9]{3,4}"
+//!
+//! [types.icd10_code]
+//! pattern = "[A-TV-Z][0-9]{2}(\\.[0-9]{1,2})?"
+//! ```
+//!
+//! Values are generated from the parsed expression: alternatives and
+//! repetition counts are picked at random, and each class yields one of its
+//! characters. Anchors and other assertions are ignored, unbounded
+//! repetitions (`*`, `+`, `{n,}`) stop [`MAX_UNBOUNDED_REPEAT`] past their
+//! minimum, and classes that include printable ASCII (`.`, `\w`) only yield
+//! those characters.
+
+use rand::Rng;
+use regex_syntax::hir::{Class, Hir, HirKind};
+
+/// Extra repetitions an unbounded quantifier may generate.
+pub const MAX_UNBOUNDED_REPEAT: u32 = 8;
+
+/// Printable ASCII, preferred within wide classes.
+const PRINTABLE: (u32, u32) = (0x20, 0x7e);
+
+/// A parsed pattern values are generated from.
+#[derive(Debug, Clone)]
+pub struct Pattern {
+    source: String,
+    hir: Hir,
+}
+
+impl Pattern {
+    /// Parse a regular expression.
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let hir = regex_syntax::parse(source)
+            .map_err(|e| format!("invalid pattern '{}': {}", source, e))?;
+        Ok(Self {
+            source: source.to_string(),
+            hir,
+        })
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Generate a string matching the pattern.
+    pub fn generate(&self, rng: &mut impl Rng) -> String {
+        let mut out = String::new();
+        generate_into(&self.hir, rng, &mut out);
+        out
+    }
+}
+
+fn generate_into(hir: &Hir, rng: &mut impl Rng, out: &mut String) {
+    match hir.kind() {
+        HirKind::Empty | HirKind::Look(_) => {}
+        HirKind::Literal(literal) => out.push_str(&String::from_utf8_lossy(&literal.0)),
+        HirKind::Class(Class::Unicode(class)) => {
+            let ranges: Vec<(u32, u32)> = class
+                .ranges()
+                .iter()
+                .map(|r| (r.start() as u32, r.end() as u32))
+                .collect();
+            if let Some(c) = pick_in_ranges(&ranges, rng).and_then(char::from_u32) {
+                out.push(c);
+            }
+        }
+        HirKind::Class(Class::Bytes(class)) => {
+            let ranges: Vec<(u32, u32)> = class
+                .ranges()
+                .iter()
+                .map(|r| (r.start() as u32, r.end() as u32))
+                .collect();
+            if let Some(c) = pick_in_ranges(&ranges, rng).and_then(char::from_u32) {
+                out.push(c);
+            }
+        }
+        HirKind::Repetition(repetition) => {
+            let max = repetition
+                .max
+                .unwrap_or(repetition.min.saturating_add(MAX_UNBOUNDED_REPEAT));
+            for _ in 0..rng.random_range(repetition.min..=max.max(repetition.min)) {
+                generate_into(&repetition.sub, rng, out);
+            }
+        }
+        HirKind::Capture(capture) => generate_into(&capture.sub, rng, out),
+        HirKind::Concat(parts) => {
+            for part in parts {
+                generate_into(part, rng, out);
+            }
+        }
+        HirKind::Alternation(alternatives) => {
+            if !alternatives.is_empty() {
+                generate_into(
+                    &alternatives[rng.random_range(0..alternatives.len())],
+                    rng,
+                    out,
+                );
+            }
+        }
+    }
+}
+
+/// Pick a code point from inclusive ranges, uniformly over the printable
+/// ASCII among them when there is any.
+fn pick_in_ranges(ranges: &[(u32, u32)], rng: &mut impl Rng) -> Option<u32> {
+    let printable: Vec<(u32, u32)> = ranges
+        .iter()
+        .filter_map(|&(start, end)| {
+            let (start, end) = (start.max(PRINTABLE.0), end.min(PRINTABLE.1));
+            (start <= end).then_some((start, end))
+        })
+        .collect();
+    let ranges = if printable.is_empty() {
+        ranges
+    } else {
+        &printable
+    };
+    let total: u64 = ranges.iter().map(|(s, e)| (e - s) as u64 + 1).sum();
+    if total == 0 {
+        return None;
+    }
+    let mut roll = rng.random_range(0..total);
+    for &(start, end) in ranges {
+        let width = (end - start) as u64 + 1;
+        if roll < width {
+            return Some(start + roll as u32);
+        }
+        roll -= width;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_generated_values_match_pattern() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        for source in [
+            r"[A-TV-Z][0-9]{2}(\.[0-9]{1,2})?",
+            r"[A-Z]{2}\d{3,4}",
+            r"^(LH|BA|UA)-\w+$",
+            r"INV-\d{6}",
+            r"v\d+\.\d+",
+            r".{5}",
+        ] {
+            let pattern = Pattern::parse(source).unwrap();
+            let check = regex::Regex::new(&format!("^(?:{})$", source)).unwrap();
+            for _ in 0..200 {
+                let value = pattern.generate(&mut rng);
+                assert!(check.is_match(&value), "{} doesn't match {}", value, source);
+                assert!(value.chars().all(|c| (' '..='~').contains(&c)), "{}", value);
+            }
+        }
+    }
+
+    #[test]
+    fn test_unbounded_repetition_is_capped() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+        let pattern = Pattern::parse("a+").unwrap();
+        for _ in 0..100 {
+            let len = pattern.generate(&mut rng).len() as u32;
+            assert!((1..=1 + MAX_UNBOUNDED_REPEAT).contains(&len));
+        }
+        assert!(Pattern::parse("[A-Z").is_err());
+    }
+}
@@ -1,10 +1,32 @@
 use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use indexmap::IndexMap;
 
 use crate::classify::semantic::{CorrelationGroup, SemanticType};
 use crate::config::ColumnConfig;
+use crate::generate::aggregate::{parse_aggregate, Aggregate};
+use crate::generate::copula::CopulaPlan;
+use crate::generate::correlated::lifecycle_rank;
+use crate::generate::foreign_key::{fan_out_shares, FanOut};
+use crate::generate::funnel::{Funnel, FunnelPlan, EVENT_COLUMN_NAMES};
+use crate::generate::language::LanguageMix;
+use crate::generate::locale::LocaleMix;
+use crate::generate::lookup::{detect_lookup_table, LookupDataset};
+use crate::generate::pattern::Pattern;
+use crate::generate::polymorphic::{PolymorphicAssociation, PolymorphicTarget};
+use crate::generate::providers::{
+    default_range_span, DEFAULT_BBOX, DEFAULT_BINARY_SIZE, DEFAULT_INTERVAL_SPAN,
+};
+use crate::generate::seed::{DefaultSeedDerivation, SeedDerivation};
+use crate::generate::segment::{Segment, SegmentPlan};
+use crate::generate::template::{parse_template, resolve_columns, TemplatePart};
+use crate::generate::time_series::{TimeSeries, TimeSeriesPlan};
+use crate::generate::value::Value;
 use crate::graph::topo::DeferredEdge;
-use crate::sample::stats::{ColumnDistribution, DistributionProfile};
-use crate::schema::types::{DatabaseSchema, ParsedCheck};
+use crate::sample::stats::{ColumnDistribution, DistributionProfile, TemporalProfile, TextProfile};
+use crate::schema::types::{Column, DataType, DatabaseSchema, DatabaseType, ParsedCheck};
 
 /// The complete generation plan for all tables.
 #[derive(Debug, Clone)]
@@ -22,7 +44,8 @@ pub struct GenerationPlan {
     /// so regeneration from a lockfile produces identical timestamps
     /// regardless of when it runs.
     pub base_time: chrono::NaiveDateTime,
-    /// Offset added to synthesized auto-increment IDs.
+    /// Offset added to synthesized auto-increment IDs, for tables without
+    /// their own `TableGenerationPlan::sequence_offset`.
     ///
     /// When seeding into an empty database with reset sequences, this is 0
     /// (the default). When seeding into a database where sequences already
@@ -32,6 +55,13 @@ pub struct GenerationPlan {
     /// For truly safe seeding into non-empty databases, prefer the
     /// `INSERT ... RETURNING id` pipeline in direct.rs instead.
     pub sequence_offset: u64,
+    /// How RNG seeds are derived from `seed`.
+    pub seed_derivation: Arc<dyn SeedDerivation>,
+    /// Give every table its own seed-derived RNG stream, so changing one
+    /// table's row count or overrides only changes that table and the
+    /// tables referencing it. Off replays lock files written before
+    /// per-table streams, where all tables but stable ones shared one RNG.
+    pub table_streams: bool,
 }
 
 /// Generation plan for a single table.
@@ -41,6 +71,31 @@ pub struct TableGenerationPlan {
     pub row_count: usize,
     pub column_plans: Vec<ColumnGenerationPlan>,
     pub correlation_groups: Vec<CorrelationGroupPlan>,
+    /// Stable tables draw from their own seed-derived RNG even without
+    /// `GenerationPlan::table_streams`, so their rows
+    /// don't shift when other tables change size.
+    pub stable: bool,
+    /// Rows replayed verbatim instead of generated (a stable table restored
+    /// from `seedkit.lock`).
+    pub pinned_rows: Option<Vec<IndexMap<String, Value>>>,
+    /// Last ID already used by this table's auto-increment key, detected
+    /// from the target database (see `generate::sequence`). Overrides the
+    /// plan-wide `sequence_offset`.
+    pub sequence_offset: Option<u64>,
+    /// Languages for this table's free-text content, picked per row.
+    pub language_mix: Option<LanguageMix>,
+    /// Rhai script run over every generated row (see `generate::script`).
+    pub row_script: Option<String>,
+    /// Lay the rows out as time series (see `generate::time_series`).
+    pub time_series: Option<TimeSeriesPlan>,
+    /// Generate the rows as funnel journeys (see `generate::funnel`).
+    pub funnel: Option<FunnelPlan>,
+    /// Groups of rows with their own column values and share of the rows
+    /// referencing them (see `generate::segment`).
+    pub segments: Vec<SegmentPlan>,
+    /// Sampled numeric columns drawn together so their correlations hold
+    /// (see `generate::copula`).
+    pub copula: Option<CopulaPlan>,
 }
 
 /// Generation plan for a single column.
@@ -54,6 +109,22 @@ pub struct ColumnGenerationPlan {
     pub null_probability: f64,
     /// Check constraints that apply to this column.
     pub check_constraints: Vec<ParsedCheck>,
+    /// Declared character limit (e.g. `varchar(20)`), if any.
+    pub max_length: Option<u32>,
+    /// Sampled lengths and character classes the semantic provider's
+    /// strings are fitted to (see `generate::text_profile`).
+    pub text_profile: Option<TextProfile>,
+    /// Sampled share of distinct values among the column's non-NULL
+    /// values, when they repeat; the semantic provider then reuses values
+    /// to match (see `DistributionProfile::distinct_ratio`).
+    pub distinct_ratio: Option<f64>,
+    /// Sampled hours, days of the week and months the semantic provider's
+    /// timestamps are moved to (see `generate::temporal_profile`).
+    pub temporal_profile: Option<TemporalProfile>,
+    /// Sampled share of the referenced rows with each number of children,
+    /// which this foreign key's rows are allocated to match (see
+    /// `ForeignKeyPool::allocate_sampled`).
+    pub sampled_fan_out: Option<Vec<(u64, f64)>>,
 }
 
 /// How a column's value should be generated.
@@ -65,19 +136,72 @@ pub enum GenerationStrategy {
     ForeignKeyReference {
         referenced_table: String,
         referenced_column: String,
+        fan_out: FanOut,
     },
     /// Use the semantic type's provider to generate a value.
     SemanticProvider,
     /// Pick from a fixed list of enum values.
     EnumValue { values: Vec<String> },
+    /// Pick any subset of a MySQL `SET` column's labels.
+    SetValue { values: Vec<String> },
+    /// Take the row's entry from a curated lookup dataset (row `i` gets
+    /// `values[i]`), so every column of a row describes the same entry.
+    Lookup { values: Vec<String> },
     /// Generate as part of a correlation group — handled by the group.
     Correlated { group_index: usize },
+    /// Type column of a polymorphic association. Picks the row's target
+    /// and, with it, the value of `id_column` (see `generate::polymorphic`).
+    PolymorphicType {
+        id_column: String,
+        targets: Vec<PolymorphicTarget>,
+    },
+    /// ID column of a polymorphic association, set with its type column.
+    PolymorphicId { type_column: String },
     /// Deferred — insert NULL now, UPDATE later (for cycle-breaking).
     Deferred,
     /// Skip entirely (e.g., generated columns, columns with server defaults).
     Skip,
     /// Custom provider (JS or WASM).
     Custom { provider_path: String },
+    /// External provider process speaking JSON over stdin/stdout, run in
+    /// `current_dir` (the directory holding seedkit.toml).
+    Process {
+        command: Vec<String>,
+        current_dir: Option<PathBuf>,
+    },
+    /// Render a template from the row's other columns and generators.
+    Template { parts: Vec<TemplatePart> },
+    /// Generate strings matching a regular expression (see
+    /// `generate::pattern`).
+    Pattern { pattern: Pattern },
+    /// Pick a status along a lifecycle path by weight, then null the
+    /// timestamps (state index, column) of states not yet reached (see
+    /// `generate::lifecycle`).
+    Lifecycle {
+        states: Vec<String>,
+        weights: Option<Vec<f64>>,
+        timestamps: Vec<(usize, String)>,
+    },
+    /// Evaluate an expression over the row's other columns (see
+    /// `generate::expression`).
+    Expression { source: String },
+    /// Aggregate over child rows, joined through `child_column` (the child
+    /// table's FK) to this table's `parent_column`. Filled in once all
+    /// tables are generated (see `generate::aggregate`).
+    Aggregate {
+        aggregate: Aggregate,
+        child_column: String,
+        parent_column: String,
+        /// Round to a whole number for integer columns.
+        integer: bool,
+        /// Decimal places otherwise.
+        scale: u32,
+    },
+    /// Generate JSON documents valid against a JSON Schema (see
+    /// `generate::json_schema`).
+    JsonSchema { schema: serde_json::Value },
+    /// Pick from texts written by the LLM provider (see `llm::content`).
+    LlmContent { texts: Vec<String> },
     /// Pick from a user-configured value list (from seedkit.toml).
     /// Distinct from EnumValue which comes from database schema enum types.
     ValueList {
@@ -86,6 +210,285 @@ pub enum GenerationStrategy {
     },
     /// Generate from a sampled production distribution profile.
     Distribution { distribution: ColumnDistribution },
+    /// Build a composite (row) value field by field.
+    Composite {
+        type_name: String,
+        fields: Vec<CompositeFieldPlan>,
+    },
+    /// Build an array of `min_len..=max_len` elements per dimension.
+    /// The element plan reuses the composite field shape (its `name` is the
+    /// column name).
+    Array {
+        element: CompositeFieldPlan,
+        dimensions: u32,
+        min_len: usize,
+        max_len: usize,
+    },
+    /// Build a non-empty `[lower, upper)` range over `subtype`, with a span
+    /// of `min_span..=max_span` (see `ColumnConfig::range_span` for units).
+    Range {
+        subtype: DataType,
+        min_span: i64,
+        max_span: i64,
+    },
+    /// Generate an interval of `min_secs..=max_secs` seconds, narrowed by the
+    /// column's CHECK constraints.
+    Interval { min_secs: i64, max_secs: i64 },
+    /// Generate `min_len..=max_len` random bytes.
+    Binary { min_len: usize, max_len: usize },
+    /// Generate an EWKT geometry of `shape` inside `bbox`. With `anchor`
+    /// (latitude column, longitude column), the geometry is centered on the
+    /// row's coordinates.
+    Spatial {
+        shape: String,
+        srid: u32,
+        bbox: (f64, f64, f64, f64),
+        anchor: Option<(String, String)>,
+    },
+}
+
+/// Default element count range for array columns.
+pub const DEFAULT_ARRAY_LENGTH: (usize, usize) = (1, 5);
+
+/// Fit a table's row count to the children-per-parent bounds of its
+/// foreign keys, given the parents' planned row counts.
+fn fit_fan_out_bounds(
+    table_name: &str,
+    row_count: usize,
+    column_plans: &[ColumnGenerationPlan],
+    parent_plans: &[TableGenerationPlan],
+) -> usize {
+    let mut fitted = row_count;
+    for col_plan in column_plans {
+        let GenerationStrategy::ForeignKeyReference {
+            ref referenced_table,
+            fan_out: FanOut::Children(min, max),
+            ..
+        } = col_plan.strategy
+        else {
+            continue;
+        };
+        let Some(parent) = parent_plans
+            .iter()
+            .find(|t| &t.table_name == referenced_table)
+        else {
+            continue;
+        };
+        let (lowest, highest) = (
+            parent.row_count.saturating_mul(min),
+            parent.row_count.saturating_mul(max),
+        );
+        let clamped = fitted.clamp(lowest, highest.max(lowest));
+        if clamped != fitted {
+            tracing::warn!(
+                "{}: {} rows don't fit {} children per {} row ({} rows); generating {}",
+                table_name,
+                fitted,
+                if min == max {
+                    min.to_string()
+                } else {
+                    format!("{}-{}", min, max)
+                },
+                referenced_table,
+                parent.row_count,
+                clamped
+            );
+            fitted = clamped;
+        }
+    }
+    fitted
+}
+
+/// Number of distinct values a strategy can produce, for those drawing
+/// from a fixed set.
+fn value_space(strategy: &GenerationStrategy) -> Option<usize> {
+    match strategy {
+        GenerationStrategy::EnumValue { values }
+        | GenerationStrategy::ValueList { values, .. }
+        | GenerationStrategy::Lookup { values } => Some(values.len()),
+        GenerationStrategy::LlmContent { texts } => Some(texts.len()),
+        GenerationStrategy::Distribution {
+            distribution: ColumnDistribution::Categorical { values },
+        } => Some(values.len()),
+        _ => None,
+    }
+}
+
+/// Warn about unique columns — declared, or unique in the sample — that
+/// draw from fewer values than the table has rows. Returns their names.
+fn check_unique_space(
+    table_name: &str,
+    row_count: usize,
+    column_plans: &[ColumnGenerationPlan],
+    unique_columns: &[&str],
+    sampled_unique: &[&str],
+) -> Vec<String> {
+    let mut infeasible = Vec::new();
+    for col_plan in column_plans {
+        let name = col_plan.column_name.as_str();
+        let declared = unique_columns.contains(&name);
+        if !declared && !sampled_unique.contains(&name) {
+            continue;
+        }
+        let Some(space) = value_space(&col_plan.strategy) else {
+            continue;
+        };
+        if space < row_count {
+            tracing::warn!(
+                "{}.{}: {} rows requested but the column {} and has only {} possible values",
+                table_name,
+                name,
+                row_count,
+                if declared {
+                    "is unique"
+                } else {
+                    "was unique in the sample"
+                },
+                space
+            );
+            infeasible.push(name.to_string());
+        }
+    }
+    infeasible
+}
+
+/// Resolve an aggregate column's join: the child table's single-column
+/// foreign key to `table_name`. `None`, with a warning, when there is none.
+fn plan_aggregate(
+    schema: &DatabaseSchema,
+    table_name: &str,
+    col_name: &str,
+    column: &Column,
+    aggregate: Aggregate,
+) -> Option<GenerationStrategy> {
+    let fk = schema.tables.get(&aggregate.table).and_then(|child| {
+        child
+            .foreign_keys
+            .iter()
+            .find(|fk| fk.referenced_table == table_name && fk.source_columns.len() == 1)
+    });
+    let Some(fk) = fk else {
+        tracing::warn!(
+            "{}.{}: aggregate over {}, which has no foreign key to {}; generating values instead",
+            table_name,
+            col_name,
+            aggregate.table,
+            table_name
+        );
+        return None;
+    };
+    Some(GenerationStrategy::Aggregate {
+        child_column: fk.source_columns[0].clone(),
+        parent_column: fk.referenced_columns[0].clone(),
+        integer: matches!(
+            column.data_type,
+            DataType::SmallInt | DataType::Integer | DataType::BigInt
+        ),
+        scale: column.numeric_scale.unwrap_or(2),
+        aggregate,
+    })
+}
+
+/// Find the table's latitude and longitude columns, if it has both.
+fn lat_lng_columns(
+    table_name: &str,
+    table: &crate::schema::types::Table,
+    classifications: &BTreeMap<(String, String), SemanticType>,
+) -> Option<(String, String)> {
+    let find = |wanted: SemanticType| {
+        table.columns.keys().find(|col| {
+            classifications.get(&(table_name.to_string(), (*col).clone())) == Some(&wanted)
+        })
+    };
+    match (find(SemanticType::Latitude), find(SemanticType::Longitude)) {
+        (Some(lat), Some(lng)) => Some((lat.clone(), lng.clone())),
+        _ => None,
+    }
+}
+
+/// Generation plan for one field of a composite type.
+#[derive(Debug, Clone)]
+pub struct CompositeFieldPlan {
+    pub name: String,
+    pub semantic_type: SemanticType,
+    pub data_type: DataType,
+    pub enum_values: Option<Vec<String>>,
+    /// Sub-fields when this field is itself a composite type.
+    pub fields: Vec<CompositeFieldPlan>,
+}
+
+/// Plan the element of an array column. The element is classified with the
+/// column's own name, so `tags text[]` yields `Tag` elements.
+fn plan_array_element(
+    schema: &DatabaseSchema,
+    table_name: &str,
+    column_name: &str,
+    column: &crate::schema::types::Column,
+    element_type: &DataType,
+) -> CompositeFieldPlan {
+    let semantic_type = crate::classify::rules::classify_column(
+        column_name,
+        element_type,
+        table_name,
+        false,
+        false,
+        column.enum_values.as_deref(),
+    );
+    let fields = match element_type {
+        DataType::Composite(type_name) => plan_composite_fields(schema, type_name, 0),
+        _ => Vec::new(),
+    };
+    CompositeFieldPlan {
+        name: column_name.to_string(),
+        semantic_type,
+        data_type: element_type.clone(),
+        enum_values: column.enum_values.clone(),
+        fields,
+    }
+}
+
+/// Maximum nesting depth followed when planning composite-in-composite fields.
+const MAX_COMPOSITE_DEPTH: usize = 4;
+
+/// Plan the fields of a composite type, classifying each field by name with
+/// the type name as table context (so `address.city` is classified like a
+/// `city` column on an `address` table).
+fn plan_composite_fields(
+    schema: &DatabaseSchema,
+    type_name: &str,
+    depth: usize,
+) -> Vec<CompositeFieldPlan> {
+    let fields = match schema.composite_types.get(type_name) {
+        Some(f) if depth < MAX_COMPOSITE_DEPTH => f,
+        _ => return Vec::new(),
+    };
+
+    fields
+        .iter()
+        .map(|field| {
+            let semantic_type = crate::classify::rules::classify_column(
+                &field.name,
+                &field.data_type,
+                type_name,
+                false,
+                false,
+                field.enum_values.as_deref(),
+            );
+            let nested = match field.data_type {
+                DataType::Composite(ref nested_name) => {
+                    plan_composite_fields(schema, nested_name, depth + 1)
+                }
+                _ => Vec::new(),
+            };
+            CompositeFieldPlan {
+                name: field.name.clone(),
+                semantic_type,
+                data_type: field.data_type.clone(),
+                enum_values: field.enum_values.clone(),
+                fields: nested,
+            }
+        })
+        .collect()
 }
 
 /// Plan for generating correlated column values.
@@ -93,6 +496,8 @@ pub enum GenerationStrategy {
 pub struct CorrelationGroupPlan {
     pub group: CorrelationGroup,
     pub columns: Vec<(String, SemanticType)>,
+    /// Name locale(s); only the person identity group uses it.
+    pub locale: LocaleMix,
 }
 
 impl GenerationPlan {
@@ -136,24 +541,48 @@ impl GenerationPlan {
             })
             .collect();
 
+        let text_profile_lookup: HashMap<(&str, &str), &TextProfile> = distribution_profiles
+            .unwrap_or(&[])
+            .iter()
+            .flat_map(|p| {
+                p.text_profiles
+                    .iter()
+                    .map(move |(col, profile)| ((p.table_name.as_str(), col.as_str()), profile))
+            })
+            .collect();
+
+        let null_rate_lookup: HashMap<(&str, &str), f64> = distribution_profiles
+            .unwrap_or(&[])
+            .iter()
+            .flat_map(|p| {
+                p.null_rates
+                    .iter()
+                    .map(move |(col, rate)| ((p.table_name.as_str(), col.as_str()), *rate))
+            })
+            .collect();
+
+        let profile_lookup: HashMap<&str, &DistributionProfile> = distribution_profiles
+            .unwrap_or(&[])
+            .iter()
+            .map(|p| (p.table_name.as_str(), p))
+            .collect();
+
         // Build ratio lookup for adjusting row counts
         let ratio_lookup: HashMap<(&str, &str), f64> = distribution_profiles
             .unwrap_or(&[])
             .iter()
             .flat_map(|p| {
-                p.column_distributions
-                    .iter()
-                    .filter_map(move |(_col, dist)| {
-                        if let ColumnDistribution::Ratio {
-                            related_table,
-                            ratio,
-                        } = dist
-                        {
-                            Some(((p.table_name.as_str(), related_table.as_str()), *ratio))
-                        } else {
-                            None
-                        }
-                    })
+                p.column_distributions.values().filter_map(move |dist| {
+                    if let ColumnDistribution::Ratio {
+                        related_table,
+                        ratio,
+                    } = dist
+                    {
+                        Some(((p.table_name.as_str(), related_table.as_str()), *ratio))
+                    } else {
+                        None
+                    }
+                })
             })
             .collect();
 
@@ -191,14 +620,28 @@ impl GenerationPlan {
             // Group columns by their correlation group
             let mut group_map: BTreeMap<CorrelationGroup, Vec<(String, SemanticType)>> =
                 BTreeMap::new();
-            for (col_name, _column) in &table.columns {
-                if let Some(st) = classifications.get(&(table_name.clone(), col_name.clone())) {
-                    if let Some(group) = st.correlation_group() {
-                        group_map
-                            .entry(group)
-                            .or_default()
-                            .push((col_name.clone(), *st));
-                    }
+            for (col_name, column) in &table.columns {
+                let st = classifications
+                    .get(&(table_name.clone(), col_name.clone()))
+                    .copied();
+                if let Some((st, group)) = st.and_then(|st| Some((st, st.correlation_group()?))) {
+                    group_map
+                        .entry(group)
+                        .or_default()
+                        .push((col_name.clone(), st));
+                } else if matches!(st, None | Some(SemanticType::Timestamp))
+                    && matches!(
+                        column.data_type,
+                        DataType::Timestamp | DataType::TimestampTz
+                    )
+                    && lifecycle_rank(col_name).is_some()
+                {
+                    // `paid_at`, `shipped_at`, ... fall between created_at
+                    // and updated_at, in lifecycle order
+                    group_map
+                        .entry(CorrelationGroup::Temporal)
+                        .or_default()
+                        .push((col_name.clone(), SemanticType::Timestamp));
                 }
             }
 
@@ -208,7 +651,11 @@ impl GenerationPlan {
                     for (col_name, _) in &columns {
                         correlated_columns.insert(col_name.clone(), group_index);
                     }
-                    correlation_groups.push(CorrelationGroupPlan { group, columns });
+                    correlation_groups.push(CorrelationGroupPlan {
+                        group,
+                        columns,
+                        locale: LocaleMix::default(),
+                    });
                 }
             }
 
@@ -220,6 +667,26 @@ impl GenerationPlan {
                 .map(|pk| pk.columns.iter().map(|s| s.as_str()).collect())
                 .unwrap_or_default();
 
+            // Columns that must be unique, alone or with others
+            let unique_columns: Vec<&str> = table
+                .unique_constraints
+                .iter()
+                .filter(|uc| uc.columns.len() == 1)
+                .flat_map(|uc| &uc.columns)
+                .map(String::as_str)
+                .chain(pk_columns.iter().copied().filter(|_| pk_columns.len() == 1))
+                .collect();
+            let constrained_columns: Vec<&str> = table
+                .unique_constraints
+                .iter()
+                .flat_map(|uc| &uc.columns)
+                .map(String::as_str)
+                .chain(pk_columns.iter().copied())
+                .collect();
+            let profile = profile_lookup.get(table_name.as_str());
+            let mut sampled_unique: Vec<&str> = Vec::new();
+
+            let column_names: Vec<&str> = table.columns.keys().map(String::as_str).collect();
             for (col_name, column) in &table.columns {
                 let semantic_type = classifications
                     .get(&(table_name.clone(), col_name.clone()))
@@ -235,16 +702,64 @@ impl GenerationPlan {
                             values: values.clone(),
                             weights: cfg.weights.clone(),
                         })
+                        .or_else(|| {
+                            // Invalid patterns are rejected by config validation
+                            let pattern = Pattern::parse(cfg.pattern.as_ref()?).ok()?;
+                            Some(GenerationStrategy::Pattern { pattern })
+                        })
+                        .or_else(|| {
+                            let lifecycle = cfg.lifecycle.as_ref()?;
+                            Some(GenerationStrategy::Lifecycle {
+                                states: lifecycle.states.clone(),
+                                weights: lifecycle.weights.clone(),
+                                timestamps: lifecycle.timestamp_columns(&column_names),
+                            })
+                        })
                         .or_else(|| {
                             cfg.custom.as_ref().map(|path| GenerationStrategy::Custom {
                                 provider_path: path.clone(),
                             })
                         })
+                        .or_else(|| {
+                            cfg.command
+                                .as_ref()
+                                .map(|command| GenerationStrategy::Process {
+                                    command: command.clone(),
+                                    current_dir: cfg.command_dir.clone(),
+                                })
+                        })
+                        .or_else(|| {
+                            // Syntax errors are rejected by config validation
+                            let parts = parse_template(cfg.template.as_ref()?).ok()?;
+                            Some(GenerationStrategy::Template {
+                                parts: resolve_columns(parts, &column_names),
+                            })
+                        })
+                        .or_else(|| {
+                            cfg.expr
+                                .as_ref()
+                                .map(|source| GenerationStrategy::Expression {
+                                    source: source.clone(),
+                                })
+                        })
+                        .or_else(|| {
+                            let aggregate = parse_aggregate(cfg.aggregate.as_ref()?).ok()?;
+                            plan_aggregate(schema, table_name, col_name, column, aggregate)
+                        })
+                        .or_else(|| {
+                            // Unreadable schema files are rejected by config validation
+                            let schema = cfg.json_schema.as_ref()?.load(None).ok()?;
+                            Some(GenerationStrategy::JsonSchema { schema })
+                        })
                 });
 
                 // Determine generation strategy — config overrides take priority
                 let strategy = if let Some(s) = config_strategy {
                     s
+                } else if column.data_type == DataType::TsVector {
+                    // Left to the trigger or generated column that derives it
+                    // from the row's text columns.
+                    GenerationStrategy::Skip
                 } else if column.is_auto_increment || column.data_type.is_serial() {
                     if pk_columns.contains(&col_name.as_str()) {
                         GenerationStrategy::AutoIncrement
@@ -267,6 +782,10 @@ impl GenerationPlan {
                         GenerationStrategy::ForeignKeyReference {
                             referenced_table: fk.referenced_table.clone(),
                             referenced_column: fk.referenced_columns[0].clone(),
+                            fan_out: column_overrides
+                                .get(&col_key)
+                                .and_then(|cfg| cfg.fan_out)
+                                .unwrap_or_default(),
                         }
                     } else {
                         GenerationStrategy::SemanticProvider
@@ -277,6 +796,77 @@ impl GenerationPlan {
                     GenerationStrategy::Distribution {
                         distribution: (*dist).clone(),
                     }
+                } else if let DataType::Array(ref element_type) = column.data_type {
+                    let (min_len, max_len) = column_overrides
+                        .get(&col_key)
+                        .and_then(|cfg| cfg.array_length)
+                        .unwrap_or(DEFAULT_ARRAY_LENGTH);
+                    GenerationStrategy::Array {
+                        element: plan_array_element(
+                            schema,
+                            table_name,
+                            col_name,
+                            column,
+                            element_type,
+                        ),
+                        dimensions: column.array_dimensions.unwrap_or(1).max(1),
+                        min_len,
+                        max_len,
+                    }
+                } else if let DataType::Range(ref subtype) = column.data_type {
+                    let (min_span, max_span) = column_overrides
+                        .get(&col_key)
+                        .and_then(|cfg| cfg.range_span)
+                        .unwrap_or_else(|| default_range_span(subtype));
+                    GenerationStrategy::Range {
+                        subtype: (**subtype).clone(),
+                        min_span,
+                        max_span,
+                    }
+                } else if column.data_type == DataType::Interval {
+                    let (min_secs, max_secs) = column_overrides
+                        .get(&col_key)
+                        .and_then(|cfg| cfg.interval_span)
+                        .unwrap_or(DEFAULT_INTERVAL_SPAN);
+                    GenerationStrategy::Interval { min_secs, max_secs }
+                } else if column.data_type == DataType::Binary {
+                    let (min_len, max_len) = column_overrides
+                        .get(&col_key)
+                        .and_then(|cfg| cfg.binary_size)
+                        .unwrap_or(DEFAULT_BINARY_SIZE);
+                    // varbinary(n)/binary(n) report their byte limit as max_length
+                    let limit = column.max_length.map_or(usize::MAX, |n| n as usize);
+                    GenerationStrategy::Binary {
+                        min_len: min_len.min(limit),
+                        max_len: max_len.min(limit),
+                    }
+                } else if let DataType::Spatial {
+                    ref shape, srid, ..
+                } = column.data_type
+                {
+                    let cfg = column_overrides.get(&col_key);
+                    let anchor = if cfg.and_then(|c| c.follow_lat_lng).unwrap_or(true) {
+                        lat_lng_columns(table_name, table, classifications)
+                    } else {
+                        None
+                    };
+                    GenerationStrategy::Spatial {
+                        shape: shape.clone(),
+                        srid,
+                        bbox: cfg.and_then(|c| c.bbox).unwrap_or(DEFAULT_BBOX),
+                        anchor,
+                    }
+                } else if let DataType::Composite(ref type_name) = column.data_type {
+                    GenerationStrategy::Composite {
+                        type_name: type_name.clone(),
+                        fields: plan_composite_fields(schema, type_name, 0),
+                    }
+                } else if let (DataType::Set(_), Some(values)) =
+                    (&column.data_type, &column.enum_values)
+                {
+                    GenerationStrategy::SetValue {
+                        values: values.clone(),
+                    }
                 } else if let Some(ref values) = column.enum_values {
                     GenerationStrategy::EnumValue {
                         values: values.clone(),
@@ -288,30 +878,88 @@ impl GenerationPlan {
                 };
 
                 // Collect applicable check constraints
-                let check_constraints: Vec<ParsedCheck> = table
+                let mut check_constraints: Vec<ParsedCheck> = table
                     .check_constraints
                     .iter()
-                    .filter_map(|cc| {
-                        cc.parsed.as_ref().and_then(|p| {
-                            if check_applies_to_column(p, col_name) {
-                                Some(p.clone())
-                            } else {
-                                None
-                            }
-                        })
-                    })
+                    .filter_map(|cc| cc.parsed.as_ref())
+                    .flat_map(ParsedCheck::conjuncts)
+                    .filter(|p| check_applies_to_column(p, col_name))
+                    .cloned()
                     .collect();
+                check_constraints.extend(integer_range_checks(column, schema.database_type));
 
-                let null_probability =
-                    if column.nullable && !pk_columns.contains(&col_name.as_str()) {
-                        match semantic_type {
+                // Bounded fan-out allocates a parent to every row, and
+                // derived columns decide their own NULLs
+                let bounded = matches!(
+                    strategy,
+                    GenerationStrategy::ForeignKeyReference {
+                        fan_out: FanOut::Children(..),
+                        ..
+                    } | GenerationStrategy::Lifecycle { .. }
+                        | GenerationStrategy::Expression { .. }
+                        | GenerationStrategy::Aggregate { .. }
+                );
+                let null_probability = if bounded {
+                    0.0
+                } else if column.nullable && !pk_columns.contains(&col_name.as_str()) {
+                    // Replay the sampled NULL rate when there is one
+                    match null_rate_lookup.get(&(table_name.as_str(), col_name.as_str())) {
+                        Some(&rate) => rate.clamp(0.0, 1.0),
+                        None => match semantic_type {
                             SemanticType::DeletedAt => 0.8, // Most rows aren't soft-deleted
                             _ => 0.05, // Small chance of NULL for nullable columns
-                        }
-                    } else {
-                        0.0
-                    };
+                        },
+                    }
+                } else {
+                    0.0
+                };
 
+                let text_profile = match strategy {
+                    GenerationStrategy::SemanticProvider => text_profile_lookup
+                        .get(&(table_name.as_str(), col_name.as_str()))
+                        .map(|profile| (*profile).clone()),
+                    _ => None,
+                };
+                let temporal_profile = match strategy {
+                    GenerationStrategy::SemanticProvider => profile
+                        .and_then(|p| p.temporal_profiles.get(col_name))
+                        .cloned(),
+                    _ => None,
+                };
+                // A configured fan-out wins over the sampled one
+                let sampled_fan_out = match strategy {
+                    GenerationStrategy::ForeignKeyReference {
+                        ref referenced_table,
+                        ..
+                    } if column_overrides
+                        .get(&col_key)
+                        .and_then(|cfg| cfg.fan_out)
+                        .is_none() =>
+                    {
+                        profile
+                            .and_then(|p| p.fan_outs.get(col_name))
+                            .and_then(|counts| {
+                                let parent_rows = profile_lookup
+                                    .get(referenced_table.as_str())
+                                    .map(|p| p.row_count);
+                                fan_out_shares(counts, parent_rows)
+                            })
+                    }
+                    _ => None,
+                };
+                let sampled_ratio = profile.and_then(|p| p.distinct_ratio(col_name));
+                if sampled_ratio == Some(1.0) {
+                    sampled_unique.push(col_name.as_str());
+                }
+                // Values repeat only where no constraint forbids it
+                let distinct_ratio = match strategy {
+                    GenerationStrategy::SemanticProvider
+                        if !constrained_columns.contains(&col_name.as_str()) =>
+                    {
+                        sampled_ratio.filter(|ratio| *ratio < 1.0)
+                    }
+                    _ => None,
+                };
                 column_plans.push(ColumnGenerationPlan {
                     column_name: col_name.clone(),
                     semantic_type,
@@ -319,14 +967,69 @@ impl GenerationPlan {
                     nullable: column.nullable,
                     null_probability,
                     check_constraints,
+                    max_length: column.max_length,
+                    text_profile,
+                    distinct_ratio,
+                    temporal_profile,
+                    sampled_fan_out,
                 });
             }
 
+            // Lifecycle timestamps are NULL exactly when the state wasn't reached
+            let lifecycle_timestamps: Vec<String> = column_plans
+                .iter()
+                .filter_map(|c| match c.strategy {
+                    GenerationStrategy::Lifecycle { ref timestamps, .. } => Some(timestamps),
+                    _ => None,
+                })
+                .flatten()
+                .map(|(_, column)| column.clone())
+                .collect();
+            for col_plan in &mut column_plans {
+                if lifecycle_timestamps.contains(&col_plan.column_name) {
+                    col_plan.null_probability = 0.0;
+                }
+            }
+
+            // Sampled numeric columns keep their sampled correlations
+            let numeric_columns: Vec<&str> = column_plans
+                .iter()
+                .filter(|c| {
+                    matches!(
+                        c.strategy,
+                        GenerationStrategy::Distribution {
+                            distribution: ColumnDistribution::Numeric { .. }
+                                | ColumnDistribution::Histogram { .. }
+                        }
+                    )
+                })
+                .map(|c| c.column_name.as_str())
+                .collect();
+            let copula = profile.and_then(|p| CopulaPlan::new(&numeric_columns, &p.correlations));
+
+            let row_count = fit_fan_out_bounds(table_name, row_count, &column_plans, &table_plans);
+            check_unique_space(
+                table_name,
+                row_count,
+                &column_plans,
+                &unique_columns,
+                &sampled_unique,
+            );
+
             table_plans.push(TableGenerationPlan {
                 table_name: table_name.clone(),
                 row_count,
                 column_plans,
                 correlation_groups,
+                stable: false,
+                pinned_rows: None,
+                sequence_offset: None,
+                language_mix: None,
+                row_script: None,
+                time_series: None,
+                funnel: None,
+                segments: Vec::new(),
+                copula,
             });
         }
 
@@ -337,6 +1040,469 @@ impl GenerationPlan {
             default_row_count,
             base_time: base_time.unwrap_or_else(|| chrono::Utc::now().naive_utc()),
             sequence_offset: 0,
+            seed_derivation: Arc::new(DefaultSeedDerivation),
+            table_streams: true,
+        }
+    }
+
+    /// Generate person names (and the emails and usernames derived from
+    /// them) for `locale`, or for a weighted mix of locales picked per row.
+    pub fn set_locale(&mut self, locale: impl Into<LocaleMix>) {
+        let locale = locale.into();
+        for table_plan in &mut self.table_plans {
+            for group in &mut table_plan.correlation_groups {
+                if group.group == CorrelationGroup::PersonIdentity {
+                    group.locale = locale.clone();
+                }
+            }
+        }
+    }
+
+    /// Mark a table as stable. Returns `false` if it is not in the plan.
+    pub fn mark_stable(&mut self, table_name: &str) -> bool {
+        match self
+            .table_plans
+            .iter_mut()
+            .find(|t| t.table_name == table_name)
+        {
+            Some(table_plan) => {
+                table_plan.stable = true;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Replay `rows` for a stable table instead of generating it. The row
+    /// count follows the recorded rows, overriding any configured count.
+    pub fn pin_rows(&mut self, table_name: &str, rows: Vec<IndexMap<String, Value>>) -> bool {
+        match self
+            .table_plans
+            .iter_mut()
+            .find(|t| t.table_name == table_name)
+        {
+            Some(table_plan) => {
+                table_plan.stable = true;
+                table_plan.row_count = rows.len();
+                table_plan.pinned_rows = Some(rows);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Fill lookup tables (countries, currencies, ...) from curated datasets.
+    ///
+    /// Tables listed in `overrides` use the given dataset, or none; the rest
+    /// are detected by name and shape. A lookup table gets one row per
+    /// dataset entry, or its count from `row_overrides` when that is
+    /// smaller (more rows would repeat entries). Returns the tables
+    /// switched, with their dataset.
+    pub fn apply_lookup_tables(
+        &mut self,
+        schema: &DatabaseSchema,
+        overrides: &BTreeMap<String, Option<LookupDataset>>,
+        row_overrides: &BTreeMap<String, usize>,
+    ) -> Vec<(String, LookupDataset)> {
+        let mut applied = Vec::new();
+        for table_plan in &mut self.table_plans {
+            let Some(table) = schema.tables.get(&table_plan.table_name) else {
+                continue;
+            };
+            let dataset = match overrides.get(&table_plan.table_name) {
+                Some(dataset) => *dataset,
+                None => detect_lookup_table(table),
+            };
+            let Some(dataset) = dataset else {
+                continue;
+            };
+
+            let mut filled = false;
+            for col_plan in &mut table_plan.column_plans {
+                let values = table
+                    .columns
+                    .get(&col_plan.column_name)
+                    .and_then(|c| dataset.column_values(c));
+                if let Some(values) = values {
+                    col_plan.strategy = GenerationStrategy::Lookup { values };
+                    col_plan.null_probability = 0.0;
+                    filled = true;
+                }
+            }
+            if filled {
+                table_plan.row_count = match row_overrides.get(&table_plan.table_name) {
+                    Some(&rows) => rows.min(dataset.len()),
+                    None => dataset.len(),
+                };
+                applied.push((table_plan.table_name.clone(), dataset));
+            }
+        }
+        applied
+    }
+
+    /// Mix languages into free-text content: `mixes` per table, `default`
+    /// for the rest.
+    pub fn set_language_mixes(
+        &mut self,
+        mixes: &BTreeMap<String, LanguageMix>,
+        default: Option<&LanguageMix>,
+    ) {
+        for table_plan in &mut self.table_plans {
+            table_plan.language_mix = mixes.get(&table_plan.table_name).or(default).cloned();
+        }
+    }
+
+    /// Post-process rows with Rhai scripts (table name → script path).
+    pub fn set_row_scripts(&mut self, scripts: &BTreeMap<String, String>) {
+        for table_plan in &mut self.table_plans {
+            table_plan.row_script = scripts.get(&table_plan.table_name).cloned();
+        }
+    }
+
+    /// Generate tables as time series (table name → settings). The
+    /// timestamp column defaults to the table's first timestamp or date
+    /// column; tables without one, and `series_by`/`value` columns that
+    /// don't fit, are left out with a warning.
+    pub fn set_time_series(
+        &mut self,
+        schema: &DatabaseSchema,
+        settings: &BTreeMap<String, TimeSeries>,
+    ) {
+        let generated: Vec<String> = self
+            .table_plans
+            .iter()
+            .map(|t| t.table_name.clone())
+            .collect();
+        for table_plan in &mut self.table_plans {
+            table_plan.time_series = None;
+            let Some(ts) = settings.get(&table_plan.table_name) else {
+                continue;
+            };
+            let Some(table) = schema.tables.get(&table_plan.table_name) else {
+                continue;
+            };
+            let is_time = |c: &Column| {
+                matches!(
+                    c.data_type,
+                    DataType::Timestamp | DataType::TimestampTz | DataType::Date
+                )
+            };
+            let timestamp = match ts.timestamp {
+                Some(ref name) => table.columns.get(name).filter(|c| is_time(c)),
+                None => table.columns.values().find(|c| is_time(c)),
+            };
+            let Some(timestamp) = timestamp else {
+                tracing::warn!(
+                    "{}: time_series needs a timestamp column{}",
+                    table_plan.table_name,
+                    ts.timestamp
+                        .as_ref()
+                        .map(|name| format!(" ('{}' is not one)", name))
+                        .unwrap_or_default()
+                );
+                continue;
+            };
+
+            let series_by = ts.series_by.as_ref().and_then(|name| {
+                let fk = table.foreign_keys.iter().find(|fk| {
+                    fk.source_columns.len() == 1
+                        && fk.source_columns[0] == *name
+                        && generated.contains(&fk.referenced_table)
+                });
+                if fk.is_none() {
+                    tracing::warn!(
+                        "{}: time_series series_by '{}' is not a foreign key to a generated table",
+                        table_plan.table_name,
+                        name
+                    );
+                }
+                fk.map(|fk| {
+                    (
+                        name.clone(),
+                        fk.referenced_table.clone(),
+                        fk.referenced_columns[0].clone(),
+                    )
+                })
+            });
+            let value = ts.value.as_ref().filter(|name| {
+                let numeric = table
+                    .columns
+                    .get(*name)
+                    .is_some_and(|c| c.data_type.is_numeric());
+                if !numeric {
+                    tracing::warn!(
+                        "{}: time_series value '{}' is not a numeric column",
+                        table_plan.table_name,
+                        name
+                    );
+                }
+                numeric
+            });
+
+            let Some(plan) =
+                TimeSeriesPlan::new(ts, timestamp.name.clone(), series_by, value.cloned())
+            else {
+                continue;
+            };
+            // Every reading has a time, a series and a value
+            for col_plan in &mut table_plan.column_plans {
+                let name = Some(&col_plan.column_name);
+                if name == Some(&plan.timestamp_column)
+                    || name == plan.series_by.as_ref().map(|(c, _, _)| c)
+                    || name == plan.value_column.as_ref()
+                {
+                    col_plan.null_probability = 0.0;
+                }
+            }
+            table_plan.time_series = Some(plan);
+        }
+    }
+
+    /// Generate columns from LLM-written texts (`table.column` → texts).
+    /// Columns without texts keep their strategy.
+    pub fn set_llm_content(&mut self, pools: &BTreeMap<String, Vec<String>>) {
+        for table_plan in &mut self.table_plans {
+            for col_plan in &mut table_plan.column_plans {
+                let key = format!("{}.{}", table_plan.table_name, col_plan.column_name);
+                if let Some(texts) = pools.get(&key).filter(|t| !t.is_empty()) {
+                    col_plan.strategy = GenerationStrategy::LlmContent {
+                        texts: texts.clone(),
+                    };
+                }
+            }
+        }
+    }
+
+    /// Generate event tables as funnel journeys (table name → settings).
+    /// Tables without an event or timestamp column are left out with a
+    /// warning, as are user and session columns they don't have.
+    pub fn set_funnels(&mut self, schema: &DatabaseSchema, settings: &BTreeMap<String, Funnel>) {
+        for table_plan in &mut self.table_plans {
+            table_plan.funnel = None;
+            let Some(funnel) = settings.get(&table_plan.table_name) else {
+                continue;
+            };
+            let Some(table) = schema.tables.get(&table_plan.table_name) else {
+                continue;
+            };
+            let has = |name: &String| table.columns.contains_key(name);
+
+            let event_column = match funnel.event_column {
+                Some(ref name) => Some(name.clone()).filter(has),
+                None => EVENT_COLUMN_NAMES
+                    .iter()
+                    .map(|name| name.to_string())
+                    .find(has),
+            };
+            let is_time = |c: &&Column| {
+                matches!(
+                    c.data_type,
+                    DataType::Timestamp | DataType::TimestampTz | DataType::Date
+                )
+            };
+            let timestamp = match funnel.timestamp {
+                Some(ref name) => table.columns.get(name).filter(is_time),
+                None => table.columns.values().find(is_time),
+            };
+            let (Some(event_column), Some(timestamp)) = (event_column, timestamp) else {
+                tracing::warn!(
+                    "{}: funnel needs an event column and a timestamp column",
+                    table_plan.table_name
+                );
+                continue;
+            };
+
+            let mut journey_columns = Vec::new();
+            for (configured, default) in [
+                (&funnel.user_column, "user_id"),
+                (&funnel.session_column, "session_id"),
+            ] {
+                match configured {
+                    Some(name) if has(name) => journey_columns.push(name.clone()),
+                    Some(name) => tracing::warn!(
+                        "{}: funnel column '{}' does not exist",
+                        table_plan.table_name,
+                        name
+                    ),
+                    None if has(&default.to_string()) => journey_columns.push(default.to_string()),
+                    None => {}
+                }
+            }
+
+            let Some(plan) = FunnelPlan::new(
+                funnel,
+                event_column,
+                timestamp.name.clone(),
+                journey_columns,
+            ) else {
+                continue;
+            };
+            for col_plan in &mut table_plan.column_plans {
+                let name = &col_plan.column_name;
+                if *name == plan.event_column
+                    || *name == plan.timestamp_column
+                    || plan.journey_columns.contains(name)
+                {
+                    col_plan.null_probability = 0.0;
+                }
+            }
+            table_plan.funnel = Some(plan);
+        }
+    }
+
+    /// Split tables into population segments (table name → segment name →
+    /// settings). Columns the table doesn't have, and key columns, whose
+    /// values must stay unique or reference a parent, are left out with a
+    /// warning.
+    pub fn set_segments(
+        &mut self,
+        schema: &DatabaseSchema,
+        settings: &BTreeMap<String, BTreeMap<String, Segment>>,
+    ) {
+        for table_plan in &mut self.table_plans {
+            table_plan.segments.clear();
+            let (Some(segments), Some(table)) = (
+                settings.get(&table_plan.table_name),
+                schema.tables.get(&table_plan.table_name),
+            ) else {
+                continue;
+            };
+            let is_key = |column: &str| {
+                table
+                    .primary_key
+                    .iter()
+                    .flat_map(|pk| &pk.columns)
+                    .chain(table.foreign_keys.iter().flat_map(|fk| &fk.source_columns))
+                    .chain(
+                        table
+                            .unique_constraints
+                            .iter()
+                            .filter(|uc| uc.columns.len() == 1)
+                            .flat_map(|uc| &uc.columns),
+                    )
+                    .any(|c| c == column)
+            };
+
+            for (name, segment) in segments {
+                let mut columns = Vec::new();
+                for (column, values) in &segment.columns {
+                    if !table.columns.contains_key(column) {
+                        tracing::warn!(
+                            "{}: segment '{}' sets column '{}', which does not exist",
+                            table_plan.table_name,
+                            name,
+                            column
+                        );
+                    } else if is_key(column) {
+                        tracing::warn!(
+                            "{}: segment '{}' can't set key column '{}'",
+                            table_plan.table_name,
+                            name,
+                            column
+                        );
+                    } else {
+                        columns.push((column.clone(), values.clone()));
+                    }
+                }
+                table_plan.segments.push(SegmentPlan {
+                    name: name.clone(),
+                    share: segment.share,
+                    activity: segment.activity,
+                    columns,
+                });
+            }
+        }
+    }
+
+    /// Generate the type/ID column pairs of polymorphic associations from
+    /// their target tables. Targets outside the plan or without a
+    /// single-column primary key are left out.
+    pub fn set_polymorphic_associations(
+        &mut self,
+        schema: &DatabaseSchema,
+        associations: &[PolymorphicAssociation],
+    ) {
+        for association in associations {
+            let targets: Vec<PolymorphicTarget> = association
+                .targets
+                .iter()
+                .filter(|(_, table)| self.table_plans.iter().any(|t| &t.table_name == *table))
+                .filter_map(|(type_value, table)| {
+                    let pk = schema.tables.get(table)?.primary_key.as_ref()?;
+                    let [ref column] = pk.columns[..] else {
+                        return None;
+                    };
+                    Some(PolymorphicTarget {
+                        type_value: type_value.clone(),
+                        table: table.clone(),
+                        column: column.clone(),
+                    })
+                })
+                .collect();
+            let Some(table_plan) = self
+                .table_plans
+                .iter_mut()
+                .find(|t| t.table_name == association.table)
+            else {
+                continue;
+            };
+            if targets.is_empty() {
+                tracing::warn!(
+                    "{}.{}: no target table is being generated with a single-column primary key",
+                    association.table,
+                    association.name
+                );
+                continue;
+            }
+
+            let (type_column, id_column) = (association.type_column(), association.id_column());
+            let has = |name: &str| {
+                table_plan
+                    .column_plans
+                    .iter()
+                    .any(|c| c.column_name == name)
+            };
+            if !has(&type_column) || !has(&id_column) {
+                tracing::warn!(
+                    "{}: polymorphic '{}' needs columns {} and {}",
+                    association.table,
+                    association.name,
+                    type_column,
+                    id_column
+                );
+                continue;
+            }
+            for col_plan in &mut table_plan.column_plans {
+                if col_plan.column_name == type_column {
+                    col_plan.strategy = GenerationStrategy::PolymorphicType {
+                        id_column: id_column.clone(),
+                        targets: targets.clone(),
+                    };
+                } else if col_plan.column_name == id_column {
+                    col_plan.strategy = GenerationStrategy::PolymorphicId {
+                        type_column: type_column.clone(),
+                    };
+                } else {
+                    continue;
+                }
+                // The pair is written together or not at all
+                col_plan.null_probability = 0.0;
+            }
+        }
+    }
+
+    /// Derive RNG seeds with a custom scheme, e.g. per tenant.
+    pub fn set_seed_derivation(&mut self, derivation: Arc<dyn SeedDerivation>) {
+        self.seed_derivation = derivation;
+    }
+
+    /// Apply per-table sequence offsets (table name → last used ID) so
+    /// synthesized IDs continue after the rows already in the database.
+    pub fn set_sequence_offsets(&mut self, offsets: &BTreeMap<String, u64>) {
+        for table_plan in &mut self.table_plans {
+            if let Some(&offset) = offsets.get(&table_plan.table_name) {
+                table_plan.sequence_offset = Some(offset);
+            }
         }
     }
 }
@@ -395,16 +1561,104 @@ pub fn filter_insertion_order(
         .collect()
 }
 
-fn check_applies_to_column(check: &ParsedCheck, column_name: &str) -> bool {
+/// The storage range of a MySQL unsigned or narrow (`tinyint`,
+/// `mediumint`) integer column as CHECK bounds, so it is generated within
+/// range. Other columns, and other databases, get none.
+pub(crate) fn integer_range_checks(column: &Column, db_type: DatabaseType) -> Vec<ParsedCheck> {
+    let narrow = matches!(
+        column.raw_type.to_lowercase().as_str(),
+        "tinyint" | "mediumint"
+    );
+    if db_type != DatabaseType::MySQL || !(column.unsigned || narrow) {
+        return Vec::new();
+    }
+    match column.integer_bounds() {
+        Some((min, max)) => vec![
+            ParsedCheck::GreaterThanOrEqual {
+                column: column.name.clone(),
+                value: min as f64,
+            },
+            ParsedCheck::LessThanOrEqual {
+                column: column.name.clone(),
+                value: max as f64,
+            },
+        ],
+        None => Vec::new(),
+    }
+}
+
+pub(crate) fn check_applies_to_column(check: &ParsedCheck, column_name: &str) -> bool {
     match check {
         ParsedCheck::GreaterThanOrEqual { column, .. }
         | ParsedCheck::GreaterThan { column, .. }
         | ParsedCheck::LessThanOrEqual { column, .. }
         | ParsedCheck::LessThan { column, .. }
         | ParsedCheck::MinLength { column, .. }
-        | ParsedCheck::InValues { column, .. } => column == column_name,
+        | ParsedCheck::MaxLength { column, .. }
+        | ParsedCheck::InValues { column, .. }
+        | ParsedCheck::Like { column, .. }
+        | ParsedCheck::After { column, .. }
+        | ParsedCheck::Before { column, .. } => column == column_name,
         ParsedCheck::Between { column, .. } => column == column_name,
-        ParsedCheck::ColumnLessThan { left, right } => left == column_name || right == column_name,
+        ParsedCheck::ColumnLessThan { left, right, .. } => {
+            left == column_name || right == column_name
+        }
+        ParsedCheck::All(checks) => checks
+            .iter()
+            .any(|c| check_applies_to_column(c, column_name)),
+    }
+}
+
+/// A plain non-null column generated by `strategy`, for tests.
+#[cfg(test)]
+pub(crate) fn column_plan(column: &str, strategy: GenerationStrategy) -> ColumnGenerationPlan {
+    ColumnGenerationPlan {
+        column_name: column.to_string(),
+        semantic_type: SemanticType::Unknown,
+        strategy,
+        nullable: false,
+        null_probability: 0.0,
+        check_constraints: Vec::new(),
+        max_length: None,
+        text_profile: None,
+        distinct_ratio: None,
+        temporal_profile: None,
+        sampled_fan_out: None,
+    }
+}
+
+/// A plan of `row_count` rows of one table with one column, for tests.
+/// Tests needing more columns replace `table_plans[0].column_plans`.
+#[cfg(test)]
+pub(crate) fn single_column_plan(
+    table: &str,
+    column: &str,
+    strategy: GenerationStrategy,
+    row_count: usize,
+) -> GenerationPlan {
+    GenerationPlan {
+        table_plans: vec![TableGenerationPlan {
+            table_name: table.to_string(),
+            row_count,
+            column_plans: vec![column_plan(column, strategy)],
+            correlation_groups: Vec::new(),
+            stable: false,
+            pinned_rows: None,
+            sequence_offset: None,
+            language_mix: None,
+            row_script: None,
+            time_series: None,
+            funnel: None,
+            segments: Vec::new(),
+            copula: None,
+        }],
+        deferred_edges: Vec::new(),
+        seed: 42,
+        default_row_count: row_count,
+        base_time: chrono::Utc::now().naive_utc(),
+        sequence_offset: 0,
+        seed_derivation: Arc::new(DefaultSeedDerivation),
+        table_streams: true,
     }
 }
 
@@ -565,8 +1819,26 @@ mod tests {
         assert_eq!(result, vec!["users"]);
     }
 
-    // --- Dependency guard: FK to excluded parent falls back to SemanticProvider ---
-
+    // --- Dependency guard: FK to excluded parent falls back to SemanticProvider ---
+
+    #[test]
+    fn test_integer_range_checks_cover_mysql_unsigned_and_narrow_types() {
+        let mut unsigned = Column::new("n".to_string(), DataType::Integer, "int".to_string());
+        unsigned.unsigned = true;
+        let tiny = Column::new("n".to_string(), DataType::SmallInt, "tinyint".to_string());
+        let plain = Column::new("n".to_string(), DataType::Integer, "int".to_string());
+        let sqlite = Column::new("n".to_string(), DataType::Integer, "INTEGER".to_string());
+
+        assert_eq!(
+            integer_range_checks(&unsigned, DatabaseType::MySQL).len(),
+            2
+        );
+        assert_eq!(integer_range_checks(&tiny, DatabaseType::MySQL).len(), 2);
+        assert!(integer_range_checks(&plain, DatabaseType::MySQL).is_empty());
+        assert!(integer_range_checks(&tiny, DatabaseType::SQLite).is_empty());
+        assert!(integer_range_checks(&sqlite, DatabaseType::SQLite).is_empty());
+    }
+
     #[test]
     fn test_fk_to_excluded_parent_uses_semantic_provider() {
         let mut schema = build_chain_schema();
@@ -676,6 +1948,166 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_lifecycle_timestamps_join_temporal_group() {
+        let mut schema = DatabaseSchema::new(DatabaseType::PostgreSQL, "test".to_string());
+        let mut orders = Table::new("orders".to_string());
+        for (name, data_type) in [
+            ("created_at", DataType::TimestampTz),
+            ("shipped_at", DataType::TimestampTz),
+            ("delivered_on", DataType::Date),
+            ("expires_at", DataType::TimestampTz),
+        ] {
+            let col = Column::new(name.to_string(), data_type, "timestamptz".to_string());
+            orders.columns.insert(name.to_string(), col);
+        }
+        schema.tables.insert("orders".to_string(), orders);
+
+        let plan = GenerationPlan::build(
+            &schema,
+            &crate::classify::rules::classify_schema(&schema),
+            &["orders".to_string()],
+            Vec::new(),
+            10,
+            &BTreeMap::new(),
+            42,
+            None,
+            &BTreeMap::new(),
+            None,
+        );
+
+        let groups = &plan.table_plans[0].correlation_groups;
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].group, CorrelationGroup::Temporal);
+        let columns: Vec<&str> = groups[0].columns.iter().map(|(c, _)| c.as_str()).collect();
+        assert_eq!(columns, ["created_at", "shipped_at"]);
+    }
+
+    #[test]
+    fn test_fk_fan_out_comes_from_column_config() {
+        let mut schema = build_chain_schema();
+        let user_id_col = Column::new(
+            "user_id".to_string(),
+            DataType::Integer,
+            "integer".to_string(),
+        );
+        schema
+            .tables
+            .get_mut("orders")
+            .unwrap()
+            .columns
+            .insert("user_id".to_string(), user_id_col);
+
+        let mut overrides = BTreeMap::new();
+        overrides.insert(
+            "orders.user_id".to_string(),
+            crate::config::ColumnConfig {
+                fan_out: Some(FanOut::Zipf(1.2)),
+                ..Default::default()
+            },
+        );
+        let plan = GenerationPlan::build(
+            &schema,
+            &BTreeMap::new(),
+            &["users".to_string(), "orders".to_string()],
+            Vec::new(),
+            10,
+            &BTreeMap::new(),
+            42,
+            None,
+            &overrides,
+            None,
+        );
+
+        let user_id_plan = plan.table_plans[1]
+            .column_plans
+            .iter()
+            .find(|c| c.column_name == "user_id")
+            .unwrap();
+        assert!(
+            matches!(
+                user_id_plan.strategy,
+                GenerationStrategy::ForeignKeyReference {
+                    fan_out: FanOut::Zipf(s),
+                    ..
+                } if s == 1.2
+            ),
+            "got {:?}",
+            user_id_plan.strategy
+        );
+    }
+
+    #[test]
+    fn test_sampled_fan_out_unless_configured() {
+        use crate::sample::stats::DistributionProfile;
+
+        let mut schema = build_chain_schema();
+        let user_id_col = Column::new(
+            "user_id".to_string(),
+            DataType::Integer,
+            "integer".to_string(),
+        );
+        schema
+            .tables
+            .get_mut("orders")
+            .unwrap()
+            .columns
+            .insert("user_id".to_string(), user_id_col);
+        let profile = |table: &str, row_count: u64| DistributionProfile {
+            table_name: table.to_string(),
+            row_count,
+            unique_pairs: Vec::new(),
+            text_profiles: HashMap::new(),
+            null_rates: HashMap::new(),
+            distinct_counts: HashMap::new(),
+            correlations: Vec::new(),
+            temporal_profiles: HashMap::new(),
+            fan_outs: HashMap::new(),
+            column_distributions: HashMap::new(),
+        };
+        let mut orders = profile("orders", 150);
+        orders
+            .fan_outs
+            .insert("user_id".to_string(), vec![(1, 50), (4, 25)]);
+        let profiles = vec![profile("users", 100), orders];
+
+        let sampled_fan_out = |overrides: &BTreeMap<String, ColumnConfig>| {
+            let plan = GenerationPlan::build(
+                &schema,
+                &BTreeMap::new(),
+                &["users".to_string(), "orders".to_string()],
+                Vec::new(),
+                10,
+                &BTreeMap::new(),
+                42,
+                None,
+                overrides,
+                Some(&profiles),
+            );
+            plan.table_plans[1]
+                .column_plans
+                .iter()
+                .find(|c| c.column_name == "user_id")
+                .unwrap()
+                .sampled_fan_out
+                .clone()
+        };
+        // A quarter of the users have no orders
+        assert_eq!(
+            sampled_fan_out(&BTreeMap::new()),
+            Some(vec![(0, 0.25), (1, 0.5), (4, 0.25)])
+        );
+
+        let overrides = BTreeMap::from([(
+            "orders.user_id".to_string(),
+            ColumnConfig {
+                fan_out: Some(FanOut::Uniform),
+                ..Default::default()
+            },
+        )]);
+        assert_eq!(sampled_fan_out(&overrides), None);
+    }
+
     // --- Config column override tests ---
 
     #[test]
@@ -700,6 +2132,7 @@ mod tests {
                 values: Some(vec!["red".into(), "blue".into()]),
                 weights: Some(vec![0.7, 0.3]),
                 custom: None,
+                ..Default::default()
             },
         );
 
@@ -747,6 +2180,7 @@ mod tests {
                 values: None,
                 weights: None,
                 custom: Some("./scripts/tax_gen.js".to_string()),
+                ..Default::default()
             },
         );
 
@@ -775,6 +2209,150 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_range_column_uses_configured_span() {
+        let mut schema = DatabaseSchema::new(DatabaseType::PostgreSQL, "test".to_string());
+        let mut table = Table::new("bookings".to_string());
+        let col = Column::new(
+            "during".to_string(),
+            DataType::from_raw("daterange"),
+            "daterange".to_string(),
+        );
+        table.columns.insert("during".to_string(), col);
+        schema.tables.insert("bookings".to_string(), table);
+
+        let mut overrides = BTreeMap::new();
+        overrides.insert(
+            "bookings.during".to_string(),
+            crate::config::ColumnConfig {
+                range_span: Some((2, 14)),
+                ..Default::default()
+            },
+        );
+
+        let plan = GenerationPlan::build(
+            &schema,
+            &BTreeMap::new(),
+            &["bookings".to_string()],
+            Vec::new(),
+            10,
+            &BTreeMap::new(),
+            42,
+            None,
+            &overrides,
+            None,
+        );
+
+        let col_plan = &plan.table_plans[0].column_plans[0];
+        match &col_plan.strategy {
+            GenerationStrategy::Range {
+                subtype,
+                min_span,
+                max_span,
+            } => {
+                assert_eq!(*subtype, DataType::Date);
+                assert_eq!((*min_span, *max_span), (2, 14));
+            }
+            other => panic!("Expected Range strategy, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_binary_column_size_is_capped_by_max_length() {
+        let mut schema = DatabaseSchema::new(DatabaseType::MySQL, "test".to_string());
+        let mut table = Table::new("api_tokens".to_string());
+        let mut token = Column::new(
+            "token".to_string(),
+            DataType::from_raw("varbinary"),
+            "varbinary(16)".to_string(),
+        );
+        token.max_length = Some(16);
+        table.columns.insert("token".to_string(), token);
+        let payload = Column::new("payload".to_string(), DataType::Binary, "blob".to_string());
+        table.columns.insert("payload".to_string(), payload);
+        schema.tables.insert("api_tokens".to_string(), table);
+
+        let mut overrides = BTreeMap::new();
+        for key in ["api_tokens.token", "api_tokens.payload"] {
+            overrides.insert(
+                key.to_string(),
+                crate::config::ColumnConfig {
+                    binary_size: Some((8, 64)),
+                    ..Default::default()
+                },
+            );
+        }
+
+        let plan = GenerationPlan::build(
+            &schema,
+            &BTreeMap::new(),
+            &["api_tokens".to_string()],
+            Vec::new(),
+            10,
+            &BTreeMap::new(),
+            42,
+            None,
+            &overrides,
+            None,
+        );
+
+        let strategies: Vec<_> = plan.table_plans[0]
+            .column_plans
+            .iter()
+            .map(|c| &c.strategy)
+            .collect();
+        assert!(matches!(
+            strategies[0],
+            GenerationStrategy::Binary {
+                min_len: 8,
+                max_len: 16
+            }
+        ));
+        assert!(matches!(
+            strategies[1],
+            GenerationStrategy::Binary {
+                min_len: 8,
+                max_len: 64
+            }
+        ));
+    }
+
+    #[test]
+    fn test_tsvector_column_is_skipped_even_when_nullable() {
+        let mut schema = DatabaseSchema::new(DatabaseType::PostgreSQL, "test".to_string());
+        let mut table = Table::new("articles".to_string());
+        let mut col = Column::new(
+            "search".to_string(),
+            DataType::from_raw("tsvector"),
+            "tsvector".to_string(),
+        );
+        col.nullable = true;
+        table.columns.insert("search".to_string(), col);
+        schema.tables.insert("articles".to_string(), table);
+
+        let plan = GenerationPlan::build(
+            &schema,
+            &BTreeMap::new(),
+            &["articles".to_string()],
+            Vec::new(),
+            20,
+            &BTreeMap::new(),
+            42,
+            None,
+            &BTreeMap::new(),
+            None,
+        );
+
+        assert!(matches!(
+            plan.table_plans[0].column_plans[0].strategy,
+            GenerationStrategy::Skip
+        ));
+        let data = crate::generate::engine::execute_plan(&plan, &schema, None).unwrap();
+        assert!(data.tables["articles"]
+            .iter()
+            .all(|row| !row.contains_key("search")));
+    }
+
     #[test]
     fn test_override_for_nonexistent_column_is_harmless() {
         let mut schema = DatabaseSchema::new(DatabaseType::PostgreSQL, "test".to_string());
@@ -790,6 +2368,7 @@ mod tests {
                 values: Some(vec!["x".into()]),
                 weights: None,
                 custom: None,
+                ..Default::default()
             },
         );
 
@@ -840,11 +2419,25 @@ mod tests {
             DistributionProfile {
                 table_name: "users".to_string(),
                 row_count: 1000,
+                unique_pairs: Vec::new(),
+                text_profiles: HashMap::new(),
+                null_rates: HashMap::new(),
+                distinct_counts: HashMap::new(),
+                correlations: Vec::new(),
+                temporal_profiles: HashMap::new(),
+                fan_outs: HashMap::new(),
                 column_distributions: std::collections::HashMap::new(),
             },
             DistributionProfile {
                 table_name: "orders".to_string(),
                 row_count: 3200,
+                unique_pairs: Vec::new(),
+                text_profiles: HashMap::new(),
+                null_rates: HashMap::new(),
+                distinct_counts: HashMap::new(),
+                correlations: Vec::new(),
+                temporal_profiles: HashMap::new(),
+                fan_outs: HashMap::new(),
                 column_distributions: {
                     let mut m = std::collections::HashMap::new();
                     m.insert(
@@ -909,6 +2502,13 @@ mod tests {
         let profiles = vec![DistributionProfile {
             table_name: "products".to_string(),
             row_count: 500,
+            unique_pairs: Vec::new(),
+            text_profiles: HashMap::new(),
+            null_rates: HashMap::new(),
+            distinct_counts: HashMap::new(),
+            correlations: Vec::new(),
+            temporal_profiles: HashMap::new(),
+            fan_outs: HashMap::new(),
             column_distributions: {
                 let mut m = std::collections::HashMap::new();
                 m.insert(
@@ -945,4 +2545,246 @@ mod tests {
             col_plan.strategy
         );
     }
+
+    #[test]
+    fn test_text_profile_fits_provider_columns_only() {
+        use crate::sample::stats::{profile_text, ColumnDistribution, DistributionProfile};
+
+        let mut schema = DatabaseSchema::new(DatabaseType::PostgreSQL, "test".to_string());
+        let mut table = Table::new("coupons".to_string());
+        for name in ["code", "status"] {
+            let col = Column::new(name.to_string(), DataType::VarChar, "varchar".to_string());
+            table.columns.insert(name.to_string(), col);
+        }
+        schema.tables.insert("coupons".to_string(), table);
+
+        let profile = profile_text(["X7K2P9", "Q4M8Z1"]).unwrap();
+        let profiles = vec![DistributionProfile {
+            table_name: "coupons".to_string(),
+            row_count: 500,
+            unique_pairs: Vec::new(),
+            text_profiles: HashMap::from([
+                ("code".to_string(), profile.clone()),
+                ("status".to_string(), profile.clone()),
+            ]),
+            null_rates: HashMap::new(),
+            distinct_counts: HashMap::new(),
+            correlations: Vec::new(),
+            temporal_profiles: HashMap::new(),
+            fan_outs: HashMap::new(),
+            column_distributions: HashMap::from([(
+                "status".to_string(),
+                ColumnDistribution::Categorical {
+                    values: vec![("active".to_string(), 1.0)],
+                },
+            )]),
+        }];
+
+        let plan = GenerationPlan::build(
+            &schema,
+            &BTreeMap::new(),
+            &["coupons".to_string()],
+            Vec::new(),
+            10,
+            &BTreeMap::new(),
+            42,
+            None,
+            &BTreeMap::new(),
+            Some(&profiles),
+        );
+        let text_profile = |name: &str| {
+            plan.table_plans[0]
+                .column_plans
+                .iter()
+                .find(|c| c.column_name == name)
+                .unwrap()
+                .text_profile
+                .clone()
+        };
+        assert_eq!(text_profile("code"), Some(profile));
+        assert_eq!(text_profile("status"), None);
+    }
+
+    #[test]
+    fn test_sampled_null_rates_replace_defaults() {
+        use crate::sample::stats::DistributionProfile;
+
+        let mut schema = DatabaseSchema::new(DatabaseType::PostgreSQL, "test".to_string());
+        let mut table = Table::new("users".to_string());
+        for name in ["nickname", "bio", "deleted_at"] {
+            let mut col = Column::new(name.to_string(), DataType::Text, "text".to_string());
+            col.nullable = true;
+            table.columns.insert(name.to_string(), col);
+        }
+        table.columns["deleted_at"].data_type = DataType::Timestamp;
+        schema.tables.insert("users".to_string(), table);
+
+        let profiles = vec![DistributionProfile {
+            table_name: "users".to_string(),
+            row_count: 500,
+            unique_pairs: Vec::new(),
+            text_profiles: HashMap::new(),
+            null_rates: HashMap::from([
+                ("nickname".to_string(), 0.62),
+                ("deleted_at".to_string(), 0.0),
+            ]),
+            distinct_counts: HashMap::new(),
+            correlations: Vec::new(),
+            temporal_profiles: HashMap::new(),
+            fan_outs: HashMap::new(),
+            column_distributions: HashMap::new(),
+        }];
+
+        let plan = GenerationPlan::build(
+            &schema,
+            &BTreeMap::new(),
+            &["users".to_string()],
+            Vec::new(),
+            10,
+            &BTreeMap::new(),
+            42,
+            None,
+            &BTreeMap::new(),
+            Some(&profiles),
+        );
+        let null_probability = |name: &str| {
+            plan.table_plans[0]
+                .column_plans
+                .iter()
+                .find(|c| c.column_name == name)
+                .unwrap()
+                .null_probability
+        };
+        assert_eq!(null_probability("nickname"), 0.62);
+        assert_eq!(null_probability("deleted_at"), 0.0);
+        // Not sampled, so the default applies
+        assert_eq!(null_probability("bio"), 0.05);
+    }
+
+    #[test]
+    fn test_sampled_cardinality() {
+        use crate::sample::stats::{ColumnDistribution, DistributionProfile};
+
+        let mut schema = DatabaseSchema::new(DatabaseType::PostgreSQL, "test".to_string());
+        let mut table = Table::new("plans".to_string());
+        for name in ["code", "label", "region", "notes"] {
+            let col = Column::new(name.to_string(), DataType::VarChar, "varchar".to_string());
+            table.columns.insert(name.to_string(), col);
+        }
+        table.unique_constraints.push(UniqueConstraint {
+            name: None,
+            columns: vec!["code".to_string()],
+        });
+        schema.tables.insert("plans".to_string(), table);
+
+        let categorical = |values: &[&str]| ColumnDistribution::Categorical {
+            values: values.iter().map(|v| (v.to_string(), 0.25)).collect(),
+        };
+        let profiles = vec![DistributionProfile {
+            table_name: "plans".to_string(),
+            row_count: 4,
+            unique_pairs: Vec::new(),
+            text_profiles: HashMap::new(),
+            null_rates: HashMap::new(),
+            distinct_counts: HashMap::from([
+                ("code".to_string(), 4),
+                ("label".to_string(), 4),
+                ("region".to_string(), 2),
+                ("notes".to_string(), 4),
+            ]),
+            correlations: Vec::new(),
+            temporal_profiles: HashMap::new(),
+            fan_outs: HashMap::new(),
+            column_distributions: HashMap::from([
+                ("code".to_string(), categorical(&["a", "b", "c", "d"])),
+                ("label".to_string(), categorical(&["A", "B", "C", "D"])),
+            ]),
+        }];
+
+        let plan = GenerationPlan::build(
+            &schema,
+            &BTreeMap::new(),
+            &["plans".to_string()],
+            Vec::new(),
+            10,
+            &BTreeMap::new(),
+            42,
+            None,
+            &BTreeMap::new(),
+            Some(&profiles),
+        );
+        let column_plans = &plan.table_plans[0].column_plans;
+        let distinct_ratio = |name: &str| {
+            column_plans
+                .iter()
+                .find(|c| c.column_name == name)
+                .unwrap()
+                .distinct_ratio
+        };
+        assert_eq!(distinct_ratio("region"), Some(0.5));
+        assert_eq!(distinct_ratio("notes"), None);
+        assert_eq!(distinct_ratio("code"), None);
+
+        // Unique columns, declared or sampled, with too few values for 10 rows
+        assert_eq!(
+            check_unique_space("plans", 10, column_plans, &["code"], &["label", "notes"]),
+            vec!["code", "label"]
+        );
+        assert!(check_unique_space("plans", 4, column_plans, &["code"], &["label"]).is_empty());
+    }
+
+    #[test]
+    fn test_sampled_correlations_plan_a_copula() {
+        use crate::sample::stats::{ColumnDistribution, DistributionProfile};
+
+        let mut schema = DatabaseSchema::new(DatabaseType::PostgreSQL, "test".to_string());
+        let mut table = Table::new("orders".to_string());
+        for name in ["quantity", "total", "weight"] {
+            let col = Column::new(name.to_string(), DataType::Double, "double".to_string());
+            table.columns.insert(name.to_string(), col);
+        }
+        schema.tables.insert("orders".to_string(), table);
+
+        let numeric = ColumnDistribution::Numeric {
+            min: 1.0,
+            max: 10.0,
+            mean: 3.0,
+            stddev: 2.0,
+            quantiles: Vec::new(),
+        };
+        let profiles = vec![DistributionProfile {
+            table_name: "orders".to_string(),
+            row_count: 500,
+            unique_pairs: Vec::new(),
+            text_profiles: HashMap::new(),
+            null_rates: HashMap::new(),
+            distinct_counts: HashMap::new(),
+            correlations: vec![
+                ("quantity".to_string(), "total".to_string(), 0.85),
+                // Not sampled as a distribution, so left out
+                ("total".to_string(), "weight".to_string(), 0.5),
+            ],
+            temporal_profiles: HashMap::new(),
+            fan_outs: HashMap::new(),
+            column_distributions: HashMap::from([
+                ("quantity".to_string(), numeric.clone()),
+                ("total".to_string(), numeric),
+            ]),
+        }];
+
+        let plan = GenerationPlan::build(
+            &schema,
+            &BTreeMap::new(),
+            &["orders".to_string()],
+            Vec::new(),
+            10,
+            &BTreeMap::new(),
+            42,
+            None,
+            &BTreeMap::new(),
+            Some(&profiles),
+        );
+        let copula = plan.table_plans[0].copula.as_ref().unwrap();
+        assert_eq!(copula.columns, vec!["quantity", "total"]);
+    }
 }
@@ -0,0 +1,127 @@
+//! # Polymorphic Associations
+//!
+//! Rails and Laravel store references to one of several tables in a pair of
+//! columns, `commentable_type` and `commentable_id`, with no foreign key the
+//! database could report. Declare the candidate targets in `seedkit.toml`,
+//! mapping each type value to its table:
+//!
+//! ```toml
+//! [tables.comments.polymorphic]
+//! commentable = { Post = "posts", Photo = "photos" }
+//! ```
+//!
+//! Each row picks a target, writes its type value, and takes an existing ID
+//! from that target's FK pool, so the pair always points at a real row.
+//! Targets are ordered before the table in the dependency graph like real
+//! parents.
+
+use std::collections::BTreeMap;
+
+use chrono::NaiveDateTime;
+use rand::Rng;
+
+use crate::generate::foreign_key::{FanOut, ForeignKeyPool};
+use crate::generate::value::Value;
+
+/// A `<name>_type` / `<name>_id` column pair of a table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PolymorphicAssociation {
+    pub table: String,
+    /// Column prefix, e.g. `commentable`.
+    pub name: String,
+    /// Type value → target table.
+    pub targets: BTreeMap<String, String>,
+}
+
+impl PolymorphicAssociation {
+    pub fn type_column(&self) -> String {
+        format!("{}_type", self.name)
+    }
+
+    pub fn id_column(&self) -> String {
+        format!("{}_id", self.name)
+    }
+}
+
+/// A target table resolved against the schema.
+#[derive(Debug, Clone)]
+pub struct PolymorphicTarget {
+    /// Value written to the type column.
+    pub type_value: String,
+    pub table: String,
+    /// The target's primary key column.
+    pub column: String,
+}
+
+/// Pick a target with generated rows and one of its IDs, as (type value,
+/// id, the target row's `created_at`). `None` when no target has rows.
+pub fn pick_target(
+    targets: &[PolymorphicTarget],
+    fk_pool: &ForeignKeyPool,
+    rng: &mut impl Rng,
+) -> Option<(Value, Value, Option<NaiveDateTime>)> {
+    let available: Vec<&PolymorphicTarget> = targets
+        .iter()
+        .filter(|t| fk_pool.pool_size(&t.table, &t.column) > 0)
+        .collect();
+    if available.is_empty() {
+        return None;
+    }
+    let target = available[rng.random_range(0..available.len())];
+    let (id, created_at) =
+        fk_pool.pick_reference_at(&target.table, &target.column, FanOut::Uniform, rng)?;
+    Some((
+        Value::String(target.type_value.clone().into()),
+        id,
+        created_at,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    fn target(type_value: &str, table: &str) -> PolymorphicTarget {
+        PolymorphicTarget {
+            type_value: type_value.to_string(),
+            table: table.to_string(),
+            column: "id".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_pick_target_keeps_type_and_id_consistent() {
+        let mut pool = ForeignKeyPool::new();
+        for id in 1..=3 {
+            pool.record_value("posts", "id", Value::Int(id));
+        }
+        for id in 100..=102 {
+            pool.record_value("photos", "id", Value::Int(id));
+        }
+        let targets = [
+            target("Post", "posts"),
+            target("Photo", "photos"),
+            target("Video", "videos"),
+        ];
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        let mut seen = std::collections::HashSet::new();
+        for _ in 0..50 {
+            let (type_value, id, _) = pick_target(&targets, &pool, &mut rng).unwrap();
+            let Value::Int(id) = id else {
+                panic!("Expected an int id, got {:?}", id);
+            };
+            match type_value.to_string().as_str() {
+                "Post" => assert!((1..=3).contains(&id)),
+                "Photo" => assert!((100..=102).contains(&id)),
+                other => panic!("{} has no rows", other),
+            }
+            seen.insert(type_value.to_string());
+        }
+        assert_eq!(seen.len(), 2);
+
+        let empty = ForeignKeyPool::new();
+        assert!(pick_target(&targets, &empty, &mut rng).is_none());
+    }
+}
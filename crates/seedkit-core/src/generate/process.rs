@@ -0,0 +1,291 @@
+//! # External Provider Processes
+//!
+//! Any executable can supply a column's values — a Python script wrapping
+//! Faker, a Node module, a Go binary — by speaking line-delimited JSON over
+//! stdin/stdout:
+//!
+//! ```toml
+//! [columns."users.nickname"]
+//! command = ["python3", "providers/nickname.py"]
+//! ```
+//!
+//! The process is started once per column and asked for values in batches.
+//! Each request is one line:
+//!
+//! ```json
+//! {"table":"users","column":"nickname","data_type":"text","seed":1234,"start_row":0,"count":256}
+//! ```
+//!
+//! and the process answers with one line holding exactly `count` values,
+//! for rows `start_row..start_row + count`, or an error:
+//!
+//! ```json
+//! {"values":["ace","blaze",...]}
+//! {"error":"unknown data type"}
+//! ```
+//!
+//! `seed` is derived from the plan's seed and the column name. Batches start
+//! at the same rows on every run, so a provider that seeds its generator
+//! from `seed` and `start_row` is deterministic. The process's stderr is
+//! passed through; it is stopped once generation finishes.
+//!
+//! The process runs in the directory holding seedkit.toml, so relative
+//! paths in `command` mean the same from wherever SeedKit is run. A batch
+//! not answered within 30 seconds fails generation.
+
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::time::Duration;
+
+use crate::error::{Result, SeedKitError};
+use crate::generate::value::Value;
+
+/// Rows requested from a provider process at a time.
+const PROCESS_BATCH_SIZE: usize = 256;
+
+/// How long a provider process may take to answer one batch.
+const PROCESS_REPLY_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A running provider process for one column.
+pub struct ProcessProvider {
+    command: String,
+    child: Child,
+    stdin: Option<ChildStdin>,
+    /// Lines of stdout, read on their own thread so a reply can time out.
+    replies: Receiver<std::io::Result<String>>,
+    reply_timeout: Duration,
+    /// `table`, `column` and `data_type` of every request.
+    context: serde_json::Value,
+    seed: u64,
+    row_count: usize,
+    batch_start: usize,
+    batch: Vec<Value>,
+}
+
+impl ProcessProvider {
+    /// Start `command` in `current_dir`, if given, for a column of
+    /// `row_count` rows.
+    pub fn spawn(
+        command: &[String],
+        current_dir: Option<&Path>,
+        context: serde_json::Value,
+        seed: u64,
+        row_count: usize,
+    ) -> Result<Self> {
+        let display = command.join(" ");
+        let (program, args) = command
+            .split_first()
+            .ok_or_else(|| process_error(&display, "empty command"))?;
+        let mut process = Command::new(program);
+        if let Some(dir) = current_dir {
+            process.current_dir(dir);
+        }
+        let mut child = process
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .map_err(|e| process_error(&display, format!("failed to start: {}", e)))?;
+        let stdin = child.stdin.take();
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| process_error(&display, "stdout is not available"))?;
+        let (sender, replies) = mpsc::channel();
+        std::thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok(Self {
+            command: display,
+            child,
+            stdin,
+            replies,
+            reply_timeout: PROCESS_REPLY_TIMEOUT,
+            context,
+            seed,
+            row_count,
+            batch_start: 0,
+            batch: Vec::new(),
+        })
+    }
+
+    /// The value for `row_index`, requesting the batch starting there if it
+    /// isn't buffered.
+    pub fn generate(&mut self, row_index: usize) -> Result<Value> {
+        let buffered = row_index
+            .checked_sub(self.batch_start)
+            .filter(|&offset| offset < self.batch.len());
+        let offset = match buffered {
+            Some(offset) => offset,
+            None => {
+                let count = PROCESS_BATCH_SIZE.min(self.row_count.saturating_sub(row_index));
+                self.batch = self.request(row_index, count.max(1))?;
+                self.batch_start = row_index;
+                0
+            }
+        };
+        Ok(self.batch[offset].clone())
+    }
+
+    fn request(&mut self, start_row: usize, count: usize) -> Result<Vec<Value>> {
+        let mut request = self.context.clone();
+        request["seed"] = self.seed.into();
+        request["start_row"] = start_row.into();
+        request["count"] = count.into();
+
+        let stdin = self
+            .stdin
+            .as_mut()
+            .ok_or_else(|| process_error(&self.command, "stdin is closed"))?;
+        writeln!(stdin, "{}", request)
+            .and_then(|_| stdin.flush())
+            .map_err(|e| process_error(&self.command, format!("failed to send request: {}", e)))?;
+
+        let line = match self.replies.recv_timeout(self.reply_timeout) {
+            Ok(line) => line.map_err(|e| {
+                process_error(&self.command, format!("failed to read reply: {}", e))
+            })?,
+            Err(RecvTimeoutError::Timeout) => {
+                return Err(process_error(
+                    &self.command,
+                    format!(
+                        "did not answer rows {}.. within {}s",
+                        start_row,
+                        self.reply_timeout.as_secs_f64()
+                    ),
+                ));
+            }
+            Err(RecvTimeoutError::Disconnected) => {
+                return Err(process_error(
+                    &self.command,
+                    format!("exited without answering rows {}..", start_row),
+                ));
+            }
+        };
+
+        let reply: serde_json::Value = serde_json::from_str(&line)
+            .map_err(|e| process_error(&self.command, format!("reply is not JSON: {}", e)))?;
+        if let Some(error) = reply.get("error") {
+            let message = error
+                .as_str()
+                .map_or_else(|| error.to_string(), str::to_string);
+            return Err(process_error(&self.command, message));
+        }
+        match reply.get("values").and_then(|v| v.as_array()) {
+            Some(values) if values.len() == count => {
+                Ok(values.iter().map(Value::from_json).collect())
+            }
+            Some(values) => Err(process_error(
+                &self.command,
+                format!(
+                    "asked for {} values from row {}, got {}",
+                    count,
+                    start_row,
+                    values.len()
+                ),
+            )),
+            None => Err(process_error(
+                &self.command,
+                "reply has neither \"values\" nor \"error\"",
+            )),
+        }
+    }
+}
+
+impl Drop for ProcessProvider {
+    fn drop(&mut self) {
+        // Closing stdin tells a well-behaved provider to exit
+        drop(self.stdin.take());
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+fn process_error(command: &str, message: impl std::fmt::Display) -> SeedKitError {
+    SeedKitError::CustomProvider {
+        path: command.to_string(),
+        message: message.to_string(),
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    /// Answers every request with `"C-<row>"` for each requested row.
+    const ECHO_ROWS: &str = r#"
+        while IFS= read -r req; do
+          start=$(echo "$req" | sed 's/.*"start_row":\([0-9]*\).*/\1/')
+          count=$(echo "$req" | sed 's/.*"count":\([0-9]*\).*/\1/')
+          out=""; i=0
+          while [ "$i" -lt "$count" ]; do
+            out="$out${out:+,}\"C-$((start + i))\""; i=$((i + 1))
+          done
+          echo "{\"values\":[$out]}"
+        done
+    "#;
+
+    fn spawn(script: &str, row_count: usize) -> ProcessProvider {
+        let command = ["sh".to_string(), "-c".to_string(), script.to_string()];
+        let context = serde_json::json!({"table": "users", "column": "nickname"});
+        ProcessProvider::spawn(&command, None, context, 7, row_count).unwrap()
+    }
+
+    #[test]
+    fn test_process_provider_answers_in_batches() {
+        let mut provider = spawn(ECHO_ROWS, 300);
+        assert_eq!(provider.generate(0).unwrap(), Value::String("C-0".into()));
+        assert_eq!(
+            provider.generate(255).unwrap(),
+            Value::String("C-255".into())
+        );
+        assert_eq!(provider.batch.len(), PROCESS_BATCH_SIZE);
+        assert_eq!(
+            provider.generate(299).unwrap(),
+            Value::String("C-299".into())
+        );
+        assert_eq!(
+            provider.generate(256).unwrap(),
+            Value::String("C-256".into())
+        );
+        assert_eq!(provider.batch.len(), 44, "last batch stops at row_count");
+    }
+
+    #[test]
+    fn test_process_provider_reports_errors() {
+        let mut provider = spawn(r#"read -r req; echo '{"error":"no faker"}'"#, 10);
+        let err = provider.generate(0).unwrap_err();
+        assert!(err.to_string().contains("no faker"), "{}", err);
+
+        let mut provider = spawn("read -r req; echo '{\"values\":[1]}'", 10);
+        let err = provider.generate(0).unwrap_err();
+        assert!(err.to_string().contains("got 1"), "{}", err);
+
+        let mut provider = spawn("exit 0", 10);
+        assert!(provider.generate(0).is_err());
+
+        let mut provider = spawn("read -r req; sleep 5", 10);
+        provider.reply_timeout = Duration::from_millis(100);
+        let err = provider.generate(0).unwrap_err();
+        assert!(err.to_string().contains("did not answer"), "{}", err);
+    }
+
+    #[test]
+    fn test_process_provider_runs_in_current_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("provider.sh"), ECHO_ROWS).unwrap();
+        let command = ["sh".to_string(), "provider.sh".to_string()];
+        let context = serde_json::json!({"table": "users", "column": "nickname"});
+        let mut provider =
+            ProcessProvider::spawn(&command, Some(dir.path()), context, 7, 3).unwrap();
+        assert_eq!(provider.generate(2).unwrap(), Value::String("C-2".into()));
+    }
+}
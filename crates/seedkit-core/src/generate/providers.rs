@@ -13,7 +13,7 @@ use uuid::Uuid;
 
 use crate::classify::semantic::SemanticType;
 use crate::generate::value::Value;
-use crate::schema::types::ParsedCheck;
+use crate::schema::types::{DataType, ParsedCheck};
 
 /// Wrap a dynamically generated String into a Value::String.
 #[inline]
@@ -378,8 +378,668 @@ pub fn generate_value(
     value
 }
 
+/// Generate a value for a semantic type, falling back to the column's data
+/// type when the semantic type is `Unknown`.
+///
+/// `generate_value` is purely semantic, so an unclassified integer column
+/// would otherwise receive a lorem word. Nested generators (composite fields,
+/// array elements) use this to stay type-correct without a classification.
+pub fn generate_value_for_type(
+    semantic_type: SemanticType,
+    data_type: &DataType,
+    rng: &mut impl Rng,
+    row_index: usize,
+    check_constraints: &[ParsedCheck],
+    base_time: chrono::NaiveDateTime,
+) -> Value {
+    if semantic_type != SemanticType::Unknown {
+        return generate_value(semantic_type, rng, row_index, check_constraints, base_time);
+    }
+
+    let fallback = match data_type {
+        DataType::SmallInt => {
+            let (min, max) = compute_numeric_bounds_i64(0, 1000, check_constraints);
+            return Value::Int(rng.random_range(min..=max));
+        }
+        DataType::Integer | DataType::BigInt | DataType::Serial | DataType::BigSerial => {
+            let (min, max) = compute_numeric_bounds_i64(1, 100_000, check_constraints);
+            return Value::Int(rng.random_range(min..=max));
+        }
+        DataType::Float | DataType::Double | DataType::Numeric | DataType::Money => {
+            let (min, max) = compute_numeric_bounds_f64(0.0, 1000.0, check_constraints);
+            let val: f64 = rng.random_range(min..=max);
+            return Value::Float((val * 100.0).round() / 100.0);
+        }
+        DataType::Range(subtype) => {
+            let (min_span, max_span) = default_range_span(subtype);
+            return generate_range(subtype, min_span, max_span, rng, base_time);
+        }
+        DataType::Interval => {
+            let (min_secs, max_secs) = DEFAULT_INTERVAL_SPAN;
+            return generate_interval(min_secs, max_secs, check_constraints, rng);
+        }
+        DataType::Binary => {
+            let (min_len, max_len) = DEFAULT_BINARY_SIZE;
+            return generate_binary(min_len, max_len, rng);
+        }
+        DataType::Boolean => SemanticType::BooleanFlag,
+        DataType::Date => SemanticType::DateOnly,
+        DataType::Time => SemanticType::TimeOnly,
+        DataType::Timestamp | DataType::TimestampTz => SemanticType::Timestamp,
+        DataType::Uuid => SemanticType::Uuid,
+        DataType::Json | DataType::Jsonb => SemanticType::JsonData,
+        DataType::Inet => SemanticType::IpAddress,
+        DataType::MacAddr => SemanticType::MacAddress,
+        _ => SemanticType::Unknown,
+    };
+    generate_value(fallback, rng, row_index, check_constraints, base_time)
+}
+
+/// Default span `[min, max]` for generated ranges, in the subtype's unit:
+/// plain units for numeric ranges, days for `daterange`, hours for
+/// timestamp ranges.
+pub fn default_range_span(subtype: &DataType) -> (i64, i64) {
+    match subtype {
+        DataType::Date => (1, 30),
+        DataType::Timestamp | DataType::TimestampTz => (1, 72),
+        _ => (1, 100),
+    }
+}
+
+/// Generate a non-empty `[lower, upper)` range over `subtype`, with
+/// `upper - lower` drawn from `min_span..=max_span` (clamped to at least 1).
+pub fn generate_range(
+    subtype: &DataType,
+    min_span: i64,
+    max_span: i64,
+    rng: &mut impl Rng,
+    base_time: chrono::NaiveDateTime,
+) -> Value {
+    let min_span = min_span.max(1);
+    let span = rng.random_range(min_span..=max_span.max(min_span));
+    let (lower, upper) = match subtype {
+        DataType::Date => {
+            let lower = base_time.date() - ChronoDuration::days(rng.random_range(0..=365));
+            (
+                Value::Date(lower),
+                Value::Date(lower + ChronoDuration::days(span)),
+            )
+        }
+        DataType::Timestamp | DataType::TimestampTz => {
+            let lower = base_time - ChronoDuration::hours(rng.random_range(0..=365 * 24));
+            (
+                Value::Timestamp(lower),
+                Value::Timestamp(lower + ChronoDuration::hours(span)),
+            )
+        }
+        DataType::Numeric | DataType::Float | DataType::Double => {
+            let lower: f64 = (rng.random_range(0.0..1000.0_f64) * 100.0).round() / 100.0;
+            (Value::Float(lower), Value::Float(lower + span as f64))
+        }
+        _ => {
+            let lower = rng.random_range(1..=10_000_i64);
+            (Value::Int(lower), Value::Int(lower + span))
+        }
+    };
+    Value::Range {
+        lower: Box::new(lower),
+        upper: Box::new(upper),
+    }
+}
+
+/// Default interval length `(min, max)` in seconds: one hour to 90 days.
+pub const DEFAULT_INTERVAL_SPAN: (i64, i64) = (3600, 90 * 86_400);
+
+/// Generate an interval literal such as `3 days 04:00:00`, between
+/// `min_secs` and `max_secs` seconds long after narrowing by the column's
+/// CHECK constraints (parsed into seconds during introspection).
+///
+/// Spans of an hour or more are snapped to whole minutes so values read like
+/// hand-written billing periods rather than arbitrary second counts.
+pub fn generate_interval(
+    min_secs: i64,
+    max_secs: i64,
+    check_constraints: &[ParsedCheck],
+    rng: &mut impl Rng,
+) -> Value {
+    let (min, max) = compute_numeric_bounds_i64(min_secs, max_secs, check_constraints);
+    let mut secs = rng.random_range(min..=max);
+    if max - min >= 3600 {
+        secs -= secs.rem_euclid(60);
+        if secs < min {
+            secs += 60;
+        }
+    }
+    owned(format_interval(secs))
+}
+
+/// Format a second count as a PostgreSQL interval literal
+/// (`[-]N days HH:MM:SS`).
+fn format_interval(secs: i64) -> String {
+    let sign = if secs < 0 { "-" } else { "" };
+    let abs = secs.unsigned_abs();
+    let (days, rem) = (abs / 86_400, abs % 86_400);
+    let clock = format!(
+        "{}{:02}:{:02}:{:02}",
+        sign,
+        rem / 3600,
+        (rem % 3600) / 60,
+        rem % 60
+    );
+    match days {
+        0 => clock,
+        1 => format!("{}1 day {}", sign, clock),
+        n => format!("{}{} days {}", sign, n, clock),
+    }
+}
+
+/// Default binary payload size `(min, max)` in bytes.
+pub const DEFAULT_BINARY_SIZE: (usize, usize) = (8, 32);
+
+/// Generate `min_len..=max_len` random bytes for a bytea/blob column.
+pub fn generate_binary(min_len: usize, max_len: usize, rng: &mut impl Rng) -> Value {
+    let len = rng.random_range(min_len..=max_len.max(min_len));
+    let mut bytes = vec![0u8; len];
+    rng.fill(&mut bytes[..]);
+    Value::Bytes(bytes)
+}
+
+/// Default PostGIS bounding box `(min_lng, min_lat, max_lng, max_lat)`:
+/// the continental US, matching the correlated latitude/longitude generator.
+pub const DEFAULT_BBOX: (f64, f64, f64, f64) = (-125.0, 25.0, -70.0, 48.0);
+
+/// Generate an EWKT geometry (`SRID=4326;POINT(-73.98 40.75)`) of the given
+/// PostGIS shape inside `bbox`.
+///
+/// With a `center`, the geometry is placed around that point instead of a
+/// random one, so it agrees with the row's latitude/longitude columns.
+/// Polygons are star-shaped rings (vertices sorted by angle), which keeps
+/// them simple and valid. Unconstrained `Geometry` columns get points.
+pub fn generate_geometry(
+    shape: &str,
+    srid: u32,
+    bbox: (f64, f64, f64, f64),
+    center: Option<(f64, f64)>,
+    rng: &mut impl Rng,
+) -> Value {
+    let (min_x, min_y, max_x, max_y) = bbox;
+    let (cx, cy) = center.unwrap_or_else(|| {
+        (
+            rng.random_range(min_x..=max_x),
+            rng.random_range(min_y..=max_y),
+        )
+    });
+    // Features span up to 1% of the box so they stay local
+    let extent = ((max_x - min_x).min(max_y - min_y) * 0.01).max(1e-6);
+    let clamp = |x: f64, y: f64| (x.clamp(min_x, max_x), y.clamp(min_y, max_y));
+
+    let upper = shape.to_ascii_uppercase();
+    let (multi, base) = match upper.strip_prefix("MULTI") {
+        Some(rest) => (true, rest),
+        None => (false, upper.as_str()),
+    };
+
+    let body = match base {
+        "LINESTRING" => {
+            let count = rng.random_range(2..=5);
+            let (mut x, mut y) = (cx, cy);
+            let mut points = Vec::with_capacity(count);
+            for _ in 0..count {
+                points.push(format_coord(x, y));
+                let (nx, ny) = clamp(
+                    x + rng.random_range(-extent..=extent),
+                    y + rng.random_range(-extent..=extent),
+                );
+                x = nx;
+                y = ny;
+            }
+            format!("({})", points.join(", "))
+        }
+        "POLYGON" => {
+            let count = rng.random_range(4..=8);
+            let mut angles: Vec<f64> = (0..count)
+                .map(|_| rng.random_range(0.0..std::f64::consts::TAU))
+                .collect();
+            angles.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+            let mut ring: Vec<String> = angles
+                .iter()
+                .map(|angle| {
+                    let radius = rng.random_range(extent * 0.3..=extent);
+                    let (x, y) = clamp(cx + radius * angle.cos(), cy + radius * angle.sin());
+                    format_coord(x, y)
+                })
+                .collect();
+            ring.push(ring[0].clone());
+            format!("(({}))", ring.join(", "))
+        }
+        _ => format!("({})", format_coord(cx, cy)),
+    };
+
+    let kind = match base {
+        "LINESTRING" => "LINESTRING",
+        "POLYGON" => "POLYGON",
+        _ => "POINT",
+    };
+    let wkt = if multi {
+        format!("MULTI{}({})", kind, body)
+    } else {
+        format!("{}{}", kind, body)
+    };
+
+    if srid == 0 {
+        owned(wkt)
+    } else {
+        owned(format!("SRID={};{}", srid, wkt))
+    }
+}
+
+fn format_coord(x: f64, y: f64) -> String {
+    format!(
+        "{} {}",
+        (x * 1_000_000.0).round() / 1_000_000.0,
+        (y * 1_000_000.0).round() / 1_000_000.0
+    )
+}
+
+/// Domains tried, longest first, when an email has to fit a short column.
+const SHORT_EMAIL_DOMAINS: &[&str] = &["example.com", "example.org", "ex.com", "e.io"];
+
+/// Shrink an identity value so it fits a `varchar(n)` budget.
+///
+/// Plain truncation breaks emails (the domain is cut off) and collapses the
+/// row-index suffix that keeps emails and usernames unique. Instead, this
+/// picks a shorter form of the same kind of value: letters from the original
+/// followed by the row index for emails/usernames, short reserved domains,
+/// and initials for full names. Values already within the budget, and
+/// non-identity types, are returned unchanged.
+///
+/// `attempt` is non-zero when retrying a value that collided with a unique
+/// constraint; it is added to the suffix apart from the row index, so retries
+/// don't land on the values of later rows.
+pub fn fit_identity_to_length(
+    semantic_type: SemanticType,
+    value: Value,
+    max_len: usize,
+    row_index: usize,
+    attempt: usize,
+) -> Value {
+    let text = match &value {
+        Value::String(s) if s.chars().count() > max_len => s.to_string(),
+        _ => return value,
+    };
+
+    match semantic_type {
+        SemanticType::Email => owned(fit_email(&text, max_len, row_index, attempt)),
+        SemanticType::Username => owned(with_index_suffix(&text, max_len, row_index, attempt)),
+        SemanticType::FullName | SemanticType::DisplayName => owned(fit_full_name(&text, max_len)),
+        SemanticType::FirstName | SemanticType::LastName => {
+            owned(text.chars().take(max_len).collect())
+        }
+        _ => value,
+    }
+}
+
+/// Letters of `source` (lowercased) trimmed so that `prefix + row_index` fits
+/// `budget`. Letters-then-digits keeps distinct row indices distinct; a retry
+/// appends `x<attempt>`, which no first attempt ends with. A budget too small
+/// for the row index itself is cut to the budget.
+fn with_index_suffix(source: &str, budget: usize, row_index: usize, attempt: usize) -> String {
+    let suffix = index_suffix(row_index, attempt);
+    let letters: String = source
+        .chars()
+        .filter(|c| c.is_ascii_alphabetic())
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+    let letters = if letters.is_empty() {
+        "u".to_string()
+    } else {
+        letters
+    };
+    let room = budget.saturating_sub(suffix.len()).max(1);
+    let mut result: String = letters.chars().take(room).collect();
+    result.push_str(&suffix);
+    result.chars().take(budget).collect()
+}
+
+fn index_suffix(row_index: usize, attempt: usize) -> String {
+    if attempt == 0 {
+        row_index.to_string()
+    } else {
+        format!("{}x{}", row_index, attempt)
+    }
+}
+
+fn fit_email(email: &str, max_len: usize, row_index: usize, attempt: usize) -> String {
+    let local = email.split('@').next().unwrap_or("user");
+    let suffix_len = index_suffix(row_index, attempt).len();
+    // Prefer the longest domain that still leaves at least one letter.
+    let domain = SHORT_EMAIL_DOMAINS
+        .iter()
+        .find(|d| d.len() + 1 + suffix_len < max_len)
+        .unwrap_or(&SHORT_EMAIL_DOMAINS[SHORT_EMAIL_DOMAINS.len() - 1]);
+    let local_budget = max_len.saturating_sub(domain.len() + 1);
+    let email = format!(
+        "{}@{}",
+        with_index_suffix(local, local_budget, row_index, attempt),
+        domain
+    );
+    // Below `x@e.io` no valid email fits; the column still gets its length
+    email.chars().take(max_len).collect()
+}
+
+/// "Jonathan Smithson" -> "J. Smithson" -> "J. S." -> truncated.
+fn fit_full_name(name: &str, max_len: usize) -> String {
+    let parts: Vec<&str> = name.split_whitespace().collect();
+    if parts.len() >= 2 {
+        let last = parts[parts.len() - 1];
+        let first_initial = parts[0].chars().next().unwrap_or('X');
+        let short = format!("{}. {}", first_initial, last);
+        if short.chars().count() <= max_len {
+            return short;
+        }
+        let initials = format!("{}. {}.", first_initial, last.chars().next().unwrap_or('X'));
+        if initials.chars().count() <= max_len {
+            return initials;
+        }
+    }
+    name.chars().take(max_len).collect()
+}
+
+/// Adjust a generated value so it passes the column's CHECK constraints.
+///
+/// Semantic providers already aim inside numeric bounds; this catches the
+/// rest (`IN` lists, `LIKE` patterns, length and date bounds) and values from
+/// providers that don't read checks at all. A value that already passes is
+/// returned unchanged without drawing from the RNG, so columns whose checks
+/// were always met keep generating the same output.
+pub fn satisfy_checks(value: Value, checks: &[ParsedCheck], rng: &mut impl Rng) -> Value {
+    if checks.is_empty() || value.is_null() {
+        return value;
+    }
+
+    // An IN list pins the value outright
+    let allowed = checks.iter().find_map(|c| match c {
+        ParsedCheck::InValues { values, .. } if !values.is_empty() => Some(values),
+        _ => None,
+    });
+    if let Some(allowed) = allowed {
+        return satisfy_in_values(value, allowed, rng);
+    }
+
+    match value {
+        Value::Int(v) => {
+            let (min, max) = compute_numeric_bounds_i64(i64::MIN, i64::MAX, checks);
+            if (min..=max).contains(&v) {
+                Value::Int(v)
+            } else {
+                let (low, high) = finite_span_i64(min, max);
+                Value::Int(rng.random_range(low..=high))
+            }
+        }
+        Value::Float(v) => {
+            let (min, max) = compute_numeric_bounds_f64(f64::MIN, f64::MAX, checks);
+            if (min..=max).contains(&v) {
+                Value::Float(v)
+            } else {
+                let (low, high) = finite_span_f64(min, max);
+                Value::Float((rng.random_range(low..=high) * 100.0).round() / 100.0)
+            }
+        }
+        Value::String(s) => satisfy_text(s, checks, rng),
+        Value::Timestamp(t) => {
+            let (min, max) = datetime_bounds(checks);
+            Value::Timestamp(pick_within(t, min, max, ChronoDuration::seconds(1), rng))
+        }
+        Value::Date(d) => {
+            let (min, max) = datetime_bounds(checks);
+            // Round the bounds inward to whole days
+            let min = min.map(|m| {
+                if m.time() == NaiveTime::MIN {
+                    m
+                } else {
+                    (m.date() + ChronoDuration::days(1)).and_time(NaiveTime::MIN)
+                }
+            });
+            let max = max.map(|m| m.date().and_time(NaiveTime::MIN));
+            let picked = pick_within(
+                d.and_time(NaiveTime::MIN),
+                min,
+                max,
+                ChronoDuration::days(1),
+                rng,
+            );
+            Value::Date(picked.date())
+        }
+        other => other,
+    }
+}
+
+fn satisfy_in_values(value: Value, allowed: &[String], rng: &mut impl Rng) -> Value {
+    let current = match &value {
+        Value::Int(v) => v.to_string(),
+        Value::Float(v) => v.to_string(),
+        Value::String(s) => s.to_string(),
+        _ => return value,
+    };
+    if allowed.contains(&current) {
+        return value;
+    }
+    let picked = &allowed[rng.random_range(0..allowed.len())];
+    match value {
+        Value::Int(_) => picked.parse().ok().map(Value::Int),
+        Value::Float(_) => picked.parse().ok().map(Value::Float),
+        _ => None,
+    }
+    .unwrap_or_else(|| owned(picked.clone()))
+}
+
+/// A bounded range to draw from when one side is open.
+fn finite_span_i64(min: i64, max: i64) -> (i64, i64) {
+    match (min == i64::MIN, max == i64::MAX) {
+        (true, true) => (0, 1000),
+        (true, false) => (max.saturating_sub(1000), max),
+        (false, true) => (min, min.saturating_add(1000)),
+        (false, false) => (min, max),
+    }
+}
+
+fn finite_span_f64(min: f64, max: f64) -> (f64, f64) {
+    match (min <= f64::MIN, max >= f64::MAX) {
+        (true, true) => (0.0, 1000.0),
+        (true, false) => (max - 1000.0, max),
+        (false, true) => (min, min + 1000.0),
+        (false, false) => (min, max),
+    }
+}
+
+fn satisfy_text(s: Cow<'static, str>, checks: &[ParsedCheck], rng: &mut impl Rng) -> Value {
+    let mut min_len = 0;
+    let mut max_len: Option<usize> = None;
+    let mut pattern = None;
+    for check in checks {
+        match check {
+            ParsedCheck::MinLength { min, .. } => min_len = min_len.max(*min),
+            ParsedCheck::MaxLength { max, .. } => {
+                max_len = Some(max_len.map_or(*max, |m: usize| m.min(*max)))
+            }
+            ParsedCheck::Like { pattern: p, .. } => pattern = Some(p.as_str()),
+            _ => {}
+        }
+    }
+
+    let len = s.chars().count();
+    let length_ok = len >= min_len && max_len.is_none_or(|max| len <= max);
+    if length_ok && pattern.is_none_or(|p| like_matches(&s, p)) {
+        return Value::String(s);
+    }
+
+    match pattern {
+        Some(p) => owned(fill_like_pattern(p, &s, min_len, max_len, rng)),
+        None => {
+            let mut text: String = s.chars().take(max_len.unwrap_or(len)).collect();
+            if len < min_len {
+                text.push_str(&generate_random_alphanumeric(rng, min_len - len));
+            }
+            owned(text)
+        }
+    }
+}
+
+/// One element of a `LIKE` pattern.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum LikeToken {
+    Literal(char),
+    /// `_`: exactly one character
+    One,
+    /// `%`: any run of characters
+    Many,
+}
+
+fn like_tokens(pattern: &str) -> Vec<LikeToken> {
+    let mut tokens = Vec::new();
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        tokens.push(match c {
+            '\\' => LikeToken::Literal(chars.next().unwrap_or('\\')),
+            '_' => LikeToken::One,
+            '%' => LikeToken::Many,
+            c => LikeToken::Literal(c),
+        });
+    }
+    tokens
+}
+
+/// Whether `text` matches a SQL `LIKE` pattern.
+pub fn like_matches(text: &str, pattern: &str) -> bool {
+    let tokens = like_tokens(pattern);
+    let text: Vec<char> = text.chars().collect();
+    let (mut t, mut p) = (0, 0);
+    // Position after the last `%` and the text index it was tried at
+    let mut backtrack: Option<(usize, usize)> = None;
+    while t < text.len() {
+        match tokens.get(p) {
+            Some(LikeToken::Many) => {
+                backtrack = Some((p + 1, t));
+                p += 1;
+            }
+            Some(LikeToken::One) => {
+                t += 1;
+                p += 1;
+            }
+            Some(LikeToken::Literal(c)) if *c == text[t] => {
+                t += 1;
+                p += 1;
+            }
+            _ => match backtrack {
+                Some((bp, bt)) => {
+                    backtrack = Some((bp, bt + 1));
+                    p = bp;
+                    t = bt + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+    tokens[p..].iter().all(|token| *token == LikeToken::Many)
+}
+
+/// Build a string matching `pattern`, putting `filler` (resized to fit the
+/// length bounds) in the first `%` and random characters in each `_`.
+fn fill_like_pattern(
+    pattern: &str,
+    filler: &str,
+    min_len: usize,
+    max_len: Option<usize>,
+    rng: &mut impl Rng,
+) -> String {
+    let tokens = like_tokens(pattern);
+    let fixed = tokens.iter().filter(|t| **t != LikeToken::Many).count();
+    let filler_len = filler.chars().count();
+    let total = (fixed + filler_len)
+        .max(min_len)
+        .min(max_len.unwrap_or(usize::MAX).max(fixed));
+    let budget = total - fixed;
+
+    let mut out = String::with_capacity(total);
+    let mut filled = false;
+    for token in tokens {
+        match token {
+            LikeToken::Literal(c) => out.push(c),
+            LikeToken::One => out.push_str(&generate_random_alphanumeric(rng, 1)),
+            LikeToken::Many if !filled => {
+                filled = true;
+                out.extend(filler.chars().take(budget));
+                if filler_len < budget {
+                    out.push_str(&generate_random_alphanumeric(rng, budget - filler_len));
+                }
+            }
+            LikeToken::Many => {}
+        }
+    }
+    out
+}
+
+/// Inclusive date/timestamp bounds from `After`/`Before` checks, with
+/// exclusive bounds moved in by a second.
+pub(crate) fn datetime_bounds(
+    checks: &[ParsedCheck],
+) -> (Option<chrono::NaiveDateTime>, Option<chrono::NaiveDateTime>) {
+    let mut min: Option<chrono::NaiveDateTime> = None;
+    let mut max: Option<chrono::NaiveDateTime> = None;
+    for check in checks {
+        match check {
+            ParsedCheck::After {
+                value, inclusive, ..
+            } => {
+                let bound = if *inclusive {
+                    *value
+                } else {
+                    *value + ChronoDuration::seconds(1)
+                };
+                min = Some(min.map_or(bound, |m| m.max(bound)));
+            }
+            ParsedCheck::Before {
+                value, inclusive, ..
+            } => {
+                let bound = if *inclusive {
+                    *value
+                } else {
+                    *value - ChronoDuration::seconds(1)
+                };
+                max = Some(max.map_or(bound, |m| m.min(bound)));
+            }
+            _ => {}
+        }
+    }
+    (min, max)
+}
+
+/// Keep `value` if it's within the bounds, otherwise pick a point inside
+/// them in whole `step`s. An open side extends a year past the other bound.
+fn pick_within(
+    value: chrono::NaiveDateTime,
+    min: Option<chrono::NaiveDateTime>,
+    max: Option<chrono::NaiveDateTime>,
+    step: ChronoDuration,
+    rng: &mut impl Rng,
+) -> chrono::NaiveDateTime {
+    if min.is_none_or(|m| value >= m) && max.is_none_or(|m| value <= m) {
+        return value;
+    }
+    let year = ChronoDuration::days(365);
+    let (low, high) = match (min, max) {
+        (Some(low), Some(high)) if low <= high => (low, high),
+        (Some(_), Some(_)) => return value,
+        (Some(low), None) => (low, low + year),
+        (None, Some(high)) => (high - year, high),
+        (None, None) => return value,
+    };
+    let steps = (high - low).num_seconds() / step.num_seconds();
+    low + ChronoDuration::seconds(step.num_seconds() * rng.random_range(0..=steps))
+}
+
 /// Compute safe f64 bounds from CHECK constraints, clamping if they conflict.
-fn compute_numeric_bounds_f64(
+pub(crate) fn compute_numeric_bounds_f64(
     default_min: f64,
     default_max: f64,
     constraints: &[ParsedCheck],
@@ -392,6 +1052,10 @@ fn compute_numeric_bounds_f64(
             ParsedCheck::GreaterThan { value, .. } => min = min.max(*value + 0.01),
             ParsedCheck::LessThanOrEqual { value, .. } => max = max.min(*value),
             ParsedCheck::LessThan { value, .. } => max = max.min(*value - 0.01),
+            ParsedCheck::Between { low, high, .. } => {
+                min = min.max(*low);
+                max = max.min(*high);
+            }
             _ => {}
         }
     }
@@ -407,7 +1071,7 @@ fn compute_numeric_bounds_f64(
 }
 
 /// Compute safe i64 bounds from CHECK constraints.
-fn compute_numeric_bounds_i64(
+pub(crate) fn compute_numeric_bounds_i64(
     default_min: i64,
     default_max: i64,
     constraints: &[ParsedCheck],
@@ -417,9 +1081,15 @@ fn compute_numeric_bounds_i64(
     for c in constraints {
         match c {
             ParsedCheck::GreaterThanOrEqual { value, .. } => min = min.max(*value as i64),
-            ParsedCheck::GreaterThan { value, .. } => min = min.max(*value as i64 + 1),
+            ParsedCheck::GreaterThan { value, .. } => {
+                min = min.max((*value as i64).saturating_add(1))
+            }
             ParsedCheck::LessThanOrEqual { value, .. } => max = max.min(*value as i64),
-            ParsedCheck::LessThan { value, .. } => max = max.min(*value as i64 - 1),
+            ParsedCheck::LessThan { value, .. } => max = max.min((*value as i64).saturating_sub(1)),
+            ParsedCheck::Between { low, high, .. } => {
+                min = min.max(*low as i64);
+                max = max.min(*high as i64);
+            }
             _ => {}
         }
     }
@@ -429,7 +1099,7 @@ fn compute_numeric_bounds_i64(
             min,
             max
         );
-        max = min + 100;
+        max = min.saturating_add(100);
     }
     (min, max)
 }
@@ -453,6 +1123,184 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_generate_value_for_type_falls_back_to_data_type() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        let base = test_base_time();
+        let v = generate_value_for_type(
+            SemanticType::Unknown,
+            &DataType::Integer,
+            &mut rng,
+            0,
+            &[],
+            base,
+        );
+        assert!(matches!(v, Value::Int(_)));
+        let v = generate_value_for_type(
+            SemanticType::Unknown,
+            &DataType::Date,
+            &mut rng,
+            0,
+            &[],
+            base,
+        );
+        assert!(matches!(v, Value::Date(_)));
+        // A known semantic type wins over the data type
+        let v =
+            generate_value_for_type(SemanticType::Email, &DataType::Text, &mut rng, 0, &[], base);
+        assert!(v.as_string().is_some_and(|s| s.contains('@')));
+    }
+
+    #[test]
+    fn test_fit_identity_to_length_keeps_values_valid_and_unique() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        let mut seen = std::collections::HashSet::new();
+        for row in 0..500 {
+            let email = generate_value(SemanticType::Email, &mut rng, row, &[], test_base_time());
+            let fitted = fit_identity_to_length(SemanticType::Email, email, 20, row, 0);
+            let s = fitted.to_string();
+            assert!(s.len() <= 20, "'{}' exceeds budget", s);
+            let (local, domain) = s.split_once('@').expect("email must keep its '@'");
+            assert!(
+                !local.is_empty() && domain.contains('.'),
+                "invalid email '{}'",
+                s
+            );
+            assert!(seen.insert(s.clone()), "duplicate email '{}'", s);
+        }
+
+        let mut seen = std::collections::HashSet::new();
+        for row in 0..500 {
+            let user = generate_value(SemanticType::Username, &mut rng, row, &[], test_base_time());
+            let s = fit_identity_to_length(SemanticType::Username, user, 10, row, 0).to_string();
+            assert!(s.len() <= 10, "'{}' exceeds budget", s);
+            assert!(seen.insert(s.clone()), "duplicate username '{}'", s);
+        }
+
+        // Retries of a row don't take the values of later rows
+        let retry = |row, attempt| {
+            let user = owned("jonathanson".to_string());
+            fit_identity_to_length(SemanticType::Username, user, 10, row, attempt).to_string()
+        };
+        assert_ne!(retry(1, 1), retry(2, 0));
+        assert_ne!(retry(1, 1), retry(11, 0));
+
+        // Budgets too small for the row index are still respected
+        for budget in 1..8 {
+            let user = owned("jonathanson".to_string());
+            let s = fit_identity_to_length(SemanticType::Username, user, budget, 1_234_567, 3);
+            assert!(s.to_string().len() <= budget);
+            let email = owned("jonathan@example.com".to_string());
+            let s = fit_identity_to_length(SemanticType::Email, email, budget, 1_234_567, 3);
+            assert!(s.to_string().len() <= budget, "'{}' exceeds {}", s, budget);
+        }
+    }
+
+    #[test]
+    fn test_generate_range_is_non_empty() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        for subtype in [
+            DataType::Integer,
+            DataType::Numeric,
+            DataType::Date,
+            DataType::TimestampTz,
+        ] {
+            for _ in 0..100 {
+                match generate_range(&subtype, 1, 10, &mut rng, test_base_time()) {
+                    Value::Range { lower, upper } => match (*lower, *upper) {
+                        (Value::Int(l), Value::Int(u)) => assert!(l < u),
+                        (Value::Float(l), Value::Float(u)) => assert!(l < u),
+                        (Value::Date(l), Value::Date(u)) => assert!(l < u),
+                        (Value::Timestamp(l), Value::Timestamp(u)) => assert!(l < u),
+                        other => panic!("Unexpected bounds for {}: {:?}", subtype, other),
+                    },
+                    other => panic!("Expected range, got {:?}", other),
+                }
+            }
+        }
+
+        let value = Value::Range {
+            lower: Box::new(Value::Int(1)),
+            upper: Box::new(Value::Int(10)),
+        };
+        assert_eq!(
+            value.to_sql_literal(&crate::schema::types::DatabaseType::PostgreSQL),
+            "'[1,10)'"
+        );
+        let ts = generate_range(&DataType::Timestamp, 2, 2, &mut rng, test_base_time());
+        let text = ts.to_range_text();
+        assert!(
+            text.starts_with("[\"") && text.ends_with("\")"),
+            "timestamp bounds contain a space and must be quoted: {}",
+            text
+        );
+    }
+
+    #[test]
+    fn test_generate_interval_respects_checks() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        let checks = vec![ParsedCheck::GreaterThanOrEqual {
+            column: "billing_period".to_string(),
+            value: 7.0 * 86_400.0,
+        }];
+        for _ in 0..50 {
+            let Value::String(s) = generate_interval(3600, 90 * 86_400, &checks, &mut rng) else {
+                panic!("expected interval literal");
+            };
+            let days: i64 = s.split(' ').next().unwrap().parse().unwrap();
+            assert!((7..=90).contains(&days), "{}", s);
+            assert!(s.ends_with(":00"), "{}", s);
+        }
+        assert_eq!(format_interval(3 * 86_400 + 4 * 3600), "3 days 04:00:00");
+        assert_eq!(format_interval(86_400), "1 day 00:00:00");
+        assert_eq!(format_interval(-90), "-00:01:30");
+    }
+
+    #[test]
+    fn test_generate_geometry_shapes() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        let bbox = (-74.26, 40.49, -73.70, 40.92);
+
+        let point = generate_geometry("Point", 4326, bbox, None, &mut rng).to_string();
+        assert!(point.starts_with("SRID=4326;POINT("), "{}", point);
+        let coords: Vec<f64> = point["SRID=4326;POINT(".len()..point.len() - 1]
+            .split(' ')
+            .map(|c| c.parse().unwrap())
+            .collect();
+        assert!(coords[0] >= bbox.0 && coords[0] <= bbox.2);
+        assert!(coords[1] >= bbox.1 && coords[1] <= bbox.3);
+
+        let polygon = generate_geometry("Polygon", 0, bbox, None, &mut rng).to_string();
+        assert!(polygon.starts_with("POLYGON(("), "{}", polygon);
+        let ring: Vec<&str> = polygon["POLYGON((".len()..polygon.len() - 2]
+            .split(", ")
+            .collect();
+        assert!(ring.len() >= 5);
+        assert_eq!(ring.first(), ring.last(), "polygon ring must be closed");
+
+        let multi = generate_geometry("MultiLineString", 4326, bbox, None, &mut rng).to_string();
+        assert!(
+            multi.starts_with("SRID=4326;MULTILINESTRING(("),
+            "{}",
+            multi
+        );
+
+        let centered = generate_geometry("Point", 4326, bbox, Some((-74.0, 40.7)), &mut rng);
+        assert_eq!(centered.to_string(), "SRID=4326;POINT(-74 40.7)");
+    }
+
+    #[test]
+    fn test_fit_full_name_uses_initials() {
+        let name = owned("Jonathan Smithson".to_string());
+        let fitted = fit_identity_to_length(SemanticType::FullName, name.clone(), 12, 0, 0);
+        assert_eq!(fitted.to_string(), "J. Smithson");
+        let fitted = fit_identity_to_length(SemanticType::FullName, name.clone(), 6, 0, 0);
+        assert_eq!(fitted.to_string(), "J. S.");
+        // Within budget: untouched
+        let fitted = fit_identity_to_length(SemanticType::FullName, name, 50, 0, 0);
+        assert_eq!(fitted.to_string(), "Jonathan Smithson");
+    }
+
     #[test]
     fn test_generate_email() {
         let mut rng = rand::rngs::StdRng::seed_from_u64(42);
@@ -534,4 +1382,90 @@ mod tests {
             "Same seed + same base_time must produce identical timestamps"
         );
     }
+
+    #[test]
+    fn test_satisfy_checks_leaves_passing_values() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(1);
+        let checks = vec![ParsedCheck::GreaterThan {
+            column: "qty".to_string(),
+            value: 0.0,
+        }];
+        assert_eq!(
+            satisfy_checks(Value::Int(3), &checks, &mut rng),
+            Value::Int(3)
+        );
+        let Value::Int(v) = satisfy_checks(Value::Int(-4), &checks, &mut rng) else {
+            panic!("Expected an int");
+        };
+        assert!(v > 0);
+    }
+
+    #[test]
+    fn test_satisfy_checks_in_values_keep_type() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(1);
+        let checks = vec![ParsedCheck::InValues {
+            column: "priority".to_string(),
+            values: vec!["1".to_string(), "2".to_string()],
+        }];
+        let value = satisfy_checks(Value::Int(40), &checks, &mut rng);
+        assert!(matches!(value, Value::Int(1 | 2)));
+        let value = satisfy_checks(owned("urgent".to_string()), &checks, &mut rng);
+        assert!(matches!(value.as_string(), Some("1" | "2")));
+    }
+
+    #[test]
+    fn test_satisfy_checks_text_length_and_pattern() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(1);
+        let length = vec![
+            ParsedCheck::MinLength {
+                column: "code".to_string(),
+                min: 4,
+            },
+            ParsedCheck::MaxLength {
+                column: "code".to_string(),
+                max: 6,
+            },
+        ];
+        let short = satisfy_checks(borrowed("ab"), &length, &mut rng);
+        assert_eq!(short.as_string().unwrap().len(), 4);
+        let long = satisfy_checks(borrowed("abcdefghij"), &length, &mut rng);
+        assert_eq!(long.as_string(), Some("abcdef"));
+
+        let mut like = length.clone();
+        like.push(ParsedCheck::Like {
+            column: "code".to_string(),
+            pattern: "X\\_%".to_string(),
+        });
+        let value = satisfy_checks(borrowed("widget"), &like, &mut rng);
+        let text = value.as_string().unwrap();
+        assert!(text.starts_with("X_") && text.len() == 6, "{}", text);
+        assert!(like_matches(text, "X\\_%"));
+    }
+
+    #[test]
+    fn test_like_matches() {
+        assert!(like_matches("alice@example.com", "%@%.%"));
+        assert!(like_matches("SKU-0042", "SKU-____"));
+        assert!(!like_matches("SKU-42", "SKU-____"));
+        assert!(like_matches("", "%"));
+        assert!(!like_matches("abc", "a%d"));
+        assert!(like_matches("100%", "100\\%"));
+        assert!(!like_matches("1000", "100\\%"));
+    }
+
+    #[test]
+    fn test_satisfy_checks_date_bounds() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(1);
+        let floor = chrono::NaiveDate::from_ymd_opt(2030, 1, 1).unwrap();
+        let checks = vec![ParsedCheck::After {
+            column: "due_on".to_string(),
+            value: floor.and_hms_opt(0, 0, 0).unwrap(),
+            inclusive: false,
+        }];
+        let early = chrono::NaiveDate::from_ymd_opt(2024, 6, 1).unwrap();
+        let Value::Date(d) = satisfy_checks(Value::Date(early), &checks, &mut rng) else {
+            panic!("Expected a date");
+        };
+        assert!(d > floor && d <= floor + ChronoDuration::days(366));
+    }
 }
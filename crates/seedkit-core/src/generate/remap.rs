@@ -0,0 +1,776 @@
+//! # ID Remapping
+//!
+//! Merging several partial (often anonymized) dumps into one environment
+//! fails on primary-key collisions: every dump numbers its `users` from 1.
+//! This module loads dumps — SQL `INSERT` statements, pg_dump's
+//! `COPY ... FROM stdin` blocks, or SeedKit's JSON output — renumbers each
+//! table's integer primary key into one compact range `1..=n`, and rewrites
+//! every foreign key to the new IDs.
+//!
+//! References resolve within the dump they came from. A reference whose
+//! target row isn't in that dump is set to NULL when the column allows it;
+//! otherwise the referencing row is dropped, and rows referencing it in turn.
+//! Tables are processed parents-first in dependency order; references along
+//! cycle-breaking edges are emitted as deferred updates, just like generated
+//! data.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+
+use indexmap::IndexMap;
+use sqlparser::ast::{
+    AssignmentTarget, BinaryOperator, CopySource, CopyTarget, Expr, ObjectName, SetExpr, Statement,
+    TableFactor, TableObject, UnaryOperator, Value as SqlValue,
+};
+use sqlparser::dialect::{Dialect, MySqlDialect, PostgreSqlDialect, SQLiteDialect};
+use sqlparser::parser::Parser;
+use sqlparser::tokenizer::{Token, Tokenizer};
+
+use crate::error::{Result, SeedKitError};
+use crate::generate::engine::{DeferredUpdate, GeneratedData};
+use crate::generate::value::Value;
+use crate::graph::topo::DeferredEdge;
+use crate::schema::types::{DatabaseSchema, DatabaseType, Table};
+
+type Row = IndexMap<String, Value>;
+
+/// What [`remap_ids`] changed beyond renumbering.
+#[derive(Debug, Default)]
+pub struct RemapReport {
+    /// Tables whose primary keys were renumbered.
+    pub remapped_tables: Vec<String>,
+    /// References to rows missing from their dump that were set to NULL.
+    pub nulled_references: usize,
+    /// Rows dropped per table because a required reference was missing.
+    pub dropped_rows: BTreeMap<String, usize>,
+    /// Rows of natural-key tables already present in an earlier dump.
+    pub duplicate_rows: usize,
+    /// Dump tables that aren't in the schema and were left out.
+    pub unknown_tables: Vec<String>,
+}
+
+/// Load a dump file: `.json` in SeedKit's JSON output format, anything else
+/// as SQL. Only `INSERT` statements, `COPY ... FROM stdin` blocks (and
+/// `UPDATE`s setting columns of rows matched by equality, as written for
+/// deferred foreign keys) are read; DDL and session statements are skipped.
+pub fn load_dump(path: &Path, schema: &DatabaseSchema) -> Result<GeneratedData> {
+    let contents = std::fs::read_to_string(path).map_err(|e| SeedKitError::Output {
+        message: format!("Failed to read dump {}", path.display()),
+        source: e,
+    })?;
+    let is_json = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+    let parsed = if is_json {
+        parse_json_dump(&contents)
+    } else {
+        parse_sql_dump(&contents, schema)
+    };
+    parsed.map_err(|message| SeedKitError::Config {
+        message: format!("Failed to parse dump {}: {}", path.display(), message),
+    })
+}
+
+fn parse_json_dump(contents: &str) -> std::result::Result<GeneratedData, String> {
+    let root: serde_json::Map<String, serde_json::Value> =
+        serde_json::from_str(contents).map_err(|e| e.to_string())?;
+    let mut tables: IndexMap<String, Vec<Row>> = IndexMap::new();
+    let mut updates = Vec::new();
+    for (key, entries) in root {
+        let entries = entries
+            .as_array()
+            .ok_or_else(|| format!("'{}' is not an array of rows", key))?;
+        if key == "_deferred_updates" {
+            updates = entries.clone();
+            continue;
+        }
+        let rows = entries
+            .iter()
+            .map(|entry| {
+                let object = entry
+                    .as_object()
+                    .ok_or_else(|| format!("'{}' holds a row that is not an object", key))?;
+                Ok(object
+                    .iter()
+                    .map(|(col, v)| (col.clone(), Value::from_json(v)))
+                    .collect())
+            })
+            .collect::<std::result::Result<Vec<Row>, String>>()?;
+        tables.insert(key, rows);
+    }
+
+    // Fold deferred updates back into their rows
+    for update in updates {
+        let field = |name: &str| update.get(name);
+        let (Some(table), Some(row_index), Some(column), Some(value)) = (
+            field("table").and_then(|v| v.as_str()),
+            field("row_index").and_then(|v| v.as_u64()),
+            field("column").and_then(|v| v.as_str()),
+            field("value"),
+        ) else {
+            return Err("malformed entry in _deferred_updates".to_string());
+        };
+        if let Some(row) = tables
+            .get_mut(table)
+            .and_then(|rows| rows.get_mut(row_index as usize))
+        {
+            row.insert(column.to_string(), Value::from_json(value));
+        }
+    }
+
+    Ok(GeneratedData {
+        tables,
+        deferred_updates: Vec::new(),
+    })
+}
+
+fn parse_sql_dump(
+    contents: &str,
+    schema: &DatabaseSchema,
+) -> std::result::Result<GeneratedData, String> {
+    let dialect: Box<dyn Dialect> = match schema.database_type {
+        DatabaseType::PostgreSQL => Box::new(PostgreSqlDialect {}),
+        DatabaseType::MySQL => Box::new(MySqlDialect {}),
+        DatabaseType::SQLite => Box::new(SQLiteDialect {}),
+    };
+    // COPY data isn't SQL, so its blocks are read before tokenizing the rest
+    let (contents, copy_blocks) = split_copy_blocks(contents);
+    let tokens = Tokenizer::new(dialect.as_ref(), &contents)
+        .tokenize()
+        .map_err(|e| e.to_string())?;
+
+    let mut tables: IndexMap<String, Vec<Row>> = IndexMap::new();
+    for (header, lines) in copy_blocks {
+        let (table_name, columns) = parse_copy_header(header, dialect.as_ref(), schema)?;
+        let table = schema.tables.get(&table_name);
+        let rows = tables.entry(table_name.clone()).or_default();
+        for line in lines {
+            let fields: Vec<&str> = line.split('\t').collect();
+            if fields.len() != columns.len() {
+                return Err(format!(
+                    "COPY into {} has {} values for {} columns",
+                    table_name,
+                    fields.len(),
+                    columns.len()
+                ));
+            }
+            let row = columns
+                .iter()
+                .zip(fields)
+                .map(|(col, field)| {
+                    let integer = table
+                        .and_then(|t| t.columns.get(col))
+                        .is_some_and(|c| c.integer_bounds().is_some());
+                    (col.clone(), copy_field_to_value(field, integer))
+                })
+                .collect();
+            rows.push(row);
+        }
+    }
+    // Split on `;` at the token level so only INSERT and UPDATE statements
+    // need to parse; everything else in a dump is skipped unread.
+    for statement in tokens.split(|t| *t == Token::SemiColon) {
+        let keyword = statement.iter().find_map(|t| match t {
+            Token::Word(w) => Some(w.value.to_ascii_uppercase()),
+            Token::Whitespace(_) => None,
+            _ => Some(String::new()),
+        });
+        if !matches!(keyword.as_deref(), Some("INSERT" | "UPDATE")) {
+            continue;
+        }
+        let parsed = Parser::new(dialect.as_ref())
+            .with_tokens(statement.to_vec())
+            .parse_statement()
+            .map_err(|e| e.to_string())?;
+        match parsed {
+            Statement::Insert(insert) => {
+                let TableObject::TableName(name) = &insert.table else {
+                    continue;
+                };
+                let table_name = last_ident(name)?;
+                let columns = match insert.columns.as_slice() {
+                    [] => schema_columns(schema, &table_name)?,
+                    columns => columns
+                        .iter()
+                        .map(last_ident)
+                        .collect::<std::result::Result<_, _>>()?,
+                };
+                let Some(SetExpr::Values(values)) = insert.source.as_ref().map(|q| q.body.as_ref())
+                else {
+                    return Err(format!("INSERT into {} without VALUES", table_name));
+                };
+                let rows = tables.entry(table_name.clone()).or_default();
+                for exprs in &values.rows {
+                    if exprs.len() != columns.len() {
+                        return Err(format!(
+                            "INSERT into {} has {} values for {} columns",
+                            table_name,
+                            exprs.len(),
+                            columns.len()
+                        ));
+                    }
+                    let row = columns
+                        .iter()
+                        .zip(exprs.iter())
+                        .map(|(col, expr)| Ok((col.clone(), expr_to_value(expr)?)))
+                        .collect::<std::result::Result<Row, String>>()
+                        .map_err(|e| format!("{} in {}", e, table_name))?;
+                    rows.push(row);
+                }
+            }
+            Statement::Update(update) => {
+                let TableFactor::Table { name, .. } = &update.table.relation else {
+                    continue;
+                };
+                let table_name = last_ident(name)?;
+                let mut matches = Vec::new();
+                if let Some(selection) = &update.selection {
+                    equality_terms(selection, &mut matches)?;
+                }
+                let mut assignments = Vec::new();
+                for assignment in &update.assignments {
+                    let AssignmentTarget::ColumnName(column) = &assignment.target else {
+                        return Err(format!("unsupported UPDATE of {}", table_name));
+                    };
+                    assignments.push((last_ident(column)?, expr_to_value(&assignment.value)?));
+                }
+                for row in tables.entry(table_name).or_default() {
+                    if matches.iter().all(|(col, v)| row.get(col) == Some(v)) {
+                        row.extend(assignments.iter().cloned());
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(GeneratedData {
+        tables,
+        deferred_updates: Vec::new(),
+    })
+}
+
+/// Take pg_dump's `COPY ... FROM stdin;` blocks out of a dump. Returns the
+/// SQL around them, and each block's header with its data lines.
+fn split_copy_blocks(contents: &str) -> (String, Vec<(&str, Vec<&str>)>) {
+    let mut sql = String::with_capacity(contents.len());
+    let mut blocks = Vec::new();
+    let mut lines = contents.lines();
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim();
+        let is_copy = trimmed
+            .get(..5)
+            .is_some_and(|start| start.eq_ignore_ascii_case("COPY "))
+            && trimmed.to_ascii_lowercase().ends_with("from stdin;");
+        if !is_copy {
+            sql.push_str(line);
+            sql.push('\n');
+            continue;
+        }
+        let data = lines.by_ref().take_while(|l| *l != "\\.").collect();
+        blocks.push((trimmed.trim_end_matches(';'), data));
+    }
+    (sql, blocks)
+}
+
+/// The table and columns of a `COPY table (columns) FROM stdin` header.
+fn parse_copy_header(
+    header: &str,
+    dialect: &dyn Dialect,
+    schema: &DatabaseSchema,
+) -> std::result::Result<(String, Vec<String>), String> {
+    let parsed = Parser::new(dialect)
+        .try_with_sql(header)
+        .and_then(|mut parser| parser.parse_statement())
+        .map_err(|e| e.to_string())?;
+    let Statement::Copy {
+        source: CopySource::Table {
+            table_name,
+            columns,
+        },
+        to: false,
+        target: CopyTarget::Stdin,
+        ..
+    } = parsed
+    else {
+        return Err(format!("unsupported {}", header));
+    };
+    let table_name = last_ident(&table_name)?;
+    let columns = match columns.as_slice() {
+        [] => schema_columns(schema, &table_name)?,
+        columns => columns.iter().map(|c| c.value.clone()).collect(),
+    };
+    Ok((table_name, columns))
+}
+
+/// Convert a field of COPY's text format: `\N` is NULL, and the backslash
+/// escapes pg_dump writes (control characters and backslash itself) are
+/// decoded. Integer columns are read as numbers so their keys can be
+/// renumbered; other text goes back quoted, like cast literals.
+fn copy_field_to_value(field: &str, integer: bool) -> Value {
+    if field == "\\N" {
+        return Value::Null;
+    }
+    if integer {
+        if let Ok(i) = field.parse() {
+            return Value::Int(i);
+        }
+    }
+    let mut text = String::with_capacity(field.len());
+    let mut chars = field.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            text.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('b') => text.push('\u{8}'),
+            Some('f') => text.push('\u{c}'),
+            Some('n') => text.push('\n'),
+            Some('r') => text.push('\r'),
+            Some('t') => text.push('\t'),
+            Some('v') => text.push('\u{b}'),
+            Some(other) => text.push(other),
+            None => text.push(c),
+        }
+    }
+    Value::String(text.into())
+}
+
+fn last_ident(name: &ObjectName) -> std::result::Result<String, String> {
+    name.0
+        .last()
+        .and_then(|part| part.as_ident())
+        .map(|ident| ident.value.clone())
+        .ok_or_else(|| format!("unsupported name {}", name))
+}
+
+/// Column order of a table, for INSERTs without a column list.
+fn schema_columns(
+    schema: &DatabaseSchema,
+    table: &str,
+) -> std::result::Result<Vec<String>, String> {
+    let table = schema
+        .tables
+        .get(table)
+        .ok_or_else(|| format!("INSERT into {} has no column list", table))?;
+    let mut columns: Vec<_> = table.columns.values().collect();
+    columns.sort_by_key(|c| c.ordinal_position);
+    Ok(columns.iter().map(|c| c.name.clone()).collect())
+}
+
+/// Collect `column = literal` terms of an `AND` chain.
+fn equality_terms(
+    expr: &Expr,
+    terms: &mut Vec<(String, Value)>,
+) -> std::result::Result<(), String> {
+    match expr {
+        Expr::Nested(inner) => equality_terms(inner, terms),
+        Expr::BinaryOp {
+            left,
+            op: BinaryOperator::And,
+            right,
+        } => {
+            equality_terms(left, terms)?;
+            equality_terms(right, terms)
+        }
+        Expr::BinaryOp {
+            left,
+            op: BinaryOperator::Eq,
+            right,
+        } => {
+            let column = match left.as_ref() {
+                Expr::Identifier(ident) => ident.value.clone(),
+                Expr::CompoundIdentifier(parts) => {
+                    parts.last().map(|i| i.value.clone()).unwrap_or_default()
+                }
+                other => return Err(format!("unsupported UPDATE condition {}", other)),
+            };
+            terms.push((column, expr_to_value(right)?));
+            Ok(())
+        }
+        other => Err(format!("unsupported UPDATE condition {}", other)),
+    }
+}
+
+/// Convert a literal from a dump into a value. Casts are dropped: the text
+/// is written back quoted and the database converts it on insert again.
+fn expr_to_value(expr: &Expr) -> std::result::Result<Value, String> {
+    match expr {
+        Expr::Nested(inner) | Expr::Cast { expr: inner, .. } => expr_to_value(inner),
+        Expr::Prefixed { value, .. } => expr_to_value(value),
+        Expr::Value(value) => Ok(match &value.value {
+            SqlValue::Number(text, _) => match text.parse::<i64>() {
+                Ok(i) => Value::Int(i),
+                Err(_) => text
+                    .parse::<f64>()
+                    .map(Value::Float)
+                    .unwrap_or_else(|_| Value::String(text.clone().into())),
+            },
+            SqlValue::Boolean(b) => Value::Bool(*b),
+            SqlValue::Null => Value::Null,
+            other => match other.clone().into_string() {
+                Some(text) => Value::String(text.into()),
+                None => return Err(format!("unsupported value {}", other)),
+            },
+        }),
+        Expr::UnaryOp {
+            op: UnaryOperator::Minus,
+            expr,
+        } => match expr_to_value(expr)? {
+            Value::Int(i) => Ok(Value::Int(-i)),
+            Value::Float(f) => Ok(Value::Float(-f)),
+            _ => Err(format!("unsupported value {}", expr)),
+        },
+        Expr::Array(array) => Ok(Value::Array(
+            array
+                .elem
+                .iter()
+                .map(expr_to_value)
+                .collect::<std::result::Result<_, _>>()?,
+        )),
+        other => Err(format!("unsupported value {}", other)),
+    }
+}
+
+/// Merge `dumps` and renumber integer primary keys per table, rewriting
+/// foreign keys to match. `insertion_order` and `deferred` come from the
+/// schema's dependency graph after breaking cycles.
+///
+/// Rows without their primary key (auto-increment IDs left out, as in
+/// SeedKit's own output) are taken to be numbered by position from 1.
+pub fn remap_ids(
+    dumps: &[GeneratedData],
+    schema: &DatabaseSchema,
+    insertion_order: &[String],
+    deferred: &[DeferredEdge],
+) -> (GeneratedData, RemapReport) {
+    let mut report = RemapReport::default();
+    let remapped: HashMap<&str, &str> = schema
+        .tables
+        .iter()
+        .filter_map(|(name, table)| Some((name.as_str(), remappable_key(table)?)))
+        .collect();
+    let is_late = |table: &str, column: &str| {
+        deferred
+            .iter()
+            .any(|e| e.source_table == table && e.source_columns.iter().any(|c| c == column))
+    };
+
+    for dump in dumps {
+        for name in dump.tables.keys() {
+            if !schema.tables.contains_key(name) && !report.unknown_tables.contains(name) {
+                report.unknown_tables.push(name.clone());
+            }
+        }
+    }
+
+    // (dump, table, old id) -> new id
+    let mut id_map: HashMap<(usize, &str, i64), i64> = HashMap::new();
+    // (table, output row, column, dump, referenced table, old id)
+    let mut pending: Vec<(&str, usize, &str, usize, &str, i64)> = Vec::new();
+    let mut out = GeneratedData {
+        tables: IndexMap::new(),
+        deferred_updates: Vec::new(),
+    };
+
+    for table_name in insertion_order {
+        let Some(table) = schema.tables.get(table_name) else {
+            continue;
+        };
+        let pk_column = remapped.get(table_name.as_str()).copied();
+        let natural_key: Option<&[String]> = match (pk_column, &table.primary_key) {
+            (None, Some(pk)) => Some(&pk.columns),
+            _ => None,
+        };
+        let mut seen_keys: HashSet<String> = HashSet::new();
+        let mut rows: Vec<Row> = Vec::new();
+        let mut next_id = 1i64;
+
+        for (dump_index, dump) in dumps.iter().enumerate() {
+            let Some(dump_rows) = dump.tables.get(table_name) else {
+                continue;
+            };
+            'rows: for (position, row) in dump_rows.iter().enumerate() {
+                let mut row = row.clone();
+
+                for fk in &table.foreign_keys {
+                    let [column] = fk.source_columns.as_slice() else {
+                        continue;
+                    };
+                    let target_key = remapped.get(fk.referenced_table.as_str()).copied();
+                    if fk.referenced_columns.len() != 1
+                        || target_key != Some(fk.referenced_columns[0].as_str())
+                    {
+                        continue;
+                    }
+                    let Some(old) = row.get(column).and_then(Value::as_int) else {
+                        continue;
+                    };
+                    let nullable = table.columns.get(column).is_none_or(|c| c.nullable);
+                    if is_late(table_name, column) {
+                        pending.push((
+                            table_name.as_str(),
+                            rows.len(),
+                            column.as_str(),
+                            dump_index,
+                            fk.referenced_table.as_str(),
+                            old,
+                        ));
+                        if nullable {
+                            row.insert(column.clone(), Value::Null);
+                        }
+                        continue;
+                    }
+                    match id_map.get(&(dump_index, fk.referenced_table.as_str(), old)) {
+                        Some(&new) => {
+                            row.insert(column.clone(), Value::Int(new));
+                        }
+                        None if nullable => {
+                            row.insert(column.clone(), Value::Null);
+                            report.nulled_references += 1;
+                        }
+                        None => {
+                            *report.dropped_rows.entry(table_name.clone()).or_default() += 1;
+                            continue 'rows;
+                        }
+                    }
+                }
+
+                if let Some(pk_column) = pk_column {
+                    let old = row
+                        .get(pk_column)
+                        .and_then(Value::as_int)
+                        .unwrap_or(position as i64 + 1);
+                    id_map.insert((dump_index, table_name.as_str(), old), next_id);
+                    row.insert(pk_column.to_string(), Value::Int(next_id));
+                    next_id += 1;
+                } else if let Some(key_columns) = natural_key {
+                    let key: Vec<String> = key_columns
+                        .iter()
+                        .map(|c| row.get(c).map(Value::to_unique_key).unwrap_or_default())
+                        .collect();
+                    if !seen_keys.insert(key.join("\u{1f}")) {
+                        report.duplicate_rows += 1;
+                        continue;
+                    }
+                }
+                rows.push(row);
+            }
+        }
+
+        if pk_column.is_some() && !rows.is_empty() {
+            report.remapped_tables.push(table_name.clone());
+        }
+        if !rows.is_empty() {
+            out.tables.insert(table_name.clone(), rows);
+        }
+    }
+
+    // Cycle-breaking references resolve once every table has its new IDs
+    for (table_name, row_index, column, dump_index, target, old) in pending {
+        let nullable = schema
+            .tables
+            .get(table_name)
+            .and_then(|t| t.columns.get(column))
+            .is_none_or(|c| c.nullable);
+        match id_map.get(&(dump_index, target, old)) {
+            Some(&new) if nullable => out.deferred_updates.push(DeferredUpdate {
+                table_name: table_name.to_string(),
+                row_index,
+                column_name: column.to_string(),
+                value: Value::Int(new),
+            }),
+            Some(&new) => {
+                if let Some(row) = out
+                    .tables
+                    .get_mut(table_name)
+                    .and_then(|rows| rows.get_mut(row_index))
+                {
+                    row.insert(column.to_string(), Value::Int(new));
+                }
+            }
+            None if nullable => report.nulled_references += 1,
+            None => tracing::warn!(
+                "{}.{} references missing row {} and can't be NULL; left unchanged",
+                table_name,
+                column,
+                old
+            ),
+        }
+    }
+
+    (out, report)
+}
+
+/// The table's single-column integer primary key, which can be renumbered.
+fn remappable_key(table: &Table) -> Option<&str> {
+    let pk = table.primary_key.as_ref()?;
+    let [column] = pk.columns.as_slice() else {
+        return None;
+    };
+    table
+        .columns
+        .get(column)?
+        .integer_bounds()
+        .map(|_| column.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::cycle::break_cycles;
+    use crate::graph::dag::DependencyGraph;
+    use crate::graph::topo::topological_sort;
+    use crate::schema::types::*;
+
+    fn int_column(name: &str, nullable: bool) -> Column {
+        let mut col = Column::new(name.to_string(), DataType::Integer, "integer".to_string());
+        col.nullable = nullable;
+        col
+    }
+
+    fn fk(column: &str, target: &str) -> ForeignKey {
+        ForeignKey {
+            name: None,
+            source_columns: vec![column.to_string()],
+            referenced_table: target.to_string(),
+            referenced_columns: vec!["id".to_string()],
+            on_delete: ForeignKeyAction::NoAction,
+            on_update: ForeignKeyAction::NoAction,
+            is_deferrable: false,
+        }
+    }
+
+    fn schema() -> DatabaseSchema {
+        let mut schema = DatabaseSchema::new(DatabaseType::PostgreSQL, "test".to_string());
+        let mut users = Table::new("users".to_string());
+        users
+            .columns
+            .insert("id".to_string(), int_column("id", false));
+        users
+            .columns
+            .insert("referred_by".to_string(), int_column("referred_by", true));
+        users.columns.insert(
+            "email".to_string(),
+            Column::new("email".to_string(), DataType::Text, "text".to_string()),
+        );
+        users.primary_key = Some(PrimaryKey {
+            columns: vec!["id".to_string()],
+            name: None,
+        });
+        users.foreign_keys.push(fk("referred_by", "users"));
+
+        let mut orders = Table::new("orders".to_string());
+        orders
+            .columns
+            .insert("id".to_string(), int_column("id", false));
+        orders
+            .columns
+            .insert("user_id".to_string(), int_column("user_id", false));
+        orders.primary_key = users.primary_key.clone();
+        orders.foreign_keys.push(fk("user_id", "users"));
+
+        schema.tables.insert("users".to_string(), users);
+        schema.tables.insert("orders".to_string(), orders);
+        schema
+    }
+
+    fn remap(schema: &DatabaseSchema, dumps: &[GeneratedData]) -> (GeneratedData, RemapReport) {
+        let mut graph = DependencyGraph::from_schema(schema);
+        let deferred = break_cycles(&mut graph, &[]).unwrap();
+        let order = topological_sort(&graph).unwrap();
+        remap_ids(dumps, schema, &order.tables, &deferred)
+    }
+
+    #[test]
+    fn test_load_sql_dump() {
+        let schema = schema();
+        let sql = "BEGIN;\n\
+            SET session_replication_role = replica;\n\
+            CREATE TABLE ignored (x int);\n\
+            INSERT INTO \"users\" (\"id\", \"referred_by\", \"email\") VALUES \
+              (10, NULL, 'a@x.io'), (11, 10, 'semi;colon@x.io');\n\
+            INSERT INTO public.orders VALUES (7, 11), (8, -3);\n\
+            UPDATE \"users\" SET \"referred_by\" = 11 WHERE \"id\" = 10;\n\
+            COMMIT;";
+        let data = parse_sql_dump(sql, &schema).unwrap();
+        let users = &data.tables["users"];
+        assert_eq!(users.len(), 2);
+        assert_eq!(users[0]["referred_by"], Value::Int(11));
+        assert_eq!(users[1]["email"].as_string(), Some("semi;colon@x.io"));
+        assert_eq!(data.tables["orders"][1]["user_id"], Value::Int(-3));
+        assert!(!data.tables.contains_key("ignored"));
+    }
+
+    #[test]
+    fn test_load_pg_dump_copy_blocks() {
+        let schema = schema();
+        let sql = "SET client_encoding = 'UTF8';\n\
+            COPY public.users (id, referred_by, email) FROM stdin;\n\
+            10\t\\N\tit's\\ta@x.io\n\
+            11\t10\tb@x.io\n\
+            \\.\n\
+            COPY public.orders FROM stdin;\n\
+            7\t11\n\
+            \\.\n\
+            SELECT pg_catalog.setval('public.users_id_seq', 11, true);\n";
+        let data = parse_sql_dump(sql, &schema).unwrap();
+        let users = &data.tables["users"];
+        assert_eq!(users.len(), 2);
+        assert_eq!(users[0]["id"], Value::Int(10));
+        assert_eq!(users[0]["referred_by"], Value::Null);
+        assert_eq!(users[0]["email"].as_string(), Some("it's\ta@x.io"));
+        assert_eq!(users[1]["referred_by"], Value::Int(10));
+        assert_eq!(data.tables["orders"][0]["user_id"], Value::Int(11));
+    }
+
+    #[test]
+    fn test_load_json_dump_applies_deferred_updates() {
+        let json = r#"{
+            "users": [{"id": 1, "referred_by": null, "email": "a@x.io"}],
+            "_deferred_updates": [{"table": "users", "row_index": 0, "column": "referred_by", "value": 1}]
+        }"#;
+        let data = parse_json_dump(json).unwrap();
+        assert_eq!(data.tables["users"][0]["referred_by"], Value::Int(1));
+    }
+
+    #[test]
+    fn test_remap_merges_dumps_into_one_id_range() {
+        let schema = schema();
+        let first = parse_sql_dump(
+            "INSERT INTO users (id, referred_by, email) VALUES (1, NULL, 'a'), (2, 1, 'b');\n\
+             INSERT INTO orders (id, user_id) VALUES (1, 2), (2, 1);",
+            &schema,
+        )
+        .unwrap();
+        let second = parse_sql_dump(
+            "INSERT INTO users (id, referred_by, email) VALUES (1, 99, 'c');\n\
+             INSERT INTO orders (id, user_id) VALUES (5, 1), (6, 42);",
+            &schema,
+        )
+        .unwrap();
+
+        let (data, report) = remap(&schema, &[first, second]);
+        let users = &data.tables["users"];
+        let ids: Vec<_> = users.iter().map(|r| r["id"].clone()).collect();
+        assert_eq!(ids, [Value::Int(1), Value::Int(2), Value::Int(3)]);
+
+        // Orders follow their users; the order for missing user 42 is dropped
+        let orders = &data.tables["orders"];
+        let pairs: Vec<_> = orders
+            .iter()
+            .map(|r| (r["id"].as_int().unwrap(), r["user_id"].as_int().unwrap()))
+            .collect();
+        assert_eq!(pairs, [(1, 2), (2, 1), (3, 3)]);
+        assert_eq!(report.dropped_rows.get("orders"), Some(&1));
+
+        // Self-references are deferred; the one to missing user 99 is nulled
+        assert!(users.iter().all(|r| r["referred_by"].is_null()));
+        assert_eq!(data.deferred_updates.len(), 1);
+        let update = &data.deferred_updates[0];
+        assert_eq!((update.row_index, &update.value), (1, &Value::Int(1)));
+        assert_eq!(report.nulled_references, 1);
+        assert_eq!(report.remapped_tables, ["users", "orders"]);
+    }
+}
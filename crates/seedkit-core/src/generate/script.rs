@@ -0,0 +1,309 @@
+//! # Row Scripts
+//!
+//! Power users can post-process every generated row of a table with a
+//! [Rhai](https://rhai.rs) script configured in `seedkit.toml`:
+//!
+//! ```toml
+//! [tables.orders]
+//! script = "./scripts/orders.rhai"
+//! ```
+//!
+//! The script sees the row as a mutable object map `row`, plus `row_index`
+//! and `table`, and changes values in place:
+//!
+//! ```rhai
+//! if row.status == "refunded" { row.refunded_total = row.total; }
+//! row.total = row.subtotal + row.tax;
+//! ```
+//!
+//! Scripts run after the row's values are generated, before child tables
+//! pick up its keys, so foreign keys see the final values. They have no
+//! randomness or I/O, keeping output deterministic under the seed. Values
+//! without a Rhai counterpart (timestamps, UUIDs, ...) arrive as strings and
+//! keep their type unless the script changes them. `print` goes to the log,
+//! not to the generated output.
+//!
+//! Rhai is the `rhai` cargo feature (on by default). Without it, a plan
+//! with a row script fails to load.
+
+#[cfg(feature = "rhai")]
+use std::collections::HashMap;
+#[cfg(feature = "rhai")]
+use std::path::PathBuf;
+
+use indexmap::IndexMap;
+#[cfg(feature = "rhai")]
+use rhai::{Array, Dynamic, Engine, Map, Scope, AST};
+
+use crate::error::{Result, SeedKitError};
+use crate::generate::plan::GenerationPlan;
+use crate::generate::value::Value;
+use crate::schema::types::DatabaseSchema;
+
+/// Operations a script may run per row before it is stopped.
+#[cfg(feature = "rhai")]
+const MAX_OPERATIONS_PER_ROW: u64 = 1_000_000;
+
+/// The compiled row scripts of a plan, by table.
+#[cfg(feature = "rhai")]
+pub struct RowScripts {
+    engine: Engine,
+    scripts: HashMap<String, RowScript>,
+}
+
+#[cfg(feature = "rhai")]
+struct RowScript {
+    path: String,
+    ast: AST,
+    /// Columns the script may set: the table's columns in the schema.
+    columns: Vec<String>,
+}
+
+#[cfg(feature = "rhai")]
+impl RowScripts {
+    /// Compile the row script of every table in the plan that has one.
+    pub fn load(plan: &GenerationPlan, schema: &DatabaseSchema) -> Result<Self> {
+        let mut engine = Engine::new();
+        engine.set_max_operations(MAX_OPERATIONS_PER_ROW);
+        engine.on_print(|text| tracing::info!("row script: {}", text));
+        engine.on_debug(|text, _, pos| tracing::debug!("row script {}: {}", pos, text));
+
+        let mut scripts = HashMap::new();
+        for table_plan in &plan.table_plans {
+            let Some(ref path) = table_plan.row_script else {
+                continue;
+            };
+            let ast = engine
+                .compile_file(PathBuf::from(path))
+                .map_err(|e| script_error(path, e))?;
+            let columns = schema
+                .tables
+                .get(&table_plan.table_name)
+                .map(|t| t.columns.keys().cloned().collect())
+                .unwrap_or_default();
+            scripts.insert(
+                table_plan.table_name.clone(),
+                RowScript {
+                    path: path.clone(),
+                    ast,
+                    columns,
+                },
+            );
+        }
+        Ok(Self { engine, scripts })
+    }
+
+    /// Run `table`'s script, if any, over `row`.
+    pub fn apply(
+        &self,
+        table: &str,
+        row_index: usize,
+        row: &mut IndexMap<String, Value>,
+    ) -> Result<()> {
+        let Some(script) = self.scripts.get(table) else {
+            return Ok(());
+        };
+
+        let map: Map = row
+            .iter()
+            .map(|(name, value)| (name.as_str().into(), to_dynamic(value)))
+            .collect();
+        let mut scope = Scope::new();
+        scope.push("row", map);
+        scope.push_constant("row_index", row_index as i64);
+        scope.push_constant("table", table.to_string());
+        self.engine
+            .run_ast_with_scope(&mut scope, &script.ast)
+            .map_err(|e| script_error(&script.path, format!("row {}: {}", row_index, e)))?;
+
+        let map = scope.get_value::<Map>("row").ok_or_else(|| {
+            script_error(
+                &script.path,
+                format!("row {}: `row` is no longer an object map", row_index),
+            )
+        })?;
+        for (name, dynamic) in map {
+            let name = name.to_string();
+            match row.get_mut(&name) {
+                // Unchanged values keep their original type
+                Some(value) if to_dynamic(value).to_string() == dynamic.to_string() => {}
+                Some(value) => *value = from_dynamic(dynamic),
+                None if script.columns.contains(&name) => {
+                    row.insert(name, from_dynamic(dynamic));
+                }
+                None => {
+                    return Err(script_error(
+                        &script.path,
+                        format!("row {}: {}.{} is not a column", row_index, table, name),
+                    ))
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Without Rhai there is nothing to run: no table may have a row script.
+#[cfg(not(feature = "rhai"))]
+pub struct RowScripts;
+
+#[cfg(not(feature = "rhai"))]
+impl RowScripts {
+    pub fn load(plan: &GenerationPlan, _schema: &DatabaseSchema) -> Result<Self> {
+        match plan.table_plans.iter().find_map(|t| t.row_script.as_ref()) {
+            Some(path) => Err(script_error(
+                path,
+                "SeedKit was built without the `rhai` feature",
+            )),
+            None => Ok(Self),
+        }
+    }
+
+    pub fn apply(
+        &self,
+        _table: &str,
+        _row_index: usize,
+        _row: &mut IndexMap<String, Value>,
+    ) -> Result<()> {
+        Ok(())
+    }
+}
+
+fn script_error(path: &str, message: impl std::fmt::Display) -> SeedKitError {
+    SeedKitError::RowScript {
+        path: path.to_string(),
+        message: message.to_string(),
+    }
+}
+
+#[cfg(feature = "rhai")]
+pub(crate) fn to_dynamic(value: &Value) -> Dynamic {
+    match value {
+        Value::Null => Dynamic::UNIT,
+        Value::Bool(b) => Dynamic::from(*b),
+        Value::Int(n) => Dynamic::from(*n),
+        Value::Float(f) => Dynamic::from(*f),
+        Value::String(s) => Dynamic::from(s.to_string()),
+        Value::Array(items) => Dynamic::from(items.iter().map(to_dynamic).collect::<Array>()),
+        other => Dynamic::from(other.to_csv_string()),
+    }
+}
+
+#[cfg(feature = "rhai")]
+pub(crate) fn from_dynamic(dynamic: Dynamic) -> Value {
+    if dynamic.is_unit() {
+        Value::Null
+    } else if let Ok(b) = dynamic.as_bool() {
+        Value::Bool(b)
+    } else if let Ok(n) = dynamic.as_int() {
+        Value::Int(n)
+    } else if let Ok(f) = dynamic.as_float() {
+        Value::Float(f)
+    } else if dynamic.is_array() {
+        let items = dynamic.cast::<Array>();
+        Value::Array(items.into_iter().map(from_dynamic).collect())
+    } else if dynamic.is_map() {
+        Value::Json(to_json(dynamic))
+    } else {
+        Value::String(dynamic.to_string().into())
+    }
+}
+
+#[cfg(feature = "rhai")]
+fn to_json(dynamic: Dynamic) -> serde_json::Value {
+    if dynamic.is_unit() {
+        serde_json::Value::Null
+    } else if let Ok(b) = dynamic.as_bool() {
+        serde_json::Value::Bool(b)
+    } else if let Ok(n) = dynamic.as_int() {
+        n.into()
+    } else if let Ok(f) = dynamic.as_float() {
+        f.into()
+    } else if dynamic.is_array() {
+        serde_json::Value::Array(dynamic.cast::<Array>().into_iter().map(to_json).collect())
+    } else if dynamic.is_map() {
+        serde_json::Value::Object(
+            dynamic
+                .cast::<Map>()
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), to_json(v)))
+                .collect(),
+        )
+    } else {
+        serde_json::Value::String(dynamic.to_string())
+    }
+}
+
+#[cfg(all(test, feature = "rhai"))]
+mod tests {
+    use super::*;
+    use crate::generate::plan::{single_column_plan, GenerationStrategy};
+    use crate::schema::types::{Column, DataType, DatabaseType, Table};
+
+    fn load_scripts(source: &str) -> (tempfile::TempDir, RowScripts) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("orders.rhai");
+        std::fs::write(&path, source).unwrap();
+
+        let mut schema = DatabaseSchema::new(DatabaseType::PostgreSQL, "test".to_string());
+        let mut table = Table::new("orders".to_string());
+        for name in ["status", "total", "refunded_total", "placed_at"] {
+            let col = Column::new(name.to_string(), DataType::Text, "text".to_string());
+            table.columns.insert(name.to_string(), col);
+        }
+        schema.tables.insert("orders".to_string(), table);
+
+        let mut plan =
+            single_column_plan("orders", "status", GenerationStrategy::SemanticProvider, 1);
+        plan.table_plans[0].row_script = Some(path.to_string_lossy().into_owned());
+        let scripts = RowScripts::load(&plan, &schema).unwrap();
+        (dir, scripts)
+    }
+
+    fn order() -> IndexMap<String, Value> {
+        let placed_at = chrono::NaiveDate::from_ymd_opt(2025, 6, 15)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap();
+        IndexMap::from([
+            ("status".to_string(), Value::String("refunded".into())),
+            ("total".to_string(), Value::Float(12.5)),
+            ("placed_at".to_string(), Value::Timestamp(placed_at)),
+        ])
+    }
+
+    #[test]
+    fn test_script_mutates_and_derives_columns() {
+        let (_dir, scripts) = load_scripts(
+            r#"
+            if row.status == "refunded" { row.refunded_total = row.total; }
+            row.total = row.total * 2.0 + row_index;
+            "#,
+        );
+        let mut row = order();
+        let placed_at = row["placed_at"].clone();
+        scripts.apply("orders", 3, &mut row).unwrap();
+
+        assert_eq!(row["total"], Value::Float(28.0));
+        assert_eq!(row["refunded_total"], Value::Float(12.5));
+        assert_eq!(
+            row["placed_at"], placed_at,
+            "untouched values keep their type"
+        );
+
+        let mut other = order();
+        scripts.apply("customers", 0, &mut other).unwrap();
+        assert_eq!(other, order());
+    }
+
+    #[test]
+    fn test_script_errors_name_the_row() {
+        let (_dir, scripts) = load_scripts("row.discount = 5;");
+        let err = scripts.apply("orders", 7, &mut order()).unwrap_err();
+        assert!(err.to_string().contains("orders.discount"), "{}", err);
+
+        let (_dir, scripts) = load_scripts("loop {}");
+        let err = scripts.apply("orders", 7, &mut order()).unwrap_err();
+        assert!(err.to_string().contains("row 7"), "{}", err);
+    }
+}
@@ -0,0 +1,66 @@
+//! # Seed Derivation
+//!
+//! The engine draws from a few independent random streams: one per table,
+//! one per custom provider column, and one per column whose values repeat.
+//! (Plans replaying lock files written before per-table streams share one
+//! stream among all tables but stable ones.)
+//! A [`SeedDerivation`] decides how each stream's seed follows from the
+//! run seed. The engine itself always uses `StdRng`.
+//!
+//! Library users can plug in their own scheme to give every tenant its own
+//! reproducible dataset, generated independently on any machine:
+//!
+//! ```
+//! use std::sync::Arc;
+//! use seedkit_core::generate::engine::stable_table_seed;
+//! use seedkit_core::generate::seed::SeedDerivation;
+//!
+//! #[derive(Debug)]
+//! struct TenantSeeds {
+//!     tenant_id: String,
+//! }
+//!
+//! impl SeedDerivation for TenantSeeds {
+//!     fn run_seed(&self, seed: u64) -> u64 {
+//!         stable_table_seed(seed, &self.tenant_id)
+//!     }
+//!
+//!     fn derive(&self, seed: u64, scope: &str) -> u64 {
+//!         stable_table_seed(self.run_seed(seed), scope)
+//!     }
+//! }
+//!
+//! // plan.set_seed_derivation(Arc::new(TenantSeeds { tenant_id: "acme".into() }));
+//! # let _: Arc<dyn SeedDerivation> = Arc::new(TenantSeeds { tenant_id: "acme".into() });
+//! ```
+
+use std::fmt;
+
+use crate::generate::engine::stable_table_seed;
+
+/// How the engine's RNG seeds follow from the plan's seed.
+///
+/// Implementations must be pure functions of their inputs (and their own
+/// fixed state), or generation stops being reproducible.
+pub trait SeedDerivation: fmt::Debug + Send + Sync {
+    /// Seed for the RNG shared by all non-stable tables when
+    /// `GenerationPlan::table_streams` is off, and by deferred references.
+    fn run_seed(&self, seed: u64) -> u64 {
+        seed
+    }
+
+    /// Seed for the independent stream named `scope`: a table's name, or
+    /// `table.column` for a custom provider or a column's repeated values.
+    fn derive(&self, seed: u64, scope: &str) -> u64;
+}
+
+/// The built-in scheme: the run seed as is, and a SHA-256 of the run seed
+/// and scope name for everything else.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultSeedDerivation;
+
+impl SeedDerivation for DefaultSeedDerivation {
+    fn derive(&self, seed: u64, scope: &str) -> u64 {
+        stable_table_seed(seed, scope)
+    }
+}
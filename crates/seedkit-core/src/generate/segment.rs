@@ -0,0 +1,167 @@
+//! # Population Segments
+//!
+//! Real user bases aren't uniform: a few power users place most of the
+//! orders while many accounts sit dormant. Segments split a table's rows
+//! into groups with their own column values and their own share of the
+//! child rows that reference them:
+//!
+//! ```toml
+//! [tables.users.segments.power]
+//! share = 0.05                              # 5% of users
+//! activity = 50                             # 50x the orders of a regular user
+//! columns = { plan = ["pro", "enterprise"] }
+//!
+//! [tables.users.segments.dormant]
+//! share = 0.2
+//! activity = 0                              # never referenced
+//! columns = { status = ["inactive"] }
+//! ```
+//!
+//! Each segment gets `share` of the rows, rounded, picked at random; the
+//! rest are regular rows with an activity of 1. A segment row's `columns`
+//! take one of the listed values. Foreign keys referencing the table pick
+//! its rows in proportion to their activity, on top of any `zipf` fan-out,
+//! and sampled fan-outs are scaled by it; `children = [min, max]` bounds
+//! still hold every parent to their limits.
+
+use std::collections::BTreeMap;
+
+use indexmap::IndexMap;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::Deserialize;
+
+use crate::generate::value::Value;
+
+/// One of a table's `segments` from `seedkit.toml`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Segment {
+    /// Share of the table's rows in the segment.
+    pub share: f64,
+    /// How many child rows reference a row of the segment, relative to a
+    /// regular row.
+    #[serde(default = "default_activity")]
+    pub activity: f64,
+    /// Values the segment's rows take, by column.
+    #[serde(default)]
+    pub columns: BTreeMap<String, Vec<String>>,
+}
+
+fn default_activity() -> f64 {
+    1.0
+}
+
+/// Check a table's segments: shares between 0 and 1 adding up to at most
+/// 1, non-negative activities, and a value for every column.
+pub fn validate_segments(segments: &BTreeMap<String, Segment>) -> Result<(), String> {
+    for (name, segment) in segments {
+        if !(0.0..=1.0).contains(&segment.share) {
+            return Err(format!(
+                "{}: share {} is not between 0 and 1",
+                name, segment.share
+            ));
+        }
+        if !segment.activity.is_finite() || segment.activity < 0.0 {
+            return Err(format!(
+                "{}: activity {} is not a number of at least 0",
+                name, segment.activity
+            ));
+        }
+        if let Some((column, _)) = segment.columns.iter().find(|(_, v)| v.is_empty()) {
+            return Err(format!(
+                "{}: columns.{} needs at least one value",
+                name, column
+            ));
+        }
+    }
+    let total: f64 = segments.values().map(|s| s.share).sum();
+    if total > 1.0 + 1e-9 {
+        return Err(format!("shares add up to {}, more than 1", total));
+    }
+    Ok(())
+}
+
+/// A segment resolved against the schema.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SegmentPlan {
+    pub name: String,
+    pub share: f64,
+    pub activity: f64,
+    /// Columns of the table the segment sets, with their values.
+    pub columns: Vec<(String, Vec<String>)>,
+}
+
+/// The segment of each of `row_count` rows (`None` for regular rows):
+/// `share` of the rows, rounded, per segment, in random order.
+pub fn assign_segments(
+    segments: &[SegmentPlan],
+    row_count: usize,
+    rng: &mut impl Rng,
+) -> Vec<Option<usize>> {
+    let mut assigned = Vec::with_capacity(row_count);
+    for (index, segment) in segments.iter().enumerate() {
+        let rows = (segment.share * row_count as f64).round() as usize;
+        let rows = rows.min(row_count - assigned.len());
+        assigned.extend(std::iter::repeat_n(Some(index), rows));
+    }
+    assigned.resize(row_count, None);
+    assigned.shuffle(rng);
+    assigned
+}
+
+impl SegmentPlan {
+    /// Set the segment's columns in `row`.
+    pub fn apply(&self, row: &mut IndexMap<String, Value>, rng: &mut impl Rng) {
+        for (column, values) in &self.columns {
+            let value = &values[rng.random_range(0..values.len())];
+            row.insert(column.clone(), Value::String(value.clone().into()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_segments() {
+        let segments: BTreeMap<String, Segment> = toml::from_str(
+            r#"
+power = { share = 0.05, activity = 50, columns = { plan = ["pro"] } }
+dormant = { share = 0.2, activity = 0 }
+"#,
+        )
+        .unwrap();
+        assert!(validate_segments(&segments).is_ok());
+        assert_eq!(segments["dormant"].columns.len(), 0);
+
+        let mut too_many = segments.clone();
+        too_many.get_mut("dormant").unwrap().share = 0.99;
+        assert!(validate_segments(&too_many)
+            .unwrap_err()
+            .contains("more than 1"));
+        let mut negative = segments.clone();
+        negative.get_mut("power").unwrap().activity = -1.0;
+        assert!(validate_segments(&negative).is_err());
+
+        let plans: Vec<SegmentPlan> = segments
+            .iter()
+            .map(|(name, s)| SegmentPlan {
+                name: name.clone(),
+                share: s.share,
+                activity: s.activity,
+                columns: s.columns.clone().into_iter().collect(),
+            })
+            .collect();
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+        let assigned = assign_segments(&plans, 1000, &mut rng);
+        assert_eq!(assigned.len(), 1000);
+        let count = |segment| assigned.iter().filter(|s| **s == segment).count();
+        assert_eq!(count(Some(0)), 200);
+        assert_eq!(count(Some(1)), 50);
+        assert_eq!(count(None), 750);
+        // Spread over the table, not bunched at the start
+        assert!(assigned[..100].contains(&None));
+    }
+}
@@ -0,0 +1,206 @@
+//! # Sequence Offsets
+//!
+//! The engine synthesizes auto-increment IDs as `1..=row_count` so child
+//! tables can reference rows before the database has assigned real IDs.
+//! When the target table already has rows, the database hands out IDs after
+//! the existing ones and those references point at the wrong rows.
+//!
+//! This module reads each auto-increment primary key's high-water mark
+//! (the larger of `MAX(id)` and the sequence's last value) so the plan can
+//! shift its synthesized IDs to line up with what the database will assign.
+
+use std::collections::BTreeMap;
+
+use crate::error::{Result, SeedKitError};
+use crate::output::direct::quote_identifier;
+use crate::schema::types::{DatabaseSchema, DatabaseType, Table};
+
+/// The table's single-column auto-increment primary key, if it has one.
+/// Composite and natural keys are generated as values, not synthesized.
+pub fn auto_increment_pk(table: &Table) -> Option<&str> {
+    let pk = table.primary_key.as_ref()?;
+    let [column] = pk.columns.as_slice() else {
+        return None;
+    };
+    let col = table.columns.get(column)?;
+    (col.is_auto_increment || col.data_type.is_serial()).then_some(column.as_str())
+}
+
+/// SQL returning the last ID already used by `table.column`, or 0 when the
+/// table is empty and its sequence untouched.
+///
+/// SQLite reads only `MAX(column)`: without `AUTOINCREMENT` that is exactly
+/// where the next rowid starts, and `sqlite_sequence` is queried separately
+/// because it only exists once an `AUTOINCREMENT` table does.
+pub fn sequence_offset_query(table: &str, column: &str, db_type: DatabaseType) -> String {
+    let qt = quote_identifier(table, &db_type);
+    let qc = quote_identifier(column, &db_type);
+    let table_literal = table.replace('\'', "''");
+    let column_literal = column.replace('\'', "''");
+    match db_type {
+        DatabaseType::PostgreSQL => format!(
+            "SELECT GREATEST(\
+             COALESCE((SELECT MAX({col}) FROM {tbl}), 0), \
+             COALESCE((SELECT last_value FROM pg_sequences \
+             WHERE format('%I.%I', schemaname, sequencename) \
+             = pg_get_serial_sequence('{tbl_lit}', '{col_lit}')), 0))::bigint AS offset_val",
+            col = qc,
+            tbl = qt,
+            tbl_lit = qt.replace('\'', "''"),
+            col_lit = column_literal,
+        ),
+        DatabaseType::MySQL => format!(
+            "SELECT CAST(GREATEST(\
+             COALESCE((SELECT MAX({col}) FROM {tbl}), 0), \
+             COALESCE((SELECT AUTO_INCREMENT - 1 FROM information_schema.tables \
+             WHERE table_schema = DATABASE() AND table_name = '{tbl_lit}'), 0)) AS SIGNED) AS offset_val",
+            col = qc,
+            tbl = qt,
+            tbl_lit = table_literal,
+        ),
+        DatabaseType::SQLite => format!(
+            "SELECT COALESCE(MAX({col}), 0) AS offset_val FROM {tbl}",
+            col = qc,
+            tbl = qt,
+        ),
+    }
+}
+
+/// Read the sequence offset of every listed table with an auto-increment
+/// primary key. Tables that are empty (offset 0) are left out.
+pub async fn detect_sequence_offsets(
+    url: &str,
+    schema: &DatabaseSchema,
+    tables: &[String],
+) -> Result<BTreeMap<String, u64>> {
+    let targets: Vec<(&str, &str)> = tables
+        .iter()
+        .filter_map(|name| {
+            let table = schema.tables.get(name)?;
+            Some((name.as_str(), auto_increment_pk(table)?))
+        })
+        .collect();
+
+    let mut offsets = BTreeMap::new();
+    if targets.is_empty() {
+        return Ok(offsets);
+    }
+
+    let connect_err = |e| SeedKitError::Introspection {
+        query: "sequence offset connect".to_string(),
+        source: e,
+    };
+    let query_err = |table: &str| {
+        let query = format!("sequence offset for {}", table);
+        move |e| SeedKitError::Introspection { query, source: e }
+    };
+
+    match schema.database_type {
+        DatabaseType::PostgreSQL => {
+            let pool = sqlx::postgres::PgPoolOptions::new()
+                .max_connections(1)
+                .connect(url)
+                .await
+                .map_err(connect_err)?;
+            for (table, column) in targets {
+                let sql = sequence_offset_query(table, column, DatabaseType::PostgreSQL);
+                let offset: i64 = sqlx::query_scalar(&sql)
+                    .fetch_one(&pool)
+                    .await
+                    .map_err(query_err(table))?;
+                record_offset(&mut offsets, table, offset);
+            }
+            pool.close().await;
+        }
+        DatabaseType::MySQL => {
+            let pool = sqlx::mysql::MySqlPoolOptions::new()
+                .max_connections(1)
+                .connect(url)
+                .await
+                .map_err(connect_err)?;
+            for (table, column) in targets {
+                let sql = sequence_offset_query(table, column, DatabaseType::MySQL);
+                let offset: i64 = sqlx::query_scalar(&sql)
+                    .fetch_one(&pool)
+                    .await
+                    .map_err(query_err(table))?;
+                record_offset(&mut offsets, table, offset);
+            }
+            pool.close().await;
+        }
+        DatabaseType::SQLite => {
+            let pool = sqlx::sqlite::SqlitePoolOptions::new()
+                .max_connections(1)
+                .connect(url)
+                .await
+                .map_err(connect_err)?;
+            for (table, column) in targets {
+                let sql = sequence_offset_query(table, column, DatabaseType::SQLite);
+                let max_id: i64 = sqlx::query_scalar(&sql)
+                    .fetch_one(&pool)
+                    .await
+                    .map_err(query_err(table))?;
+                // AUTOINCREMENT tables never reuse IDs, even after deletes.
+                let last_seq: Option<i64> =
+                    sqlx::query_scalar("SELECT seq FROM sqlite_sequence WHERE name = ?")
+                        .bind(table)
+                        .fetch_optional(&pool)
+                        .await
+                        .ok()
+                        .flatten();
+                record_offset(&mut offsets, table, max_id.max(last_seq.unwrap_or(0)));
+            }
+            pool.close().await;
+        }
+    }
+
+    Ok(offsets)
+}
+
+fn record_offset(offsets: &mut BTreeMap<String, u64>, table: &str, offset: i64) {
+    if offset > 0 {
+        offsets.insert(table.to_string(), offset as u64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::types::*;
+
+    #[test]
+    fn test_auto_increment_pk() {
+        let mut table = Table::new("users".to_string());
+        let mut id = Column::new("id".to_string(), DataType::Serial, "serial".to_string());
+        id.nullable = false;
+        table.columns.insert("id".to_string(), id);
+        table.primary_key = Some(PrimaryKey {
+            columns: vec!["id".to_string()],
+            name: None,
+        });
+        assert_eq!(auto_increment_pk(&table), Some("id"));
+
+        let code = Column::new("code".to_string(), DataType::VarChar, "varchar".to_string());
+        table.columns.insert("code".to_string(), code);
+        table.primary_key = Some(PrimaryKey {
+            columns: vec!["code".to_string()],
+            name: None,
+        });
+        assert_eq!(auto_increment_pk(&table), None);
+    }
+
+    #[test]
+    fn test_sequence_offset_query_pg() {
+        let sql = sequence_offset_query("users", "id", DatabaseType::PostgreSQL);
+        assert!(sql.contains("MAX(\"id\") FROM \"users\""));
+        assert!(sql.contains("pg_get_serial_sequence('\"users\"', 'id')"));
+    }
+
+    #[test]
+    fn test_sequence_offset_query_mysql() {
+        let sql = sequence_offset_query("users", "id", DatabaseType::MySQL);
+        assert!(sql.contains("MAX(`id`) FROM `users`"));
+        assert!(sql.contains("AUTO_INCREMENT - 1"));
+        assert!(sql.contains("table_name = 'users'"));
+    }
+}
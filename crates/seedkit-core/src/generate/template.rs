@@ -0,0 +1,216 @@
+//! # Template Strings
+//!
+//! Columns configured with `template = "{first_name}.{last_name}@{company_domain}"`
+//! are rendered from the rest of their row. A placeholder names either a
+//! column of the same table, which interpolates that row's value, or a
+//! generator (`first_name`, `city`, `uuid`, ... — any semantic type in
+//! snake_case, plus `company_domain` and `row`), which draws a fresh value.
+//! Columns win when a name is both. `{{` and `}}` write literal braces.
+//!
+//! Templates render after the row's other columns, in column order, so a
+//! template may reference an earlier template column but not a later one.
+
+use std::borrow::Cow;
+
+use rand::Rng;
+
+use crate::classify::semantic::SemanticType;
+use crate::generate::providers::generate_value;
+use crate::generate::value::Value;
+
+/// One piece of a parsed template.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TemplatePart {
+    Literal(String),
+    /// Interpolate this column's value from the same row.
+    Column(String),
+    /// Draw a value from a named generator.
+    Generator(String),
+}
+
+/// Parse a template into literals and placeholders. Placeholders come back
+/// as [`TemplatePart::Column`]; [`resolve_columns`] turns those that aren't
+/// columns into generators.
+pub fn parse_template(template: &str) -> Result<Vec<TemplatePart>, String> {
+    let mut parts = Vec::new();
+    let mut literal = String::new();
+    let mut chars = template.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '{' => {
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => name.push(c),
+                        None => return Err(format!("unclosed '{{' in template '{}'", template)),
+                    }
+                }
+                let name = name.trim();
+                if name.is_empty() || name.contains('{') {
+                    return Err(format!("invalid placeholder in template '{}'", template));
+                }
+                if !literal.is_empty() {
+                    parts.push(TemplatePart::Literal(std::mem::take(&mut literal)));
+                }
+                parts.push(TemplatePart::Column(name.to_string()));
+            }
+            '}' => {
+                return Err(format!(
+                    "unmatched '}}' in template '{}' (write '}}}}' for a literal brace)",
+                    template
+                ))
+            }
+            _ => literal.push(c),
+        }
+    }
+    if !literal.is_empty() {
+        parts.push(TemplatePart::Literal(literal));
+    }
+    Ok(parts)
+}
+
+/// Turn placeholders that don't name one of `columns` into generators.
+/// Names that are neither stay columns; [`unknown_column`] finds them.
+pub fn resolve_columns(parts: Vec<TemplatePart>, columns: &[&str]) -> Vec<TemplatePart> {
+    parts
+        .into_iter()
+        .map(|part| match part {
+            TemplatePart::Column(name)
+                if !columns.contains(&name.as_str()) && is_generator(&name) =>
+            {
+                TemplatePart::Generator(name)
+            }
+            part => part,
+        })
+        .collect()
+}
+
+/// The first column placeholder for which `is_column` is false.
+pub fn unknown_column(parts: &[TemplatePart], is_column: impl Fn(&str) -> bool) -> Option<&str> {
+    parts.iter().find_map(|part| match part {
+        TemplatePart::Column(name) if !is_column(name) => Some(name.as_str()),
+        _ => None,
+    })
+}
+
+/// Whether `name` is a generator a template can call.
+pub fn is_generator(name: &str) -> bool {
+    matches!(name, "company_domain" | "row") || semantic_type(name).is_some()
+}
+
+/// Semantic type for a snake_case name (`first_name` → `FirstName`).
+fn semantic_type(name: &str) -> Option<SemanticType> {
+    SemanticType::from_snake_case(name)
+}
+
+/// Render resolved `parts`, reading columns through `column`.
+pub fn render(
+    parts: &[TemplatePart],
+    column: impl Fn(&str) -> Option<Value>,
+    rng: &mut impl Rng,
+    row_index: usize,
+    base_time: chrono::NaiveDateTime,
+) -> Value {
+    let mut out = String::new();
+    for part in parts {
+        match part {
+            TemplatePart::Literal(text) => out.push_str(text),
+            TemplatePart::Column(name) => {
+                if let Some(value) = column(name) {
+                    out.push_str(&value.to_csv_string());
+                }
+            }
+            TemplatePart::Generator(name) => {
+                out.push_str(&generate(name, rng, row_index, base_time));
+            }
+        }
+    }
+    Value::String(Cow::Owned(out))
+}
+
+fn generate(
+    name: &str,
+    rng: &mut impl Rng,
+    row_index: usize,
+    base_time: chrono::NaiveDateTime,
+) -> String {
+    match name {
+        "row" => (row_index + 1).to_string(),
+        "company_domain" => {
+            let company = generate_value(SemanticType::CompanyName, rng, row_index, &[], base_time)
+                .to_csv_string();
+            let slug: Vec<String> = company
+                .split(|c: char| !c.is_ascii_alphanumeric())
+                .filter(|w| !w.is_empty() && !w.eq_ignore_ascii_case("and"))
+                .map(str::to_ascii_lowercase)
+                .collect();
+            format!("{}.com", slug.join("-"))
+        }
+        _ => semantic_type(name)
+            .map(|st| generate_value(st, rng, row_index, &[], base_time).to_csv_string())
+            .unwrap_or_default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_parse_template() {
+        let parts = parse_template("{first_name}.{ last_name }@x.io {{v}}").unwrap();
+        assert_eq!(
+            parts,
+            [
+                TemplatePart::Column("first_name".into()),
+                TemplatePart::Literal(".".into()),
+                TemplatePart::Column("last_name".into()),
+                TemplatePart::Literal("@x.io {v}".into()),
+            ]
+        );
+        assert!(parse_template("{first_name").is_err());
+        assert!(parse_template("first}").is_err());
+        assert!(parse_template("{}").is_err());
+        assert!(parse_template("{{ raw").is_ok());
+    }
+
+    #[test]
+    fn test_resolve_columns_prefers_columns() {
+        let parts = parse_template("{first_name}-{city}-{handle}").unwrap();
+        let resolved = resolve_columns(parts, &["first_name"]);
+        assert_eq!(resolved[0], TemplatePart::Column("first_name".into()));
+        assert_eq!(resolved[2], TemplatePart::Generator("city".into()));
+        assert_eq!(
+            unknown_column(&resolved, |name| name == "first_name"),
+            Some("handle")
+        );
+    }
+
+    #[test]
+    fn test_render_interpolates_columns_and_generators() {
+        let parts = resolve_columns(
+            parse_template("{first_name}.{last_name}@{company_domain}").unwrap(),
+            &["first_name", "last_name"],
+        );
+        let column = |name: &str| match name {
+            "first_name" => Some(Value::String("ada".into())),
+            _ => Some(Value::Null),
+        };
+        let mut rng = StdRng::seed_from_u64(3);
+        let value = render(&parts, column, &mut rng, 0, chrono::Utc::now().naive_utc());
+        let email = value.as_string().unwrap();
+        assert!(email.starts_with("ada.@"), "{}", email);
+        assert!(email.ends_with(".com") && !email.contains(' '), "{}", email);
+    }
+}
@@ -0,0 +1,153 @@
+//! # Sampled Temporal Patterns
+//!
+//! `seedkit sample` records when timestamp and date columns' values fall
+//! (see `sample::stats::TemporalProfile`): orders placed in office hours,
+//! signups peaking on Mondays, sales spiking in December. Timestamps the
+//! semantic providers generate are moved to follow the same pattern, so
+//! load tests replaying them see traffic shaped like production's:
+//!
+//! - a month and a day of the week are picked by their shares, and the
+//!   value moved back to the latest such day, at most a year and a month
+//!   earlier;
+//! - for timestamps, so is an hour of the day, at a random minute.
+//!
+//! Values only ever move back in time, so they stay before the plan's base
+//! time.
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime};
+use rand::Rng;
+
+use crate::sample::stats::TemporalProfile;
+
+/// Move a generated timestamp to follow a sampled profile.
+pub fn shape_timestamp(
+    profile: &TemporalProfile,
+    value: NaiveDateTime,
+    rng: &mut impl Rng,
+) -> NaiveDateTime {
+    let time = pick(&profile.hours, rng)
+        .and_then(|hour| {
+            NaiveTime::from_hms_opt(
+                hour as u32,
+                rng.random_range(0..60),
+                rng.random_range(0..60),
+            )
+        })
+        .unwrap_or(value.time());
+    // A later time on the value's own day would move it forward
+    let latest = if time > value.time() {
+        value.date() - Duration::days(1)
+    } else {
+        value.date()
+    };
+    shape_date(profile, latest, rng).and_time(time)
+}
+
+/// Move a generated date to follow a sampled profile's months and days of
+/// the week.
+pub fn shape_date(profile: &TemporalProfile, value: NaiveDate, rng: &mut impl Rng) -> NaiveDate {
+    let month = pick(&profile.months, rng);
+    let weekday = pick(&profile.weekdays, rng);
+    let mut latest = value;
+    // Every month has every day of the week, so a second pass, from the
+    // month's last day a year earlier, always lands inside it
+    for _ in 0..2 {
+        let mut date = match month {
+            Some(month) => latest_in_month(latest, month as u32),
+            None => latest,
+        };
+        if let Some(weekday) = weekday {
+            let current = date.weekday().num_days_from_sunday() as usize;
+            date -= Duration::days(((current + 7 - weekday) % 7) as i64);
+        }
+        if month.is_none_or(|month| date.month0() as usize == month) {
+            return date;
+        }
+        latest = date;
+    }
+    latest
+}
+
+/// The latest day on or before `date` in month `month0` (0 = January).
+fn latest_in_month(date: NaiveDate, month0: u32) -> NaiveDate {
+    if date.month0() == month0 {
+        return date;
+    }
+    let back = (date.month0() + 12 - month0) % 12;
+    let total = date.year() * 12 + date.month0() as i32 - back as i32;
+    // The first of the following month, less a day
+    let next = total + 1;
+    NaiveDate::from_ymd_opt(next.div_euclid(12), next.rem_euclid(12) as u32 + 1, 1)
+        .map_or(date, |first| first - Duration::days(1))
+}
+
+/// Pick an index by share; `None` when no share is positive.
+fn pick(shares: &[f64], rng: &mut impl Rng) -> Option<usize> {
+    let total: f64 = shares.iter().map(|s| s.max(0.0)).sum();
+    if total <= 0.0 {
+        return None;
+    }
+    let mut roll = rng.random::<f64>() * total;
+    for (i, share) in shares.iter().enumerate() {
+        roll -= share.max(0.0);
+        if roll < 0.0 {
+            return Some(i);
+        }
+    }
+    shares.iter().rposition(|s| *s > 0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Timelike;
+    use rand::SeedableRng;
+
+    fn office_hours_in_december() -> TemporalProfile {
+        let mut hours = vec![0.0; 24];
+        hours[9] = 0.5;
+        hours[14] = 0.5;
+        let mut weekdays = vec![0.0; 7];
+        weekdays[1] = 1.0; // Mondays
+        let mut months = vec![0.0; 12];
+        months[11] = 1.0;
+        TemporalProfile {
+            hours,
+            weekdays,
+            months,
+        }
+    }
+
+    #[test]
+    fn test_shape_timestamp_follows_profile() {
+        let profile = office_hours_in_december();
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        let start = NaiveDate::from_ymd_opt(2024, 1, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+        for days in (0..700).step_by(13) {
+            let value = start + Duration::days(days) + Duration::minutes(days * 37);
+            let shaped = shape_timestamp(&profile, value, &mut rng);
+            assert!(shaped <= value, "{} moved forward to {}", value, shaped);
+            assert!(value - shaped < Duration::days(366 + 31));
+            assert_eq!(shaped.month(), 12, "{}", shaped);
+            assert_eq!(shaped.weekday(), chrono::Weekday::Mon, "{}", shaped);
+            assert!([9, 14].contains(&shaped.hour()), "{}", shaped);
+        }
+    }
+
+    #[test]
+    fn test_latest_in_month() {
+        let date = NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+        assert_eq!(latest_in_month(date, 2), date);
+        assert_eq!(
+            latest_in_month(date, 1),
+            NaiveDate::from_ymd_opt(2024, 2, 29).unwrap()
+        );
+        assert_eq!(
+            latest_in_month(date, 11),
+            NaiveDate::from_ymd_opt(2023, 12, 31).unwrap()
+        );
+    }
+}
@@ -0,0 +1,108 @@
+//! # Sampled Text Profiles
+//!
+//! `seedkit sample` records the lengths and character classes of string
+//! columns (see `sample::stats::TextProfile`). Columns generated by their
+//! semantic provider are fitted to them, so a `varchar(20)` column of
+//! eight-character codes gets eight-character codes rather than a sentence
+//! of lorem ipsum:
+//!
+//! - classes no sampled value had are removed — whitespace is dropped,
+//!   letters become digits, digits become letters, non-ASCII characters
+//!   become ASCII letters;
+//! - values are cut to the longest sampled length and padded to the
+//!   shortest.
+
+use rand::Rng;
+
+use crate::sample::stats::TextProfile;
+
+/// Fit a generated string to a sampled profile.
+pub fn conform_text(profile: &TextProfile, value: &str, rng: &mut impl Rng) -> String {
+    let mut chars: Vec<char> = Vec::with_capacity(value.len());
+    for c in value.chars() {
+        let c = if profile.non_ascii == 0.0 && !c.is_ascii() {
+            if c.is_alphabetic() {
+                random_letter(rng)
+            } else {
+                continue;
+            }
+        } else {
+            c
+        };
+        let c = if profile.letters == 0.0 && c.is_alphabetic() {
+            random_digit(rng)
+        } else if profile.digits == 0.0 && c.is_ascii_digit() {
+            random_letter(rng)
+        } else {
+            c
+        };
+        if profile.whitespace == 0.0 && c.is_whitespace() {
+            continue;
+        }
+        chars.push(c);
+    }
+
+    let (min, max) = profile.length;
+    if chars.len() > max {
+        chars.truncate(max);
+        while chars.last().is_some_and(|c| c.is_whitespace()) {
+            chars.pop();
+        }
+    }
+    while chars.len() < min {
+        chars.push(if profile.letters == 0.0 {
+            random_digit(rng)
+        } else {
+            random_letter(rng)
+        });
+    }
+    chars.into_iter().collect()
+}
+
+fn random_letter(rng: &mut impl Rng) -> char {
+    char::from(rng.random_range(b'a'..=b'z'))
+}
+
+fn random_digit(rng: &mut impl Rng) -> char {
+    char::from(rng.random_range(b'0'..=b'9'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sample::stats::profile_text;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_conform_to_sampled_codes() {
+        let profile = profile_text(["AB12CD34", "ZX99QW01", "KK00LM55"]).unwrap();
+        assert_eq!(profile.length, (8, 8));
+        assert_eq!(profile.whitespace, 0.0);
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        let code = conform_text(
+            &profile,
+            "Lorem ipsum dolor sit amet, consectetur",
+            &mut rng,
+        );
+        assert_eq!(code, "Loremips");
+        let padded = conform_text(&profile, "Zoë", &mut rng);
+        assert_eq!(padded.chars().count(), 8);
+        assert!(padded.is_ascii() && padded.starts_with("Zo"), "{}", padded);
+    }
+
+    #[test]
+    fn test_conform_to_sampled_digits() {
+        let profile = profile_text(["0412 555 019", "0413 555 772"]).unwrap();
+        assert_eq!(profile.letters, 0.0);
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        let value = conform_text(&profile, "call me maybe", &mut rng);
+        assert_eq!(value.chars().count(), 12);
+        assert!(
+            value.chars().all(|c| c.is_ascii_digit() || c == ' '),
+            "{}",
+            value
+        );
+    }
+}
@@ -0,0 +1,270 @@
+//! # Time-Series Tables
+//!
+//! Metrics and telemetry tables hold readings at a steady cadence, not rows
+//! scattered over random timestamps. A table configured as a time series
+//! gets one series per parent row of `series_by`, each ending at the plan's
+//! base time:
+//!
+//! ```toml
+//! [tables.metrics.time_series]
+//! timestamp = "recorded_at"   # default: the table's first timestamp column
+//! interval = "5 minutes"
+//! jitter = 0.2                # up to 20% of an interval early
+//! series_by = "device_id"     # one series per device
+//! value = "cpu_percent"
+//! trend = 0.05                # change per reading
+//! seasonality = { period = "1 day", amplitude = 10.0 }
+//! noise = 2.0                 # standard deviation
+//! ```
+//!
+//! Rows are split evenly over the series, each series a contiguous run of
+//! rows in time order. The `value` column starts each series at its
+//! generated value and follows `trend`, `seasonality` and `noise` from
+//! there, kept inside the column's CHECK constraints. Intervals and periods
+//! are written like PostgreSQL intervals (`30 seconds`, `1 hour`, `00:05:00`).
+
+use chrono::{Duration, NaiveDateTime};
+use indexmap::IndexMap;
+use rand::Rng;
+use serde::Deserialize;
+
+use crate::generate::engine::box_muller_normal;
+use crate::generate::foreign_key::ForeignKeyPool;
+use crate::generate::plan::TableGenerationPlan;
+use crate::generate::providers::satisfy_checks;
+use crate::generate::value::Value;
+use crate::schema::check_expr::parse_interval_seconds;
+
+/// A table's `time_series` settings from `seedkit.toml`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct TimeSeries {
+    pub timestamp: Option<String>,
+    pub interval: String,
+    #[serde(default)]
+    pub jitter: f64,
+    pub series_by: Option<String>,
+    pub value: Option<String>,
+    #[serde(default)]
+    pub trend: f64,
+    pub seasonality: Option<Seasonality>,
+    #[serde(default)]
+    pub noise: f64,
+}
+
+/// A repeating swing of the value column.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Seasonality {
+    pub period: String,
+    pub amplitude: f64,
+}
+
+impl TimeSeries {
+    /// Check the settings that don't depend on the schema.
+    pub fn validate(&self) -> Result<(), String> {
+        parse_span("interval", &self.interval)?;
+        if !(0.0..1.0).contains(&self.jitter) {
+            return Err(format!(
+                "jitter must be at least 0 and below 1, got {}",
+                self.jitter
+            ));
+        }
+        if let Some(ref seasonality) = self.seasonality {
+            parse_span("seasonality period", &seasonality.period)?;
+        }
+        if !self.noise.is_finite() || self.noise < 0.0 {
+            return Err(format!("noise must not be negative, got {}", self.noise));
+        }
+        Ok(())
+    }
+}
+
+fn parse_span(name: &str, literal: &str) -> Result<i64, String> {
+    match parse_interval_seconds(literal) {
+        Some(secs) if secs > 0 => Ok(secs),
+        _ => Err(format!(
+            "{} '{}' is not a positive interval such as \"5 minutes\"",
+            name, literal
+        )),
+    }
+}
+
+/// A time series resolved against the schema.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeSeriesPlan {
+    pub timestamp_column: String,
+    pub interval_secs: i64,
+    pub jitter: f64,
+    /// The series column and the (table, column) its foreign key references.
+    pub series_by: Option<(String, String, String)>,
+    pub value_column: Option<String>,
+    pub trend: f64,
+    /// (period in seconds, amplitude).
+    pub seasonality: Option<(i64, f64)>,
+    pub noise: f64,
+}
+
+impl TimeSeriesPlan {
+    /// Resolve `settings` with the given columns. Invalid settings are
+    /// rejected by config validation and give `None` here.
+    pub fn new(
+        settings: &TimeSeries,
+        timestamp_column: String,
+        series_by: Option<(String, String, String)>,
+        value_column: Option<String>,
+    ) -> Option<Self> {
+        let seasonality = match settings.seasonality {
+            Some(ref s) => Some((parse_span("period", &s.period).ok()?, s.amplitude)),
+            None => None,
+        };
+        Some(Self {
+            timestamp_column,
+            interval_secs: parse_span("interval", &settings.interval).ok()?,
+            jitter: settings.jitter,
+            series_by,
+            value_column,
+            trend: settings.trend,
+            seasonality,
+            noise: settings.noise,
+        })
+    }
+}
+
+/// Lays a table's rows out as time series while it is generated.
+pub struct SeriesCursor<'a> {
+    plan: &'a TimeSeriesPlan,
+    table_plan: &'a TableGenerationPlan,
+    series_count: usize,
+    /// The value column's level at the start of the current series.
+    level: Option<f64>,
+}
+
+impl<'a> SeriesCursor<'a> {
+    /// One series per parent row of the series column (capped at one row
+    /// each), or a single series.
+    pub fn new(table_plan: &'a TableGenerationPlan, fk_pool: &ForeignKeyPool) -> Option<Self> {
+        let plan = table_plan.time_series.as_ref()?;
+        let parents = match plan.series_by {
+            Some((_, ref table, ref column)) => fk_pool.pool_size(table, column),
+            None => 1,
+        };
+        Some(Self {
+            plan,
+            table_plan,
+            series_count: parents.clamp(1, table_plan.row_count.max(1)),
+            level: None,
+        })
+    }
+
+    fn series_start(&self, series: usize) -> usize {
+        series * self.table_plan.row_count / self.series_count
+    }
+
+    /// Set row `row_index`'s timestamp, series key and value.
+    pub fn apply(
+        &mut self,
+        row_index: usize,
+        row: &mut IndexMap<String, Value>,
+        fk_pool: &ForeignKeyPool,
+        base_time: NaiveDateTime,
+        rng: &mut impl Rng,
+    ) {
+        let n = self.table_plan.row_count;
+        let series = ((row_index + 1) * self.series_count - 1) / n;
+        let start = self.series_start(series);
+        let len = self.series_start(series + 1) - start;
+        let step = row_index - start;
+
+        // Readings stay in order: jitter moves each one less than an
+        // interval, and only earlier
+        let interval = self.plan.interval_secs as f64;
+        let before_end = (len - 1 - step) as f64 * interval;
+        let jitter = rng.random::<f64>() * self.plan.jitter * interval;
+        let time = base_time - Duration::milliseconds(((before_end + jitter) * 1000.0) as i64);
+        if let Some(value) = row.get_mut(&self.plan.timestamp_column) {
+            *value = match value {
+                Value::Date(_) => Value::Date(time.date()),
+                _ => Value::Timestamp(time),
+            };
+        }
+
+        if let Some((ref column, ref table, ref referenced)) = self.plan.series_by {
+            if let Some(key) = fk_pool
+                .get_pool(table, referenced)
+                .and_then(|pool| pool.get(series))
+            {
+                row.insert(column.clone(), key.clone());
+            }
+        }
+
+        let Some(ref column) = self.plan.value_column else {
+            return;
+        };
+        let Some(current) = row.get(column) else {
+            return;
+        };
+        let generated = match current {
+            Value::Int(n) => *n as f64,
+            Value::Float(f) => *f,
+            _ => return,
+        };
+        if step == 0 {
+            self.level = Some(generated);
+        }
+        let mut reading = self.level.unwrap_or(generated) + self.plan.trend * step as f64;
+        if let Some((period, amplitude)) = self.plan.seasonality {
+            let phase = time.and_utc().timestamp() as f64 / period as f64;
+            reading += amplitude * (phase * std::f64::consts::TAU).sin();
+        }
+        if self.plan.noise > 0.0 {
+            reading += self.plan.noise * box_muller_normal(rng);
+        }
+        let value = match current {
+            Value::Int(_) => Value::Int(reading.round() as i64),
+            _ => Value::Float((reading * 100.0).round() / 100.0),
+        };
+        let checks = self
+            .table_plan
+            .column_plans
+            .iter()
+            .find(|c| c.column_name == *column)
+            .map(|c| c.check_constraints.as_slice())
+            .unwrap_or_default();
+        row.insert(column.clone(), satisfy_checks(value, checks, rng));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(toml: &str) -> TimeSeries {
+        toml::from_str(toml).unwrap()
+    }
+
+    #[test]
+    fn test_validate_time_series() {
+        let ts = settings(
+            r#"
+interval = "5 minutes"
+jitter = 0.2
+series_by = "device_id"
+value = "cpu_percent"
+seasonality = { period = "1 day", amplitude = 10.0 }
+"#,
+        );
+        assert!(ts.validate().is_ok());
+        let plan = TimeSeriesPlan::new(&ts, "recorded_at".to_string(), None, None).unwrap();
+        assert_eq!(plan.interval_secs, 300);
+        assert_eq!(plan.seasonality, Some((86_400, 10.0)));
+
+        for bad in [
+            "interval = \"soon\"",
+            "interval = \"0 seconds\"",
+            "interval = \"1 hour\"\njitter = 1.0",
+            "interval = \"1 hour\"\nnoise = -1.0",
+            "interval = \"1 hour\"\nseasonality = { period = \"weekly\", amplitude = 1.0 }",
+        ] {
+            assert!(settings(bad).validate().is_err(), "{}", bad);
+        }
+    }
+}
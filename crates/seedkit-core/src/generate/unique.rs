@@ -1,19 +1,34 @@
+use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
 
+use crate::classify::semantic::SemanticType;
 use crate::generate::value::Value;
+use crate::schema::types::UniqueKeySpec;
 
 /// Tracks generated values for unique constraint enforcement.
 pub struct UniqueTracker {
     /// Map from constraint key (table + columns) to set of seen values.
     constraints: HashMap<String, HashSet<String>>,
+    /// How values are compared for constraints that aren't plain
+    /// full-value uniqueness (MySQL prefix and multi-valued indexes).
+    key_specs: HashMap<String, KeySpec>,
     /// Maximum retries before giving up.
     pub max_retries: usize,
 }
 
+/// Comparison rules for one unique index.
+struct KeySpec {
+    /// Characters compared per column; `None` compares the whole value.
+    prefix_lengths: Vec<Option<u32>>,
+    /// Each element of a JSON array value is a separate index entry.
+    multi_valued: bool,
+}
+
 impl UniqueTracker {
     pub fn new() -> Self {
         Self {
             constraints: HashMap::new(),
+            key_specs: HashMap::new(),
             max_retries: 1000,
         }
     }
@@ -24,19 +39,84 @@ impl UniqueTracker {
         self.constraints.entry(key).or_default();
     }
 
+    /// Compare a registered constraint's values by its prefix lengths or
+    /// multi-valued semantics.
+    pub fn register_key_spec(&mut self, table_name: &str, spec: &UniqueKeySpec) {
+        self.key_specs.insert(
+            constraint_key(table_name, &spec.columns),
+            KeySpec {
+                prefix_lengths: spec.prefix_lengths.clone(),
+                multi_valued: spec.multi_valued,
+            },
+        );
+    }
+
+    /// Prefix length of a single-column constraint, if one is registered.
+    pub fn prefix_length(&self, table_name: &str, column_name: &str) -> Option<u32> {
+        self.key_specs
+            .get(&constraint_key(table_name, &[column_name.to_string()]))
+            .and_then(|spec| spec.prefix_lengths.first().copied().flatten())
+    }
+
     /// Check if a value (or composite value) has been seen before.
     /// If not, record it and return true. If duplicate, return false.
+    ///
+    /// Prefix-indexed columns collide when their first N characters match.
+    /// For multi-valued indexes, every array element forms its own entry and
+    /// the row is rejected if any of them was seen before.
     pub fn try_insert(&mut self, table_name: &str, columns: &[String], values: &[&Value]) -> bool {
         let key = constraint_key(table_name, columns);
 
         if let Some(seen) = self.constraints.get_mut(&key) {
-            let value_key = values
-                .iter()
-                .map(|v| v.to_unique_key())
-                .collect::<Vec<_>>()
-                .join("|");
+            let spec = self.key_specs.get(&key);
 
-            seen.insert(value_key)
+            // One list of index-entry parts per column
+            let parts: Vec<Vec<String>> = values
+                .iter()
+                .enumerate()
+                .map(|(i, v)| {
+                    let elements = match (spec, v) {
+                        (Some(spec), Value::Json(serde_json::Value::Array(items)))
+                            if spec.multi_valued =>
+                        {
+                            let mut keys: Vec<String> =
+                                items.iter().map(|item| item.to_string()).collect();
+                            keys.sort();
+                            keys.dedup();
+                            keys
+                        }
+                        _ => vec![v.to_unique_key()],
+                    };
+                    let prefix = spec.and_then(|s| s.prefix_lengths.get(i).copied().flatten());
+                    match prefix {
+                        Some(n) => elements
+                            .into_iter()
+                            .map(|e| e.chars().take(n as usize).collect())
+                            .collect(),
+                        None => elements,
+                    }
+                })
+                .collect();
+
+            let entries: Vec<String> = parts.iter().fold(vec![String::new()], |acc, part| {
+                acc.iter()
+                    .flat_map(|prefix| {
+                        part.iter().map(move |p| {
+                            if prefix.is_empty() {
+                                p.clone()
+                            } else {
+                                format!("{}|{}", prefix, p)
+                            }
+                        })
+                    })
+                    .collect()
+            });
+
+            if entries.iter().any(|e| seen.contains(e)) {
+                return false;
+            }
+            seen.extend(entries);
+            true
         } else {
             // Constraint not registered, allow anything
             true
@@ -66,6 +146,23 @@ impl Default for UniqueTracker {
     }
 }
 
+/// Put the row index at the front of a string value so it is distinct
+/// within a prefix-length unique index. The separator keeps the value valid
+/// for its type (`17.jane.doe@example.com`, `17_jdoe`).
+pub fn lead_with_row_index(semantic_type: SemanticType, value: Value, row_index: usize) -> Value {
+    match value {
+        Value::String(s) => {
+            let separator = match semantic_type {
+                SemanticType::Email => ".",
+                SemanticType::Username | SemanticType::Slug => "_",
+                _ => "-",
+            };
+            Value::String(Cow::Owned(format!("{}{}{}", row_index, separator, s)))
+        }
+        other => other,
+    }
+}
+
 fn constraint_key(table_name: &str, columns: &[String]) -> String {
     format!("{}:{}", table_name, columns.join(","))
 }
@@ -73,7 +170,6 @@ fn constraint_key(table_name: &str, columns: &[String]) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::borrow::Cow;
 
     #[test]
     fn test_unique_tracking() {
@@ -91,6 +187,51 @@ mod tests {
         assert!(tracker.try_insert_single("users", "email", &v2));
     }
 
+    #[test]
+    fn test_prefix_unique_compares_leading_characters() {
+        let mut tracker = UniqueTracker::new();
+        let spec = UniqueKeySpec {
+            columns: vec!["email".to_string()],
+            prefix_lengths: vec![Some(5)],
+            multi_valued: false,
+        };
+        tracker.register_constraint("users", &spec.columns);
+        tracker.register_key_spec("users", &spec);
+        assert_eq!(tracker.prefix_length("users", "email"), Some(5));
+
+        let v1 = Value::String(Cow::Owned("alice@example.com".to_string()));
+        let v2 = Value::String(Cow::Owned("alice.b@example.com".to_string()));
+        let v3 = Value::String(Cow::Owned("bob@example.com".to_string()));
+        assert!(tracker.try_insert_single("users", "email", &v1));
+        assert!(
+            !tracker.try_insert_single("users", "email", &v2),
+            "same 5-character prefix must collide"
+        );
+        assert!(tracker.try_insert_single("users", "email", &v3));
+    }
+
+    #[test]
+    fn test_multi_valued_unique_checks_each_element() {
+        let mut tracker = UniqueTracker::new();
+        let spec = UniqueKeySpec {
+            columns: vec!["zips".to_string()],
+            prefix_lengths: Vec::new(),
+            multi_valued: true,
+        };
+        tracker.register_constraint("customers", &spec.columns);
+        tracker.register_key_spec("customers", &spec);
+
+        let v1 = Value::Json(serde_json::json!([10001, 10002]));
+        let v2 = Value::Json(serde_json::json!([10003, 10002]));
+        let v3 = Value::Json(serde_json::json!([10004, 10004]));
+        assert!(tracker.try_insert_single("customers", "zips", &v1));
+        assert!(
+            !tracker.try_insert_single("customers", "zips", &v2),
+            "10002 is already indexed"
+        );
+        assert!(tracker.try_insert_single("customers", "zips", &v3));
+    }
+
     #[test]
     fn test_composite_unique() {
         let mut tracker = UniqueTracker::new();
@@ -24,6 +24,18 @@ pub enum Value {
     Uuid(Uuid),
     Json(serde_json::Value),
     Bytes(Vec<u8>),
+    /// A composite (row) value: the type name plus named fields in order.
+    Composite {
+        type_name: String,
+        fields: Vec<(String, Value)>,
+    },
+    /// An array value; multi-dimensional arrays nest `Array`s.
+    Array(Vec<Value>),
+    /// A range value with an inclusive lower and exclusive upper bound (`[)`).
+    Range {
+        lower: Box<Value>,
+        upper: Box<Value>,
+    },
 }
 
 impl Value {
@@ -75,6 +87,114 @@ impl Value {
                     format!("X'{}'", hex_encode(b))
                 }
             },
+            Value::Composite { type_name, fields } => match db_type {
+                crate::schema::types::DatabaseType::PostgreSQL => {
+                    let parts: Vec<String> = fields
+                        .iter()
+                        .map(|(_, v)| v.to_sql_literal(db_type))
+                        .collect();
+                    format!(
+                        "ROW({})::\"{}\"",
+                        parts.join(", "),
+                        type_name.replace('"', "\"\"")
+                    )
+                }
+                _ => format!("'{}'", self.to_record_text().replace('\'', "''")),
+            },
+            // ARRAY[...] needs typed elements: untyped string literals would
+            // make a text[] that enum, uuid or date arrays don't accept. The
+            // quoted `{...}` form is coerced to the column's array type.
+            Value::Array(_) => match db_type {
+                crate::schema::types::DatabaseType::PostgreSQL if self.has_typed_elements() => {
+                    self.to_array_constructor(db_type)
+                }
+                _ => format!("'{}'", self.to_array_text().replace('\'', "''")),
+            },
+            Value::Range { .. } => format!("'{}'", self.to_range_text().replace('\'', "''")),
+        }
+    }
+
+    /// Render a range value in PostgreSQL's range text format,
+    /// e.g. `[1,10)` or `["2024-01-01 00:00:00","2024-01-03 12:00:00")`.
+    pub fn to_range_text(&self) -> String {
+        match self {
+            Value::Range { lower, upper } => format!(
+                "[{},{})",
+                quote_range_bound(&pg_text(lower)),
+                quote_range_bound(&pg_text(upper))
+            ),
+            other => pg_text(other),
+        }
+    }
+
+    /// Render an array value as an `ARRAY[...]` constructor, e.g.
+    /// `ARRAY[1,2,NULL]` or `ARRAY[ARRAY[1,2],ARRAY[3,4]]`.
+    pub fn to_array_constructor(&self, db_type: &crate::schema::types::DatabaseType) -> String {
+        match self {
+            Value::Array(items) => {
+                let parts: Vec<String> = items
+                    .iter()
+                    .map(|v| v.to_array_constructor(db_type))
+                    .collect();
+                format!("ARRAY[{}]", parts.join(","))
+            }
+            other => other.to_sql_literal(db_type),
+        }
+    }
+
+    /// Whether every element of an array is a number, boolean or NULL, with
+    /// at least one non-NULL, so that `ARRAY[...]` infers the element type.
+    fn has_typed_elements(&self) -> bool {
+        fn leaves<'a>(value: &'a Value, out: &mut Vec<&'a Value>) {
+            match value {
+                Value::Array(items) => items.iter().for_each(|v| leaves(v, out)),
+                other => out.push(other),
+            }
+        }
+        let mut all = Vec::new();
+        leaves(self, &mut all);
+        all.iter().any(|v| !matches!(v, Value::Null))
+            && all.iter().all(|v| {
+                matches!(v, Value::Null | Value::Bool(_) | Value::Int(_))
+                    || matches!(v, Value::Float(f) if f.is_finite())
+            })
+    }
+
+    /// Render an array value in PostgreSQL's array text format,
+    /// e.g. `{red,"dark blue",NULL}` or `{{1,2},{3,4}}`.
+    pub fn to_array_text(&self) -> String {
+        match self {
+            Value::Array(items) => {
+                let parts: Vec<String> = items
+                    .iter()
+                    .map(|v| match v {
+                        Value::Null => "NULL".to_string(),
+                        Value::Array(_) => v.to_array_text(),
+                        other => quote_array_element(&pg_text(other)),
+                    })
+                    .collect();
+                format!("{{{}}}", parts.join(","))
+            }
+            other => pg_text(other),
+        }
+    }
+
+    /// Render a composite value in PostgreSQL's record text format,
+    /// e.g. `(123 Main St,Springfield,"New York")`. Non-composite values
+    /// are rendered as a single field.
+    pub fn to_record_text(&self) -> String {
+        match self {
+            Value::Composite { fields, .. } => {
+                let parts: Vec<String> = fields
+                    .iter()
+                    .map(|(_, v)| match v {
+                        Value::Null => String::new(),
+                        other => quote_record_field(&pg_text(other)),
+                    })
+                    .collect();
+                format!("({})", parts.join(","))
+            }
+            other => other.to_csv_string(),
         }
     }
 
@@ -92,6 +212,9 @@ impl Value {
             Value::Uuid(u) => u.to_string(),
             Value::Json(j) => j.to_string(),
             Value::Bytes(b) => hex_encode(b),
+            Value::Composite { .. } => self.to_record_text(),
+            Value::Array(_) => self.to_array_text(),
+            Value::Range { .. } => self.to_range_text(),
         }
     }
 
@@ -109,6 +232,9 @@ impl Value {
             Value::Uuid(u) => u.to_string(),
             Value::Json(j) => j.to_string(),
             Value::Bytes(b) => hex_encode(b),
+            Value::Composite { .. } => self.to_record_text(),
+            Value::Array(_) => self.to_array_text(),
+            Value::Range { .. } => self.to_range_text(),
         }
     }
 
@@ -129,6 +255,21 @@ impl Value {
             _ => None,
         }
     }
+
+    /// Convert a JSON scalar to the matching value. Arrays and objects stay
+    /// JSON.
+    pub fn from_json(value: &serde_json::Value) -> Value {
+        match value {
+            serde_json::Value::Null => Value::Null,
+            serde_json::Value::Bool(b) => Value::Bool(*b),
+            serde_json::Value::Number(n) => match n.as_i64() {
+                Some(i) => Value::Int(i),
+                None => Value::Float(n.as_f64().unwrap_or_default()),
+            },
+            serde_json::Value::String(s) => Value::String(Cow::Owned(s.clone())),
+            other => Value::Json(other.clone()),
+        }
+    }
 }
 
 impl std::fmt::Display for Value {
@@ -145,6 +286,9 @@ impl std::fmt::Display for Value {
             Value::Uuid(u) => write!(f, "{}", u),
             Value::Json(j) => write!(f, "{}", j),
             Value::Bytes(b) => write!(f, "{}", hex_encode(b)),
+            Value::Composite { .. } => write!(f, "{}", self.to_record_text()),
+            Value::Array(_) => write!(f, "{}", self.to_array_text()),
+            Value::Range { .. } => write!(f, "{}", self.to_range_text()),
         }
     }
 }
@@ -152,3 +296,56 @@ impl std::fmt::Display for Value {
 fn hex_encode(bytes: &[u8]) -> String {
     bytes.iter().map(|b| format!("{:02x}", b)).collect()
 }
+
+/// Text form of a value as PostgreSQL's input functions expect it inside
+/// record and array literals.
+fn pg_text(value: &Value) -> String {
+    match value {
+        Value::Bytes(b) => format!("\\x{}", hex_encode(b)),
+        Value::Composite { .. } => value.to_record_text(),
+        Value::Array(_) => value.to_array_text(),
+        Value::Range { .. } => value.to_range_text(),
+        other => other.to_csv_string(),
+    }
+}
+
+/// Double-quote an array element if it is empty, spells `NULL`, or contains
+/// characters that are significant in array syntax.
+fn quote_array_element(s: &str) -> String {
+    let needs_quotes = s.is_empty()
+        || s.eq_ignore_ascii_case("null")
+        || s.chars()
+            .any(|c| matches!(c, '{' | '}' | ',' | '"' | '\\') || c.is_whitespace());
+    if needs_quotes {
+        format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        s.to_string()
+    }
+}
+
+/// Double-quote a record field if it is empty or contains characters that
+/// are significant in record syntax; embedded quotes and backslashes are
+/// doubled.
+fn quote_record_field(s: &str) -> String {
+    let needs_quotes = s.is_empty()
+        || s.chars()
+            .any(|c| matches!(c, '(' | ')' | ',' | '"' | '\\') || c.is_whitespace());
+    if needs_quotes {
+        format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+/// Double-quote a range bound if it is empty or contains characters that
+/// are significant in range syntax (e.g. the space inside a timestamp).
+fn quote_range_bound(s: &str) -> String {
+    let needs_quotes = s.is_empty()
+        || s.chars()
+            .any(|c| matches!(c, '[' | ']' | '(' | ')' | ',' | '"' | '\\') || c.is_whitespace());
+    if needs_quotes {
+        format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        s.to_string()
+    }
+}
@@ -1,9 +1,63 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt::{self, Write};
+
 use petgraph::algo::tarjan_scc;
-use petgraph::graph::EdgeIndex;
+use petgraph::graph::{EdgeIndex, NodeIndex};
+use petgraph::visit::EdgeRef;
 
 use crate::error::{Result, SeedKitError};
-use crate::graph::dag::DependencyGraph;
+use crate::graph::dag::{DependencyGraph, EdgeInfo};
 use crate::graph::topo::DeferredEdge;
+use crate::output::direct::quote_identifier;
+use crate::schema::types::DatabaseSchema;
+
+/// Why an edge was picked to break a cycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BreakReason {
+    /// Listed in `[graph] break_cycle_at`.
+    Configured,
+    /// The foreign key columns are nullable.
+    Nullable,
+    /// The constraint is deferrable.
+    Deferrable,
+    /// No edge of the cycle is nullable or deferrable.
+    LastResort,
+    /// The table references itself.
+    SelfReference,
+}
+
+impl fmt::Display for BreakReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BreakReason::Configured => write!(f, "listed in [graph] break_cycle_at"),
+            BreakReason::Nullable => write!(f, "nullable, so rows can be inserted with it NULL"),
+            BreakReason::Deferrable => write!(
+                f,
+                "the constraint is DEFERRABLE, so it is only checked at commit"
+            ),
+            BreakReason::LastResort => write!(
+                f,
+                "no foreign key in the cycle is nullable or deferrable; inserting NULL \
+                 will fail unless one is made nullable"
+            ),
+            BreakReason::SelfReference => write!(
+                f,
+                "self-reference, so rows can point at rows inserted after them"
+            ),
+        }
+    }
+}
+
+/// A cycle broken by [`break_cycles_explained`]: the tables around it and
+/// the edge deferred to break it.
+#[derive(Debug, Clone)]
+pub struct CycleBreak {
+    /// The tables of the cycle, each referencing the next, starting and
+    /// ending with the deferred edge's source table.
+    pub cycle: Vec<String>,
+    pub edge: DeferredEdge,
+    pub reason: BreakReason,
+}
 
 /// Detect and break cycles in the dependency graph.
 /// Returns the list of edges that were removed (deferred) to make the graph acyclic.
@@ -17,7 +71,19 @@ pub fn break_cycles(
     graph: &mut DependencyGraph,
     break_at: &[String], // User-specified edges like "users.invited_by_id"
 ) -> Result<Vec<DeferredEdge>> {
-    let mut deferred = Vec::new();
+    Ok(break_cycles_explained(graph, break_at)?
+        .into_iter()
+        .map(|b| b.edge)
+        .collect())
+}
+
+/// [`break_cycles`], also returning the cycle each edge broke and why it
+/// was picked.
+pub fn break_cycles_explained(
+    graph: &mut DependencyGraph,
+    break_at: &[String],
+) -> Result<Vec<CycleBreak>> {
+    let mut breaks = Vec::new();
 
     loop {
         let sccs = tarjan_scc(&graph.graph);
@@ -31,8 +97,12 @@ pub fn break_cycles(
             // Find the best edge to break in this SCC
             let edge_to_break = find_best_edge_to_break(graph, scc, break_at)?;
 
-            if let Some((edge_idx, deferred_edge)) = edge_to_break {
-                deferred.push(deferred_edge);
+            if let Some((edge_idx, deferred_edge, reason)) = edge_to_break {
+                breaks.push(CycleBreak {
+                    cycle: cycle_through(graph, scc, edge_idx),
+                    edge: deferred_edge,
+                    reason,
+                });
                 graph.graph.remove_edge(edge_idx);
             }
         }
@@ -41,25 +111,86 @@ pub fn break_cycles(
     // Also handle self-referencing FKs (single-node SCCs where node has edge to itself)
     let self_refs = find_self_references(graph);
     for (edge_idx, deferred_edge) in self_refs {
-        deferred.push(deferred_edge);
+        breaks.push(CycleBreak {
+            cycle: vec![
+                deferred_edge.source_table.clone(),
+                deferred_edge.source_table.clone(),
+            ],
+            edge: deferred_edge,
+            reason: BreakReason::SelfReference,
+        });
         graph.graph.remove_edge(edge_idx);
     }
 
-    Ok(deferred)
+    Ok(breaks)
+}
+
+/// The shortest cycle within `scc` through `edge`: its source, its target,
+/// then the tables back to its source.
+fn cycle_through(graph: &DependencyGraph, scc: &[NodeIndex], edge: EdgeIndex) -> Vec<String> {
+    let (source, target) = graph.graph.edge_endpoints(edge).expect("edge in graph");
+    let scc_set: HashSet<_> = scc.iter().copied().collect();
+
+    // Breadth-first search from the target back to the source
+    let mut previous: HashMap<NodeIndex, NodeIndex> = HashMap::new();
+    let mut queue = VecDeque::from([target]);
+    while let Some(node) = queue.pop_front() {
+        if node == source {
+            break;
+        }
+        for next in graph.graph.edges(node).map(|e| e.target()) {
+            if scc_set.contains(&next) && next != target && !previous.contains_key(&next) {
+                previous.insert(next, node);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    let mut path = vec![source];
+    let mut node = source;
+    while node != target {
+        match previous.get(&node) {
+            Some(&prev) => {
+                path.push(prev);
+                node = prev;
+            }
+            None => break,
+        }
+    }
+    path.push(source);
+    path.reverse();
+    path.into_iter()
+        .map(|n| graph.table_name(n).to_string())
+        .collect()
+}
+
+/// An edge within a strongly connected component: its index, info, and
+/// source and target tables.
+type Candidate<'a> = (EdgeIndex, &'a EdgeInfo, String, String);
+
+fn defer(candidate: &Candidate, reason: BreakReason) -> (EdgeIndex, DeferredEdge, BreakReason) {
+    let (edge_idx, info, source_table, target_table) = candidate;
+    (
+        *edge_idx,
+        DeferredEdge {
+            source_table: source_table.clone(),
+            source_columns: info.source_columns.clone(),
+            target_table: target_table.clone(),
+            target_columns: info.referenced_columns.clone(),
+        },
+        reason,
+    )
 }
 
 fn find_best_edge_to_break(
     graph: &DependencyGraph,
-    scc: &[petgraph::graph::NodeIndex],
+    scc: &[NodeIndex],
     break_at: &[String],
-) -> Result<Option<(EdgeIndex, DeferredEdge)>> {
-    use petgraph::visit::EdgeRef;
-
-    let scc_set: std::collections::HashSet<_> = scc.iter().copied().collect();
+) -> Result<Option<(EdgeIndex, DeferredEdge, BreakReason)>> {
+    let scc_set: HashSet<_> = scc.iter().copied().collect();
 
     // Collect all edges within this SCC
-    let mut candidate_edges: Vec<(EdgeIndex, &crate::graph::dag::EdgeInfo, String, String)> =
-        Vec::new();
+    let mut candidate_edges: Vec<Candidate> = Vec::new();
 
     for &node in scc {
         let table_name = graph.table_name(node).to_string();
@@ -82,69 +213,35 @@ fn find_best_edge_to_break(
     }
 
     // Priority 1: User-specified break points
-    for (edge_idx, info, source_table, _target_table) in &candidate_edges {
-        for col in &info.source_columns {
-            let qualified = format!("{}.{}", source_table, col);
-            if break_at.contains(&qualified) {
-                return Ok(Some((
-                    *edge_idx,
-                    DeferredEdge {
-                        source_table: source_table.clone(),
-                        source_columns: info.source_columns.clone(),
-                        target_table: _target_table.clone(),
-                        target_columns: info.referenced_columns.clone(),
-                    },
-                )));
-            }
-        }
+    if let Some(edge) = candidate_edges.iter().find(|(_, info, source_table, _)| {
+        info.source_columns
+            .iter()
+            .any(|col| break_at.contains(&format!("{}.{}", source_table, col)))
+    }) {
+        return Ok(Some(defer(edge, BreakReason::Configured)));
     }
 
     // Priority 2: Nullable FK columns
-    for (edge_idx, info, source_table, target_table) in &candidate_edges {
-        if info.is_nullable {
-            return Ok(Some((
-                *edge_idx,
-                DeferredEdge {
-                    source_table: source_table.clone(),
-                    source_columns: info.source_columns.clone(),
-                    target_table: target_table.clone(),
-                    target_columns: info.referenced_columns.clone(),
-                },
-            )));
-        }
+    if let Some(edge) = candidate_edges
+        .iter()
+        .find(|(_, info, _, _)| info.is_nullable)
+    {
+        return Ok(Some(defer(edge, BreakReason::Nullable)));
     }
 
     // Priority 3: Deferrable FK constraints
-    for (edge_idx, info, source_table, target_table) in &candidate_edges {
-        if info.is_deferrable {
-            return Ok(Some((
-                *edge_idx,
-                DeferredEdge {
-                    source_table: source_table.clone(),
-                    source_columns: info.source_columns.clone(),
-                    target_table: target_table.clone(),
-                    target_columns: info.referenced_columns.clone(),
-                },
-            )));
-        }
+    if let Some(edge) = candidate_edges
+        .iter()
+        .find(|(_, info, _, _)| info.is_deferrable)
+    {
+        return Ok(Some(defer(edge, BreakReason::Deferrable)));
     }
 
     // Priority 4: Any edge (last resort)
-    let (edge_idx, info, source_table, target_table) = &candidate_edges[0];
-    Ok(Some((
-        *edge_idx,
-        DeferredEdge {
-            source_table: source_table.clone(),
-            source_columns: info.source_columns.clone(),
-            target_table: target_table.clone(),
-            target_columns: info.referenced_columns.clone(),
-        },
-    )))
+    Ok(Some(defer(&candidate_edges[0], BreakReason::LastResort)))
 }
 
 fn find_self_references(graph: &DependencyGraph) -> Vec<(EdgeIndex, DeferredEdge)> {
-    use petgraph::visit::EdgeRef;
-
     let mut self_refs = Vec::new();
 
     for node in graph.graph.node_indices() {
@@ -167,6 +264,90 @@ fn find_self_references(graph: &DependencyGraph) -> Vec<(EdgeIndex, DeferredEdge
     self_refs
 }
 
+/// A report of every broken cycle: the tables around it, the foreign key
+/// deferred with its nullability and `ON DELETE` action, why it was picked,
+/// and the `UPDATE` that fills it in after the inserts.
+pub fn explain_cycles(schema: &DatabaseSchema, breaks: &[CycleBreak]) -> String {
+    let db_type = &schema.database_type;
+    let mut out = String::new();
+    for (i, cycle_break) in breaks.iter().enumerate() {
+        let edge = &cycle_break.edge;
+        let source = schema.tables.get(&edge.source_table);
+        let fk = source.and_then(|t| {
+            t.foreign_keys
+                .iter()
+                .find(|fk| fk.source_columns == edge.source_columns)
+        });
+        let nullable = source.is_some_and(|t| {
+            edge.source_columns
+                .iter()
+                .all(|c| t.columns.get(c).is_some_and(|c| c.nullable))
+        });
+
+        if i > 0 {
+            out.push('\n');
+        }
+        let _ = writeln!(out, "Cycle {}: {}", i + 1, cycle_break.cycle.join(" → "));
+        let mut details = vec![if nullable { "nullable" } else { "NOT NULL" }.to_string()];
+        match fk {
+            Some(fk) => details.push(format!("ON DELETE {}", fk.on_delete)),
+            None => details.push("no constraint".to_string()),
+        }
+        let _ = writeln!(
+            out,
+            "  deferred: {}.{} → {}.{} ({})",
+            edge.source_table,
+            edge.source_columns.join(", "),
+            edge.target_table,
+            edge.target_columns.join(", "),
+            details.join(", ")
+        );
+        let _ = writeln!(out, "  why:      {}", cycle_break.reason);
+
+        let set: Vec<String> = edge
+            .source_columns
+            .iter()
+            .zip(&edge.target_columns)
+            .map(|(column, target)| {
+                format!(
+                    "{} = <{}.{}>",
+                    quote_identifier(column, db_type),
+                    edge.target_table,
+                    target
+                )
+            })
+            .collect();
+        let pk: Vec<String> = source
+            .and_then(|t| t.primary_key.as_ref())
+            .map(|pk| pk.columns.clone())
+            .unwrap_or_default();
+        let where_clause = if pk.is_empty() {
+            "<row>".to_string()
+        } else {
+            pk.iter()
+                .map(|c| {
+                    format!(
+                        "{} = <{}.{}>",
+                        quote_identifier(c, db_type),
+                        edge.source_table,
+                        c
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(" AND ")
+        };
+        let _ = writeln!(
+            out,
+            "  then:     rows are inserted with {} NULL, then\n            UPDATE {} SET {} WHERE {}",
+            edge.source_columns.join(", "),
+            quote_identifier(&edge.source_table, db_type),
+            set.join(", "),
+            where_clause
+        );
+    }
+    out
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -260,4 +441,72 @@ mod tests {
         // Should break the nullable edge (table_a.b_id)
         assert_eq!(deferred[0].source_table, "table_a");
     }
+
+    #[test]
+    fn test_explain_cycles() {
+        // orders.user_id → users.last_invoice_id → invoices.order_id → orders
+        let mut schema = DatabaseSchema::new(DatabaseType::PostgreSQL, "test".to_string());
+        for (table, column, parent, nullable) in [
+            ("orders", "user_id", "users", false),
+            ("users", "last_invoice_id", "invoices", true),
+            ("invoices", "order_id", "orders", false),
+        ] {
+            let mut t = Table::new(table.to_string());
+            t.columns.insert(
+                "id".to_string(),
+                Column::new("id".to_string(), DataType::Serial, "serial".to_string()),
+            );
+            let mut fk_column =
+                Column::new(column.to_string(), DataType::Integer, "integer".to_string());
+            fk_column.nullable = nullable;
+            t.columns.insert(column.to_string(), fk_column);
+            t.primary_key = Some(PrimaryKey {
+                columns: vec!["id".to_string()],
+                name: None,
+            });
+            t.foreign_keys.push(ForeignKey {
+                name: None,
+                source_columns: vec![column.to_string()],
+                referenced_table: parent.to_string(),
+                referenced_columns: vec!["id".to_string()],
+                on_delete: ForeignKeyAction::SetNull,
+                on_update: ForeignKeyAction::NoAction,
+                is_deferrable: false,
+            });
+            schema.tables.insert(table.to_string(), t);
+        }
+
+        let mut graph = DependencyGraph::from_schema(&schema);
+        let breaks = break_cycles_explained(&mut graph, &[]).unwrap();
+        assert_eq!(breaks.len(), 1);
+        assert_eq!(
+            breaks[0].cycle,
+            vec!["users", "invoices", "orders", "users"]
+        );
+        assert_eq!(breaks[0].reason, BreakReason::Nullable);
+
+        let report = explain_cycles(&schema, &breaks);
+        assert!(
+            report.contains("Cycle 1: users → invoices → orders → users"),
+            "{}",
+            report
+        );
+        assert!(
+            report.contains("users.last_invoice_id → invoices.id (nullable, ON DELETE SET NULL)"),
+            "{}",
+            report
+        );
+        assert!(
+            report.contains(
+                "UPDATE \"users\" SET \"last_invoice_id\" = <invoices.id> WHERE \"id\" = <users.id>"
+            ),
+            "{}",
+            report
+        );
+
+        let mut graph = DependencyGraph::from_schema(&schema);
+        let breaks = break_cycles_explained(&mut graph, &["orders.user_id".to_string()]).unwrap();
+        assert_eq!(breaks[0].edge.source_table, "orders");
+        assert_eq!(breaks[0].reason, BreakReason::Configured);
+    }
 }
@@ -1,6 +1,9 @@
 use petgraph::graph::{DiGraph, NodeIndex};
-use std::collections::HashMap;
+use petgraph::visit::EdgeRef;
+use petgraph::Direction;
+use std::collections::{BTreeSet, HashMap};
 
+use crate::generate::polymorphic::PolymorphicAssociation;
 use crate::schema::types::DatabaseSchema;
 
 /// A directed graph representing table dependencies via foreign keys.
@@ -75,6 +78,43 @@ impl DependencyGraph {
         }
     }
 
+    /// Add edges from tables with polymorphic associations to their target
+    /// tables, so targets are generated first. The database knows nothing
+    /// of these references, so the edges have no constraint.
+    pub fn add_polymorphic_edges(
+        &mut self,
+        schema: &DatabaseSchema,
+        associations: &[PolymorphicAssociation],
+    ) {
+        for association in associations {
+            let Some(from_idx) = self.node_index(&association.table) else {
+                continue;
+            };
+            for target in association.targets.values() {
+                let Some(to_idx) = self.node_index(target) else {
+                    continue;
+                };
+                let referenced_columns = schema
+                    .tables
+                    .get(target)
+                    .and_then(|t| t.primary_key.as_ref())
+                    .map(|pk| pk.columns.clone())
+                    .unwrap_or_default();
+                self.graph.add_edge(
+                    from_idx,
+                    to_idx,
+                    EdgeInfo {
+                        constraint_name: None,
+                        source_columns: vec![association.id_column()],
+                        referenced_columns,
+                        is_nullable: false,
+                        is_deferrable: false,
+                    },
+                );
+            }
+        }
+    }
+
     /// Get the table name for a node index.
     pub fn table_name(&self, idx: NodeIndex) -> &str {
         &self.graph[idx]
@@ -99,6 +139,54 @@ impl DependencyGraph {
     pub fn edge_count(&self) -> usize {
         self.graph.edge_count()
     }
+
+    /// The tables within `depth` foreign keys of `table`: itself, the
+    /// tables it references up to `depth` levels up, and the tables
+    /// referencing it up to `depth` levels down. `None` for an unknown table.
+    pub fn neighborhood(&self, table: &str, depth: usize) -> Option<BTreeSet<String>> {
+        let start = self.node_index(table)?;
+        let mut tables = BTreeSet::from([table.to_string()]);
+        for direction in [Direction::Outgoing, Direction::Incoming] {
+            let mut frontier = vec![start];
+            for _ in 0..depth {
+                let mut next = Vec::new();
+                for node in frontier {
+                    for edge in self.graph.edges_directed(node, direction) {
+                        let other = match direction {
+                            Direction::Outgoing => edge.target(),
+                            Direction::Incoming => edge.source(),
+                        };
+                        if tables.insert(self.table_name(other).to_string()) {
+                            next.push(other);
+                        }
+                    }
+                }
+                frontier = next;
+            }
+        }
+        Some(tables)
+    }
+
+    /// The graph of `tables` and the edges between them.
+    pub fn subgraph(&self, tables: &BTreeSet<String>) -> DependencyGraph {
+        let mut graph = DiGraph::new();
+        let mut node_indices = HashMap::new();
+        for name in self.graph.node_weights().filter(|t| tables.contains(*t)) {
+            node_indices.insert(name.clone(), graph.add_node(name.clone()));
+        }
+        for edge in self.graph.edge_references() {
+            if let (Some(&from), Some(&to)) = (
+                node_indices.get(self.table_name(edge.source())),
+                node_indices.get(self.table_name(edge.target())),
+            ) {
+                graph.add_edge(from, to, edge.weight().clone());
+            }
+        }
+        Self {
+            graph,
+            node_indices,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -176,4 +264,58 @@ mod tests {
         assert_eq!(graph.table_count(), 3);
         assert_eq!(graph.edge_count(), 2);
     }
+
+    #[test]
+    fn test_polymorphic_edges_order_targets_first() {
+        let mut schema = make_test_schema();
+        schema
+            .tables
+            .insert("comments".to_string(), Table::new("comments".to_string()));
+        let association = PolymorphicAssociation {
+            table: "comments".to_string(),
+            name: "commentable".to_string(),
+            targets: [("Order", "orders"), ("Item", "order_items")]
+                .into_iter()
+                .map(|(t, table)| (t.to_string(), table.to_string()))
+                .collect(),
+        };
+
+        let mut graph = DependencyGraph::from_schema(&schema);
+        graph.add_polymorphic_edges(&schema, &[association]);
+        assert_eq!(graph.edge_count(), 4);
+
+        let order = crate::graph::topo::topological_sort(&graph).unwrap().tables;
+        let position = |t: &str| order.iter().position(|o| o == t).unwrap();
+        assert!(position("comments") > position("order_items"));
+    }
+
+    #[test]
+    fn test_neighborhood_and_subgraph() {
+        let mut schema = make_test_schema();
+        schema
+            .tables
+            .insert("audit_log".to_string(), Table::new("audit_log".to_string()));
+        let graph = DependencyGraph::from_schema(&schema);
+
+        let names = |tables: &BTreeSet<String>| tables.iter().cloned().collect::<Vec<_>>();
+        let around_orders = graph.neighborhood("orders", 1).unwrap();
+        assert_eq!(
+            names(&around_orders),
+            vec!["order_items", "orders", "users"]
+        );
+        // Ancestors only upward, descendants only downward
+        let above_items = graph.neighborhood("order_items", 1).unwrap();
+        assert_eq!(names(&above_items), vec!["order_items", "orders"]);
+        let above_items = graph.neighborhood("order_items", 2).unwrap();
+        assert_eq!(names(&above_items), vec!["order_items", "orders", "users"]);
+        assert_eq!(graph.neighborhood("order_items", 0).unwrap().len(), 1);
+        assert!(graph.neighborhood("missing", 1).is_none());
+
+        let subgraph = graph.subgraph(&graph.neighborhood("orders", 0).unwrap());
+        assert_eq!(subgraph.table_count(), 1);
+        assert_eq!(subgraph.edge_count(), 0);
+        let subgraph = graph.subgraph(&above_items);
+        assert_eq!(subgraph.table_count(), 3);
+        assert_eq!(subgraph.edge_count(), 2);
+    }
 }
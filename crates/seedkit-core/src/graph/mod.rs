@@ -1,4 +1,5 @@
 pub mod cycle;
 pub mod dag;
+pub mod html;
 pub mod topo;
 pub mod visualize;
@@ -1,4 +1,7 @@
+use std::collections::HashMap;
+
 use petgraph::algo::toposort;
+use petgraph::visit::EdgeRef;
 
 use crate::error::{Result, SeedKitError};
 use crate::graph::dag::DependencyGraph;
@@ -58,6 +61,36 @@ pub fn topological_sort(graph: &DependencyGraph) -> Result<InsertionOrder> {
     }
 }
 
+/// Group the tables into insertion levels: a table's level is one more
+/// than the highest level of the tables it references, so tables within a
+/// level don't depend on each other and can be inserted in parallel once
+/// the levels before are done. Within a level, tables keep their
+/// [`topological_sort`] order.
+///
+/// Like [`topological_sort`], this fails on cycles — break them first.
+pub fn insertion_levels(graph: &DependencyGraph) -> Result<Vec<Vec<String>>> {
+    let order = topological_sort(graph)?;
+    let mut level_of: HashMap<&str, usize> = HashMap::new();
+    let mut levels: Vec<Vec<String>> = Vec::new();
+    for table in &order.tables {
+        let node = graph.node_index(table).expect("sorted table in graph");
+        // Parents come first in the order, so their levels are known
+        let level = graph
+            .graph
+            .edges(node)
+            .filter(|edge| edge.target() != node)
+            .map(|edge| level_of[graph.table_name(edge.target())] + 1)
+            .max()
+            .unwrap_or(0);
+        level_of.insert(table, level);
+        if levels.len() <= level {
+            levels.resize(level + 1, Vec::new());
+        }
+        levels[level].push(table.clone());
+    }
+    Ok(levels)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -98,4 +131,43 @@ mod tests {
 
         assert!(users_pos < orders_pos, "users must come before orders");
     }
+
+    #[test]
+    fn test_insertion_levels() {
+        let mut schema = DatabaseSchema::new(DatabaseType::PostgreSQL, "test".to_string());
+        // (table, referenced tables)
+        for (table, parents) in [
+            ("users", vec![]),
+            ("products", vec![]),
+            ("orders", vec!["users"]),
+            ("order_items", vec!["orders", "products"]),
+            ("reviews", vec!["users", "products"]),
+        ] {
+            let mut t = Table::new(table.to_string());
+            for parent in parents {
+                t.foreign_keys.push(ForeignKey {
+                    name: None,
+                    source_columns: vec![format!("{}_id", parent)],
+                    referenced_table: parent.to_string(),
+                    referenced_columns: vec!["id".to_string()],
+                    on_delete: ForeignKeyAction::NoAction,
+                    on_update: ForeignKeyAction::NoAction,
+                    is_deferrable: false,
+                });
+            }
+            schema.tables.insert(table.to_string(), t);
+        }
+
+        let graph = DependencyGraph::from_schema(&schema);
+        let mut levels = insertion_levels(&graph).unwrap();
+        levels.iter_mut().for_each(|level| level.sort());
+        assert_eq!(
+            levels,
+            vec![
+                vec!["products", "users"],
+                vec!["orders", "reviews"],
+                vec!["order_items"],
+            ]
+        );
+    }
 }
@@ -1,5 +1,6 @@
 use crate::graph::dag::DependencyGraph;
-use crate::graph::topo::DeferredEdge;
+use crate::graph::topo::{topological_sort, DeferredEdge};
+use crate::schema::types::{DatabaseSchema, Table};
 use petgraph::visit::EdgeRef;
 
 /// Output format for graph visualization.
@@ -56,28 +57,213 @@ fn generate_mermaid(graph: &DependencyGraph, deferred_edges: &[DeferredEdge]) ->
     output
 }
 
+/// A Mermaid identifier: `name` with characters Mermaid doesn't allow
+/// replaced by `_`.
+fn mermaid_id(name: &str) -> String {
+    let mut id: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if !id.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+        id.insert(0, '_');
+    }
+    id
+}
+
+/// A column's type as a Mermaid attribute type: the raw type without its
+/// length or precision, e.g. `character_varying` for `character varying(255)`.
+fn mermaid_type(raw_type: &str) -> String {
+    let base = raw_type.split('(').next().unwrap_or_default().trim();
+    let base = base.replace(' ', "_");
+    if base.is_empty() {
+        "unknown".to_string()
+    } else {
+        mermaid_id(&base)
+    }
+}
+
+/// Whether `columns` are unique in `table`: its primary key or one of its
+/// unique constraints.
+fn is_unique_key(table: &Table, columns: &[String]) -> bool {
+    let same =
+        |key: &[String]| key.len() == columns.len() && key.iter().all(|c| columns.contains(c));
+    table
+        .primary_key
+        .as_ref()
+        .is_some_and(|pk| same(&pk.columns))
+        || table.unique_constraints.iter().any(|uc| same(&uc.columns))
+}
+
+/// Mermaid `erDiagram` of `schema`, for pasting into Markdown docs: every
+/// table with its key columns (primary, foreign and unique keys) and one
+/// relationship per foreign key. A child row references at most one parent
+/// (`|o` when the key is nullable, `||` otherwise); a parent has any
+/// number of children (`o{`), or at most one when the foreign key is
+/// unique (`o|`).
+pub fn er_diagram(schema: &DatabaseSchema) -> String {
+    let mut output = String::from("erDiagram\n");
+
+    for (name, table) in &schema.tables {
+        let id = mermaid_id(name);
+        let entity = if id == *name {
+            id
+        } else {
+            format!("{}[\"{}\"]", id, name.replace('"', "'"))
+        };
+        let pk = table
+            .primary_key
+            .as_ref()
+            .map(|pk| pk.columns.as_slice())
+            .unwrap_or_default();
+        let mut attributes = Vec::new();
+        for (col_name, column) in &table.columns {
+            let mut keys = Vec::new();
+            if pk.contains(col_name) {
+                keys.push("PK");
+            }
+            if table
+                .foreign_keys
+                .iter()
+                .any(|fk| fk.source_columns.contains(col_name))
+            {
+                keys.push("FK");
+            }
+            if table
+                .unique_constraints
+                .iter()
+                .any(|uc| uc.columns.contains(col_name))
+            {
+                keys.push("UK");
+            }
+            if !keys.is_empty() {
+                attributes.push(format!(
+                    "        {} {} {}\n",
+                    mermaid_type(&column.raw_type),
+                    mermaid_id(col_name),
+                    keys.join(", ")
+                ));
+            }
+        }
+        if attributes.is_empty() {
+            output.push_str(&format!("    {}\n", entity));
+        } else {
+            output.push_str(&format!("    {} {{\n", entity));
+            attributes.iter().for_each(|a| output.push_str(a));
+            output.push_str("    }\n");
+        }
+    }
+
+    // Relationships, parent to child, between the tables drawn
+    for (name, table) in &schema.tables {
+        for fk in &table.foreign_keys {
+            if !schema.tables.contains_key(&fk.referenced_table) {
+                continue;
+            }
+            let nullable = fk
+                .source_columns
+                .iter()
+                .any(|c| table.columns.get(c).is_none_or(|c| c.nullable));
+            output.push_str(&format!(
+                "    {} {}--{} {} : \"{}\"\n",
+                mermaid_id(&fk.referenced_table),
+                if nullable { "|o" } else { "||" },
+                if is_unique_key(table, &fk.source_columns) {
+                    "o|"
+                } else {
+                    "o{"
+                },
+                mermaid_id(name),
+                fk.source_columns.join(", ").replace('"', "'")
+            ));
+        }
+    }
+
+    output
+}
+
+/// A DOT string literal.
+fn dot_string(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// An FK edge label: `columns -> referenced columns`.
+fn fk_label(source_columns: &[String], referenced_columns: &[String]) -> String {
+    format!(
+        "{} -> {}",
+        source_columns.join(", "),
+        referenced_columns.join(", ")
+    )
+}
+
+/// Graphviz DOT of the (cycle-broken) graph: one node per table, numbered
+/// in insertion order, FK edges from child to parent with their columns,
+/// nullable ones dotted, and the edges broken to resolve cycles in red,
+/// filled in by UPDATEs after the inserts.
 fn generate_dot(graph: &DependencyGraph, deferred_edges: &[DeferredEdge]) -> String {
     let mut output = String::from("digraph dependencies {\n");
-    output.push_str("    rankdir=TB;\n");
+    output.push_str("    rankdir=BT;\n");
     output.push_str("    node [shape=box, style=rounded];\n\n");
 
-    // Add edges
+    // Nodes, in insertion order when the graph is acyclic
+    let order = topological_sort(graph)
+        .map(|order| order.tables)
+        .unwrap_or_default();
+    let mut tables: Vec<&str> = order.iter().map(String::as_str).collect();
+    let mut unordered: Vec<&str> = graph
+        .table_names()
+        .into_iter()
+        .filter(|t| !order.iter().any(|o| o == t))
+        .collect();
+    unordered.sort_unstable();
+    tables.extend(unordered);
+    for (i, table) in tables.iter().enumerate() {
+        let label = if i < order.len() {
+            format!("{}. {}", i + 1, table)
+        } else {
+            table.to_string()
+        };
+        output.push_str(&format!(
+            "    {} [label={}];\n",
+            dot_string(table),
+            dot_string(&label)
+        ));
+    }
+    output.push('\n');
+
+    // FK edges, child -> parent
     for edge in graph.graph.edge_references() {
-        let from = graph.table_name(edge.source());
-        let to = graph.table_name(edge.target());
-        let label = edge.weight().source_columns.join(", ");
+        let info = edge.weight();
+        let style = if info.is_nullable {
+            ", style=dotted"
+        } else {
+            ""
+        };
         output.push_str(&format!(
-            "    \"{}\" -> \"{}\" [label=\"{}\"];\n",
-            from, to, label
+            "    {} -> {} [label={}{}];\n",
+            dot_string(graph.table_name(edge.source())),
+            dot_string(graph.table_name(edge.target())),
+            dot_string(&fk_label(&info.source_columns, &info.referenced_columns)),
+            style
         ));
     }
 
-    // Add deferred edges
+    // Edges broken to resolve cycles, left out of the layout ranking
     for deferred in deferred_edges {
-        let label = deferred.source_columns.join(", ");
+        let label = format!(
+            "{} (cycle broken: deferred UPDATE)",
+            fk_label(&deferred.source_columns, &deferred.target_columns)
+        );
         output.push_str(&format!(
-            "    \"{}\" -> \"{}\" [label=\"{} (deferred)\", style=dashed, color=red];\n",
-            deferred.source_table, deferred.target_table, label
+            "    {} -> {} [label={}, style=dashed, color=red, fontcolor=red, penwidth=2, constraint=false];\n",
+            dot_string(&deferred.source_table),
+            dot_string(&deferred.target_table),
+            dot_string(&label)
         ));
     }
 
@@ -124,4 +310,130 @@ mod tests {
         assert!(output.contains("orders"));
         assert!(output.contains("users"));
     }
+
+    #[test]
+    fn test_er_diagram() {
+        let mut schema = DatabaseSchema::new(DatabaseType::PostgreSQL, "test".to_string());
+        let mut users = Table::new("users".to_string());
+        for (name, raw_type) in [
+            ("id", "bigint"),
+            ("email", "character varying(255)"),
+            ("bio", "text"),
+        ] {
+            users.columns.insert(
+                name.to_string(),
+                Column::new(name.to_string(), DataType::Text, raw_type.to_string()),
+            );
+        }
+        users.primary_key = Some(PrimaryKey {
+            columns: vec!["id".to_string()],
+            name: None,
+        });
+        users.unique_constraints.push(UniqueConstraint {
+            name: None,
+            columns: vec!["email".to_string()],
+        });
+        schema.tables.insert("users".to_string(), users);
+
+        let mut profiles = Table::new("user profiles".to_string());
+        let mut user_id = Column::new("user_id".to_string(), DataType::BigInt, "int8".to_string());
+        user_id.nullable = false;
+        profiles.columns.insert("user_id".to_string(), user_id);
+        profiles.primary_key = Some(PrimaryKey {
+            columns: vec!["user_id".to_string()],
+            name: None,
+        });
+        profiles.foreign_keys.push(ForeignKey {
+            name: None,
+            source_columns: vec!["user_id".to_string()],
+            referenced_table: "users".to_string(),
+            referenced_columns: vec!["id".to_string()],
+            on_delete: ForeignKeyAction::Cascade,
+            on_update: ForeignKeyAction::NoAction,
+            is_deferrable: false,
+        });
+        schema.tables.insert("user profiles".to_string(), profiles);
+        schema
+            .tables
+            .insert("tags".to_string(), Table::new("tags".to_string()));
+
+        let output = er_diagram(&schema);
+        assert!(output.starts_with("erDiagram\n"), "{}", output);
+        assert!(
+            output.contains(
+                "    users {\n        bigint id PK\n        character_varying email UK\n    }\n"
+            ),
+            "{}",
+            output
+        );
+        assert!(!output.contains("bio"), "{}", output);
+        assert!(
+            output
+                .contains("    user_profiles[\"user profiles\"] {\n        int8 user_id PK, FK\n"),
+            "{}",
+            output
+        );
+        assert!(output.contains("    tags\n"), "{}", output);
+        // One profile per user, required
+        assert!(
+            output.contains("    users ||--o| user_profiles : \"user_id\"\n"),
+            "{}",
+            output
+        );
+    }
+
+    #[test]
+    fn test_dot_output() {
+        use crate::graph::cycle::break_cycles;
+
+        let fk = |column: &str, table: &str| ForeignKey {
+            name: None,
+            source_columns: vec![column.to_string()],
+            referenced_table: table.to_string(),
+            referenced_columns: vec!["id".to_string()],
+            on_delete: ForeignKeyAction::NoAction,
+            on_update: ForeignKeyAction::NoAction,
+            is_deferrable: false,
+        };
+        let column = |name: &str, nullable: bool| {
+            let mut column = Column::new(name.to_string(), DataType::Integer, "int4".to_string());
+            column.nullable = nullable;
+            column
+        };
+        // users.team_id and teams.owner_id form a cycle, broken at the
+        // nullable users.team_id
+        let mut schema = DatabaseSchema::new(DatabaseType::PostgreSQL, "test".to_string());
+        let mut users = Table::new("users".to_string());
+        users
+            .columns
+            .insert("team_id".to_string(), column("team_id", true));
+        users.foreign_keys.push(fk("team_id", "teams"));
+        let mut teams = Table::new("teams".to_string());
+        teams
+            .columns
+            .insert("owner_id".to_string(), column("owner_id", false));
+        teams.foreign_keys.push(fk("owner_id", "users"));
+        schema.tables.insert("users".to_string(), users);
+        schema.tables.insert("teams".to_string(), teams);
+        schema.tables.insert(
+            "audit \"log\"".to_string(),
+            Table::new("audit \"log\"".to_string()),
+        );
+
+        let mut graph = DependencyGraph::from_schema(&schema);
+        let deferred = break_cycles(&mut graph, &[]).unwrap();
+        let output = visualize(&graph, &deferred, GraphFormat::Dot);
+
+        assert!(output.starts_with("digraph dependencies {"), "{}", output);
+        assert!(
+            output.contains("\"teams\" -> \"users\" [label=\"owner_id -> id\"];"),
+            "{}",
+            output
+        );
+        assert!(output.contains("\"users\" -> \"teams\" [label=\"team_id -> id (cycle broken: deferred UPDATE)\", style=dashed, color=red"), "{}", output);
+        // Insertion order numbers the nodes: users before teams
+        let position = |label: &str| output.find(label).unwrap();
+        assert!(position(". users\"") < position(". teams\""), "{}", output);
+        assert!(output.contains("\"audit \\\"log\\\"\""), "{}", output);
+    }
 }
@@ -1,6 +1,8 @@
 pub mod check;
 pub mod classify;
 pub mod config;
+pub mod config_schema;
+pub mod ephemeral;
 pub mod error;
 pub mod generate;
 pub mod graph;
@@ -9,7 +11,10 @@ pub mod lock;
 pub mod output;
 pub mod sample;
 pub mod schema;
+#[cfg(feature = "proptest")]
+pub mod strategy;
 
 // Re-export key types for convenience
+pub use ephemeral::{ephemeral_sqlite, EphemeralOptions};
 pub use error::{Result, SeedKitError};
 pub use schema::types::{DatabaseSchema, DatabaseType};
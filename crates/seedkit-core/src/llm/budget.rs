@@ -0,0 +1,218 @@
+//! # LLM Budget
+//!
+//! Before any request is sent, the CLI estimates how many requests and
+//! tokens a run's `--ai` classification and LLM-written columns will use,
+//! leaving out what the caches already hold, and prints the estimate. Caps
+//! in `seedkit.toml` stop a run from spending more than planned:
+//!
+//! ```toml
+//! [llm]
+//! max_tokens_per_run = 200000
+//! max_requests = 20
+//! over_budget = "rule-based"        # or "abort" (default)
+//! usd_per_million_tokens = 3.0      # adds a cost to the estimate
+//! ```
+//!
+//! Token counts are estimated at four characters per token for prompts,
+//! plus the expected length of each answer. They are estimates: providers
+//! tokenize differently, so leave some headroom.
+
+use std::fmt;
+use std::ops::AddAssign;
+
+use serde::Deserialize;
+
+use crate::config::LlmConfig;
+
+/// Characters per token assumed by estimates.
+pub const CHARS_PER_TOKEN: usize = 4;
+
+/// Most tokens one response may use (the `max_tokens` of every request).
+pub const MAX_OUTPUT_TOKENS: u64 = 4096;
+
+/// Tokens one column's classification takes in a response.
+pub const CLASSIFICATION_TOKENS_PER_COLUMN: u64 = 30;
+
+/// What to do when a phase's estimate doesn't fit in the budget.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum OverBudget {
+    /// Fail the run before sending anything.
+    #[default]
+    Abort,
+    /// Skip the phase: rule-based classification and usual generators.
+    RuleBased,
+}
+
+/// Requests and tokens a phase of a run is expected to use.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LlmEstimate {
+    pub requests: usize,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+}
+
+impl LlmEstimate {
+    /// Count a request sending `prompt` and expecting about
+    /// `output_tokens` back.
+    pub fn add_request(&mut self, prompt: &str, output_tokens: u64) {
+        self.requests += 1;
+        self.input_tokens += estimate_tokens(prompt);
+        self.output_tokens += output_tokens.min(MAX_OUTPUT_TOKENS);
+    }
+
+    pub fn tokens(&self) -> u64 {
+        self.input_tokens + self.output_tokens
+    }
+}
+
+impl AddAssign for LlmEstimate {
+    fn add_assign(&mut self, other: Self) {
+        self.requests += other.requests;
+        self.input_tokens += other.input_tokens;
+        self.output_tokens += other.output_tokens;
+    }
+}
+
+impl fmt::Display for LlmEstimate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} requests, ~{} tokens ({} in, {} out)",
+            self.requests,
+            self.tokens(),
+            self.input_tokens,
+            self.output_tokens
+        )
+    }
+}
+
+/// Estimated tokens of a text.
+pub fn estimate_tokens(text: &str) -> u64 {
+    text.chars().count().div_ceil(CHARS_PER_TOKEN) as u64
+}
+
+/// A run's LLM budget, and what earlier phases reserved of it.
+#[derive(Debug, Clone, Default)]
+pub struct LlmBudget {
+    pub max_tokens: Option<u64>,
+    pub max_requests: Option<usize>,
+    pub over_budget: OverBudget,
+    pub usd_per_million_tokens: Option<f64>,
+    reserved: LlmEstimate,
+}
+
+impl LlmBudget {
+    /// The budget set in `[llm]`; unlimited without one.
+    pub fn from_config(config: Option<&LlmConfig>) -> Self {
+        let Some(config) = config else {
+            return Self::default();
+        };
+        Self {
+            max_tokens: config.max_tokens_per_run,
+            max_requests: config.max_requests,
+            over_budget: config.over_budget.unwrap_or_default(),
+            usd_per_million_tokens: config.usd_per_million_tokens,
+            reserved: LlmEstimate::default(),
+        }
+    }
+
+    /// Describe `estimate` for printing, with its cost when a price is set.
+    pub fn describe(&self, estimate: &LlmEstimate) -> String {
+        match self.usd_per_million_tokens {
+            Some(price) => format!(
+                "{} ≈ ${:.2}",
+                estimate,
+                estimate.tokens() as f64 * price / 1e6
+            ),
+            None => estimate.to_string(),
+        }
+    }
+
+    /// Reserve `estimate` out of what is left of the budget, or explain
+    /// which limit it would exceed.
+    pub fn reserve(&mut self, estimate: &LlmEstimate) -> Result<(), String> {
+        let tokens = self.reserved.tokens() + estimate.tokens();
+        if let Some(max) = self.max_tokens.filter(|max| tokens > *max) {
+            return Err(format!(
+                "~{} tokens would exceed max_tokens_per_run = {}",
+                tokens, max
+            ));
+        }
+        let requests = self.reserved.requests + estimate.requests;
+        if let Some(max) = self.max_requests.filter(|max| requests > *max) {
+            return Err(format!(
+                "{} requests would exceed max_requests = {}",
+                requests, max
+            ));
+        }
+        self.reserved += *estimate;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate() {
+        assert_eq!(estimate_tokens("abcdefgh"), 2);
+        assert_eq!(estimate_tokens("abcdefghi"), 3);
+
+        let mut estimate = LlmEstimate::default();
+        estimate.add_request(&"x".repeat(400), 50);
+        estimate.add_request("", 10_000);
+        assert_eq!(
+            estimate,
+            LlmEstimate {
+                requests: 2,
+                input_tokens: 100,
+                output_tokens: 50 + MAX_OUTPUT_TOKENS,
+            }
+        );
+    }
+
+    #[test]
+    fn test_budget_reserves_across_phases() {
+        let config = LlmConfig {
+            max_tokens_per_run: Some(1000),
+            max_requests: Some(3),
+            usd_per_million_tokens: Some(3.0),
+            ..LlmConfig::default()
+        };
+        let mut budget = LlmBudget::from_config(Some(&config));
+        assert_eq!(budget.over_budget, OverBudget::Abort);
+
+        let phase = LlmEstimate {
+            requests: 1,
+            input_tokens: 400,
+            output_tokens: 100,
+        };
+        assert_eq!(
+            budget.describe(&phase),
+            "1 requests, ~500 tokens (400 in, 100 out) ≈ $0.00"
+        );
+        assert!(budget.reserve(&phase).is_ok());
+        assert!(budget.reserve(&phase).is_ok());
+        // A third phase would go over the token cap, and isn't reserved
+        let err = budget.reserve(&phase).unwrap_err();
+        assert!(err.contains("max_tokens_per_run"), "{}", err);
+
+        let small = LlmEstimate {
+            requests: 2,
+            ..LlmEstimate::default()
+        };
+        let err = budget.reserve(&small).unwrap_err();
+        assert!(err.contains("max_requests"), "{}", err);
+
+        let mut unlimited = LlmBudget::from_config(None);
+        assert!(unlimited
+            .reserve(&LlmEstimate {
+                requests: 1000,
+                input_tokens: u32::MAX as u64,
+                output_tokens: 0,
+            })
+            .is_ok());
+    }
+}
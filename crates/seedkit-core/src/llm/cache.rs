@@ -0,0 +1,204 @@
+//! # Per-Column Classification Cache
+//!
+//! `--ai` classification results are cached per column under
+//! `.seedkit/cache/classifications.json` in the project, keyed by table,
+//! column, declared type and model. A run only sends the LLM the tables
+//! with a column it hasn't classified yet, so repeated runs and CI jobs
+//! (which can cache the directory) don't pay tokens or latency again for
+//! unchanged columns, and a migration adding one column re-classifies one
+//! table instead of the schema.
+//!
+//! Columns the LLM skipped are cached too, as unclassified, so they aren't
+//! sent again either. Changing a column's type, its comment or the model
+//! invalidates its entry.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::llm::parse::ColumnClassification;
+use crate::schema::types::{Column, DatabaseSchema};
+
+/// Classification cache path, relative to the project directory.
+pub const CLASSIFICATION_CACHE_FILE: &str = ".seedkit/cache/classifications.json";
+
+/// Cached classifications, keyed by `"table.column type @ model"` (with
+/// the column's comment after its type, when it has one). `None`
+/// marks a column the LLM was asked about and skipped.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ClassificationCache {
+    pub columns: BTreeMap<String, Option<ColumnClassification>>,
+}
+
+/// The cached classifications of a schema, and the tables still to send.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CacheLookup {
+    pub classifications: BTreeMap<(String, String), ColumnClassification>,
+    /// Tables with at least one column missing from the cache.
+    pub pending_tables: Vec<String>,
+}
+
+impl ClassificationCache {
+    /// Load the cache at `path`. A missing or unreadable cache is empty.
+    pub fn load(path: &Path) -> Self {
+        match std::fs::read_to_string(path) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+                tracing::warn!("Ignoring corrupt LLM cache {}: {}", path.display(), e);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    /// Save the cache to `path` atomically, creating its directory.
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+        let tmp_path = path.with_extension("json.tmp");
+        std::fs::write(&tmp_path, json)?;
+        std::fs::rename(&tmp_path, path)
+    }
+
+    /// Split `schema`'s columns into cached classifications for `model` and
+    /// tables that still have to be sent.
+    pub fn lookup(&self, schema: &DatabaseSchema, model: &str) -> CacheLookup {
+        let mut lookup = CacheLookup::default();
+        for (table_name, table) in &schema.tables {
+            let mut pending = false;
+            for column in table.columns.values() {
+                match self.columns.get(&cache_key(table_name, column, model)) {
+                    Some(Some(classification)) => {
+                        lookup
+                            .classifications
+                            .insert((table_name.clone(), column.name.clone()), *classification);
+                    }
+                    Some(None) => {}
+                    None => pending = true,
+                }
+            }
+            if pending {
+                lookup.pending_tables.push(table_name.clone());
+            }
+        }
+        lookup
+    }
+
+    /// Record the LLM's answer for every column of `schema` (the tables
+    /// that were sent); columns it didn't classify are cached as skipped.
+    pub fn record(
+        &mut self,
+        schema: &DatabaseSchema,
+        model: &str,
+        classifications: &BTreeMap<(String, String), ColumnClassification>,
+    ) {
+        for (table_name, table) in &schema.tables {
+            for column in table.columns.values() {
+                let classification = classifications
+                    .get(&(table_name.clone(), column.name.clone()))
+                    .copied();
+                self.columns
+                    .insert(cache_key(table_name, column, model), classification);
+            }
+        }
+    }
+}
+
+/// The subset of `schema` made of `tables`, to send for classification.
+pub fn schema_subset(schema: &DatabaseSchema, tables: &[String]) -> DatabaseSchema {
+    let mut subset = schema.clone();
+    subset.tables.retain(|name, _| tables.contains(name));
+    subset
+}
+
+fn cache_key(table: &str, column: &Column, model: &str) -> String {
+    match column.comment {
+        Some(ref comment) => format!(
+            "{}.{} {} COMMENT '{}' @ {}",
+            table, column.name, column.raw_type, comment, model
+        ),
+        None => format!("{}.{} {} @ {}", table, column.name, column.raw_type, model),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::classify::semantic::SemanticType;
+    use crate::schema::types::{DataType, DatabaseType, Table};
+
+    fn schema() -> DatabaseSchema {
+        let mut schema = DatabaseSchema::new(DatabaseType::PostgreSQL, "app".to_string());
+        for (table_name, columns) in [("users", ["tagline", "id"]), ("products", ["margin", "id"])]
+        {
+            let mut table = Table::new(table_name.to_string());
+            for name in columns {
+                table.columns.insert(
+                    name.to_string(),
+                    Column::new(name.to_string(), DataType::Text, "text".to_string()),
+                );
+            }
+            schema.tables.insert(table_name.to_string(), table);
+        }
+        schema
+    }
+
+    #[test]
+    fn test_cache_sends_only_unclassified_tables() {
+        let schema = schema();
+        let mut cache = ClassificationCache::default();
+        let lookup = cache.lookup(&schema, "claude-sonnet-4-20250514");
+        assert_eq!(lookup.pending_tables, vec!["users", "products"]);
+
+        let answer = BTreeMap::from([(
+            ("users".to_string(), "tagline".to_string()),
+            ColumnClassification {
+                semantic_type: SemanticType::Sentence,
+                confidence: 0.8,
+            },
+        )]);
+        let sent = schema_subset(&schema, &["users".to_string()]);
+        cache.record(&sent, "claude-sonnet-4-20250514", &answer);
+
+        // Skipped columns count as classified
+        let lookup = cache.lookup(&schema, "claude-sonnet-4-20250514");
+        assert_eq!(lookup.classifications, answer);
+        assert_eq!(lookup.pending_tables, vec!["products"]);
+
+        // Another model or a changed type misses
+        assert_eq!(cache.lookup(&schema, "gpt-4o").pending_tables.len(), 2);
+        let mut altered = schema.clone();
+        altered.tables["users"].columns["tagline"].raw_type = "varchar(80)".to_string();
+        let lookup = cache.lookup(&altered, "claude-sonnet-4-20250514");
+        assert_eq!(lookup.pending_tables, vec!["users", "products"]);
+
+        // So does a new comment
+        let mut commented = schema.clone();
+        commented.tables["users"].columns["tagline"].comment = Some("Shown on profiles".into());
+        let lookup = cache.lookup(&commented, "claude-sonnet-4-20250514");
+        assert_eq!(lookup.pending_tables, vec!["users", "products"]);
+    }
+
+    #[test]
+    fn test_cache_round_trip_on_disk() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(CLASSIFICATION_CACHE_FILE);
+        assert_eq!(
+            ClassificationCache::load(&path),
+            ClassificationCache::default()
+        );
+
+        let mut cache = ClassificationCache::default();
+        cache.record(&schema(), "llama3.1", &BTreeMap::new());
+        cache.save(&path).unwrap();
+        assert_eq!(ClassificationCache::load(&path), cache);
+
+        std::fs::write(&path, "not json").unwrap();
+        assert_eq!(
+            ClassificationCache::load(&path),
+            ClassificationCache::default()
+        );
+    }
+}
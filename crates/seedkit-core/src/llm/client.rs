@@ -1,21 +1,92 @@
 //! # LLM API Client
 //!
-//! Sends schema analysis requests to Claude or OpenAI and returns the raw
-//! response text. Supports auto-detection of the API key from environment
+//! Sends schema analysis and content requests to Claude, OpenAI or a
+//! self-hosted model and returns the raw response text. Supports auto-detection of the API key from environment
 //! variables and optional model overrides via `--model`.
 //!
-//! Responses are cached to `~/.seedkit/cache/` keyed by schema hash so
-//! repeated runs against the same schema avoid redundant API calls.
+//! The provider and model can also be pinned in `seedkit.toml`'s `[llm]`
+//! section or with `SEEDKIT_LLM_PROVIDER` / `SEEDKIT_LLM_MODEL`, so teams
+//! standardized on one vendor never fall through to the other's key.
+//!
+//! For shops that can't send schema metadata to an external API, the
+//! `local` provider (alias `ollama`) calls any OpenAI-compatible chat
+//! endpoint instead — Ollama, vLLM, llama.cpp's server — at `base_url`
+//! (default `http://localhost:11434/v1`). It never falls back to a hosted
+//! provider.
+//!
+//! Content responses are cached to `~/.seedkit/cache/` keyed by prompt hash
+//! so repeated runs avoid redundant API calls; classifications are cached
+//! per column in the project (see `llm::cache`).
 
 use std::path::PathBuf;
 
+use crate::config::LlmConfig;
 use crate::error::{Result, SeedKitError};
+use crate::llm::budget::MAX_OUTPUT_TOKENS;
 
 /// Supported LLM providers.
 #[derive(Debug, Clone)]
 pub enum LlmProvider {
-    Claude { api_key: String, model: String },
-    OpenAI { api_key: String, model: String },
+    Claude {
+        api_key: String,
+        model: String,
+    },
+    OpenAI {
+        api_key: String,
+        model: String,
+    },
+    /// An OpenAI-compatible endpoint, usually self-hosted.
+    Local {
+        base_url: String,
+        api_key: Option<String>,
+        model: String,
+    },
+}
+
+/// Base URL of Ollama's OpenAI-compatible API, the default local endpoint.
+pub const DEFAULT_LOCAL_BASE_URL: &str = "http://localhost:11434/v1";
+
+/// A provider named in configuration, before its API key is looked up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProviderKind {
+    Anthropic,
+    OpenAI,
+    Local,
+}
+
+impl ProviderKind {
+    /// Parse a provider name: `anthropic` (or `claude`), `openai` or
+    /// `local` (or `ollama`).
+    pub fn parse(name: &str) -> std::result::Result<Self, String> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "anthropic" | "claude" => Ok(ProviderKind::Anthropic),
+            "openai" => Ok(ProviderKind::OpenAI),
+            "local" | "ollama" => Ok(ProviderKind::Local),
+            other => Err(format!(
+                "unknown LLM provider '{}' (expected 'anthropic', 'openai' or 'local')",
+                other
+            )),
+        }
+    }
+
+    /// Environment variable holding the provider's API key, optional for
+    /// local endpoints.
+    pub fn key_var(self) -> &'static str {
+        match self {
+            ProviderKind::Anthropic => "ANTHROPIC_API_KEY",
+            ProviderKind::OpenAI => "OPENAI_API_KEY",
+            ProviderKind::Local => "SEEDKIT_LLM_API_KEY",
+        }
+    }
+
+    /// Model used unless one is configured.
+    pub fn default_model(self) -> &'static str {
+        match self {
+            ProviderKind::Anthropic => "claude-sonnet-4-20250514",
+            ProviderKind::OpenAI => "gpt-4o",
+            ProviderKind::Local => "llama3.1",
+        }
+    }
 }
 
 impl LlmProvider {
@@ -25,32 +96,133 @@ impl LlmProvider {
     /// a sensible default model for each provider unless `model_override` is
     /// specified.
     pub fn from_env(model_override: Option<&str>) -> Result<Self> {
-        if let Ok(key) = std::env::var("ANTHROPIC_API_KEY") {
-            return Ok(LlmProvider::Claude {
-                api_key: key,
-                model: model_override
-                    .unwrap_or("claude-sonnet-4-20250514")
-                    .to_string(),
-            });
-        }
+        Self::resolve(None, model_override, None, |var| std::env::var(var).ok())
+    }
 
-        if let Ok(key) = std::env::var("OPENAI_API_KEY") {
-            return Ok(LlmProvider::OpenAI {
-                api_key: key,
-                model: model_override.unwrap_or("gpt-4o").to_string(),
-            });
+    /// Pick the provider from `[llm]` in seedkit.toml and the environment.
+    ///
+    /// `SEEDKIT_LLM_PROVIDER` overrides the configured provider, and with
+    /// neither set the provider is auto-detected as in `from_env`, unless a
+    /// base URL (`SEEDKIT_LLM_BASE_URL` or `base_url`) points at a local
+    /// endpoint. The model is `model_override` (`--model`), else
+    /// `SEEDKIT_LLM_MODEL`, else the configured one, else the provider's
+    /// default.
+    pub fn from_config(config: Option<&LlmConfig>, model_override: Option<&str>) -> Result<Self> {
+        let env = |var: &str| std::env::var(var).ok().filter(|v| !v.is_empty());
+        let provider = env("SEEDKIT_LLM_PROVIDER").or_else(|| config?.provider.clone());
+        let model = model_override
+            .map(str::to_string)
+            .or_else(|| env("SEEDKIT_LLM_MODEL"))
+            .or_else(|| config?.model.clone());
+        let base_url = env("SEEDKIT_LLM_BASE_URL").or_else(|| config?.base_url.clone());
+        let kind = provider
+            .map(|name| ProviderKind::parse(&name))
+            .transpose()
+            .map_err(|message| SeedKitError::LlmError { message })?;
+        Self::resolve(kind, model.as_deref(), base_url.as_deref(), env)
+    }
+
+    /// Build the provider of `kind` (auto-detected when `None`) with the
+    /// API key `env` finds for it. A `base_url` implies a local endpoint.
+    fn resolve(
+        kind: Option<ProviderKind>,
+        model: Option<&str>,
+        base_url: Option<&str>,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> Result<Self> {
+        let candidates = match kind.or(base_url.map(|_| ProviderKind::Local)) {
+            Some(ProviderKind::Local) => vec![ProviderKind::Local],
+            Some(kind) if base_url.is_some() => {
+                return Err(SeedKitError::LlmError {
+                    message: format!(
+                        "An LLM base URL only applies to the local provider, not {:?}.",
+                        kind
+                    ),
+                })
+            }
+            Some(kind) => vec![kind],
+            None => vec![ProviderKind::Anthropic, ProviderKind::OpenAI],
+        };
+        for kind in candidates {
+            let model = model.unwrap_or(kind.default_model()).to_string();
+            match (kind, env(kind.key_var())) {
+                (ProviderKind::Local, api_key) => {
+                    return Ok(LlmProvider::Local {
+                        base_url: base_url
+                            .unwrap_or(DEFAULT_LOCAL_BASE_URL)
+                            .trim_end_matches('/')
+                            .to_string(),
+                        api_key,
+                        model,
+                    })
+                }
+                (ProviderKind::Anthropic, Some(api_key)) => {
+                    return Ok(LlmProvider::Claude { api_key, model })
+                }
+                (ProviderKind::OpenAI, Some(api_key)) => {
+                    return Ok(LlmProvider::OpenAI { api_key, model })
+                }
+                _ => {}
+            }
         }
 
         Err(SeedKitError::LlmError {
-            message: "No LLM API key found. Set ANTHROPIC_API_KEY or OPENAI_API_KEY environment variable.".to_string(),
+            message: match kind {
+                Some(kind) => format!(
+                    "The configured LLM provider needs an API key. Set {} environment variable.",
+                    kind.key_var()
+                ),
+                None => "No LLM API key found. Set ANTHROPIC_API_KEY or OPENAI_API_KEY environment variable.".to_string(),
+            },
         })
     }
 
+    /// The model requests are sent to.
+    pub fn model(&self) -> &str {
+        match self {
+            LlmProvider::Claude { model, .. }
+            | LlmProvider::OpenAI { model, .. }
+            | LlmProvider::Local { model, .. } => model,
+        }
+    }
+
     /// Send a prompt to the LLM and return the raw response text.
     pub async fn classify(&self, prompt: &str) -> Result<String> {
+        self.complete(prompt).await
+    }
+
+    /// Send a prompt asking for a JSON array and return the raw response
+    /// text.
+    pub async fn complete(&self, prompt: &str) -> Result<String> {
         match self {
             LlmProvider::Claude { api_key, model } => call_claude(api_key, model, prompt).await,
-            LlmProvider::OpenAI { api_key, model } => call_openai(api_key, model, prompt).await,
+            LlmProvider::OpenAI { api_key, model } => {
+                call_chat_completions(
+                    OPENAI_CHAT_URL,
+                    Some(api_key),
+                    model,
+                    prompt,
+                    "OpenAI API",
+                    API_TIMEOUT,
+                )
+                .await
+            }
+            LlmProvider::Local {
+                base_url,
+                api_key,
+                model,
+            } => {
+                let url = format!("{}/chat/completions", base_url);
+                call_chat_completions(
+                    &url,
+                    api_key.as_deref(),
+                    model,
+                    prompt,
+                    "Local LLM",
+                    LOCAL_API_TIMEOUT,
+                )
+                .await
+            }
         }
     }
 }
@@ -58,11 +230,123 @@ impl LlmProvider {
 /// Maximum time to wait for an LLM API response before aborting.
 const API_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(45);
 
+/// Maximum time to wait for a local model, which may run on a CPU.
+const LOCAL_API_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(300);
+
+/// OpenAI's chat completions endpoint.
+const OPENAI_CHAT_URL: &str = "https://api.openai.com/v1/chat/completions";
+
+/// Attempts per request, including the first.
+const MAX_ATTEMPTS: u32 = 4;
+
+/// Delay before the first retry; doubles on each later one.
+const RETRY_BACKOFF: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Longest wait between attempts, whatever a `retry-after` header asks for.
+const MAX_RETRY_DELAY: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// A failed API request.
+#[derive(Debug)]
+struct RequestError {
+    message: String,
+    /// Rate limits, overload, server errors and timeouts pass; the request
+    /// is worth sending again.
+    retryable: bool,
+    /// How long the provider asked to wait (`retry-after`).
+    retry_after: Option<std::time::Duration>,
+}
+
+/// Whether a response status is worth retrying: rate limited (429),
+/// request timeout (408), or a server error such as Anthropic's 529
+/// overload.
+fn is_retryable_status(status: reqwest::StatusCode) -> bool {
+    status == reqwest::StatusCode::TOO_MANY_REQUESTS
+        || status == reqwest::StatusCode::REQUEST_TIMEOUT
+        || status.is_server_error()
+}
+
+/// Parse a `retry-after` header given in seconds.
+fn parse_retry_after(headers: &reqwest::header::HeaderMap) -> Option<std::time::Duration> {
+    let seconds: f64 = headers
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()?;
+    (seconds.is_finite() && seconds >= 0.0).then(|| std::time::Duration::from_secs_f64(seconds))
+}
+
+/// Send a request and return the body of a successful response.
+async fn send_request(
+    request: reqwest::RequestBuilder,
+    service: &str,
+) -> std::result::Result<String, RequestError> {
+    let response = request.send().await.map_err(|e| RequestError {
+        message: format!("Failed to call {}: {}", service, e),
+        retryable: e.is_timeout() || e.is_connect(),
+        retry_after: None,
+    })?;
+
+    let status = response.status();
+    let retry_after = parse_retry_after(response.headers());
+    let response_text = response.text().await.map_err(|e| RequestError {
+        message: format!("Failed to read {} response: {}", service, e),
+        retryable: e.is_timeout(),
+        retry_after: None,
+    })?;
+
+    if !status.is_success() {
+        return Err(RequestError {
+            message: format!(
+                "{} returned {}: {}",
+                service,
+                status,
+                truncate(&response_text, 500),
+            ),
+            retryable: is_retryable_status(status),
+            retry_after,
+        });
+    }
+    Ok(response_text)
+}
+
+/// Run a request, retrying rate limits and transient failures with
+/// exponential backoff (or the provider's `retry-after`) up to
+/// `MAX_ATTEMPTS` times.
+async fn retry_request<F, Fut>(mut run: F) -> Result<String>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = std::result::Result<String, RequestError>>,
+{
+    let mut delay = RETRY_BACKOFF;
+    let mut attempt = 1;
+    loop {
+        match run().await {
+            Ok(text) => return Ok(text),
+            Err(e) if e.retryable && attempt < MAX_ATTEMPTS => {
+                let wait = e.retry_after.unwrap_or(delay).min(MAX_RETRY_DELAY);
+                tracing::warn!(
+                    "{} (attempt {}/{}); retrying in {:.0?}",
+                    e.message,
+                    attempt,
+                    MAX_ATTEMPTS,
+                    wait
+                );
+                tokio::time::sleep(wait).await;
+                delay *= 2;
+                attempt += 1;
+            }
+            Err(e) => return Err(SeedKitError::LlmError { message: e.message }),
+        }
+    }
+}
+
 /// Build an HTTP client with a strict timeout so requests never hang
 /// indefinitely on flaky networks or partial API outages.
-fn build_http_client() -> reqwest::Client {
+fn build_http_client(timeout: std::time::Duration) -> reqwest::Client {
     reqwest::Client::builder()
-        .timeout(API_TIMEOUT)
+        .timeout(timeout)
         .build()
         .unwrap_or_else(|_| reqwest::Client::new())
 }
@@ -72,11 +356,11 @@ fn build_http_client() -> reqwest::Client {
 /// Uses assistant pre-fill (`[`) to force Claude to start its response
 /// with a raw JSON array, avoiding markdown fences or preamble text.
 async fn call_claude(api_key: &str, model: &str, prompt: &str) -> Result<String> {
-    let client = build_http_client();
+    let client = build_http_client(API_TIMEOUT);
 
     let body = serde_json::json!({
         "model": model,
-        "max_tokens": 4096,
+        "max_tokens": MAX_OUTPUT_TOKENS,
         "messages": [
             {
                 "role": "user",
@@ -89,32 +373,16 @@ async fn call_claude(api_key: &str, model: &str, prompt: &str) -> Result<String>
         ]
     });
 
-    let response = client
-        .post("https://api.anthropic.com/v1/messages")
-        .header("x-api-key", api_key)
-        .header("anthropic-version", "2023-06-01")
-        .header("content-type", "application/json")
-        .json(&body)
-        .send()
-        .await
-        .map_err(|e| SeedKitError::LlmError {
-            message: format!("Failed to call Claude API: {}", e),
-        })?;
-
-    let status = response.status();
-    let response_text = response.text().await.map_err(|e| SeedKitError::LlmError {
-        message: format!("Failed to read Claude API response: {}", e),
-    })?;
-
-    if !status.is_success() {
-        return Err(SeedKitError::LlmError {
-            message: format!(
-                "Claude API returned {}: {}",
-                status,
-                truncate(&response_text, 500),
-            ),
-        });
-    }
+    let response_text = retry_request(|| {
+        let request = client
+            .post("https://api.anthropic.com/v1/messages")
+            .header("x-api-key", api_key)
+            .header("anthropic-version", "2023-06-01")
+            .header("content-type", "application/json")
+            .json(&body);
+        send_request(request, "Claude API")
+    })
+    .await?;
 
     // Extract text from the first content block
     let parsed: serde_json::Value =
@@ -135,12 +403,20 @@ async fn call_claude(api_key: &str, model: &str, prompt: &str) -> Result<String>
     Ok(format!("[{}", text))
 }
 
-/// Call the OpenAI Chat Completions API.
+/// Call the OpenAI Chat Completions API, or a compatible endpoint at `url`
+/// (Ollama, vLLM, llama.cpp's server).
 ///
 /// Uses `response_format: { "type": "json_object" }` to force the model
 /// to return valid JSON without markdown wrapping or preamble text.
-async fn call_openai(api_key: &str, model: &str, prompt: &str) -> Result<String> {
-    let client = build_http_client();
+async fn call_chat_completions(
+    url: &str,
+    api_key: Option<&str>,
+    model: &str,
+    prompt: &str,
+    service: &str,
+    timeout: std::time::Duration,
+) -> Result<String> {
+    let client = build_http_client(timeout);
 
     let body = serde_json::json!({
         "model": model,
@@ -150,39 +426,22 @@ async fn call_openai(api_key: &str, model: &str, prompt: &str) -> Result<String>
                 "content": prompt
             }
         ],
-        "max_tokens": 4096,
+        "max_tokens": MAX_OUTPUT_TOKENS,
         "response_format": { "type": "json_object" }
     });
 
-    let response = client
-        .post("https://api.openai.com/v1/chat/completions")
-        .header("Authorization", format!("Bearer {}", api_key))
-        .header("content-type", "application/json")
-        .json(&body)
-        .send()
-        .await
-        .map_err(|e| SeedKitError::LlmError {
-            message: format!("Failed to call OpenAI API: {}", e),
-        })?;
-
-    let status = response.status();
-    let response_text = response.text().await.map_err(|e| SeedKitError::LlmError {
-        message: format!("Failed to read OpenAI API response: {}", e),
-    })?;
-
-    if !status.is_success() {
-        return Err(SeedKitError::LlmError {
-            message: format!(
-                "OpenAI API returned {}: {}",
-                status,
-                truncate(&response_text, 500),
-            ),
-        });
-    }
+    let response_text = retry_request(|| {
+        let mut request = client.post(url).header("content-type", "application/json");
+        if let Some(api_key) = api_key {
+            request = request.header("Authorization", format!("Bearer {}", api_key));
+        }
+        send_request(request.json(&body), service)
+    })
+    .await?;
 
     let parsed: serde_json::Value =
         serde_json::from_str(&response_text).map_err(|e| SeedKitError::LlmError {
-            message: format!("Failed to parse OpenAI API response JSON: {}", e),
+            message: format!("Failed to parse {} response JSON: {}", service, e),
         })?;
 
     let text = parsed["choices"]
@@ -190,7 +449,7 @@ async fn call_openai(api_key: &str, model: &str, prompt: &str) -> Result<String>
         .and_then(|arr| arr.first())
         .and_then(|choice| choice["message"]["content"].as_str())
         .ok_or_else(|| SeedKitError::LlmError {
-            message: "OpenAI API response missing choices[0].message.content".to_string(),
+            message: format!("{} response missing choices[0].message.content", service),
         })?;
 
     Ok(text.to_string())
@@ -318,6 +577,85 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_resolve_configured_provider() {
+        let both = |var: &str| Some(format!("{}-value", var));
+        let provider = LlmProvider::resolve(Some(ProviderKind::OpenAI), None, None, both).unwrap();
+        match provider {
+            LlmProvider::OpenAI { api_key, model } => {
+                assert_eq!(api_key, "OPENAI_API_KEY-value");
+                assert_eq!(model, "gpt-4o");
+            }
+            _ => panic!("Expected OpenAI provider"),
+        }
+
+        // Auto-detection prefers Anthropic
+        let provider =
+            LlmProvider::resolve(None, Some("claude-opus-4-20250514"), None, both).unwrap();
+        assert!(matches!(
+            provider,
+            LlmProvider::Claude { ref model, .. } if model == "claude-opus-4-20250514"
+        ));
+
+        // A configured provider never falls back to the other's key
+        let openai_only = |var: &str| (var == "OPENAI_API_KEY").then(|| "key".to_string());
+        let err = LlmProvider::resolve(Some(ProviderKind::Anthropic), None, None, openai_only)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("ANTHROPIC_API_KEY"), "{}", err);
+    }
+
+    #[test]
+    fn test_provider_kind_parse() {
+        assert_eq!(
+            ProviderKind::parse("Anthropic"),
+            Ok(ProviderKind::Anthropic)
+        );
+        assert_eq!(ProviderKind::parse("claude"), Ok(ProviderKind::Anthropic));
+        assert_eq!(ProviderKind::parse("openai"), Ok(ProviderKind::OpenAI));
+        assert_eq!(ProviderKind::parse("ollama"), Ok(ProviderKind::Local));
+        assert!(ProviderKind::parse("gemini").is_err());
+    }
+
+    #[test]
+    fn test_resolve_local_provider() {
+        // Hosted keys in the environment are never used for a local endpoint
+        let hosted = |var: &str| (var != "SEEDKIT_LLM_API_KEY").then(|| "key".to_string());
+        let provider = LlmProvider::resolve(
+            None,
+            Some("qwen2.5:14b"),
+            Some("http://gpu-box:8000/v1/"),
+            hosted,
+        )
+        .unwrap();
+        match provider {
+            LlmProvider::Local {
+                base_url,
+                api_key,
+                model,
+            } => {
+                assert_eq!(base_url, "http://gpu-box:8000/v1");
+                assert_eq!(api_key, None);
+                assert_eq!(model, "qwen2.5:14b");
+            }
+            other => panic!("Expected local provider, got {:?}", other),
+        }
+
+        let provider = LlmProvider::resolve(Some(ProviderKind::Local), None, None, |_| None);
+        assert!(matches!(
+            provider,
+            Ok(LlmProvider::Local { ref base_url, .. }) if base_url == DEFAULT_LOCAL_BASE_URL
+        ));
+
+        let err = LlmProvider::resolve(
+            Some(ProviderKind::OpenAI),
+            None,
+            Some("http://localhost:8000/v1"),
+            hosted,
+        );
+        assert!(err.is_err());
+    }
+
     #[test]
     fn test_cache_round_trip() {
         let hash = "test_cache_hash_12345";
@@ -386,12 +724,109 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_retryable_statuses_and_retry_after() {
+        assert!(is_retryable_status(reqwest::StatusCode::TOO_MANY_REQUESTS));
+        assert!(is_retryable_status(
+            reqwest::StatusCode::from_u16(529).unwrap()
+        ));
+        assert!(is_retryable_status(reqwest::StatusCode::BAD_GATEWAY));
+        assert!(!is_retryable_status(reqwest::StatusCode::UNAUTHORIZED));
+        assert!(!is_retryable_status(reqwest::StatusCode::BAD_REQUEST));
+
+        let mut headers = reqwest::header::HeaderMap::new();
+        assert_eq!(parse_retry_after(&headers), None);
+        headers.insert(reqwest::header::RETRY_AFTER, "7".parse().unwrap());
+        assert_eq!(
+            parse_retry_after(&headers),
+            Some(std::time::Duration::from_secs(7))
+        );
+        headers.insert(
+            reqwest::header::RETRY_AFTER,
+            "Wed, 21 Oct 2015 07:28:00 GMT".parse().unwrap(),
+        );
+        assert_eq!(parse_retry_after(&headers), None);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_retry_request_backs_off() {
+        let rate_limited = || RequestError {
+            message: "Claude API returned 429".to_string(),
+            retryable: true,
+            retry_after: None,
+        };
+
+        // Succeeds on the third attempt, after waiting 2s then 4s
+        let start = tokio::time::Instant::now();
+        let mut calls = 0;
+        let result = retry_request(|| {
+            calls += 1;
+            let attempt = calls;
+            async move {
+                if attempt < 3 {
+                    Err(rate_limited())
+                } else {
+                    Ok("[]".to_string())
+                }
+            }
+        })
+        .await;
+        assert_eq!(result.unwrap(), "[]");
+        assert_eq!(start.elapsed(), std::time::Duration::from_secs(6));
+
+        // The provider's retry-after wins over the backoff
+        let start = tokio::time::Instant::now();
+        let mut calls = 0;
+        let result = retry_request(|| {
+            calls += 1;
+            let attempt = calls;
+            async move {
+                if attempt < 2 {
+                    Err(RequestError {
+                        retry_after: Some(std::time::Duration::from_secs(30)),
+                        ..rate_limited()
+                    })
+                } else {
+                    Ok("[]".to_string())
+                }
+            }
+        })
+        .await;
+        assert!(result.is_ok());
+        assert_eq!(start.elapsed(), std::time::Duration::from_secs(30));
+
+        // Gives up after MAX_ATTEMPTS
+        let mut calls = 0;
+        let result = retry_request(|| {
+            calls += 1;
+            async { Err(rate_limited()) }
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(calls, MAX_ATTEMPTS);
+
+        // Other errors aren't retried
+        let mut calls = 0;
+        let result = retry_request(|| {
+            calls += 1;
+            async {
+                Err(RequestError {
+                    retryable: false,
+                    ..rate_limited()
+                })
+            }
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(calls, 1);
+    }
+
     #[test]
     fn test_http_client_has_timeout() {
         // Verify the client builder produces a client with timeout configured.
         // We can't directly inspect reqwest's internals, but we can verify
         // build_http_client() doesn't panic.
-        let client = build_http_client();
+        let client = build_http_client(API_TIMEOUT);
         // If we got here, the client was built successfully with timeout
         assert!(std::mem::size_of_val(&client) > 0);
     }
@@ -0,0 +1,252 @@
+//! # LLM Content
+//!
+//! Columns marked for LLM generation get text written by the configured
+//! provider instead of lorem ipsum, so demo datasets read naturally:
+//!
+//! ```toml
+//! [columns."products.description".llm]
+//! prompt = "A two-sentence description of a kitchenware product"
+//! pool = 40   # distinct texts to write (default 50), reused across rows
+//! ```
+//!
+//! Texts are requested in batches of [`BATCH_SIZE`] and cached like
+//! classification responses, keyed by a hash of each request, so later runs
+//! make no API calls. Rows pick from the pool with the run's RNG, so a seed
+//! always gives the same dataset. Without an API key, or when a request
+//! fails, the column keeps its usual generator.
+
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+use crate::error::{Result, SeedKitError};
+use crate::llm::budget::{LlmEstimate, CHARS_PER_TOKEN};
+use crate::llm::client::{load_cached_response, save_cached_response, LlmProvider};
+
+/// Texts requested per API call.
+pub const BATCH_SIZE: usize = 25;
+
+/// Pool size when `pool` isn't set.
+pub const DEFAULT_POOL: usize = 50;
+
+/// Largest pool a column may ask for.
+pub const MAX_POOL: usize = 500;
+
+/// A column's `llm` settings from `seedkit.toml`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct LlmContent {
+    /// What to write, e.g. "A short support ticket subject line".
+    pub prompt: String,
+    /// Number of distinct texts to write.
+    pub pool: Option<usize>,
+}
+
+impl LlmContent {
+    pub fn validate(&self) -> std::result::Result<(), String> {
+        if self.prompt.trim().is_empty() {
+            return Err("prompt must not be empty".to_string());
+        }
+        if !(1..=MAX_POOL).contains(&self.pool_size()) {
+            return Err(format!("pool must be between 1 and {}", MAX_POOL));
+        }
+        Ok(())
+    }
+
+    pub fn pool_size(&self) -> usize {
+        self.pool.unwrap_or(DEFAULT_POOL)
+    }
+}
+
+/// Build the prompt for batch `batch` (0-based) of a column's texts.
+pub fn content_prompt(
+    table: &str,
+    column: &str,
+    instructions: &str,
+    max_length: Option<u32>,
+    count: usize,
+    batch: usize,
+) -> String {
+    let limit = match max_length {
+        Some(n) => format!(" Each text must be at most {} characters.", n),
+        None => String::new(),
+    };
+    format!(
+        r#"You are writing realistic sample data for the column `{column}` of the table `{table}`.
+
+Write {count} distinct texts, each one: {instructions}{limit}
+Vary tone, length and subject between texts. Do not use real people's names or contact details. This is batch {number}; do not repeat texts from other batches.
+
+Return ONLY a JSON array of strings with no surrounding text."#,
+        column = column,
+        table = table,
+        count = count,
+        instructions = instructions.trim(),
+        limit = limit,
+        number = batch + 1,
+    )
+}
+
+/// Parse a response into texts. Accepts a JSON array of strings, or an
+/// object holding one (OpenAI's JSON mode only returns objects), optionally
+/// inside markdown fences.
+pub fn parse_content_response(response: &str) -> Result<Vec<String>> {
+    let trimmed = response.trim();
+    let json = trimmed
+        .find("```")
+        .and_then(|start| {
+            let body = &trimmed[start + 3..];
+            let body = body.strip_prefix("json").unwrap_or(body);
+            body.find("```").map(|end| body[..end].trim())
+        })
+        .unwrap_or(trimmed);
+
+    let value: serde_json::Value =
+        serde_json::from_str(json).map_err(|e| SeedKitError::LlmError {
+            message: format!("Failed to parse LLM content response as JSON: {}", e),
+        })?;
+    let items = match value {
+        serde_json::Value::Array(items) => items,
+        serde_json::Value::Object(map) => map
+            .into_iter()
+            .find_map(|(_, v)| match v {
+                serde_json::Value::Array(items) => Some(items),
+                _ => None,
+            })
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    };
+    let texts: Vec<String> = items
+        .into_iter()
+        .filter_map(|item| match item {
+            serde_json::Value::String(s) if !s.trim().is_empty() => Some(s.trim().to_string()),
+            _ => None,
+        })
+        .collect();
+    if texts.is_empty() {
+        return Err(SeedKitError::LlmError {
+            message: "LLM content response holds no texts".to_string(),
+        });
+    }
+    Ok(texts)
+}
+
+/// Characters a text is expected to take when its column has no length
+/// limit.
+const TYPICAL_TEXT_CHARS: u64 = 240;
+
+/// A column's batches: the prompt, cache key and text count of each.
+fn content_batches(
+    table: &str,
+    column: &str,
+    content: &LlmContent,
+    max_length: Option<u32>,
+) -> Vec<(String, String, usize)> {
+    let pool = content.pool_size();
+    (0..pool.div_ceil(BATCH_SIZE))
+        .map(|batch| {
+            let count = BATCH_SIZE.min(pool - batch * BATCH_SIZE);
+            let prompt = content_prompt(table, column, &content.prompt, max_length, count, batch);
+            let key = format!("content_{:x}", Sha256::digest(prompt.as_bytes()));
+            (prompt, key, count)
+        })
+        .collect()
+}
+
+/// Requests and tokens writing a column's pool will use; cached batches
+/// are free.
+pub fn estimate_content(
+    table: &str,
+    column: &str,
+    content: &LlmContent,
+    max_length: Option<u32>,
+) -> LlmEstimate {
+    let text_tokens = max_length
+        .map_or(TYPICAL_TEXT_CHARS, |n| n as u64)
+        .div_ceil(CHARS_PER_TOKEN as u64);
+    let mut estimate = LlmEstimate::default();
+    for (prompt, key, count) in content_batches(table, column, content, max_length) {
+        if load_cached_response(&key).is_none() {
+            estimate.add_request(&prompt, count as u64 * text_tokens);
+        }
+    }
+    estimate
+}
+
+/// Write a column's pool of texts, batch by batch, from the cache where
+/// possible. Texts are cut to `max_length` characters and deduplicated.
+pub async fn fetch_content(
+    provider: &LlmProvider,
+    table: &str,
+    column: &str,
+    content: &LlmContent,
+    max_length: Option<u32>,
+) -> Result<Vec<String>> {
+    let mut texts: Vec<String> = Vec::new();
+    for (prompt, key, _) in content_batches(table, column, content, max_length) {
+        let response = match load_cached_response(&key) {
+            Some(cached) => cached,
+            None => {
+                let response = provider.complete(&prompt).await?;
+                // Only responses that parse are worth keeping
+                parse_content_response(&response)?;
+                save_cached_response(&key, &response);
+                response
+            }
+        };
+        for text in parse_content_response(&response)? {
+            let text: String = match max_length {
+                Some(n) => text.chars().take(n as usize).collect(),
+                None => text,
+            };
+            if !texts.contains(&text) {
+                texts.push(text);
+            }
+        }
+    }
+    Ok(texts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_llm_content() {
+        let content: LlmContent = toml::from_str("prompt = \"A product blurb\"").unwrap();
+        assert!(content.validate().is_ok());
+        assert_eq!(content.pool_size(), DEFAULT_POOL);
+
+        for bad in [
+            "prompt = \"  \"",
+            "prompt = \"x\"\npool = 0",
+            "prompt = \"x\"\npool = 501",
+        ] {
+            let content: LlmContent = toml::from_str(bad).unwrap();
+            assert!(content.validate().is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_content_prompt() {
+        let prompt = content_prompt("tickets", "subject", "A support subject", Some(80), 25, 1);
+        assert!(prompt.contains("`subject`"));
+        assert!(prompt.contains("Write 25 distinct texts"));
+        assert!(prompt.contains("at most 80 characters"));
+        assert!(prompt.contains("batch 2"));
+    }
+
+    #[test]
+    fn test_parse_content_response() {
+        let texts = parse_content_response(r#"["Sturdy pan.", " ", 3, "Sharp knife."]"#).unwrap();
+        assert_eq!(texts, vec!["Sturdy pan.", "Sharp knife."]);
+
+        let texts = parse_content_response(r#"{"texts": ["Sturdy pan."]}"#).unwrap();
+        assert_eq!(texts, vec!["Sturdy pan."]);
+
+        let texts =
+            parse_content_response("Here you go:\n```json\n[\"Sturdy pan.\"]\n```").unwrap();
+        assert_eq!(texts, vec!["Sturdy pan."]);
+
+        assert!(parse_content_response("[]").is_err());
+        assert!(parse_content_response("no json").is_err());
+    }
+}
@@ -1,3 +1,6 @@
+pub mod budget;
+pub mod cache;
 pub mod client;
+pub mod content;
 pub mod parse;
 pub mod prompt;
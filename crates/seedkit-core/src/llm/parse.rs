@@ -7,7 +7,7 @@
 
 use std::collections::BTreeMap;
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::classify::semantic::SemanticType;
 use crate::error::{Result, SeedKitError};
@@ -22,6 +22,32 @@ struct LlmClassification {
     confidence: f64,
 }
 
+/// The LLM's classification of one column.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ColumnClassification {
+    pub semantic_type: SemanticType,
+    pub confidence: f64,
+}
+
+/// Parse the LLM's JSON response into classifications keyed by
+/// `(table, column)`. Rows that don't parse are dropped.
+pub fn parse_classifications(
+    response: &str,
+) -> Result<BTreeMap<(String, String), ColumnClassification>> {
+    Ok(parse_llm_response(response)?
+        .into_iter()
+        .map(|c| {
+            (
+                (c.table, c.column),
+                ColumnClassification {
+                    semantic_type: c.semantic_type,
+                    confidence: c.confidence,
+                },
+            )
+        })
+        .collect())
+}
+
 /// Parse the LLM's JSON response into a list of classifications.
 ///
 /// Uses a two-stage deserialization strategy:
@@ -111,22 +137,28 @@ pub fn merge_classifications(
     rule_based: &BTreeMap<(String, String), SemanticType>,
     llm_response: &str,
 ) -> BTreeMap<(String, String), SemanticType> {
-    let mut merged = rule_based.clone();
-
-    let llm_results = match parse_llm_response(llm_response) {
-        Ok(r) => r,
+    match parse_classifications(llm_response) {
+        Ok(llm_results) => merge_column_classifications(rule_based, &llm_results),
         Err(e) => {
             tracing::warn!("Failed to parse LLM response, using rule-based only: {}", e);
-            return merged;
+            rule_based.clone()
         }
-    };
+    }
+}
+
+/// Merge parsed (or cached) LLM classifications with rule-based results,
+/// with the same conservative strategy as `merge_classifications`.
+pub fn merge_column_classifications(
+    rule_based: &BTreeMap<(String, String), SemanticType>,
+    llm_results: &BTreeMap<(String, String), ColumnClassification>,
+) -> BTreeMap<(String, String), SemanticType> {
+    let mut merged = rule_based.clone();
 
-    for classification in llm_results {
-        let key = (classification.table, classification.column);
-        let existing = merged.get(&key).copied().unwrap_or(SemanticType::Unknown);
+    for (key, classification) in llm_results {
+        let existing = merged.get(key).copied().unwrap_or(SemanticType::Unknown);
 
         if existing == SemanticType::Unknown && classification.confidence >= 0.5 {
-            merged.insert(key, classification.semantic_type);
+            merged.insert(key.clone(), classification.semantic_type);
         }
     }
 
@@ -3,9 +3,36 @@
 //! Builds structured prompts for LLM-based schema analysis. The prompt
 //! includes a compact DDL representation of the database schema and
 //! requests a JSON classification response.
+//!
+//! Large schemas are classified in batches of whole tables (see
+//! `classification_batches`), so one slow or failed request costs a batch
+//! rather than the run, and responses stay under the output token limit.
 
 use crate::schema::types::DatabaseSchema;
 
+/// Most columns sent in one classification prompt. A wider table is sent
+/// on its own.
+pub const CLASSIFICATION_BATCH_COLUMNS: usize = 80;
+
+/// Group `schema`'s tables, in order, into batches of at most
+/// `max_columns` columns.
+pub fn classification_batches(schema: &DatabaseSchema, max_columns: usize) -> Vec<Vec<String>> {
+    let mut batches: Vec<Vec<String>> = Vec::new();
+    let mut columns = 0;
+    for (table_name, table) in &schema.tables {
+        let width = table.columns.len();
+        if batches.is_empty() || columns + width > max_columns {
+            batches.push(Vec::new());
+            columns = 0;
+        }
+        if let Some(batch) = batches.last_mut() {
+            batch.push(table_name.clone());
+        }
+        columns += width;
+    }
+    batches
+}
+
 /// Generate a classification prompt from a schema DDL string.
 pub fn classification_prompt(ddl: &str) -> String {
     format!(
@@ -41,8 +68,8 @@ Schema DDL:
 /// Build a compact DDL representation from an introspected schema.
 ///
 /// Produces a CREATE TABLE-style summary that includes column names, types,
-/// nullability, primary keys, and foreign keys — enough for the LLM to
-/// classify columns without needing the full raw DDL.
+/// nullability, comments, primary keys, and foreign keys — enough for the
+/// LLM to classify columns without needing the full raw DDL.
 pub fn schema_to_compact_ddl(schema: &DatabaseSchema) -> String {
     let mut ddl = String::new();
 
@@ -64,6 +91,9 @@ pub fn schema_to_compact_ddl(schema: &DatabaseSchema) -> String {
             if let Some(ref vals) = column.enum_values {
                 line.push_str(&format!(" /* ENUM: {} */", vals.join(", ")));
             }
+            if let Some(ref comment) = column.comment {
+                line.push_str(&format!(" COMMENT '{}'", comment.replace('\'', "''")));
+            }
             col_lines.push(line);
         }
 
@@ -151,6 +181,29 @@ mod tests {
         assert!(ddl.contains("PRIMARY KEY (id)"));
     }
 
+    #[test]
+    fn test_classification_batches() {
+        let mut schema = DatabaseSchema::new(DatabaseType::PostgreSQL, "test".to_string());
+        for (name, width) in [("users", 3), ("orders", 2), ("events", 9), ("tags", 1)] {
+            let mut table = Table::new(name.to_string());
+            for i in 0..width {
+                let column = format!("c{}", i);
+                table.columns.insert(
+                    column.clone(),
+                    Column::new(column, DataType::Text, "text".to_string()),
+                );
+            }
+            schema.tables.insert(name.to_string(), table);
+        }
+
+        // Tables stay whole; a table wider than a batch goes alone
+        assert_eq!(
+            classification_batches(&schema, 5),
+            vec![vec!["users", "orders"], vec!["events"], vec!["tags"]]
+        );
+        assert_eq!(classification_batches(&schema, 100).len(), 1);
+    }
+
     #[test]
     fn test_compact_ddl_includes_foreign_keys() {
         let mut schema = DatabaseSchema::new(DatabaseType::PostgreSQL, "test".to_string());
@@ -204,6 +257,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_compact_ddl_includes_comments() {
+        let mut schema = DatabaseSchema::new(DatabaseType::PostgreSQL, "test".to_string());
+        let mut table = Table::new("customers".to_string());
+        let mut column = Column::new(
+            "cst_ml".to_string(),
+            DataType::VarChar,
+            "varchar(120)".to_string(),
+        );
+        column.comment = Some("Customer's e-mail".to_string());
+        table.columns.insert("cst_ml".to_string(), column);
+        schema.tables.insert("customers".to_string(), table);
+
+        let ddl = schema_to_compact_ddl(&schema);
+        assert!(
+            ddl.contains("cst_ml varchar(120) COMMENT 'Customer''s e-mail'"),
+            "{}",
+            ddl
+        );
+    }
+
     #[test]
     fn test_compact_ddl_includes_check_constraints() {
         let mut schema = DatabaseSchema::new(DatabaseType::PostgreSQL, "test".to_string());
@@ -17,11 +17,22 @@
 //!
 //! This regenerates a clean lock file from the current schema. Never
 //! attempt to merge lock file JSON by hand.
+//!
+//! ## Profiles
+//!
+//! Environments that need different datasets from the same schema (a small
+//! CI dataset, a big demo one) each keep their own lock file, selected with
+//! `--profile`: `seedkit.lock.ci`, `seedkit.lock.demo`. Every profile has
+//! its own seed, row counts and overrides, and is reproduced and checked
+//! for drift on its own. The schema snapshot is the one thing they share:
+//! `seedkit check --update` refreshes it in every lock file of the
+//! directory, so accepting drift once doesn't leave the other profiles
+//! behind.
 
 pub mod types;
 
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use self::types::LockFile;
 use crate::error::{Result, SeedKitError};
@@ -29,6 +40,48 @@ use crate::error::{Result, SeedKitError};
 /// Default lock file name.
 pub const LOCK_FILE_NAME: &str = "seedkit.lock";
 
+/// Lock file name of a profile (`seedkit.lock.ci`), or the default one.
+pub fn lock_file_name(profile: Option<&str>) -> Result<String> {
+    let Some(profile) = profile else {
+        return Ok(LOCK_FILE_NAME.to_string());
+    };
+    if profile.is_empty()
+        || !profile
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(SeedKitError::LockFile {
+            message: format!(
+                "Invalid profile name '{}': use letters, digits, '-' and '_'",
+                profile
+            ),
+        });
+    }
+    Ok(format!("{}.{}", LOCK_FILE_NAME, profile))
+}
+
+/// Every lock file in `dir`, the default one and each profile's, sorted.
+pub fn lock_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let entries = fs::read_dir(dir).map_err(|e| SeedKitError::Output {
+        message: format!("Failed to list lock files in {}", dir.display()),
+        source: e,
+    })?;
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name().into_string().ok()?;
+            let is_lock = match name.strip_prefix(LOCK_FILE_NAME)? {
+                "" => true,
+                profile => profile
+                    .strip_prefix('.')
+                    .is_some_and(|p| lock_file_name(Some(p)).is_ok()),
+            };
+            is_lock.then(|| dir.join(name))
+        })
+        .collect();
+    paths.sort();
+    Ok(paths)
+}
+
 /// Write a lock file to disk atomically.
 ///
 /// Writes to a temporary file in the same directory, then renames it
@@ -43,7 +96,11 @@ pub fn write_lock_file(lock: &LockFile, path: &Path) -> Result<()> {
     })?;
 
     let dir = path.parent().unwrap_or(Path::new("."));
-    let tmp_path = dir.join(".seedkit.lock.tmp");
+    let file_name = path
+        .file_name()
+        .map(|f| f.to_string_lossy().into_owned())
+        .unwrap_or_else(|| LOCK_FILE_NAME.to_string());
+    let tmp_path = dir.join(format!(".{}.tmp", file_name));
 
     // Write to temp file, flush to disk
     let mut file = fs::File::create(&tmp_path).map_err(|e| SeedKitError::Output {
@@ -79,10 +136,14 @@ pub fn read_lock_file(path: &Path) -> Result<LockFile> {
         message: format!("Failed to read lock file from {}", path.display()),
         source: e,
     })?;
-    let lock: LockFile = serde_json::from_str(&content).map_err(|e| SeedKitError::LockFile {
+    parse_lock_file(&content)
+}
+
+/// Parse lock file JSON, e.g. a version read from git history.
+pub fn parse_lock_file(content: &str) -> Result<LockFile> {
+    serde_json::from_str(content).map_err(|e| SeedKitError::LockFile {
         message: format!("Failed to parse lock file: {}", e),
-    })?;
-    Ok(lock)
+    })
 }
 
 #[cfg(test)]
@@ -114,6 +175,10 @@ mod tests {
                 exclude_tables: Vec::new(),
                 ai_classifications: None,
                 column_overrides: None,
+                locale: None,
+                table_streams: true,
+                lookup_tables: None,
+                scenario: None,
             },
             dummy_schema(),
         )
@@ -124,13 +189,45 @@ mod tests {
         let dir = tempdir().unwrap();
         let path = dir.path().join("seedkit.lock");
 
-        let lock = make_lock();
+        let mut lock = make_lock();
+        assert!(!serde_json::to_string(&lock).unwrap().contains("scenario"));
+        lock.config.scenario = Some("demo".to_string());
         write_lock_file(&lock, &path).unwrap();
         let loaded = read_lock_file(&path).unwrap();
 
         assert_eq!(loaded.schema_hash, "abc123");
         assert_eq!(loaded.seed, 42);
         assert_eq!(loaded.config.default_row_count, 100);
+        assert_eq!(loaded.config.scenario.as_deref(), Some("demo"));
+    }
+
+    #[test]
+    fn test_stable_tables_round_trip() {
+        use crate::generate::value::Value;
+        use crate::lock::types::StableTableLock;
+
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("seedkit.lock");
+
+        let mut lock = make_lock();
+        let json = serde_json::to_string(&lock).unwrap();
+        assert!(!json.contains("stable_tables"), "empty map is omitted");
+
+        let mut row = indexmap::IndexMap::new();
+        row.insert("sku".to_string(), Value::String("SKU-001".into()));
+        row.insert("price".to_string(), Value::Float(19.99));
+        lock.stable_tables.insert(
+            "products".to_string(),
+            StableTableLock {
+                table_hash: "def456".to_string(),
+                rows: vec![row.clone()],
+            },
+        );
+        write_lock_file(&lock, &path).unwrap();
+        let loaded = read_lock_file(&path).unwrap();
+
+        assert_eq!(loaded.stable_tables["products"].table_hash, "def456");
+        assert_eq!(loaded.stable_tables["products"].rows, vec![row]);
     }
 
     #[test]
@@ -190,6 +287,10 @@ mod tests {
                 exclude_tables: Vec::new(),
                 ai_classifications: None,
                 column_overrides: None,
+                locale: None,
+                table_streams: true,
+                lookup_tables: None,
+                scenario: None,
             },
             dummy_schema(),
         );
@@ -231,6 +332,10 @@ mod tests {
                 exclude_tables: Vec::new(),
                 ai_classifications: None,
                 column_overrides: None,
+                locale: None,
+                table_streams: true,
+                lookup_tables: None,
+                scenario: None,
             },
             dummy_schema(),
         );
@@ -246,6 +351,10 @@ mod tests {
                 exclude_tables: Vec::new(),
                 ai_classifications: None,
                 column_overrides: None,
+                locale: None,
+                table_streams: true,
+                lookup_tables: None,
+                scenario: None,
             },
             dummy_schema(),
         );
@@ -299,6 +408,10 @@ mod tests {
                 exclude_tables: Vec::new(),
                 ai_classifications: Some(classifications),
                 column_overrides: None,
+                locale: None,
+                table_streams: true,
+                lookup_tables: None,
+                scenario: None,
             },
             dummy_schema(),
         );
@@ -314,6 +427,98 @@ mod tests {
         assert_eq!(loaded_cls["orders"]["total"], SemanticType::Price,);
     }
 
+    #[test]
+    fn test_update_schema_keeps_generation_config() {
+        use crate::check::{check_drift, compute_schema_hash};
+        use crate::schema::types::*;
+
+        let mut lock = make_lock();
+        lock.config
+            .table_row_overrides
+            .insert("users".to_string(), 10);
+
+        let mut schema = dummy_schema();
+        let mut users = Table::new("users".to_string());
+        users.columns.insert(
+            "id".to_string(),
+            Column::new("id".to_string(), DataType::Serial, "serial".to_string()),
+        );
+        schema.tables.insert("users".to_string(), users);
+        assert!(check_drift(&lock.schema_snapshot, &lock.schema_hash, &schema).has_drift);
+
+        lock.update_schema(schema.clone());
+        assert_eq!(lock.schema_hash, compute_schema_hash(&schema));
+        assert!(!check_drift(&lock.schema_snapshot, &lock.schema_hash, &schema).has_drift);
+        assert_eq!(lock.seed, 42);
+        assert_eq!(lock.base_time, "2025-06-15T12:00:00");
+        assert_eq!(lock.config.table_row_overrides["users"], 10);
+    }
+
+    #[test]
+    fn test_lookup_tables_replay_without_detection() {
+        use crate::generate::lookup::LookupDataset;
+        use crate::schema::types::Table;
+
+        let mut lock = make_lock();
+        let mut schema = dummy_schema();
+        for name in ["countries", "currencies"] {
+            schema
+                .tables
+                .insert(name.to_string(), Table::new(name.to_string()));
+        }
+        assert!(lock.config.lookup_overrides(&schema).is_none());
+
+        lock.config.lookup_tables = Some(BTreeMap::from([(
+            "countries".to_string(),
+            "countries".to_string(),
+        )]));
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("seedkit.lock");
+        write_lock_file(&lock, &path).unwrap();
+        let loaded = read_lock_file(&path).unwrap();
+
+        let overrides = loaded.config.lookup_overrides(&schema).unwrap();
+        assert_eq!(overrides["countries"], Some(LookupDataset::Countries));
+        assert_eq!(overrides["currencies"], None);
+    }
+
+    #[test]
+    fn test_lock_file_name() {
+        assert_eq!(lock_file_name(None).unwrap(), "seedkit.lock");
+        assert_eq!(lock_file_name(Some("ci")).unwrap(), "seedkit.lock.ci");
+        assert_eq!(
+            lock_file_name(Some("demo_2-large")).unwrap(),
+            "seedkit.lock.demo_2-large"
+        );
+        assert!(lock_file_name(Some("")).is_err());
+        assert!(lock_file_name(Some("../ci")).is_err());
+    }
+
+    #[test]
+    fn test_lock_files() {
+        let dir = tempdir().unwrap();
+        for name in [
+            "seedkit.lock.demo",
+            "seedkit.lock",
+            "seedkit.lock.ci",
+            ".seedkit.lock.tmp",
+            "seedkit.lock.",
+            "seedkit.locked",
+            "seedkit.toml",
+        ] {
+            fs::write(dir.path().join(name), "{}").unwrap();
+        }
+        let names: Vec<String> = lock_files(dir.path())
+            .unwrap()
+            .iter()
+            .map(|p| p.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(
+            names,
+            vec!["seedkit.lock", "seedkit.lock.ci", "seedkit.lock.demo"]
+        );
+    }
+
     #[test]
     fn test_ai_classifications_none_when_not_used() {
         let lock = make_lock();
@@ -360,6 +565,10 @@ mod tests {
                 exclude_tables: Vec::new(),
                 ai_classifications: None,
                 column_overrides: None,
+                locale: None,
+                table_streams: true,
+                lookup_tables: None,
+                scenario: None,
             },
             schema,
         );
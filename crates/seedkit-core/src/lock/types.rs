@@ -1,8 +1,11 @@
 use std::collections::BTreeMap;
 
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 
 use crate::classify::semantic::SemanticType;
+use crate::generate::lookup::LookupDataset;
+use crate::generate::value::Value;
 use crate::schema::types::DatabaseSchema;
 
 /// The seedkit.lock file structure for deterministic reproducibility.
@@ -44,6 +47,25 @@ pub struct LockFile {
     pub base_time: String,
     /// Timestamp when the lock file was created.
     pub created_at: String,
+    /// Recorded rows of stable tables, replayed on every later run so
+    /// bookmarked entities keep their IDs and values. BTreeMap for
+    /// deterministic JSON ordering.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub stable_tables: BTreeMap<String, StableTableLock>,
+    /// Sequence offsets detected from the target database at generation
+    /// time (table name → last used ID), so `--from-lock` synthesizes the
+    /// same foreign key IDs.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub sequence_offsets: BTreeMap<String, u64>,
+}
+
+/// Rows recorded for a stable table.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StableTableLock {
+    /// Hash of the table definition the rows were generated for. When the
+    /// table changes shape the rows are discarded and regenerated.
+    pub table_hash: String,
+    pub rows: Vec<IndexMap<String, Value>>,
 }
 
 /// Configuration captured in the lock file for reproducible generation.
@@ -83,6 +105,51 @@ pub struct LockConfig {
     /// BTreeMap for deterministic JSON ordering.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub column_overrides: Option<BTreeMap<String, ColumnOverrideLock>>,
+
+    /// Locale used for person names (e.g. `"ja"`). `None` means the default
+    /// English names.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+
+    /// Whether every table drew from its own RNG stream (see
+    /// `GenerationPlan::table_streams`). Lock files written before
+    /// per-table streams lack it, and replay with the shared stream.
+    #[serde(default)]
+    pub table_streams: bool,
+
+    /// Tables filled from curated lookup datasets (table name → dataset),
+    /// replayed as recorded so a change to lookup detection can't alter
+    /// the run. `None` in lock files written before lookup tables were
+    /// recorded, which detect them again.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lookup_tables: Option<BTreeMap<String, String>>,
+
+    /// The `[scenario.<name>]` of seedkit.toml the run was shaped by,
+    /// applied again by `--from-lock` and `seedkit regen`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scenario: Option<String>,
+}
+
+impl LockConfig {
+    /// Lookup overrides replaying the recorded lookup tables: each
+    /// recorded table gets its dataset and every other table in `schema`
+    /// opts out of detection. `None` when nothing was recorded.
+    pub fn lookup_overrides(
+        &self,
+        schema: &DatabaseSchema,
+    ) -> Option<BTreeMap<String, Option<LookupDataset>>> {
+        let recorded = self.lookup_tables.as_ref()?;
+        Some(
+            schema
+                .tables
+                .keys()
+                .map(|table| {
+                    let dataset = recorded.get(table).and_then(|d| d.parse().ok());
+                    (table.clone(), dataset)
+                })
+                .collect(),
+        )
+    }
 }
 
 /// Column value override captured in the lock file.
@@ -110,9 +177,22 @@ impl LockFile {
             schema_snapshot: schema,
             base_time: base_time.format("%Y-%m-%dT%H:%M:%S").to_string(),
             created_at: chrono::Utc::now().to_rfc3339(),
+            stable_tables: BTreeMap::new(),
+            sequence_offsets: BTreeMap::new(),
         }
     }
 
+    /// Accept the current schema: replace the snapshot and its hash, keeping
+    /// the seed, base time, row counts, classifications and overrides.
+    ///
+    /// Stable table rows are kept too; the next run still discards the rows
+    /// of any stable table whose shape changed.
+    pub fn update_schema(&mut self, schema: DatabaseSchema) {
+        self.schema_hash = crate::check::compute_schema_hash(&schema);
+        self.schema_snapshot = schema;
+        self.seedkit_version = env!("CARGO_PKG_VERSION").to_string();
+    }
+
     /// Parse the stored base_time back to NaiveDateTime.
     pub fn parse_base_time(&self) -> Option<chrono::NaiveDateTime> {
         chrono::NaiveDateTime::parse_from_str(&self.base_time, "%Y-%m-%dT%H:%M:%S").ok()
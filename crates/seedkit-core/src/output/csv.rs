@@ -105,7 +105,7 @@ pub fn write_csv_table<W: Write>(
 }
 
 /// Escape a string for CSV: quote if it contains comma, quote, or newline.
-fn csv_escape(s: &str) -> String {
+pub(crate) fn csv_escape(s: &str) -> String {
     if s.contains(',') || s.contains('"') || s.contains('\n') || s.contains('\r') {
         format!("\"{}\"", s.replace('"', "\"\""))
     } else {
@@ -0,0 +1,427 @@
+//! # Data Dictionary
+//!
+//! Describes every generated column — semantic type, strategy, example
+//! values, null rate, uniqueness and the constraints it honors — so people
+//! using a seeded environment know what the data means without reading
+//! `seedkit.toml`. Written as a markdown table or as CSV.
+
+use std::collections::HashSet;
+use std::io::Write;
+
+use crate::error::{Result, SeedKitError};
+use crate::generate::engine::GeneratedData;
+use crate::generate::foreign_key::FanOut;
+use crate::generate::plan::{GenerationPlan, GenerationStrategy};
+use crate::generate::value::Value;
+use crate::output::csv::csv_escape;
+use crate::schema::types::{DatabaseSchema, Table};
+
+/// Example values listed per column.
+const EXAMPLE_COUNT: usize = 3;
+
+/// Examples longer than this are cut short.
+const EXAMPLE_MAX_CHARS: usize = 40;
+
+/// Description of one generated column.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DictionaryEntry {
+    pub table: String,
+    pub column: String,
+    /// Declared database type, e.g. `varchar(255)`.
+    pub data_type: String,
+    pub semantic_type: String,
+    pub strategy: String,
+    /// Up to three distinct non-NULL values from the generated rows.
+    pub examples: Vec<String>,
+    /// Share of generated rows that are NULL.
+    pub null_rate: f64,
+    /// Distinct non-NULL values in the generated rows.
+    pub distinct_values: usize,
+    pub constraints: Vec<String>,
+}
+
+/// Describe every planned column, in plan order, from the generated rows.
+pub fn build_dictionary(
+    plan: &GenerationPlan,
+    schema: &DatabaseSchema,
+    data: &GeneratedData,
+) -> Vec<DictionaryEntry> {
+    let mut entries = Vec::new();
+    for table_plan in &plan.table_plans {
+        let table = schema.tables.get(&table_plan.table_name);
+        let rows = data
+            .tables
+            .get(&table_plan.table_name)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        for col_plan in &table_plan.column_plans {
+            let name = col_plan.column_name.as_str();
+            let values: Vec<&Value> = rows.iter().filter_map(|row| row.get(name)).collect();
+            let nulls = values.iter().filter(|v| v.is_null()).count();
+
+            let mut seen = HashSet::new();
+            let mut examples = Vec::new();
+            for value in values.iter().filter(|v| !v.is_null()) {
+                let text = value.to_csv_string();
+                if seen.insert(text.clone()) && examples.len() < EXAMPLE_COUNT {
+                    examples.push(shorten(&text));
+                }
+            }
+
+            entries.push(DictionaryEntry {
+                table: table_plan.table_name.clone(),
+                column: name.to_string(),
+                data_type: table
+                    .and_then(|t| t.columns.get(name))
+                    .map(|c| c.raw_type.clone())
+                    .unwrap_or_default(),
+                semantic_type: col_plan.semantic_type.to_string(),
+                strategy: describe_strategy(&col_plan.strategy),
+                examples,
+                null_rate: if values.is_empty() {
+                    0.0
+                } else {
+                    nulls as f64 / values.len() as f64
+                },
+                distinct_values: seen.len(),
+                constraints: table.map(|t| constraints(t, name)).unwrap_or_default(),
+            });
+        }
+    }
+    entries
+}
+
+/// Write the dictionary as one markdown table per database table.
+pub fn write_dictionary_markdown<W: Write>(
+    writer: &mut W,
+    entries: &[DictionaryEntry],
+) -> Result<()> {
+    let mut out = String::from("# Data Dictionary\n");
+    let mut current_table = None;
+    for entry in entries {
+        if current_table != Some(entry.table.as_str()) {
+            current_table = Some(entry.table.as_str());
+            out.push_str(&format!(
+                "\n## {}\n\n\
+                 | Column | Type | Semantic type | Strategy | Examples | Null rate | Distinct | Constraints |\n\
+                 |---|---|---|---|---|---|---|---|\n",
+                entry.table
+            ));
+        }
+        let examples: Vec<String> = entry
+            .examples
+            .iter()
+            .map(|e| format!("`{}`", e.replace('`', "'")))
+            .collect();
+        let cells = [
+            entry.column.clone(),
+            entry.data_type.clone(),
+            entry.semantic_type.clone(),
+            entry.strategy.clone(),
+            examples.join(", "),
+            format!("{:.1}%", entry.null_rate * 100.0),
+            entry.distinct_values.to_string(),
+            entry.constraints.join(", "),
+        ];
+        let cells: Vec<String> = cells.iter().map(|c| markdown_escape(c)).collect();
+        out.push_str(&format!("| {} |\n", cells.join(" | ")));
+    }
+    writer
+        .write_all(out.as_bytes())
+        .map_err(|e| SeedKitError::Output {
+            message: "writing data dictionary".to_string(),
+            source: e,
+        })
+}
+
+/// Write the dictionary as CSV, one line per column. List fields are
+/// joined with `; `.
+pub fn write_dictionary_csv<W: Write>(writer: &mut W, entries: &[DictionaryEntry]) -> Result<()> {
+    let mut out = String::from(
+        "table,column,data_type,semantic_type,strategy,examples,null_rate,distinct_values,constraints\n",
+    );
+    for entry in entries {
+        let fields = [
+            entry.table.clone(),
+            entry.column.clone(),
+            entry.data_type.clone(),
+            entry.semantic_type.clone(),
+            entry.strategy.clone(),
+            entry.examples.join("; "),
+            format!("{:.4}", entry.null_rate),
+            entry.distinct_values.to_string(),
+            entry.constraints.join("; "),
+        ];
+        let fields: Vec<String> = fields.iter().map(|f| csv_escape(f)).collect();
+        out.push_str(&fields.join(","));
+        out.push('\n');
+    }
+    writer
+        .write_all(out.as_bytes())
+        .map_err(|e| SeedKitError::Output {
+            message: "writing data dictionary".to_string(),
+            source: e,
+        })
+}
+
+/// Short description of how a column is generated.
+pub fn describe_strategy(strategy: &GenerationStrategy) -> String {
+    match strategy {
+        GenerationStrategy::AutoIncrement => "auto-increment".to_string(),
+        GenerationStrategy::ForeignKeyReference {
+            referenced_table,
+            referenced_column,
+            fan_out,
+        } => match fan_out {
+            FanOut::Uniform => format!("reference to {}.{}", referenced_table, referenced_column),
+            FanOut::Zipf(s) => format!(
+                "reference to {}.{} (zipf fan-out, exponent {})",
+                referenced_table, referenced_column, s
+            ),
+            FanOut::Children(min, max) => format!(
+                "reference to {}.{} ({}-{} per parent)",
+                referenced_table, referenced_column, min, max
+            ),
+        },
+        GenerationStrategy::SemanticProvider => "semantic provider".to_string(),
+        GenerationStrategy::EnumValue { values } => format!("enum ({} values)", values.len()),
+        GenerationStrategy::SetValue { values } => format!("set ({} labels)", values.len()),
+        GenerationStrategy::Lookup { .. } => "lookup dataset".to_string(),
+        GenerationStrategy::Correlated { .. } => "correlated with other columns".to_string(),
+        GenerationStrategy::PolymorphicType { targets, .. } => format!(
+            "polymorphic type ({})",
+            targets
+                .iter()
+                .map(|t| format!("{}: {}", t.type_value, t.table))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        GenerationStrategy::PolymorphicId { type_column } => {
+            format!("polymorphic reference (table named by {})", type_column)
+        }
+        GenerationStrategy::Deferred => "deferred (set by UPDATE after insert)".to_string(),
+        GenerationStrategy::Skip => "skipped (database default)".to_string(),
+        GenerationStrategy::Custom { provider_path } => {
+            format!("custom provider {}", provider_path)
+        }
+        GenerationStrategy::Process { command, .. } => {
+            format!("external provider `{}`", command.join(" "))
+        }
+        GenerationStrategy::Template { .. } => "template".to_string(),
+        GenerationStrategy::Pattern { pattern } => format!("pattern `{}`", pattern.source()),
+        GenerationStrategy::Lifecycle { states, .. } => {
+            format!("lifecycle ({})", states.join(" → "))
+        }
+        GenerationStrategy::Expression { source } => format!("expression `{}`", source),
+        GenerationStrategy::Aggregate { aggregate, .. } => {
+            format!("{} over {}", aggregate.function, aggregate.table)
+        }
+        GenerationStrategy::JsonSchema { .. } => "JSON Schema documents".to_string(),
+        GenerationStrategy::LlmContent { texts } => {
+            format!("LLM-written text ({} variants)", texts.len())
+        }
+        GenerationStrategy::ValueList { values, weights } => format!(
+            "{} list ({} values)",
+            if weights.is_some() {
+                "weighted value"
+            } else {
+                "value"
+            },
+            values.len()
+        ),
+        GenerationStrategy::Distribution { .. } => "sampled distribution".to_string(),
+        GenerationStrategy::Composite { type_name, .. } => format!("composite {}", type_name),
+        GenerationStrategy::Array { dimensions, .. } => format!("array ({}-D)", dimensions),
+        GenerationStrategy::Range { subtype, .. } => format!("range of {}", subtype),
+        GenerationStrategy::Interval { .. } => "interval".to_string(),
+        GenerationStrategy::Binary { min_len, max_len } => {
+            format!("binary ({}-{} bytes)", min_len, max_len)
+        }
+        GenerationStrategy::Spatial { shape, .. } => format!("spatial {}", shape),
+    }
+}
+
+/// Constraints on `column`: keys, NOT NULL, foreign keys, checks, length.
+fn constraints(table: &Table, column: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    let owns = |columns: &[String]| columns.iter().any(|c| c == column);
+
+    if let Some(pk) = table.primary_key.as_ref().filter(|pk| owns(&pk.columns)) {
+        out.push(match pk.columns.len() {
+            1 => "primary key".to_string(),
+            _ => format!("primary key ({})", pk.columns.join(", ")),
+        });
+    }
+    for uc in table
+        .unique_constraints
+        .iter()
+        .filter(|uc| owns(&uc.columns))
+    {
+        out.push(match uc.columns.len() {
+            1 => "unique".to_string(),
+            _ => format!("unique ({})", uc.columns.join(", ")),
+        });
+    }
+    if let Some(col) = table.columns.get(column) {
+        if !col.nullable {
+            out.push("not null".to_string());
+        }
+        if let Some(len) = col.max_length {
+            out.push(format!("max length {}", len));
+        }
+    }
+    for fk in table
+        .foreign_keys
+        .iter()
+        .filter(|fk| owns(&fk.source_columns))
+    {
+        out.push(format!(
+            "references {}({})",
+            fk.referenced_table,
+            fk.referenced_columns.join(", ")
+        ));
+    }
+    for check in &table.check_constraints {
+        let mentions = check
+            .expression
+            .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .any(|word| word == column);
+        if mentions {
+            out.push(format!("check ({})", check.expression));
+        }
+    }
+    out
+}
+
+pub(crate) fn shorten(text: &str) -> String {
+    let text = text.replace(['\n', '\r'], " ");
+    match text.char_indices().nth(EXAMPLE_MAX_CHARS) {
+        Some((cut, _)) => format!("{}…", &text[..cut]),
+        None => text,
+    }
+}
+
+pub(crate) fn markdown_escape(s: &str) -> String {
+    s.replace('|', "\\|")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::plan::{self, single_column_plan, ColumnGenerationPlan};
+    use crate::schema::types::{
+        CheckConstraint, Column, DataType, DatabaseType, PrimaryKey, UniqueConstraint,
+    };
+
+    fn column_plan(
+        name: &str,
+        strategy: GenerationStrategy,
+        nullable: bool,
+    ) -> ColumnGenerationPlan {
+        let mut col_plan = plan::column_plan(name, strategy);
+        col_plan.nullable = nullable;
+        col_plan.null_probability = if nullable { 0.5 } else { 0.0 };
+        col_plan
+    }
+
+    #[test]
+    fn test_dictionary_describes_generated_columns() {
+        let mut schema = DatabaseSchema::new(DatabaseType::PostgreSQL, "test".to_string());
+        let mut table = Table::new("products".to_string());
+        let mut sku = Column::new("sku".to_string(), DataType::VarChar, "varchar(12)".into());
+        sku.nullable = false;
+        sku.max_length = Some(12);
+        table.columns.insert("sku".to_string(), sku);
+        let color = Column::new("color".to_string(), DataType::Text, "text".into());
+        table.columns.insert("color".to_string(), color);
+        table.primary_key = Some(PrimaryKey {
+            columns: vec!["sku".to_string()],
+            name: None,
+        });
+        table.unique_constraints.push(UniqueConstraint {
+            name: None,
+            columns: vec!["sku".to_string(), "color".to_string()],
+        });
+        table.check_constraints.push(CheckConstraint {
+            name: None,
+            expression: "color <> 'mauve'".to_string(),
+            parsed: None,
+        });
+        schema.tables.insert("products".to_string(), table);
+
+        let mut plan = single_column_plan("products", "sku", GenerationStrategy::Skip, 4);
+        plan.table_plans[0].column_plans = vec![
+            column_plan(
+                "sku",
+                GenerationStrategy::ValueList {
+                    values: vec!["SKU|1".into(), "SKU|2".into()],
+                    weights: None,
+                },
+                false,
+            ),
+            column_plan(
+                "color",
+                GenerationStrategy::ValueList {
+                    values: vec!["red".into(), "blue".into()],
+                    weights: Some(vec![0.5, 0.5]),
+                },
+                true,
+            ),
+        ];
+        let color = |c: Option<&'static str>| c.map_or(Value::Null, |c| Value::String(c.into()));
+        let rows = [
+            ("SKU|1", Some("red")),
+            ("SKU|2", Some("blue")),
+            ("SKU|3", None),
+            ("SKU|4", Some("red")),
+        ]
+        .into_iter()
+        .map(|(sku, c)| {
+            indexmap::IndexMap::from([
+                ("sku".to_string(), Value::String(sku.into())),
+                ("color".to_string(), color(c)),
+            ])
+        })
+        .collect();
+        let data = GeneratedData {
+            tables: indexmap::IndexMap::from([("products".to_string(), rows)]),
+            deferred_updates: Vec::new(),
+        };
+        let entries = build_dictionary(&plan, &schema, &data);
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].examples, ["SKU|1", "SKU|2", "SKU|3"]);
+        assert_eq!(entries[0].distinct_values, 4);
+        assert_eq!(entries[0].null_rate, 0.0);
+        assert_eq!(
+            entries[0].constraints,
+            [
+                "primary key",
+                "unique (sku, color)",
+                "not null",
+                "max length 12"
+            ]
+        );
+        assert_eq!(entries[1].strategy, "weighted value list (2 values)");
+        assert_eq!(entries[1].distinct_values, 2);
+        assert_eq!(entries[1].null_rate, 0.25);
+        assert!(entries[1]
+            .constraints
+            .contains(&"check (color <> 'mauve')".to_string()));
+
+        let mut markdown = Vec::new();
+        write_dictionary_markdown(&mut markdown, &entries).unwrap();
+        let markdown = String::from_utf8(markdown).unwrap();
+        assert!(markdown.contains("## products"), "{}", markdown);
+        assert!(markdown.contains("| sku | varchar(12) |"), "{}", markdown);
+        assert!(markdown.contains("`SKU\\|1`"), "{}", markdown);
+
+        let mut csv = Vec::new();
+        write_dictionary_csv(&mut csv, &entries).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert_eq!(csv.lines().count(), 3);
+        assert!(csv.contains(
+            "products,sku,varchar(12),Unknown,value list (2 values),SKU|1; SKU|2; SKU|3,0.0000,4,"
+        ));
+    }
+}
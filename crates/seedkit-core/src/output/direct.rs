@@ -28,10 +28,10 @@ use crate::generate::value::Value;
 use crate::schema::types::{DatabaseSchema, DatabaseType};
 
 /// Batch size for multi-row INSERT statements.
-const INSERT_BATCH_SIZE: usize = 100;
+pub(crate) const INSERT_BATCH_SIZE: usize = 100;
 
-/// Progress reporting interval (rows) to avoid terminal I/O overhead.
-const PROGRESS_BATCH_SIZE: usize = 100;
+/// Receives (table, rows_inserted_so_far, total_rows) during an insert.
+pub type InsertProgress<'a> = dyn Fn(&str, usize, usize) + Send + Sync + 'a;
 
 /// Insert generated data directly into a database.
 ///
@@ -40,13 +40,14 @@ const PROGRESS_BATCH_SIZE: usize = 100;
 /// run inside a single transaction — if any batch fails, the entire operation
 /// is rolled back so no partial data is left behind.
 ///
-/// The `progress_callback` receives (rows_inserted_so_far, total_rows) and is
-/// called every `PROGRESS_BATCH_SIZE` rows to avoid terminal I/O overhead.
+/// The `progress_callback` receives (table, rows_inserted_so_far, total_rows)
+/// and is called after every batch of `INSERT_BATCH_SIZE` rows, which keeps
+/// terminal I/O down.
 pub async fn insert_direct(
     data: &GeneratedData,
     schema: &DatabaseSchema,
     db_url: &str,
-    progress_callback: Option<&(dyn Fn(usize, usize) + Send + Sync)>,
+    progress_callback: Option<&InsertProgress<'_>>,
 ) -> Result<()> {
     let total_rows: usize = data.tables.values().map(|rows| rows.len()).sum();
 
@@ -72,17 +73,13 @@ async fn insert_postgres(
     schema: &DatabaseSchema,
     db_url: &str,
     total_rows: usize,
-    progress_callback: Option<&(dyn Fn(usize, usize) + Send + Sync)>,
+    progress_callback: Option<&InsertProgress<'_>>,
 ) -> Result<()> {
     let pool = sqlx::postgres::PgPoolOptions::new()
         .max_connections(5)
         .connect(db_url)
         .await
-        .map_err(|e| SeedKitError::Connection {
-            message: "Failed to connect for direct insertion".to_string(),
-            connection_hint: sanitize_url(db_url),
-            source: e,
-        })?;
+        .map_err(|e| connection_error("Failed to connect for direct insertion", db_url, e))?;
 
     // Wrap all inserts in a single transaction — if any batch fails,
     // the entire insertion is rolled back (no partial data left behind).
@@ -125,7 +122,7 @@ async fn insert_postgres(
                 })?;
 
             rows_inserted += chunk.len();
-            report_progress(progress_callback, rows_inserted, total_rows);
+            report_progress(progress_callback, table_name, rows_inserted, total_rows);
         }
 
         // Sequence synchronization: if the table has an auto-increment PK,
@@ -180,17 +177,13 @@ async fn insert_mysql(
     schema: &DatabaseSchema,
     db_url: &str,
     total_rows: usize,
-    progress_callback: Option<&(dyn Fn(usize, usize) + Send + Sync)>,
+    progress_callback: Option<&InsertProgress<'_>>,
 ) -> Result<()> {
     let pool = sqlx::mysql::MySqlPoolOptions::new()
         .max_connections(5)
         .connect(db_url)
         .await
-        .map_err(|e| SeedKitError::Connection {
-            message: "Failed to connect for direct insertion".to_string(),
-            connection_hint: sanitize_url(db_url),
-            source: e,
-        })?;
+        .map_err(|e| connection_error("Failed to connect for direct insertion", db_url, e))?;
 
     // Begin transaction — all inserts are atomic.
     let mut tx = pool.begin().await.map_err(|e| SeedKitError::InsertFailed {
@@ -245,7 +238,7 @@ async fn insert_mysql(
                 })?;
 
             rows_inserted += chunk.len();
-            report_progress(progress_callback, rows_inserted, total_rows);
+            report_progress(progress_callback, table_name, rows_inserted, total_rows);
         }
     }
 
@@ -278,18 +271,27 @@ async fn insert_sqlite(
     schema: &DatabaseSchema,
     db_url: &str,
     total_rows: usize,
-    progress_callback: Option<&(dyn Fn(usize, usize) + Send + Sync)>,
+    progress_callback: Option<&InsertProgress<'_>>,
 ) -> Result<()> {
     let pool = sqlx::sqlite::SqlitePoolOptions::new()
         .max_connections(1)
         .connect(db_url)
         .await
-        .map_err(|e| SeedKitError::Connection {
-            message: "Failed to connect for direct insertion".to_string(),
-            connection_hint: sanitize_url(db_url),
-            source: e,
-        })?;
+        .map_err(|e| connection_error("Failed to connect for direct insertion", db_url, e))?;
 
+    insert_sqlite_pool(data, schema, &pool, total_rows, progress_callback).await
+}
+
+/// SQLite insertion over an existing pool. Used directly by
+/// [`crate::ephemeral::ephemeral_sqlite`], whose in-memory database only
+/// exists on its own connection.
+pub(crate) async fn insert_sqlite_pool(
+    data: &GeneratedData,
+    schema: &DatabaseSchema,
+    pool: &sqlx::SqlitePool,
+    total_rows: usize,
+    progress_callback: Option<&InsertProgress<'_>>,
+) -> Result<()> {
     let db_type = &DatabaseType::SQLite;
 
     // SQLite uses sqlx transactions too for atomicity.
@@ -343,7 +345,7 @@ async fn insert_sqlite(
                 })?;
 
             rows_inserted += chunk.len();
-            report_progress(progress_callback, rows_inserted, total_rows);
+            report_progress(progress_callback, table_name, rows_inserted, total_rows);
         }
     }
 
@@ -374,7 +376,7 @@ async fn insert_sqlite(
 /// Build a batched multi-row INSERT statement.
 ///
 /// Produces: `INSERT INTO "table" ("col1", "col2") VALUES (v1, v2), (v3, v4)`
-fn build_batched_insert(
+pub(crate) fn build_batched_insert(
     quoted_table: &str,
     col_list: &str,
     columns: &[&String],
@@ -408,7 +410,7 @@ fn build_batched_insert(
 ///
 /// Requires the row's primary key value to identify which row to update.
 /// Returns `None` if the table has no PK or the PK value isn't available.
-fn build_deferred_update(
+pub(crate) fn build_deferred_update(
     update: &DeferredUpdate,
     data: &GeneratedData,
     schema: &DatabaseSchema,
@@ -535,7 +537,7 @@ async fn execute_deferred_updates_sqlite(
 }
 
 /// Quote a SQL identifier based on database type.
-fn quote_identifier(name: &str, db_type: &DatabaseType) -> String {
+pub(crate) fn quote_identifier(name: &str, db_type: &DatabaseType) -> String {
     match db_type {
         DatabaseType::MySQL => format!("`{}`", name),
         _ => format!("\"{}\"", name),
@@ -543,7 +545,7 @@ fn quote_identifier(name: &str, db_type: &DatabaseType) -> String {
 }
 
 /// Truncate a SQL string for error messages.
-fn truncate_sql(sql: &str, max_len: usize) -> String {
+pub(crate) fn truncate_sql(sql: &str, max_len: usize) -> String {
     if sql.len() <= max_len {
         sql.to_string()
     } else {
@@ -556,7 +558,7 @@ fn truncate_sql(sql: &str, max_len: usize) -> String {
 /// Uses the `url` crate for proper RFC 3986 parsing instead of fragile
 /// string slicing. Handles all edge cases: encoded characters, unusual
 /// ports, query parameters, usernames with special characters, etc.
-fn sanitize_url(db_url: &str) -> String {
+pub(crate) fn sanitize_url(db_url: &str) -> String {
     if let Ok(mut parsed) = url::Url::parse(db_url) {
         if parsed.password().is_some() {
             let _ = parsed.set_password(Some("****"));
@@ -567,16 +569,38 @@ fn sanitize_url(db_url: &str) -> String {
     db_url.to_string()
 }
 
-/// Report progress in batches to avoid terminal I/O overhead.
+/// A connection failure, with the password hidden from the URL.
+pub(crate) fn connection_error(message: &str, db_url: &str, source: sqlx::Error) -> SeedKitError {
+    SeedKitError::Connection {
+        message: message.to_string(),
+        connection_hint: sanitize_url(db_url),
+        source,
+    }
+}
+
+/// Open a one-connection pool, for the short bookkeeping and wipe sessions.
+pub(crate) async fn connect_single<DB: sqlx::Database>(
+    db_url: &str,
+    message: &str,
+) -> Result<sqlx::Pool<DB>> {
+    sqlx::pool::PoolOptions::<DB>::new()
+        .max_connections(1)
+        .connect(db_url)
+        .await
+        .map_err(|e| connection_error(message, db_url, e))
+}
+
+/// Report progress after a batch. Batches end mid-way through the count
+/// whenever a table's rows don't fill the last one, so every batch is
+/// reported rather than multiples of the batch size.
 fn report_progress(
-    callback: Option<&(dyn Fn(usize, usize) + Send + Sync)>,
+    callback: Option<&InsertProgress<'_>>,
+    table: &str,
     current: usize,
     total: usize,
 ) {
     if let Some(cb) = callback {
-        if current.is_multiple_of(PROGRESS_BATCH_SIZE) || current == total {
-            cb(current, total);
-        }
+        cb(table, current, total);
     }
 }
 
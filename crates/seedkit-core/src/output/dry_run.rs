@@ -0,0 +1,375 @@
+//! # Dry-Run Insertion
+//!
+//! `seedkit generate --dry-run` connects and runs every statement a direct
+//! insert would — the batched `INSERT`s, then the deferred foreign key
+//! `UPDATE`s — inside one transaction that is rolled back at the end. Each
+//! statement runs under its own savepoint, so a statement that violates a
+//! constraint is recorded and the rest still run, and the report lists
+//! every violation rather than only the first.
+//!
+//! The session is set up as for a real insert (foreign key checks off on
+//! MySQL), so the violations are the ones the insert would hit. PostgreSQL
+//! sequences and MySQL `AUTO_INCREMENT` counters are not transactional:
+//! the IDs the rolled-back rows took are not handed back, and the next run
+//! starts after them.
+
+use crate::error::{Result, SeedKitError};
+use crate::generate::engine::GeneratedData;
+use crate::output::direct::{
+    build_batched_insert, build_deferred_update, quote_identifier, sanitize_url, truncate_sql,
+    InsertProgress, INSERT_BATCH_SIZE,
+};
+use crate::schema::types::{DatabaseSchema, DatabaseType};
+
+/// One statement of a direct insert.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlannedStatement {
+    pub table: String,
+    /// Index of the first row the statement inserts or updates.
+    pub first_row: usize,
+    /// Rows inserted; 0 for a deferred foreign key `UPDATE`.
+    pub rows: usize,
+    pub sql: String,
+}
+
+/// The rows and SQL one table would get.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DryRunTable {
+    pub table: String,
+    pub rows: usize,
+    pub statements: usize,
+    /// Bytes of SQL sent for the table.
+    pub sql_bytes: u64,
+}
+
+/// A statement the database rejected.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DryRunViolation {
+    pub table: String,
+    pub first_row: usize,
+    /// Rows of the rejected batch; 0 for a deferred foreign key `UPDATE`.
+    pub rows: usize,
+    pub message: String,
+    pub sql_preview: String,
+}
+
+/// What a dry run found.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DryRunReport {
+    /// Tables in insertion order.
+    pub tables: Vec<DryRunTable>,
+    pub violations: Vec<DryRunViolation>,
+}
+
+impl DryRunReport {
+    pub fn total_rows(&self) -> usize {
+        self.tables.iter().map(|t| t.rows).sum()
+    }
+
+    pub fn total_sql_bytes(&self) -> u64 {
+        self.tables.iter().map(|t| t.sql_bytes).sum()
+    }
+}
+
+/// The statements a direct insert of `data` runs, in order: batched
+/// `INSERT`s table by table, then deferred foreign key `UPDATE`s.
+pub fn planned_statements(data: &GeneratedData, schema: &DatabaseSchema) -> Vec<PlannedStatement> {
+    let db_type = &schema.database_type;
+    let mut statements = Vec::new();
+
+    for (table_name, rows) in &data.tables {
+        if rows.is_empty() {
+            continue;
+        }
+        let columns: Vec<&String> = rows[0].keys().collect();
+        let quoted_table = quote_identifier(table_name, db_type);
+        let quoted_columns: Vec<String> = columns
+            .iter()
+            .map(|c| quote_identifier(c, db_type))
+            .collect();
+        let col_list = quoted_columns.join(", ");
+
+        for (batch, chunk) in rows.chunks(INSERT_BATCH_SIZE).enumerate() {
+            statements.push(PlannedStatement {
+                table: table_name.clone(),
+                first_row: batch * INSERT_BATCH_SIZE,
+                rows: chunk.len(),
+                sql: build_batched_insert(&quoted_table, &col_list, &columns, chunk, db_type),
+            });
+        }
+    }
+
+    for update in &data.deferred_updates {
+        if let Some(sql) = build_deferred_update(update, data, schema, db_type) {
+            statements.push(PlannedStatement {
+                table: update.table_name.clone(),
+                first_row: update.row_index,
+                rows: 0,
+                sql,
+            });
+        }
+    }
+
+    statements
+}
+
+/// Tally the planned statements by table, in the order the tables are
+/// first inserted into.
+fn tally(statements: &[PlannedStatement]) -> Vec<DryRunTable> {
+    let mut tables: Vec<DryRunTable> = Vec::new();
+    for statement in statements {
+        let entry = match tables.iter().position(|t| t.table == statement.table) {
+            Some(index) => &mut tables[index],
+            None => {
+                tables.push(DryRunTable {
+                    table: statement.table.clone(),
+                    rows: 0,
+                    statements: 0,
+                    sql_bytes: 0,
+                });
+                tables.last_mut().unwrap()
+            }
+        };
+        entry.rows += statement.rows;
+        entry.statements += 1;
+        entry.sql_bytes += statement.sql.len() as u64;
+    }
+    tables
+}
+
+/// Run a direct insert of `data` and roll it back, reporting the rows and
+/// SQL per table and every statement the database rejected.
+///
+/// The `progress_callback` receives (table, rows_inserted_so_far,
+/// total_rows) after every batch.
+pub async fn dry_run_direct(
+    data: &GeneratedData,
+    schema: &DatabaseSchema,
+    db_url: &str,
+    progress_callback: Option<&InsertProgress<'_>>,
+) -> Result<DryRunReport> {
+    let statements = planned_statements(data, schema);
+    let total_rows: usize = statements.iter().map(|s| s.rows).sum();
+    let mut report = DryRunReport {
+        tables: tally(&statements),
+        violations: Vec::new(),
+    };
+
+    let connect_failed = |e| SeedKitError::Connection {
+        message: "Failed to connect for dry-run insertion".to_string(),
+        connection_hint: sanitize_url(db_url),
+        source: e,
+    };
+    let session_failed = |sql: &str| {
+        let sql_preview = sql.to_string();
+        move |e| SeedKitError::InsertFailed {
+            table: "(session)".to_string(),
+            row_index: 0,
+            message: "Dry run session statement failed".to_string(),
+            sql_preview,
+            source: e,
+        }
+    };
+    // Anything but a database error (a dropped connection, say) ends the run
+    let statement_failed = |statement: &PlannedStatement, e: sqlx::Error| {
+        let message = match e.as_database_error() {
+            Some(db_error) => db_error.message().to_string(),
+            None => {
+                return Err(SeedKitError::InsertFailed {
+                    table: statement.table.clone(),
+                    row_index: statement.first_row,
+                    message: "Dry run statement failed".to_string(),
+                    sql_preview: truncate_sql(&statement.sql, 200),
+                    source: e,
+                })
+            }
+        };
+        Ok(DryRunViolation {
+            table: statement.table.clone(),
+            first_row: statement.first_row,
+            rows: statement.rows,
+            message,
+            sql_preview: truncate_sql(&statement.sql, 200),
+        })
+    };
+
+    const SAVEPOINT: &str = "SAVEPOINT seedkit_dry_run";
+    const RELEASE: &str = "RELEASE SAVEPOINT seedkit_dry_run";
+    const ROLLBACK_TO: &str = "ROLLBACK TO SAVEPOINT seedkit_dry_run";
+    let mut rows_inserted = 0usize;
+
+    match schema.database_type {
+        DatabaseType::PostgreSQL => {
+            let pool = sqlx::postgres::PgPoolOptions::new()
+                .max_connections(1)
+                .connect(db_url)
+                .await
+                .map_err(connect_failed)?;
+            let mut tx = pool.begin().await.map_err(session_failed("BEGIN"))?;
+            for statement in &statements {
+                sqlx::query(SAVEPOINT)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(session_failed(SAVEPOINT))?;
+                match sqlx::query(&statement.sql).execute(&mut *tx).await {
+                    Ok(_) => sqlx::query(RELEASE)
+                        .execute(&mut *tx)
+                        .await
+                        .map_err(session_failed(RELEASE))?,
+                    Err(e) => {
+                        report.violations.push(statement_failed(statement, e)?);
+                        sqlx::query(ROLLBACK_TO)
+                            .execute(&mut *tx)
+                            .await
+                            .map_err(session_failed(ROLLBACK_TO))?
+                    }
+                };
+                rows_inserted += statement.rows;
+                if statement.rows > 0 {
+                    if let Some(cb) = progress_callback {
+                        cb(&statement.table, rows_inserted, total_rows);
+                    }
+                }
+            }
+            tx.rollback().await.map_err(session_failed("ROLLBACK"))?;
+            pool.close().await;
+        }
+        DatabaseType::MySQL => {
+            let pool = sqlx::mysql::MySqlPoolOptions::new()
+                .max_connections(1)
+                .connect(db_url)
+                .await
+                .map_err(connect_failed)?;
+            let mut tx = pool.begin().await.map_err(session_failed("BEGIN"))?;
+            sqlx::query("SET FOREIGN_KEY_CHECKS = 0")
+                .execute(&mut *tx)
+                .await
+                .map_err(session_failed("SET FOREIGN_KEY_CHECKS = 0"))?;
+            for statement in &statements {
+                sqlx::query(SAVEPOINT)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(session_failed(SAVEPOINT))?;
+                match sqlx::query(&statement.sql).execute(&mut *tx).await {
+                    Ok(_) => sqlx::query(RELEASE)
+                        .execute(&mut *tx)
+                        .await
+                        .map_err(session_failed(RELEASE))?,
+                    Err(e) => {
+                        report.violations.push(statement_failed(statement, e)?);
+                        sqlx::query(ROLLBACK_TO)
+                            .execute(&mut *tx)
+                            .await
+                            .map_err(session_failed(ROLLBACK_TO))?
+                    }
+                };
+                rows_inserted += statement.rows;
+                if statement.rows > 0 {
+                    if let Some(cb) = progress_callback {
+                        cb(&statement.table, rows_inserted, total_rows);
+                    }
+                }
+            }
+            let _ = sqlx::query("SET FOREIGN_KEY_CHECKS = 1")
+                .execute(&mut *tx)
+                .await;
+            tx.rollback().await.map_err(session_failed("ROLLBACK"))?;
+            pool.close().await;
+        }
+        DatabaseType::SQLite => {
+            let pool = sqlx::sqlite::SqlitePoolOptions::new()
+                .max_connections(1)
+                .connect(db_url)
+                .await
+                .map_err(connect_failed)?;
+            let mut tx = pool.begin().await.map_err(session_failed("BEGIN"))?;
+            sqlx::query("PRAGMA foreign_keys = OFF")
+                .execute(&mut *tx)
+                .await
+                .map_err(session_failed("PRAGMA foreign_keys = OFF"))?;
+            for statement in &statements {
+                sqlx::query(SAVEPOINT)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(session_failed(SAVEPOINT))?;
+                match sqlx::query(&statement.sql).execute(&mut *tx).await {
+                    Ok(_) => sqlx::query(RELEASE)
+                        .execute(&mut *tx)
+                        .await
+                        .map_err(session_failed(RELEASE))?,
+                    Err(e) => {
+                        report.violations.push(statement_failed(statement, e)?);
+                        sqlx::query(ROLLBACK_TO)
+                            .execute(&mut *tx)
+                            .await
+                            .map_err(session_failed(ROLLBACK_TO))?
+                    }
+                };
+                rows_inserted += statement.rows;
+                if statement.rows > 0 {
+                    if let Some(cb) = progress_callback {
+                        cb(&statement.table, rows_inserted, total_rows);
+                    }
+                }
+            }
+            tx.rollback().await.map_err(session_failed("ROLLBACK"))?;
+            pool.close().await;
+        }
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::engine::DeferredUpdate;
+    use crate::generate::value::Value;
+    use crate::schema::types::*;
+    use indexmap::IndexMap;
+
+    #[test]
+    fn test_planned_statements() {
+        let mut schema = DatabaseSchema::new(DatabaseType::SQLite, "shop".to_string());
+        let mut users = Table::new("users".to_string());
+        let mut id = Column::new("id".to_string(), DataType::Integer, "integer".to_string());
+        id.is_auto_increment = true;
+        users.columns.insert("id".to_string(), id);
+        users.primary_key = Some(PrimaryKey {
+            columns: vec!["id".to_string()],
+            name: None,
+        });
+        schema.tables.insert("users".to_string(), users);
+
+        let row = IndexMap::from([("email".to_string(), Value::String("a@b.c".into()))]);
+        let mut data = GeneratedData {
+            tables: IndexMap::new(),
+            deferred_updates: vec![DeferredUpdate {
+                table_name: "users".to_string(),
+                row_index: 4,
+                column_name: "invited_by".to_string(),
+                value: Value::Int(1),
+            }],
+        };
+        data.tables
+            .insert("users".to_string(), vec![row; INSERT_BATCH_SIZE + 5]);
+
+        let statements = planned_statements(&data, &schema);
+        assert_eq!(statements.len(), 3);
+        assert_eq!(statements[1].first_row, INSERT_BATCH_SIZE);
+        assert_eq!(statements[1].rows, 5);
+        assert_eq!(
+            statements[2].sql,
+            "UPDATE \"users\" SET \"invited_by\" = 1 WHERE \"id\" = 5"
+        );
+
+        let tables = tally(&statements);
+        assert_eq!(tables.len(), 1);
+        assert_eq!(tables[0].rows, INSERT_BATCH_SIZE + 5);
+        assert_eq!(tables[0].statements, 3);
+        assert_eq!(
+            tables[0].sql_bytes,
+            statements.iter().map(|s| s.sql.len() as u64).sum::<u64>()
+        );
+    }
+}
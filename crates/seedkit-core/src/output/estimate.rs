@@ -0,0 +1,187 @@
+//! # Output Size Estimation
+//!
+//! Large exports can take an hour to generate, and running out of disk while
+//! writing the last few percent throws all of it away. Before generating, a
+//! small batch of each table is serialized in the chosen format and the byte
+//! counts are scaled up to the planned row counts, so the caller can compare
+//! the estimate against the free space at the output path.
+
+use std::io::{self, Write};
+use std::path::Path;
+
+use indexmap::IndexMap;
+
+use crate::error::Result;
+use crate::generate::engine::GeneratedData;
+use crate::generate::plan::GenerationPlan;
+
+/// Rows generated per table for the sample batch.
+pub const SAMPLE_ROWS_PER_TABLE: usize = 100;
+
+/// A copy of `plan` with every table capped at `rows_per_table` rows.
+pub fn sample_plan(plan: &GenerationPlan, rows_per_table: usize) -> GenerationPlan {
+    let mut sample = plan.clone();
+    for table_plan in &mut sample.table_plans {
+        table_plan.row_count = table_plan.row_count.min(rows_per_table);
+    }
+    sample
+}
+
+/// Estimate the bytes `write` produces for the full plan, given its output
+/// for `sample` (generated from [`sample_plan`]).
+///
+/// Each table is serialized on its own and scaled by its planned row count,
+/// so wide tables with few rows and narrow tables with many are weighted
+/// correctly. Per-table headers are scaled too, which errs on the large side.
+pub fn estimate_output_size<F>(
+    plan: &GenerationPlan,
+    sample: &GeneratedData,
+    mut write: F,
+) -> Result<u64>
+where
+    F: FnMut(&mut ByteCounter, &GeneratedData) -> Result<()>,
+{
+    let mut total: u128 = 0;
+    for table_plan in &plan.table_plans {
+        let Some(rows) = sample
+            .tables
+            .get(&table_plan.table_name)
+            .filter(|r| !r.is_empty())
+        else {
+            continue;
+        };
+
+        let mut tables = IndexMap::new();
+        tables.insert(table_plan.table_name.clone(), rows.clone());
+        let table_data = GeneratedData {
+            tables,
+            deferred_updates: sample
+                .deferred_updates
+                .iter()
+                .filter(|u| u.table_name == table_plan.table_name)
+                .cloned()
+                .collect(),
+        };
+
+        let mut counter = ByteCounter::default();
+        write(&mut counter, &table_data)?;
+        total += counter.bytes() as u128 * table_plan.row_count as u128 / rows.len() as u128;
+    }
+    Ok(total.min(u64::MAX as u128) as u64)
+}
+
+/// A writer that discards its input and counts the bytes.
+#[derive(Debug, Default)]
+pub struct ByteCounter {
+    bytes: u64,
+}
+
+impl ByteCounter {
+    pub fn bytes(&self) -> u64 {
+        self.bytes
+    }
+}
+
+impl Write for ByteCounter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.bytes += buf.len() as u64;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Bytes available to unprivileged writers on the filesystem holding
+/// `path` (which need not exist yet). `None` when it can't be determined.
+pub fn available_space(path: &Path) -> Option<u64> {
+    let dir = path
+        .ancestors()
+        .skip(1)
+        .find(|p| p.as_os_str().is_empty() || p.is_dir())?;
+    let dir = if dir.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        dir
+    };
+    free_space(dir)
+}
+
+#[cfg(unix)]
+fn free_space(dir: &Path) -> Option<u64> {
+    let stat = rustix::fs::statvfs(dir).ok()?;
+    Some(stat.f_bavail.saturating_mul(stat.f_frsize))
+}
+
+#[cfg(not(unix))]
+fn free_space(_dir: &Path) -> Option<u64> {
+    None
+}
+
+/// Format a byte count for messages, e.g. `1.5 GB`.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::engine::execute_plan;
+    use crate::generate::plan::{single_column_plan, GenerationStrategy};
+    use crate::output::csv::write_csv;
+    use crate::schema::types::{DatabaseSchema, DatabaseType};
+
+    fn plan_with_rows(row_count: usize) -> GenerationPlan {
+        single_column_plan(
+            "events",
+            "kind",
+            GenerationStrategy::EnumValue {
+                values: vec!["click".to_string()],
+            },
+            row_count,
+        )
+    }
+
+    #[test]
+    fn test_estimate_scales_sample_to_planned_rows() {
+        let schema = DatabaseSchema::new(DatabaseType::PostgreSQL, "test".to_string());
+        let plan = plan_with_rows(10_000);
+        let sample_plan = sample_plan(&plan, 10);
+        assert_eq!(sample_plan.table_plans[0].row_count, 10);
+
+        let sample = execute_plan(&sample_plan, &schema, None).unwrap();
+        let estimate = estimate_output_size(&plan, &sample, write_csv).unwrap();
+
+        // Every row is "click\n"; the header lines are scaled along with them.
+        let mut counter = ByteCounter::default();
+        write_csv(&mut counter, &sample).unwrap();
+        assert_eq!(estimate, counter.bytes() * 1000);
+        assert!(estimate >= 60_000);
+    }
+
+    #[test]
+    fn test_available_space_for_new_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("missing").join("seed.sql");
+        assert!(available_space(&path).is_some_and(|b| b > 0) || cfg!(not(unix)));
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KB");
+        assert_eq!(format_bytes(3 * 1024 * 1024 * 1024), "3.0 GB");
+    }
+}
@@ -122,6 +122,14 @@ fn value_to_json(value: &Value) -> serde_json::Value {
         Value::Bytes(b) => {
             serde_json::Value::String(base64::engine::general_purpose::STANDARD.encode(b))
         }
+        Value::Composite { fields, .. } => serde_json::Value::Object(
+            fields
+                .iter()
+                .map(|(name, v)| (name.clone(), value_to_json(v)))
+                .collect(),
+        ),
+        Value::Array(items) => serde_json::Value::Array(items.iter().map(value_to_json).collect()),
+        Value::Range { .. } => serde_json::Value::String(value.to_range_text()),
     }
 }
 
@@ -0,0 +1,108 @@
+//! # Markdown Preview
+//!
+//! `seedkit preview --format markdown` renders the sample rows of every
+//! table as Markdown tables, to paste into a pull request or design doc
+//! when proposing a change to the seed data. Long values are cut short and
+//! NULLs are set in italics, so they don't read as the string `NULL`.
+
+use std::fmt::Write;
+
+use crate::generate::engine::GeneratedData;
+use crate::generate::value::Value;
+use crate::output::dictionary::{markdown_escape, shorten};
+use crate::schema::types::DatabaseSchema;
+
+/// Every table with generated rows as a `###` heading, the comments on its
+/// columns, and its rows as a Markdown table.
+pub fn preview_markdown(data: &GeneratedData, schema: &DatabaseSchema) -> String {
+    let mut out = String::new();
+    for (table_name, rows) in &data.tables {
+        let Some(first) = rows.first() else {
+            continue;
+        };
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        let _ = writeln!(
+            out,
+            "### `{}` ({} row{})\n",
+            table_name,
+            rows.len(),
+            if rows.len() == 1 { "" } else { "s" }
+        );
+
+        // Comments say what cryptic column names mean
+        if let Some(table) = schema.tables.get(table_name) {
+            let comments: Vec<String> = table
+                .columns
+                .values()
+                .filter_map(|c| Some(format!("- `{}`: {}", c.name, c.comment.as_ref()?)))
+                .collect();
+            if !comments.is_empty() {
+                let _ = writeln!(out, "{}\n", comments.join("\n"));
+            }
+        }
+
+        let columns: Vec<&String> = first.keys().collect();
+        let header: Vec<String> = columns.iter().map(|c| markdown_escape(c)).collect();
+        let _ = writeln!(out, "| {} |", header.join(" | "));
+        let _ = writeln!(out, "|{}", "---|".repeat(columns.len()));
+        for row in rows {
+            let cells: Vec<String> = columns
+                .iter()
+                .map(|column| match row.get(*column) {
+                    None | Some(Value::Null) => "*NULL*".to_string(),
+                    Some(value) => markdown_escape(&shorten(&value.to_string())),
+                })
+                .collect();
+            let _ = writeln!(out, "| {} |", cells.join(" | "));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::types::{Column, DataType, DatabaseType, Table};
+    use indexmap::IndexMap;
+
+    #[test]
+    fn test_preview_markdown() {
+        let mut schema = DatabaseSchema::new(DatabaseType::PostgreSQL, "shop".to_string());
+        let mut users = Table::new("users".to_string());
+        let mut bio = Column::new("bio".to_string(), DataType::Text, "text".to_string());
+        bio.comment = Some("Shown on the profile page".to_string());
+        users.columns.insert("bio".to_string(), bio);
+        schema.tables.insert("users".to_string(), users);
+
+        let mut data = GeneratedData {
+            tables: IndexMap::new(),
+            deferred_updates: Vec::new(),
+        };
+        data.tables.insert(
+            "users".to_string(),
+            vec![
+                IndexMap::from([
+                    ("id".to_string(), Value::Int(1)),
+                    ("bio".to_string(), Value::String("a | b\nc".into())),
+                ]),
+                IndexMap::from([
+                    ("id".to_string(), Value::Int(2)),
+                    ("bio".to_string(), Value::Null),
+                ]),
+            ],
+        );
+        data.tables.insert("empty".to_string(), Vec::new());
+
+        assert_eq!(
+            preview_markdown(&data, &schema),
+            "### `users` (2 rows)\n\n\
+             - `bio`: Shown on the profile page\n\n\
+             | id | bio |\n\
+             |---|---|\n\
+             | 1 | a \\| b c |\n\
+             | 2 | *NULL* |\n"
+        );
+    }
+}
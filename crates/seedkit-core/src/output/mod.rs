@@ -1,4 +1,11 @@
 pub mod csv;
+pub mod dictionary;
 pub mod direct;
+pub mod dry_run;
+pub mod estimate;
 pub mod json;
+pub mod markdown;
+pub mod runs;
 pub mod sql;
+pub mod starter_config;
+pub mod wipe;
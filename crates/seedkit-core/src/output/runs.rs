@@ -0,0 +1,406 @@
+//! # Seed Runs
+//!
+//! Several people seeding one shared development database can't wipe
+//! everything after themselves. Tracked runs make one run's rows
+//! removable on their own, by `seedkit wipe --run <ID>`, in two ways:
+//!
+//! - **Bookkeeping**: after inserting, `seedkit generate` records the range
+//!   of auto-increment IDs it used in each table in a `_seedkit_runs`
+//!   table, which SeedKit never seeds itself.
+//! - **Marker column**: with `[generate] run_marker = "seed_run"`, every
+//!   table with a `seed_run` column gets the run ID in it, which also
+//!   covers tables without an auto-increment key.
+//!
+//! ```toml
+//! [generate]
+//! track_runs = true          # or pass --run-id <ID>
+//! run_marker = "seed_run"
+//! ```
+//!
+//! The IDs are the ones the rows were generated with, so the ranges
+//! assume nobody else inserts into the tables while the run is seeded.
+
+use indexmap::IndexMap;
+
+use crate::error::{Result, SeedKitError};
+use crate::generate::engine::GeneratedData;
+use crate::generate::plan::GenerationPlan;
+use crate::generate::sequence::auto_increment_pk;
+use crate::generate::value::Value;
+use crate::output::direct::{connect_single, quote_identifier};
+use crate::schema::types::{DatabaseSchema, DatabaseType};
+
+/// The table runs are recorded in.
+pub const RUNS_TABLE: &str = "_seedkit_runs";
+
+const CONNECT_FAILED: &str = "Failed to connect for seed run bookkeeping";
+
+/// The rows one run inserted into one table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunTable {
+    pub table: String,
+    /// The auto-increment key, if the table has one.
+    pub pk_column: Option<String>,
+    /// First and last ID inserted, with `pk_column`.
+    pub first_id: Option<i64>,
+    pub last_id: Option<i64>,
+    /// The marker column set to the run ID, if any.
+    pub marker_column: Option<String>,
+    pub row_count: u64,
+}
+
+/// A recorded run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Run {
+    pub run_id: String,
+    /// When the run was recorded, in RFC 3339.
+    pub created_at: String,
+    pub tables: Vec<RunTable>,
+}
+
+impl Run {
+    /// A run recorded now.
+    pub fn new(run_id: String, tables: Vec<RunTable>) -> Self {
+        Run {
+            run_id,
+            created_at: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            tables,
+        }
+    }
+}
+
+/// A run ID from the current time, e.g. `run-20260314-093012`.
+pub fn default_run_id() -> String {
+    chrono::Utc::now().format("run-%Y%m%d-%H%M%S").to_string()
+}
+
+/// Set the `marker` column of every generated row to the run ID, in the
+/// tables that have it. Returns the tables stamped.
+pub fn stamp_run_marker(
+    data: &mut GeneratedData,
+    schema: &DatabaseSchema,
+    marker: &str,
+    run_id: &str,
+) -> Vec<String> {
+    let mut stamped = Vec::new();
+    for (table_name, rows) in &mut data.tables {
+        if !schema
+            .tables
+            .get(table_name)
+            .is_some_and(|t| t.columns.contains_key(marker))
+        {
+            continue;
+        }
+        for row in rows.iter_mut() {
+            row.insert(marker.to_string(), Value::String(run_id.to_string().into()));
+        }
+        stamped.push(table_name.clone());
+    }
+    stamped
+}
+
+/// The rows inserted into each table of `data`. The IDs of an
+/// auto-increment key follow the table's sequence offset, as the engine
+/// assigned them; `marker` is the column stamped by `stamp_run_marker`.
+pub fn run_tables(
+    plan: &GenerationPlan,
+    schema: &DatabaseSchema,
+    data: &GeneratedData,
+    marker: Option<&str>,
+) -> Vec<RunTable> {
+    plan.table_plans
+        .iter()
+        .filter_map(|table_plan| {
+            let rows = data.tables.get(&table_plan.table_name)?;
+            if rows.is_empty() {
+                return None;
+            }
+            let table = schema.tables.get(&table_plan.table_name)?;
+            // A key given in the rows isn't a contiguous range
+            let pk = auto_increment_pk(table).filter(|pk| !rows[0].contains_key(*pk));
+            let offset = table_plan.sequence_offset.unwrap_or(plan.sequence_offset) as i64;
+            Some(RunTable {
+                table: table_plan.table_name.clone(),
+                pk_column: pk.map(str::to_string),
+                first_id: pk.map(|_| offset + 1),
+                last_id: pk.map(|_| offset + rows.len() as i64),
+                marker_column: marker
+                    .filter(|m| table.columns.contains_key(*m))
+                    .map(str::to_string),
+                row_count: rows.len() as u64,
+            })
+        })
+        .collect()
+}
+
+/// The statements creating the runs table, if needed, and recording a run.
+pub fn record_run_statements(run: &Run, db_type: DatabaseType) -> Vec<String> {
+    let string = |s: &str| Value::String(s.to_string().into()).to_sql_literal(&db_type);
+    let optional = |v: Option<i64>| v.map_or_else(|| "NULL".to_string(), |v| v.to_string());
+    let optional_string = |v: Option<&str>| v.map_or_else(|| "NULL".to_string(), &string);
+
+    let mut statements = vec![format!(
+        "CREATE TABLE IF NOT EXISTS {} (\
+         run_id VARCHAR(255) NOT NULL, \
+         table_name VARCHAR(255) NOT NULL, \
+         pk_column VARCHAR(255), \
+         first_id BIGINT, \
+         last_id BIGINT, \
+         marker_column VARCHAR(255), \
+         row_count BIGINT NOT NULL, \
+         created_at VARCHAR(64) NOT NULL)",
+        quote_identifier(RUNS_TABLE, &db_type)
+    )];
+    for table in &run.tables {
+        statements.push(format!(
+            "INSERT INTO {} (run_id, table_name, pk_column, first_id, last_id, marker_column, row_count, created_at) \
+             VALUES ({}, {}, {}, {}, {}, {}, {}, {})",
+            quote_identifier(RUNS_TABLE, &db_type),
+            string(&run.run_id),
+            string(&table.table),
+            optional_string(table.pk_column.as_deref()),
+            optional(table.first_id),
+            optional(table.last_id),
+            optional_string(table.marker_column.as_deref()),
+            table.row_count,
+            string(&run.created_at),
+        ));
+    }
+    statements
+}
+
+/// Record a run in the runs table, creating the table on first use.
+pub async fn record_run(url: &str, db_type: DatabaseType, run: &Run) -> Result<()> {
+    let statements = record_run_statements(run, db_type);
+    match db_type {
+        DatabaseType::PostgreSQL => record_with::<sqlx::Postgres>(url, run, &statements).await,
+        DatabaseType::MySQL => record_with::<sqlx::MySql>(url, run, &statements).await,
+        DatabaseType::SQLite => record_with::<sqlx::Sqlite>(url, run, &statements).await,
+    }
+}
+
+async fn record_with<DB>(url: &str, run: &Run, statements: &[String]) -> Result<()>
+where
+    DB: sqlx::Database,
+    for<'c> &'c mut DB::Connection: sqlx::Executor<'c, Database = DB>,
+    for<'q> DB::Arguments<'q>: sqlx::IntoArguments<'q, DB>,
+{
+    let pool = connect_single::<DB>(url, CONNECT_FAILED).await?;
+    for sql in statements {
+        sqlx::query(sql)
+            .execute(&pool)
+            .await
+            .map_err(|e| SeedKitError::InsertFailed {
+                table: RUNS_TABLE.to_string(),
+                row_index: 0,
+                message: format!("Failed to record run '{}'", run.run_id),
+                sql_preview: sql.chars().take(200).collect(),
+                source: e,
+            })?;
+    }
+    pool.close().await;
+    Ok(())
+}
+
+type RunRow = (
+    String,
+    String,
+    Option<String>,
+    Option<i64>,
+    Option<i64>,
+    Option<String>,
+    i64,
+    String,
+);
+
+/// The runs recorded in the database, oldest first; none when the runs
+/// table doesn't exist.
+pub async fn load_runs(url: &str, db_type: DatabaseType) -> Result<Vec<Run>> {
+    let query_err = |e| SeedKitError::Introspection {
+        query: RUNS_TABLE.to_string(),
+        source: e,
+    };
+    let select = format!(
+        "SELECT run_id, table_name, pk_column, first_id, last_id, marker_column, row_count, created_at \
+         FROM {} ORDER BY created_at, run_id",
+        quote_identifier(RUNS_TABLE, &db_type)
+    );
+
+    let rows: Vec<RunRow> = match db_type {
+        DatabaseType::PostgreSQL => {
+            let pool = connect_single::<sqlx::Postgres>(url, CONNECT_FAILED).await?;
+            let exists: i64 = sqlx::query_scalar(
+                "SELECT COUNT(*) FROM information_schema.tables \
+                 WHERE table_schema = current_schema() AND table_name = $1",
+            )
+            .bind(RUNS_TABLE)
+            .fetch_one(&pool)
+            .await
+            .map_err(query_err)?;
+            let rows = if exists > 0 {
+                sqlx::query_as(&select)
+                    .fetch_all(&pool)
+                    .await
+                    .map_err(query_err)?
+            } else {
+                Vec::new()
+            };
+            pool.close().await;
+            rows
+        }
+        DatabaseType::MySQL => {
+            let pool = connect_single::<sqlx::MySql>(url, CONNECT_FAILED).await?;
+            let exists: i64 = sqlx::query_scalar(
+                "SELECT COUNT(*) FROM information_schema.TABLES \
+                 WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME = ?",
+            )
+            .bind(RUNS_TABLE)
+            .fetch_one(&pool)
+            .await
+            .map_err(query_err)?;
+            let rows = if exists > 0 {
+                sqlx::query_as(&select)
+                    .fetch_all(&pool)
+                    .await
+                    .map_err(query_err)?
+            } else {
+                Vec::new()
+            };
+            pool.close().await;
+            rows
+        }
+        DatabaseType::SQLite => {
+            let pool = connect_single::<sqlx::Sqlite>(url, CONNECT_FAILED).await?;
+            let exists: i64 = sqlx::query_scalar(
+                "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = ?",
+            )
+            .bind(RUNS_TABLE)
+            .fetch_one(&pool)
+            .await
+            .map_err(query_err)?;
+            let rows = if exists > 0 {
+                sqlx::query_as(&select)
+                    .fetch_all(&pool)
+                    .await
+                    .map_err(query_err)?
+            } else {
+                Vec::new()
+            };
+            pool.close().await;
+            rows
+        }
+    };
+
+    let mut runs: IndexMap<String, Run> = IndexMap::new();
+    for (run_id, table, pk_column, first_id, last_id, marker_column, row_count, created_at) in rows
+    {
+        runs.entry(run_id.clone())
+            .or_insert_with(|| Run {
+                run_id,
+                created_at,
+                tables: Vec::new(),
+            })
+            .tables
+            .push(RunTable {
+                table,
+                pk_column,
+                first_id,
+                last_id,
+                marker_column,
+                row_count: row_count.max(0) as u64,
+            });
+    }
+    Ok(runs.into_values().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    use crate::schema::types::*;
+
+    #[test]
+    fn test_run_bookkeeping() {
+        let mut schema = DatabaseSchema::new(DatabaseType::SQLite, "shop".to_string());
+        for name in ["users", "tags"] {
+            let mut table = Table::new(name.to_string());
+            let mut id = Column::new("id".to_string(), DataType::Integer, "integer".to_string());
+            id.is_auto_increment = name == "users";
+            table.columns.insert("id".to_string(), id);
+            let marker = Column::new("seed_run".to_string(), DataType::Text, "text".to_string());
+            if name == "tags" {
+                table.columns.insert("seed_run".to_string(), marker);
+            }
+            table.primary_key = Some(PrimaryKey {
+                columns: vec!["id".to_string()],
+                name: None,
+            });
+            schema.tables.insert(name.to_string(), table);
+        }
+
+        let mut data = GeneratedData {
+            tables: IndexMap::new(),
+            deferred_updates: Vec::new(),
+        };
+        data.tables
+            .insert("users".to_string(), vec![IndexMap::new(); 3]);
+        let tag = IndexMap::from([("id".to_string(), Value::Int(7))]);
+        data.tables.insert("tags".to_string(), vec![tag; 2]);
+
+        assert_eq!(
+            stamp_run_marker(&mut data, &schema, "seed_run", "run-1"),
+            vec!["tags"]
+        );
+        assert_eq!(
+            data.tables["tags"][1]["seed_run"],
+            Value::String("run-1".to_string().into())
+        );
+        assert!(data.tables["users"][0].is_empty());
+
+        let mut plan = GenerationPlan::build(
+            &schema,
+            &BTreeMap::new(),
+            &["users".to_string(), "tags".to_string()],
+            Vec::new(),
+            3,
+            &BTreeMap::new(),
+            42,
+            None,
+            &BTreeMap::new(),
+            None,
+        );
+        plan.table_plans[0].sequence_offset = Some(40);
+        let tables = run_tables(&plan, &schema, &data, Some("seed_run"));
+        assert_eq!(
+            tables[0],
+            RunTable {
+                table: "users".to_string(),
+                pk_column: Some("id".to_string()),
+                first_id: Some(41),
+                last_id: Some(43),
+                marker_column: None,
+                row_count: 3,
+            }
+        );
+        // Explicit keys aren't recorded as a range
+        assert_eq!(tables[1].pk_column, None);
+        assert_eq!(tables[1].marker_column.as_deref(), Some("seed_run"));
+
+        let run = Run {
+            run_id: "run-1".to_string(),
+            created_at: "2026-03-14T09:30:12Z".to_string(),
+            tables,
+        };
+        let statements = record_run_statements(&run, DatabaseType::SQLite);
+        assert!(statements[0].starts_with("CREATE TABLE IF NOT EXISTS \"_seedkit_runs\""));
+        assert!(
+            statements[1].ends_with(
+                "VALUES ('run-1', 'users', 'id', 41, 43, NULL, 3, '2026-03-14T09:30:12Z')"
+            ),
+            "{}",
+            statements[1]
+        );
+        assert!(statements[2].contains("'tags', NULL, NULL, NULL, 'seed_run', 2"));
+    }
+}
@@ -2,6 +2,7 @@ use std::io::Write;
 
 use crate::error::{Result, SeedKitError};
 use crate::generate::engine::GeneratedData;
+use crate::generate::sequence::auto_increment_pk;
 use crate::generate::value::Value;
 use crate::schema::types::{DatabaseSchema, DatabaseType};
 
@@ -158,6 +159,68 @@ pub fn write_sql<W: Write>(
     Ok(())
 }
 
+/// Move each auto-increment sequence past the largest ID in `data`, so rows
+/// inserted later don't collide with it: `setval` on PostgreSQL, `ALTER
+/// TABLE ... AUTO_INCREMENT` on MySQL. SQLite continues after the largest
+/// rowid on its own.
+pub fn write_sequence_resets<W: Write>(
+    writer: &mut W,
+    data: &GeneratedData,
+    schema: &DatabaseSchema,
+) -> Result<()> {
+    let db_type = &schema.database_type;
+    let mut statements = Vec::new();
+    for (table_name, rows) in &data.tables {
+        let Some(pk) = schema.tables.get(table_name).and_then(auto_increment_pk) else {
+            continue;
+        };
+        let Some(max_id) = rows
+            .iter()
+            .filter_map(|row| row.get(pk).and_then(Value::as_int))
+            .max()
+            .filter(|id| *id > 0)
+        else {
+            continue;
+        };
+        let quoted_table = quote_identifier(table_name, db_type);
+        statements.push(match db_type {
+            DatabaseType::PostgreSQL => format!(
+                "SELECT setval(pg_get_serial_sequence('{}', '{}'), {}, true);",
+                quoted_table.replace('\'', "''"),
+                pk.replace('\'', "''"),
+                max_id
+            ),
+            DatabaseType::MySQL => {
+                format!(
+                    "ALTER TABLE {} AUTO_INCREMENT = {};",
+                    quoted_table,
+                    max_id + 1
+                )
+            }
+            DatabaseType::SQLite => continue,
+        });
+    }
+    if statements.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(writer, "-- Sequence resets").map_err(|e| SeedKitError::Output {
+        message: "writing sequence header".to_string(),
+        source: e,
+    })?;
+    for sql in statements {
+        writeln!(writer, "{}", sql).map_err(|e| SeedKitError::Output {
+            message: "writing sequence reset".to_string(),
+            source: e,
+        })?;
+    }
+    writeln!(writer).map_err(|e| SeedKitError::Output {
+        message: "writing newline".to_string(),
+        source: e,
+    })?;
+    Ok(())
+}
+
 /// Write generated data using PostgreSQL COPY format for fast bulk loading.
 pub fn write_postgres_copy<W: Write>(
     writer: &mut W,
@@ -256,6 +319,17 @@ fn value_to_copy_format(value: &Value) -> String {
                 .map(|byte| format!("{:02x}", byte))
                 .collect::<String>()
         ),
+        Value::Composite { .. } => value
+            .to_record_text()
+            .replace('\\', "\\\\")
+            .replace('\t', "\\t")
+            .replace('\n', "\\n"),
+        Value::Array(_) => value
+            .to_array_text()
+            .replace('\\', "\\\\")
+            .replace('\t', "\\t")
+            .replace('\n', "\\n"),
+        Value::Range { .. } => value.to_range_text().replace('\\', "\\\\"),
     }
 }
 
@@ -272,8 +346,30 @@ fn build_deferred_update_sql(
     let rows = data.tables.get(&update.table_name)?;
     let row = rows.get(update.row_index)?;
 
+    let where_clause = pk_where_clause(&pk.columns, row, update.row_index, db_type)?;
+
+    Some(format!(
+        "UPDATE {} SET {} = {} WHERE {}",
+        quote_identifier(&update.table_name, db_type),
+        quote_identifier(&update.column_name, db_type),
+        update.value.to_sql_literal(db_type),
+        where_clause,
+    ))
+}
+
+/// Build a `WHERE` clause matching a generated row by primary key.
+///
+/// Auto-increment PK columns are absent from generated rows; they are
+/// matched on the synthesized ID (`row_index + 1`), which assumes the table
+/// was seeded into an empty table.
+fn pk_where_clause(
+    pk_columns: &[String],
+    row: &indexmap::IndexMap<String, Value>,
+    row_index: usize,
+    db_type: &DatabaseType,
+) -> Option<String> {
     let mut where_parts = Vec::new();
-    for pk_col in &pk.columns {
+    for pk_col in pk_columns {
         if let Some(pk_val) = row.get(pk_col) {
             where_parts.push(format!(
                 "{} = {}",
@@ -282,22 +378,109 @@ fn build_deferred_update_sql(
             ));
         } else {
             // Auto-increment PK not in the generated row — use synthesized ID
-            let id = update.row_index as i64 + 1;
+            let id = row_index as i64 + 1;
             where_parts.push(format!("{} = {}", quote_identifier(pk_col, db_type), id,));
         }
     }
 
     if where_parts.is_empty() {
-        return None;
+        None
+    } else {
+        Some(where_parts.join(" AND "))
     }
+}
 
-    Some(format!(
-        "UPDATE {} SET {} = {} WHERE {}",
-        quote_identifier(&update.table_name, db_type),
-        quote_identifier(&update.column_name, db_type),
-        update.value.to_sql_literal(db_type),
-        where_parts.join(" AND "),
-    ))
+/// Write one table's generated rows as `UPDATE ... WHERE <pk>` statements.
+///
+/// Used to refresh the column values of an already-seeded table in place:
+/// primary-key columns identify the row and are never updated, and deferred
+/// (cycle-breaking) FK values are folded into the row's own statement.
+/// Returns the number of statements written.
+pub fn write_updates<W: Write>(
+    writer: &mut W,
+    data: &GeneratedData,
+    schema: &DatabaseSchema,
+    table_name: &str,
+) -> Result<usize> {
+    let db_type = &schema.database_type;
+    let pk_columns = schema
+        .tables
+        .get(table_name)
+        .and_then(|t| t.primary_key.as_ref())
+        .map(|pk| pk.columns.clone())
+        .ok_or_else(|| SeedKitError::Config {
+            message: format!(
+                "Table '{}' has no primary key; UPDATE output needs one to address rows.",
+                table_name
+            ),
+        })?;
+    let rows = match data.tables.get(table_name) {
+        Some(rows) => rows,
+        None => return Ok(0),
+    };
+
+    let write_err = |e| SeedKitError::Output {
+        message: format!("writing UPDATE for {}", table_name),
+        source: e,
+    };
+
+    writeln!(writer, "-- Generated by SeedKit").map_err(write_err)?;
+    writeln!(writer, "-- Database: {}", schema.database_name).map_err(write_err)?;
+    writeln!(writer, "-- Table: {} (UPDATE by primary key)", table_name).map_err(write_err)?;
+    writeln!(writer).map_err(write_err)?;
+    let begin = match db_type {
+        DatabaseType::MySQL => "START TRANSACTION;",
+        DatabaseType::SQLite => "BEGIN TRANSACTION;",
+        DatabaseType::PostgreSQL => "BEGIN;",
+    };
+    writeln!(writer, "{}", begin).map_err(write_err)?;
+
+    let mut written = 0;
+    for (row_index, row) in rows.iter().enumerate() {
+        let mut assignments: Vec<(String, String)> = row
+            .iter()
+            .filter(|(col, _)| !pk_columns.contains(col))
+            .map(|(col, value)| (col.clone(), value.to_sql_literal(db_type)))
+            .collect();
+        for update in data
+            .deferred_updates
+            .iter()
+            .filter(|u| u.table_name == table_name && u.row_index == row_index)
+        {
+            let literal = update.value.to_sql_literal(db_type);
+            match assignments
+                .iter_mut()
+                .find(|(col, _)| *col == update.column_name)
+            {
+                Some(existing) => existing.1 = literal,
+                None => assignments.push((update.column_name.clone(), literal)),
+            }
+        }
+        if assignments.is_empty() {
+            continue;
+        }
+
+        let where_clause = match pk_where_clause(&pk_columns, row, row_index, db_type) {
+            Some(clause) => clause,
+            None => continue,
+        };
+        let set_clause: Vec<String> = assignments
+            .iter()
+            .map(|(col, literal)| format!("{} = {}", quote_identifier(col, db_type), literal))
+            .collect();
+        writeln!(
+            writer,
+            "UPDATE {} SET {} WHERE {};",
+            quote_identifier(table_name, db_type),
+            set_clause.join(", "),
+            where_clause
+        )
+        .map_err(write_err)?;
+        written += 1;
+    }
+
+    writeln!(writer, "COMMIT;").map_err(write_err)?;
+    Ok(written)
 }
 
 /// Quote a SQL identifier based on database type.
@@ -340,6 +523,42 @@ mod tests {
         assert!(sql.contains("30"));
     }
 
+    #[test]
+    fn test_write_sequence_resets() {
+        use crate::schema::types::{Column, DataType, PrimaryKey, Table};
+
+        let ids = |n: i64| -> Vec<IndexMap<String, Value>> {
+            (1..=n)
+                .map(|id| IndexMap::from([("id".to_string(), Value::Int(id))]))
+                .collect()
+        };
+        let data = GeneratedData {
+            tables: IndexMap::from([("users".to_string(), ids(12)), ("tags".to_string(), ids(3))]),
+            deferred_updates: Vec::new(),
+        };
+        let resets = |db_type| {
+            let mut schema = DatabaseSchema::new(db_type, "test".to_string());
+            let mut users = Table::new("users".to_string());
+            let mut id = Column::new("id".to_string(), DataType::Integer, "int".to_string());
+            id.is_auto_increment = true;
+            users.columns.insert("id".to_string(), id);
+            users.primary_key = Some(PrimaryKey {
+                columns: vec!["id".to_string()],
+                name: None,
+            });
+            schema.tables.insert("users".to_string(), users);
+            let mut output = Vec::new();
+            write_sequence_resets(&mut output, &data, &schema).unwrap();
+            String::from_utf8(output).unwrap()
+        };
+
+        let pg = resets(DatabaseType::PostgreSQL);
+        assert!(pg.contains("SELECT setval(pg_get_serial_sequence('\"users\"', 'id'), 12, true);"));
+        assert!(!pg.contains("tags"));
+        assert!(resets(DatabaseType::MySQL).contains("ALTER TABLE `users` AUTO_INCREMENT = 13;"));
+        assert!(resets(DatabaseType::SQLite).is_empty());
+    }
+
     #[test]
     fn test_quote_identifier() {
         assert_eq!(
@@ -441,4 +660,68 @@ mod tests {
             sql
         );
     }
+
+    #[test]
+    fn test_write_updates_keys_by_pk_and_folds_deferred() {
+        use crate::generate::engine::DeferredUpdate;
+        use crate::schema::types::*;
+
+        let mut tables = IndexMap::new();
+        let mut row = IndexMap::new();
+        row.insert("id".to_string(), Value::Int(7));
+        row.insert(
+            "name".to_string(),
+            Value::String(Cow::Owned("Widget".to_string())),
+        );
+        row.insert("parent_id".to_string(), Value::Null);
+        tables.insert("products".to_string(), vec![row]);
+
+        let data = GeneratedData {
+            tables,
+            deferred_updates: vec![DeferredUpdate {
+                table_name: "products".to_string(),
+                row_index: 0,
+                column_name: "parent_id".to_string(),
+                value: Value::Int(7),
+            }],
+        };
+
+        let mut schema = DatabaseSchema::new(DatabaseType::PostgreSQL, "test".to_string());
+        let mut table = Table::new("products".to_string());
+        table.primary_key = Some(PrimaryKey {
+            columns: vec!["id".to_string()],
+            name: None,
+        });
+        schema.tables.insert("products".to_string(), table);
+
+        let mut output = Vec::new();
+        let written = write_updates(&mut output, &data, &schema, "products").unwrap();
+
+        let sql = String::from_utf8(output).unwrap();
+        assert_eq!(written, 1);
+        assert!(
+            sql.contains(
+                "UPDATE \"products\" SET \"name\" = 'Widget', \"parent_id\" = 7 WHERE \"id\" = 7;"
+            ),
+            "got:\n{}",
+            sql
+        );
+        assert!(!sql.contains("INSERT"));
+    }
+
+    #[test]
+    fn test_write_updates_requires_primary_key() {
+        let mut schema = DatabaseSchema::new(DatabaseType::PostgreSQL, "test".to_string());
+        schema.tables.insert(
+            "logs".to_string(),
+            crate::schema::types::Table::new("logs".to_string()),
+        );
+        let data = GeneratedData {
+            tables: IndexMap::new(),
+            deferred_updates: Vec::new(),
+        };
+
+        let mut output = Vec::new();
+        assert!(write_updates(&mut output, &data, &schema, "logs").is_err());
+    }
 }
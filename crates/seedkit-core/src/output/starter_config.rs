@@ -0,0 +1,195 @@
+//! # Starter Config
+//!
+//! `seedkit init` writes a commented `seedkit.toml` for the introspected
+//! schema, so new users edit a file that already names their tables and
+//! columns instead of starting from a blank one:
+//!
+//! - a `[tables.<name>]` row count for every table;
+//! - for enum columns, a commented-out `[columns]` override listing their
+//!   values, ready to be weighted;
+//! - for string columns the rules couldn't classify, a commented-out
+//!   override suggesting a `type`, `values` or `pattern`.
+//!
+//! Everything else is left at the defaults `seedkit generate` already uses.
+
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::Write;
+
+use crate::classify::semantic::SemanticType;
+use crate::config::CONFIG_FILE_NAME;
+use crate::schema::types::DatabaseSchema;
+
+/// A TOML key, quoted unless it is bare.
+pub(crate) fn toml_key(key: &str) -> String {
+    if !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        key.to_string()
+    } else {
+        toml_string(key)
+    }
+}
+
+/// A TOML basic string.
+fn toml_string(value: &str) -> String {
+    toml::Value::String(value.to_string()).to_string()
+}
+
+/// The commented starter `seedkit.toml` for `schema`, with `rows` rows per
+/// table.
+pub fn starter_config(
+    schema: &DatabaseSchema,
+    classifications: &BTreeMap<(String, String), SemanticType>,
+    rows: usize,
+) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "# {} for {} ({}), written by `seedkit init`.",
+        CONFIG_FILE_NAME, schema.database_name, schema.database_type
+    );
+    out.push_str(
+        "# Every setting is optional: delete what you don't need. The connection\n\
+         # comes from --db, DATABASE_URL or .env, or set [database] url here.\n\
+         \n\
+         [generate]\n",
+    );
+    let _ = writeln!(out, "rows = {}", rows);
+    out.push_str("# seed = 42\n");
+
+    // Row counts per table
+    for table_name in schema.tables.keys() {
+        let _ = writeln!(out, "\n[tables.{}]\nrows = {}", toml_key(table_name), rows);
+    }
+
+    let mut enums = Vec::new();
+    let mut unknown = Vec::new();
+    for (table_name, table) in &schema.tables {
+        let keys: HashSet<&str> = table
+            .primary_key
+            .iter()
+            .flat_map(|pk| &pk.columns)
+            .chain(table.foreign_keys.iter().flat_map(|fk| &fk.source_columns))
+            .map(String::as_str)
+            .collect();
+        for (col_name, column) in &table.columns {
+            let key = format!("{}.{}", table_name, col_name);
+            if let Some(values) = column.enum_values.as_ref().filter(|v| !v.is_empty()) {
+                enums.push((key, values));
+                continue;
+            }
+            let classified = classifications
+                .get(&(table_name.clone(), col_name.clone()))
+                .copied()
+                .unwrap_or(SemanticType::Unknown);
+            if classified == SemanticType::Unknown
+                && column.data_type.is_string()
+                && !keys.contains(col_name.as_str())
+            {
+                unknown.push((key, column.raw_type.as_str()));
+            }
+        }
+    }
+
+    if !enums.is_empty() {
+        out.push_str(
+            "\n# Enum columns get one of their values uniformly. Uncomment to weight them:\n",
+        );
+        for (key, values) in enums {
+            let list: Vec<String> = values.iter().map(|v| toml_string(v)).collect();
+            let weight = format!("{:.2}", 1.0 / values.len() as f64);
+            let _ = writeln!(
+                out,
+                "\n# [columns.{}]\n# values = [{}]\n# weights = [{}]",
+                toml_string(&key),
+                list.join(", "),
+                vec![weight; values.len()].join(", ")
+            );
+        }
+    }
+
+    if !unknown.is_empty() {
+        out.push_str(
+            "\n# These columns weren't recognized by name and get random text. Give\n\
+             # them a type (email, full_name, url, company_name, ...), a list of\n\
+             # values or a pattern:\n",
+        );
+        for (key, raw_type) in unknown {
+            let _ = writeln!(
+                out,
+                "\n# [columns.{}]  # {}\n# type = \"email\"\n# values = [\"a\", \"b\"]\n# pattern = \"[A-Z]{{3}}-[0-9]{{4}}\"",
+                toml_string(&key),
+                raw_type
+            );
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::classify::rules::classify_schema;
+    use crate::config::SeedKitConfig;
+    use crate::schema::types::*;
+
+    #[test]
+    fn test_starter_config_parses() {
+        let mut schema = DatabaseSchema::new(DatabaseType::PostgreSQL, "shop".to_string());
+        let mut orders = Table::new("order items".to_string());
+        let mut status = Column::new(
+            "status".to_string(),
+            DataType::Enum("order_status".to_string()),
+            "order_status".to_string(),
+        );
+        status.enum_values = Some(vec!["pending".to_string(), "shipped".to_string()]);
+        orders.columns.insert("status".to_string(), status);
+        orders.columns.insert(
+            "ref2".to_string(),
+            Column::new(
+                "ref2".to_string(),
+                DataType::VarChar,
+                "varchar(20)".to_string(),
+            ),
+        );
+        orders.columns.insert(
+            "email".to_string(),
+            Column::new(
+                "email".to_string(),
+                DataType::VarChar,
+                "varchar".to_string(),
+            ),
+        );
+        schema.tables.insert("order items".to_string(), orders);
+
+        let text = starter_config(&schema, &classify_schema(&schema), 250);
+        assert!(
+            text.contains("[tables.\"order items\"]\nrows = 250"),
+            "{}",
+            text
+        );
+        assert!(text.contains("# [columns.\"order items.status\"]\n# values = [\"pending\", \"shipped\"]\n# weights = [0.50, 0.50]"), "{}", text);
+        assert!(
+            text.contains("# [columns.\"order items.ref2\"]  # varchar(20)"),
+            "{}",
+            text
+        );
+        assert!(!text.contains("order items.email"), "{}", text);
+
+        let config: SeedKitConfig = toml::from_str(&text).unwrap();
+        config.validate().unwrap();
+        assert_eq!(config.generate.rows, Some(250));
+        assert_eq!(config.table_row_overrides()["order items"], 250);
+        assert!(config.columns.is_empty());
+
+        // Uncommenting an override gives a valid config
+        let uncommented = text
+            .replace("# [columns", "[columns")
+            .replace("# values = ", "values = ");
+        let config: SeedKitConfig = toml::from_str(&uncommented).unwrap();
+        assert_eq!(config.columns.len(), 2);
+    }
+}
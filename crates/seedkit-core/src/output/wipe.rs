@@ -0,0 +1,550 @@
+//! # Wiping Seeded Data
+//!
+//! `seedkit wipe` resets a database after seeding, without hand-written
+//! `DELETE` scripts. Two modes:
+//!
+//! - **Delete** (the default): `DELETE` from each table, children before
+//!   parents. Foreign keys deferred to break cycles are set to NULL first,
+//!   so rows in a cycle can go. When the lock file recorded sequence offsets
+//!   (the table already had rows when it was seeded), only the rows after
+//!   the offset are deleted, and the sequence is wound back to it, so
+//!   replaying the lock file assigns the same IDs again.
+//! - **Truncate**: empty the tables and restart their IDs — one `TRUNCATE
+//!   ... RESTART IDENTITY` on PostgreSQL, `TRUNCATE TABLE` with foreign key
+//!   checks off on MySQL, and `DELETE` on SQLite, which has no `TRUNCATE`.
+//!
+//! `seedkit wipe --run <ID>` deletes only the rows of one tracked run (see
+//! `output::runs`), by its marker column or recorded ID ranges, and leaves
+//! the sequences alone.
+//!
+//! All statements run in one transaction, except that MySQL commits
+//! implicitly on `TRUNCATE` and `ALTER TABLE`.
+
+use std::collections::BTreeMap;
+
+use crate::error::{Result, SeedKitError};
+use crate::generate::sequence::auto_increment_pk;
+use crate::graph::topo::DeferredEdge;
+use crate::output::direct::{connect_single, quote_identifier};
+use crate::output::runs::{Run, RUNS_TABLE};
+use crate::schema::types::{DatabaseSchema, DatabaseType};
+
+const CONNECT_FAILED: &str = "Failed to connect to wipe seeded data";
+
+/// How `seedkit wipe` removes rows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WipeMode {
+    Delete,
+    Truncate,
+}
+
+/// One statement of a wipe, with the table it acts on for error messages.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WipeStatement {
+    pub table: String,
+    pub sql: String,
+}
+
+/// The statements wiping `tables`, given in insertion order (parents
+/// first). `deferred_edges` are the foreign keys broken to resolve cycles
+/// and `offsets` the sequence offsets recorded in the lock file.
+/// `sqlite_sequence` says whether the SQLite database has the table
+/// `AUTOINCREMENT` counters are kept in.
+pub fn wipe_statements(
+    schema: &DatabaseSchema,
+    tables: &[String],
+    deferred_edges: &[DeferredEdge],
+    offsets: &BTreeMap<String, u64>,
+    mode: WipeMode,
+    sqlite_sequence: bool,
+) -> Vec<WipeStatement> {
+    let db_type = schema.database_type;
+    let quote = |name: &str| quote_identifier(name, &db_type);
+    let statement = |table: &str, sql: String| WipeStatement {
+        table: table.to_string(),
+        sql,
+    };
+
+    match (mode, db_type) {
+        (WipeMode::Truncate, DatabaseType::PostgreSQL) => {
+            if tables.is_empty() {
+                return Vec::new();
+            }
+            let list: Vec<String> = tables.iter().map(|t| quote(t)).collect();
+            return vec![statement(
+                &tables.join(", "),
+                format!("TRUNCATE {} RESTART IDENTITY", list.join(", ")),
+            )];
+        }
+        (WipeMode::Truncate, DatabaseType::MySQL) => {
+            let mut statements = vec![statement(
+                "(session)",
+                "SET FOREIGN_KEY_CHECKS = 0".to_string(),
+            )];
+            for table in tables.iter().rev() {
+                statements.push(statement(table, format!("TRUNCATE TABLE {}", quote(table))));
+            }
+            statements.push(statement(
+                "(session)",
+                "SET FOREIGN_KEY_CHECKS = 1".to_string(),
+            ));
+            return statements;
+        }
+        _ => {}
+    }
+
+    // Only rows seeded after the recorded offset are deleted; truncating
+    // ignores the offsets and empties the table.
+    let offset_of = |table: &str| -> Option<(&str, u64)> {
+        if mode == WipeMode::Truncate {
+            return None;
+        }
+        let pk = auto_increment_pk(schema.tables.get(table)?)?;
+        let offset = *offsets.get(table).filter(|o| **o > 0)?;
+        Some((pk, offset))
+    };
+    let where_clause = |table: &str| match offset_of(table) {
+        Some((pk, offset)) => format!(" WHERE {} > {}", quote(pk), offset),
+        None => String::new(),
+    };
+
+    let mut statements = delete_statements(schema, tables, deferred_edges, where_clause);
+
+    // Wind the sequences back, after the deletes: MySQL commits on ALTER TABLE
+    for table in tables.iter().rev() {
+        let Some(pk) = schema.tables.get(table).and_then(auto_increment_pk) else {
+            continue;
+        };
+        let offset = offset_of(table).map_or(0, |(_, offset)| offset);
+        let sql = match db_type {
+            DatabaseType::PostgreSQL => format!(
+                "SELECT setval(pg_get_serial_sequence('{}', '{}'), {}, {})",
+                quote(table).replace('\'', "''"),
+                pk.replace('\'', "''"),
+                offset.max(1),
+                offset > 0
+            ),
+            DatabaseType::MySQL => {
+                format!(
+                    "ALTER TABLE {} AUTO_INCREMENT = {}",
+                    quote(table),
+                    offset + 1
+                )
+            }
+            DatabaseType::SQLite if !sqlite_sequence => continue,
+            DatabaseType::SQLite if offset > 0 => format!(
+                "UPDATE sqlite_sequence SET seq = {} WHERE name = '{}'",
+                offset,
+                table.replace('\'', "''")
+            ),
+            DatabaseType::SQLite => format!(
+                "DELETE FROM sqlite_sequence WHERE name = '{}'",
+                table.replace('\'', "''")
+            ),
+        };
+        statements.push(statement(table, sql));
+    }
+
+    statements
+}
+
+/// The conditions selecting one run's rows, by table. A recorded run is
+/// found by the marker column or ID range it recorded for each table; a run
+/// that wasn't recorded (written to a file, say) by the `marker` column,
+/// in the tables that have it. Tables with neither are left out.
+pub fn run_filters(
+    schema: &DatabaseSchema,
+    tables: &[String],
+    run_id: &str,
+    run: Option<&Run>,
+    marker: Option<&str>,
+) -> BTreeMap<String, String> {
+    let db_type = schema.database_type;
+    let quote = |name: &str| quote_identifier(name, &db_type);
+    let mut filters = BTreeMap::new();
+    for table in tables {
+        let (marker, range) = match run {
+            Some(run) => {
+                let Some(recorded) = run.tables.iter().find(|t| &t.table == table) else {
+                    continue;
+                };
+                let range = recorded
+                    .pk_column
+                    .as_deref()
+                    .zip(recorded.first_id)
+                    .zip(recorded.last_id)
+                    .map(|((pk, first), last)| (pk, first, last));
+                (recorded.marker_column.as_deref(), range)
+            }
+            None => {
+                let marker = marker.filter(|m| {
+                    schema
+                        .tables
+                        .get(table)
+                        .is_some_and(|t| t.columns.contains_key(*m))
+                });
+                (marker, None)
+            }
+        };
+        let filter = match (marker, range) {
+            (Some(marker), _) => format!("{} = '{}'", quote(marker), run_id.replace('\'', "''")),
+            (None, Some((pk, first, last))) => {
+                format!("{} BETWEEN {} AND {}", quote(pk), first, last)
+            }
+            (None, None) => continue,
+        };
+        filters.insert(table.clone(), filter);
+    }
+    filters
+}
+
+/// The statements deleting one run's rows, given the conditions from
+/// `run_filters`, then its record in the runs table.
+pub fn run_wipe_statements(
+    schema: &DatabaseSchema,
+    tables: &[String],
+    deferred_edges: &[DeferredEdge],
+    run_id: &str,
+    filters: &BTreeMap<String, String>,
+    recorded: bool,
+) -> Vec<WipeStatement> {
+    let tables: Vec<String> = tables
+        .iter()
+        .filter(|t| filters.contains_key(*t))
+        .cloned()
+        .collect();
+    let where_clause = |table: &str| match filters.get(table) {
+        Some(filter) => format!(" WHERE {}", filter),
+        None => String::new(),
+    };
+    let mut statements = delete_statements(schema, &tables, deferred_edges, where_clause);
+    if recorded {
+        statements.push(WipeStatement {
+            table: RUNS_TABLE.to_string(),
+            sql: format!(
+                "DELETE FROM {} WHERE run_id = '{}'",
+                quote_identifier(RUNS_TABLE, &schema.database_type),
+                run_id.replace('\'', "''")
+            ),
+        });
+    }
+    statements
+}
+
+/// `DELETE` from `tables` children first, clearing the nullable foreign
+/// keys that close cycles beforehand. `where_clause` gives each table's
+/// ` WHERE ...`, or nothing for all its rows.
+fn delete_statements(
+    schema: &DatabaseSchema,
+    tables: &[String],
+    deferred_edges: &[DeferredEdge],
+    where_clause: impl Fn(&str) -> String,
+) -> Vec<WipeStatement> {
+    let db_type = schema.database_type;
+    let quote = |name: &str| quote_identifier(name, &db_type);
+    let statement = |table: &str, sql: String| WipeStatement {
+        table: table.to_string(),
+        sql,
+    };
+    let mut statements = Vec::new();
+
+    // Clear the foreign keys that close cycles, so neither side blocks the
+    // other's delete
+    for edge in deferred_edges {
+        if !tables.contains(&edge.source_table) || !tables.contains(&edge.target_table) {
+            continue;
+        }
+        let nullable = schema.tables.get(&edge.source_table).is_some_and(|t| {
+            edge.source_columns
+                .iter()
+                .all(|c| t.columns.get(c).is_some_and(|c| c.nullable))
+        });
+        if !nullable {
+            continue;
+        }
+        let set: Vec<String> = edge
+            .source_columns
+            .iter()
+            .map(|c| format!("{} = NULL", quote(c)))
+            .collect();
+        statements.push(statement(
+            &edge.source_table,
+            format!(
+                "UPDATE {} SET {}{}",
+                quote(&edge.source_table),
+                set.join(", "),
+                where_clause(&edge.source_table)
+            ),
+        ));
+    }
+
+    for table in tables.iter().rev() {
+        statements.push(statement(
+            table,
+            format!("DELETE FROM {}{}", quote(table), where_clause(table)),
+        ));
+    }
+
+    statements
+}
+
+/// Whether the SQLite database at `url` has a `sqlite_sequence` table,
+/// which only exists once an `AUTOINCREMENT` table was created.
+pub async fn sqlite_sequence_exists(url: &str) -> Result<bool> {
+    let pool = connect_single::<sqlx::Sqlite>(url, CONNECT_FAILED).await?;
+    let exists: Option<i64> =
+        sqlx::query_scalar("SELECT 1 FROM sqlite_master WHERE name = 'sqlite_sequence'")
+            .fetch_optional(&pool)
+            .await
+            .map_err(|e| SeedKitError::Introspection {
+                query: "sqlite_sequence".to_string(),
+                source: e,
+            })?;
+    pool.close().await;
+    Ok(exists.is_some())
+}
+
+/// Run the statements of a wipe in one transaction.
+pub async fn execute_wipe(
+    url: &str,
+    db_type: DatabaseType,
+    statements: &[WipeStatement],
+) -> Result<()> {
+    match db_type {
+        DatabaseType::PostgreSQL => wipe_with::<sqlx::Postgres>(url, statements).await,
+        DatabaseType::MySQL => wipe_with::<sqlx::MySql>(url, statements).await,
+        DatabaseType::SQLite => wipe_with::<sqlx::Sqlite>(url, statements).await,
+    }
+}
+
+async fn wipe_with<DB>(url: &str, statements: &[WipeStatement]) -> Result<()>
+where
+    DB: sqlx::Database,
+    for<'c> &'c mut DB::Connection: sqlx::Executor<'c, Database = DB>,
+    for<'q> DB::Arguments<'q>: sqlx::IntoArguments<'q, DB>,
+{
+    let session_failed = |message: &str, sql_preview: &str| {
+        let message = message.to_string();
+        let sql_preview = sql_preview.to_string();
+        move |e| SeedKitError::WipeFailed {
+            table: "(session)".to_string(),
+            message,
+            sql_preview,
+            source: e,
+        }
+    };
+
+    let pool = connect_single::<DB>(url, CONNECT_FAILED).await?;
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(session_failed("Failed to begin transaction", "BEGIN"))?;
+    execute_statements(&mut tx, statements).await?;
+    tx.commit()
+        .await
+        .map_err(session_failed("Failed to commit", "COMMIT"))?;
+    pool.close().await;
+    Ok(())
+}
+
+/// Execute each statement in `tx`, naming the table of the one that fails.
+async fn execute_statements<DB>(
+    tx: &mut sqlx::Transaction<'_, DB>,
+    statements: &[WipeStatement],
+) -> Result<()>
+where
+    DB: sqlx::Database,
+    for<'c> &'c mut DB::Connection: sqlx::Executor<'c, Database = DB>,
+    for<'q> DB::Arguments<'q>: sqlx::IntoArguments<'q, DB>,
+{
+    for statement in statements {
+        sqlx::query(&statement.sql)
+            .execute(&mut **tx)
+            .await
+            .map_err(|e| SeedKitError::WipeFailed {
+                table: statement.table.clone(),
+                message: "statement failed, nothing was removed".to_string(),
+                sql_preview: statement.sql.clone(),
+                source: e,
+            })?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::cycle::break_cycles;
+    use crate::graph::dag::DependencyGraph;
+    use crate::graph::topo::topological_sort;
+    use crate::output::runs::RunTable;
+    use crate::schema::types::*;
+
+    fn cyclic_schema(database_type: DatabaseType) -> DatabaseSchema {
+        // users.last_order_id → orders, orders.user_id → users
+        let mut schema = DatabaseSchema::new(database_type, "shop".to_string());
+        for (table, column, parent, nullable) in [
+            ("users", "last_order_id", "orders", true),
+            ("orders", "user_id", "users", false),
+        ] {
+            let mut t = Table::new(table.to_string());
+            let mut id = Column::new("id".to_string(), DataType::Integer, "integer".to_string());
+            id.is_auto_increment = true;
+            t.columns.insert("id".to_string(), id);
+            let mut fk_column =
+                Column::new(column.to_string(), DataType::Integer, "integer".to_string());
+            fk_column.nullable = nullable;
+            t.columns.insert(column.to_string(), fk_column);
+            t.primary_key = Some(PrimaryKey {
+                columns: vec!["id".to_string()],
+                name: None,
+            });
+            t.foreign_keys.push(ForeignKey {
+                name: None,
+                source_columns: vec![column.to_string()],
+                referenced_table: parent.to_string(),
+                referenced_columns: vec!["id".to_string()],
+                on_delete: ForeignKeyAction::NoAction,
+                on_update: ForeignKeyAction::NoAction,
+                is_deferrable: false,
+            });
+            schema.tables.insert(table.to_string(), t);
+        }
+        schema
+    }
+
+    #[test]
+    fn test_wipe_statements() {
+        let sqls = |statements: Vec<WipeStatement>| {
+            statements.into_iter().map(|s| s.sql).collect::<Vec<_>>()
+        };
+
+        let schema = cyclic_schema(DatabaseType::SQLite);
+        let mut graph = DependencyGraph::from_schema(&schema);
+        let deferred = break_cycles(&mut graph, &[]).unwrap();
+        let tables = topological_sort(&graph).unwrap().tables;
+        assert_eq!(tables, vec!["users", "orders"]);
+
+        let offsets = BTreeMap::from([("users".to_string(), 40)]);
+        assert_eq!(
+            sqls(wipe_statements(
+                &schema,
+                &tables,
+                &deferred,
+                &offsets,
+                WipeMode::Delete,
+                true
+            )),
+            vec![
+                "UPDATE \"users\" SET \"last_order_id\" = NULL WHERE \"id\" > 40",
+                "DELETE FROM \"orders\"",
+                "DELETE FROM \"users\" WHERE \"id\" > 40",
+                "DELETE FROM sqlite_sequence WHERE name = 'orders'",
+                "UPDATE sqlite_sequence SET seq = 40 WHERE name = 'users'",
+            ]
+        );
+        // Without AUTOINCREMENT tables there are no counters to reset
+        assert_eq!(
+            wipe_statements(
+                &schema,
+                &tables,
+                &deferred,
+                &offsets,
+                WipeMode::Truncate,
+                false
+            )
+            .len(),
+            3
+        );
+
+        let schema = cyclic_schema(DatabaseType::PostgreSQL);
+        assert_eq!(
+            sqls(wipe_statements(
+                &schema,
+                &tables,
+                &deferred,
+                &offsets,
+                WipeMode::Truncate,
+                false
+            )),
+            vec!["TRUNCATE \"users\", \"orders\" RESTART IDENTITY"]
+        );
+        let statements = sqls(wipe_statements(
+            &schema,
+            &tables,
+            &deferred,
+            &offsets,
+            WipeMode::Delete,
+            false,
+        ));
+        assert_eq!(
+            statements.last().unwrap(),
+            "SELECT setval(pg_get_serial_sequence('\"users\"', 'id'), 40, true)"
+        );
+    }
+
+    #[test]
+    fn test_run_wipe_statements() {
+        let mut schema = cyclic_schema(DatabaseType::SQLite);
+        let marker = Column::new("seed_run".to_string(), DataType::Text, "text".to_string());
+        schema
+            .tables
+            .get_mut("orders")
+            .unwrap()
+            .columns
+            .insert("seed_run".to_string(), marker);
+        let mut graph = DependencyGraph::from_schema(&schema);
+        let deferred = break_cycles(&mut graph, &[]).unwrap();
+        let tables = topological_sort(&graph).unwrap().tables;
+
+        let run = Run {
+            run_id: "run-1".to_string(),
+            created_at: "2026-03-14T09:30:12Z".to_string(),
+            tables: vec![RunTable {
+                table: "users".to_string(),
+                pk_column: Some("id".to_string()),
+                first_id: Some(41),
+                last_id: Some(60),
+                marker_column: None,
+                row_count: 20,
+            }],
+        };
+        let filters = run_filters(&schema, &tables, "run-1", Some(&run), Some("seed_run"));
+        assert_eq!(filters["users"], "\"id\" BETWEEN 41 AND 60");
+        // The run didn't insert into orders
+        assert_eq!(filters.len(), 1);
+        let mut run = run;
+        run.tables.push(RunTable {
+            table: "orders".to_string(),
+            pk_column: Some("id".to_string()),
+            first_id: Some(1),
+            last_id: Some(20),
+            marker_column: Some("seed_run".to_string()),
+            row_count: 20,
+        });
+        let filters = run_filters(&schema, &tables, "run-1", Some(&run), None);
+        assert_eq!(filters["orders"], "\"seed_run\" = 'run-1'");
+
+        let statements: Vec<String> =
+            run_wipe_statements(&schema, &tables, &deferred, "run-1", &filters, true)
+                .into_iter()
+                .map(|s| s.sql)
+                .collect();
+        assert_eq!(
+            statements,
+            vec![
+                "UPDATE \"users\" SET \"last_order_id\" = NULL WHERE \"id\" BETWEEN 41 AND 60",
+                "DELETE FROM \"orders\" WHERE \"seed_run\" = 'run-1'",
+                "DELETE FROM \"users\" WHERE \"id\" BETWEEN 41 AND 60",
+                "DELETE FROM \"_seedkit_runs\" WHERE run_id = 'run-1'",
+            ]
+        );
+
+        // Without a record only the marker column identifies the rows
+        assert!(run_filters(&schema, &tables, "run-2", None, None).is_empty());
+        assert_eq!(
+            run_filters(&schema, &tables, "run-2", None, Some("seed_run")).len(),
+            1
+        );
+    }
+}
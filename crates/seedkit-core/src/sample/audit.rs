@@ -0,0 +1,282 @@
+//! # k-Anonymity Audit
+//!
+//! Categorical profiles of quasi-identifiers — columns like zip code,
+//! birth date, gender or job title that don't name anyone alone but can
+//! single people out together — are checked before the profiles are saved:
+//!
+//! - a category held by fewer than `k` rows is a rare category, and can be
+//!   dropped from the profile;
+//! - a pair of quasi-identifiers whose rarest categories would, if the
+//!   columns were independent, share fewer than `k` rows is reported as a
+//!   rare combination. Profiles only hold per-column frequencies, so this
+//!   is an estimate and is only ever reported.
+
+use std::fmt;
+
+use super::stats::{ColumnDistribution, DistributionProfile};
+
+/// Rows a category must cover unless configured otherwise.
+pub const DEFAULT_K: u64 = 5;
+
+/// Column name patterns of quasi-identifiers (case-insensitive substrings).
+const QUASI_IDENTIFIER_PATTERNS: &[&str] = &[
+    "zip",
+    "postal",
+    "postcode",
+    "birth",
+    "dob",
+    "age",
+    "gender",
+    "sex",
+    "city",
+    "county",
+    "region",
+    "state",
+    "country",
+    "nationality",
+    "ethnicity",
+    "race",
+    "religion",
+    "marital",
+    "job_title",
+    "occupation",
+    "employer",
+];
+
+/// Patterns that only match a whole `_`-separated word.
+const WORD_PATTERNS: &[&str] = &["age", "sex", "race", "state"];
+
+/// Check if a column name looks like a quasi-identifier.
+pub fn is_quasi_identifier(name: &str) -> bool {
+    let lower = name.to_lowercase();
+    QUASI_IDENTIFIER_PATTERNS.iter().any(|pattern| {
+        // Short patterns must be whole words: "age" isn't in "page_count"
+        if WORD_PATTERNS.contains(pattern) {
+            lower.split('_').any(|word| word == *pattern)
+        } else {
+            lower.contains(pattern)
+        }
+    })
+}
+
+/// A re-identification risk found in a profile.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Finding {
+    /// Categories of a quasi-identifier held by fewer than `k` rows.
+    RareCategories {
+        table: String,
+        column: String,
+        categories: usize,
+    },
+    /// Two quasi-identifiers whose rarest categories together are expected
+    /// to cover fewer than `k` rows.
+    RareCombination {
+        table: String,
+        columns: (String, String),
+        expected_rows: f64,
+    },
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Finding::RareCategories {
+                table,
+                column,
+                categories,
+            } => write!(
+                f,
+                "{}.{}: {} categories cover too few rows",
+                table, column, categories
+            ),
+            Finding::RareCombination {
+                table,
+                columns: (a, b),
+                expected_rows,
+            } => write!(
+                f,
+                "{}.({}, {}): rarest combination expected in {:.1} rows",
+                table, a, b, expected_rows
+            ),
+        }
+    }
+}
+
+/// Categorical quasi-identifier columns of a profile, sorted by name.
+fn quasi_identifiers(profile: &DistributionProfile) -> Vec<(&String, &Vec<(String, f64)>)> {
+    let mut columns: Vec<_> = profile
+        .column_distributions
+        .iter()
+        .filter_map(|(column, dist)| match dist {
+            ColumnDistribution::Categorical { values }
+                if !values.is_empty() && is_quasi_identifier(column) =>
+            {
+                Some((column, values))
+            }
+            _ => None,
+        })
+        .collect();
+    columns.sort_by(|a, b| a.0.cmp(b.0));
+    columns
+}
+
+fn is_rare(frequency: f64, row_count: u64, k: u64) -> bool {
+    frequency * (row_count as f64) < k as f64
+}
+
+/// Find categories and quasi-identifier pairs covering fewer than `k` rows.
+pub fn audit_profiles(profiles: &[DistributionProfile], k: u64) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    for profile in profiles {
+        let columns = quasi_identifiers(profile);
+        for (column, values) in &columns {
+            let categories = values
+                .iter()
+                .filter(|(_, frequency)| is_rare(*frequency, profile.row_count, k))
+                .count();
+            if categories > 0 {
+                findings.push(Finding::RareCategories {
+                    table: profile.table_name.clone(),
+                    column: column.to_string(),
+                    categories,
+                });
+            }
+        }
+
+        let rarest = |values: &[(String, f64)]| {
+            values
+                .iter()
+                .map(|(_, frequency)| *frequency)
+                .fold(f64::INFINITY, f64::min)
+        };
+        for (i, (a, a_values)) in columns.iter().enumerate() {
+            for (b, b_values) in &columns[i + 1..] {
+                let expected_rows = rarest(a_values) * rarest(b_values) * profile.row_count as f64;
+                if expected_rows < k as f64 {
+                    findings.push(Finding::RareCombination {
+                        table: profile.table_name.clone(),
+                        columns: (a.to_string(), b.to_string()),
+                        expected_rows,
+                    });
+                }
+            }
+        }
+    }
+    findings
+}
+
+/// Drop quasi-identifier categories covering fewer than `k` rows. A column
+/// left without categories loses its distribution and falls back to
+/// semantic generation.
+///
+/// Returns the number of categories dropped.
+pub fn drop_rare_categories(profiles: &mut [DistributionProfile], k: u64) -> usize {
+    let mut dropped = 0;
+    for profile in profiles.iter_mut() {
+        let row_count = profile.row_count;
+        profile.column_distributions.retain(|column, dist| {
+            let ColumnDistribution::Categorical { values } = dist else {
+                return true;
+            };
+            if !is_quasi_identifier(column) {
+                return true;
+            }
+            let before = values.len();
+            values.retain(|(_, frequency)| !is_rare(*frequency, row_count, k));
+            dropped += before - values.len();
+            !values.is_empty()
+        });
+    }
+    dropped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn profile() -> DistributionProfile {
+        let categorical = |values: &[(&str, f64)]| ColumnDistribution::Categorical {
+            values: values.iter().map(|(v, f)| (v.to_string(), *f)).collect(),
+        };
+        DistributionProfile {
+            table_name: "patients".to_string(),
+            row_count: 1000,
+            unique_pairs: Vec::new(),
+            text_profiles: HashMap::new(),
+            null_rates: HashMap::new(),
+            distinct_counts: HashMap::new(),
+            correlations: Vec::new(),
+            temporal_profiles: HashMap::new(),
+            fan_outs: HashMap::new(),
+            column_distributions: HashMap::from([
+                (
+                    "zip_code".to_string(),
+                    categorical(&[("02139", 0.6), ("02140", 0.398), ("99501", 0.002)]),
+                ),
+                (
+                    "gender".to_string(),
+                    categorical(&[("f", 0.51), ("m", 0.48), ("x", 0.01)]),
+                ),
+                (
+                    "status".to_string(),
+                    categorical(&[("active", 0.999), ("banned", 0.001)]),
+                ),
+            ]),
+        }
+    }
+
+    #[test]
+    fn test_quasi_identifier_detection() {
+        assert!(is_quasi_identifier("zip_code"));
+        assert!(is_quasi_identifier("DateOfBirth"));
+        assert!(is_quasi_identifier("age"));
+        assert!(is_quasi_identifier("patient_age"));
+        assert!(is_quasi_identifier("billing_state"));
+        assert!(!is_quasi_identifier("page_count"));
+        assert!(!is_quasi_identifier("usage"));
+        assert!(!is_quasi_identifier("statement"));
+        assert!(!is_quasi_identifier("status"));
+        assert!(!is_quasi_identifier("trace_id"));
+    }
+
+    #[test]
+    fn test_audit_finds_rare_categories_and_combinations() {
+        let findings = audit_profiles(&[profile()], DEFAULT_K);
+        assert_eq!(
+            findings,
+            vec![
+                Finding::RareCategories {
+                    table: "patients".to_string(),
+                    column: "zip_code".to_string(),
+                    categories: 1,
+                },
+                Finding::RareCombination {
+                    table: "patients".to_string(),
+                    columns: ("gender".to_string(), "zip_code".to_string()),
+                    expected_rows: 0.01 * 0.002 * 1000.0,
+                },
+            ]
+        );
+        assert!(audit_profiles(&[profile()], 1)
+            .iter()
+            .all(|f| matches!(f, Finding::RareCombination { .. })));
+    }
+
+    #[test]
+    fn test_drop_rare_categories() {
+        let mut profiles = vec![profile()];
+        assert_eq!(drop_rare_categories(&mut profiles, 20), 2);
+        let values = |column: &str| match &profiles[0].column_distributions[column] {
+            ColumnDistribution::Categorical { values } => values.len(),
+            other => panic!("unexpected {:?}", other),
+        };
+        assert_eq!(values("zip_code"), 2);
+        assert_eq!(values("gender"), 2);
+        // Not a quasi-identifier, so its rare category stays
+        assert_eq!(values("status"), 2);
+
+        assert_eq!(drop_rare_categories(&mut profiles, 10_000), 4);
+        assert!(!profiles[0].column_distributions.contains_key("zip_code"));
+    }
+}
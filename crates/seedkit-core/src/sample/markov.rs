@@ -0,0 +1,263 @@
+//! # Markov Text Models
+//!
+//! With `seedkit sample --text-models`, free-text columns (descriptions,
+//! bios, comments) are profiled as word n-gram models instead of value
+//! frequencies. Generated text then uses the domain's vocabulary and
+//! phrasing without reproducing what was sampled:
+//!
+//! - Only words found in at least [`MIN_DOCUMENTS`] sampled values are
+//!   learned, so one-off names, emails and order numbers never enter the
+//!   model.
+//! - The model keeps hashes of the sampled sentences, never the sentences,
+//!   and a generated sentence that hashes like one of them is thrown away.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+use fake::faker::lorem::en::Sentence;
+use fake::Fake;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Words of context each next word is chosen from.
+pub const TEXT_MODEL_ORDER: usize = 2;
+
+/// Distinct sampled values a word must appear in to be learned.
+pub const MIN_DOCUMENTS: usize = 2;
+
+/// Longest generated sentence, in words.
+const MAX_SENTENCE_WORDS: usize = 40;
+
+/// Attempts at a sentence not found in the sample before giving up on it.
+const SENTENCE_ATTEMPTS: usize = 20;
+
+/// A word n-gram model of a free-text column.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TextModel {
+    pub order: usize,
+    /// Sentence openings (their first `order` words), with counts.
+    pub starts: Vec<(Vec<String>, u32)>,
+    /// Words following each run of `order` words (joined by a space), with
+    /// counts. An empty word ends the sentence.
+    pub transitions: BTreeMap<String, Vec<(String, u32)>>,
+    /// Fewest and most sentences in a sampled value.
+    pub sentences: (usize, usize),
+    /// Sorted hashes of the sampled sentences (see [`sentence_hash`]).
+    pub sentence_hashes: Vec<u64>,
+}
+
+impl TextModel {
+    /// Train a model on sampled values. `None` when too little text is
+    /// shared between values to learn from.
+    pub fn train<'a>(documents: impl IntoIterator<Item = &'a str>, order: usize) -> Option<Self> {
+        let documents: Vec<Vec<Vec<&str>>> = documents
+            .into_iter()
+            .map(|doc| {
+                split_sentences(doc)
+                    .map(|s| s.split_whitespace().collect())
+                    .collect()
+            })
+            .filter(|sentences: &Vec<Vec<&str>>| !sentences.is_empty())
+            .collect();
+
+        let mut document_counts: HashMap<&str, usize> = HashMap::new();
+        for doc in &documents {
+            let words: HashSet<&str> = doc.iter().flatten().copied().collect();
+            for word in words {
+                *document_counts.entry(word).or_default() += 1;
+            }
+        }
+        let shared = |word: &str| document_counts.get(word).copied().unwrap_or(0) >= MIN_DOCUMENTS;
+
+        let mut starts: BTreeMap<Vec<String>, u32> = BTreeMap::new();
+        let mut transitions: BTreeMap<String, BTreeMap<String, u32>> = BTreeMap::new();
+        let mut hashes = BTreeSet::new();
+        for sentence in documents.iter().flatten() {
+            hashes.insert(sentence_hash(&sentence.join(" ")));
+            // Rare words split the sentence into runs learned separately
+            for run in sentence.split(|word| !shared(word)) {
+                if run.len() < order {
+                    continue;
+                }
+                *starts
+                    .entry(run[..order].iter().map(|w| w.to_string()).collect())
+                    .or_default() += 1;
+                for window in run.windows(order + 1) {
+                    *transitions
+                        .entry(window[..order].join(" "))
+                        .or_default()
+                        .entry(window[order].to_string())
+                        .or_default() += 1;
+                }
+                *transitions
+                    .entry(run[run.len() - order..].join(" "))
+                    .or_default()
+                    .entry(String::new())
+                    .or_default() += 1;
+            }
+        }
+        if starts.is_empty() {
+            return None;
+        }
+
+        let counts = documents.iter().map(Vec::len);
+        Some(Self {
+            order,
+            starts: starts.into_iter().collect(),
+            transitions: transitions
+                .into_iter()
+                .map(|(state, next)| (state, next.into_iter().collect()))
+                .collect(),
+            sentences: (counts.clone().min()?, counts.max()?),
+            sentence_hashes: hashes.into_iter().collect(),
+        })
+    }
+
+    /// Generate a value of one or more sentences.
+    pub fn generate(&self, rng: &mut impl Rng) -> String {
+        let (min, max) = self.sentences;
+        let count = rng.random_range(min.max(1)..=max.max(1));
+        let sentences: Vec<String> = (0..count)
+            .filter_map(|_| {
+                (0..SENTENCE_ATTEMPTS)
+                    .map(|_| self.sentence(rng))
+                    .find(|s| {
+                        self.sentence_hashes
+                            .binary_search(&sentence_hash(s))
+                            .is_err()
+                    })
+            })
+            .collect();
+        if sentences.is_empty() {
+            return Sentence(5..12).fake_with_rng(rng);
+        }
+        sentences.join(" ")
+    }
+
+    fn sentence(&self, rng: &mut impl Rng) -> String {
+        let mut words: Vec<&str> = weighted(&self.starts, rng)
+            .map(|start| start.iter().map(String::as_str).collect())
+            .unwrap_or_default();
+        while words.len() < MAX_SENTENCE_WORDS {
+            let state = words[words.len().saturating_sub(self.order)..].join(" ");
+            match self
+                .transitions
+                .get(&state)
+                .and_then(|next| weighted(next, rng))
+            {
+                Some(word) if !word.is_empty() => words.push(word),
+                _ => break,
+            }
+        }
+
+        let mut sentence = words.join(" ");
+        if let Some(first) = sentence.chars().next() {
+            sentence.replace_range(..first.len_utf8(), &first.to_uppercase().to_string());
+        }
+        if !sentence.ends_with(['.', '!', '?']) {
+            sentence.push('.');
+        }
+        sentence
+    }
+}
+
+/// Pick an item by count.
+fn weighted<'a, T>(items: &'a [(T, u32)], rng: &mut impl Rng) -> Option<&'a T> {
+    let total: u32 = items.iter().map(|(_, count)| count).sum();
+    if total == 0 {
+        return None;
+    }
+    let mut roll = rng.random_range(0..total);
+    for (item, count) in items {
+        if roll < *count {
+            return Some(item);
+        }
+        roll -= count;
+    }
+    None
+}
+
+/// Split text into sentences at `.`, `!` or `?` followed by whitespace.
+fn split_sentences(text: &str) -> impl Iterator<Item = &str> {
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        let at_break = chars.peek().is_none_or(|(_, next)| next.is_whitespace());
+        if matches!(c, '.' | '!' | '?') && at_break {
+            sentences.push(&text[start..i + c.len_utf8()]);
+            start = i + c.len_utf8();
+        }
+    }
+    sentences.push(&text[start..]);
+    sentences
+        .into_iter()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+}
+
+/// FNV-1a hash of a sentence's words, ignoring case and end punctuation,
+/// stable across runs and platforms.
+pub fn sentence_hash(sentence: &str) -> u64 {
+    let normalized = sentence
+        .trim()
+        .trim_end_matches(['.', '!', '?'])
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase();
+    normalized
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325, |hash: u64, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    const REVIEWS: &[&str] = &[
+        "The blender works great for smoothies. Shipping was fast.",
+        "The blender works well for soups. Ordered from Mr Okonkwo.",
+        "The kettle works great for tea. Shipping was slow.",
+        "The kettle boils fast and works great for coffee.",
+        "Shipping was fast and the blender works great.",
+    ];
+
+    #[test]
+    fn test_train_skips_rare_words() {
+        let model = TextModel::train(REVIEWS.iter().copied(), TEXT_MODEL_ORDER).unwrap();
+        assert_eq!(model.sentences, (1, 2));
+        assert!(model.transitions.contains_key("blender works"));
+        let learned = serde_json::to_string(&model).unwrap();
+        assert!(!learned.contains("Okonkwo"), "{}", learned);
+        assert!(!learned.contains("smoothies"), "{}", learned);
+
+        assert!(TextModel::train(["One of a kind.", "Nothing alike here."], 2).is_none());
+    }
+
+    #[test]
+    fn test_generated_sentences_are_not_sampled_ones() {
+        let model = TextModel::train(REVIEWS.iter().copied(), TEXT_MODEL_ORDER).unwrap();
+        let sampled: HashSet<u64> = REVIEWS
+            .iter()
+            .flat_map(|r| split_sentences(r))
+            .map(sentence_hash)
+            .collect();
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        for _ in 0..100 {
+            let text = model.generate(&mut rng);
+            assert!(!text.is_empty());
+            for sentence in split_sentences(&text) {
+                assert!(!sampled.contains(&sentence_hash(sentence)), "{}", sentence);
+            }
+        }
+    }
+
+    #[test]
+    fn test_split_sentences() {
+        let sentences: Vec<&str> = split_sentences("Costs $3.50 now! Really? Yes. ").collect();
+        assert_eq!(sentences, vec!["Costs $3.50 now!", "Really?", "Yes."]);
+    }
+}
@@ -8,8 +8,29 @@
 //!
 //! Numeric aggregates (min, max, mean, stddev) are safe because they
 //! don't contain individual values.
+//!
+//! Instead of dropping PII values, [`pseudonymize_pii_distributions`] can
+//! replace each one with a synthetic value derived from a keyed hash
+//! (HMAC-SHA256) of it. The same production value always maps to the same
+//! pseudonym — in every table and on every run with the same key — so
+//! values that join across tables still join, while the key keeps the
+//! mapping from being reversed by hashing guesses. With
+//! [`Pseudonymizer::preserve_format`] pseudonyms keep the original's shape
+//! instead: digits stay digits, letters keep their case, length and
+//! punctuation are unchanged, emails keep their domain structure and ISO
+//! dates stay valid dates.
+
+use chrono::NaiveDateTime;
+use hmac::{Hmac, Mac};
+use rand::{Rng, SeedableRng};
+use sha2::Sha256;
 
 use super::stats::{ColumnDistribution, DistributionProfile};
+use crate::classify::rules::classify_column;
+use crate::classify::semantic::SemanticType;
+use crate::generate::providers::generate_value;
+use crate::generate::value::Value;
+use crate::schema::types::DataType;
 
 /// Known PII column name patterns.
 ///
@@ -89,6 +110,33 @@ const PII_PATTERNS: &[&str] = &[
     "mac_address",
 ];
 
+/// Kind of pseudonym generated for PII columns whose name contains the
+/// pattern, checked in order before the classifier. Names like
+/// `billing_email` that the classifier doesn't recognize get the same kind
+/// as `email`, so their values pseudonymize alike.
+const PSEUDONYM_KINDS: &[(&str, SemanticType)] = &[
+    ("email", SemanticType::Email),
+    ("e_mail", SemanticType::Email),
+    ("ip_address", SemanticType::IpAddress),
+    ("mac_address", SemanticType::MacAddress),
+    ("user_agent", SemanticType::UserAgent),
+    ("first_name", SemanticType::FirstName),
+    ("given_name", SemanticType::FirstName),
+    ("last_name", SemanticType::LastName),
+    ("family_name", SemanticType::LastName),
+    ("surname", SemanticType::LastName),
+    ("full_name", SemanticType::FullName),
+    ("phone", SemanticType::Phone),
+    ("mobile", SemanticType::Phone),
+    ("street", SemanticType::StreetAddress),
+    ("address", SemanticType::StreetAddress),
+    ("city", SemanticType::City),
+    ("zip", SemanticType::ZipCode),
+    ("postal", SemanticType::PostalCode),
+    ("birth", SemanticType::BirthDate),
+    ("dob", SemanticType::BirthDate),
+];
+
 /// Check if a column name matches known PII patterns.
 ///
 /// The check is case-insensitive and uses substring matching.
@@ -99,10 +147,11 @@ pub fn is_pii_column(name: &str) -> bool {
 
 /// Mask PII columns in distribution profiles.
 ///
-/// Removes `Categorical` distributions for columns that match PII patterns,
-/// since those distributions would contain actual production PII values.
-/// `Numeric` distributions are kept because aggregates (min/max/mean/stddev)
-/// don't reveal individual values. `Ratio` distributions are always safe.
+/// Removes `Categorical` distributions and text models for columns that
+/// match PII patterns, since those would contain actual production PII
+/// values or words.
+/// `Numeric` and `Histogram` distributions are kept because aggregates
+/// (min/max/mean/stddev, bucket shares) don't reveal individual values. `Ratio` distributions are always safe.
 ///
 /// Returns the number of distributions masked.
 pub fn mask_pii_distributions(profiles: &mut [DistributionProfile]) -> usize {
@@ -112,7 +161,11 @@ pub fn mask_pii_distributions(profiles: &mut [DistributionProfile]) -> usize {
             .column_distributions
             .iter()
             .filter(|(col_name, dist)| {
-                is_pii_column(col_name) && matches!(dist, ColumnDistribution::Categorical { .. })
+                is_pii_column(col_name)
+                    && matches!(
+                        dist,
+                        ColumnDistribution::Categorical { .. } | ColumnDistribution::Text { .. }
+                    )
             })
             .map(|(col_name, _)| col_name.clone())
             .collect();
@@ -125,6 +178,163 @@ pub fn mask_pii_distributions(profiles: &mut [DistributionProfile]) -> usize {
     masked_count
 }
 
+/// Maps production values to synthetic ones with a secret key.
+pub struct Pseudonymizer {
+    key: Vec<u8>,
+    preserve_format: bool,
+}
+
+impl Pseudonymizer {
+    pub fn new(key: impl AsRef<[u8]>) -> Self {
+        Self {
+            key: key.as_ref().to_vec(),
+            preserve_format: false,
+        }
+    }
+
+    /// Make pseudonyms keep the shape of the original values.
+    pub fn preserve_format(mut self) -> Self {
+        self.preserve_format = true;
+        self
+    }
+
+    /// The pseudonym of `value` in a column named `column`. The column name
+    /// only picks the kind of value generated (email, phone, name...), so
+    /// `users.email` and `orders.customer_email` map a value alike.
+    pub fn pseudonym(&self, column: &str, value: &str) -> String {
+        let digest = hmac_sha256(&self.key, value.as_bytes());
+        let seed = u64::from_be_bytes(digest[..8].try_into().expect("digest is 32 bytes"));
+        if self.preserve_format {
+            return mask_format(value, &mut rand::rngs::StdRng::seed_from_u64(seed));
+        }
+        let token = || self.token(value);
+
+        let lower = column.to_lowercase();
+        let semantic = PSEUDONYM_KINDS
+            .iter()
+            .find(|(pattern, _)| lower.contains(pattern))
+            .map(|(_, kind)| *kind)
+            .unwrap_or_else(|| classify_column(column, &DataType::Text, "", false, false, None));
+        if semantic == SemanticType::Unknown {
+            return token();
+        }
+        let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+        // The index keeps generators that add one (emails, usernames) from
+        // giving two values the same pseudonym
+        let row_index = (seed % 100_000) as usize;
+        match generate_value(semantic, &mut rng, row_index, &[], pseudonym_base_time()) {
+            Value::Null => token(),
+            generated => generated.to_string(),
+        }
+    }
+}
+
+impl Pseudonymizer {
+    /// An opaque keyed hash of `value`, like `anon_3f9a1c02b7e4`, for
+    /// values no realistic pseudonym fits.
+    pub fn token(&self, value: &str) -> String {
+        let digest = hmac_sha256(&self.key, value.as_bytes());
+        let hex: String = digest[8..14].iter().map(|b| format!("{:02x}", b)).collect();
+        format!("anon_{}", hex)
+    }
+}
+
+/// Replace `value` with a random one of the same shape.
+fn mask_format(value: &str, rng: &mut impl Rng) -> String {
+    if let Ok(date) = chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        let shifted = date + chrono::Duration::days(rng.random_range(-365..=365));
+        return shifted.format("%Y-%m-%d").to_string();
+    }
+    // Emails keep their dots and the top-level domain
+    if let Some((local, domain)) = value.rsplit_once('@') {
+        if let Some((name, tld)) = domain.rsplit_once('.') {
+            return format!(
+                "{}@{}.{}",
+                mask_chars(local, rng),
+                mask_chars(name, rng),
+                tld
+            );
+        }
+    }
+    mask_chars(value, rng)
+}
+
+/// Replace each digit with a digit and each ASCII letter with a letter of
+/// the same case, keeping everything else. A run of digits doesn't gain a
+/// leading zero it didn't have.
+fn mask_chars(value: &str, rng: &mut impl Rng) -> String {
+    let mut masked = String::with_capacity(value.len());
+    let mut previous_digit = false;
+    for c in value.chars() {
+        masked.push(match c {
+            '1'..='9' if !previous_digit => char::from(rng.random_range(b'1'..=b'9')),
+            '0'..='9' => char::from(rng.random_range(b'0'..=b'9')),
+            'a'..='z' => char::from(rng.random_range(b'a'..=b'z')),
+            'A'..='Z' => char::from(rng.random_range(b'A'..=b'Z')),
+            other => other,
+        });
+        previous_digit = c.is_ascii_digit();
+    }
+    masked
+}
+
+/// Fixed so date pseudonyms don't change between runs.
+fn pseudonym_base_time() -> NaiveDateTime {
+    chrono::NaiveDate::from_ymd_opt(2024, 1, 1)
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .expect("valid date")
+}
+
+/// HMAC-SHA256 (RFC 2104).
+fn hmac_sha256(key: &[u8], message: &[u8]) -> [u8; 32] {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC takes keys of any length");
+    mac.update(message);
+    mac.finalize().into_bytes().into()
+}
+
+/// Pseudonymize PII columns in distribution profiles.
+///
+/// Like [`mask_pii_distributions`], but `Categorical` distributions keep
+/// their frequencies with each value replaced by its pseudonym (values
+/// sharing a pseudonym are merged). Text models of PII columns are still
+/// removed, since they can't be pseudonymized value by value.
+///
+/// Returns the number of distributions pseudonymized or removed.
+pub fn pseudonymize_pii_distributions(
+    profiles: &mut [DistributionProfile],
+    pseudonymizer: &Pseudonymizer,
+) -> usize {
+    let mut masked_count = 0;
+    for profile in profiles.iter_mut() {
+        profile.column_distributions.retain(|col_name, dist| {
+            if !is_pii_column(col_name) {
+                return true;
+            }
+            match dist {
+                ColumnDistribution::Categorical { values } => {
+                    let mut pseudonymized: Vec<(String, f64)> = Vec::with_capacity(values.len());
+                    for (value, frequency) in values.drain(..) {
+                        let pseudonym = pseudonymizer.pseudonym(col_name, &value);
+                        match pseudonymized.iter_mut().find(|(v, _)| *v == pseudonym) {
+                            Some((_, total)) => *total += frequency,
+                            None => pseudonymized.push((pseudonym, frequency)),
+                        }
+                    }
+                    *values = pseudonymized;
+                    masked_count += 1;
+                    true
+                }
+                ColumnDistribution::Text { .. } => {
+                    masked_count += 1;
+                    false
+                }
+                _ => true,
+            }
+        });
+    }
+    masked_count
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -179,6 +389,13 @@ mod tests {
         let mut profiles = vec![DistributionProfile {
             table_name: "users".to_string(),
             row_count: 1000,
+            unique_pairs: Vec::new(),
+            text_profiles: HashMap::new(),
+            null_rates: HashMap::new(),
+            distinct_counts: HashMap::new(),
+            correlations: Vec::new(),
+            temporal_profiles: HashMap::new(),
+            fan_outs: HashMap::new(),
             column_distributions: {
                 let mut m = HashMap::new();
                 m.insert(
@@ -217,6 +434,13 @@ mod tests {
         let mut profiles = vec![DistributionProfile {
             table_name: "users".to_string(),
             row_count: 1000,
+            unique_pairs: Vec::new(),
+            text_profiles: HashMap::new(),
+            null_rates: HashMap::new(),
+            distinct_counts: HashMap::new(),
+            correlations: Vec::new(),
+            temporal_profiles: HashMap::new(),
+            fan_outs: HashMap::new(),
             column_distributions: {
                 let mut m = HashMap::new();
                 // Numeric distribution for a PII-like column name
@@ -227,6 +451,7 @@ mod tests {
                         max: 9999999999.0,
                         mean: 5000000000.0,
                         stddev: 2000000000.0,
+                        quantiles: Vec::new(),
                     },
                 );
                 m
@@ -246,6 +471,13 @@ mod tests {
         let mut profiles = vec![DistributionProfile {
             table_name: "users".to_string(),
             row_count: 500,
+            unique_pairs: Vec::new(),
+            text_profiles: HashMap::new(),
+            null_rates: HashMap::new(),
+            distinct_counts: HashMap::new(),
+            correlations: Vec::new(),
+            temporal_profiles: HashMap::new(),
+            fan_outs: HashMap::new(),
             column_distributions: {
                 let mut m = HashMap::new();
                 m.insert(
@@ -281,4 +513,115 @@ mod tests {
         assert_eq!(profiles[0].column_distributions.len(), 1);
         assert!(profiles[0].column_distributions.contains_key("role"));
     }
+
+    #[test]
+    fn test_hmac_sha256_matches_rfc_4231() {
+        // Test case 2
+        let mac = hmac_sha256(b"Jefe", b"what do ya want for nothing?");
+        let hex: String = mac.iter().map(|b| format!("{:02x}", b)).collect();
+        assert_eq!(
+            hex,
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn test_pseudonyms_are_keyed_and_consistent() {
+        let key = Pseudonymizer::new("s3cret");
+        let email = key.pseudonym("email", "alice@corp.example");
+        assert!(email.contains('@'), "{}", email);
+        assert_ne!(email, "alice@corp.example");
+        assert_eq!(email, key.pseudonym("email", "alice@corp.example"));
+        assert_eq!(
+            email,
+            Pseudonymizer::new("s3cret").pseudonym("customer_email", "alice@corp.example")
+        );
+        assert_ne!(email, key.pseudonym("email", "bob@corp.example"));
+        assert_ne!(
+            email,
+            Pseudonymizer::new("other").pseudonym("email", "alice@corp.example")
+        );
+        assert!(key.pseudonym("passport", "X1234567").starts_with("anon_"));
+    }
+
+    #[test]
+    fn test_pseudonymize_keeps_frequencies() {
+        let categorical = |values: &[(&str, f64)]| ColumnDistribution::Categorical {
+            values: values.iter().map(|(v, f)| (v.to_string(), *f)).collect(),
+        };
+        let profile = |table: &str, column: &str| DistributionProfile {
+            table_name: table.to_string(),
+            row_count: 100,
+            unique_pairs: Vec::new(),
+            text_profiles: HashMap::new(),
+            null_rates: HashMap::new(),
+            distinct_counts: HashMap::new(),
+            correlations: Vec::new(),
+            temporal_profiles: HashMap::new(),
+            fan_outs: HashMap::new(),
+            column_distributions: HashMap::from([
+                (
+                    column.to_string(),
+                    categorical(&[("ann@corp.example", 0.7), ("bo@corp.example", 0.3)]),
+                ),
+                ("role".to_string(), categorical(&[("admin", 1.0)])),
+            ]),
+        };
+        let mut profiles = vec![
+            profile("users", "email"),
+            profile("invoices", "billing_email"),
+        ];
+
+        let masked = pseudonymize_pii_distributions(&mut profiles, &Pseudonymizer::new("k"));
+        assert_eq!(masked, 2);
+        let values = |i: usize, column: &str| match &profiles[i].column_distributions[column] {
+            ColumnDistribution::Categorical { values } => values.clone(),
+            other => panic!("unexpected {:?}", other),
+        };
+        let users = values(0, "email");
+        assert_eq!(users.len(), 2);
+        assert_eq!(users[0].1, 0.7);
+        assert!(users.iter().all(|(v, _)| !v.ends_with("corp.example")));
+        assert_eq!(users, values(1, "billing_email"));
+        assert_eq!(values(0, "role"), vec![("admin".to_string(), 1.0)]);
+    }
+
+    #[test]
+    fn test_format_preserving_pseudonyms() {
+        let key = Pseudonymizer::new("s3cret").preserve_format();
+        let shape = |s: &str| -> String {
+            s.chars()
+                .map(|c| match c {
+                    '0'..='9' => '9',
+                    'a'..='z' => 'a',
+                    'A'..='Z' => 'A',
+                    other => other,
+                })
+                .collect()
+        };
+
+        for (column, value) in [
+            ("phone", "+1 (415) 555-0132"),
+            ("ssn", "078-05-1120"),
+            ("postal_code", "SW1A 1AA"),
+            ("card_number", "4111 1111 1111 1111"),
+        ] {
+            let masked = key.pseudonym(column, value);
+            assert_ne!(masked, value);
+            assert_eq!(shape(&masked), shape(value), "{} -> {}", value, masked);
+            assert_eq!(masked, key.pseudonym(column, value));
+        }
+
+        let email = key.pseudonym("email", "Jane.Doe@mail.corp.example");
+        assert_eq!(shape(&email), shape("Jane.Doe@mail.corp.example"));
+        assert!(email.ends_with(".example"), "{}", email);
+        assert!(!email.contains("Jane"));
+
+        let dob = key.pseudonym("date_of_birth", "1987-02-28");
+        assert!(
+            chrono::NaiveDate::parse_from_str(&dob, "%Y-%m-%d").is_ok(),
+            "{}",
+            dob
+        );
+    }
 }
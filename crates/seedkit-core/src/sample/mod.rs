@@ -1,15 +1,22 @@
 //! # Smart Sampling
 //!
 //! Connects to a production read-only replica and extracts statistical
-//! distributions (value frequencies, numeric ranges, row count ratios)
-//! without copying actual data. The generation engine then uses these
-//! distributions to produce synthetic data that mirrors production patterns.
+//! distributions (value frequencies, numeric ranges, row count ratios, the
+//! structure of JSON documents) without copying actual data. The generation
+//! engine then uses these distributions to produce synthetic data that
+//! mirrors production patterns.
 
 use std::path::Path;
 
 use crate::error::{Result, SeedKitError};
+use crate::schema::types::{DatabaseSchema, UniqueConstraint};
 
+pub mod audit;
+pub mod markov;
 pub mod mask;
+pub mod privacy;
+pub mod progress;
+pub mod scan;
 pub mod stats;
 
 /// Default filename for saved distribution profiles.
@@ -40,6 +47,41 @@ pub fn load_profiles(path: &Path) -> Result<Vec<stats::DistributionProfile>> {
     Ok(profiles)
 }
 
+/// Turn sampled unique-pair hints into composite unique constraints on the
+/// schema, so the generation engine enforces them like declared ones.
+///
+/// Pairs that reference unknown tables/columns or duplicate an existing
+/// constraint are ignored. Returns the number of constraints added.
+pub fn apply_unique_pair_hints(
+    schema: &mut DatabaseSchema,
+    profiles: &[stats::DistributionProfile],
+) -> usize {
+    let mut added = 0;
+    for profile in profiles {
+        let table = match schema.tables.get_mut(&profile.table_name) {
+            Some(t) => t,
+            None => continue,
+        };
+        for (col_a, col_b) in &profile.unique_pairs {
+            if !table.columns.contains_key(col_a) || !table.columns.contains_key(col_b) {
+                continue;
+            }
+            let exists = table.unique_constraints.iter().any(|uc| {
+                uc.columns.len() == 2 && uc.columns.contains(col_a) && uc.columns.contains(col_b)
+            });
+            if exists {
+                continue;
+            }
+            table.unique_constraints.push(UniqueConstraint {
+                name: Some(format!("sampled_{}_{}_key", col_a, col_b)),
+                columns: vec![col_a.clone(), col_b.clone()],
+            });
+            added += 1;
+        }
+    }
+    added
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -52,6 +94,13 @@ mod tests {
             DistributionProfile {
                 table_name: "users".to_string(),
                 row_count: 1000,
+                unique_pairs: Vec::new(),
+                text_profiles: HashMap::new(),
+                null_rates: HashMap::new(),
+                distinct_counts: HashMap::new(),
+                correlations: Vec::new(),
+                temporal_profiles: HashMap::new(),
+                fan_outs: HashMap::new(),
                 column_distributions: {
                     let mut m = HashMap::new();
                     m.insert(
@@ -71,6 +120,7 @@ mod tests {
                             max: 90.0,
                             mean: 35.0,
                             stddev: 12.5,
+                            quantiles: Vec::new(),
                         },
                     );
                     m
@@ -79,6 +129,13 @@ mod tests {
             DistributionProfile {
                 table_name: "orders".to_string(),
                 row_count: 3200,
+                unique_pairs: Vec::new(),
+                text_profiles: HashMap::new(),
+                null_rates: HashMap::new(),
+                distinct_counts: HashMap::new(),
+                correlations: Vec::new(),
+                temporal_profiles: HashMap::new(),
+                fan_outs: HashMap::new(),
                 column_distributions: {
                     let mut m = HashMap::new();
                     m.insert(
@@ -122,4 +179,43 @@ mod tests {
         let loaded = load_profiles(&path).unwrap();
         assert!(loaded.is_empty());
     }
+
+    #[test]
+    fn test_apply_unique_pair_hints() {
+        use crate::schema::types::*;
+
+        let mut schema = DatabaseSchema::new(DatabaseType::PostgreSQL, "test".to_string());
+        let mut table = Table::new("memberships".to_string());
+        for name in ["user_id", "org_id"] {
+            table.columns.insert(
+                name.to_string(),
+                Column::new(name.to_string(), DataType::Integer, "integer".to_string()),
+            );
+        }
+        schema.tables.insert("memberships".to_string(), table);
+
+        let profiles = vec![DistributionProfile {
+            table_name: "memberships".to_string(),
+            row_count: 100,
+            unique_pairs: vec![
+                ("user_id".to_string(), "org_id".to_string()),
+                ("user_id".to_string(), "missing".to_string()),
+            ],
+            text_profiles: HashMap::new(),
+            null_rates: HashMap::new(),
+            distinct_counts: HashMap::new(),
+            correlations: Vec::new(),
+            temporal_profiles: HashMap::new(),
+            fan_outs: HashMap::new(),
+            column_distributions: HashMap::new(),
+        }];
+
+        assert_eq!(apply_unique_pair_hints(&mut schema, &profiles), 1);
+        let ucs = &schema.tables["memberships"].unique_constraints;
+        assert_eq!(ucs.len(), 1);
+        assert_eq!(ucs[0].columns, vec!["user_id", "org_id"]);
+
+        // Applying again is a no-op
+        assert_eq!(apply_unique_pair_hints(&mut schema, &profiles), 0);
+    }
 }
@@ -0,0 +1,457 @@
+//! # Differential Privacy
+//!
+//! `seedkit sample --epsilon <ε>` adds Laplace noise to every statistic
+//! before the profiles are written, so each table's profile is
+//! (ε, δ)-differentially private with respect to adding or removing one of
+//! its rows, with δ = [`DELTA`]. Each table's budget is split evenly over its
+//! row count, its distributions, its NULL rates, its distinct counts, its
+//! temporal profiles' hour, weekday and month counts and its fan-outs:
+//!
+//! - **Row counts**, **categorical counts**, **histogram counts**, **NULL
+//!   counts**, **temporal counts** (frequency × rows) and **distinct
+//!   counts** get noise of scale 1/ε. Categories whose noisy count falls
+//!   below a threshold are dropped, so a rare category's presence doesn't
+//!   give anyone away; the threshold is what costs δ.
+//! - **Fan-outs** (parents per child count) are first grouped into
+//!   power-of-two buckets of child counts (1, 2, 3–4, 5–8, ...), so an
+//!   unusual count isn't published as is, then get noise of scale 2/ε: one
+//!   child row moves its parent between two buckets. Buckets below the
+//!   threshold are dropped, like rare categories.
+//! - **Numeric** mean and stddev get noise scaled to the column's range over
+//!   the row count. The range is public: the bounds of the column's CHECK
+//!   constraints or, for integers narrower than 32 bits, of its type. The
+//!   sampled min and max are never published; a numeric column without
+//!   public bounds is dropped and generated as if it hadn't been sampled.
+//! - **Ratios** get noise scaled to one row of the related table.
+//! - Text models, JSON shapes, histograms (whose buckets are cut at the
+//!   sampled min and max), numeric quantiles, text profiles, correlations
+//!   and detected unique pairs can't be noised and are dropped.
+//!
+//! Smaller ε means stronger privacy and noisier profiles; 1.0 is a common
+//! starting point.
+
+use std::collections::BTreeMap;
+
+use rand::Rng;
+
+use super::stats::{ColumnDistribution, DistributionProfile};
+use crate::error::{Result, SeedKitError};
+use crate::generate::plan::check_applies_to_column;
+use crate::generate::providers::compute_numeric_bounds_f64;
+use crate::schema::types::{DatabaseSchema, ParsedCheck, Table};
+
+/// Probability the guarantee doesn't hold, spent on dropping rare
+/// categories.
+pub const DELTA: f64 = 1e-6;
+
+/// Draw from a Laplace distribution centred on zero.
+fn laplace(scale: f64, rng: &mut impl Rng) -> f64 {
+    let u: f64 = rng.random_range(-0.5..0.5);
+    -scale * u.signum() * (1.0 - 2.0 * u.abs()).ln()
+}
+
+/// Noisy counts below this, for noise of `scale`, are dropped: a count
+/// this far above zero comes from noise alone with probability `DELTA`.
+fn threshold(scale: f64) -> f64 {
+    1.0 + scale * (1.0 / (2.0 * DELTA)).ln()
+}
+
+/// The range of `column` known without looking at its rows: its CHECK
+/// bounds, within its type's range for integers narrower than 32 bits.
+/// None unless both ends are bounded.
+fn public_bounds(table: &Table, column: &str) -> Option<(f64, f64)> {
+    let (type_min, type_max) = table
+        .columns
+        .get(column)?
+        .integer_bounds()
+        .filter(|&(min, max)| max - min < 1 << 24)
+        .map_or((f64::NEG_INFINITY, f64::INFINITY), |(min, max)| {
+            (min as f64, max as f64)
+        });
+    let checks: Vec<ParsedCheck> = table
+        .check_constraints
+        .iter()
+        .filter_map(|cc| cc.parsed.as_ref())
+        .flat_map(ParsedCheck::conjuncts)
+        .filter(|p| check_applies_to_column(p, column))
+        .cloned()
+        .collect();
+    let (min, max) = compute_numeric_bounds_f64(type_min, type_max, &checks);
+    (min.is_finite() && max.is_finite()).then_some((min, max))
+}
+
+/// The child count standing for `children`'s power-of-two bucket: 1 and 2
+/// as they are, then the middle of 3–4, 5–8, 9–16 and so on.
+fn fan_out_bucket(children: u64) -> u64 {
+    if children <= 2 {
+        return children;
+    }
+    let high = children.next_power_of_two();
+    let low = high / 2 + 1;
+    (low + high) / 2
+}
+
+/// Add noise to shares of `rows` as counts, and turn them back into shares
+/// of `noisy_rows`.
+fn noise_shares(shares: &mut [f64], rows: f64, noisy_rows: f64, share: f64, rng: &mut impl Rng) {
+    for value in shares.iter_mut() {
+        let count = (*value * rows + laplace(1.0 / share, rng)).max(0.0);
+        *value = count / noisy_rows.max(1.0);
+    }
+}
+
+/// Add noise to every profile for an ε-differential privacy budget per
+/// table, taking numeric bounds from `schema`.
+///
+/// Returns the number of distributions dropped because they can't be
+/// noised, because a numeric column has no public bounds or, for
+/// categorical ones, because every category fell below the threshold.
+pub fn apply_differential_privacy(
+    profiles: &mut [DistributionProfile],
+    schema: &DatabaseSchema,
+    epsilon: f64,
+    rng: &mut impl Rng,
+) -> Result<usize> {
+    if !epsilon.is_finite() || epsilon <= 0.0 {
+        return Err(SeedKitError::Config {
+            message: format!("epsilon must be a positive number, got {}", epsilon),
+        });
+    }
+    let row_counts: Vec<(String, u64)> = profiles
+        .iter()
+        .map(|p| (p.table_name.clone(), p.row_count))
+        .collect();
+
+    let mut dropped = 0;
+    for profile in profiles.iter_mut() {
+        let table = schema.tables.get(&profile.table_name);
+        let before = profile.column_distributions.len();
+        profile
+            .column_distributions
+            .retain(|column, dist| match dist {
+                ColumnDistribution::Categorical { .. } | ColumnDistribution::Ratio { .. } => true,
+                ColumnDistribution::Numeric { .. } => {
+                    table.and_then(|t| public_bounds(t, column)).is_some()
+                }
+                _ => false,
+            });
+        dropped += before - profile.column_distributions.len();
+        profile.unique_pairs.clear();
+        profile.text_profiles.clear();
+        profile.correlations.clear();
+
+        let share = epsilon
+            / (profile.column_distributions.len()
+                + profile.null_rates.len()
+                + profile.distinct_counts.len()
+                + 3 * profile.temporal_profiles.len()
+                + profile.fan_outs.len()
+                + 1) as f64;
+        let rows = profile.row_count as f64;
+        let noisy_rows = (rows + laplace(1.0 / share, rng)).round().max(0.0);
+        profile.row_count = noisy_rows as u64;
+
+        for rate in profile.null_rates.values_mut() {
+            let count = *rate * rows + laplace(1.0 / share, rng);
+            *rate = (count / noisy_rows.max(1.0)).clamp(0.0, 1.0);
+        }
+        for distinct in profile.distinct_counts.values_mut() {
+            let count = *distinct as f64 + laplace(1.0 / share, rng);
+            *distinct = count.round().clamp(0.0, noisy_rows) as u64;
+        }
+        for temporal in profile.temporal_profiles.values_mut() {
+            for shares in [
+                &mut temporal.hours,
+                &mut temporal.weekdays,
+                &mut temporal.months,
+            ] {
+                noise_shares(shares, rows, noisy_rows, share, rng);
+            }
+        }
+        for fan_out in profile.fan_outs.values_mut() {
+            let mut buckets: BTreeMap<u64, u64> = BTreeMap::new();
+            for (children, parents) in fan_out.drain(..) {
+                *buckets.entry(fan_out_bucket(children)).or_default() += parents;
+            }
+            *fan_out = buckets
+                .into_iter()
+                .filter_map(|(children, parents)| {
+                    let count = (parents as f64 + laplace(2.0 / share, rng)).round();
+                    (count >= threshold(2.0 / share)).then_some((children, count as u64))
+                })
+                .collect();
+        }
+        profile.fan_outs.retain(|_, fan_out| !fan_out.is_empty());
+
+        profile.column_distributions.retain(|column, dist| {
+            match dist {
+                ColumnDistribution::Categorical { values } => {
+                    let threshold = threshold(1.0 / share);
+                    let noisy: Vec<(String, f64)> = values
+                        .drain(..)
+                        .map(|(value, frequency)| {
+                            (value, frequency * rows + laplace(1.0 / share, rng))
+                        })
+                        .filter(|(_, count)| *count >= threshold)
+                        .collect();
+                    *values = noisy
+                        .into_iter()
+                        .map(|(value, count)| (value, count / noisy_rows.max(1.0)))
+                        .collect();
+                    if values.is_empty() {
+                        dropped += 1;
+                        return false;
+                    }
+                }
+                ColumnDistribution::Numeric {
+                    min,
+                    max,
+                    mean,
+                    stddev,
+                    quantiles,
+                } => {
+                    quantiles.clear();
+                    // Kept above only with public bounds
+                    let Some((low, high)) = table.and_then(|t| public_bounds(t, column)) else {
+                        return false;
+                    };
+                    (*min, *max) = (low, high);
+                    let range = *max - *min;
+                    // Half the share each for the mean and the stddev
+                    let n = rows.max(1.0);
+                    *mean = (*mean + laplace(2.0 * range / (n * share), rng)).clamp(*min, *max);
+                    *stddev = (*stddev + laplace(2.0 * range / (n.sqrt() * share), rng))
+                        .clamp(0.0, range);
+                }
+                ColumnDistribution::Ratio {
+                    related_table,
+                    ratio,
+                } => {
+                    let Some(&(_, related_rows)) =
+                        row_counts.iter().find(|(table, _)| table == related_table)
+                    else {
+                        dropped += 1;
+                        return false;
+                    };
+                    let scale = 1.0 / ((related_rows.max(1) as f64) * share);
+                    *ratio = (*ratio + laplace(scale, rng)).max(0.0);
+                }
+                _ => {}
+            }
+            true
+        });
+    }
+    Ok(dropped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sample::markov::{TextModel, TEXT_MODEL_ORDER};
+    use crate::sample::stats::TemporalProfile;
+    use crate::schema::types::{CheckConstraint, Column, DataType, DatabaseType};
+    use rand::SeedableRng;
+    use std::collections::HashMap;
+
+    fn profiles() -> Vec<DistributionProfile> {
+        let users = DistributionProfile {
+            table_name: "users".to_string(),
+            row_count: 100_000,
+            unique_pairs: vec![("org_id".to_string(), "email".to_string())],
+            text_profiles: HashMap::new(),
+            null_rates: HashMap::from([("nickname".to_string(), 0.3)]),
+            distinct_counts: HashMap::from([("nickname".to_string(), 40_000)]),
+            correlations: Vec::new(),
+            temporal_profiles: HashMap::from([(
+                "created_at".to_string(),
+                TemporalProfile {
+                    hours: vec![1.0 / 24.0; 24],
+                    weekdays: vec![1.0 / 7.0; 7],
+                    months: vec![1.0 / 12.0; 12],
+                },
+            )]),
+            fan_outs: HashMap::new(),
+            column_distributions: HashMap::from([
+                (
+                    "plan".to_string(),
+                    ColumnDistribution::Categorical {
+                        values: vec![
+                            ("free".to_string(), 0.8),
+                            ("pro".to_string(), 0.19999),
+                            ("enterprise".to_string(), 0.00001),
+                        ],
+                    },
+                ),
+                (
+                    "age".to_string(),
+                    ColumnDistribution::Numeric {
+                        min: 18.0,
+                        max: 93.0,
+                        mean: 41.2,
+                        stddev: 12.5,
+                        quantiles: vec![(0.5, 39.0), (0.99, 88.0)],
+                    },
+                ),
+                (
+                    "score".to_string(),
+                    ColumnDistribution::Numeric {
+                        min: 0.5,
+                        max: 7.25,
+                        mean: 3.1,
+                        stddev: 1.2,
+                        quantiles: Vec::new(),
+                    },
+                ),
+                (
+                    "bio".to_string(),
+                    ColumnDistribution::Text {
+                        model: TextModel::train(
+                            ["Likes tea and cats.", "Likes tea and dogs."],
+                            TEXT_MODEL_ORDER,
+                        )
+                        .unwrap(),
+                    },
+                ),
+            ]),
+        };
+        let orders = DistributionProfile {
+            table_name: "orders".to_string(),
+            row_count: 320_000,
+            unique_pairs: Vec::new(),
+            text_profiles: HashMap::new(),
+            null_rates: HashMap::new(),
+            distinct_counts: HashMap::new(),
+            correlations: Vec::new(),
+            temporal_profiles: HashMap::new(),
+            fan_outs: HashMap::from([(
+                "user_id".to_string(),
+                vec![(1, 20_000), (3, 10_000), (4, 5_000), (500, 2)],
+            )]),
+            column_distributions: HashMap::from([
+                (
+                    "user_id".to_string(),
+                    ColumnDistribution::Ratio {
+                        related_table: "users".to_string(),
+                        ratio: 3.2,
+                    },
+                ),
+                (
+                    "coupon_id".to_string(),
+                    ColumnDistribution::Ratio {
+                        related_table: "coupons".to_string(),
+                        ratio: 0.4,
+                    },
+                ),
+            ]),
+        };
+        vec![users, orders]
+    }
+
+    fn schema() -> DatabaseSchema {
+        let mut schema = DatabaseSchema::new(DatabaseType::PostgreSQL, "test".to_string());
+        let mut users = Table::new("users".to_string());
+        for (name, data_type) in [("age", DataType::Integer), ("score", DataType::Double)] {
+            let column = Column::new(name.to_string(), data_type, "numeric".to_string());
+            users.columns.insert(name.to_string(), column);
+        }
+        users.check_constraints.push(CheckConstraint {
+            name: None,
+            expression: "age BETWEEN 0 AND 150".to_string(),
+            parsed: Some(ParsedCheck::Between {
+                column: "age".to_string(),
+                low: 0.0,
+                high: 150.0,
+            }),
+        });
+        schema.tables.insert("users".to_string(), users);
+        schema
+    }
+
+    #[test]
+    fn test_laplace_is_centred_with_the_right_spread() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        let draws: Vec<f64> = (0..20_000).map(|_| laplace(2.0, &mut rng)).collect();
+        let mean = draws.iter().sum::<f64>() / draws.len() as f64;
+        let mean_abs = draws.iter().map(|d| d.abs()).sum::<f64>() / draws.len() as f64;
+        assert!(mean.abs() < 0.1, "{}", mean);
+        assert!((mean_abs - 2.0).abs() < 0.1, "{}", mean_abs);
+    }
+
+    #[test]
+    fn test_public_bounds() {
+        let schema = schema();
+        let users = &schema.tables["users"];
+        assert_eq!(public_bounds(users, "age"), Some((0.0, 150.0)));
+        assert_eq!(public_bounds(users, "score"), None);
+
+        let level = Column::new("level".to_string(), DataType::SmallInt, "int2".into());
+        let mut table = Table::new("players".to_string());
+        table.columns.insert("level".to_string(), level);
+        assert_eq!(public_bounds(&table, "level"), Some((-32768.0, 32767.0)));
+    }
+
+    #[test]
+    fn test_fan_out_bucket() {
+        let buckets: Vec<u64> = [0, 1, 2, 3, 4, 5, 8, 9, 500].map(fan_out_bucket).to_vec();
+        assert_eq!(buckets, vec![0, 1, 2, 3, 3, 6, 6, 12, 384]);
+    }
+
+    #[test]
+    fn test_apply_differential_privacy() {
+        let mut profiles = profiles();
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+        let dropped = apply_differential_privacy(&mut profiles, &schema(), 1.0, &mut rng).unwrap();
+        // The text model, the unbounded score and the ratio to an
+        // unprofiled table
+        assert_eq!(dropped, 3);
+        assert!(!profiles[0].column_distributions.contains_key("score"));
+
+        let users = &profiles[0];
+        assert!(users.unique_pairs.is_empty());
+        assert!(users.row_count.abs_diff(100_000) < 100);
+        assert!((users.null_rates["nickname"] - 0.3).abs() < 0.01);
+        assert!(users.distinct_counts["nickname"].abs_diff(40_000) < 100);
+        let temporal = &users.temporal_profiles["created_at"];
+        assert_eq!(temporal.hours.len(), 24);
+        assert!(temporal.hours.iter().all(|h| (h - 1.0 / 24.0).abs() < 0.01));
+        match &users.column_distributions["plan"] {
+            ColumnDistribution::Categorical { values } => {
+                let names: Vec<&str> = values.iter().map(|(v, _)| v.as_str()).collect();
+                assert_eq!(names, vec!["free", "pro"], "one-row category dropped");
+                assert!((values[0].1 - 0.8).abs() < 0.01);
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        match users.column_distributions["age"] {
+            ColumnDistribution::Numeric {
+                min,
+                max,
+                mean,
+                stddev,
+                ref quantiles,
+            } => {
+                assert_eq!(
+                    (min, max),
+                    (0.0, 150.0),
+                    "public bounds, not the sampled ones"
+                );
+                assert!(quantiles.is_empty());
+                assert!((mean - 41.2).abs() < 1.0);
+                assert!((stddev - 12.5).abs() < 8.0);
+            }
+            ref other => panic!("unexpected {:?}", other),
+        }
+        match &profiles[1].column_distributions["user_id"] {
+            ColumnDistribution::Ratio { ratio, .. } => assert!((ratio - 3.2).abs() < 0.01),
+            other => panic!("unexpected {:?}", other),
+        }
+        let fan_out = &profiles[1].fan_outs["user_id"];
+        let children: Vec<u64> = fan_out.iter().map(|&(children, _)| children).collect();
+        assert_eq!(children, vec![1, 3], "the 500-children bucket is too rare");
+        assert!(fan_out[0].1.abs_diff(20_000) < 100);
+        assert!(fan_out[1].1.abs_diff(15_000) < 100);
+
+        assert!(apply_differential_privacy(&mut profiles, &schema(), 0.0, &mut rng).is_err());
+        assert!(apply_differential_privacy(&mut profiles, &schema(), f64::NAN, &mut rng).is_err());
+    }
+}
@@ -0,0 +1,274 @@
+//! # Resumable Sampling
+//!
+//! Profiling every table of a large schema can take hours. With a progress
+//! file (`seedkit sample --progress-file`), each table's profile is saved
+//! as soon as it is extracted, so an interrupted run picks up where it
+//! stopped:
+//!
+//! - a saved table is reused while its row count stays within the resample
+//!   threshold (10% by default) of the count it was profiled at and its
+//!   columns are unchanged;
+//! - changing any setting that shapes profiles (quantiles, categorical
+//!   limit, ...) discards the saved progress.
+//!
+//! Saved profiles are taken before PII masking, differential privacy and
+//! the k-anonymity audit, which are applied to every run's output again.
+//! The file therefore holds unmasked statistics: it is only written when
+//! asked for, and deleted once a run has sampled every table.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::stats::DistributionProfile;
+use crate::error::{Result, SeedKitError};
+use crate::schema::types::Table;
+
+/// Default filename for sampling progress.
+pub const PROGRESS_FILE_NAME: &str = "seedkit.sample-progress.json";
+
+/// Relative change in a table's row count above which a saved profile is
+/// re-sampled.
+pub const DEFAULT_RESAMPLE_THRESHOLD: f64 = 0.1;
+
+/// Per-table profiles saved by earlier (possibly interrupted) runs.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SampleProgress {
+    /// Settings the profiles were sampled with (see
+    /// `SampleOptions::fingerprint`).
+    pub settings: String,
+    pub tables: BTreeMap<String, TableProgress>,
+    /// Where progress is saved after each table; `None` keeps it in memory.
+    #[serde(skip)]
+    path: Option<PathBuf>,
+}
+
+/// A table's saved profile.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TableProgress {
+    /// The table's columns and types when it was profiled.
+    pub columns: Vec<String>,
+    pub profile: DistributionProfile,
+}
+
+impl SampleProgress {
+    /// Progress kept in memory only.
+    pub fn new(settings: String) -> Self {
+        Self {
+            settings,
+            ..Self::default()
+        }
+    }
+
+    /// Load the progress saved at `path`, saving there from now on. A
+    /// missing file, or one sampled with other `settings`, starts over.
+    pub fn load(path: &Path, settings: String) -> Result<Self> {
+        let mut progress = match fs::read_to_string(path) {
+            Ok(contents) => {
+                serde_json::from_str::<Self>(&contents).map_err(|e| SeedKitError::Config {
+                    message: format!(
+                        "Failed to parse sampling progress {}: {} (delete it to start over)",
+                        path.display(),
+                        e
+                    ),
+                })?
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Self::default(),
+            Err(e) => {
+                return Err(SeedKitError::Output {
+                    message: format!("Failed to read sampling progress from {}", path.display()),
+                    source: e,
+                })
+            }
+        };
+        if progress.settings != settings {
+            if !progress.tables.is_empty() {
+                tracing::info!(
+                    "Sampling settings changed since {} was saved; re-sampling every table",
+                    path.display()
+                );
+            }
+            progress = Self::new(settings);
+        }
+        progress.path = Some(path.to_path_buf());
+        Ok(progress)
+    }
+
+    /// Start over, saving to `path` from now on.
+    pub fn fresh(path: &Path, settings: String) -> Self {
+        Self {
+            path: Some(path.to_path_buf()),
+            ..Self::new(settings)
+        }
+    }
+
+    /// Whether a profile of `table_name` was saved.
+    pub fn contains(&self, table_name: &str) -> bool {
+        self.tables.contains_key(table_name)
+    }
+
+    /// The saved profile of `table`, if its columns are unchanged and
+    /// `row_count` is within `threshold` of the count it was profiled at.
+    pub fn reuse(
+        &self,
+        table: &Table,
+        row_count: u64,
+        threshold: f64,
+    ) -> Option<&DistributionProfile> {
+        let saved = self.tables.get(&table.name)?;
+        if saved.columns != table_columns(table)
+            || row_count_changed(saved.profile.row_count, row_count, threshold)
+        {
+            return None;
+        }
+        Some(&saved.profile)
+    }
+
+    /// Save a freshly extracted profile of `table`.
+    pub fn record(&mut self, table: &Table, profile: &DistributionProfile) -> Result<()> {
+        self.tables.insert(
+            table.name.clone(),
+            TableProgress {
+                columns: table_columns(table),
+                profile: profile.clone(),
+            },
+        );
+        match &self.path {
+            Some(path) => self.save(path),
+            None => Ok(()),
+        }
+    }
+
+    /// Delete the progress file, once every table is sampled.
+    pub fn remove(&self) -> Result<()> {
+        let Some(ref path) = self.path else {
+            return Ok(());
+        };
+        match fs::remove_file(path) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(SeedKitError::Output {
+                message: format!("Failed to delete sampling progress {}", path.display()),
+                source: e,
+            }),
+        }
+    }
+
+    /// Write the progress to `path` atomically, so an interrupted run
+    /// never leaves a half-written file.
+    fn save(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string(self).map_err(|e| SeedKitError::Config {
+            message: format!("Failed to serialize sampling progress: {}", e),
+        })?;
+        let dir = path.parent().unwrap_or(Path::new("."));
+        let tmp_path = dir.join(".seedkit.sample-progress.tmp");
+        fs::write(&tmp_path, json).map_err(|e| SeedKitError::Output {
+            message: format!(
+                "Failed to write sampling progress to {}",
+                tmp_path.display()
+            ),
+            source: e,
+        })?;
+        fs::rename(&tmp_path, path).map_err(|e| SeedKitError::Output {
+            message: format!(
+                "Failed to rename {} → {}",
+                tmp_path.display(),
+                path.display()
+            ),
+            source: e,
+        })
+    }
+}
+
+/// A table's columns and declared types, sorted.
+fn table_columns(table: &Table) -> Vec<String> {
+    let mut columns: Vec<String> = table
+        .columns
+        .values()
+        .map(|c| format!("{} {}", c.name, c.raw_type))
+        .collect();
+    columns.sort();
+    columns
+}
+
+/// Whether a row count moved by more than `threshold` of what it was.
+pub fn row_count_changed(saved: u64, current: u64, threshold: f64) -> bool {
+    current.abs_diff(saved) as f64 > threshold * saved.max(1) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::types::{Column, DataType};
+    use std::collections::HashMap;
+
+    fn orders() -> Table {
+        let mut table = Table::new("orders".to_string());
+        table.columns.insert(
+            "total".to_string(),
+            Column::new(
+                "total".to_string(),
+                DataType::Numeric,
+                "numeric".to_string(),
+            ),
+        );
+        table
+    }
+
+    fn profile(row_count: u64) -> DistributionProfile {
+        DistributionProfile {
+            table_name: "orders".to_string(),
+            row_count,
+            unique_pairs: Vec::new(),
+            text_profiles: HashMap::new(),
+            null_rates: HashMap::new(),
+            distinct_counts: HashMap::new(),
+            correlations: Vec::new(),
+            temporal_profiles: HashMap::new(),
+            fan_outs: HashMap::new(),
+            column_distributions: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_row_count_changed() {
+        assert!(!row_count_changed(1000, 1099, 0.1));
+        assert!(!row_count_changed(1000, 900, 0.1));
+        assert!(row_count_changed(1000, 1101, 0.1));
+        assert!(row_count_changed(0, 2, 0.1));
+        assert!(!row_count_changed(0, 0, 0.1));
+    }
+
+    #[test]
+    fn test_progress_resumes_unchanged_tables() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(PROGRESS_FILE_NAME);
+        let mut progress = SampleProgress::load(&path, "quantiles=[0.5]".to_string()).unwrap();
+        assert!(!progress.contains("orders"));
+        progress.record(&orders(), &profile(1000)).unwrap();
+
+        let progress = SampleProgress::load(&path, "quantiles=[0.5]".to_string()).unwrap();
+        assert!(progress.contains("orders"));
+        assert_eq!(
+            progress.reuse(&orders(), 1050, 0.1).map(|p| p.row_count),
+            Some(1000)
+        );
+        assert!(progress.reuse(&orders(), 1500, 0.1).is_none());
+
+        let mut altered = orders();
+        altered.columns["total"].raw_type = "bigint".to_string();
+        assert!(progress.reuse(&altered, 1000, 0.1).is_none());
+
+        // Other settings discard what was saved
+        let progress = SampleProgress::load(&path, "quantiles=[0.9]".to_string()).unwrap();
+        assert!(!progress.contains("orders"));
+        assert!(!SampleProgress::fresh(&path, "quantiles=[0.5]".to_string()).contains("orders"));
+
+        // A complete run leaves no unmasked profiles behind
+        progress.remove().unwrap();
+        assert!(!path.exists());
+        progress.remove().unwrap();
+    }
+}
@@ -0,0 +1,291 @@
+//! # PII Scan of Saved Profiles
+//!
+//! Masking at sample time only catches PII columns by name (see
+//! `sample::mask`): a `notes` or `reference` column full of email addresses
+//! keeps them verbatim. `seedkit sample audit` scans a saved profiles file
+//! for categorical values that look like PII whatever their column is
+//! called, before the file is committed:
+//!
+//! - **emails** — `local@domain.tld`;
+//! - **phone numbers** — 7 to 15 digits with phone punctuation, not dates,
+//!   decimals or IP addresses;
+//! - **names** — two or three capitalized words starting with a common
+//!   first name.
+//!
+//! Flagged values can be stripped, or replaced by keyed hashes
+//! (`anon_3f9a1c02b7e4`, see `Pseudonymizer::token`). Hashes don't look like
+//! PII themselves, so a hashed file passes the next audit, and a value
+//! hashes alike in every column, so joins on it still work.
+
+use std::fmt;
+use std::sync::LazyLock;
+
+use fake::locales::{Data, EN};
+use regex::Regex;
+
+use super::mask::Pseudonymizer;
+use super::stats::{ColumnDistribution, DistributionProfile};
+
+static EMAIL: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^[A-Za-z0-9._%+-]+@[A-Za-z0-9-]+(\.[A-Za-z0-9-]+)*\.[A-Za-z]{2,}$").unwrap()
+});
+
+static PHONE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\+?[0-9][0-9 ().-]*[0-9]$").unwrap());
+
+/// Kind of PII a value looks like.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PiiKind {
+    Email,
+    Phone,
+    Name,
+}
+
+impl fmt::Display for PiiKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            PiiKind::Email => "email addresses",
+            PiiKind::Phone => "phone numbers",
+            PiiKind::Name => "names",
+        })
+    }
+}
+
+/// Categorical values of a column that look like PII.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PiiFinding {
+    pub table: String,
+    pub column: String,
+    pub kind: PiiKind,
+    pub values: usize,
+}
+
+impl fmt::Display for PiiFinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}.{}: {} values look like {}",
+            self.table, self.column, self.values, self.kind
+        )
+    }
+}
+
+/// The kind of PII `value` looks like, if any.
+pub fn detect_pii(value: &str) -> Option<PiiKind> {
+    let value = value.trim();
+    if EMAIL.is_match(value) {
+        return Some(PiiKind::Email);
+    }
+    if is_phone(value) {
+        return Some(PiiKind::Phone);
+    }
+    if is_name(value) {
+        return Some(PiiKind::Name);
+    }
+    None
+}
+
+fn is_phone(value: &str) -> bool {
+    if !PHONE.is_match(value) {
+        return false;
+    }
+    // Bare digit runs are as likely to be order numbers or timestamps
+    let digits = value.chars().filter(char::is_ascii_digit).count();
+    if !(7..=15).contains(&digits) || value.len() == digits {
+        return false;
+    }
+    value.parse::<f64>().is_err()
+        && value.parse::<std::net::Ipv4Addr>().is_err()
+        && chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d").is_err()
+}
+
+fn is_name(value: &str) -> bool {
+    let words: Vec<&str> = value.split_whitespace().collect();
+    if !(2..=3).contains(&words.len()) {
+        return false;
+    }
+    let capitalized = words.iter().all(|word| {
+        let mut chars = word.chars();
+        chars.next().is_some_and(|c| c.is_uppercase())
+            && chars.all(|c| c.is_alphabetic() || c == '\'' || c == '-')
+    });
+    capitalized && EN::NAME_FIRST_NAME.contains(&words[0])
+}
+
+/// Find categorical values that look like PII, one finding per column and
+/// kind, sorted by table and column.
+pub fn scan_profiles(profiles: &[DistributionProfile]) -> Vec<PiiFinding> {
+    let mut findings = Vec::new();
+    for profile in profiles {
+        for (column, dist) in &profile.column_distributions {
+            let ColumnDistribution::Categorical { values } = dist else {
+                continue;
+            };
+            let mut kinds: Vec<PiiKind> =
+                values.iter().filter_map(|(v, _)| detect_pii(v)).collect();
+            kinds.sort();
+            for chunk in kinds.chunk_by(|a, b| a == b) {
+                findings.push(PiiFinding {
+                    table: profile.table_name.clone(),
+                    column: column.clone(),
+                    kind: chunk[0],
+                    values: chunk.len(),
+                });
+            }
+        }
+    }
+    findings.sort_by(|a, b| (&a.table, &a.column, a.kind).cmp(&(&b.table, &b.column, b.kind)));
+    findings
+}
+
+/// Remove categorical values that look like PII. A column left without
+/// values loses its distribution and falls back to semantic generation.
+///
+/// Returns the number of values removed.
+pub fn strip_pii_values(profiles: &mut [DistributionProfile]) -> usize {
+    let mut stripped = 0;
+    for profile in profiles.iter_mut() {
+        profile.column_distributions.retain(|_, dist| {
+            let ColumnDistribution::Categorical { values } = dist else {
+                return true;
+            };
+            let before = values.len();
+            values.retain(|(value, _)| detect_pii(value).is_none());
+            stripped += before - values.len();
+            !values.is_empty()
+        });
+    }
+    stripped
+}
+
+/// Replace categorical values that look like PII with their keyed hashes.
+/// Values sharing a hash are merged.
+///
+/// Returns the number of values replaced.
+pub fn hash_pii_values(
+    profiles: &mut [DistributionProfile],
+    pseudonymizer: &Pseudonymizer,
+) -> usize {
+    let mut hashed = 0;
+    for profile in profiles.iter_mut() {
+        for dist in profile.column_distributions.values_mut() {
+            let ColumnDistribution::Categorical { values } = dist else {
+                continue;
+            };
+            let mut replaced: Vec<(String, f64)> = Vec::with_capacity(values.len());
+            for (value, frequency) in values.drain(..) {
+                let value = if detect_pii(&value).is_some() {
+                    hashed += 1;
+                    pseudonymizer.token(&value)
+                } else {
+                    value
+                };
+                match replaced.iter_mut().find(|(v, _)| *v == value) {
+                    Some((_, total)) => *total += frequency,
+                    None => replaced.push((value, frequency)),
+                }
+            }
+            *values = replaced;
+        }
+    }
+    hashed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn profile() -> DistributionProfile {
+        let categorical = |values: &[&str]| ColumnDistribution::Categorical {
+            values: values
+                .iter()
+                .map(|v| (v.to_string(), 1.0 / values.len() as f64))
+                .collect(),
+        };
+        DistributionProfile {
+            table_name: "tickets".to_string(),
+            row_count: 1000,
+            unique_pairs: Vec::new(),
+            text_profiles: HashMap::new(),
+            null_rates: HashMap::new(),
+            distinct_counts: HashMap::new(),
+            correlations: Vec::new(),
+            temporal_profiles: HashMap::new(),
+            fan_outs: HashMap::new(),
+            column_distributions: HashMap::from([
+                (
+                    "reporter".to_string(),
+                    categorical(&["ada@example.com", "Grace Hopper", "+1 (555) 010-9999"]),
+                ),
+                (
+                    "status".to_string(),
+                    categorical(&["open", "closed", "Won Fix"]),
+                ),
+                (
+                    "contact".to_string(),
+                    categorical(&["ada@example.com", "bob@example.org"]),
+                ),
+            ]),
+        }
+    }
+
+    #[test]
+    fn test_detect_pii() {
+        assert_eq!(
+            detect_pii("jane.doe+work@mail.example.co.uk"),
+            Some(PiiKind::Email)
+        );
+        assert_eq!(detect_pii("+44 20 7946 0958"), Some(PiiKind::Phone));
+        assert_eq!(detect_pii("555-0199"), Some(PiiKind::Phone));
+        assert_eq!(detect_pii("Abigail O'Neil"), Some(PiiKind::Name));
+        for safe in [
+            "2024-01-15",
+            "192.168.1.10",
+            "1234567.89",
+            "4155550199",
+            "In Progress",
+            "abigail smith",
+            "SKU-12",
+            "not@an-email",
+        ] {
+            assert_eq!(detect_pii(safe), None, "{}", safe);
+        }
+    }
+
+    #[test]
+    fn test_scan_profiles() {
+        let findings = scan_profiles(&[profile()]);
+        let summary: Vec<String> = findings.iter().map(|f| f.to_string()).collect();
+        assert_eq!(
+            summary,
+            vec![
+                "tickets.contact: 2 values look like email addresses",
+                "tickets.reporter: 1 values look like email addresses",
+                "tickets.reporter: 1 values look like phone numbers",
+                "tickets.reporter: 1 values look like names",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_strip_and_hash_pii_values() {
+        let mut profiles = vec![profile()];
+        assert_eq!(strip_pii_values(&mut profiles), 5);
+        let columns = &profiles[0].column_distributions;
+        assert!(!columns.contains_key("reporter") && !columns.contains_key("contact"));
+        assert!(columns.contains_key("status"));
+
+        let mut profiles = vec![profile()];
+        let pseudonymizer = Pseudonymizer::new("test-key");
+        assert_eq!(hash_pii_values(&mut profiles, &pseudonymizer), 5);
+        assert!(scan_profiles(&profiles).is_empty());
+        let value = |column: &str| match &profiles[0].column_distributions[column] {
+            ColumnDistribution::Categorical { values } => values[0].0.clone(),
+            other => panic!("unexpected {:?}", other),
+        };
+        // The same address hashes alike in every column
+        assert_eq!(value("reporter"), value("contact"));
+        assert!(value("contact").starts_with("anon_"));
+    }
+}
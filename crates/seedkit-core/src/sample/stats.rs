@@ -1,15 +1,69 @@
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
+use serde_json::Value as Json;
 use std::collections::HashMap;
+use std::future::Future;
+use std::path::PathBuf;
+use std::time::Duration;
 
+use crate::classify::rules::classify_column;
+use crate::classify::semantic::SemanticType;
 use crate::error::{Result, SeedKitError};
-use crate::schema::types::{DatabaseSchema, DatabaseType};
+use crate::sample::markov::{TextModel, TEXT_MODEL_ORDER};
+use crate::sample::progress::{SampleProgress, DEFAULT_RESAMPLE_THRESHOLD};
+use crate::schema::types::{Column, DataType, DatabaseSchema, DatabaseType, Table};
 
 /// Statistical distribution profile extracted from production data.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DistributionProfile {
     pub table_name: String,
     pub row_count: u64,
+    /// Column pairs that were unique together in every sampled row, even
+    /// though no constraint enforces it (e.g. `(user_id, role_id)`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unique_pairs: Vec<(String, String)>,
     pub column_distributions: HashMap<String, ColumnDistribution>,
+    /// Length and character classes of string columns, which generated
+    /// values are fitted to when a column has no distribution to draw from.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub text_profiles: HashMap<String, TextProfile>,
+    /// Fraction of rows that are NULL in each nullable column, used instead
+    /// of the generator's default null probability.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub null_rates: HashMap<String, f64>,
+    /// Distinct non-NULL values per column, from which uniqueness and how
+    /// often values repeat are inferred.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub distinct_counts: HashMap<String, u64>,
+    /// Pearson correlations between numeric columns, `(a, b, r)`, for pairs
+    /// with `|r| >= MIN_CORRELATION`. Generated values keep them through a
+    /// Gaussian copula (see `generate::copula`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub correlations: Vec<(String, String, f64)>,
+    /// Hour-of-day, day-of-week and month shares of timestamp and date
+    /// columns, which generated timestamps are shifted to follow.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub temporal_profiles: HashMap<String, TemporalProfile>,
+    /// Children-per-parent counts of each single-column foreign key,
+    /// `(children, parents)` for parents with at least one child, so
+    /// generated children spread over their parents the same way.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub fan_outs: HashMap<String, Vec<(u64, u64)>>,
+}
+
+impl DistributionProfile {
+    /// Share of a column's non-NULL values that are distinct: 1.0 for a
+    /// column unique in the sample, lower the more its values repeat.
+    /// `None` when the column's distinct values weren't counted.
+    pub fn distinct_ratio(&self, column: &str) -> Option<f64> {
+        let distinct = *self.distinct_counts.get(column)?;
+        let null_rate = self.null_rates.get(column).copied().unwrap_or(0.0);
+        let non_null = (self.row_count as f64 * (1.0 - null_rate)).round();
+        if non_null < 1.0 {
+            return None;
+        }
+        Some((distinct as f64 / non_null).min(1.0))
+    }
 }
 
 /// Distribution information for a single column.
@@ -25,12 +79,92 @@ pub enum ColumnDistribution {
         max: f64,
         mean: f64,
         stddev: f64,
+        /// Sampled (quantile, value) points, e.g. `(0.99, 1840.0)`; when
+        /// present, values follow them instead of a normal curve.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        quantiles: Vec<(f64, f64)>,
+    },
+    /// Numeric distribution as an equi-width histogram: `buckets` holds the
+    /// share of values in each of `buckets.len()` equal slices of
+    /// `min..=max`, so skewed and multi-modal columns keep their shape.
+    Histogram {
+        min: f64,
+        max: f64,
+        buckets: Vec<f64>,
+        /// Sampled (quantile, value) points refining the buckets' tails.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        quantiles: Vec<(f64, f64)>,
     },
     /// Ratio to another table's row count.
     Ratio {
         related_table: String,
         ratio: f64, // e.g., 3.2 orders per user
     },
+    /// Structure of JSON documents: value types, keys and how often they
+    /// appear, nesting and array lengths. No values are kept.
+    Json { shape: JsonShape },
+    /// Word n-gram model of a free-text column (see `sample::markov`).
+    Text { model: TextModel },
+}
+
+/// Shape of the JSON values found at one place in sampled documents.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct JsonShape {
+    /// Share of values of each JSON type (`object`, `array`, `string`,
+    /// `integer`, `number`, `boolean`, `null`), most common first.
+    pub types: Vec<(String, f64)>,
+    /// Keys of object values, in the order first seen.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub keys: Vec<JsonKey>,
+    /// Shortest and longest array.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub array_length: Option<(usize, usize)>,
+    /// Shape of array elements.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub items: Option<Box<JsonShape>>,
+    /// Smallest and largest number.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub number_range: Option<(f64, f64)>,
+    /// Shortest and longest string, in characters.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub string_length: Option<(usize, usize)>,
+}
+
+/// An object key seen in sampled documents.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JsonKey {
+    pub name: String,
+    /// Share of objects that have the key.
+    pub frequency: f64,
+    pub shape: JsonShape,
+}
+
+/// Length and character classes of a string column's sampled values.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TextProfile {
+    /// Shortest and longest value, in characters.
+    pub length: (usize, usize),
+    pub mean_length: f64,
+    /// Share of values with at least one digit.
+    pub digits: f64,
+    /// Share of values with at least one letter.
+    pub letters: f64,
+    /// Share of values with at least one whitespace character.
+    pub whitespace: f64,
+    /// Share of values with at least one non-ASCII character.
+    pub non_ascii: f64,
+}
+
+/// When a timestamp column's values fall: shares of values per hour of the
+/// day, day of the week and month.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TemporalProfile {
+    /// Hours 0 to 23.
+    pub hours: Vec<f64>,
+    /// Sunday (0) to Saturday (6).
+    pub weekdays: Vec<f64>,
+    /// January (0) to December (11).
+    pub months: Vec<f64>,
 }
 
 /// Options for controlling the sampling process.
@@ -42,6 +176,43 @@ pub struct SampleOptions {
     pub categorical_limit: usize,
     /// Minimum number of rows a table must have to be sampled.
     pub min_row_count: u64,
+    /// Probe candidate column pairs for de-facto composite uniqueness.
+    pub detect_unique_pairs: bool,
+    /// Attempts per query when a replica cancels it for conflicting with
+    /// recovery. The column is skipped once they run out.
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubles on each later one.
+    pub retry_backoff: Duration,
+    /// Number of documents read to profile a JSON column's structure.
+    pub json_sample_size: usize,
+    /// Profile free-text columns as Markov text models rather than value
+    /// frequencies.
+    pub text_models: bool,
+    /// Number of values read to train a text model.
+    pub text_sample_size: usize,
+    /// Buckets of numeric histograms; 0 keeps only min/max/mean/stddev.
+    pub histogram_buckets: usize,
+    /// Quantiles captured per numeric column, each in (0, 1).
+    pub quantiles: Vec<f64>,
+    /// Number of values read to profile a string column's lengths and
+    /// character classes; 0 skips text profiles.
+    pub string_sample_size: usize,
+    /// Count the distinct values of every column.
+    pub distinct_counts: bool,
+    /// Measure correlations between numeric columns.
+    pub correlations: bool,
+    /// Profile when timestamp and date columns' values fall.
+    pub temporal_patterns: bool,
+    /// Count how many children each parent has through every foreign key.
+    pub fan_outs: bool,
+    /// Save each table's profile here as it is extracted, and reuse those
+    /// saved by earlier runs (see `sample::progress`).
+    pub progress_file: Option<PathBuf>,
+    /// Reuse profiles saved in `progress_file`; `false` starts over.
+    pub resume: bool,
+    /// Relative change in row count above which a saved profile is
+    /// re-sampled.
+    pub resample_threshold: f64,
 }
 
 impl Default for SampleOptions {
@@ -50,16 +221,232 @@ impl Default for SampleOptions {
             tables: None,
             categorical_limit: 50,
             min_row_count: 10,
+            detect_unique_pairs: true,
+            max_attempts: 5,
+            retry_backoff: Duration::from_millis(250),
+            json_sample_size: 200,
+            text_models: false,
+            text_sample_size: 500,
+            histogram_buckets: 20,
+            quantiles: vec![0.5, 0.9, 0.99],
+            string_sample_size: 500,
+            distinct_counts: true,
+            correlations: true,
+            temporal_patterns: true,
+            fan_outs: true,
+            progress_file: None,
+            resume: true,
+            resample_threshold: DEFAULT_RESAMPLE_THRESHOLD,
         }
     }
 }
 
+impl SampleOptions {
+    /// The settings that shape profiles, so profiles saved with other ones
+    /// aren't reused.
+    pub fn fingerprint(&self) -> String {
+        format!(
+            "categorical_limit={} min_row_count={} unique_pairs={} json_sample_size={} \
+             text_models={} text_sample_size={} histogram_buckets={} quantiles={:?} \
+             string_sample_size={} distinct_counts={} correlations={} \
+             temporal_patterns={} fan_outs={}",
+            self.categorical_limit,
+            self.min_row_count,
+            self.detect_unique_pairs,
+            self.json_sample_size,
+            self.text_models,
+            self.text_sample_size,
+            self.histogram_buckets,
+            self.quantiles,
+            self.string_sample_size,
+            self.distinct_counts,
+            self.correlations,
+            self.temporal_patterns,
+            self.fan_outs,
+        )
+    }
+}
+
+/// Maximum number of numeric columns per table whose correlations are
+/// measured; 8 columns means at most 28 queries.
+const MAX_CORRELATION_COLUMNS: usize = 8;
+
+/// Weakest correlation worth keeping.
+pub const MIN_CORRELATION: f64 = 0.1;
+
+/// Maximum number of columns per table considered for unique-pair probing.
+/// Pairs grow quadratically, so 8 columns means at most 28 probe queries.
+const MAX_UNIQUE_PAIR_CANDIDATES: usize = 8;
+
+/// SQLSTATEs for queries a hot-standby replica cancels because they
+/// conflict with WAL replay ("canceling statement due to conflict with
+/// recovery"), and for deadlocks. Re-running the query usually succeeds.
+const RETRYABLE_SQLSTATES: &[&str] = &["40001", "40P01"];
+
+fn is_replica_conflict(err: &sqlx::Error) -> bool {
+    err.as_database_error()
+        .and_then(|e| e.code())
+        .is_some_and(|code| RETRYABLE_SQLSTATES.contains(&code.as_ref()))
+}
+
+/// Run a sampling query, retrying replica conflicts with exponential
+/// backoff up to `options.max_attempts` times.
+async fn retry_query<T, F, Fut>(options: &SampleOptions, mut run: F) -> sqlx::Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = sqlx::Result<T>>,
+{
+    let mut delay = options.retry_backoff;
+    let mut attempt = 1;
+    loop {
+        match run().await {
+            Err(e) if is_replica_conflict(&e) && attempt < options.max_attempts => {
+                tracing::debug!("Retrying sampling query after replica conflict: {}", e);
+                tokio::time::sleep(delay).await;
+                delay *= 2;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Run a per-column sampling query, retrying replica conflicts. A failed
+/// query skips `target` rather than the whole run; a conflict that
+/// outlasts the retries is reported.
+async fn sample_query<T, F, Fut>(options: &SampleOptions, target: &str, run: F) -> Option<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = sqlx::Result<T>>,
+{
+    match retry_query(options, run).await {
+        Ok(value) => Some(value),
+        Err(e) if is_replica_conflict(&e) => {
+            tracing::warn!(
+                "Skipping {}: still conflicting with replica recovery after {} attempts: {}",
+                target,
+                options.max_attempts,
+                e
+            );
+            None
+        }
+        Err(_) => None,
+    }
+}
+
+/// Run a table's row count query, retrying replica conflicts. A conflict
+/// that outlasts the retries skips the table (`None`); other errors fail.
+async fn row_count<F, Fut>(options: &SampleOptions, table_name: &str, run: F) -> Result<Option<u64>>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = sqlx::Result<(i64,)>>,
+{
+    match retry_query(options, run).await {
+        Ok((count,)) => Ok(Some(count as u64)),
+        Err(e) if is_replica_conflict(&e) => {
+            tracing::warn!(
+                "Skipping table {}: still conflicting with replica recovery after {} attempts: {}",
+                table_name,
+                options.max_attempts,
+                e
+            );
+            Ok(None)
+        }
+        Err(e) => Err(SeedKitError::Introspection {
+            query: format!("row count for {}", table_name),
+            source: e,
+        }),
+    }
+}
+
 /// Generate a SQL query to extract the row count for a table.
 pub fn row_count_query(table: &str, db_type: DatabaseType) -> String {
     let quoted = quote_ident(table, db_type);
     format!("SELECT COUNT(*) AS cnt FROM {}", quoted)
 }
 
+/// Generate a SQL query counting the rows of a table (as `total`) and the
+/// non-NULL values of each column (as `n0`, `n1`, ...).
+pub fn null_count_query(table: &str, columns: &[&str], db_type: DatabaseType) -> String {
+    let counts: Vec<String> = columns
+        .iter()
+        .enumerate()
+        .map(|(i, col)| format!("COUNT({}) AS n{}", quote_ident(col, db_type), i))
+        .collect();
+    format!(
+        "SELECT COUNT(*) AS total, {} FROM {}",
+        counts.join(", "),
+        quote_ident(table, db_type)
+    )
+}
+
+/// Generate a SQL query counting the distinct values of `columns` (as `d0`,
+/// `d1`, ...).
+pub fn distinct_count_query(table: &str, columns: &[&str], db_type: DatabaseType) -> String {
+    let counts: Vec<String> = columns
+        .iter()
+        .enumerate()
+        .map(|(i, col)| format!("COUNT(DISTINCT {}) AS d{}", quote_ident(col, db_type), i))
+        .collect();
+    format!(
+        "SELECT {} FROM {}",
+        counts.join(", "),
+        quote_ident(table, db_type)
+    )
+}
+
+/// Columns whose distinct values are counted: all but auto-increment keys
+/// and types without equality (JSON, spatial).
+fn countable_columns(table: &Table) -> Vec<&str> {
+    table
+        .columns
+        .iter()
+        .filter(|(_, column)| {
+            !column.is_auto_increment
+                && !column.data_type.is_serial()
+                && !matches!(
+                    column.data_type,
+                    DataType::Json | DataType::Jsonb | DataType::Spatial { .. }
+                )
+        })
+        .map(|(name, _)| name.as_str())
+        .collect()
+}
+
+/// Nullable columns outside the primary key, whose NULL rates are sampled.
+fn nullable_columns(table: &Table) -> Vec<&str> {
+    let pk: &[String] = table
+        .primary_key
+        .as_ref()
+        .map(|pk| pk.columns.as_slice())
+        .unwrap_or(&[]);
+    table
+        .columns
+        .iter()
+        .filter(|(name, column)| column.nullable && !pk.contains(name))
+        .map(|(name, _)| name.as_str())
+        .collect()
+}
+
+/// Turn the non-NULL counts from [`null_count_query`] into NULL rates.
+pub fn null_rates_from_counts(
+    columns: &[&str],
+    non_null: &[i64],
+    total: i64,
+) -> HashMap<String, f64> {
+    if total <= 0 {
+        return HashMap::new();
+    }
+    columns
+        .iter()
+        .zip(non_null)
+        .map(|(col, &count)| {
+            let rate = (total - count).clamp(0, total) as f64 / total as f64;
+            (col.to_string(), rate)
+        })
+        .collect()
+}
+
 /// Generate a SQL query to extract categorical distribution (value frequencies).
 ///
 /// Returns rows of (value, frequency) where frequency is a proportion (0.0–1.0).
@@ -104,14 +491,16 @@ pub fn numeric_query(table: &str, column: &str, db_type: DatabaseType) -> String
     match db_type {
         DatabaseType::PostgreSQL => format!(
             "SELECT MIN({col})::float8 AS min_val, MAX({col})::float8 AS max_val, \
-             AVG({col})::float8 AS mean_val, COALESCE(STDDEV_POP({col})::float8, 0) AS stddev_val \
+             AVG({col})::float8 AS mean_val, COALESCE(STDDEV_POP({col})::float8, 0) AS stddev_val, \
+             COUNT({col}) AS count_val \
              FROM {tbl} WHERE {col} IS NOT NULL",
             col = qc,
             tbl = qt,
         ),
         DatabaseType::MySQL => format!(
             "SELECT MIN({col}) AS min_val, MAX({col}) AS max_val, \
-             AVG({col}) AS mean_val, COALESCE(STDDEV({col}), 0) AS stddev_val \
+             AVG({col}) AS mean_val, COALESCE(STDDEV({col}), 0) AS stddev_val, \
+             COUNT({col}) AS count_val \
              FROM {tbl} WHERE {col} IS NOT NULL",
             col = qc,
             tbl = qt,
@@ -119,7 +508,7 @@ pub fn numeric_query(table: &str, column: &str, db_type: DatabaseType) -> String
         // SQLite has no built-in stddev; we compute min/max/avg and set stddev=0
         DatabaseType::SQLite => format!(
             "SELECT MIN({col}) AS min_val, MAX({col}) AS max_val, \
-             AVG({col}) AS mean_val, 0.0 AS stddev_val \
+             AVG({col}) AS mean_val, 0.0 AS stddev_val, COUNT({col}) AS count_val \
              FROM {tbl} WHERE {col} IS NOT NULL",
             col = qc,
             tbl = qt,
@@ -127,6 +516,360 @@ pub fn numeric_query(table: &str, column: &str, db_type: DatabaseType) -> String
     }
 }
 
+/// Generate a SQL query counting a numeric column's values in each of
+/// `buckets` equal slices of `min..=max` (`bucket`, `cnt`).
+pub fn histogram_query(
+    table: &str,
+    column: &str,
+    db_type: DatabaseType,
+    min: f64,
+    max: f64,
+    buckets: usize,
+) -> String {
+    let qt = quote_ident(table, db_type);
+    let qc = quote_ident(column, db_type);
+    let scaled = format!("({} - {:?}) * {} / {:?}", qc, min, buckets, max - min);
+    let last = buckets.saturating_sub(1);
+    let bucket = match db_type {
+        DatabaseType::PostgreSQL => format!("LEAST(FLOOR({}), {})::bigint", scaled, last),
+        DatabaseType::MySQL => format!("CAST(LEAST(FLOOR({}), {}) AS SIGNED)", scaled, last),
+        DatabaseType::SQLite => format!("MIN(CAST({} AS INTEGER), {})", scaled, last),
+    };
+    format!(
+        "SELECT {bucket} AS bucket, COUNT(*) AS cnt FROM {tbl} WHERE {col} IS NOT NULL GROUP BY 1",
+        bucket = bucket,
+        tbl = qt,
+        col = qc,
+    )
+}
+
+/// Build a `Histogram` from per-bucket counts; `None` when nothing was
+/// counted.
+pub fn histogram_from_counts(
+    min: f64,
+    max: f64,
+    buckets: usize,
+    counts: &[(i64, i64)],
+) -> Option<ColumnDistribution> {
+    let total: i64 = counts.iter().map(|(_, count)| count.max(&0)).sum();
+    if buckets == 0 || total == 0 {
+        return None;
+    }
+    let mut shares = vec![0.0; buckets];
+    for &(bucket, count) in counts {
+        let index = (bucket.max(0) as usize).min(buckets - 1);
+        shares[index] += count.max(0) as f64 / total as f64;
+    }
+    Some(ColumnDistribution::Histogram {
+        min,
+        max,
+        buckets: shares,
+        quantiles: Vec::new(),
+    })
+}
+
+/// Attach sampled quantiles to a numeric distribution.
+fn with_quantiles(
+    mut distribution: ColumnDistribution,
+    points: Vec<(f64, f64)>,
+) -> ColumnDistribution {
+    if let ColumnDistribution::Numeric { quantiles, .. }
+    | ColumnDistribution::Histogram { quantiles, .. } = &mut distribution
+    {
+        *quantiles = points;
+    }
+    distribution
+}
+
+/// Generate a SQL query reading the value at `offset` in a numeric
+/// column's sorted non-NULL values (`val`).
+pub fn quantile_query(table: &str, column: &str, db_type: DatabaseType, offset: u64) -> String {
+    let qt = quote_ident(table, db_type);
+    let qc = quote_ident(column, db_type);
+    let val = match db_type {
+        DatabaseType::PostgreSQL => format!("{}::float8", qc),
+        DatabaseType::MySQL => format!("CAST({} AS DOUBLE)", qc),
+        DatabaseType::SQLite => format!("CAST({} AS REAL)", qc),
+    };
+    format!(
+        "SELECT {val} AS val FROM {tbl} WHERE {col} IS NOT NULL ORDER BY {col} LIMIT 1 OFFSET {offset}",
+        val = val,
+        tbl = qt,
+        col = qc,
+        offset = offset,
+    )
+}
+
+/// Offset of quantile `q` among `count` sorted values (nearest rank).
+pub fn quantile_offset(q: f64, count: u64) -> u64 {
+    ((q * count as f64).ceil() as u64).clamp(1, count.max(1)) - 1
+}
+
+/// Generate a SQL query counting a timestamp or date column's values by
+/// hour of the day (0-23), day of the week (0 = Sunday) and month (1-12):
+/// `hour_val`, `dow_val`, `month_val`, `cnt`.
+pub fn temporal_query(table: &str, column: &str, db_type: DatabaseType) -> String {
+    let qt = quote_ident(table, db_type);
+    let qc = quote_ident(column, db_type);
+    let (hour, dow, month) = match db_type {
+        // Dates are cast so EXTRACT accepts HOUR on them
+        DatabaseType::PostgreSQL => (
+            format!("EXTRACT(HOUR FROM {}::timestamp)::bigint", qc),
+            format!("EXTRACT(DOW FROM {}::timestamp)::bigint", qc),
+            format!("EXTRACT(MONTH FROM {}::timestamp)::bigint", qc),
+        ),
+        DatabaseType::MySQL => (
+            format!("CAST(HOUR({}) AS SIGNED)", qc),
+            format!("CAST(DAYOFWEEK({}) - 1 AS SIGNED)", qc),
+            format!("CAST(MONTH({}) AS SIGNED)", qc),
+        ),
+        DatabaseType::SQLite => (
+            format!("CAST(strftime('%H', {}) AS INTEGER)", qc),
+            format!("CAST(strftime('%w', {}) AS INTEGER)", qc),
+            format!("CAST(strftime('%m', {}) AS INTEGER)", qc),
+        ),
+    };
+    format!(
+        "SELECT {hour} AS hour_val, {dow} AS dow_val, {month} AS month_val, COUNT(*) AS cnt \
+         FROM {tbl} WHERE {col} IS NOT NULL GROUP BY 1, 2, 3",
+        hour = hour,
+        dow = dow,
+        month = month,
+        tbl = qt,
+        col = qc,
+    )
+}
+
+/// Build a [`TemporalProfile`] from `(hour, day of week, month, count)`
+/// rows of [`temporal_query`]; `None` when nothing was counted.
+pub fn temporal_from_counts(counts: &[(i64, i64, i64, i64)]) -> Option<TemporalProfile> {
+    let mut hours = vec![0.0; 24];
+    let mut weekdays = vec![0.0; 7];
+    let mut months = vec![0.0; 12];
+    let mut total = 0.0;
+    for &(hour, dow, month, count) in counts {
+        let (Ok(hour), Ok(dow), Ok(month)) = (
+            usize::try_from(hour),
+            usize::try_from(dow),
+            usize::try_from(month - 1),
+        ) else {
+            continue;
+        };
+        if hour >= 24 || dow >= 7 || month >= 12 || count <= 0 {
+            continue;
+        }
+        let count = count as f64;
+        hours[hour] += count;
+        weekdays[dow] += count;
+        months[month] += count;
+        total += count;
+    }
+    if total <= 0.0 {
+        return None;
+    }
+    for share in hours.iter_mut().chain(&mut weekdays).chain(&mut months) {
+        *share /= total;
+    }
+    Some(TemporalProfile {
+        hours,
+        weekdays,
+        months,
+    })
+}
+
+/// Generate a SQL query that reads up to `limit` non-NULL values of a
+/// column as text, for profiles built from the values themselves (JSON
+/// shapes, text models).
+pub fn sample_values_query(
+    table: &str,
+    column: &str,
+    db_type: DatabaseType,
+    limit: usize,
+) -> String {
+    let qt = quote_ident(table, db_type);
+    let qc = quote_ident(column, db_type);
+    let text = match db_type {
+        DatabaseType::MySQL => "CHAR",
+        DatabaseType::PostgreSQL | DatabaseType::SQLite => "TEXT",
+    };
+    format!(
+        "SELECT CAST({col} AS {text}) AS val FROM {tbl} WHERE {col} IS NOT NULL LIMIT {lim}",
+        col = qc,
+        text = text,
+        tbl = qt,
+        lim = limit
+    )
+}
+
+/// Whether a column holds free text worth a text model: descriptions,
+/// bios, comments and the like.
+fn is_free_text(table_name: &str, col_name: &str, column: &Column) -> bool {
+    column.data_type.is_string()
+        && matches!(
+            classify_column(col_name, &column.data_type, table_name, false, false, None),
+            SemanticType::Description
+                | SemanticType::Bio
+                | SemanticType::Paragraph
+                | SemanticType::Sentence
+        )
+}
+
+/// Profile the lengths and character classes of sampled string values.
+pub fn profile_text<'a>(values: impl IntoIterator<Item = &'a str>) -> Option<TextProfile> {
+    let mut count = 0usize;
+    let (mut min, mut max, mut total) = (usize::MAX, 0, 0);
+    let mut classes = [0usize; 4];
+    for value in values {
+        let length = value.chars().count();
+        min = min.min(length);
+        max = max.max(length);
+        total += length;
+        count += 1;
+        let found = [
+            value.chars().any(|c| c.is_ascii_digit()),
+            value.chars().any(char::is_alphabetic),
+            value.chars().any(char::is_whitespace),
+            !value.is_ascii(),
+        ];
+        for (class, found) in classes.iter_mut().zip(found) {
+            *class += found as usize;
+        }
+    }
+    if count == 0 {
+        return None;
+    }
+    let share = |n: usize| n as f64 / count as f64;
+    Some(TextProfile {
+        length: (min, max),
+        mean_length: share(total),
+        digits: share(classes[0]),
+        letters: share(classes[1]),
+        whitespace: share(classes[2]),
+        non_ascii: share(classes[3]),
+    })
+}
+
+/// Share of sampled values the top categories must account for to be kept
+/// when the categorical limit was reached. Below it the column is mostly
+/// one-off values (codes, names, free text) that are better generated.
+const MIN_CATEGORICAL_COVERAGE: f64 = 0.5;
+
+/// Whether a column's top `values` describe it, judged against a `sample`
+/// of its values. Always true when fewer than `limit` distinct values exist.
+fn is_categorical(values: &[(String, f64)], limit: usize, sample: &[String]) -> bool {
+    if values.len() < limit || sample.is_empty() {
+        return true;
+    }
+    let covered = sample
+        .iter()
+        .filter(|s| values.iter().any(|(v, _)| v == *s))
+        .count();
+    covered as f64 / sample.len() as f64 >= MIN_CATEGORICAL_COVERAGE
+}
+
+/// Nesting below this depth is profiled by type only.
+const MAX_JSON_DEPTH: usize = 8;
+
+/// Most object keys kept per level. Documents keyed by IDs or other data
+/// would otherwise carry those values into the profile; only the most
+/// common keys are structure.
+const MAX_JSON_KEYS: usize = 64;
+
+/// Profile the structure of sampled JSON documents. Documents that don't
+/// parse are skipped.
+pub fn profile_json<'a>(documents: impl IntoIterator<Item = &'a str>) -> JsonShape {
+    let parsed: Vec<Json> = documents
+        .into_iter()
+        .filter_map(|text| serde_json::from_str(text).ok())
+        .collect();
+    let values: Vec<&Json> = parsed.iter().collect();
+    profile_values(&values, 0)
+}
+
+fn profile_values(values: &[&Json], depth: usize) -> JsonShape {
+    let mut shape = JsonShape::default();
+    if values.is_empty() {
+        return shape;
+    }
+
+    let mut type_counts: IndexMap<&str, usize> = IndexMap::new();
+    let mut objects = 0;
+    let mut keys: IndexMap<&str, Vec<&Json>> = IndexMap::new();
+    let mut elements = Vec::new();
+    for value in values {
+        let type_name = match value {
+            Json::Object(map) => {
+                objects += 1;
+                for (key, child) in map {
+                    keys.entry(key.as_str()).or_default().push(child);
+                }
+                "object"
+            }
+            Json::Array(items) => {
+                shape.array_length = Some(widen(shape.array_length, items.len()));
+                elements.extend(items);
+                "array"
+            }
+            Json::Number(n) => {
+                let n = n.as_f64().unwrap_or_default();
+                shape.number_range = Some(match shape.number_range {
+                    Some((min, max)) => (min.min(n), max.max(n)),
+                    None => (n, n),
+                });
+                if value.is_i64() || value.is_u64() {
+                    "integer"
+                } else {
+                    "number"
+                }
+            }
+            Json::String(s) => {
+                shape.string_length = Some(widen(shape.string_length, s.chars().count()));
+                "string"
+            }
+            Json::Bool(_) => "boolean",
+            Json::Null => "null",
+        };
+        *type_counts.entry(type_name).or_default() += 1;
+    }
+
+    let total = values.len() as f64;
+    shape.types = type_counts
+        .into_iter()
+        .map(|(name, count)| (name.to_string(), count as f64 / total))
+        .collect();
+    shape
+        .types
+        .sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+
+    if depth < MAX_JSON_DEPTH {
+        let mut common: Vec<(&str, usize)> =
+            keys.iter().map(|(key, seen)| (*key, seen.len())).collect();
+        common.sort_by_key(|(_, seen)| std::cmp::Reverse(*seen));
+        common.truncate(MAX_JSON_KEYS);
+        shape.keys = keys
+            .iter()
+            .filter(|(key, _)| common.iter().any(|(k, _)| k == *key))
+            .map(|(key, seen)| JsonKey {
+                name: key.to_string(),
+                frequency: seen.len() as f64 / objects as f64,
+                shape: profile_values(seen, depth + 1),
+            })
+            .collect();
+        if !elements.is_empty() {
+            shape.items = Some(Box::new(profile_values(&elements, depth + 1)));
+        }
+    }
+    shape
+}
+
+/// Extend a (min, max) range to cover `n`.
+fn widen(range: Option<(usize, usize)>, n: usize) -> (usize, usize) {
+    match range {
+        Some((min, max)) => (min.min(n), max.max(n)),
+        None => (n, n),
+    }
+}
+
 /// Generate a SQL query to compute the ratio of child rows per parent row.
 ///
 /// Returns a single row with the average number of child rows per distinct parent FK value.
@@ -166,6 +909,252 @@ pub fn ratio_query(
     .replace("__FK_COLUMN__", &qfk) // placeholder not used, but keep param for API consistency
 }
 
+/// Generate a SQL query counting parents by their number of children
+/// through a foreign key column.
+///
+/// Returns one row per child count, `(children, parents)`, for parents with
+/// at least one child.
+pub fn fan_out_query(child_table: &str, fk_column: &str, db_type: DatabaseType) -> String {
+    let qct = quote_ident(child_table, db_type);
+    let qfk = quote_ident(fk_column, db_type);
+    format!(
+        "SELECT children, COUNT(*) AS parents FROM \
+         (SELECT COUNT(*) AS children FROM {ct} WHERE {fk} IS NOT NULL GROUP BY {fk}) fan \
+         GROUP BY children ORDER BY children",
+        ct = qct,
+        fk = qfk,
+    )
+}
+
+/// Turn `(children, parents)` rows into a fan-out, dropping malformed ones.
+/// `None` when no parent has a child.
+pub fn fan_out_from_counts(rows: &[(i64, i64)]) -> Option<Vec<(u64, u64)>> {
+    let mut fan_out: Vec<(u64, u64)> = rows
+        .iter()
+        .filter(|(children, parents)| *children > 0 && *parents > 0)
+        .map(|&(children, parents)| (children as u64, parents as u64))
+        .collect();
+    fan_out.sort_unstable();
+    (!fan_out.is_empty()).then_some(fan_out)
+}
+
+/// Generate a SQL query that checks whether a column pair is unique together.
+///
+/// Returns a single row with the number of duplicated `(a, b)` combinations,
+/// the distinct counts of each column, and the number of rows considered.
+/// Rows where either column is NULL are ignored, matching how unique
+/// constraints treat NULLs.
+pub fn unique_pair_query(table: &str, col_a: &str, col_b: &str, db_type: DatabaseType) -> String {
+    let qt = quote_ident(table, db_type);
+    let qa = quote_ident(col_a, db_type);
+    let qb = quote_ident(col_b, db_type);
+    format!(
+        "SELECT \
+         (SELECT COUNT(*) FROM (SELECT {a}, {b} FROM {tbl} \
+          WHERE {a} IS NOT NULL AND {b} IS NOT NULL \
+          GROUP BY {a}, {b} HAVING COUNT(*) > 1) dup) AS dup_pairs, \
+         COUNT(DISTINCT {a}) AS distinct_a, COUNT(DISTINCT {b}) AS distinct_b, \
+         COUNT(*) AS total \
+         FROM {tbl} WHERE {a} IS NOT NULL AND {b} IS NOT NULL",
+        a = qa,
+        b = qb,
+        tbl = qt,
+    )
+}
+
+/// Generate a SQL query for the moments of two numeric columns over rows
+/// where both are set (`mean_a`, `mean_b`, `mean_aa`, `mean_bb`, `mean_ab`),
+/// from which [`correlation_from_moments`] works out their correlation.
+pub fn correlation_query(table: &str, col_a: &str, col_b: &str, db_type: DatabaseType) -> String {
+    let qt = quote_ident(table, db_type);
+    let qa = quote_ident(col_a, db_type);
+    let qb = quote_ident(col_b, db_type);
+    let (a, b) = match db_type {
+        DatabaseType::PostgreSQL => (format!("{}::float8", qa), format!("{}::float8", qb)),
+        // Multiplying by a float literal makes MySQL average doubles, not decimals
+        DatabaseType::MySQL | DatabaseType::SQLite => {
+            (format!("({} * 1e0)", qa), format!("({} * 1e0)", qb))
+        }
+    };
+    format!(
+        "SELECT AVG({a}) AS mean_a, AVG({b}) AS mean_b, \
+         AVG({a} * {a}) AS mean_aa, AVG({b} * {b}) AS mean_bb, AVG({a} * {b}) AS mean_ab \
+         FROM {tbl} WHERE {qa} IS NOT NULL AND {qb} IS NOT NULL",
+        a = a,
+        b = b,
+        qa = qa,
+        qb = qb,
+        tbl = qt,
+    )
+}
+
+/// Pearson correlation from the moments returned by [`correlation_query`].
+/// `None` when a column doesn't vary or the correlation is weaker than
+/// [`MIN_CORRELATION`].
+pub fn correlation_from_moments(
+    mean_a: f64,
+    mean_b: f64,
+    mean_aa: f64,
+    mean_bb: f64,
+    mean_ab: f64,
+) -> Option<f64> {
+    let var_a = mean_aa - mean_a * mean_a;
+    let var_b = mean_bb - mean_b * mean_b;
+    if var_a <= f64::EPSILON * mean_aa.abs() || var_b <= f64::EPSILON * mean_bb.abs() {
+        return None;
+    }
+    let r = ((mean_ab - mean_a * mean_b) / (var_a * var_b).sqrt()).clamp(-1.0, 1.0);
+    (r.abs() >= MIN_CORRELATION).then_some(r)
+}
+
+/// Pick the numeric column pairs whose correlation is measured: columns
+/// profiled as numeric distributions, other than foreign keys.
+pub fn correlation_candidates(
+    table: &Table,
+    distributions: &HashMap<String, ColumnDistribution>,
+) -> Vec<(String, String)> {
+    let columns: Vec<&String> = table
+        .columns
+        .keys()
+        .filter(|col_name| {
+            matches!(
+                distributions.get(*col_name),
+                Some(ColumnDistribution::Numeric { .. } | ColumnDistribution::Histogram { .. })
+            ) && !table
+                .foreign_keys
+                .iter()
+                .any(|fk| fk.source_columns.contains(col_name))
+        })
+        .take(MAX_CORRELATION_COLUMNS)
+        .collect();
+
+    let mut pairs = Vec::new();
+    for (i, a) in columns.iter().enumerate() {
+        for b in &columns[i + 1..] {
+            pairs.push(((*a).clone(), (*b).clone()));
+        }
+    }
+    pairs
+}
+
+/// Pick the column pairs worth probing for implicit composite uniqueness.
+///
+/// Candidates are FK columns and low-cardinality categorical columns — the
+/// shapes that typically form implicit keys like `(user_id, role_id)` or
+/// `(tenant_id, slug)`. Columns that are already unique on their own are
+/// skipped (any pair containing them is trivially unique), as are pairs
+/// already covered by a declared unique constraint or primary key.
+pub fn unique_pair_candidates(
+    table: &Table,
+    distributions: &HashMap<String, ColumnDistribution>,
+) -> Vec<(String, String)> {
+    let declared: Vec<&Vec<String>> = table
+        .unique_constraints
+        .iter()
+        .map(|uc| &uc.columns)
+        .chain(table.primary_key.iter().map(|pk| &pk.columns))
+        .collect();
+
+    let singly_unique = |col: &str| {
+        declared
+            .iter()
+            .any(|cols| cols.len() == 1 && cols[0] == col)
+    };
+
+    let mut columns: Vec<&String> = Vec::new();
+    for (col_name, column) in &table.columns {
+        if column.is_auto_increment || column.data_type.is_serial() || singly_unique(col_name) {
+            continue;
+        }
+        let is_fk = table
+            .foreign_keys
+            .iter()
+            .any(|fk| fk.source_columns.contains(col_name));
+        let is_categorical = matches!(
+            distributions.get(col_name),
+            Some(ColumnDistribution::Categorical { .. })
+        );
+        if is_fk || is_categorical {
+            columns.push(col_name);
+        }
+    }
+    columns.truncate(MAX_UNIQUE_PAIR_CANDIDATES);
+
+    let mut pairs = Vec::new();
+    for (i, a) in columns.iter().enumerate() {
+        for b in &columns[i + 1..] {
+            let covered = declared
+                .iter()
+                .any(|cols| cols.len() == 2 && cols.contains(a) && cols.contains(b));
+            if !covered {
+                pairs.push(((*a).clone(), (*b).clone()));
+            }
+        }
+    }
+    pairs
+}
+
+/// Decide whether a probed pair is a meaningful implicit unique key.
+///
+/// The pair must have no duplicated combinations, and neither column may be
+/// unique on its own in the data (otherwise the pair adds no information).
+fn is_implicit_unique_pair(
+    dup_pairs: i64,
+    distinct_a: i64,
+    distinct_b: i64,
+    total: i64,
+    min_rows: u64,
+) -> bool {
+    dup_pairs == 0
+        && total > 0
+        && total as u64 >= min_rows
+        && distinct_a < total
+        && distinct_b < total
+}
+
+/// Probe each candidate pair of `table` for implicit composite uniqueness.
+/// `fetch` runs one `unique_pair_query` and returns its
+/// `(dup_pairs, distinct_a, distinct_b, total)` row; a probe that fails is
+/// logged and the pair skipped.
+async fn probe_unique_pairs<F, Fut>(
+    options: &SampleOptions,
+    table: &Table,
+    table_name: &str,
+    distributions: &HashMap<String, ColumnDistribution>,
+    db_type: DatabaseType,
+    fetch: F,
+) -> Vec<(String, String)>
+where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = sqlx::Result<(i64, i64, i64, i64)>>,
+{
+    let mut unique_pairs = Vec::new();
+    for (col_a, col_b) in unique_pair_candidates(table, distributions) {
+        let sql = unique_pair_query(table_name, &col_a, &col_b, db_type);
+        match retry_query(options, || fetch(sql.clone())).await {
+            Ok((dup_pairs, distinct_a, distinct_b, total)) => {
+                if is_implicit_unique_pair(
+                    dup_pairs,
+                    distinct_a,
+                    distinct_b,
+                    total,
+                    options.min_row_count,
+                ) {
+                    unique_pairs.push((col_a, col_b));
+                }
+            }
+            Err(e) => tracing::warn!(
+                "Skipping unique-pair probe {}.({}, {}): {}",
+                table_name,
+                col_a,
+                col_b,
+                e
+            ),
+        }
+    }
+    unique_pairs
+}
+
 /// Extract distribution profiles from a live database.
 pub async fn extract_distributions(
     url: &str,
@@ -183,6 +1172,11 @@ pub async fn extract_distributions(
     };
 
     let mut profiles = Vec::new();
+    let mut progress = match options.progress_file {
+        Some(ref path) if options.resume => SampleProgress::load(path, options.fingerprint())?,
+        Some(ref path) => SampleProgress::fresh(path, options.fingerprint()),
+        None => SampleProgress::new(options.fingerprint()),
+    };
 
     match schema.database_type {
         DatabaseType::PostgreSQL => {
@@ -196,8 +1190,18 @@ pub async fn extract_distributions(
                 })?;
 
             for table_name in &tables_to_sample {
+                let table = &schema.tables[*table_name];
+                let sql = row_count_query(table_name, DatabaseType::PostgreSQL);
+                let saved = saved_profile(&progress, options, table, || {
+                    sqlx::query_as::<_, (i64,)>(&sql).fetch_one(&pool)
+                });
+                if let Some(saved) = saved.await? {
+                    profiles.push(saved);
+                    continue;
+                }
                 let profile = extract_table_profile_pg(&pool, table_name, schema, options).await?;
                 if let Some(p) = profile {
+                    progress.record(table, &p)?;
                     profiles.push(p);
                 }
             }
@@ -214,9 +1218,19 @@ pub async fn extract_distributions(
                 })?;
 
             for table_name in &tables_to_sample {
+                let table = &schema.tables[*table_name];
+                let sql = row_count_query(table_name, DatabaseType::MySQL);
+                let saved = saved_profile(&progress, options, table, || {
+                    sqlx::query_as::<_, (i64,)>(&sql).fetch_one(&pool)
+                });
+                if let Some(saved) = saved.await? {
+                    profiles.push(saved);
+                    continue;
+                }
                 let profile =
                     extract_table_profile_mysql(&pool, table_name, schema, options).await?;
                 if let Some(p) = profile {
+                    progress.record(table, &p)?;
                     profiles.push(p);
                 }
             }
@@ -233,9 +1247,19 @@ pub async fn extract_distributions(
                 })?;
 
             for table_name in &tables_to_sample {
+                let table = &schema.tables[*table_name];
+                let sql = row_count_query(table_name, DatabaseType::SQLite);
+                let saved = saved_profile(&progress, options, table, || {
+                    sqlx::query_as::<_, (i64,)>(&sql).fetch_one(&pool)
+                });
+                if let Some(saved) = saved.await? {
+                    profiles.push(saved);
+                    continue;
+                }
                 let profile =
                     extract_table_profile_sqlite(&pool, table_name, schema, options).await?;
                 if let Some(p) = profile {
+                    progress.record(table, &p)?;
                     profiles.push(p);
                 }
             }
@@ -243,12 +1267,38 @@ pub async fn extract_distributions(
         }
     }
 
+    // Every table is sampled: nothing is left to resume
+    progress.remove()?;
+
     // Compute ratio distributions from FK relationships
     compute_ratio_distributions(&mut profiles, schema);
 
     Ok(profiles)
 }
 
+/// The profile of `table` saved by an earlier run, if its row count,
+/// counted by `count`, is still close to the saved one.
+async fn saved_profile<F, Fut>(
+    progress: &SampleProgress,
+    options: &SampleOptions,
+    table: &Table,
+    count: F,
+) -> Result<Option<DistributionProfile>>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = sqlx::Result<(i64,)>>,
+{
+    if !progress.contains(&table.name) {
+        return Ok(None);
+    }
+    let current = row_count(options, &table.name, count).await?;
+    let saved = current.and_then(|rows| progress.reuse(table, rows, options.resample_threshold));
+    if saved.is_some() {
+        tracing::info!("{}: unchanged since last sampled, reusing", table.name);
+    }
+    Ok(saved.cloned())
+}
+
 async fn extract_table_profile_pg(
     pool: &sqlx::PgPool,
     table_name: &str,
@@ -259,15 +1309,13 @@ async fn extract_table_profile_pg(
 
     // Get row count
     let count_sql = row_count_query(table_name, DatabaseType::PostgreSQL);
-    let row: (i64,) = sqlx::query_as(&count_sql)
-        .fetch_one(pool)
-        .await
-        .map_err(|e| SeedKitError::Introspection {
-            query: format!("row count for {}", table_name),
-            source: e,
-        })?;
-
-    let row_count = row.0 as u64;
+    let Some(row_count) = row_count(options, table_name, || {
+        sqlx::query_as::<_, (i64,)>(&count_sql).fetch_one(pool)
+    })
+    .await?
+    else {
+        return Ok(None);
+    };
     if row_count < options.min_row_count {
         return Ok(None);
     }
@@ -278,6 +1326,8 @@ async fn extract_table_profile_pg(
     };
 
     let mut distributions = HashMap::new();
+    let mut text_profiles = HashMap::new();
+    let mut temporal_profiles = HashMap::new();
 
     for (col_name, column) in &table.columns {
         // Skip auto-increment PKs
@@ -287,27 +1337,156 @@ async fn extract_table_profile_pg(
 
         if column.data_type.is_numeric() {
             let sql = numeric_query(table_name, col_name, DatabaseType::PostgreSQL);
-            let result = sqlx::query(&sql).fetch_one(pool).await;
-            if let Ok(row) = result {
+            let target = format!("{}.{}", table_name, col_name);
+            let result = sample_query(options, &target, || sqlx::query(&sql).fetch_one(pool)).await;
+            if let Some(row) = result {
                 let min_val: Option<f64> = row.get("min_val");
                 let max_val: Option<f64> = row.get("max_val");
                 let mean_val: Option<f64> = row.get("mean_val");
                 let stddev_val: Option<f64> = row.get("stddev_val");
+                let count_val: Option<i64> = row.get("count_val");
                 if let (Some(min), Some(max), Some(mean), Some(stddev)) =
                     (min_val, max_val, mean_val, stddev_val)
                 {
-                    distributions.insert(
-                        col_name.clone(),
-                        ColumnDistribution::Numeric {
+                    let mut quantiles = Vec::new();
+                    for &q in &options.quantiles {
+                        let offset = quantile_offset(q, count_val.unwrap_or(0).max(0) as u64);
+                        let sql =
+                            quantile_query(table_name, col_name, DatabaseType::PostgreSQL, offset);
+                        let result =
+                            sample_query(options, &target, || sqlx::query(&sql).fetch_one(pool))
+                                .await;
+                        if let Some(value) = result.and_then(|r| r.get::<Option<f64>, _>("val")) {
+                            quantiles.push((q, value));
+                        }
+                    }
+                    let histogram = if options.histogram_buckets > 0 && max > min {
+                        let sql = histogram_query(
+                            table_name,
+                            col_name,
+                            DatabaseType::PostgreSQL,
+                            min,
+                            max,
+                            options.histogram_buckets,
+                        );
+                        sample_query(options, &target, || sqlx::query(&sql).fetch_all(pool))
+                            .await
+                            .and_then(|rows| {
+                                let counts: Vec<(i64, i64)> = rows
+                                    .iter()
+                                    .filter_map(|r| {
+                                        let bucket: Option<i64> = r.get("bucket");
+                                        let cnt: Option<i64> = r.get("cnt");
+                                        bucket.zip(cnt)
+                                    })
+                                    .collect();
+                                histogram_from_counts(min, max, options.histogram_buckets, &counts)
+                            })
+                    } else {
+                        None
+                    };
+                    let distribution = match histogram {
+                        Some(histogram) => with_quantiles(histogram, quantiles),
+                        None => ColumnDistribution::Numeric {
                             min,
                             max,
                             mean,
                             stddev,
+                            quantiles,
                         },
-                    );
+                    };
+                    distributions.insert(col_name.clone(), distribution);
+                }
+            }
+        } else if matches!(column.data_type, DataType::Json | DataType::Jsonb) {
+            let sql = sample_values_query(
+                table_name,
+                col_name,
+                DatabaseType::PostgreSQL,
+                options.json_sample_size,
+            );
+            let target = format!("{}.{}", table_name, col_name);
+            let result = sample_query(options, &target, || sqlx::query(&sql).fetch_all(pool)).await;
+            if let Some(rows) = result {
+                let documents: Vec<String> = rows
+                    .iter()
+                    .filter_map(|r| r.get::<Option<String>, _>("val"))
+                    .collect();
+                let shape = profile_json(documents.iter().map(String::as_str));
+                if !shape.types.is_empty() {
+                    distributions.insert(col_name.clone(), ColumnDistribution::Json { shape });
+                }
+            }
+        } else if options.text_models && is_free_text(table_name, col_name, column) {
+            let sql = sample_values_query(
+                table_name,
+                col_name,
+                DatabaseType::PostgreSQL,
+                options.text_sample_size,
+            );
+            let target = format!("{}.{}", table_name, col_name);
+            let result = sample_query(options, &target, || sqlx::query(&sql).fetch_all(pool)).await;
+            if let Some(rows) = result {
+                let values: Vec<String> = rows
+                    .iter()
+                    .filter_map(|r| r.get::<Option<String>, _>("val"))
+                    .collect();
+                if let Some(model) =
+                    TextModel::train(values.iter().map(String::as_str), TEXT_MODEL_ORDER)
+                {
+                    distributions.insert(col_name.clone(), ColumnDistribution::Text { model });
+                }
+            }
+        } else if matches!(
+            column.data_type,
+            DataType::Date | DataType::Timestamp | DataType::TimestampTz
+        ) {
+            if options.temporal_patterns {
+                let sql = temporal_query(table_name, col_name, DatabaseType::PostgreSQL);
+                let target = format!("{}.{}", table_name, col_name);
+                let result =
+                    sample_query(options, &target, || sqlx::query(&sql).fetch_all(pool)).await;
+                if let Some(rows) = result {
+                    let counts: Vec<(i64, i64, i64, i64)> = rows
+                        .iter()
+                        .filter_map(|r| {
+                            let hour: Option<i64> = r.get("hour_val");
+                            let dow: Option<i64> = r.get("dow_val");
+                            let month: Option<i64> = r.get("month_val");
+                            let cnt: i64 = r.get("cnt");
+                            Some((hour?, dow?, month?, cnt))
+                        })
+                        .collect();
+                    if let Some(profile) = temporal_from_counts(&counts) {
+                        temporal_profiles.insert(col_name.clone(), profile);
+                    }
                 }
             }
         } else if column.data_type.is_string() || column.enum_values.is_some() {
+            let target = format!("{}.{}", table_name, col_name);
+            let sample: Vec<String> =
+                if options.string_sample_size > 0 && column.data_type.is_string() {
+                    let sql = sample_values_query(
+                        table_name,
+                        col_name,
+                        DatabaseType::PostgreSQL,
+                        options.string_sample_size,
+                    );
+                    sample_query(options, &target, || sqlx::query(&sql).fetch_all(pool))
+                        .await
+                        .map(|rows| {
+                            rows.iter()
+                                .filter_map(|r| r.get::<Option<String>, _>("val"))
+                                .collect()
+                        })
+                        .unwrap_or_default()
+                } else {
+                    Vec::new()
+                };
+            if let Some(profile) = profile_text(sample.iter().map(String::as_str)) {
+                text_profiles.insert(col_name.clone(), profile);
+            }
+
             // Only sample categoricals if cardinality is reasonable
             let sql = categorical_query(
                 table_name,
@@ -315,8 +1494,8 @@ async fn extract_table_profile_pg(
                 DatabaseType::PostgreSQL,
                 options.categorical_limit,
             );
-            let result = sqlx::query(&sql).fetch_all(pool).await;
-            if let Ok(rows) = result {
+            let result = sample_query(options, &target, || sqlx::query(&sql).fetch_all(pool)).await;
+            if let Some(rows) = result {
                 let values: Vec<(String, f64)> = rows
                     .iter()
                     .filter_map(|r| {
@@ -325,7 +1504,8 @@ async fn extract_table_profile_pg(
                         val.zip(freq)
                     })
                     .collect();
-                if !values.is_empty() {
+                if !values.is_empty() && is_categorical(&values, options.categorical_limit, &sample)
+                {
                     distributions
                         .insert(col_name.clone(), ColumnDistribution::Categorical { values });
                 }
@@ -333,10 +1513,105 @@ async fn extract_table_profile_pg(
         }
     }
 
+    let mut null_rates = HashMap::new();
+    let nullable = nullable_columns(table);
+    if !nullable.is_empty() {
+        let sql = null_count_query(table_name, &nullable, DatabaseType::PostgreSQL);
+        let result = sample_query(options, table_name, || sqlx::query(&sql).fetch_one(pool)).await;
+        if let Some(row) = result {
+            let non_null: Vec<i64> = (0..nullable.len())
+                .map(|i| row.get::<i64, _>(format!("n{}", i).as_str()))
+                .collect();
+            let total: i64 = row.get("total");
+            null_rates = null_rates_from_counts(&nullable, &non_null, total);
+        }
+    }
+
+    let mut distinct_counts = HashMap::new();
+    let countable = countable_columns(table);
+    if options.distinct_counts && !countable.is_empty() {
+        let sql = distinct_count_query(table_name, &countable, DatabaseType::PostgreSQL);
+        let result = sample_query(options, table_name, || sqlx::query(&sql).fetch_one(pool)).await;
+        if let Some(row) = result {
+            for (i, col) in countable.iter().enumerate() {
+                let distinct: i64 = row.get(format!("d{}", i).as_str());
+                distinct_counts.insert(col.to_string(), distinct.max(0) as u64);
+            }
+        }
+    }
+
+    let mut correlations = Vec::new();
+    if options.correlations {
+        for (col_a, col_b) in correlation_candidates(table, &distributions) {
+            let sql = correlation_query(table_name, &col_a, &col_b, DatabaseType::PostgreSQL);
+            let target = format!("{}.({}, {})", table_name, col_a, col_b);
+            let result = sample_query(options, &target, || sqlx::query(&sql).fetch_one(pool)).await;
+            if let Some(row) = result {
+                let moment = |name: &str| row.get::<Option<f64>, _>(name);
+                let r = match (
+                    moment("mean_a"),
+                    moment("mean_b"),
+                    moment("mean_aa"),
+                    moment("mean_bb"),
+                    moment("mean_ab"),
+                ) {
+                    (Some(a), Some(b), Some(aa), Some(bb), Some(ab)) => {
+                        correlation_from_moments(a, b, aa, bb, ab)
+                    }
+                    _ => None,
+                };
+                if let Some(r) = r {
+                    correlations.push((col_a, col_b, r));
+                }
+            }
+        }
+    }
+
+    let mut fan_outs = HashMap::new();
+    if options.fan_outs {
+        for fk in table
+            .foreign_keys
+            .iter()
+            .filter(|fk| fk.source_columns.len() == 1)
+        {
+            let col = &fk.source_columns[0];
+            let sql = fan_out_query(table_name, col, DatabaseType::PostgreSQL);
+            let target = format!("{}.{}", table_name, col);
+            let result = sample_query(options, &target, || {
+                sqlx::query_as::<_, (i64, i64)>(&sql).fetch_all(pool)
+            })
+            .await;
+            if let Some(fan_out) = result.and_then(|rows| fan_out_from_counts(&rows)) {
+                fan_outs.insert(col.clone(), fan_out);
+            }
+        }
+    }
+
+    let unique_pairs = if options.detect_unique_pairs {
+        probe_unique_pairs(
+            options,
+            table,
+            table_name,
+            &distributions,
+            DatabaseType::PostgreSQL,
+            |sql| async move { sqlx::query_as(&sql).fetch_one(pool).await },
+        )
+        .await
+    } else {
+        Vec::new()
+    };
+
     Ok(Some(DistributionProfile {
         table_name: table_name.to_string(),
         row_count,
+        unique_pairs,
         column_distributions: distributions,
+        text_profiles,
+        null_rates,
+        distinct_counts,
+        correlations,
+        temporal_profiles,
+        fan_outs,
     }))
 }
 
@@ -349,15 +1624,13 @@ async fn extract_table_profile_mysql(
     use sqlx::Row;
 
     let count_sql = row_count_query(table_name, DatabaseType::MySQL);
-    let row: (i64,) = sqlx::query_as(&count_sql)
-        .fetch_one(pool)
-        .await
-        .map_err(|e| SeedKitError::Introspection {
-            query: format!("row count for {}", table_name),
-            source: e,
-        })?;
-
-    let row_count = row.0 as u64;
+    let Some(row_count) = row_count(options, table_name, || {
+        sqlx::query_as::<_, (i64,)>(&count_sql).fetch_one(pool)
+    })
+    .await?
+    else {
+        return Ok(None);
+    };
     if row_count < options.min_row_count {
         return Ok(None);
     }
@@ -368,6 +1641,8 @@ async fn extract_table_profile_mysql(
     };
 
     let mut distributions = HashMap::new();
+    let mut text_profiles = HashMap::new();
+    let mut temporal_profiles = HashMap::new();
 
     for (col_name, column) in &table.columns {
         if column.is_auto_increment || column.data_type.is_serial() {
@@ -376,35 +1651,164 @@ async fn extract_table_profile_mysql(
 
         if column.data_type.is_numeric() {
             let sql = numeric_query(table_name, col_name, DatabaseType::MySQL);
-            let result = sqlx::query(&sql).fetch_one(pool).await;
-            if let Ok(row) = result {
+            let target = format!("{}.{}", table_name, col_name);
+            let result = sample_query(options, &target, || sqlx::query(&sql).fetch_one(pool)).await;
+            if let Some(row) = result {
                 let min_val: Option<f64> = row.get("min_val");
                 let max_val: Option<f64> = row.get("max_val");
                 let mean_val: Option<f64> = row.get("mean_val");
                 let stddev_val: Option<f64> = row.get("stddev_val");
+                let count_val: Option<i64> = row.get("count_val");
                 if let (Some(min), Some(max), Some(mean), Some(stddev)) =
                     (min_val, max_val, mean_val, stddev_val)
                 {
-                    distributions.insert(
-                        col_name.clone(),
-                        ColumnDistribution::Numeric {
+                    let mut quantiles = Vec::new();
+                    for &q in &options.quantiles {
+                        let offset = quantile_offset(q, count_val.unwrap_or(0).max(0) as u64);
+                        let sql = quantile_query(table_name, col_name, DatabaseType::MySQL, offset);
+                        let result =
+                            sample_query(options, &target, || sqlx::query(&sql).fetch_one(pool))
+                                .await;
+                        if let Some(value) = result.and_then(|r| r.get::<Option<f64>, _>("val")) {
+                            quantiles.push((q, value));
+                        }
+                    }
+                    let histogram = if options.histogram_buckets > 0 && max > min {
+                        let sql = histogram_query(
+                            table_name,
+                            col_name,
+                            DatabaseType::MySQL,
+                            min,
+                            max,
+                            options.histogram_buckets,
+                        );
+                        sample_query(options, &target, || sqlx::query(&sql).fetch_all(pool))
+                            .await
+                            .and_then(|rows| {
+                                let counts: Vec<(i64, i64)> = rows
+                                    .iter()
+                                    .filter_map(|r| {
+                                        let bucket: Option<i64> = r.get("bucket");
+                                        let cnt: Option<i64> = r.get("cnt");
+                                        bucket.zip(cnt)
+                                    })
+                                    .collect();
+                                histogram_from_counts(min, max, options.histogram_buckets, &counts)
+                            })
+                    } else {
+                        None
+                    };
+                    let distribution = match histogram {
+                        Some(histogram) => with_quantiles(histogram, quantiles),
+                        None => ColumnDistribution::Numeric {
                             min,
                             max,
                             mean,
                             stddev,
+                            quantiles,
                         },
-                    );
+                    };
+                    distributions.insert(col_name.clone(), distribution);
+                }
+            }
+        } else if matches!(column.data_type, DataType::Json | DataType::Jsonb) {
+            let sql = sample_values_query(
+                table_name,
+                col_name,
+                DatabaseType::MySQL,
+                options.json_sample_size,
+            );
+            let target = format!("{}.{}", table_name, col_name);
+            let result = sample_query(options, &target, || sqlx::query(&sql).fetch_all(pool)).await;
+            if let Some(rows) = result {
+                let documents: Vec<String> = rows
+                    .iter()
+                    .filter_map(|r| r.get::<Option<String>, _>("val"))
+                    .collect();
+                let shape = profile_json(documents.iter().map(String::as_str));
+                if !shape.types.is_empty() {
+                    distributions.insert(col_name.clone(), ColumnDistribution::Json { shape });
+                }
+            }
+        } else if options.text_models && is_free_text(table_name, col_name, column) {
+            let sql = sample_values_query(
+                table_name,
+                col_name,
+                DatabaseType::MySQL,
+                options.text_sample_size,
+            );
+            let target = format!("{}.{}", table_name, col_name);
+            let result = sample_query(options, &target, || sqlx::query(&sql).fetch_all(pool)).await;
+            if let Some(rows) = result {
+                let values: Vec<String> = rows
+                    .iter()
+                    .filter_map(|r| r.get::<Option<String>, _>("val"))
+                    .collect();
+                if let Some(model) =
+                    TextModel::train(values.iter().map(String::as_str), TEXT_MODEL_ORDER)
+                {
+                    distributions.insert(col_name.clone(), ColumnDistribution::Text { model });
+                }
+            }
+        } else if matches!(
+            column.data_type,
+            DataType::Date | DataType::Timestamp | DataType::TimestampTz
+        ) {
+            if options.temporal_patterns {
+                let sql = temporal_query(table_name, col_name, DatabaseType::MySQL);
+                let target = format!("{}.{}", table_name, col_name);
+                let result =
+                    sample_query(options, &target, || sqlx::query(&sql).fetch_all(pool)).await;
+                if let Some(rows) = result {
+                    let counts: Vec<(i64, i64, i64, i64)> = rows
+                        .iter()
+                        .filter_map(|r| {
+                            let hour: Option<i64> = r.get("hour_val");
+                            let dow: Option<i64> = r.get("dow_val");
+                            let month: Option<i64> = r.get("month_val");
+                            let cnt: i64 = r.get("cnt");
+                            Some((hour?, dow?, month?, cnt))
+                        })
+                        .collect();
+                    if let Some(profile) = temporal_from_counts(&counts) {
+                        temporal_profiles.insert(col_name.clone(), profile);
+                    }
                 }
             }
         } else if column.data_type.is_string() || column.enum_values.is_some() {
+            let target = format!("{}.{}", table_name, col_name);
+            let sample: Vec<String> =
+                if options.string_sample_size > 0 && column.data_type.is_string() {
+                    let sql = sample_values_query(
+                        table_name,
+                        col_name,
+                        DatabaseType::MySQL,
+                        options.string_sample_size,
+                    );
+                    sample_query(options, &target, || sqlx::query(&sql).fetch_all(pool))
+                        .await
+                        .map(|rows| {
+                            rows.iter()
+                                .filter_map(|r| r.get::<Option<String>, _>("val"))
+                                .collect()
+                        })
+                        .unwrap_or_default()
+                } else {
+                    Vec::new()
+                };
+            if let Some(profile) = profile_text(sample.iter().map(String::as_str)) {
+                text_profiles.insert(col_name.clone(), profile);
+            }
+
+            // Only sample categoricals if cardinality is reasonable
             let sql = categorical_query(
                 table_name,
                 col_name,
                 DatabaseType::MySQL,
                 options.categorical_limit,
             );
-            let result = sqlx::query(&sql).fetch_all(pool).await;
-            if let Ok(rows) = result {
+            let result = sample_query(options, &target, || sqlx::query(&sql).fetch_all(pool)).await;
+            if let Some(rows) = result {
                 let values: Vec<(String, f64)> = rows
                     .iter()
                     .filter_map(|r| {
@@ -413,7 +1817,8 @@ async fn extract_table_profile_mysql(
                         val.zip(freq)
                     })
                     .collect();
-                if !values.is_empty() {
+                if !values.is_empty() && is_categorical(&values, options.categorical_limit, &sample)
+                {
                     distributions
                         .insert(col_name.clone(), ColumnDistribution::Categorical { values });
                 }
@@ -421,10 +1826,105 @@ async fn extract_table_profile_mysql(
         }
     }
 
+    let mut null_rates = HashMap::new();
+    let nullable = nullable_columns(table);
+    if !nullable.is_empty() {
+        let sql = null_count_query(table_name, &nullable, DatabaseType::MySQL);
+        let result = sample_query(options, table_name, || sqlx::query(&sql).fetch_one(pool)).await;
+        if let Some(row) = result {
+            let non_null: Vec<i64> = (0..nullable.len())
+                .map(|i| row.get::<i64, _>(format!("n{}", i).as_str()))
+                .collect();
+            let total: i64 = row.get("total");
+            null_rates = null_rates_from_counts(&nullable, &non_null, total);
+        }
+    }
+
+    let mut distinct_counts = HashMap::new();
+    let countable = countable_columns(table);
+    if options.distinct_counts && !countable.is_empty() {
+        let sql = distinct_count_query(table_name, &countable, DatabaseType::MySQL);
+        let result = sample_query(options, table_name, || sqlx::query(&sql).fetch_one(pool)).await;
+        if let Some(row) = result {
+            for (i, col) in countable.iter().enumerate() {
+                let distinct: i64 = row.get(format!("d{}", i).as_str());
+                distinct_counts.insert(col.to_string(), distinct.max(0) as u64);
+            }
+        }
+    }
+
+    let mut correlations = Vec::new();
+    if options.correlations {
+        for (col_a, col_b) in correlation_candidates(table, &distributions) {
+            let sql = correlation_query(table_name, &col_a, &col_b, DatabaseType::MySQL);
+            let target = format!("{}.({}, {})", table_name, col_a, col_b);
+            let result = sample_query(options, &target, || sqlx::query(&sql).fetch_one(pool)).await;
+            if let Some(row) = result {
+                let moment = |name: &str| row.get::<Option<f64>, _>(name);
+                let r = match (
+                    moment("mean_a"),
+                    moment("mean_b"),
+                    moment("mean_aa"),
+                    moment("mean_bb"),
+                    moment("mean_ab"),
+                ) {
+                    (Some(a), Some(b), Some(aa), Some(bb), Some(ab)) => {
+                        correlation_from_moments(a, b, aa, bb, ab)
+                    }
+                    _ => None,
+                };
+                if let Some(r) = r {
+                    correlations.push((col_a, col_b, r));
+                }
+            }
+        }
+    }
+
+    let mut fan_outs = HashMap::new();
+    if options.fan_outs {
+        for fk in table
+            .foreign_keys
+            .iter()
+            .filter(|fk| fk.source_columns.len() == 1)
+        {
+            let col = &fk.source_columns[0];
+            let sql = fan_out_query(table_name, col, DatabaseType::MySQL);
+            let target = format!("{}.{}", table_name, col);
+            let result = sample_query(options, &target, || {
+                sqlx::query_as::<_, (i64, i64)>(&sql).fetch_all(pool)
+            })
+            .await;
+            if let Some(fan_out) = result.and_then(|rows| fan_out_from_counts(&rows)) {
+                fan_outs.insert(col.clone(), fan_out);
+            }
+        }
+    }
+
+    let unique_pairs = if options.detect_unique_pairs {
+        probe_unique_pairs(
+            options,
+            table,
+            table_name,
+            &distributions,
+            DatabaseType::MySQL,
+            |sql| async move { sqlx::query_as(&sql).fetch_one(pool).await },
+        )
+        .await
+    } else {
+        Vec::new()
+    };
+
     Ok(Some(DistributionProfile {
         table_name: table_name.to_string(),
         row_count,
+        unique_pairs,
         column_distributions: distributions,
+        text_profiles,
+        null_rates,
+        distinct_counts,
+        correlations,
+        temporal_profiles,
+        fan_outs,
     }))
 }
 
@@ -437,15 +1937,13 @@ async fn extract_table_profile_sqlite(
     use sqlx::Row;
 
     let count_sql = row_count_query(table_name, DatabaseType::SQLite);
-    let row: (i64,) = sqlx::query_as(&count_sql)
-        .fetch_one(pool)
-        .await
-        .map_err(|e| SeedKitError::Introspection {
-            query: format!("row count for {}", table_name),
-            source: e,
-        })?;
-
-    let row_count = row.0 as u64;
+    let Some(row_count) = row_count(options, table_name, || {
+        sqlx::query_as::<_, (i64,)>(&count_sql).fetch_one(pool)
+    })
+    .await?
+    else {
+        return Ok(None);
+    };
     if row_count < options.min_row_count {
         return Ok(None);
     }
@@ -456,6 +1954,8 @@ async fn extract_table_profile_sqlite(
     };
 
     let mut distributions = HashMap::new();
+    let mut text_profiles = HashMap::new();
+    let mut temporal_profiles = HashMap::new();
 
     for (col_name, column) in &table.columns {
         if column.is_auto_increment || column.data_type.is_serial() {
@@ -464,35 +1964,165 @@ async fn extract_table_profile_sqlite(
 
         if column.data_type.is_numeric() {
             let sql = numeric_query(table_name, col_name, DatabaseType::SQLite);
-            let result = sqlx::query(&sql).fetch_one(pool).await;
-            if let Ok(row) = result {
+            let target = format!("{}.{}", table_name, col_name);
+            let result = sample_query(options, &target, || sqlx::query(&sql).fetch_one(pool)).await;
+            if let Some(row) = result {
                 let min_val: Option<f64> = row.get("min_val");
                 let max_val: Option<f64> = row.get("max_val");
                 let mean_val: Option<f64> = row.get("mean_val");
                 let stddev_val: Option<f64> = row.get("stddev_val");
+                let count_val: Option<i64> = row.get("count_val");
                 if let (Some(min), Some(max), Some(mean), Some(stddev)) =
                     (min_val, max_val, mean_val, stddev_val)
                 {
-                    distributions.insert(
-                        col_name.clone(),
-                        ColumnDistribution::Numeric {
+                    let mut quantiles = Vec::new();
+                    for &q in &options.quantiles {
+                        let offset = quantile_offset(q, count_val.unwrap_or(0).max(0) as u64);
+                        let sql =
+                            quantile_query(table_name, col_name, DatabaseType::SQLite, offset);
+                        let result =
+                            sample_query(options, &target, || sqlx::query(&sql).fetch_one(pool))
+                                .await;
+                        if let Some(value) = result.and_then(|r| r.get::<Option<f64>, _>("val")) {
+                            quantiles.push((q, value));
+                        }
+                    }
+                    let histogram = if options.histogram_buckets > 0 && max > min {
+                        let sql = histogram_query(
+                            table_name,
+                            col_name,
+                            DatabaseType::SQLite,
+                            min,
+                            max,
+                            options.histogram_buckets,
+                        );
+                        sample_query(options, &target, || sqlx::query(&sql).fetch_all(pool))
+                            .await
+                            .and_then(|rows| {
+                                let counts: Vec<(i64, i64)> = rows
+                                    .iter()
+                                    .filter_map(|r| {
+                                        let bucket: Option<i64> = r.get("bucket");
+                                        let cnt: Option<i64> = r.get("cnt");
+                                        bucket.zip(cnt)
+                                    })
+                                    .collect();
+                                histogram_from_counts(min, max, options.histogram_buckets, &counts)
+                            })
+                    } else {
+                        None
+                    };
+                    let distribution = match histogram {
+                        Some(histogram) => with_quantiles(histogram, quantiles),
+                        None => ColumnDistribution::Numeric {
                             min,
                             max,
                             mean,
                             stddev,
+                            quantiles,
                         },
-                    );
+                    };
+                    distributions.insert(col_name.clone(), distribution);
+                }
+            }
+        } else if matches!(column.data_type, DataType::Json | DataType::Jsonb) {
+            let sql = sample_values_query(
+                table_name,
+                col_name,
+                DatabaseType::SQLite,
+                options.json_sample_size,
+            );
+            let target = format!("{}.{}", table_name, col_name);
+            let result = sample_query(options, &target, || sqlx::query(&sql).fetch_all(pool)).await;
+            if let Some(rows) = result {
+                let documents: Vec<String> = rows
+                    .iter()
+                    .filter_map(|r| r.get::<Option<String>, _>("val"))
+                    .collect();
+                let shape = profile_json(documents.iter().map(String::as_str));
+                if !shape.types.is_empty() {
+                    distributions.insert(col_name.clone(), ColumnDistribution::Json { shape });
+                }
+            }
+        } else if options.text_models && is_free_text(table_name, col_name, column) {
+            let sql = sample_values_query(
+                table_name,
+                col_name,
+                DatabaseType::SQLite,
+                options.text_sample_size,
+            );
+            let target = format!("{}.{}", table_name, col_name);
+            let result = sample_query(options, &target, || sqlx::query(&sql).fetch_all(pool)).await;
+            if let Some(rows) = result {
+                let values: Vec<String> = rows
+                    .iter()
+                    .filter_map(|r| r.get::<Option<String>, _>("val"))
+                    .collect();
+                if let Some(model) =
+                    TextModel::train(values.iter().map(String::as_str), TEXT_MODEL_ORDER)
+                {
+                    distributions.insert(col_name.clone(), ColumnDistribution::Text { model });
+                }
+            }
+        } else if matches!(
+            column.data_type,
+            DataType::Date | DataType::Timestamp | DataType::TimestampTz
+        ) {
+            if options.temporal_patterns {
+                let sql = temporal_query(table_name, col_name, DatabaseType::SQLite);
+                let target = format!("{}.{}", table_name, col_name);
+                let result =
+                    sample_query(options, &target, || sqlx::query(&sql).fetch_all(pool)).await;
+                if let Some(rows) = result {
+                    let counts: Vec<(i64, i64, i64, i64)> = rows
+                        .iter()
+                        .filter_map(|r| {
+                            let hour: Option<i64> = r.get("hour_val");
+                            let dow: Option<i64> = r.get("dow_val");
+                            let month: Option<i64> = r.get("month_val");
+                            let cnt: i64 = r.get("cnt");
+                            Some((hour?, dow?, month?, cnt))
+                        })
+                        .collect();
+                    if let Some(profile) = temporal_from_counts(&counts) {
+                        temporal_profiles.insert(col_name.clone(), profile);
+                    }
                 }
             }
         } else if column.data_type.is_string() || column.enum_values.is_some() {
+            let target = format!("{}.{}", table_name, col_name);
+            let sample: Vec<String> =
+                if options.string_sample_size > 0 && column.data_type.is_string() {
+                    let sql = sample_values_query(
+                        table_name,
+                        col_name,
+                        DatabaseType::SQLite,
+                        options.string_sample_size,
+                    );
+                    sample_query(options, &target, || sqlx::query(&sql).fetch_all(pool))
+                        .await
+                        .map(|rows| {
+                            rows.iter()
+                                .filter_map(|r| r.get::<Option<String>, _>("val"))
+                                .collect()
+                        })
+                        .unwrap_or_default()
+                } else {
+                    Vec::new()
+                };
+            if let Some(profile) = profile_text(sample.iter().map(String::as_str)) {
+                text_profiles.insert(col_name.clone(), profile);
+            }
+
+            // Only sample categoricals if cardinality is reasonable
             let sql = categorical_query(
                 table_name,
                 col_name,
                 DatabaseType::SQLite,
                 options.categorical_limit,
             );
-            let result = sqlx::query(&sql).fetch_all(pool).await;
-            if let Ok(rows) = result {
+            let result = sample_query(options, &target, || sqlx::query(&sql).fetch_all(pool)).await;
+            if let Some(rows) = result {
                 let values: Vec<(String, f64)> = rows
                     .iter()
                     .filter_map(|r| {
@@ -501,7 +2131,8 @@ async fn extract_table_profile_sqlite(
                         val.zip(freq)
                     })
                     .collect();
-                if !values.is_empty() {
+                if !values.is_empty() && is_categorical(&values, options.categorical_limit, &sample)
+                {
                     distributions
                         .insert(col_name.clone(), ColumnDistribution::Categorical { values });
                 }
@@ -509,10 +2140,105 @@ async fn extract_table_profile_sqlite(
         }
     }
 
+    let mut null_rates = HashMap::new();
+    let nullable = nullable_columns(table);
+    if !nullable.is_empty() {
+        let sql = null_count_query(table_name, &nullable, DatabaseType::SQLite);
+        let result = sample_query(options, table_name, || sqlx::query(&sql).fetch_one(pool)).await;
+        if let Some(row) = result {
+            let non_null: Vec<i64> = (0..nullable.len())
+                .map(|i| row.get::<i64, _>(format!("n{}", i).as_str()))
+                .collect();
+            let total: i64 = row.get("total");
+            null_rates = null_rates_from_counts(&nullable, &non_null, total);
+        }
+    }
+
+    let mut distinct_counts = HashMap::new();
+    let countable = countable_columns(table);
+    if options.distinct_counts && !countable.is_empty() {
+        let sql = distinct_count_query(table_name, &countable, DatabaseType::SQLite);
+        let result = sample_query(options, table_name, || sqlx::query(&sql).fetch_one(pool)).await;
+        if let Some(row) = result {
+            for (i, col) in countable.iter().enumerate() {
+                let distinct: i64 = row.get(format!("d{}", i).as_str());
+                distinct_counts.insert(col.to_string(), distinct.max(0) as u64);
+            }
+        }
+    }
+
+    let mut correlations = Vec::new();
+    if options.correlations {
+        for (col_a, col_b) in correlation_candidates(table, &distributions) {
+            let sql = correlation_query(table_name, &col_a, &col_b, DatabaseType::SQLite);
+            let target = format!("{}.({}, {})", table_name, col_a, col_b);
+            let result = sample_query(options, &target, || sqlx::query(&sql).fetch_one(pool)).await;
+            if let Some(row) = result {
+                let moment = |name: &str| row.get::<Option<f64>, _>(name);
+                let r = match (
+                    moment("mean_a"),
+                    moment("mean_b"),
+                    moment("mean_aa"),
+                    moment("mean_bb"),
+                    moment("mean_ab"),
+                ) {
+                    (Some(a), Some(b), Some(aa), Some(bb), Some(ab)) => {
+                        correlation_from_moments(a, b, aa, bb, ab)
+                    }
+                    _ => None,
+                };
+                if let Some(r) = r {
+                    correlations.push((col_a, col_b, r));
+                }
+            }
+        }
+    }
+
+    let mut fan_outs = HashMap::new();
+    if options.fan_outs {
+        for fk in table
+            .foreign_keys
+            .iter()
+            .filter(|fk| fk.source_columns.len() == 1)
+        {
+            let col = &fk.source_columns[0];
+            let sql = fan_out_query(table_name, col, DatabaseType::SQLite);
+            let target = format!("{}.{}", table_name, col);
+            let result = sample_query(options, &target, || {
+                sqlx::query_as::<_, (i64, i64)>(&sql).fetch_all(pool)
+            })
+            .await;
+            if let Some(fan_out) = result.and_then(|rows| fan_out_from_counts(&rows)) {
+                fan_outs.insert(col.clone(), fan_out);
+            }
+        }
+    }
+
+    let unique_pairs = if options.detect_unique_pairs {
+        probe_unique_pairs(
+            options,
+            table,
+            table_name,
+            &distributions,
+            DatabaseType::SQLite,
+            |sql| async move { sqlx::query_as(&sql).fetch_one(pool).await },
+        )
+        .await
+    } else {
+        Vec::new()
+    };
+
     Ok(Some(DistributionProfile {
         table_name: table_name.to_string(),
         row_count,
+        unique_pairs,
         column_distributions: distributions,
+        text_profiles,
+        null_rates,
+        distinct_counts,
+        correlations,
+        temporal_profiles,
+        fan_outs,
     }))
 }
 
@@ -624,6 +2350,62 @@ mod tests {
         assert!(!sql.contains("STDDEV"));
     }
 
+    #[test]
+    fn test_histogram_query() {
+        let sql = histogram_query("orders", "total", DatabaseType::PostgreSQL, 5.0, 105.0, 20);
+        assert!(sql.contains("LEAST(FLOOR((\"total\" - 5.0) * 20 / 100.0), 19)::bigint"));
+        assert!(sql.ends_with("GROUP BY 1"));
+        let sql = histogram_query("orders", "total", DatabaseType::SQLite, 5.0, 105.0, 20);
+        assert!(sql.contains("MIN(CAST((\"total\" - 5.0) * 20 / 100.0 AS INTEGER), 19)"));
+    }
+
+    #[test]
+    fn test_quantile_query() {
+        let sql = quantile_query("orders", "total", DatabaseType::MySQL, 989);
+        assert!(sql.starts_with("SELECT CAST(`total` AS DOUBLE) AS val"));
+        assert!(sql.ends_with("ORDER BY `total` LIMIT 1 OFFSET 989"));
+        assert_eq!(quantile_offset(0.5, 1000), 499);
+        assert_eq!(quantile_offset(0.99, 1000), 989);
+        assert_eq!(quantile_offset(0.99, 1), 0);
+        assert_eq!(quantile_offset(0.5, 0), 0);
+    }
+
+    #[test]
+    fn test_profile_text() {
+        let profile = profile_text(["SKU-0042", "SKU-1187", "Café 1"]).unwrap();
+        assert_eq!(profile.length, (6, 8));
+        assert_eq!(profile.digits, 1.0);
+        assert!((profile.whitespace - 1.0 / 3.0).abs() < 1e-9);
+        assert!((profile.non_ascii - 1.0 / 3.0).abs() < 1e-9);
+        assert!(profile_text([]).is_none());
+    }
+
+    #[test]
+    fn test_is_categorical() {
+        let top = |n: usize| -> Vec<(String, f64)> {
+            (0..n).map(|i| (format!("v{}", i), 0.01)).collect()
+        };
+        let sample: Vec<String> = (0..10).map(|i| format!("v{}", i * 10)).collect();
+        // Every distinct value was captured
+        assert!(is_categorical(&top(3), 50, &sample));
+        // At the limit, only v0 of the sample is among the top values
+        assert!(!is_categorical(&top(5), 5, &sample));
+        assert!(is_categorical(&top(50), 50, &sample));
+    }
+
+    #[test]
+    fn test_histogram_from_counts() {
+        // Bimodal: most values near either end
+        let counts = [(0, 45), (1, 5), (3, 40), (4, 10)];
+        match histogram_from_counts(0.0, 100.0, 4, &counts) {
+            Some(ColumnDistribution::Histogram { buckets, .. }) => {
+                assert_eq!(buckets, vec![0.45, 0.05, 0.0, 0.5]);
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(histogram_from_counts(0.0, 100.0, 4, &[]).is_none());
+    }
+
     #[test]
     fn test_ratio_query_postgres() {
         let sql = ratio_query("orders", "user_id", "users", DatabaseType::PostgreSQL);
@@ -632,18 +2414,248 @@ mod tests {
         assert!(sql.contains("::float"));
     }
 
+    #[test]
+    fn test_sample_values_query() {
+        let sql = sample_values_query("users", "settings", DatabaseType::MySQL, 200);
+        assert!(sql.contains("CAST(`settings` AS CHAR)"));
+        assert!(sql.ends_with("LIMIT 200"));
+    }
+
+    #[test]
+    fn test_is_free_text() {
+        use crate::schema::types::Column;
+        let text = Column::new(
+            "description".to_string(),
+            DataType::Text,
+            "text".to_string(),
+        );
+        assert!(is_free_text("products", "description", &text));
+        let sku = Column::new("sku".to_string(), DataType::Text, "text".to_string());
+        assert!(!is_free_text("products", "sku", &sku));
+    }
+
+    #[test]
+    fn test_profile_json_records_structure_only() {
+        let shape = profile_json([
+            r#"{"plan": "pro", "seats": [1, 2.5]}"#,
+            r#"{"plan": "free"}"#,
+            "not json",
+        ]);
+        assert_eq!(shape.keys.len(), 2);
+        assert_eq!(shape.keys[0].shape.string_length, Some((3, 4)));
+        assert_eq!(shape.keys[1].frequency, 0.5);
+        let items = shape.keys[1].shape.items.as_ref().unwrap();
+        assert_eq!(items.number_range, Some((1.0, 2.5)));
+        let json = serde_json::to_string(&shape).unwrap();
+        assert!(!json.contains("pro") && !json.contains("free"), "{}", json);
+    }
+
     #[test]
     fn test_row_count_query() {
         let sql = row_count_query("users", DatabaseType::PostgreSQL);
         assert_eq!(sql, "SELECT COUNT(*) AS cnt FROM \"users\"");
     }
 
+    #[test]
+    fn test_distinct_count_query_and_ratio() {
+        let sql = distinct_count_query("users", &["plan", "email"], DatabaseType::PostgreSQL);
+        assert_eq!(
+            sql,
+            "SELECT COUNT(DISTINCT \"plan\") AS d0, COUNT(DISTINCT \"email\") AS d1 FROM \"users\""
+        );
+
+        let profile = DistributionProfile {
+            table_name: "users".to_string(),
+            row_count: 1000,
+            unique_pairs: Vec::new(),
+            column_distributions: HashMap::new(),
+            text_profiles: HashMap::new(),
+            null_rates: HashMap::from([("nickname".to_string(), 0.5)]),
+            distinct_counts: HashMap::from([
+                ("email".to_string(), 1000),
+                ("nickname".to_string(), 100),
+            ]),
+            correlations: Vec::new(),
+            temporal_profiles: HashMap::new(),
+            fan_outs: HashMap::new(),
+        };
+        assert_eq!(profile.distinct_ratio("email"), Some(1.0));
+        assert_eq!(profile.distinct_ratio("nickname"), Some(0.2));
+        assert_eq!(profile.distinct_ratio("plan"), None);
+    }
+
+    #[test]
+    fn test_correlation_from_moments() {
+        // a = 1, 2, 3; b = 2, 4, 7
+        let (a, b) = ([1.0, 2.0, 3.0], [2.0, 4.0, 7.0]);
+        let mean = |f: &dyn Fn(usize) -> f64| (0..3).map(f).sum::<f64>() / 3.0;
+        let r = correlation_from_moments(
+            mean(&|i| a[i]),
+            mean(&|i| b[i]),
+            mean(&|i| a[i] * a[i]),
+            mean(&|i| b[i] * b[i]),
+            mean(&|i| a[i] * b[i]),
+        )
+        .unwrap();
+        assert!((r - 0.9934).abs() < 1e-3, "{}", r);
+
+        // Constant column, and uncorrelated columns
+        assert_eq!(correlation_from_moments(5.0, 1.0, 25.0, 2.0, 5.0), None);
+        assert_eq!(correlation_from_moments(0.0, 0.0, 1.0, 1.0, 0.01), None);
+
+        let sql = correlation_query("orders", "quantity", "total", DatabaseType::PostgreSQL);
+        assert!(sql.contains("AVG(\"quantity\"::float8 * \"total\"::float8) AS mean_ab"));
+    }
+
+    #[test]
+    fn test_temporal_query_and_profile() {
+        let sql = temporal_query("orders", "created_at", DatabaseType::SQLite);
+        assert!(sql.contains("CAST(strftime('%w', \"created_at\") AS INTEGER) AS dow_val"));
+        assert!(sql.ends_with("GROUP BY 1, 2, 3"));
+
+        // Weekday mornings in March, and one Saturday night in December
+        let profile = temporal_from_counts(&[
+            (9, 1, 3, 60),
+            (10, 2, 3, 30),
+            (23, 6, 12, 10),
+            (25, 0, 1, 5),
+        ])
+        .unwrap();
+        assert_eq!(profile.hours[9], 0.6);
+        assert_eq!(profile.hours[23], 0.1);
+        assert_eq!(profile.weekdays[6], 0.1);
+        assert_eq!(profile.months[2], 0.9);
+        assert_eq!(profile.months[0], 0.0, "out-of-range hour skipped");
+        assert!(temporal_from_counts(&[]).is_none());
+    }
+
+    #[test]
+    fn test_fan_out_query_and_counts() {
+        let sql = fan_out_query("orders", "user_id", DatabaseType::PostgreSQL);
+        assert!(sql.contains("FROM \"orders\" WHERE \"user_id\" IS NOT NULL GROUP BY \"user_id\""));
+        assert!(sql.ends_with("GROUP BY children ORDER BY children"));
+
+        let fan_out = fan_out_from_counts(&[(40, 1), (1, 700), (2, 250), (0, 3)]).unwrap();
+        assert_eq!(fan_out, vec![(1, 700), (2, 250), (40, 1)]);
+        assert!(fan_out_from_counts(&[]).is_none());
+    }
+
+    #[test]
+    fn test_null_count_query_and_rates() {
+        let sql = null_count_query("users", &["bio", "deleted_at"], DatabaseType::MySQL);
+        assert_eq!(
+            sql,
+            "SELECT COUNT(*) AS total, COUNT(`bio`) AS n0, COUNT(`deleted_at`) AS n1 FROM `users`"
+        );
+
+        let rates = null_rates_from_counts(&["bio", "deleted_at"], &[150, 200], 200);
+        assert_eq!(rates["bio"], 0.25);
+        assert_eq!(rates["deleted_at"], 0.0);
+        assert!(null_rates_from_counts(&["bio"], &[0], 0).is_empty());
+    }
+
     #[test]
     fn test_sample_options_default() {
         let opts = SampleOptions::default();
         assert_eq!(opts.categorical_limit, 50);
         assert_eq!(opts.min_row_count, 10);
         assert!(opts.tables.is_none());
+        assert!(opts.detect_unique_pairs);
+        assert!(opts.progress_file.is_none() && opts.resume);
+
+        let other = SampleOptions {
+            quantiles: vec![0.5],
+            ..SampleOptions::default()
+        };
+        assert_ne!(opts.fingerprint(), other.fingerprint());
+        let retried = SampleOptions {
+            max_attempts: 1,
+            ..SampleOptions::default()
+        };
+        assert_eq!(opts.fingerprint(), retried.fingerprint());
+    }
+
+    #[test]
+    fn test_unique_pair_query_postgres() {
+        let sql = unique_pair_query("memberships", "user_id", "org_id", DatabaseType::PostgreSQL);
+        assert!(sql.contains("GROUP BY \"user_id\", \"org_id\" HAVING COUNT(*) > 1"));
+        assert!(sql.contains("COUNT(DISTINCT \"user_id\") AS distinct_a"));
+        assert!(sql.contains("FROM \"memberships\""));
+    }
+
+    #[test]
+    fn test_unique_pair_candidates_skips_declared_and_unique_columns() {
+        use crate::schema::types::*;
+
+        let mut table = Table::new("memberships".to_string());
+        for name in ["id", "user_id", "org_id", "role", "email"] {
+            table.columns.insert(
+                name.to_string(),
+                Column::new(name.to_string(), DataType::Integer, "integer".to_string()),
+            );
+        }
+        table.primary_key = Some(PrimaryKey {
+            columns: vec!["id".to_string()],
+            name: None,
+        });
+        table.unique_constraints.push(UniqueConstraint {
+            name: None,
+            columns: vec!["email".to_string()],
+        });
+        for (col, target) in [("user_id", "users"), ("org_id", "orgs")] {
+            table.foreign_keys.push(ForeignKey {
+                name: None,
+                source_columns: vec![col.to_string()],
+                referenced_table: target.to_string(),
+                referenced_columns: vec!["id".to_string()],
+                on_delete: ForeignKeyAction::NoAction,
+                on_update: ForeignKeyAction::NoAction,
+                is_deferrable: false,
+            });
+        }
+
+        let mut distributions = HashMap::new();
+        distributions.insert(
+            "role".to_string(),
+            ColumnDistribution::Categorical {
+                values: vec![("admin".to_string(), 0.1)],
+            },
+        );
+        distributions.insert(
+            "email".to_string(),
+            ColumnDistribution::Categorical {
+                values: vec![("a@b.com".to_string(), 0.1)],
+            },
+        );
+
+        let pairs = unique_pair_candidates(&table, &distributions);
+        assert_eq!(
+            pairs,
+            vec![
+                ("user_id".to_string(), "org_id".to_string()),
+                ("user_id".to_string(), "role".to_string()),
+                ("org_id".to_string(), "role".to_string()),
+            ]
+        );
+
+        // A declared composite unique removes that pair from probing
+        table.unique_constraints.push(UniqueConstraint {
+            name: None,
+            columns: vec!["org_id".to_string(), "user_id".to_string()],
+        });
+        let pairs = unique_pair_candidates(&table, &distributions);
+        assert!(!pairs.contains(&("user_id".to_string(), "org_id".to_string())));
+    }
+
+    #[test]
+    fn test_is_implicit_unique_pair() {
+        assert!(is_implicit_unique_pair(0, 40, 12, 200, 10));
+        // Duplicated combinations
+        assert!(!is_implicit_unique_pair(3, 40, 12, 200, 10));
+        // One column unique on its own makes the pair uninformative
+        assert!(!is_implicit_unique_pair(0, 200, 12, 200, 10));
+        // Too few rows to trust
+        assert!(!is_implicit_unique_pair(0, 2, 2, 5, 10));
     }
 
     #[test]
@@ -651,6 +2663,13 @@ mod tests {
         let profile = DistributionProfile {
             table_name: "users".to_string(),
             row_count: 1000,
+            unique_pairs: Vec::new(),
+            text_profiles: HashMap::new(),
+            null_rates: HashMap::new(),
+            distinct_counts: HashMap::new(),
+            correlations: Vec::new(),
+            temporal_profiles: HashMap::new(),
+            fan_outs: HashMap::new(),
             column_distributions: {
                 let mut m = HashMap::new();
                 m.insert(
@@ -666,6 +2685,7 @@ mod tests {
                         max: 90.0,
                         mean: 35.0,
                         stddev: 12.5,
+                        quantiles: Vec::new(),
                     },
                 );
                 m.insert(
@@ -685,4 +2705,96 @@ mod tests {
         assert_eq!(restored.row_count, 1000);
         assert_eq!(restored.column_distributions.len(), 3);
     }
+
+    /// A database error carrying only a SQLSTATE.
+    #[derive(Debug)]
+    struct SqlState(&'static str);
+
+    impl std::fmt::Display for SqlState {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "SQLSTATE {}", self.0)
+        }
+    }
+
+    impl std::error::Error for SqlState {}
+
+    impl sqlx::error::DatabaseError for SqlState {
+        fn message(&self) -> &str {
+            "canceling statement due to conflict with recovery"
+        }
+
+        fn code(&self) -> Option<std::borrow::Cow<'_, str>> {
+            Some(self.0.into())
+        }
+
+        fn as_error(&self) -> &(dyn std::error::Error + Send + Sync + 'static) {
+            self
+        }
+
+        fn as_error_mut(&mut self) -> &mut (dyn std::error::Error + Send + Sync + 'static) {
+            self
+        }
+
+        fn into_error(self: Box<Self>) -> Box<dyn std::error::Error + Send + Sync + 'static> {
+            self
+        }
+
+        fn kind(&self) -> sqlx::error::ErrorKind {
+            sqlx::error::ErrorKind::Other
+        }
+    }
+
+    fn db_error(code: &'static str) -> sqlx::Error {
+        sqlx::Error::Database(Box::new(SqlState(code)))
+    }
+
+    #[test]
+    fn test_is_replica_conflict() {
+        assert!(is_replica_conflict(&db_error("40001")));
+        assert!(is_replica_conflict(&db_error("40P01")));
+        assert!(!is_replica_conflict(&db_error("42P01")));
+        assert!(!is_replica_conflict(&sqlx::Error::RowNotFound));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_sample_query_retries_replica_conflicts() {
+        let options = SampleOptions {
+            max_attempts: 3,
+            ..SampleOptions::default()
+        };
+
+        // Succeeds on the third attempt
+        let mut calls = 0;
+        let result = sample_query(&options, "users.email", || {
+            calls += 1;
+            let attempt = calls;
+            async move {
+                if attempt < 3 {
+                    Err(db_error("40001"))
+                } else {
+                    Ok(attempt)
+                }
+            }
+        })
+        .await;
+        assert_eq!(result, Some(3));
+
+        // Gives up after max_attempts and skips the column
+        let mut calls = 0;
+        let result: Option<()> = sample_query(&options, "users.email", || {
+            calls += 1;
+            async { Err(db_error("40001")) }
+        })
+        .await;
+        assert_eq!((result, calls), (None, 3));
+
+        // Other errors aren't retried
+        let mut calls = 0;
+        let result: Option<()> = sample_query(&options, "users.email", || {
+            calls += 1;
+            async { Err(db_error("42P01")) }
+        })
+        .await;
+        assert_eq!((result, calls), (None, 1));
+    }
 }
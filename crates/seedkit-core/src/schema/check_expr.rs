@@ -0,0 +1,704 @@
+//! # CHECK Constraint Parsing
+//!
+//! CHECK clauses come back from the catalog as SQL text, usually in the
+//! database's own normalized spelling, e.g.
+//! `((status)::text = ANY ((ARRAY['active'::character varying])::text[]))`.
+//! They are parsed into an expression tree with `sqlparser` and lowered into
+//! [`ParsedCheck`]s the providers know how to satisfy.
+//!
+//! Lowering is best effort. Parts of an `AND` that can't be lowered are
+//! dropped, so the columns they constrain may produce values the database
+//! rejects. An `OR` is lowered to one branch that is fully understood, which
+//! is stricter than the original but always satisfies it.
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use sqlparser::ast::{
+    BinaryOperator, DataType as SqlDataType, Expr, FunctionArg, FunctionArgExpr, FunctionArguments,
+    UnaryOperator, Value as SqlValue,
+};
+use sqlparser::dialect::PostgreSqlDialect;
+use sqlparser::parser::Parser;
+
+use crate::schema::types::ParsedCheck;
+
+/// Parse a CHECK expression into structured form. `None` when no part of it
+/// maps onto a [`ParsedCheck`].
+pub fn parse_check_constraint(expr: &str) -> Option<ParsedCheck> {
+    let dialect = PostgreSqlDialect {};
+    let ast = Parser::new(&dialect)
+        .try_with_sql(expr)
+        .ok()?
+        .parse_expr()
+        .ok()?;
+    lower(&ast, false)
+}
+
+/// Lower an expression. With `strict`, any part that can't be lowered
+/// fails the whole expression instead of being dropped.
+fn lower(expr: &Expr, strict: bool) -> Option<ParsedCheck> {
+    match expr {
+        Expr::Nested(inner) => lower(inner, strict),
+        Expr::BinaryOp {
+            left,
+            op: BinaryOperator::And,
+            right,
+        } => {
+            let mut checks = Vec::new();
+            for side in [left, right] {
+                match lower(side, strict) {
+                    Some(ParsedCheck::All(inner)) => checks.extend(inner),
+                    Some(check) => checks.push(check),
+                    None if strict => return None,
+                    None => {}
+                }
+            }
+            all_of(checks)
+        }
+        Expr::BinaryOp {
+            op: BinaryOperator::Or,
+            ..
+        } => lower_or(expr),
+        Expr::BinaryOp { left, op, right } => lower_comparison(left, op, right),
+        Expr::Between {
+            expr,
+            negated: false,
+            low,
+            high,
+        } => {
+            let column = column_name(expr)?;
+            match (literal(low)?, literal(high)?) {
+                (Literal::Date(low, _), Literal::Date(high, _)) => all_of(vec![
+                    ParsedCheck::After {
+                        column: column.clone(),
+                        value: low,
+                        inclusive: true,
+                    },
+                    ParsedCheck::Before {
+                        column,
+                        value: high,
+                        inclusive: true,
+                    },
+                ]),
+                (low, high) => Some(ParsedCheck::Between {
+                    column,
+                    low: low.number()?,
+                    high: high.number()?,
+                }),
+            }
+        }
+        Expr::InList {
+            expr,
+            list,
+            negated: false,
+        } => Some(ParsedCheck::InValues {
+            column: column_name(expr)?,
+            values: list.iter().map(literal_text).collect::<Option<_>>()?,
+        }),
+        // PostgreSQL renders `IN (...)` as `= ANY (ARRAY[...])`
+        Expr::AnyOp {
+            left,
+            compare_op: BinaryOperator::Eq,
+            right,
+            ..
+        } => Some(ParsedCheck::InValues {
+            column: column_name(left)?,
+            values: array_values(right)?,
+        }),
+        Expr::Like {
+            negated: false,
+            any: false,
+            expr,
+            pattern,
+            escape_char: None,
+        }
+        | Expr::ILike {
+            negated: false,
+            any: false,
+            expr,
+            pattern,
+            escape_char: None,
+        } => like(expr, pattern),
+        _ => None,
+    }
+}
+
+/// Lower `a OR b OR ...`. Equalities and `IN` lists on one column merge
+/// into a single `IN`; `column IS NULL` branches are dropped since
+/// generating a non-null value for another branch satisfies the check.
+/// Otherwise the first fully understood branch stands in for the whole.
+fn lower_or(expr: &Expr) -> Option<ParsedCheck> {
+    let mut branches = Vec::new();
+    collect_or_branches(expr, &mut branches);
+
+    let lowered: Vec<ParsedCheck> = branches
+        .into_iter()
+        .filter(|b| !matches!(b, Expr::IsNull(_)))
+        .filter_map(|b| lower(b, true))
+        .collect();
+
+    let mut merged: Option<(String, Vec<String>)> = None;
+    for check in &lowered {
+        match (check, &mut merged) {
+            (ParsedCheck::InValues { column, values }, None) => {
+                merged = Some((column.clone(), values.clone()));
+            }
+            (ParsedCheck::InValues { column, values }, Some((merged_column, merged_values)))
+                if column == merged_column =>
+            {
+                merged_values.extend(values.iter().cloned());
+            }
+            _ => {
+                merged = None;
+                break;
+            }
+        }
+    }
+
+    match merged {
+        Some((column, values)) => Some(ParsedCheck::InValues { column, values }),
+        None => lowered.into_iter().next(),
+    }
+}
+
+fn collect_or_branches<'a>(expr: &'a Expr, branches: &mut Vec<&'a Expr>) {
+    match expr {
+        Expr::Nested(inner) => collect_or_branches(inner, branches),
+        Expr::BinaryOp {
+            left,
+            op: BinaryOperator::Or,
+            right,
+        } => {
+            collect_or_branches(left, branches);
+            collect_or_branches(right, branches);
+        }
+        other => branches.push(other),
+    }
+}
+
+fn lower_comparison(left: &Expr, op: &BinaryOperator, right: &Expr) -> Option<ParsedCheck> {
+    if matches!(
+        op,
+        BinaryOperator::PGLikeMatch | BinaryOperator::PGILikeMatch
+    ) {
+        return like(left, right);
+    }
+
+    // Put the column on the left: `0 < price` reads as `price > 0`
+    let (left, op, right) = if literal(left).is_some() && literal(right).is_none() {
+        (right, flip(op)?, left)
+    } else {
+        (left, op.clone(), right)
+    };
+
+    if let Some(column) = length_column(left) {
+        let n = literal(right)?.number()?;
+        if n < 0.0 || n.fract() != 0.0 {
+            return None;
+        }
+        let n = n as usize;
+        let min = |min| ParsedCheck::MinLength {
+            column: column.clone(),
+            min,
+        };
+        let max = |max| ParsedCheck::MaxLength {
+            column: column.clone(),
+            max,
+        };
+        return match op {
+            BinaryOperator::Gt => Some(min(n + 1)),
+            BinaryOperator::GtEq => Some(min(n)),
+            BinaryOperator::Lt => Some(max(n.checked_sub(1)?)),
+            BinaryOperator::LtEq => Some(max(n)),
+            BinaryOperator::Eq => all_of(vec![min(n), max(n)]),
+            _ => None,
+        };
+    }
+
+    let column = column_name(left)?;
+
+    let Some(value) = literal(right) else {
+        // column <op> column
+        let right = column_name(right)?;
+        return match op {
+            BinaryOperator::Lt | BinaryOperator::LtEq => Some(ParsedCheck::ColumnLessThan {
+                left: column,
+                right,
+                inclusive: op == BinaryOperator::LtEq,
+            }),
+            BinaryOperator::Gt | BinaryOperator::GtEq => Some(ParsedCheck::ColumnLessThan {
+                left: right,
+                right: column,
+                inclusive: op == BinaryOperator::GtEq,
+            }),
+            _ => None,
+        };
+    };
+
+    if op == BinaryOperator::Eq {
+        return Some(ParsedCheck::InValues {
+            column,
+            values: vec![value.text()],
+        });
+    }
+
+    if let Literal::Date(value, _) = value {
+        return match op {
+            BinaryOperator::Gt | BinaryOperator::GtEq => Some(ParsedCheck::After {
+                column,
+                value,
+                inclusive: op == BinaryOperator::GtEq,
+            }),
+            BinaryOperator::Lt | BinaryOperator::LtEq => Some(ParsedCheck::Before {
+                column,
+                value,
+                inclusive: op == BinaryOperator::LtEq,
+            }),
+            _ => None,
+        };
+    }
+
+    let value = value.number()?;
+    match op {
+        BinaryOperator::GtEq => Some(ParsedCheck::GreaterThanOrEqual { column, value }),
+        BinaryOperator::Gt => Some(ParsedCheck::GreaterThan { column, value }),
+        BinaryOperator::LtEq => Some(ParsedCheck::LessThanOrEqual { column, value }),
+        BinaryOperator::Lt => Some(ParsedCheck::LessThan { column, value }),
+        _ => None,
+    }
+}
+
+fn flip(op: &BinaryOperator) -> Option<BinaryOperator> {
+    Some(match op {
+        BinaryOperator::Lt => BinaryOperator::Gt,
+        BinaryOperator::LtEq => BinaryOperator::GtEq,
+        BinaryOperator::Gt => BinaryOperator::Lt,
+        BinaryOperator::GtEq => BinaryOperator::LtEq,
+        BinaryOperator::Eq => BinaryOperator::Eq,
+        _ => return None,
+    })
+}
+
+fn like(expr: &Expr, pattern: &Expr) -> Option<ParsedCheck> {
+    match literal(pattern)? {
+        Literal::Text(pattern) => Some(ParsedCheck::Like {
+            column: column_name(expr)?,
+            pattern,
+        }),
+        _ => None,
+    }
+}
+
+fn all_of(mut checks: Vec<ParsedCheck>) -> Option<ParsedCheck> {
+    match checks.len() {
+        0 => None,
+        1 => checks.pop(),
+        _ => Some(ParsedCheck::All(checks)),
+    }
+}
+
+/// The column an operand refers to, looking through parentheses and casts
+/// (`(code)::text`).
+fn column_name(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Identifier(ident) => Some(ident.value.clone()),
+        Expr::CompoundIdentifier(parts) => parts.last().map(|ident| ident.value.clone()),
+        Expr::Nested(inner) | Expr::Cast { expr: inner, .. } => column_name(inner),
+        _ => None,
+    }
+}
+
+/// The column measured by `length(column)`, `char_length(column)` or
+/// `character_length(column)`, optionally trimmed (`length(btrim(name))`).
+fn length_column(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Nested(inner) | Expr::Cast { expr: inner, .. } => length_column(inner),
+        Expr::Function(function) => {
+            let name = function.name.to_string().to_lowercase();
+            if !matches!(name.as_str(), "length" | "char_length" | "character_length") {
+                return None;
+            }
+            trimmed_column(single_argument(&function.args)?)
+        }
+        _ => None,
+    }
+}
+
+fn trimmed_column(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Nested(inner) | Expr::Cast { expr: inner, .. } => trimmed_column(inner),
+        Expr::Trim { expr, .. } => column_name(expr),
+        Expr::Function(function) => {
+            let name = function.name.to_string().to_lowercase();
+            if !matches!(name.as_str(), "btrim" | "trim" | "ltrim" | "rtrim") {
+                return None;
+            }
+            column_name(single_argument(&function.args)?)
+        }
+        other => column_name(other),
+    }
+}
+
+fn single_argument(args: &FunctionArguments) -> Option<&Expr> {
+    let FunctionArguments::List(list) = args else {
+        return None;
+    };
+    match list.args.as_slice() {
+        [FunctionArg::Unnamed(FunctionArgExpr::Expr(expr))] => Some(expr),
+        _ => None,
+    }
+}
+
+/// The elements of `ARRAY[...]`, looking through parentheses and casts.
+fn array_values(expr: &Expr) -> Option<Vec<String>> {
+    match expr {
+        Expr::Nested(inner) | Expr::Cast { expr: inner, .. } => array_values(inner),
+        Expr::Array(array) => array.elem.iter().map(literal_text).collect(),
+        _ => None,
+    }
+}
+
+/// A constant operand of a check.
+#[derive(Debug, Clone, PartialEq)]
+enum Literal {
+    /// A number, kept with its source spelling for `IN` lists.
+    Number(f64, String),
+    Text(String),
+    /// A date or timestamp, kept with its source spelling so a text
+    /// column's `IN ('2024-01-01')` keeps that exact value.
+    Date(NaiveDateTime, String),
+}
+
+impl Literal {
+    /// The literal as a number. Quoted numbers (`'10'`) count; intervals
+    /// are already seconds.
+    fn number(&self) -> Option<f64> {
+        match self {
+            Literal::Number(value, _) => Some(*value),
+            Literal::Text(text) => text.trim().parse().ok(),
+            Literal::Date(..) => None,
+        }
+    }
+
+    fn text(self) -> String {
+        match self {
+            Literal::Number(_, text) | Literal::Text(text) | Literal::Date(_, text) => text,
+        }
+    }
+}
+
+fn literal_text(expr: &Expr) -> Option<String> {
+    literal(expr).map(Literal::text)
+}
+
+fn literal(expr: &Expr) -> Option<Literal> {
+    match expr {
+        Expr::Nested(inner) => literal(inner),
+        Expr::Value(value) => match &value.value {
+            SqlValue::Number(text, _) => Some(Literal::Number(text.parse().ok()?, text.clone())),
+            SqlValue::SingleQuotedString(text) => {
+                // Unmarked strings compared against a column may be dates
+                match parse_datetime_literal(text) {
+                    Some(value) => Some(Literal::Date(value, text.clone())),
+                    None => Some(Literal::Text(text.clone())),
+                }
+            }
+            _ => None,
+        },
+        Expr::UnaryOp {
+            op: UnaryOperator::Minus,
+            expr,
+        } => match literal(expr)? {
+            Literal::Number(value, text) => Some(Literal::Number(-value, format!("-{}", text))),
+            _ => None,
+        },
+        Expr::Cast {
+            expr, data_type, ..
+        } => match data_type {
+            SqlDataType::Interval { .. } => interval_literal(&literal_text(expr)?),
+            SqlDataType::Date
+            | SqlDataType::Datetime(_)
+            | SqlDataType::Timestamp(..)
+            | SqlDataType::TimestampNtz(_) => {
+                let text = literal_text(expr)?;
+                Some(Literal::Date(parse_datetime_literal(&text)?, text))
+            }
+            _ => literal(expr),
+        },
+        Expr::TypedString(typed) => {
+            let text = match &typed.value.value {
+                SqlValue::SingleQuotedString(text) => text,
+                _ => return None,
+            };
+            match typed.data_type {
+                SqlDataType::Interval { .. } => interval_literal(text),
+                _ => Some(Literal::Date(parse_datetime_literal(text)?, text.clone())),
+            }
+        }
+        Expr::Interval(interval) => {
+            let text = literal_text(&interval.value)?;
+            match &interval.leading_field {
+                Some(unit) => interval_literal(&format!("{} {}", text, unit)),
+                None => interval_literal(&text),
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Intervals compare as seconds, matching how interval columns are bounded.
+fn interval_literal(text: &str) -> Option<Literal> {
+    let seconds = parse_interval_seconds(text)?;
+    Some(Literal::Number(seconds as f64, seconds.to_string()))
+}
+
+/// Parse a date or timestamp literal (`2024-01-01`, `2024-01-01 12:00:00`,
+/// `2024-01-01 12:00:00+02`). Timestamps with an offset convert to UTC.
+fn parse_datetime_literal(text: &str) -> Option<NaiveDateTime> {
+    let text = text.trim();
+    if let Ok(date) = NaiveDate::parse_from_str(text, "%Y-%m-%d") {
+        return date.and_hms_opt(0, 0, 0);
+    }
+    for format in ["%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%dT%H:%M:%S%.f"] {
+        if let Ok(value) = NaiveDateTime::parse_from_str(text, format) {
+            return Some(value);
+        }
+    }
+    for format in ["%Y-%m-%d %H:%M:%S%.f%#z", "%Y-%m-%dT%H:%M:%S%.f%#z"] {
+        if let Ok(value) = DateTime::parse_from_str(text, format) {
+            return Some(value.naive_utc());
+        }
+    }
+    None
+}
+
+/// Convert a PostgreSQL interval literal such as `1 year 2 mons 3 days
+/// 04:05:06` into seconds. Months count as 30 days and years as 365, which
+/// is close enough for bounding generated values.
+pub(crate) fn parse_interval_seconds(literal: &str) -> Option<i64> {
+    let mut total = 0i64;
+    let mut tokens = literal.trim().trim_start_matches('@').split_whitespace();
+    while let Some(token) = tokens.next() {
+        if token.contains(':') {
+            let (sign, clock) = match token.strip_prefix('-') {
+                Some(rest) => (-1, rest),
+                None => (1, token.trim_start_matches('+')),
+            };
+            let mut secs = 0i64;
+            for part in clock.split(':') {
+                secs = secs * 60 + part.split('.').next()?.parse::<i64>().ok()?;
+            }
+            total += sign * secs;
+            continue;
+        }
+        let amount: i64 = token.parse().ok()?;
+        let unit = tokens.next()?.to_lowercase();
+        let factor = match unit.trim_end_matches('s') {
+            "year" => 365 * 86_400,
+            "mon" | "month" => 30 * 86_400,
+            "week" => 7 * 86_400,
+            "day" => 86_400,
+            "hour" => 3600,
+            "min" | "minute" => 60,
+            "sec" | "second" => 1,
+            _ => return None,
+        };
+        total += amount * factor;
+    }
+    Some(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDateTime {
+        parse_datetime_literal(s).unwrap()
+    }
+
+    #[test]
+    fn test_parse_check_gte() {
+        let parsed = parse_check_constraint("(price >= 0)");
+        assert!(matches!(
+            parsed,
+            Some(ParsedCheck::GreaterThanOrEqual { ref column, value }) if column == "price" && value == 0.0
+        ));
+    }
+
+    #[test]
+    fn test_parse_check_gt() {
+        let parsed = parse_check_constraint("quantity > 0");
+        assert!(matches!(
+            parsed,
+            Some(ParsedCheck::GreaterThan { ref column, value }) if column == "quantity" && value == 0.0
+        ));
+    }
+
+    #[test]
+    fn test_parse_check_casts_and_flipped_operands() {
+        let parsed = parse_check_constraint("((price)::numeric >= (0)::numeric)");
+        assert!(matches!(
+            parsed,
+            Some(ParsedCheck::GreaterThanOrEqual { ref column, value }) if column == "price" && value == 0.0
+        ));
+        let parsed = parse_check_constraint("(-10 < delta)");
+        assert!(matches!(
+            parsed,
+            Some(ParsedCheck::GreaterThan { ref column, value }) if column == "delta" && value == -10.0
+        ));
+    }
+
+    #[test]
+    fn test_parse_check_length() {
+        let parsed = parse_check_constraint("(length(name) > 0)");
+        assert!(matches!(
+            parsed,
+            Some(ParsedCheck::MinLength { ref column, min }) if column == "name" && min == 1
+        ));
+        let parsed = parse_check_constraint("(char_length(btrim((title)::text)) <= 80)");
+        assert!(matches!(
+            parsed,
+            Some(ParsedCheck::MaxLength { ref column, max }) if column == "title" && max == 80
+        ));
+        let Some(ParsedCheck::All(checks)) =
+            parse_check_constraint("(char_length((code)::text) = 3)")
+        else {
+            panic!("expected both length bounds");
+        };
+        assert!(matches!(checks[0], ParsedCheck::MinLength { min: 3, .. }));
+        assert!(matches!(checks[1], ParsedCheck::MaxLength { max: 3, .. }));
+    }
+
+    #[test]
+    fn test_parse_check_and_keeps_understood_parts() {
+        let Some(ParsedCheck::All(checks)) =
+            parse_check_constraint("((rating >= 1) AND (rating <= 5) AND (md5(body) <> ''))")
+        else {
+            panic!("expected a conjunction");
+        };
+        assert_eq!(checks.len(), 2);
+        assert!(matches!(checks[0], ParsedCheck::GreaterThanOrEqual { value, .. } if value == 1.0));
+        assert!(matches!(checks[1], ParsedCheck::LessThanOrEqual { value, .. } if value == 5.0));
+    }
+
+    #[test]
+    fn test_parse_check_in_lists() {
+        let parsed = parse_check_constraint(
+            "((status)::text = ANY ((ARRAY['active'::character varying, \
+             'paused'::character varying])::text[]))",
+        );
+        assert!(matches!(
+            parsed,
+            Some(ParsedCheck::InValues { ref column, ref values })
+                if column == "status" && values == &["active", "paused"]
+        ));
+        let parsed = parse_check_constraint("priority IN (1, 2, 3)");
+        assert!(matches!(
+            parsed,
+            Some(ParsedCheck::InValues { ref values, .. }) if values == &["1", "2", "3"]
+        ));
+        let parsed = parse_check_constraint("priority NOT IN (1, 2)");
+        assert!(parsed.is_none());
+
+        // Date-looking strings keep their spelling
+        let parsed = parse_check_constraint(
+            "((release)::text = ANY ((ARRAY['2024-01-01'::character varying])::text[]))",
+        );
+        assert!(matches!(
+            parsed,
+            Some(ParsedCheck::InValues { ref values, .. }) if values == &["2024-01-01"]
+        ));
+    }
+
+    #[test]
+    fn test_parse_check_or() {
+        let parsed =
+            parse_check_constraint("((kind = 'a'::text) OR (kind = 'b'::text) OR kind IN ('c'))");
+        assert!(matches!(
+            parsed,
+            Some(ParsedCheck::InValues { ref values, .. }) if values == &["a", "b", "c"]
+        ));
+
+        // `IS NULL` branches fall away
+        let parsed = parse_check_constraint("((discount IS NULL) OR (discount > 0))");
+        assert!(matches!(
+            parsed,
+            Some(ParsedCheck::GreaterThan { ref column, .. }) if column == "discount"
+        ));
+
+        // Mixed branches: the first fully understood one stands in
+        let parsed = parse_check_constraint("((md5(x) = 'y') OR (x BETWEEN 1 AND 9) OR (x = 0))");
+        assert!(matches!(
+            parsed,
+            Some(ParsedCheck::Between { low, high, .. }) if low == 1.0 && high == 9.0
+        ));
+    }
+
+    #[test]
+    fn test_parse_check_like() {
+        let parsed = parse_check_constraint("((sku)::text ~~ 'SKU-%'::text)");
+        assert!(matches!(
+            parsed,
+            Some(ParsedCheck::Like { ref column, ref pattern }) if column == "sku" && pattern == "SKU-%"
+        ));
+        let parsed = parse_check_constraint("email LIKE '%@%'");
+        assert!(matches!(
+            parsed,
+            Some(ParsedCheck::Like { ref pattern, .. }) if pattern == "%@%"
+        ));
+        assert!(parse_check_constraint("email NOT LIKE '%@%'").is_none());
+    }
+
+    #[test]
+    fn test_parse_check_dates() {
+        let parsed = parse_check_constraint("(born_on >= '1900-01-01'::date)");
+        assert!(matches!(
+            parsed,
+            Some(ParsedCheck::After { ref column, value, inclusive: true })
+                if column == "born_on" && value == date("1900-01-01")
+        ));
+        let parsed = parse_check_constraint(
+            "(created_at < '2030-01-01 00:00:00+00'::timestamp with time zone)",
+        );
+        assert!(matches!(
+            parsed,
+            Some(ParsedCheck::Before { value, inclusive: false, .. }) if value == date("2030-01-01")
+        ));
+        let parsed =
+            parse_check_constraint("shipped_on BETWEEN DATE '2020-01-01' AND DATE '2020-12-31'");
+        assert!(matches!(parsed, Some(ParsedCheck::All(ref checks)) if checks.len() == 2));
+        let parsed = parse_check_constraint("(ends_at > starts_at)");
+        assert!(matches!(
+            parsed,
+            Some(ParsedCheck::ColumnLessThan { ref left, ref right, inclusive: false })
+                if left == "starts_at" && right == "ends_at"
+        ));
+        let parsed = parse_check_constraint("(discount <= price)");
+        assert!(matches!(
+            parsed,
+            Some(ParsedCheck::ColumnLessThan { ref left, ref right, inclusive: true })
+                if left == "discount" && right == "price"
+        ));
+    }
+
+    #[test]
+    fn test_parse_check_interval() {
+        let parsed = parse_check_constraint("(billing_period >= '1 day'::interval)");
+        assert!(matches!(
+            parsed,
+            Some(ParsedCheck::GreaterThanOrEqual { ref column, value }) if column == "billing_period" && value == 86_400.0
+        ));
+        let parsed = parse_check_constraint("billing_period < '1 year 2 mons 03:00:00'::interval");
+        assert!(matches!(
+            parsed,
+            Some(ParsedCheck::LessThan { value, .. }) if value == (425 * 86_400 + 3 * 3600) as f64
+        ));
+        assert_eq!(parse_interval_seconds("-00:30:00"), Some(-1800));
+        assert_eq!(parse_interval_seconds("3 fortnights"), None);
+    }
+
+    #[test]
+    fn test_parse_check_unsupported() {
+        assert!(parse_check_constraint("(a + b <= 100)").is_none());
+        assert!(parse_check_constraint("not valid sql (((").is_none());
+    }
+}
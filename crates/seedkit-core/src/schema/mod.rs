@@ -1,3 +1,4 @@
+pub mod check_expr;
 pub mod introspect;
 pub mod mysql;
 pub mod postgres;
@@ -3,6 +3,7 @@ use sqlx::mysql::MySqlPool;
 use sqlx::Row;
 
 use crate::error::{Result, SeedKitError};
+use crate::output::runs::RUNS_TABLE;
 use crate::schema::introspect::SchemaIntrospector;
 use crate::schema::types::*;
 
@@ -54,7 +55,8 @@ impl MySqlIntrospector {
                 CAST(numeric_scale AS SIGNED) AS numeric_scale,
                 CAST(ordinal_position AS SIGNED) AS ordinal_position,
                 CAST(extra AS CHAR) AS extra,
-                CAST(column_key AS CHAR) AS column_key
+                CAST(column_key AS CHAR) AS column_key,
+                CAST(column_comment AS CHAR) AS column_comment
             FROM information_schema.columns
             WHERE table_schema = ?
             ORDER BY table_name, ordinal_position
@@ -81,13 +83,21 @@ impl MySqlIntrospector {
             let numeric_scale: Option<i64> = row.get("numeric_scale");
             let ordinal_position: i64 = row.get("ordinal_position");
             let extra: String = row.get("extra");
+            let comment: Option<String> = row.get("column_comment");
 
-            let (data_type, enum_values) = if data_type_str == "enum" || data_type_str == "set" {
+            let (data_type, enum_values) = match data_type_str.as_str() {
                 // Parse enum values from column_type like "enum('a','b','c')"
-                let values = parse_mysql_enum_values(&column_type);
-                (DataType::Enum(column_name.clone()), Some(values))
-            } else {
-                (DataType::from_raw(&data_type_str), None)
+                "enum" => (
+                    DataType::Enum(column_name.clone()),
+                    Some(parse_mysql_enum_values(&column_type)),
+                ),
+                "set" => (
+                    DataType::Set(column_name.clone()),
+                    Some(parse_mysql_enum_values(&column_type)),
+                ),
+                // MySQL's conventional boolean spelling
+                _ if is_mysql_boolean(&column_type) => (DataType::Boolean, None),
+                _ => (DataType::from_raw(&data_type_str), None),
             };
 
             let is_auto = extra.contains("auto_increment");
@@ -95,12 +105,15 @@ impl MySqlIntrospector {
             let mut column = Column::new(column_name.clone(), data_type, data_type_str);
             column.nullable = is_nullable == "YES";
             column.has_default = column_default.is_some();
+            column.default_expression = column_default;
             column.is_auto_increment = is_auto;
             column.max_length = max_length.map(|v| v as u32);
             column.numeric_precision = numeric_precision.map(|v| v as u32);
             column.numeric_scale = numeric_scale.map(|v| v as u32);
             column.ordinal_position = ordinal_position as u32;
             column.enum_values = enum_values;
+            column.unsigned = is_mysql_unsigned(&column_type);
+            column.comment = comment.filter(|c| !c.trim().is_empty());
 
             if let Some(table) = tables.get_mut(&table_name) {
                 table.columns.insert(column_name, column);
@@ -222,49 +235,112 @@ impl MySqlIntrospector {
         Ok(())
     }
 
+    /// Unique indexes come from `information_schema.statistics` rather than
+    /// `table_constraints` so prefix lengths (`UNIQUE(email(10))`, reported
+    /// as `SUB_PART`) and functional key parts are visible. Multi-valued
+    /// indexes over JSON arrays are functional parts whose `EXPRESSION` is
+    /// `cast(json_extract(...) as ... array)`; the `EXPRESSION` column only
+    /// exists on MySQL 8.0.13+, so older servers fall back to a query
+    /// without it.
     async fn introspect_unique_constraints(
         &self,
         tables: &mut IndexMap<String, Table>,
     ) -> Result<()> {
+        let query_with_expression = r#"
+            SELECT
+                CAST(TABLE_NAME AS CHAR) AS table_name,
+                CAST(INDEX_NAME AS CHAR) AS constraint_name,
+                CAST(COLUMN_NAME AS CHAR) AS column_name,
+                CAST(SUB_PART AS SIGNED) AS sub_part,
+                CAST(EXPRESSION AS CHAR) AS expression
+            FROM information_schema.statistics
+            WHERE TABLE_SCHEMA = ?
+                AND NON_UNIQUE = 0
+                AND INDEX_NAME <> 'PRIMARY'
+            ORDER BY TABLE_NAME, INDEX_NAME, SEQ_IN_INDEX
+        "#;
         let query = r#"
             SELECT
-                CAST(tc.table_name AS CHAR) AS table_name,
-                CAST(tc.constraint_name AS CHAR) AS constraint_name,
-                CAST(kcu.column_name AS CHAR) AS column_name,
-                kcu.ordinal_position
-            FROM information_schema.table_constraints tc
-            JOIN information_schema.key_column_usage kcu
-                ON tc.constraint_name = kcu.constraint_name
-                AND tc.table_schema = kcu.table_schema
-                AND tc.table_name = kcu.table_name
-            WHERE tc.table_schema = ?
-                AND tc.constraint_type = 'UNIQUE'
-            ORDER BY tc.table_name, tc.constraint_name, kcu.ordinal_position
+                CAST(TABLE_NAME AS CHAR) AS table_name,
+                CAST(INDEX_NAME AS CHAR) AS constraint_name,
+                CAST(COLUMN_NAME AS CHAR) AS column_name,
+                CAST(SUB_PART AS SIGNED) AS sub_part
+            FROM information_schema.statistics
+            WHERE TABLE_SCHEMA = ?
+                AND NON_UNIQUE = 0
+                AND INDEX_NAME <> 'PRIMARY'
+            ORDER BY TABLE_NAME, INDEX_NAME, SEQ_IN_INDEX
         "#;
 
-        let rows = sqlx::query(query)
+        let rows = match sqlx::query(query_with_expression)
             .bind(&self.database_name)
             .fetch_all(&self.pool)
             .await
-            .map_err(|e| SeedKitError::Introspection {
-                query: "fetch unique constraints".to_string(),
-                source: e,
-            })?;
-
-        let mut uc_map: IndexMap<(String, String), Vec<String>> = IndexMap::new();
+        {
+            Ok(rows) => rows,
+            Err(_) => sqlx::query(query)
+                .bind(&self.database_name)
+                .fetch_all(&self.pool)
+                .await
+                .map_err(|e| SeedKitError::Introspection {
+                    query: "fetch unique constraints".to_string(),
+                    source: e,
+                })?,
+        };
+
+        // (table, index) -> key parts; `None` marks a functional part we
+        // can't model, which disqualifies the whole index.
+        type KeyPart = Option<(String, Option<u32>, bool)>;
+        let mut uc_map: IndexMap<(String, String), Vec<KeyPart>> = IndexMap::new();
         for row in rows {
             let table_name: String = row.get("table_name");
             let constraint_name: String = row.get("constraint_name");
-            let column_name: String = row.get("column_name");
-
+            let column_name: Option<String> = row.get("column_name");
+            let sub_part: Option<i64> = row.get("sub_part");
+            let expression: Option<String> = row.try_get("expression").ok().flatten();
+
+            let part = match column_name {
+                Some(column) => Some((column, sub_part.map(|v| v as u32), false)),
+                None => expression
+                    .as_deref()
+                    .and_then(multi_valued_column)
+                    .map(|column| (column, None, true)),
+            };
             uc_map
                 .entry((table_name, constraint_name))
                 .or_default()
-                .push(column_name);
+                .push(part);
         }
 
-        for ((table_name, constraint_name), columns) in uc_map {
+        for ((table_name, constraint_name), parts) in uc_map {
+            let parts: Option<Vec<(String, Option<u32>, bool)>> = parts.into_iter().collect();
+            let parts = match parts {
+                Some(parts) => parts,
+                None => {
+                    tracing::debug!(
+                        "Skipping unique index {}.{}: functional key parts are not supported",
+                        table_name,
+                        constraint_name
+                    );
+                    continue;
+                }
+            };
             if let Some(table) = tables.get_mut(&table_name) {
+                let multi_valued = parts.iter().any(|(_, _, mv)| *mv);
+                let prefix_lengths: Vec<Option<u32>> = if parts.iter().any(|(_, p, _)| p.is_some())
+                {
+                    parts.iter().map(|(_, p, _)| *p).collect()
+                } else {
+                    Vec::new()
+                };
+                let columns: Vec<String> = parts.into_iter().map(|(column, _, _)| column).collect();
+                if !prefix_lengths.is_empty() || multi_valued {
+                    table.unique_key_specs.push(UniqueKeySpec {
+                        columns: columns.clone(),
+                        prefix_lengths,
+                        multi_valued,
+                    });
+                }
                 table.unique_constraints.push(UniqueConstraint {
                     name: Some(constraint_name),
                     columns,
@@ -274,6 +350,86 @@ impl MySqlIntrospector {
 
         Ok(())
     }
+
+    /// Non-unique indexes with their key parts in order. Functional key
+    /// parts hold their expression, which only MySQL 8.0.13+ reports.
+    async fn introspect_indexes(&self, tables: &mut IndexMap<String, Table>) -> Result<()> {
+        let query_with_expression = r#"
+            SELECT
+                CAST(TABLE_NAME AS CHAR) AS table_name,
+                CAST(INDEX_NAME AS CHAR) AS index_name,
+                CAST(COALESCE(COLUMN_NAME, EXPRESSION) AS CHAR) AS column_name,
+                CAST(INDEX_TYPE AS CHAR) AS index_type
+            FROM information_schema.statistics
+            WHERE TABLE_SCHEMA = ?
+                AND NON_UNIQUE = 1
+            ORDER BY TABLE_NAME, INDEX_NAME, SEQ_IN_INDEX
+        "#;
+        let query = r#"
+            SELECT
+                CAST(TABLE_NAME AS CHAR) AS table_name,
+                CAST(INDEX_NAME AS CHAR) AS index_name,
+                CAST(COLUMN_NAME AS CHAR) AS column_name,
+                CAST(INDEX_TYPE AS CHAR) AS index_type
+            FROM information_schema.statistics
+            WHERE TABLE_SCHEMA = ?
+                AND NON_UNIQUE = 1
+            ORDER BY TABLE_NAME, INDEX_NAME, SEQ_IN_INDEX
+        "#;
+
+        let rows = match sqlx::query(query_with_expression)
+            .bind(&self.database_name)
+            .fetch_all(&self.pool)
+            .await
+        {
+            Ok(rows) => rows,
+            Err(_) => sqlx::query(query)
+                .bind(&self.database_name)
+                .fetch_all(&self.pool)
+                .await
+                .map_err(|e| SeedKitError::Introspection {
+                    query: "fetch indexes".to_string(),
+                    source: e,
+                })?,
+        };
+
+        let mut index_map: IndexMap<(String, String), Index> = IndexMap::new();
+        for row in rows {
+            let table_name: String = row.get("table_name");
+            let index_name: String = row.get("index_name");
+            let column_name: Option<String> = row.get("column_name");
+            let index_type: Option<String> = row.get("index_type");
+
+            index_map
+                .entry((table_name, index_name.clone()))
+                .or_insert_with(|| Index {
+                    name: index_name,
+                    columns: Vec::new(),
+                    method: index_type,
+                })
+                .columns
+                .push(column_name.unwrap_or_else(|| "<expression>".to_string()));
+        }
+
+        for ((table_name, _), index) in index_map {
+            if let Some(table) = tables.get_mut(&table_name) {
+                table.indexes.push(index);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Extract the JSON column behind a multi-valued index expression such as
+/// ``cast(json_extract(`tags`,_utf8mb4'$') as char(32) array)``.
+fn multi_valued_column(expression: &str) -> Option<String> {
+    let lower = expression.to_lowercase();
+    if !lower.contains(" array") {
+        return None;
+    }
+    let re = regex::Regex::new(r"(?i)json_extract\(\s*`?([A-Za-z0-9_$]+)`?").ok()?;
+    re.captures(expression).map(|caps| caps[1].to_string())
 }
 
 impl SchemaIntrospector for MySqlIntrospector {
@@ -286,6 +442,10 @@ impl SchemaIntrospector for MySqlIntrospector {
         self.introspect_foreign_keys(&mut schema.tables).await?;
         self.introspect_unique_constraints(&mut schema.tables)
             .await?;
+        self.introspect_indexes(&mut schema.tables).await?;
+
+        // SeedKit's own bookkeeping, never seeded
+        schema.tables.shift_remove(RUNS_TABLE);
 
         Ok(schema)
     }
@@ -307,13 +467,70 @@ fn parse_mysql_enum_values(column_type: &str) -> Vec<String> {
     Vec::new()
 }
 
+/// `tinyint(1)` is how MySQL declares `BOOLEAN`.
+fn is_mysql_boolean(column_type: &str) -> bool {
+    column_type.trim().to_lowercase().starts_with("tinyint(1)")
+}
+
+/// Whether a numeric column_type like "int(10) unsigned zerofill" is unsigned.
+fn is_mysql_unsigned(column_type: &str) -> bool {
+    column_type
+        .to_lowercase()
+        .split_whitespace()
+        .any(|word| word == "unsigned")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_multi_valued_column() {
+        assert_eq!(
+            multi_valued_column("cast(json_extract(`tags`,_utf8mb4'$') as char(32) array)"),
+            Some("tags".to_string())
+        );
+        assert_eq!(
+            multi_valued_column("CAST(JSON_EXTRACT(zips, '$.list') AS UNSIGNED ARRAY)"),
+            Some("zips".to_string())
+        );
+        assert_eq!(multi_valued_column("lower(`email`)"), None);
+    }
+
     #[test]
     fn test_parse_mysql_enum_values() {
         let values = parse_mysql_enum_values("enum('active','inactive','suspended')");
         assert_eq!(values, vec!["active", "inactive", "suspended"]);
     }
+
+    #[test]
+    fn test_mysql_column_type_flags() {
+        assert!(is_mysql_boolean("tinyint(1)"));
+        assert!(is_mysql_boolean("TINYINT(1) UNSIGNED"));
+        assert!(!is_mysql_boolean("tinyint(4)"));
+        assert!(!is_mysql_boolean("tinyint"));
+
+        assert!(is_mysql_unsigned("int unsigned"));
+        assert!(is_mysql_unsigned("bigint(20) unsigned zerofill"));
+        assert!(!is_mysql_unsigned("int(11)"));
+    }
+
+    #[test]
+    fn test_integer_bounds() {
+        let mut col = Column::new("n".to_string(), DataType::SmallInt, "tinyint".to_string());
+        assert_eq!(col.integer_bounds(), Some((-128, 127)));
+        col.unsigned = true;
+        assert_eq!(col.integer_bounds(), Some((0, 255)));
+
+        let mut col = Column::new("n".to_string(), DataType::Integer, "mediumint".to_string());
+        col.unsigned = true;
+        assert_eq!(col.integer_bounds(), Some((0, 16_777_215)));
+
+        let mut col = Column::new("n".to_string(), DataType::BigInt, "bigint".to_string());
+        col.unsigned = true;
+        assert_eq!(col.integer_bounds(), Some((0, i64::MAX)));
+
+        let col = Column::new("n".to_string(), DataType::VarChar, "varchar".to_string());
+        assert_eq!(col.integer_bounds(), None);
+    }
 }
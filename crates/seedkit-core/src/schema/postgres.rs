@@ -1,8 +1,13 @@
+use std::sync::LazyLock;
+
 use indexmap::IndexMap;
+use regex::Regex;
 use sqlx::postgres::PgPool;
 use sqlx::Row;
 
 use crate::error::{Result, SeedKitError};
+use crate::output::runs::RUNS_TABLE;
+use crate::schema::check_expr::parse_check_constraint;
 use crate::schema::introspect::SchemaIntrospector;
 use crate::schema::types::*;
 
@@ -11,6 +16,18 @@ pub struct PostgresIntrospector {
     schema_name: String,
 }
 
+/// A domain type resolved to its underlying base type.
+#[derive(Debug, Clone, Default)]
+struct PgDomain {
+    data_type: String,
+    udt_name: String,
+    max_length: Option<i32>,
+    numeric_precision: Option<i32>,
+    numeric_scale: Option<i32>,
+    /// (constraint name, check clause) pairs, with `VALUE` as the column placeholder.
+    checks: Vec<(String, String)>,
+}
+
 impl PostgresIntrospector {
     pub fn new(pool: PgPool) -> Self {
         Self {
@@ -42,19 +59,101 @@ impl PostgresIntrospector {
         Ok(tables)
     }
 
-    async fn introspect_columns(&self, tables: &mut IndexMap<String, Table>) -> Result<()> {
+    async fn introspect_domains(&self) -> Result<IndexMap<String, PgDomain>> {
+        let query = r#"
+            SELECT
+                d.domain_name,
+                d.data_type,
+                d.udt_name,
+                d.character_maximum_length,
+                d.numeric_precision,
+                d.numeric_scale
+            FROM information_schema.domains d
+            WHERE d.domain_schema = $1
+            ORDER BY d.domain_name
+        "#;
+
+        let rows = sqlx::query(query)
+            .bind(&self.schema_name)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| SeedKitError::Introspection {
+                query: "fetch domains".to_string(),
+                source: e,
+            })?;
+
+        let mut domains: IndexMap<String, PgDomain> = IndexMap::new();
+        for row in rows {
+            let domain_name: String = row.get("domain_name");
+            domains.insert(
+                domain_name,
+                PgDomain {
+                    data_type: row.get("data_type"),
+                    udt_name: row.get("udt_name"),
+                    max_length: row.get("character_maximum_length"),
+                    numeric_precision: row.get("numeric_precision"),
+                    numeric_scale: row.get("numeric_scale"),
+                    checks: Vec::new(),
+                },
+            );
+        }
+
+        let check_query = r#"
+            SELECT
+                dc.domain_name,
+                dc.constraint_name,
+                cc.check_clause
+            FROM information_schema.domain_constraints dc
+            JOIN information_schema.check_constraints cc
+                ON dc.constraint_name = cc.constraint_name
+                AND dc.constraint_schema = cc.constraint_schema
+            WHERE dc.domain_schema = $1
+            ORDER BY dc.domain_name, dc.constraint_name
+        "#;
+
+        let rows = sqlx::query(check_query)
+            .bind(&self.schema_name)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| SeedKitError::Introspection {
+                query: "fetch domain constraints".to_string(),
+                source: e,
+            })?;
+
+        for row in rows {
+            let domain_name: String = row.get("domain_name");
+            let constraint_name: String = row.get("constraint_name");
+            let check_clause: String = row.get("check_clause");
+            if let Some(domain) = domains.get_mut(&domain_name) {
+                domain.checks.push((constraint_name, check_clause));
+            }
+        }
+
+        Ok(domains)
+    }
+
+    async fn introspect_columns(
+        &self,
+        tables: &mut IndexMap<String, Table>,
+        domains: &IndexMap<String, PgDomain>,
+    ) -> Result<()> {
         let query = r#"
             SELECT
                 c.table_name,
                 c.column_name,
                 c.data_type,
                 c.udt_name,
+                c.domain_name,
                 c.is_nullable,
                 c.column_default,
                 c.character_maximum_length,
                 c.numeric_precision,
                 c.numeric_scale,
-                c.ordinal_position
+                c.ordinal_position,
+                col_description(
+                    format('%I.%I', c.table_schema, c.table_name)::regclass,
+                    c.ordinal_position::int
+                ) AS column_comment
             FROM information_schema.columns c
             WHERE c.table_schema = $1
             ORDER BY c.table_name, c.ordinal_position
@@ -72,14 +171,29 @@ impl PostgresIntrospector {
         for row in rows {
             let table_name: String = row.get("table_name");
             let column_name: String = row.get("column_name");
-            let data_type_str: String = row.get("data_type");
-            let udt_name: String = row.get("udt_name");
+            let mut data_type_str: String = row.get("data_type");
+            let mut udt_name: String = row.get("udt_name");
+            let domain_name: Option<String> = row.get("domain_name");
             let is_nullable: String = row.get("is_nullable");
             let column_default: Option<String> = row.get("column_default");
-            let max_length: Option<i32> = row.get("character_maximum_length");
-            let numeric_precision: Option<i32> = row.get("numeric_precision");
-            let numeric_scale: Option<i32> = row.get("numeric_scale");
+            let mut max_length: Option<i32> = row.get("character_maximum_length");
+            let mut numeric_precision: Option<i32> = row.get("numeric_precision");
+            let mut numeric_scale: Option<i32> = row.get("numeric_scale");
             let ordinal_position: i32 = row.get("ordinal_position");
+            let comment: Option<String> = row.get("column_comment");
+
+            // Domain-typed columns: generate against the domain's base type,
+            // picking up the length/precision declared on the domain itself.
+            let domain = domain_name.as_ref().and_then(|name| domains.get(name));
+            if let Some(domain) = domain {
+                if data_type_str == "USER-DEFINED" && domain.data_type != "USER-DEFINED" {
+                    data_type_str = domain.data_type.clone();
+                    udt_name = domain.udt_name.clone();
+                }
+                max_length = max_length.or(domain.max_length);
+                numeric_precision = numeric_precision.or(domain.numeric_precision);
+                numeric_scale = numeric_scale.or(domain.numeric_scale);
+            }
 
             let data_type = if data_type_str == "USER-DEFINED" {
                 DataType::Enum(udt_name.clone())
@@ -110,8 +224,26 @@ impl PostgresIntrospector {
             column.numeric_precision = numeric_precision.map(|v| v as u32);
             column.numeric_scale = numeric_scale.map(|v| v as u32);
             column.ordinal_position = ordinal_position as u32;
+            column.comment = comment.filter(|c| !c.trim().is_empty());
+            column.default_expression = column_default;
+            if matches!(column.data_type, DataType::Array(_)) {
+                // Refined by introspect_array_dimensions for multi-dimensional arrays
+                column.array_dimensions = Some(1);
+            }
 
             if let Some(table) = tables.get_mut(&table_name) {
+                // Carry the domain's CHECK constraints over as column-level checks
+                if let Some(domain) = domain {
+                    for (constraint_name, clause) in &domain.checks {
+                        let expression = domain_check_for_column(clause, &column_name);
+                        let parsed = parse_check_constraint(&expression);
+                        table.check_constraints.push(CheckConstraint {
+                            name: Some(constraint_name.clone()),
+                            expression,
+                            parsed,
+                        });
+                    }
+                }
                 table.columns.insert(column_name, column);
             }
         }
@@ -288,6 +420,53 @@ impl PostgresIntrospector {
         Ok(())
     }
 
+    /// Non-unique indexes with their access method. Expression keys hold
+    /// their SQL text, as `pg_get_indexdef` prints it.
+    async fn introspect_indexes(&self, tables: &mut IndexMap<String, Table>) -> Result<()> {
+        let query = r#"
+            SELECT
+                t.relname::text AS table_name,
+                ic.relname::text AS index_name,
+                am.amname::text AS method,
+                ARRAY(
+                    SELECT pg_get_indexdef(ix.indexrelid, k, true)
+                    FROM generate_series(1, ix.indnatts) AS k
+                    ORDER BY k
+                ) AS columns
+            FROM pg_index ix
+            JOIN pg_class t ON t.oid = ix.indrelid
+            JOIN pg_class ic ON ic.oid = ix.indexrelid
+            JOIN pg_am am ON am.oid = ic.relam
+            JOIN pg_catalog.pg_namespace n ON n.oid = t.relnamespace
+            WHERE n.nspname = $1
+                AND NOT ix.indisunique
+                AND NOT ix.indisprimary
+            ORDER BY t.relname, ic.relname
+        "#;
+
+        let rows = sqlx::query(query)
+            .bind(&self.schema_name)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| SeedKitError::Introspection {
+                query: "fetch indexes".to_string(),
+                source: e,
+            })?;
+
+        for row in rows {
+            let table_name: String = row.get("table_name");
+            if let Some(table) = tables.get_mut(&table_name) {
+                table.indexes.push(Index {
+                    name: row.get("index_name"),
+                    columns: row.get("columns"),
+                    method: row.get("method"),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
     async fn introspect_check_constraints(
         &self,
         tables: &mut IndexMap<String, Table>,
@@ -365,6 +544,175 @@ impl PostgresIntrospector {
 
         Ok(enums)
     }
+
+    async fn introspect_array_dimensions(
+        &self,
+        tables: &mut IndexMap<String, Table>,
+    ) -> Result<()> {
+        let query = r#"
+            SELECT
+                c.relname AS table_name,
+                a.attname AS column_name,
+                a.attndims::int4 AS attndims
+            FROM pg_attribute a
+            JOIN pg_class c ON c.oid = a.attrelid
+            JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace
+            WHERE n.nspname = $1
+                AND c.relkind IN ('r', 'p')
+                AND a.attnum > 0
+                AND NOT a.attisdropped
+                AND a.attndims > 0
+        "#;
+
+        let rows = sqlx::query(query)
+            .bind(&self.schema_name)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| SeedKitError::Introspection {
+                query: "fetch array dimensions".to_string(),
+                source: e,
+            })?;
+
+        for row in rows {
+            let table_name: String = row.get("table_name");
+            let column_name: String = row.get("column_name");
+            // int2 on PostgreSQL 17+, int4 before
+            let dims: i32 = row.get("attndims");
+
+            if let Some(column) = tables
+                .get_mut(&table_name)
+                .and_then(|t| t.columns.get_mut(&column_name))
+            {
+                column.array_dimensions = Some(dims.max(1) as u32);
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn introspect_composite_types(&self) -> Result<IndexMap<String, Vec<Column>>> {
+        let query = r#"
+            SELECT
+                t.typname AS type_name,
+                a.attname AS field_name,
+                format_type(a.atttypid, a.atttypmod) AS field_type,
+                a.attnum
+            FROM pg_type t
+            JOIN pg_catalog.pg_namespace n ON n.oid = t.typnamespace
+            JOIN pg_class c ON c.oid = t.typrelid
+            JOIN pg_attribute a ON a.attrelid = c.oid
+            WHERE n.nspname = $1
+                AND t.typtype = 'c'
+                AND c.relkind = 'c'
+                AND a.attnum > 0
+                AND NOT a.attisdropped
+            ORDER BY t.typname, a.attnum
+        "#;
+
+        let rows = sqlx::query(query)
+            .bind(&self.schema_name)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| SeedKitError::Introspection {
+                query: "fetch composite types".to_string(),
+                source: e,
+            })?;
+
+        let mut composites: IndexMap<String, Vec<Column>> = IndexMap::new();
+        for row in rows {
+            let type_name: String = row.get("type_name");
+            let field_name: String = row.get("field_name");
+            let field_type: String = row.get("field_type");
+            let attnum: i16 = row.get("attnum");
+
+            let mut field = Column::new(field_name, DataType::from_raw(&field_type), field_type);
+            field.ordinal_position = attnum as u32;
+            composites.entry(type_name).or_default().push(field);
+        }
+
+        Ok(composites)
+    }
+
+    /// Re-type PostGIS `geometry`/`geography` columns, which arrive as
+    /// USER-DEFINED. `format_type` exposes the typmod, e.g.
+    /// `geometry(Point,4326)`. Returns no rows when PostGIS isn't installed.
+    async fn introspect_spatial_columns(&self, tables: &mut IndexMap<String, Table>) -> Result<()> {
+        let query = r#"
+            SELECT
+                c.relname AS table_name,
+                a.attname AS column_name,
+                t.typname AS type_name,
+                format_type(a.atttypid, a.atttypmod) AS full_type
+            FROM pg_attribute a
+            JOIN pg_class c ON c.oid = a.attrelid
+            JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace
+            JOIN pg_type t ON t.oid = a.atttypid
+            WHERE n.nspname = $1
+                AND c.relkind IN ('r', 'p')
+                AND t.typname IN ('geometry', 'geography')
+                AND a.attnum > 0
+                AND NOT a.attisdropped
+        "#;
+
+        let rows = sqlx::query(query)
+            .bind(&self.schema_name)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| SeedKitError::Introspection {
+                query: "fetch spatial columns".to_string(),
+                source: e,
+            })?;
+
+        for row in rows {
+            let table_name: String = row.get("table_name");
+            let column_name: String = row.get("column_name");
+            let type_name: String = row.get("type_name");
+            let full_type: String = row.get("full_type");
+
+            if let Some(column) = tables
+                .get_mut(&table_name)
+                .and_then(|t| t.columns.get_mut(&column_name))
+            {
+                column.data_type = parse_spatial_type(&type_name, &full_type);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Fetch user-defined range types and their subtypes. Built-in ranges
+    /// (`int4range`, `tstzrange`, ...) are recognised by name and are not
+    /// reported as USER-DEFINED, so only custom ranges need this lookup.
+    async fn introspect_range_types(&self) -> Result<IndexMap<String, DataType>> {
+        let query = r#"
+            SELECT
+                t.typname AS type_name,
+                format_type(r.rngsubtype, NULL) AS subtype
+            FROM pg_range r
+            JOIN pg_type t ON t.oid = r.rngtypid
+            JOIN pg_catalog.pg_namespace n ON n.oid = t.typnamespace
+            WHERE n.nspname = $1
+            ORDER BY t.typname
+        "#;
+
+        let rows = sqlx::query(query)
+            .bind(&self.schema_name)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| SeedKitError::Introspection {
+                query: "fetch range types".to_string(),
+                source: e,
+            })?;
+
+        let mut ranges = IndexMap::new();
+        for row in rows {
+            let type_name: String = row.get("type_name");
+            let subtype: String = row.get("subtype");
+            ranges.insert(type_name, DataType::from_raw(&subtype));
+        }
+
+        Ok(ranges)
+    }
 }
 
 impl SchemaIntrospector for PostgresIntrospector {
@@ -372,14 +720,70 @@ impl SchemaIntrospector for PostgresIntrospector {
         let mut schema = DatabaseSchema::new(DatabaseType::PostgreSQL, "postgres".to_string());
 
         schema.tables = self.introspect_tables().await?;
-        self.introspect_columns(&mut schema.tables).await?;
+        let domains = self.introspect_domains().await?;
+        self.introspect_columns(&mut schema.tables, &domains)
+            .await?;
         self.introspect_primary_keys(&mut schema.tables).await?;
         self.introspect_foreign_keys(&mut schema.tables).await?;
         self.introspect_unique_constraints(&mut schema.tables)
             .await?;
         self.introspect_check_constraints(&mut schema.tables)
             .await?;
+        self.introspect_indexes(&mut schema.tables).await?;
+        self.introspect_array_dimensions(&mut schema.tables).await?;
+        self.introspect_spatial_columns(&mut schema.tables).await?;
         schema.enums = self.introspect_enums().await?;
+        schema.composite_types = self.introspect_composite_types().await?;
+        let range_types = self.introspect_range_types().await?;
+
+        // USER-DEFINED columns were tentatively typed as enums; re-type the
+        // ones that actually reference a composite or range type. Arrays of
+        // enums, composites or custom ranges arrive with an unresolved
+        // element type name.
+        for table in schema.tables.values_mut() {
+            for column in table.columns.values_mut() {
+                match column.data_type {
+                    DataType::Enum(ref type_name)
+                        if schema.composite_types.contains_key(type_name) =>
+                    {
+                        column.data_type = DataType::Composite(type_name.clone());
+                    }
+                    DataType::Enum(ref type_name) if range_types.contains_key(type_name) => {
+                        column.data_type =
+                            DataType::Range(Box::new(range_types[type_name].clone()));
+                    }
+                    DataType::Array(ref mut inner) => {
+                        if let DataType::Unknown(ref name) = **inner {
+                            if let Some(values) = schema.enums.get(name) {
+                                column.enum_values = Some(values.clone());
+                                **inner = DataType::Enum(name.clone());
+                            } else if schema.composite_types.contains_key(name) {
+                                **inner = DataType::Composite(name.clone());
+                            } else if let Some(subtype) = range_types.get(name) {
+                                **inner = DataType::Range(Box::new(subtype.clone()));
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        // Composite fields that reference enum or nested composite types
+        // come back from format_type() as bare type names.
+        let composite_names: Vec<String> = schema.composite_types.keys().cloned().collect();
+        for fields in schema.composite_types.values_mut() {
+            for field in fields.iter_mut() {
+                if let DataType::Unknown(ref name) = field.data_type {
+                    if let Some(values) = schema.enums.get(name) {
+                        field.enum_values = Some(values.clone());
+                        field.data_type = DataType::Enum(name.clone());
+                    } else if composite_names.contains(name) {
+                        field.data_type = DataType::Composite(name.clone());
+                    }
+                }
+            }
+        }
 
         // Back-fill enum values into columns that reference enum types
         for table in schema.tables.values_mut() {
@@ -392,83 +796,54 @@ impl SchemaIntrospector for PostgresIntrospector {
             }
         }
 
+        // SeedKit's own bookkeeping, never seeded
+        schema.tables.shift_remove(RUNS_TABLE);
+
         Ok(schema)
     }
 }
 
-/// Parse simple CHECK constraint expressions into structured form.
-fn parse_check_constraint(expr: &str) -> Option<ParsedCheck> {
-    let expr = expr.trim();
-    // Remove outer parens
-    let expr = if expr.starts_with('(') && expr.ends_with(')') {
-        &expr[1..expr.len() - 1]
-    } else {
-        expr
-    };
-    let expr = expr.trim();
-
-    // Pattern: column >= value
-    let re_gte = regex::Regex::new(r"^(\w+)\s*>=\s*(-?[\d.]+)$").ok()?;
-    if let Some(caps) = re_gte.captures(expr) {
-        return Some(ParsedCheck::GreaterThanOrEqual {
-            column: caps[1].to_string(),
-            value: caps[2].parse().ok()?,
-        });
-    }
-
-    // Pattern: column > value
-    let re_gt = regex::Regex::new(r"^(\w+)\s*>\s*(-?[\d.]+)$").ok()?;
-    if let Some(caps) = re_gt.captures(expr) {
-        return Some(ParsedCheck::GreaterThan {
-            column: caps[1].to_string(),
-            value: caps[2].parse().ok()?,
-        });
-    }
-
-    // Pattern: column <= value
-    let re_lte = regex::Regex::new(r"^(\w+)\s*<=\s*(-?[\d.]+)$").ok()?;
-    if let Some(caps) = re_lte.captures(expr) {
-        return Some(ParsedCheck::LessThanOrEqual {
-            column: caps[1].to_string(),
-            value: caps[2].parse().ok()?,
-        });
-    }
-
-    // Pattern: column < value (not column < column)
-    let re_lt = regex::Regex::new(r"^(\w+)\s*<\s*(-?[\d.]+)$").ok()?;
-    if let Some(caps) = re_lt.captures(expr) {
-        return Some(ParsedCheck::LessThan {
-            column: caps[1].to_string(),
-            value: caps[2].parse().ok()?,
-        });
-    }
+/// Rewrite a domain CHECK clause for a concrete column by substituting the
+/// `VALUE` placeholder with the column name, e.g. `(VALUE > 0)` on column
+/// `quantity` becomes `(quantity > 0)`. String literals are left alone, so
+/// `VALUE <> 'NO VALUE'` keeps its literal.
+fn domain_check_for_column(clause: &str, column_name: &str) -> String {
+    static PLACEHOLDER: LazyLock<Regex> =
+        LazyLock::new(|| Regex::new(r"'(?:[^']|'')*'|\bVALUE\b").unwrap());
+    PLACEHOLDER
+        .replace_all(clause, |caps: &regex::Captures| {
+            let matched = &caps[0];
+            if matched.starts_with('\'') {
+                matched.to_string()
+            } else {
+                column_name.to_string()
+            }
+        })
+        .into_owned()
+}
 
-    // Pattern: column1 < column2
-    let re_col_lt = regex::Regex::new(r"^(\w+)\s*<\s*(\w+)$").ok()?;
-    if let Some(caps) = re_col_lt.captures(expr) {
-        let left = &caps[1];
-        let right = &caps[2];
-        // Only match if right is not a number
-        if right.parse::<f64>().is_err() {
-            return Some(ParsedCheck::ColumnLessThan {
-                left: left.to_string(),
-                right: right.to_string(),
-            });
+/// Parse a PostGIS type string such as `geometry(Point,4326)` or
+/// `geography`. Geography defaults to SRID 4326, geometry to 0.
+fn parse_spatial_type(type_name: &str, full_type: &str) -> DataType {
+    let geography = type_name == "geography";
+    let mut shape = "Geometry".to_string();
+    let mut srid = if geography { 4326 } else { 0 };
+
+    if let (Some(open), Some(close)) = (full_type.find('('), full_type.rfind(')')) {
+        let mut parts = full_type[open + 1..close].split(',').map(str::trim);
+        if let Some(s) = parts.next().filter(|s| !s.is_empty()) {
+            shape = s.to_string();
+        }
+        if let Some(value) = parts.next().and_then(|s| s.parse().ok()) {
+            srid = value;
         }
     }
 
-    // Pattern: length(column) > 0 or char_length(column) > 0
-    let re_len =
-        regex::Regex::new(r"^(?:length|char_length|character_length)\((\w+)\)\s*>\s*(\d+)$")
-            .ok()?;
-    if let Some(caps) = re_len.captures(expr) {
-        return Some(ParsedCheck::MinLength {
-            column: caps[1].to_string(),
-            min: caps[2].parse().ok()?,
-        });
+    DataType::Spatial {
+        geography,
+        shape,
+        srid,
     }
-
-    None
 }
 
 #[cfg(test)]
@@ -476,38 +851,50 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_parse_check_gte() {
-        let parsed = parse_check_constraint("(price >= 0)");
-        assert!(matches!(
-            parsed,
-            Some(ParsedCheck::GreaterThanOrEqual { ref column, value }) if column == "price" && value == 0.0
-        ));
+    fn test_parse_spatial_type() {
+        assert_eq!(
+            parse_spatial_type("geometry", "geometry(Point,4326)"),
+            DataType::Spatial {
+                geography: false,
+                shape: "Point".to_string(),
+                srid: 4326,
+            }
+        );
+        assert_eq!(
+            parse_spatial_type("geography", "geography"),
+            DataType::Spatial {
+                geography: true,
+                shape: "Geometry".to_string(),
+                srid: 4326,
+            }
+        );
+        assert_eq!(
+            parse_spatial_type("geometry", "geometry(MultiPolygon)"),
+            DataType::Spatial {
+                geography: false,
+                shape: "MultiPolygon".to_string(),
+                srid: 0,
+            }
+        );
     }
 
     #[test]
-    fn test_parse_check_gt() {
-        let parsed = parse_check_constraint("quantity > 0");
+    fn test_domain_check_for_column() {
+        let expr = domain_check_for_column("(VALUE > 0)", "quantity");
+        assert_eq!(expr, "(quantity > 0)");
+        let parsed = parse_check_constraint(&expr);
         assert!(matches!(
             parsed,
             Some(ParsedCheck::GreaterThan { ref column, value }) if column == "quantity" && value == 0.0
         ));
-    }
 
-    #[test]
-    fn test_parse_check_length() {
-        let parsed = parse_check_constraint("(length(name) > 0)");
-        assert!(matches!(
-            parsed,
-            Some(ParsedCheck::MinLength { ref column, min }) if column == "name" && min == 0
-        ));
-    }
+        // Only the bare placeholder is replaced, not identifiers containing it
+        let expr = domain_check_for_column("(length(VALUE) > 3 AND VALUE_X)", "email");
+        assert_eq!(expr, "(length(email) > 3 AND VALUE_X)");
 
-    #[test]
-    fn test_parse_check_column_lt() {
-        let parsed = parse_check_constraint("start_date < end_date");
-        assert!(matches!(
-            parsed,
-            Some(ParsedCheck::ColumnLessThan { ref left, ref right }) if left == "start_date" && right == "end_date"
-        ));
+        // The placeholder inside a string literal is text, not the column
+        let expr =
+            domain_check_for_column("(VALUE <> 'NO VALUE' AND VALUE <> 'it''s VALUE')", "note");
+        assert_eq!(expr, "(note <> 'NO VALUE' AND note <> 'it''s VALUE')");
     }
 }
@@ -3,6 +3,7 @@ use sqlx::sqlite::SqlitePool;
 use sqlx::Row;
 
 use crate::error::{Result, SeedKitError};
+use crate::output::runs::RUNS_TABLE;
 use crate::schema::introspect::SchemaIntrospector;
 use crate::schema::types::*;
 
@@ -59,6 +60,7 @@ impl SqliteIntrospector {
                 let mut column = Column::new(name.clone(), data_type, type_str);
                 column.nullable = notnull == 0;
                 column.has_default = dflt_value.is_some();
+                column.default_expression = dflt_value;
                 column.is_auto_increment = is_auto;
                 column.ordinal_position = cid as u32;
 
@@ -167,6 +169,57 @@ impl SqliteIntrospector {
 
         Ok(())
     }
+
+    /// Non-unique indexes created with `CREATE INDEX`. SQLite doesn't name
+    /// expression keys, so they show as `<expression>`.
+    async fn introspect_indexes(&self, tables: &mut IndexMap<String, Table>) -> Result<()> {
+        let table_names: Vec<String> = tables.keys().cloned().collect();
+        for table_name in table_names {
+            let query = format!("PRAGMA index_list(\"{}\")", table_name);
+            let indexes = sqlx::query(&query)
+                .fetch_all(&self.pool)
+                .await
+                .map_err(|e| SeedKitError::Introspection {
+                    query: format!("PRAGMA index_list({})", table_name),
+                    source: e,
+                })?;
+
+            for idx_row in &indexes {
+                let unique: i32 = idx_row.get("unique");
+                let origin: String = idx_row.get("origin");
+                if unique != 0 || origin != "c" {
+                    continue;
+                }
+                let idx_name: String = idx_row.get("name");
+                let info_query = format!("PRAGMA index_info(\"{}\")", idx_name);
+                let cols = sqlx::query(&info_query)
+                    .fetch_all(&self.pool)
+                    .await
+                    .map_err(|e| SeedKitError::Introspection {
+                        query: format!("PRAGMA index_info({})", idx_name),
+                        source: e,
+                    })?;
+
+                let columns: Vec<String> = cols
+                    .iter()
+                    .map(|r| {
+                        r.get::<Option<String>, _>("name")
+                            .unwrap_or_else(|| "<expression>".to_string())
+                    })
+                    .collect();
+
+                if let Some(table) = tables.get_mut(&table_name) {
+                    table.indexes.push(Index {
+                        name: idx_name,
+                        columns,
+                        method: None,
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
 }
 
 impl SchemaIntrospector for SqliteIntrospector {
@@ -178,6 +231,10 @@ impl SchemaIntrospector for SqliteIntrospector {
         self.introspect_foreign_keys(&mut schema.tables).await?;
         self.introspect_unique_constraints(&mut schema.tables)
             .await?;
+        self.introspect_indexes(&mut schema.tables).await?;
+
+        // SeedKit's own bookkeeping, never seeded
+        schema.tables.shift_remove(RUNS_TABLE);
 
         Ok(schema)
     }
@@ -9,6 +9,9 @@ pub struct DatabaseSchema {
     pub database_name: String,
     pub tables: IndexMap<String, Table>,
     pub enums: IndexMap<String, Vec<String>>,
+    /// Composite (row) types by name, with their fields in declaration order.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub composite_types: IndexMap<String, Vec<Column>>,
 }
 
 impl DatabaseSchema {
@@ -18,6 +21,7 @@ impl DatabaseSchema {
             database_name,
             tables: IndexMap::new(),
             enums: IndexMap::new(),
+            composite_types: IndexMap::new(),
         }
     }
 
@@ -61,6 +65,14 @@ pub struct Table {
     pub foreign_keys: Vec<ForeignKey>,
     pub unique_constraints: Vec<UniqueConstraint>,
     pub check_constraints: Vec<CheckConstraint>,
+    /// Non-unique indexes. They don't constrain generated values, but drift
+    /// in them changes query plans over seeded data.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub indexes: Vec<Index>,
+    /// Unique constraints MySQL compares by column prefix or JSON array
+    /// element rather than by whole value.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unique_key_specs: Vec<UniqueKeySpec>,
 }
 
 impl Table {
@@ -73,6 +85,8 @@ impl Table {
             foreign_keys: Vec::new(),
             unique_constraints: Vec::new(),
             check_constraints: Vec::new(),
+            indexes: Vec::new(),
+            unique_key_specs: Vec::new(),
         }
     }
 }
@@ -91,6 +105,19 @@ pub struct Column {
     pub numeric_scale: Option<u32>,
     pub enum_values: Option<Vec<String>>,
     pub ordinal_position: u32,
+    /// Number of dimensions for array columns (`int[]` = 1, `int[][]` = 2).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub array_dimensions: Option<u32>,
+    /// MySQL `UNSIGNED` integer column.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub unsigned: bool,
+    /// Column comment (`COMMENT ON COLUMN`, MySQL `COMMENT '...'`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    /// Server default as the database reports it (`now()`,
+    /// `nextval('users_id_seq'::regclass)`, `'active'`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_expression: Option<String>,
 }
 
 impl Column {
@@ -107,8 +134,32 @@ impl Column {
             numeric_scale: None,
             enum_values: None,
             ordinal_position: 0,
+            array_dimensions: None,
+            unsigned: false,
+            comment: None,
+            default_expression: None,
         }
     }
+
+    /// Storage range of an integer column, from its width (`tinyint`,
+    /// `mediumint`, ...) and signedness. `None` for non-integer columns.
+    /// Unsigned `bigint` is capped at `i64::MAX`.
+    pub fn integer_bounds(&self) -> Option<(i64, i64)> {
+        let bits = match (&self.data_type, self.raw_type.to_lowercase().as_str()) {
+            (DataType::SmallInt, "tinyint") => 8,
+            (DataType::SmallInt, _) => 16,
+            (DataType::Integer, "mediumint") => 24,
+            (DataType::Integer | DataType::Serial, _) => 32,
+            (DataType::BigInt | DataType::BigSerial, _) => 64,
+            _ => return None,
+        };
+        Some(match (self.unsigned, bits) {
+            (true, 64) => (0, i64::MAX),
+            (true, _) => (0, (1i64 << bits) - 1),
+            (false, 64) => (i64::MIN, i64::MAX),
+            (false, _) => (-(1i64 << (bits - 1)), (1i64 << (bits - 1)) - 1),
+        })
+    }
 }
 
 /// Normalized data type enum covering all supported databases.
@@ -154,6 +205,20 @@ pub enum DataType {
     Array(Box<DataType>),
     /// Database-specific enum type
     Enum(String),
+    /// MySQL `SET(...)` column: any subset of its labels, comma-separated
+    Set(String),
+    /// Composite (row) type (PostgreSQL `CREATE TYPE ... AS (...)`)
+    Composite(String),
+    /// Range type (PostgreSQL `int4range`, `tstzrange`, ...) over a subtype
+    Range(Box<DataType>),
+    /// PostGIS `geometry` / `geography` column. `shape` is the declared
+    /// geometry type (`Point`, `Polygon`, ... or `Geometry` when
+    /// unconstrained) and `srid` is 0 when unspecified.
+    Spatial {
+        geography: bool,
+        shape: String,
+        srid: u32,
+    },
     /// IP address (inet)
     Inet,
     /// MAC address
@@ -162,6 +227,9 @@ pub enum DataType {
     Xml,
     /// Money type
     Money,
+    /// Full-text search document (PostgreSQL `tsvector`). Populated by the
+    /// database (trigger or generated column), never generated.
+    TsVector,
     /// Interval/duration
     Interval,
     /// Serial (auto-incrementing integer, PG)
@@ -239,8 +307,17 @@ impl DataType {
             // Other
             "xml" => DataType::Xml,
             "money" => DataType::Money,
+            "tsvector" => DataType::TsVector,
             "interval" => DataType::Interval,
 
+            // Ranges (PostgreSQL)
+            "int4range" => DataType::Range(Box::new(DataType::Integer)),
+            "int8range" => DataType::Range(Box::new(DataType::BigInt)),
+            "numrange" => DataType::Range(Box::new(DataType::Numeric)),
+            "tsrange" => DataType::Range(Box::new(DataType::Timestamp)),
+            "tstzrange" => DataType::Range(Box::new(DataType::TimestampTz)),
+            "daterange" => DataType::Range(Box::new(DataType::Date)),
+
             // Catch USER-DEFINED (enums) - handled by caller with enum name
             "user-defined" => DataType::Unknown("USER-DEFINED".to_string()),
 
@@ -310,10 +387,30 @@ impl fmt::Display for DataType {
             DataType::Binary => write!(f, "bytea"),
             DataType::Array(inner) => write!(f, "{}[]", inner),
             DataType::Enum(name) => write!(f, "enum({})", name),
+            DataType::Set(name) => write!(f, "set({})", name),
+            DataType::Composite(name) => write!(f, "composite({})", name),
+            DataType::Range(inner) => match inner.as_ref() {
+                DataType::Integer => write!(f, "int4range"),
+                DataType::BigInt => write!(f, "int8range"),
+                DataType::Numeric => write!(f, "numrange"),
+                DataType::Timestamp => write!(f, "tsrange"),
+                DataType::TimestampTz => write!(f, "tstzrange"),
+                DataType::Date => write!(f, "daterange"),
+                other => write!(f, "range({})", other),
+            },
+            DataType::Spatial {
+                geography,
+                shape,
+                srid,
+            } => {
+                let base = if *geography { "geography" } else { "geometry" };
+                write!(f, "{}({},{})", base, shape, srid)
+            }
             DataType::Inet => write!(f, "inet"),
             DataType::MacAddr => write!(f, "macaddr"),
             DataType::Xml => write!(f, "xml"),
             DataType::Money => write!(f, "money"),
+            DataType::TsVector => write!(f, "tsvector"),
             DataType::Interval => write!(f, "interval"),
             DataType::Serial => write!(f, "serial"),
             DataType::BigSerial => write!(f, "bigserial"),
@@ -378,6 +475,33 @@ pub struct UniqueConstraint {
     pub columns: Vec<String>,
 }
 
+/// How a MySQL unique index compares values, when that is less than the
+/// whole value of each column.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UniqueKeySpec {
+    /// The unique constraint's columns, in order.
+    pub columns: Vec<String>,
+    /// Per-column index prefix length (`UNIQUE(email(10))`). Empty, or
+    /// `None` for a column, means the full value is compared.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub prefix_lengths: Vec<Option<u32>>,
+    /// Multi-valued index over a JSON array: every array element must be
+    /// unique across the table, not just the array as a whole.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub multi_valued: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Index {
+    pub name: String,
+    /// Key columns in order; expression keys hold their SQL text.
+    pub columns: Vec<String>,
+    /// Access method (`btree`, `gin`, `FULLTEXT`...), when the database
+    /// reports one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub method: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CheckConstraint {
     pub name: Option<String>,
@@ -399,10 +523,43 @@ pub enum ParsedCheck {
     LessThan { column: String, value: f64 },
     /// column BETWEEN low AND high
     Between { column: String, low: f64, high: f64 },
-    /// length(column) > 0 or char_length(column) > 0
+    /// char_length(column) >= min
     MinLength { column: String, min: usize },
+    /// char_length(column) <= max
+    MaxLength { column: String, max: usize },
     /// column IN (val1, val2, ...)
     InValues { column: String, values: Vec<String> },
-    /// column1 < column2 (e.g., start_date < end_date)
-    ColumnLessThan { left: String, right: String },
+    /// column LIKE pattern (`%` and `_` wildcards, `\` escapes)
+    Like { column: String, pattern: String },
+    /// column >= value (or > when not inclusive) on a date or timestamp
+    After {
+        column: String,
+        value: chrono::NaiveDateTime,
+        inclusive: bool,
+    },
+    /// column <= value (or < when not inclusive) on a date or timestamp
+    Before {
+        column: String,
+        value: chrono::NaiveDateTime,
+        inclusive: bool,
+    },
+    /// column1 < column2 (e.g., start_date < end_date), or <= when inclusive
+    ColumnLessThan {
+        left: String,
+        right: String,
+        #[serde(default)]
+        inclusive: bool,
+    },
+    /// Every check holds (an `AND` of checks)
+    All(Vec<ParsedCheck>),
+}
+
+impl ParsedCheck {
+    /// The individual checks of a conjunction, or just this check.
+    pub fn conjuncts(&self) -> Vec<&ParsedCheck> {
+        match self {
+            ParsedCheck::All(checks) => checks.iter().flat_map(|c| c.conjuncts()).collect(),
+            check => vec![check],
+        }
+    }
 }
@@ -0,0 +1,262 @@
+//! # Proptest Strategies
+//!
+//! Exposes SeedKit's value providers as [`proptest`] strategies (behind the
+//! `proptest` feature), so property tests draw values the same way seeding
+//! does: an `Email` column yields the emails SeedKit would insert, and a
+//! `Price` column honours the same CHECK bounds.
+//!
+//! ```ignore
+//! use proptest::prelude::*;
+//! use seedkit_core::strategy::{column_value, semantic_value};
+//!
+//! proptest! {
+//!     #[test]
+//!     fn accepts_seeded_emails(email in semantic_value(SemanticType::Email)) {
+//!         prop_assert!(validate_email(email.as_str().unwrap()));
+//!     }
+//!
+//!     #[test]
+//!     fn accepts_seeded_prices(
+//!         price in column_value(&schema, "products", "price").unwrap()
+//!     ) {
+//!         prop_assert!(Product::with_price(&price).is_ok());
+//!     }
+//! }
+//! ```
+//!
+//! Each value is generated from a seed drawn from the test runner's RNG, so
+//! failures replay from proptest's regression files. Generated values do
+//! not shrink: a smaller email is no more telling than the one that failed.
+
+use proptest::arbitrary::any;
+use proptest::strategy::{Just, NewTree, Strategy, ValueTree};
+use proptest::test_runner::TestRunner;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::classify::rules::classify_column;
+use crate::classify::semantic::SemanticType;
+use crate::generate::plan::{check_applies_to_column, integer_range_checks};
+use crate::generate::providers::{fit_identity_to_length, generate_value_for_type};
+use crate::generate::value::Value;
+use crate::schema::types::{DataType, DatabaseSchema, ParsedCheck};
+
+/// Row indices are drawn below this bound; providers that embed the index
+/// (sequential usernames, unique suffixes) see realistic magnitudes.
+const MAX_ROW_INDEX: usize = 100_000;
+
+/// A strategy producing values for one semantic type, optionally narrowed to
+/// a column's data type, CHECK constraints, enum values and length limit.
+#[derive(Debug, Clone)]
+pub struct SemanticStrategy {
+    semantic_type: SemanticType,
+    data_type: DataType,
+    check_constraints: Vec<ParsedCheck>,
+    enum_values: Option<Vec<String>>,
+    max_length: Option<usize>,
+    base_time: chrono::NaiveDateTime,
+}
+
+impl SemanticStrategy {
+    pub fn new(semantic_type: SemanticType) -> Self {
+        Self {
+            semantic_type,
+            data_type: DataType::Unknown(String::new()),
+            check_constraints: Vec::new(),
+            enum_values: None,
+            max_length: None,
+            base_time: chrono::Utc::now().naive_utc(),
+        }
+    }
+
+    /// Fall back to `data_type` when the semantic type is `Unknown`.
+    pub fn with_data_type(mut self, data_type: DataType) -> Self {
+        self.data_type = data_type;
+        self
+    }
+
+    /// Bound generated values by these CHECK constraints.
+    pub fn with_checks(mut self, checks: Vec<ParsedCheck>) -> Self {
+        self.check_constraints = checks;
+        self
+    }
+
+    /// Pick from these values instead of calling a provider.
+    pub fn with_enum_values(mut self, values: Vec<String>) -> Self {
+        self.enum_values = Some(values);
+        self
+    }
+
+    /// Keep identity values (names, emails, usernames) within `max_length`.
+    pub fn with_max_length(mut self, max_length: usize) -> Self {
+        self.max_length = Some(max_length);
+        self
+    }
+
+    /// Pin the anchor for temporal values instead of the current time.
+    pub fn with_base_time(mut self, base_time: chrono::NaiveDateTime) -> Self {
+        self.base_time = base_time;
+        self
+    }
+
+    fn draw(&self, rng: &mut impl Rng) -> Value {
+        if let Some(values) = self.enum_values.as_ref().filter(|v| !v.is_empty()) {
+            return Value::String(values[rng.random_range(0..values.len())].clone().into());
+        }
+        let row_index = rng.random_range(0..MAX_ROW_INDEX);
+        let value = generate_value_for_type(
+            self.semantic_type,
+            &self.data_type,
+            rng,
+            row_index,
+            &self.check_constraints,
+            self.base_time,
+        );
+        match self.max_length {
+            Some(max_len) => {
+                fit_identity_to_length(self.semantic_type, value, max_len, row_index, 0)
+            }
+            None => value,
+        }
+    }
+}
+
+impl Strategy for SemanticStrategy {
+    type Tree = Just<Value>;
+    type Value = Value;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        let seed = any::<u64>().new_tree(runner)?.current();
+        Ok(Just(self.draw(&mut StdRng::seed_from_u64(seed))))
+    }
+}
+
+/// Values for `semantic_type` with no column context.
+pub fn semantic_value(semantic_type: SemanticType) -> SemanticStrategy {
+    SemanticStrategy::new(semantic_type)
+}
+
+/// Values for `table.column`: classified by the built-in rules and narrowed
+/// to the column's type, CHECK constraints, enum values and length limit.
+/// `seedkit.toml` overrides and sampled distributions are not applied.
+/// Returns `None` if the column does not exist.
+pub fn column_value(
+    schema: &DatabaseSchema,
+    table: &str,
+    column: &str,
+) -> Option<SemanticStrategy> {
+    let tbl = schema.tables.get(table)?;
+    let col = tbl.columns.get(column)?;
+    let is_pk = tbl
+        .primary_key
+        .as_ref()
+        .is_some_and(|pk| pk.columns.iter().any(|c| c == column));
+    let semantic_type = classify_column(
+        column,
+        &col.data_type,
+        table,
+        col.is_auto_increment,
+        is_pk,
+        col.enum_values.as_deref(),
+    );
+    let checks = tbl
+        .check_constraints
+        .iter()
+        .filter_map(|cc| cc.parsed.as_ref())
+        .flat_map(ParsedCheck::conjuncts)
+        .filter(|p| check_applies_to_column(p, column))
+        .cloned()
+        .chain(integer_range_checks(col, schema.database_type))
+        .collect();
+
+    let mut strategy = SemanticStrategy::new(semantic_type)
+        .with_data_type(col.data_type.clone())
+        .with_checks(checks);
+    if let Some(values) = col
+        .enum_values
+        .clone()
+        .or_else(|| enum_type_values(schema, &col.data_type))
+    {
+        strategy = strategy.with_enum_values(values);
+    }
+    if let Some(max_len) = col.max_length {
+        strategy = strategy.with_max_length(max_len as usize);
+    }
+    Some(strategy)
+}
+
+fn enum_type_values(schema: &DatabaseSchema, data_type: &DataType) -> Option<Vec<String>> {
+    match data_type {
+        DataType::Enum(name) => schema.enums.get(name).cloned(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::types::*;
+    use proptest::prelude::*;
+
+    fn products_schema() -> DatabaseSchema {
+        let mut schema = DatabaseSchema::new(DatabaseType::PostgreSQL, "test".to_string());
+        let mut table = Table::new("products".to_string());
+        let mut price = Column::new(
+            "price".to_string(),
+            DataType::Numeric,
+            "numeric".to_string(),
+        );
+        price.nullable = false;
+        table.columns.insert("price".to_string(), price);
+        let mut email = Column::new(
+            "email".to_string(),
+            DataType::VarChar,
+            "varchar(12)".to_string(),
+        );
+        email.max_length = Some(12);
+        table.columns.insert("email".to_string(), email);
+        table.check_constraints.push(CheckConstraint {
+            name: None,
+            expression: "price >= 10".to_string(),
+            parsed: Some(ParsedCheck::GreaterThanOrEqual {
+                column: "price".to_string(),
+                value: 10.0,
+            }),
+        });
+        table.check_constraints.push(CheckConstraint {
+            name: None,
+            expression: "price <= 20".to_string(),
+            parsed: Some(ParsedCheck::LessThanOrEqual {
+                column: "price".to_string(),
+                value: 20.0,
+            }),
+        });
+        schema.tables.insert("products".to_string(), table);
+        schema
+    }
+
+    proptest! {
+        #[test]
+        fn emails_contain_at_sign(email in semantic_value(SemanticType::Email)) {
+            prop_assert!(email.to_string().contains('@'));
+        }
+
+        #[test]
+        fn column_strategy_respects_checks_and_lengths(
+            price in column_value(&products_schema(), "products", "price").unwrap(),
+            email in column_value(&products_schema(), "products", "email").unwrap(),
+        ) {
+            let Value::Float(price) = price else {
+                return Err(TestCaseError::fail(format!("expected float, got {:?}", price)));
+            };
+            prop_assert!((10.0..=20.0).contains(&price));
+            prop_assert!(email.to_string().chars().count() <= 12);
+        }
+    }
+
+    #[test]
+    fn test_column_value_unknown_column() {
+        assert!(column_value(&products_schema(), "products", "missing").is_none());
+        assert!(column_value(&products_schema(), "orders", "price").is_none());
+    }
+}
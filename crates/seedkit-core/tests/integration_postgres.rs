@@ -628,6 +628,10 @@ async fn test_pg_lock_file_round_trip() {
             exclude_tables: Vec::new(),
             ai_classifications: None,
             column_overrides: None,
+            locale: None,
+            table_streams: true,
+            lookup_tables: None,
+            scenario: None,
         },
         schema.clone(),
     );